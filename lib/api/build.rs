@@ -29,6 +29,14 @@ fn main() -> std::io::Result<()> {
             &["src/grpc/proto"], // specify the root location to search proto dependencies
         )?;
 
+    // Server streaming code of tonic-build 0.10 refers to `tokio_stream`, which is not re-exported
+    // by tonic 0.9. It is the same `Stream` trait as the re-exported `futures_core` one.
+    replace_in_file(
+        "src/grpc/qdrant.rs",
+        "tonic::codegen::tokio_stream::Stream",
+        "tonic::codegen::futures_core::Stream",
+    );
+
     // Append trait extension imports to generated gRPC output
     append_to_file("src/grpc/qdrant.rs", "use super::validate::ValidateExt;");
 
@@ -122,6 +130,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CreateCollection.optimizers_config", ""),
             ("CreateCollection.vectors_config", ""),
             ("CreateCollection.quantization_config", ""),
+            ("CreateCollection.ttl_sec", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("CreateCollection.strict_mode_config", ""),
             ("UpdateCollection.collection_name", "length(min = 1, max = 255)"),
             ("UpdateCollection.optimizers_config", ""),
             ("UpdateCollection.params", ""),
@@ -129,17 +139,25 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollection.hnsw_config", ""),
             ("UpdateCollection.vectors_config", ""),
             ("UpdateCollection.quantization_config", ""),
+            ("UpdateCollection.strict_mode_config", ""),
             ("DeleteCollection.collection_name", "length(min = 1, max = 255)"),
             ("DeleteCollection.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CollectionConfig.params", ""),
             ("CollectionConfig.hnsw_config", ""),
             ("CollectionConfig.optimizers_config", ""),
             ("CollectionConfig.quantization_config", ""),
+            ("CollectionConfig.strict_mode_config", ""),
             ("CollectionParams.vectors_config", ""),
+            ("CollectionParamsDiff.ttl_sec", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("ChangeAliases.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("ListCollectionAliasesRequest.collection_name", "length(min = 1, max = 255)"),
             ("HnswConfigDiff.ef_construct", "custom = \"crate::grpc::validate::validate_u64_range_min_4\""),
             ("WalConfigDiff.wal_capacity_mb", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_query_limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.search_max_batchsize", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.upsert_max_batchsize", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_points_count", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("OptimizersConfigDiff.deleted_threshold", "custom = \"crate::grpc::validate::validate_f64_range_1\""),
             ("OptimizersConfigDiff.vacuum_min_vector_number", "custom = \"crate::grpc::validate::validate_u64_range_min_100\""),
             ("VectorsConfig.config", ""),
@@ -158,7 +176,6 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollectionClusterSetupRequest.operation", ""),
         ], &[
             "ListCollectionsRequest",
-            "CollectionParamsDiff",
             "ListAliasesRequest",
             "CollectionClusterInfoRequest",
             "UpdateCollectionClusterSetupRequest",
@@ -182,6 +199,7 @@ fn configure_validation(builder: Builder) -> Builder {
         .validates(&[
             ("UpsertPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpsertPoints.points", ""),
+            ("UpsertPoints.update_filter", ""),
             ("DeletePoints.collection_name", "length(min = 1, max = 255)"),
             ("UpdatePointVectors.collection_name", "length(min = 1, max = 255)"),
             ("UpdatePointVectors.vectors", "custom(function = \"crate::grpc::validate::validate_named_vectors_not_empty\", message = \"must specify vectors to update\")"),
@@ -245,6 +263,17 @@ fn configure_validation(builder: Builder) -> Builder {
             ("DiscoverBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CountPoints.collection_name", "length(min = 1, max = 255)"),
             ("CountPoints.filter", ""),
+            ("FacetCounts.collection_name", "length(min = 1, max = 255)"),
+            ("FacetCounts.key", "length(min = 1)"),
+            ("FacetCounts.filter", ""),
+            ("FacetCounts.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("FacetCounts.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchMatrixPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SearchMatrixPoints.filter", ""),
+            ("SearchMatrixPoints.sample", "custom = \"crate::grpc::validate::validate_u64_range_min_2_max_10000\""),
+            ("SearchMatrixPoints.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchMatrixPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StreamUpdatesRequest.collection_name", "length(min = 1, max = 255)"),
             ("GeoPolygon.exterior", "custom = \"crate::grpc::validate::validate_geo_polygon_exterior\""),
             ("GeoPolygon.interiors", "custom = \"crate::grpc::validate::validate_geo_polygon_interiors\""),
             ("Filter.should", ""),
@@ -255,6 +284,10 @@ fn configure_validation(builder: Builder) -> Builder {
             ("PointStruct.vectors", ""),
             ("Vectors.vectors_options", ""),
             ("NamedVectors.vectors", ""),
+            ("Document.bm25", ""),
+            ("Bm25Config.k1", "custom = \"crate::grpc::validate::validate_f32_range_min_0\""),
+            ("Bm25Config.b", "custom = \"crate::grpc::validate::validate_f32_range_min_0_max_1\""),
+            ("Bm25Config.avg_len", "custom = \"crate::grpc::validate::validate_f32_range_min_1\""),
             ("DatetimeRange.lt", "custom = \"crate::grpc::validate::validate_timestamp\""),
             ("DatetimeRange.gt", "custom = \"crate::grpc::validate::validate_timestamp\""),
             ("DatetimeRange.lte", "custom = \"crate::grpc::validate::validate_timestamp\""),
@@ -292,6 +325,10 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ScrollPointsInternal.scroll_points", ""),
             ("GetPointsInternal.get_points", ""),
             ("CountPointsInternal.count_points", ""),
+            ("FacetCountsInternal.collection_name", "length(min = 1, max = 255)"),
+            ("FacetCountsInternal.key", "length(min = 1)"),
+            ("FacetCountsInternal.filter", ""),
+            ("FacetCountsInternal.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("SyncPointsInternal.sync_points", ""),
             ("SyncPoints.collection_name", "length(min = 1, max = 255)"),
         ], &[])
@@ -330,3 +367,15 @@ fn append_to_file(path: &str, line: &str) {
     )
     .unwrap()
 }
+
+/// Patch generated code, failing if there is nothing to patch
+///
+/// If the pattern is not found, the code generator has changed and the patch must be revisited.
+fn replace_in_file(path: &str, from: &str, to: &str) {
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(
+        content.contains(from),
+        "`{from}` is not found in {path}, patch of the generated code is outdated",
+    );
+    std::fs::write(path, content.replace(from, to)).unwrap();
+}
@@ -5,8 +5,9 @@ use std::time::Instant;
 use chrono::{NaiveDateTime, Timelike};
 use itertools::Itertools;
 use segment::data_types::integer_index::IntegerIndexType;
-use segment::data_types::text_index::TextIndexType;
+use segment::data_types::text_index::{Language, SnowballType, TextIndexType};
 use segment::data_types::vectors as segment_vectors;
+use segment::index::rescore_formula::parsed_formula::{DecayKind, ParsedExpression, ParsedFormula};
 use segment::json_path::JsonPath;
 use segment::types::{default_quantization_ignore_value, DateTimePayloadType, FloatPayloadType};
 use segment::vector_storage::query as segment_query;
@@ -15,30 +16,36 @@ use uuid::Uuid;
 
 use super::qdrant::raw_query::RawContextPair;
 use super::qdrant::{
-    raw_query, start_from, BinaryQuantization, CompressionRatio, DatetimeRange, Direction,
-    GeoLineString, GroupId, MultiVectorComparator, MultiVectorConfig, OrderBy, OrderValue, Range,
-    RawVector, RecommendStrategy, ShardKeySelector, SparseIndices, StartFrom,
+    raw_query, start_from, BinaryQuantization, BinaryQuantizationEncoding,
+    BinaryQuantizationQueryEncoding, Bm25Config, CompressionRatio, DatetimeRange,
+    DecayParamsExpression, Direction, DivExpression, Document, Expression, FacetHit, FacetValue,
+    Formula, GeoDistance, GeoLineString, GroupId, MultExpression, MultiVectorComparator,
+    MultiVectorConfig, OrderBy, OrderValue, PowExpression, Range, RawVector, RecommendStrategy,
+    ShardKeySelector, SnowballParams, SparseIndices, StartFrom, StemmingAlgorithm, StopwordsSet,
+    SumExpression,
 };
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
 use crate::grpc::qdrant::payload_index_params::IndexParams;
 use crate::grpc::qdrant::point_id::PointIdOptions;
 use crate::grpc::qdrant::r#match::MatchValue;
+use crate::grpc::qdrant::stemming_algorithm::StemmingParams;
 use crate::grpc::qdrant::value::Kind;
 use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
-    shard_key, with_vectors_selector, CollectionDescription, CollectionOperationResponse,
-    Condition, DenseVector, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon,
+    shard_key, with_vectors_selector, ArrayQuantifier, CollectionDescription,
+    CollectionOperationResponse, Condition, DatetimeIndexParams, DenseVector, Distance,
+    FieldCondition, Filter, FloatIndexParams, GeoBoundingBox, GeoIndexParams, GeoPoint, GeoPolygon,
     GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, IntegerIndexParams,
-    IsEmptyCondition, IsNullCondition, ListCollectionsResponse, ListValue, Match, MinShould,
-    MultiDenseVector, NamedVectors, NestedCondition, PayloadExcludeSelector,
+    IsEmptyCondition, IsNullCondition, KeywordIndexParams, ListCollectionsResponse, ListValue,
+    Match, MinShould, MultiDenseVector, NamedVectors, NestedCondition, PayloadExcludeSelector,
     PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId,
     PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
     QuantizationConfig, QuantizationSearchParams, QuantizationType, RepeatedIntegers,
     RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, ShardKey, SparseVector, Struct,
-    TextIndexParams, TokenizerType, UpdateResult, UpdateResultInternal, Value, ValuesCount, Vector,
-    Vectors, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+    TextIndexParams, TextQuery, TokenizerType, UpdateResult, UpdateResultInternal, UuidIndexParams,
+    Value, ValuesCount, Vector, Vectors, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
 };
 use crate::rest::schema as rest;
 
@@ -227,22 +234,128 @@ impl From<segment::data_types::text_index::TextIndexParams> for PayloadIndexPara
                 lowercase: params.lowercase,
                 min_token_len: params.min_token_len.map(|x| x as u64),
                 max_token_len: params.max_token_len.map(|x| x as u64),
+                on_disk: params.on_disk,
+                stopwords: params.stopwords.map(StopwordsSet::from),
+                stemmer: params.stemmer.map(StemmingAlgorithm::from),
+                ascii_folding: params.ascii_folding,
+                phrase_matching: params.phrase_matching,
             })),
         }
     }
 }
 
+impl From<segment::data_types::text_index::StopwordsInterface> for StopwordsSet {
+    fn from(stopwords: segment::data_types::text_index::StopwordsInterface) -> Self {
+        match stopwords {
+            segment::data_types::text_index::StopwordsInterface::Language(language) => {
+                StopwordsSet {
+                    languages: vec![language.to_string()],
+                    custom: vec![],
+                }
+            }
+            segment::data_types::text_index::StopwordsInterface::Set(set) => StopwordsSet {
+                languages: set.languages.iter().map(Language::to_string).collect(),
+                custom: set.custom,
+            },
+        }
+    }
+}
+
+impl From<segment::data_types::text_index::StemmingAlgorithm> for StemmingAlgorithm {
+    fn from(stemmer: segment::data_types::text_index::StemmingAlgorithm) -> Self {
+        let stemming_params = match stemmer {
+            segment::data_types::text_index::StemmingAlgorithm::Snowball(params) => {
+                StemmingParams::Snowball(SnowballParams {
+                    language: params.language.to_string(),
+                })
+            }
+        };
+        StemmingAlgorithm {
+            stemming_params: Some(stemming_params),
+        }
+    }
+}
+
 impl From<segment::data_types::integer_index::IntegerIndexParams> for PayloadIndexParams {
     fn from(params: segment::data_types::integer_index::IntegerIndexParams) -> Self {
         PayloadIndexParams {
             index_params: Some(IndexParams::IntegerIndexParams(IntegerIndexParams {
                 lookup: params.lookup,
                 range: params.range,
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::index::KeywordIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::index::KeywordIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::KeywordIndexParams(KeywordIndexParams {
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::index::FloatIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::index::FloatIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::FloatIndexParams(FloatIndexParams {
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::index::GeoIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::index::GeoIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::GeoIndexParams(GeoIndexParams {
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::index::DatetimeIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::index::DatetimeIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::DatetimeIndexParams(DatetimeIndexParams {
+                on_disk: params.on_disk,
             })),
         }
     }
 }
 
+impl From<segment::data_types::index::UuidIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::index::UuidIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::UuidIndexParams(UuidIndexParams {
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::types::PayloadSchemaParams> for PayloadIndexParams {
+    fn from(params: segment::types::PayloadSchemaParams) -> Self {
+        match params {
+            segment::types::PayloadSchemaParams::Keyword(keyword_params) => keyword_params.into(),
+            segment::types::PayloadSchemaParams::Integer(integer_params) => integer_params.into(),
+            segment::types::PayloadSchemaParams::Float(float_params) => float_params.into(),
+            segment::types::PayloadSchemaParams::Geo(geo_params) => geo_params.into(),
+            segment::types::PayloadSchemaParams::Text(text_index_params) => {
+                text_index_params.into()
+            }
+            segment::types::PayloadSchemaParams::Datetime(datetime_params) => {
+                datetime_params.into()
+            }
+            segment::types::PayloadSchemaParams::Uuid(uuid_params) => uuid_params.into(),
+        }
+    }
+}
+
 impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
     fn from(schema: segment::types::PayloadIndexInfo) -> Self {
         PayloadSchemaInfo {
@@ -254,16 +367,10 @@ impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
                 segment::types::PayloadSchemaType::Text => PayloadSchemaType::Text,
                 segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
                 segment::types::PayloadSchemaType::Datetime => PayloadSchemaType::Datetime,
+                segment::types::PayloadSchemaType::Uuid => PayloadSchemaType::Uuid,
             }
             .into(),
-            params: schema.params.map(|params| match params {
-                segment::types::PayloadSchemaParams::Text(text_index_params) => {
-                    text_index_params.into()
-                }
-                segment::types::PayloadSchemaParams::Integer(integer_params) => {
-                    integer_params.into()
-                }
-            }),
+            params: schema.params.map(PayloadIndexParams::from),
             points: Some(schema.points as u64),
         }
     }
@@ -297,10 +404,53 @@ impl TryFrom<TextIndexParams> for segment::data_types::text_index::TextIndexPara
             lowercase: params.lowercase,
             min_token_len: params.min_token_len.map(|x| x as usize),
             max_token_len: params.max_token_len.map(|x| x as usize),
+            on_disk: params.on_disk,
+            stopwords: params.stopwords.map(TryFrom::try_from).transpose()?,
+            stemmer: params.stemmer.map(TryFrom::try_from).transpose()?,
+            ascii_folding: params.ascii_folding,
+            phrase_matching: params.phrase_matching,
         })
     }
 }
 
+fn parse_language(language: &str) -> Result<Language, Status> {
+    Language::from_str(language)
+        .map_err(|_| Status::invalid_argument(format!("unknown language: {language}")))
+}
+
+impl TryFrom<StopwordsSet> for segment::data_types::text_index::StopwordsInterface {
+    type Error = Status;
+    fn try_from(stopwords: StopwordsSet) -> Result<Self, Self::Error> {
+        let StopwordsSet { languages, custom } = stopwords;
+        let languages: Vec<_> = languages
+            .iter()
+            .map(|language| parse_language(language))
+            .collect::<Result<_, _>>()?;
+        match (languages.as_slice(), custom.is_empty()) {
+            (&[language], true) => Ok(Self::Language(language)),
+            _ => Ok(Self::Set(segment::data_types::text_index::StopwordsSet {
+                languages,
+                custom,
+            })),
+        }
+    }
+}
+
+impl TryFrom<StemmingAlgorithm> for segment::data_types::text_index::StemmingAlgorithm {
+    type Error = Status;
+    fn try_from(stemmer: StemmingAlgorithm) -> Result<Self, Self::Error> {
+        match stemmer.stemming_params {
+            Some(StemmingParams::Snowball(params)) => Ok(Self::Snowball(
+                segment::data_types::text_index::SnowballParams {
+                    r#type: SnowballType::Snowball,
+                    language: parse_language(&params.language)?,
+                },
+            )),
+            None => Err(Status::invalid_argument("unknown stemming algorithm")),
+        }
+    }
+}
+
 impl TryFrom<IntegerIndexParams> for segment::data_types::integer_index::IntegerIndexParams {
     type Error = Status;
     fn try_from(params: IntegerIndexParams) -> Result<Self, Self::Error> {
@@ -308,21 +458,82 @@ impl TryFrom<IntegerIndexParams> for segment::data_types::integer_index::Integer
             r#type: IntegerIndexType::Integer,
             lookup: params.lookup,
             range: params.range,
+            on_disk: params.on_disk,
         })
     }
 }
 
+impl From<KeywordIndexParams> for segment::data_types::index::KeywordIndexParams {
+    fn from(params: KeywordIndexParams) -> Self {
+        segment::data_types::index::KeywordIndexParams {
+            r#type: segment::data_types::index::KeywordIndexType::Keyword,
+            on_disk: params.on_disk,
+        }
+    }
+}
+
+impl From<FloatIndexParams> for segment::data_types::index::FloatIndexParams {
+    fn from(params: FloatIndexParams) -> Self {
+        segment::data_types::index::FloatIndexParams {
+            r#type: segment::data_types::index::FloatIndexType::Float,
+            on_disk: params.on_disk,
+        }
+    }
+}
+
+impl From<GeoIndexParams> for segment::data_types::index::GeoIndexParams {
+    fn from(params: GeoIndexParams) -> Self {
+        segment::data_types::index::GeoIndexParams {
+            r#type: segment::data_types::index::GeoIndexType::Geo,
+            on_disk: params.on_disk,
+        }
+    }
+}
+
+impl From<DatetimeIndexParams> for segment::data_types::index::DatetimeIndexParams {
+    fn from(params: DatetimeIndexParams) -> Self {
+        segment::data_types::index::DatetimeIndexParams {
+            r#type: segment::data_types::index::DatetimeIndexType::Datetime,
+            on_disk: params.on_disk,
+        }
+    }
+}
+
+impl From<UuidIndexParams> for segment::data_types::index::UuidIndexParams {
+    fn from(params: UuidIndexParams) -> Self {
+        segment::data_types::index::UuidIndexParams {
+            r#type: segment::data_types::index::UuidIndexType::Uuid,
+            on_disk: params.on_disk,
+        }
+    }
+}
+
 impl TryFrom<IndexParams> for segment::types::PayloadSchemaParams {
     type Error = Status;
 
     fn try_from(value: IndexParams) -> Result<Self, Self::Error> {
         match value {
-            IndexParams::TextIndexParams(text_index_params) => Ok(
-                segment::types::PayloadSchemaParams::Text(text_index_params.try_into()?),
+            IndexParams::KeywordIndexParams(keyword_params) => Ok(
+                segment::types::PayloadSchemaParams::Keyword(keyword_params.into()),
             ),
             IndexParams::IntegerIndexParams(integer_params) => Ok(
                 segment::types::PayloadSchemaParams::Integer(integer_params.try_into()?),
             ),
+            IndexParams::FloatIndexParams(float_params) => Ok(
+                segment::types::PayloadSchemaParams::Float(float_params.into()),
+            ),
+            IndexParams::GeoIndexParams(geo_params) => {
+                Ok(segment::types::PayloadSchemaParams::Geo(geo_params.into()))
+            }
+            IndexParams::TextIndexParams(text_index_params) => Ok(
+                segment::types::PayloadSchemaParams::Text(text_index_params.try_into()?),
+            ),
+            IndexParams::DatetimeIndexParams(datetime_params) => Ok(
+                segment::types::PayloadSchemaParams::Datetime(datetime_params.into()),
+            ),
+            IndexParams::UuidIndexParams(uuid_params) => Ok(
+                segment::types::PayloadSchemaParams::Uuid(uuid_params.into()),
+            ),
         }
     }
 }
@@ -345,6 +556,7 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
                 PayloadSchemaType::Text => segment::types::PayloadSchemaType::Text,
                 PayloadSchemaType::Bool => segment::types::PayloadSchemaType::Bool,
                 PayloadSchemaType::Datetime => segment::types::PayloadSchemaType::Datetime,
+                PayloadSchemaType::Uuid => segment::types::PayloadSchemaType::Uuid,
                 PayloadSchemaType::UnknownType => {
                     return Err(Status::invalid_argument(
                         "Malformed payload schema".to_string(),
@@ -502,6 +714,7 @@ impl From<segment_vectors::Vector> for Vector {
                 data: vector,
                 indices: None,
                 vectors_count: None,
+                document: None,
             },
             segment_vectors::Vector::Sparse(vector) => Self {
                 data: vector.values,
@@ -509,6 +722,7 @@ impl From<segment_vectors::Vector> for Vector {
                     data: vector.indices,
                 }),
                 vectors_count: None,
+                document: None,
             },
             segment_vectors::Vector::MultiDense(vector) => {
                 let vector_count = vector.multi_vectors().count() as u32;
@@ -516,6 +730,7 @@ impl From<segment_vectors::Vector> for Vector {
                     data: vector.flattened_vectors,
                     indices: None,
                     vectors_count: Some(vector_count),
+                    document: None,
                 }
             }
         }
@@ -526,6 +741,14 @@ impl TryFrom<Vector> for segment_vectors::Vector {
     type Error = Status;
 
     fn try_from(vector: Vector) -> Result<Self, Self::Error> {
+        // text, converted into a sparse vector
+        if let Some(document) = vector.document {
+            let document = segment::data_types::bm25::Document::try_from(document)?;
+            return Ok(segment_vectors::Vector::Sparse(
+                document.to_sparse_document(),
+            ));
+        }
+
         // sparse vector
         if let Some(indices) = vector.indices {
             return Ok(segment_vectors::Vector::Sparse(
@@ -555,6 +778,51 @@ impl TryFrom<Vector> for segment_vectors::Vector {
     }
 }
 
+impl TryFrom<Bm25Config> for segment::data_types::bm25::Bm25Config {
+    type Error = Status;
+
+    fn try_from(config: Bm25Config) -> Result<Self, Self::Error> {
+        let Bm25Config {
+            tokenizer,
+            min_token_len,
+            max_token_len,
+            lowercase,
+            k1,
+            b,
+            avg_len,
+        } = config;
+
+        let tokenizer = match tokenizer {
+            Some(tokenizer) => TokenizerType::from_i32(tokenizer)
+                .map(|x| x.try_into())
+                .unwrap_or_else(|| Err(Status::invalid_argument("unknown tokenizer type")))?,
+            None => Default::default(),
+        };
+
+        Ok(Self {
+            tokenizer,
+            min_token_len: min_token_len.map(|x| x as usize),
+            max_token_len: max_token_len.map(|x| x as usize),
+            lowercase,
+            k1,
+            b,
+            avg_len,
+        })
+    }
+}
+
+impl TryFrom<Document> for segment::data_types::bm25::Document {
+    type Error = Status;
+
+    fn try_from(document: Document) -> Result<Self, Self::Error> {
+        let Document { text, bm25 } = document;
+        Ok(Self {
+            text,
+            bm25: bm25.map(TryFrom::try_from).transpose()?,
+        })
+    }
+}
+
 impl From<HashMap<String, segment_vectors::Vector>> for NamedVectors {
     fn from(vectors: HashMap<String, segment_vectors::Vector>) -> Self {
         Self {
@@ -626,6 +894,70 @@ impl TryFrom<OrderValue> for segment::data_types::order_by::OrderValue {
     }
 }
 
+impl From<segment::data_types::facets::FacetValue> for FacetValue {
+    fn from(value: segment::data_types::facets::FacetValue) -> Self {
+        use segment::data_types::facets as segment;
+
+        use crate::grpc::qdrant::facet_value::Variant;
+
+        let variant = match value {
+            segment::FacetValue::Keyword(value) => Variant::StringValue(value),
+            segment::FacetValue::Int(value) => Variant::IntegerValue(value),
+            segment::FacetValue::Bool(value) => Variant::BoolValue(value),
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<FacetValue> for segment::data_types::facets::FacetValue {
+    type Error = Status;
+
+    fn try_from(value: FacetValue) -> Result<Self, Self::Error> {
+        use segment::data_types::facets as segment;
+
+        use crate::grpc::qdrant::facet_value::Variant;
+
+        let variant = value
+            .variant
+            .ok_or_else(|| Status::invalid_argument("FacetValue should have a variant"))?;
+
+        let value = match variant {
+            Variant::StringValue(value) => segment::FacetValue::Keyword(value),
+            Variant::IntegerValue(value) => segment::FacetValue::Int(value),
+            Variant::BoolValue(value) => segment::FacetValue::Bool(value),
+        };
+
+        Ok(value)
+    }
+}
+
+impl From<segment::data_types::facets::FacetValueHit> for FacetHit {
+    fn from(hit: segment::data_types::facets::FacetValueHit) -> Self {
+        Self {
+            value: Some(hit.value.into()),
+            count: hit.count as u64,
+        }
+    }
+}
+
+impl TryFrom<FacetHit> for segment::data_types::facets::FacetValueHit {
+    type Error = Status;
+
+    fn try_from(hit: FacetHit) -> Result<Self, Self::Error> {
+        let value = hit
+            .value
+            .ok_or_else(|| Status::invalid_argument("FacetHit should have a value"))?;
+
+        Ok(Self {
+            value: value.try_into()?,
+            count: hit.count as usize,
+        })
+    }
+}
+
 impl From<segment::types::ScoredPoint> for ScoredPoint {
     fn from(point: segment::types::ScoredPoint) -> Self {
         Self {
@@ -750,6 +1082,12 @@ impl From<segment::types::ScalarQuantization> for ScalarQuantization {
                 segment::types::ScalarType::Int8 => {
                     crate::grpc::qdrant::QuantizationType::Int8 as i32
                 }
+                segment::types::ScalarType::Int4 => {
+                    crate::grpc::qdrant::QuantizationType::Int4 as i32
+                }
+                segment::types::ScalarType::Float8 => {
+                    crate::grpc::qdrant::QuantizationType::Float8 as i32
+                }
             },
             quantile: config.quantile,
             always_ram: config.always_ram,
@@ -765,6 +1103,8 @@ impl TryFrom<ScalarQuantization> for segment::types::ScalarQuantization {
             scalar: segment::types::ScalarQuantizationConfig {
                 r#type: match QuantizationType::from_i32(value.r#type) {
                     Some(QuantizationType::Int8) => segment::types::ScalarType::Int8,
+                    Some(QuantizationType::Int4) => segment::types::ScalarType::Int4,
+                    Some(QuantizationType::Float8) => segment::types::ScalarType::Float8,
                     Some(QuantizationType::UnknownQuantization) | None => {
                         return Err(Status::invalid_argument("Unknown quantization type"));
                     }
@@ -821,6 +1161,12 @@ impl From<segment::types::BinaryQuantization> for BinaryQuantization {
         let config = value.binary;
         BinaryQuantization {
             always_ram: config.always_ram,
+            encoding: config
+                .encoding
+                .map(|encoding| BinaryQuantizationEncoding::from(encoding) as i32),
+            query_encoding: config
+                .query_encoding
+                .map(|query_encoding| BinaryQuantizationQueryEncoding::from(query_encoding) as i32),
         }
     }
 }
@@ -832,11 +1178,73 @@ impl TryFrom<BinaryQuantization> for segment::types::BinaryQuantization {
         Ok(segment::types::BinaryQuantization {
             binary: segment::types::BinaryQuantizationConfig {
                 always_ram: value.always_ram,
+                encoding: value
+                    .encoding
+                    .map(
+                        |encoding| match BinaryQuantizationEncoding::from_i32(encoding) {
+                            Some(encoding) => Ok(encoding.into()),
+                            None => Err(Status::invalid_argument(
+                                "Unknown binary quantization encoding",
+                            )),
+                        },
+                    )
+                    .transpose()?,
+                query_encoding: value
+                    .query_encoding
+                    .map(|query_encoding| {
+                        match BinaryQuantizationQueryEncoding::from_i32(query_encoding) {
+                            Some(query_encoding) => Ok(query_encoding.into()),
+                            None => Err(Status::invalid_argument(
+                                "Unknown binary quantization query encoding",
+                            )),
+                        }
+                    })
+                    .transpose()?,
             },
         })
     }
 }
 
+impl From<segment::types::BinaryQuantizationEncoding> for BinaryQuantizationEncoding {
+    fn from(value: segment::types::BinaryQuantizationEncoding) -> Self {
+        match value {
+            segment::types::BinaryQuantizationEncoding::OneBit => Self::OneBit,
+            segment::types::BinaryQuantizationEncoding::TwoBits => Self::TwoBits,
+            segment::types::BinaryQuantizationEncoding::OneAndHalfBits => Self::OneAndHalfBits,
+        }
+    }
+}
+
+impl From<BinaryQuantizationEncoding> for segment::types::BinaryQuantizationEncoding {
+    fn from(value: BinaryQuantizationEncoding) -> Self {
+        match value {
+            BinaryQuantizationEncoding::OneBit => Self::OneBit,
+            BinaryQuantizationEncoding::TwoBits => Self::TwoBits,
+            BinaryQuantizationEncoding::OneAndHalfBits => Self::OneAndHalfBits,
+        }
+    }
+}
+
+impl From<segment::types::BinaryQuantizationQueryEncoding> for BinaryQuantizationQueryEncoding {
+    fn from(value: segment::types::BinaryQuantizationQueryEncoding) -> Self {
+        match value {
+            segment::types::BinaryQuantizationQueryEncoding::Binary => Self::Binary,
+            segment::types::BinaryQuantizationQueryEncoding::Scalar4Bits => Self::Scalar4Bits,
+            segment::types::BinaryQuantizationQueryEncoding::Scalar8Bits => Self::Scalar8Bits,
+        }
+    }
+}
+
+impl From<BinaryQuantizationQueryEncoding> for segment::types::BinaryQuantizationQueryEncoding {
+    fn from(value: BinaryQuantizationQueryEncoding) -> Self {
+        match value {
+            BinaryQuantizationQueryEncoding::Binary => Self::Binary,
+            BinaryQuantizationQueryEncoding::Scalar4Bits => Self::Scalar4Bits,
+            BinaryQuantizationQueryEncoding::Scalar8Bits => Self::Scalar8Bits,
+        }
+    }
+}
+
 impl From<segment::types::QuantizationConfig> for QuantizationConfig {
     fn from(value: segment::types::QuantizationConfig) -> Self {
         match value {
@@ -1054,6 +1462,14 @@ impl TryFrom<NestedCondition> for segment::types::Nested {
             Some(filter) => Ok(Self {
                 key: json_path_from_proto(&value.key)?,
                 filter: filter.try_into()?,
+                quantifier: value
+                    .quantifier
+                    .map(|quantifier| {
+                        ArrayQuantifier::from_i32(quantifier)
+                            .map(Into::into)
+                            .ok_or_else(|| Status::invalid_argument("Unknown array quantifier"))
+                    })
+                    .transpose()?,
             }),
         }
     }
@@ -1064,6 +1480,27 @@ impl From<segment::types::Nested> for NestedCondition {
         Self {
             key: value.key.to_string(),
             filter: Some(value.filter.into()),
+            quantifier: value
+                .quantifier
+                .map(|quantifier| ArrayQuantifier::from(quantifier) as i32),
+        }
+    }
+}
+
+impl From<ArrayQuantifier> for segment::types::ArrayQuantifier {
+    fn from(value: ArrayQuantifier) -> Self {
+        match value {
+            ArrayQuantifier::AnyElement => segment::types::ArrayQuantifier::Any,
+            ArrayQuantifier::AllElements => segment::types::ArrayQuantifier::All,
+        }
+    }
+}
+
+impl From<segment::types::ArrayQuantifier> for ArrayQuantifier {
+    fn from(value: segment::types::ArrayQuantifier) -> Self {
+        match value {
+            segment::types::ArrayQuantifier::Any => ArrayQuantifier::AnyElement,
+            segment::types::ArrayQuantifier::All => ArrayQuantifier::AllElements,
         }
     }
 }
@@ -1117,13 +1554,13 @@ impl TryFrom<HasIdCondition> for segment::types::HasIdCondition {
             .into_iter()
             .map(|p| p.try_into())
             .collect::<Result<_, _>>()?;
-        Ok(Self { has_id: set })
+        Ok(Self::from(set))
     }
 }
 
 impl From<segment::types::HasIdCondition> for HasIdCondition {
     fn from(value: segment::types::HasIdCondition) -> Self {
-        let set: Vec<PointId> = value.has_id.into_iter().map(|p| p.into()).collect();
+        let set: Vec<PointId> = value.has_id.iter().map(|&p| p.into()).collect();
         Self { has_id: set }
     }
 }
@@ -1388,6 +1825,7 @@ impl TryFrom<Match> for segment::types::Match {
                 MatchValue::Integer(int) => int.into(),
                 MatchValue::Boolean(flag) => flag.into(),
                 MatchValue::Text(text) => segment::types::Match::Text(text.into()),
+                MatchValue::Phrase(phrase) => segment::types::Match::Phrase(phrase.into()),
                 MatchValue::Keywords(kwds) => kwds.strings.into(),
                 MatchValue::Integers(ints) => ints.integers.into(),
                 MatchValue::ExceptIntegers(kwds) => {
@@ -1396,6 +1834,8 @@ impl TryFrom<Match> for segment::types::Match {
                 MatchValue::ExceptKeywords(ints) => {
                     segment::types::Match::Except(ints.strings.into())
                 }
+                MatchValue::AllKeywords(kwds) => segment::types::Match::All(kwds.strings.into()),
+                MatchValue::AllIntegers(ints) => segment::types::Match::All(ints.integers.into()),
             }),
             _ => Err(Status::invalid_argument("Malformed Match condition")),
         }
@@ -1413,6 +1853,9 @@ impl From<segment::types::Match> for Match {
             segment::types::Match::Text(segment::types::MatchText { text }) => {
                 MatchValue::Text(text)
             }
+            segment::types::Match::Phrase(segment::types::MatchPhrase { phrase }) => {
+                MatchValue::Phrase(phrase)
+            }
             segment::types::Match::Any(any) => match any.any {
                 segment::types::AnyVariants::Keywords(strings) => {
                     let strings = strings.into_iter().collect();
@@ -1433,6 +1876,16 @@ impl From<segment::types::Match> for Match {
                     MatchValue::ExceptIntegers(RepeatedIntegers { integers })
                 }
             },
+            segment::types::Match::All(all) => match all.all {
+                segment::types::AnyVariants::Keywords(strings) => {
+                    let strings = strings.into_iter().collect();
+                    MatchValue::AllKeywords(RepeatedStrings { strings })
+                }
+                segment::types::AnyVariants::Integers(integers) => {
+                    let integers = integers.into_iter().collect();
+                    MatchValue::AllIntegers(RepeatedIntegers { integers })
+                }
+            },
         };
         Self {
             match_value: Some(match_value),
@@ -1493,16 +1946,40 @@ impl TryFrom<OrderBy> for segment::data_types::order_by::OrderBy {
             key: json_path_from_proto(&value.key)?,
             direction,
             start_from,
+            geo_point: value.geo_point.map(Into::into),
         })
     }
 }
 
+impl TryFrom<TextQuery> for segment::data_types::text_query::TextQuery {
+    type Error = Status;
+
+    fn try_from(value: TextQuery) -> Result<Self, Self::Error> {
+        let TextQuery { key, text } = value;
+        Ok(Self {
+            key: json_path_from_proto(&key)?,
+            text,
+        })
+    }
+}
+
+impl From<segment::data_types::text_query::TextQuery> for TextQuery {
+    fn from(value: segment::data_types::text_query::TextQuery) -> Self {
+        let segment::data_types::text_query::TextQuery { key, text } = value;
+        Self {
+            key: key.to_string(),
+            text,
+        }
+    }
+}
+
 impl From<segment::data_types::order_by::OrderBy> for OrderBy {
     fn from(value: segment::data_types::order_by::OrderBy) -> Self {
         Self {
             key: value.key.to_string(),
             direction: value.direction.map(|d| Direction::from(d) as i32),
             start_from: value.start_from.map(|start_from| start_from.into()),
+            geo_point: value.geo_point.map(Into::into),
         }
     }
 }
@@ -1534,6 +2011,7 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
             max_indexing_threads: hnsw_config.max_indexing_threads.unwrap_or_default() as usize,
             on_disk: hnsw_config.on_disk,
             payload_m: hnsw_config.payload_m.map(|x| x as usize),
+            appendable: hnsw_config.appendable,
         }
     }
 }
@@ -1571,6 +2049,8 @@ impl TryFrom<Distance> for segment::types::Distance {
             Distance::Euclid => segment::types::Distance::Euclid,
             Distance::Dot => segment::types::Distance::Dot,
             Distance::Manhattan => segment::types::Distance::Manhattan,
+            Distance::Hamming => segment::types::Distance::Hamming,
+            Distance::Jaccard => segment::types::Distance::Jaccard,
         })
     }
 }
@@ -1636,6 +2116,8 @@ impl From<UpdateResultInternal> for UpdateResult {
         Self {
             operation_id: res.operation_id,
             status: res.status,
+            updated_count: res.updated_count,
+            skipped_count: res.skipped_count,
         }
     }
 }
@@ -1647,6 +2129,8 @@ impl From<UpdateResult> for UpdateResultInternal {
             operation_id: res.operation_id,
             status: res.status,
             clock_tag: None,
+            updated_count: res.updated_count,
+            skipped_count: res.skipped_count,
         }
     }
 }
@@ -1884,3 +2368,101 @@ impl TryFrom<raw_query::Discovery> for segment_query::DiscoveryQuery<segment_vec
         })
     }
 }
+
+impl From<ParsedFormula> for Formula {
+    fn from(value: ParsedFormula) -> Self {
+        let ParsedFormula {
+            conditions,
+            defaults,
+            formula,
+        } = value;
+
+        let defaults = defaults
+            .into_iter()
+            .map(|(variable, value)| (variable.to_string(), value))
+            .collect::<serde_json::Map<_, _>>();
+
+        Self {
+            expression: Some(expression_to_grpc(formula, &conditions)),
+            defaults: payload_to_proto(defaults.into()),
+        }
+    }
+}
+
+fn expression_to_grpc(
+    expression: ParsedExpression,
+    conditions: &[segment::types::Condition],
+) -> Expression {
+    use super::qdrant::expression::Variant;
+
+    let boxed =
+        |expression: Box<ParsedExpression>| Box::new(expression_to_grpc(*expression, conditions));
+
+    let variant = match expression {
+        ParsedExpression::Constant(constant) => Variant::Constant(constant as f32),
+        ParsedExpression::Variable(variable) => Variant::Variable(variable.to_string()),
+        ParsedExpression::Condition(index) => {
+            Variant::Condition(Condition::from(conditions[index].clone()))
+        }
+        ParsedExpression::GeoDistance { origin, key } => Variant::GeoDistance(GeoDistance {
+            origin: Some(GeoPoint::from(origin)),
+            to: key.to_string(),
+        }),
+        ParsedExpression::Datetime(datetime) => Variant::Datetime(datetime.0.to_rfc3339()),
+        ParsedExpression::DatetimeKey(key) => Variant::DatetimeKey(key.to_string()),
+        ParsedExpression::Mult(expressions) => Variant::Mult(MultExpression {
+            mult: expressions
+                .into_iter()
+                .map(|expression| expression_to_grpc(expression, conditions))
+                .collect(),
+        }),
+        ParsedExpression::Sum(expressions) => Variant::Sum(SumExpression {
+            sum: expressions
+                .into_iter()
+                .map(|expression| expression_to_grpc(expression, conditions))
+                .collect(),
+        }),
+        ParsedExpression::Neg(expression) => Variant::Neg(boxed(expression)),
+        ParsedExpression::Div {
+            left,
+            right,
+            by_zero_default,
+        } => Variant::Div(Box::new(DivExpression {
+            left: Some(boxed(left)),
+            right: Some(boxed(right)),
+            by_zero_default: by_zero_default.map(|default| default as f32),
+        })),
+        ParsedExpression::Sqrt(expression) => Variant::Sqrt(boxed(expression)),
+        ParsedExpression::Pow { base, exponent } => Variant::Pow(Box::new(PowExpression {
+            base: Some(boxed(base)),
+            exponent: Some(boxed(exponent)),
+        })),
+        ParsedExpression::Exp(expression) => Variant::Exp(boxed(expression)),
+        ParsedExpression::Log10(expression) => Variant::Log10(boxed(expression)),
+        ParsedExpression::Ln(expression) => Variant::Ln(boxed(expression)),
+        ParsedExpression::Abs(expression) => Variant::Abs(boxed(expression)),
+        ParsedExpression::Decay {
+            kind,
+            x,
+            target,
+            midpoint,
+            scale,
+        } => {
+            let params = Box::new(DecayParamsExpression {
+                x: Some(boxed(x)),
+                target: target.map(boxed),
+                scale: Some(scale as f32),
+                midpoint: Some(midpoint as f32),
+            });
+            match kind {
+                DecayKind::Lin => Variant::LinDecay(params),
+                DecayKind::Exp => Variant::ExpDecay(params),
+                DecayKind::Gauss => Variant::GaussDecay(params),
+            }
+        }
+    };
+
+    Expression {
+        variant: Some(variant),
+    }
+}
@@ -251,6 +251,10 @@ pub struct HnswConfigDiff {
     /// Number of additional payload-aware links per node in the index graph. If not set - regular M parameter will be used.
     #[prost(uint64, optional, tag = "6")]
     pub payload_m: ::core::option::Option<u64>,
+    ///
+    /// Link new points into HNSW graph of appendable segments right on insertion. Default: false
+    #[prost(bool, optional, tag = "7")]
+    pub appendable: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -385,6 +389,12 @@ pub struct BinaryQuantization {
     /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
     #[prost(bool, optional, tag = "1")]
     pub always_ram: ::core::option::Option<bool>,
+    /// Number of bits used to encode each dimension of stored vectors
+    #[prost(enumeration = "BinaryQuantizationEncoding", optional, tag = "2")]
+    pub encoding: ::core::option::Option<i32>,
+    /// Encoding of the query vector, scalar encodings improve precision at the cost of slower scoring
+    #[prost(enumeration = "BinaryQuantizationQueryEncoding", optional, tag = "3")]
+    pub query_encoding: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -443,6 +453,47 @@ pub mod quantization_config_diff {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StrictModeConfig {
+    /// Whether strict mode is enabled for the collection
+    #[prost(bool, optional, tag = "1")]
+    pub enabled: ::core::option::Option<bool>,
+    /// Max allowed `limit` parameter of read requests
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_query_limit: ::core::option::Option<u64>,
+    /// Max allowed `offset` parameter of read requests
+    #[prost(uint64, optional, tag = "3")]
+    pub max_query_offset: ::core::option::Option<u64>,
+    /// Max allowed `timeout` parameter, in seconds
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_timeout: ::core::option::Option<u64>,
+    /// Allow filtering on fields without a suitable payload index in read requests
+    #[prost(bool, optional, tag = "5")]
+    pub unindexed_filtering_retrieve: ::core::option::Option<bool>,
+    /// Allow filtering on fields without a suitable payload index in updates
+    #[prost(bool, optional, tag = "6")]
+    pub unindexed_filtering_update: ::core::option::Option<bool>,
+    /// Allow exact search, which bypasses the vector index
+    #[prost(bool, optional, tag = "7")]
+    pub search_allow_exact: ::core::option::Option<bool>,
+    /// Max number of requests in a batch of read requests
+    #[prost(uint64, optional, tag = "8")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub search_max_batchsize: ::core::option::Option<u64>,
+    /// Max number of points, or vectors, in a single update request
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub upsert_max_batchsize: ::core::option::Option<u64>,
+    /// Max number of points in the collection, inserting new points is rejected once it is reached
+    #[prost(uint64, optional, tag = "10")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_points_count: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateCollection {
     /// Name of the collection
     #[prost(string, tag = "1")]
@@ -495,6 +546,14 @@ pub struct CreateCollection {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "16")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Time-to-live of points in seconds, counted from their last upsert
+    #[prost(uint64, optional, tag = "17")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub ttl_sec: ::core::option::Option<u64>,
+    /// Limits on expensive requests to the collection
+    #[prost(message, optional, tag = "18")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -532,6 +591,10 @@ pub struct UpdateCollection {
     /// New sparse vector parameters
     #[prost(message, optional, tag = "8")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// New limits on expensive requests, unset fields are left unchanged
+    #[prost(message, optional, tag = "9")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -588,6 +651,9 @@ pub struct CollectionParams {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "10")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Time-to-live of points in seconds, counted from their last upsert
+    #[prost(uint64, optional, tag = "11")]
+    pub ttl_sec: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -606,6 +672,10 @@ pub struct CollectionParamsDiff {
     /// Fan-out every read request to these many additional remote nodes (and return first available response)
     #[prost(uint32, optional, tag = "4")]
     pub read_fan_out_factor: ::core::option::Option<u32>,
+    /// Time-to-live of points in seconds, counted from their last upsert
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub ttl_sec: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -630,6 +700,18 @@ pub struct CollectionConfig {
     #[prost(message, optional, tag = "5")]
     #[validate]
     pub quantization_config: ::core::option::Option<QuantizationConfig>,
+    /// Limits on expensive requests to the collection
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeywordIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -647,6 +729,58 @@ pub struct TextIndexParams {
     /// Maximal token length
     #[prost(uint64, optional, tag = "4")]
     pub max_token_len: ::core::option::Option<u64>,
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "5")]
+    pub on_disk: ::core::option::Option<bool>,
+    /// Stopwords to skip during indexing and querying
+    #[prost(message, optional, tag = "6")]
+    pub stopwords: ::core::option::Option<StopwordsSet>,
+    /// Algorithm for reducing tokens to their stems
+    #[prost(message, optional, tag = "7")]
+    pub stemmer: ::core::option::Option<StemmingAlgorithm>,
+    /// If true - convert non-ASCII characters of tokens into their ASCII equivalents
+    #[prost(bool, optional, tag = "8")]
+    pub ascii_folding: ::core::option::Option<bool>,
+    /// If true - store token positions to support phrase matching
+    #[prost(bool, optional, tag = "9")]
+    pub phrase_matching: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopwordsSet {
+    /// Languages of built-in stopwords, e.g. "english"
+    #[prost(string, repeated, tag = "1")]
+    pub languages: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Custom stopwords
+    #[prost(string, repeated, tag = "2")]
+    pub custom: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StemmingAlgorithm {
+    #[prost(oneof = "stemming_algorithm::StemmingParams", tags = "1")]
+    pub stemming_params: ::core::option::Option<stemming_algorithm::StemmingParams>,
+}
+/// Nested message and enum types in `StemmingAlgorithm`.
+pub mod stemming_algorithm {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum StemmingParams {
+        /// Snowball stemmer
+        #[prost(message, tag = "1")]
+        Snowball(super::SnowballParams),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SnowballParams {
+    /// Language of the stemmer, e.g. "english"
+    #[prost(string, tag = "1")]
+    pub language: ::prost::alloc::string::String,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -658,12 +792,47 @@ pub struct IntegerIndexParams {
     /// If true - support ranges filters.
     #[prost(bool, tag = "2")]
     pub range: bool,
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "3")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FloatIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatetimeIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UuidIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PayloadIndexParams {
-    #[prost(oneof = "payload_index_params::IndexParams", tags = "1, 2")]
+    #[prost(oneof = "payload_index_params::IndexParams", tags = "3, 2, 4, 5, 1, 6, 7")]
     pub index_params: ::core::option::Option<payload_index_params::IndexParams>,
 }
 /// Nested message and enum types in `PayloadIndexParams`.
@@ -672,12 +841,27 @@ pub mod payload_index_params {
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
     pub enum IndexParams {
-        /// Parameters for text index
-        #[prost(message, tag = "1")]
-        TextIndexParams(super::TextIndexParams),
+        /// Parameters for keyword index
+        #[prost(message, tag = "3")]
+        KeywordIndexParams(super::KeywordIndexParams),
         /// Parameters for integer index
         #[prost(message, tag = "2")]
         IntegerIndexParams(super::IntegerIndexParams),
+        /// Parameters for float index
+        #[prost(message, tag = "4")]
+        FloatIndexParams(super::FloatIndexParams),
+        /// Parameters for geo index
+        #[prost(message, tag = "5")]
+        GeoIndexParams(super::GeoIndexParams),
+        /// Parameters for text index
+        #[prost(message, tag = "1")]
+        TextIndexParams(super::TextIndexParams),
+        /// Parameters for datetime index
+        #[prost(message, tag = "6")]
+        DatetimeIndexParams(super::DatetimeIndexParams),
+        /// Parameters for uuid index
+        #[prost(message, tag = "7")]
+        UuidIndexParams(super::UuidIndexParams),
     }
 }
 #[derive(serde::Serialize)]
@@ -1114,6 +1298,10 @@ pub enum Datatype {
     Float32 = 1,
     Uint8 = 2,
     Float16 = 3,
+    /// Binary vector, 8 dimensions packed into a byte
+    Bit = 4,
+    /// Brain floating point, keeps the value range of `Float32`
+    Bfloat16 = 5,
 }
 impl Datatype {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1126,6 +1314,8 @@ impl Datatype {
             Datatype::Float32 => "Float32",
             Datatype::Uint8 => "Uint8",
             Datatype::Float16 => "Float16",
+            Datatype::Bit => "Bit",
+            Datatype::Bfloat16 => "Bfloat16",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1135,6 +1325,8 @@ impl Datatype {
             "Float32" => Some(Self::Float32),
             "Uint8" => Some(Self::Uint8),
             "Float16" => Some(Self::Float16),
+            "Bit" => Some(Self::Bit),
+            "Bfloat16" => Some(Self::Bfloat16),
             _ => None,
         }
     }
@@ -1200,6 +1392,10 @@ pub enum Distance {
     Euclid = 2,
     Dot = 3,
     Manhattan = 4,
+    /// Only for `Bit` datatype
+    Hamming = 5,
+    /// Only for `Bit` datatype
+    Jaccard = 6,
 }
 impl Distance {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1213,6 +1409,8 @@ impl Distance {
             Distance::Euclid => "Euclid",
             Distance::Dot => "Dot",
             Distance::Manhattan => "Manhattan",
+            Distance::Hamming => "Hamming",
+            Distance::Jaccard => "Jaccard",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1223,6 +1421,8 @@ impl Distance {
             "Euclid" => Some(Self::Euclid),
             "Dot" => Some(Self::Dot),
             "Manhattan" => Some(Self::Manhattan),
+            "Hamming" => Some(Self::Hamming),
+            "Jaccard" => Some(Self::Jaccard),
             _ => None,
         }
     }
@@ -1279,6 +1479,7 @@ pub enum PayloadSchemaType {
     Text = 5,
     Bool = 6,
     Datetime = 7,
+    Uuid = 8,
 }
 impl PayloadSchemaType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1295,6 +1496,7 @@ impl PayloadSchemaType {
             PayloadSchemaType::Text => "Text",
             PayloadSchemaType::Bool => "Bool",
             PayloadSchemaType::Datetime => "Datetime",
+            PayloadSchemaType::Uuid => "Uuid",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1308,6 +1510,7 @@ impl PayloadSchemaType {
             "Text" => Some(Self::Text),
             "Bool" => Some(Self::Bool),
             "Datetime" => Some(Self::Datetime),
+            "Uuid" => Some(Self::Uuid),
             _ => None,
         }
     }
@@ -1318,6 +1521,8 @@ impl PayloadSchemaType {
 pub enum QuantizationType {
     UnknownQuantization = 0,
     Int8 = 1,
+    Int4 = 2,
+    Float8 = 3,
 }
 impl QuantizationType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1328,6 +1533,8 @@ impl QuantizationType {
         match self {
             QuantizationType::UnknownQuantization => "UnknownQuantization",
             QuantizationType::Int8 => "Int8",
+            QuantizationType::Int4 => "Int4",
+            QuantizationType::Float8 => "Float8",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1335,6 +1542,8 @@ impl QuantizationType {
         match value {
             "UnknownQuantization" => Some(Self::UnknownQuantization),
             "Int8" => Some(Self::Int8),
+            "Int4" => Some(Self::Int4),
+            "Float8" => Some(Self::Float8),
             _ => None,
         }
     }
@@ -1378,6 +1587,66 @@ impl CompressionRatio {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum BinaryQuantizationEncoding {
+    OneBit = 0,
+    TwoBits = 1,
+    OneAndHalfBits = 2,
+}
+impl BinaryQuantizationEncoding {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            BinaryQuantizationEncoding::OneBit => "OneBit",
+            BinaryQuantizationEncoding::TwoBits => "TwoBits",
+            BinaryQuantizationEncoding::OneAndHalfBits => "OneAndHalfBits",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "OneBit" => Some(Self::OneBit),
+            "TwoBits" => Some(Self::TwoBits),
+            "OneAndHalfBits" => Some(Self::OneAndHalfBits),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum BinaryQuantizationQueryEncoding {
+    Binary = 0,
+    Scalar4Bits = 1,
+    Scalar8Bits = 2,
+}
+impl BinaryQuantizationQueryEncoding {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            BinaryQuantizationQueryEncoding::Binary => "Binary",
+            BinaryQuantizationQueryEncoding::Scalar4Bits => "Scalar4Bits",
+            BinaryQuantizationQueryEncoding::Scalar8Bits => "Scalar8Bits",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Binary" => Some(Self::Binary),
+            "Scalar4Bits" => Some(Self::Scalar4Bits),
+            "Scalar8Bits" => Some(Self::Scalar8Bits),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum ShardingMethod {
     /// Auto-sharding based on record ids
     Auto = 0,
@@ -3736,6 +4005,9 @@ pub struct Vector {
     /// Number of vectors per multi vector
     #[prost(uint32, optional, tag = "3")]
     pub vectors_count: ::core::option::Option<u32>,
+    /// Text, converted into a BM25 sparse vector on the server
+    #[prost(message, optional, tag = "4")]
+    pub document: ::core::option::Option<Document>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3760,12 +4032,57 @@ pub struct MultiDenseVector {
     #[prost(message, repeated, tag = "1")]
     pub vectors: ::prost::alloc::vec::Vec<DenseVector>,
 }
+/// Parameters of the conversion of a text into a BM25 sparse vector
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bm25Config {
+    /// Tokenizer used to split the text into terms. Default: Word
+    #[prost(enumeration = "TokenizerType", optional, tag = "1")]
+    pub tokenizer: ::core::option::Option<i32>,
+    /// Skip terms shorter than this
+    #[prost(uint64, optional, tag = "2")]
+    pub min_token_len: ::core::option::Option<u64>,
+    /// Skip terms longer than this
+    #[prost(uint64, optional, tag = "3")]
+    pub max_token_len: ::core::option::Option<u64>,
+    /// If true, lowercase all terms. Default: true
+    #[prost(bool, optional, tag = "4")]
+    pub lowercase: ::core::option::Option<bool>,
+    /// Term frequency saturation. The higher the value, the more repeated terms contribute. Default: 1.2
+    #[prost(float, optional, tag = "5")]
+    #[validate(custom = "crate::grpc::validate::validate_f32_range_min_0")]
+    pub k1: ::core::option::Option<f32>,
+    /// Document length normalization, from 0 (no normalization) to 1 (full normalization). Default: 0.75
+    #[prost(float, optional, tag = "6")]
+    #[validate(custom = "crate::grpc::validate::validate_f32_range_min_0_max_1")]
+    pub b: ::core::option::Option<f32>,
+    /// Expected average length of the documents, in terms. Default: 256
+    #[prost(float, optional, tag = "7")]
+    #[validate(custom = "crate::grpc::validate::validate_f32_range_min_1")]
+    pub avg_len: ::core::option::Option<f32>,
+}
+/// Text to be converted into a sparse vector on the server side
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Document {
+    /// Text of the document
+    #[prost(string, tag = "1")]
+    pub text: ::prost::alloc::string::String,
+    /// Tokenization and BM25 parameters. Should be the same for the documents and the queries of the same vector
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub bm25: ::core::option::Option<Bm25Config>,
+}
 /// Vector type to be used in queries. Ids will be substituted with their corresponding vectors from the collection.
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct VectorInput {
-    #[prost(oneof = "vector_input::Variant", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "vector_input::Variant", tags = "1, 2, 3, 4, 5")]
     pub variant: ::core::option::Option<vector_input::Variant>,
 }
 /// Nested message and enum types in `VectorInput`.
@@ -3782,6 +4099,9 @@ pub mod vector_input {
         Sparse(super::SparseVector),
         #[prost(message, tag = "4")]
         MultiDense(super::MultiDenseVector),
+        /// Text, converted into a BM25 sparse vector on the server
+        #[prost(message, tag = "5")]
+        Document(super::Document),
     }
 }
 /// ---------------------------------------------
@@ -3816,6 +4136,10 @@ pub struct UpsertPoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If specified, only existing points which match this filter are updated, others are skipped. New points are always inserted.
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub update_filter: ::core::option::Option<Filter>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4373,6 +4697,9 @@ pub struct OrderBy {
     /// Start from this value
     #[prost(message, optional, tag = "3")]
     pub start_from: ::core::option::Option<StartFrom>,
+    /// Order by the distance in meters from this point, requires a geo index
+    #[prost(message, optional, tag = "4")]
+    pub geo_point: ::core::option::Option<GeoPoint>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4724,6 +5051,89 @@ pub struct CountPoints {
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCounts {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Payload key of the facet, must be indexed with a keyword, integer or bool index
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - count only the points that satisfy the specified conditions
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of values to return, the most frequent values go first. Default is 10
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "6")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "7")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Filter conditions - sample only the points that satisfy the specified conditions
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// How many points to select and search within. Default is 10
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_2_max_10000")]
+    pub sample: ::core::option::Option<u64>,
+    /// How many neighbours per sample to find. Default is 3
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// Define which vector to use for querying. If missing, the default vector is used
+    #[prost(string, optional, tag = "5")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "6")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "7")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "8")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamUpdatesRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the local shard to follow, each shard has its own sequence of operations
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Operation id to start streaming from, use `operation_id + 1` of the last received event to resume. If not specified - start from the oldest operation available in the WAL
+    #[prost(uint64, optional, tag = "3")]
+    pub start_from: ::core::option::Option<u64>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -4771,12 +5181,189 @@ pub struct ContextInput {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Query {
-    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6")]
-    pub variant: ::core::option::Option<query::Variant>,
+pub struct Rrf {
+    /// Ranking constant. The higher, the less the top positions dominate the fused score. Default is 2.
+    #[prost(uint32, optional, tag = "1")]
+    pub k: ::core::option::Option<u32>,
 }
-/// Nested message and enum types in `Query`.
-pub mod query {
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WeightedFusion {
+    /// Weights of the scores of each prefetch, in the same order as the prefetches.
+    #[prost(float, repeated, tag = "1")]
+    pub weights: ::prost::alloc::vec::Vec<f32>,
+}
+/// Full-text query, ranking the points with BM25. Document frequencies and average document length are computed in each shard separately, so the scores of different shards are only comparable if the points are evenly distributed among the shards.
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TextQuery {
+    /// Payload key with a full-text index
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Text to search for
+    #[prost(string, tag = "2")]
+    pub text: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Formula {
+    /// Expression to compute the new score of each point
+    #[prost(message, optional, tag = "1")]
+    pub expression: ::core::option::Option<Expression>,
+    /// Values to use for the variables which have no value for a point. Keys are payload keys, or `$score\[<prefetch index>\]`.
+    #[prost(map = "string, message", tag = "2")]
+    pub defaults: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Expression {
+    #[prost(
+        oneof = "expression::Variant",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19"
+    )]
+    pub variant: ::core::option::Option<expression::Variant>,
+}
+/// Nested message and enum types in `Expression`.
+pub mod expression {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Constant number
+        #[prost(float, tag = "1")]
+        Constant(f32),
+        /// Payload key with a number, or `$score` for the score of the first prefetch, or `$score\[<prefetch index>\]` for the score of another prefetch
+        #[prost(string, tag = "2")]
+        Variable(::prost::alloc::string::String),
+        /// Payload condition, evaluated as 1 if the point satisfies it, 0 otherwise
+        #[prost(message, tag = "3")]
+        Condition(super::Condition),
+        /// Distance in meters to a geo point in the payload
+        #[prost(message, tag = "4")]
+        GeoDistance(super::GeoDistance),
+        /// Datetime in RFC 3339 format, or `now`. Evaluated as seconds since epoch.
+        #[prost(string, tag = "5")]
+        Datetime(::prost::alloc::string::String),
+        /// Payload key with a datetime. Evaluated as seconds since epoch.
+        #[prost(string, tag = "6")]
+        DatetimeKey(::prost::alloc::string::String),
+        /// Multiply all the expressions
+        #[prost(message, tag = "7")]
+        Mult(super::MultExpression),
+        /// Sum all the expressions
+        #[prost(message, tag = "8")]
+        Sum(super::SumExpression),
+        /// Divide the left expression by the right one
+        #[prost(message, tag = "9")]
+        Div(::prost::alloc::boxed::Box<super::DivExpression>),
+        /// Negate the expression
+        #[prost(message, tag = "10")]
+        Neg(::prost::alloc::boxed::Box<super::Expression>),
+        /// Absolute value of the expression
+        #[prost(message, tag = "11")]
+        Abs(::prost::alloc::boxed::Box<super::Expression>),
+        /// Square root of the expression
+        #[prost(message, tag = "12")]
+        Sqrt(::prost::alloc::boxed::Box<super::Expression>),
+        /// Raise the base to the power of the exponent
+        #[prost(message, tag = "13")]
+        Pow(::prost::alloc::boxed::Box<super::PowExpression>),
+        /// Raise e to the power of the expression
+        #[prost(message, tag = "14")]
+        Exp(::prost::alloc::boxed::Box<super::Expression>),
+        /// Base 10 logarithm of the expression
+        #[prost(message, tag = "15")]
+        Log10(::prost::alloc::boxed::Box<super::Expression>),
+        /// Natural logarithm of the expression
+        #[prost(message, tag = "16")]
+        Ln(::prost::alloc::boxed::Box<super::Expression>),
+        /// Exponential decay
+        #[prost(message, tag = "17")]
+        ExpDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+        /// Gaussian decay
+        #[prost(message, tag = "18")]
+        GaussDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+        /// Linear decay, reaching 0 at `scale / (1 - midpoint)` away from the target
+        #[prost(message, tag = "19")]
+        LinDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoDistance {
+    /// Geo point to measure the distance from
+    #[prost(message, optional, tag = "1")]
+    pub origin: ::core::option::Option<GeoPoint>,
+    /// Payload key with the geo point to measure the distance to
+    #[prost(string, tag = "2")]
+    pub to: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub mult: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SumExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub sum: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DivExpression {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub left: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub right: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// Result of the division when `right` is 0. If missing, such division fails the request.
+    #[prost(float, optional, tag = "3")]
+    pub by_zero_default: ::core::option::Option<f32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PowExpression {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub base: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub exponent: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecayParamsExpression {
+    /// Value to decay
+    #[prost(message, optional, boxed, tag = "1")]
+    pub x: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// Value at which the decay is 1. Default is 0.
+    #[prost(message, optional, boxed, tag = "2")]
+    pub target: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// Distance from the target at which the decay is `midpoint`. Default is 1.
+    #[prost(float, optional, tag = "3")]
+    pub scale: ::core::option::Option<f32>,
+    /// Decay at `scale` distance from the target, between 0 and 1, exclusive. Default is 0.5.
+    #[prost(float, optional, tag = "4")]
+    pub midpoint: ::core::option::Option<f32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Query {
+    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11")]
+    pub variant: ::core::option::Option<query::Variant>,
+}
+/// Nested message and enum types in `Query`.
+pub mod query {
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
@@ -4799,11 +5386,37 @@ pub mod query {
         /// Fuse the results of multiple prefetches.
         #[prost(enumeration = "super::Fusion", tag = "6")]
         Fusion(i32),
+        /// Fuse the results of multiple prefetches with Reciprocal Rank Fusion, with custom parameters.
+        #[prost(message, tag = "7")]
+        Rrf(super::Rrf),
+        /// Fuse the results of multiple prefetches with a weighted sum of their scores.
+        #[prost(message, tag = "8")]
+        Weighted(super::WeightedFusion),
+        /// Rescore the results of the prefetches with a formula, combining their scores and payload values.
+        #[prost(message, tag = "9")]
+        Formula(super::Formula),
+        /// Rank the points by BM25 relevance of a full-text indexed payload field to the text.
+        #[prost(message, tag = "10")]
+        Text(super::TextQuery),
+        /// Sample points from the collection, without scoring them against a query.
+        #[prost(enumeration = "super::Sample", tag = "11")]
+        Sample(i32),
     }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Mmr {
+    /// Trade-off between relevance and diversity, in the range \[0, 1\]. 0 only considers relevance, 1 only considers diversity. Default is 0.5.
+    #[prost(float, optional, tag = "1")]
+    pub diversity: ::core::option::Option<f32>,
+    /// Number of nearest neighbours to select the results from. Default is `limit` + `offset`.
+    #[prost(uint32, optional, tag = "2")]
+    pub candidates_limit: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PrefetchQuery {
     /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
     #[prost(message, repeated, tag = "1")]
@@ -4870,6 +5483,9 @@ pub struct QueryPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards.
     #[prost(message, optional, tag = "13")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Diversify the results of a nearest query with Maximal Marginal Relevance (MMR).
+    #[prost(message, optional, tag = "14")]
+    pub mmr: ::core::option::Option<Mmr>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4892,6 +5508,9 @@ pub mod points_update_operation {
         /// Option for custom sharding to specify used shard keys
         #[prost(message, optional, tag = "2")]
         pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+        /// If specified, only existing points which match this filter are updated, others are skipped. New points are always inserted.
+        #[prost(message, optional, tag = "3")]
+        pub update_filter: ::core::option::Option<super::Filter>,
     }
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5057,6 +5676,12 @@ pub struct UpdateResult {
     /// Operation status
     #[prost(enumeration = "UpdateStatus", tag = "2")]
     pub status: i32,
+    /// Number of inserted or updated points, provided for completed upserts with `update_filter`
+    #[prost(uint64, optional, tag = "4")]
+    pub updated_count: ::core::option::Option<u64>,
+    /// Number of existing points skipped because they don't match `update_filter`, provided for completed upserts with `update_filter`
+    #[prost(uint64, optional, tag = "5")]
+    pub skipped_count: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5219,6 +5844,111 @@ pub struct CountResult {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetValue {
+    #[prost(oneof = "facet_value::Variant", tags = "1, 2, 3")]
+    pub variant: ::core::option::Option<facet_value::Variant>,
+}
+/// Nested message and enum types in `FacetValue`.
+pub mod facet_value {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// String value of the payload field
+        #[prost(string, tag = "1")]
+        StringValue(::prost::alloc::string::String),
+        /// Integer value of the payload field
+        #[prost(int64, tag = "2")]
+        IntegerValue(i64),
+        /// Boolean value of the payload field
+        #[prost(bool, tag = "3")]
+        BoolValue(bool),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetHit {
+    /// Value of the payload field
+    #[prost(message, optional, tag = "1")]
+    pub value: ::core::option::Option<FacetValue>,
+    /// Number of points with this value
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetResponse {
+    /// Most frequent values, in descending order of counts
+    #[prost(message, repeated, tag = "1")]
+    pub hits: ::prost::alloc::vec::Vec<FacetHit>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPair {
+    /// Id of the sampled point
+    #[prost(message, optional, tag = "1")]
+    pub a: ::core::option::Option<PointId>,
+    /// Id of its neighbour in the sample
+    #[prost(message, optional, tag = "2")]
+    pub b: ::core::option::Option<PointId>,
+    /// Similarity score between the points
+    #[prost(float, tag = "3")]
+    pub score: f32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPairs {
+    /// List of pairs of points with scores
+    #[prost(message, repeated, tag = "1")]
+    pub pairs: ::prost::alloc::vec::Vec<SearchMatrixPair>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixOffsets {
+    /// Row indices of the matrix, pointing into `ids`
+    #[prost(uint64, repeated, tag = "1")]
+    pub offsets_row: ::prost::alloc::vec::Vec<u64>,
+    /// Column indices of the matrix, pointing into `ids`
+    #[prost(uint64, repeated, tag = "2")]
+    pub offsets_col: ::prost::alloc::vec::Vec<u64>,
+    /// Scores associated with matrix coordinates
+    #[prost(float, repeated, tag = "3")]
+    pub scores: ::prost::alloc::vec::Vec<f32>,
+    /// Ids of the sampled points
+    #[prost(message, repeated, tag = "4")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPairsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<SearchMatrixPairs>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixOffsetsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<SearchMatrixOffsets>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RetrievedPoint {
     #[prost(message, optional, tag = "1")]
     pub id: ::core::option::Option<PointId>,
@@ -5300,6 +6030,64 @@ pub struct UpdateBatchResponse {
     #[prost(double, tag = "2")]
     pub time: f64,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateEvent {
+    /// Id of the operation in the WAL of the shard
+    #[prost(uint64, tag = "1")]
+    pub operation_id: u64,
+    #[prost(
+        oneof = "update_event::Operation",
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12"
+    )]
+    pub operation: ::core::option::Option<update_event::Operation>,
+}
+/// Nested message and enum types in `UpdateEvent`.
+pub mod update_event {
+    #[derive(serde::Serialize)]
+    #[derive(validator::Validate)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct SyncPoints {
+        /// Points to upsert
+        #[prost(message, repeated, tag = "1")]
+        pub points: ::prost::alloc::vec::Vec<super::PointStruct>,
+        /// Start of the sync range, points in the range which are not listed are deleted
+        #[prost(message, optional, tag = "2")]
+        pub from_id: ::core::option::Option<super::PointId>,
+        /// End of the sync range
+        #[prost(message, optional, tag = "3")]
+        pub to_id: ::core::option::Option<super::PointId>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Operation {
+        #[prost(message, tag = "2")]
+        Upsert(super::UpsertPoints),
+        #[prost(message, tag = "3")]
+        DeletePoints(super::DeletePoints),
+        #[prost(message, tag = "4")]
+        UpdateVectors(super::UpdatePointVectors),
+        #[prost(message, tag = "5")]
+        DeleteVectors(super::DeletePointVectors),
+        #[prost(message, tag = "6")]
+        SetPayload(super::SetPayloadPoints),
+        #[prost(message, tag = "7")]
+        OverwritePayload(super::SetPayloadPoints),
+        #[prost(message, tag = "8")]
+        DeletePayload(super::DeletePayloadPoints),
+        #[prost(message, tag = "9")]
+        ClearPayload(super::ClearPayloadPoints),
+        #[prost(message, tag = "10")]
+        CreateFieldIndex(super::CreateFieldIndexCollection),
+        #[prost(message, tag = "11")]
+        DeleteFieldIndex(super::DeleteFieldIndexCollection),
+        #[prost(message, tag = "12")]
+        SyncPoints(SyncPoints),
+    }
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5392,6 +6180,9 @@ pub struct NestedCondition {
     #[prost(message, optional, tag = "2")]
     #[validate]
     pub filter: ::core::option::Option<Filter>,
+    /// Which elements of the array must match the filter. Default is any
+    #[prost(enumeration = "ArrayQuantifier", optional, tag = "3")]
+    pub quantifier: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5425,7 +6216,7 @@ pub struct FieldCondition {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Match {
-    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
+    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11")]
     pub match_value: ::core::option::Option<r#match::MatchValue>,
 }
 /// Nested message and enum types in `Match`.
@@ -5458,6 +6249,15 @@ pub mod r#match {
         /// Match any other value except those keywords
         #[prost(message, tag = "8")]
         ExceptKeywords(super::RepeatedStrings),
+        /// Match phrase
+        #[prost(string, tag = "9")]
+        Phrase(::prost::alloc::string::String),
+        /// Match all of the keywords, e.g. array contains all of them
+        #[prost(message, tag = "10")]
+        AllKeywords(super::RepeatedStrings),
+        /// Match all of the integers, e.g. array contains all of them
+        #[prost(message, tag = "11")]
+        AllIntegers(super::RepeatedIntegers),
     }
 }
 #[derive(serde::Serialize)]
@@ -5691,6 +6491,7 @@ pub enum FieldType {
     Text = 4,
     Bool = 5,
     Datetime = 6,
+    Uuid = 7,
 }
 impl FieldType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5706,6 +6507,7 @@ impl FieldType {
             FieldType::Text => "FieldTypeText",
             FieldType::Bool => "FieldTypeBool",
             FieldType::Datetime => "FieldTypeDatetime",
+            FieldType::Uuid => "FieldTypeUuid",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -5718,6 +6520,7 @@ impl FieldType {
             "FieldTypeText" => Some(Self::Text),
             "FieldTypeBool" => Some(Self::Bool),
             "FieldTypeDatetime" => Some(Self::Datetime),
+            "FieldTypeUuid" => Some(Self::Uuid),
             _ => None,
         }
     }
@@ -5788,6 +6591,8 @@ impl RecommendStrategy {
 pub enum Fusion {
     /// Reciprocal Rank Fusion
     Rrf = 0,
+    /// Distribution-Based Score Fusion
+    Dbsf = 1,
 }
 impl Fusion {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5797,12 +6602,39 @@ impl Fusion {
     pub fn as_str_name(&self) -> &'static str {
         match self {
             Fusion::Rrf => "RRF",
+            Fusion::Dbsf => "DBSF",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
             "RRF" => Some(Self::Rrf),
+            "DBSF" => Some(Self::Dbsf),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Sample {
+    /// Uniformly random points
+    Random = 0,
+}
+impl Sample {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Sample::Random => "Random",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Random" => Some(Self::Random),
             _ => None,
         }
     }
@@ -5843,6 +6675,35 @@ impl UpdateStatus {
         }
     }
 }
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ArrayQuantifier {
+    /// At least one element of the array must match the filter
+    AnyElement = 0,
+    /// All elements of a non-empty array must match the filter
+    AllElements = 1,
+}
+impl ArrayQuantifier {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ArrayQuantifier::AnyElement => "AnyElement",
+            ArrayQuantifier::AllElements => "AllElements",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "AnyElement" => Some(Self::AnyElement),
+            "AllElements" => Some(Self::AllElements),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod points_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -6470,6 +7331,81 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Count points per value of the given payload key, with the given filtering conditions
+        pub async fn facet(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FacetCounts>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Facet");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Facet"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Compute the distance matrix for a sample of points, as a list of pairs
+        pub async fn search_matrix_pairs(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixPairsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchMatrixPairs",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SearchMatrixPairs"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Compute the distance matrix for a sample of points, in the offsets layout
+        pub async fn search_matrix_offsets(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixOffsetsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchMatrixOffsets",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SearchMatrixOffsets"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
         /// Perform multiple update operations in one request
         pub async fn update_batch(
             &mut self,
@@ -6495,6 +7431,33 @@ pub mod points_client {
             req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "UpdateBatch"));
             self.inner.unary(req, path, codec).await
         }
+        ///
+        /// Stream operations applied to a local shard of the collection, as they are written to the WAL
+        pub async fn stream_updates(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StreamUpdatesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::UpdateEvent>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/StreamUpdates",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "StreamUpdates"));
+            self.inner.server_streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -6696,6 +7659,30 @@ pub mod points_server {
             request: tonic::Request<super::CountPoints>,
         ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status>;
         ///
+        /// Count points per value of the given payload key, with the given filtering conditions
+        async fn facet(
+            &self,
+            request: tonic::Request<super::FacetCounts>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status>;
+        ///
+        /// Compute the distance matrix for a sample of points, as a list of pairs
+        async fn search_matrix_pairs(
+            &self,
+            request: tonic::Request<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixPairsResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Compute the distance matrix for a sample of points, in the offsets layout
+        async fn search_matrix_offsets(
+            &self,
+            request: tonic::Request<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixOffsetsResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Perform multiple update operations in one request
         async fn update_batch(
             &self,
@@ -6704,6 +7691,21 @@ pub mod points_server {
             tonic::Response<super::UpdateBatchResponse>,
             tonic::Status,
         >;
+        /// Server streaming response type for the StreamUpdates method.
+        type StreamUpdatesStream: tonic::codegen::futures_core::Stream<
+                Item = std::result::Result<super::UpdateEvent, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        ///
+        /// Stream operations applied to a local shard of the collection, as they are written to the WAL
+        async fn stream_updates(
+            &self,
+            request: tonic::Request<super::StreamUpdatesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamUpdatesStream>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsServer<T: Points> {
@@ -7726,6 +8728,142 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/Facet" => {
+                    #[allow(non_camel_case_types)]
+                    struct FacetSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::FacetCounts>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FacetCounts>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::facet(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FacetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/SearchMatrixPairs" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchMatrixPairsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::SearchMatrixPoints>
+                    for SearchMatrixPairsSvc<T> {
+                        type Response = super::SearchMatrixPairsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SearchMatrixPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::search_matrix_pairs(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SearchMatrixPairsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/SearchMatrixOffsets" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchMatrixOffsetsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::SearchMatrixPoints>
+                    for SearchMatrixOffsetsSvc<T> {
+                        type Response = super::SearchMatrixOffsetsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SearchMatrixPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::search_matrix_offsets(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SearchMatrixOffsetsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.Points/UpdateBatch" => {
                     #[allow(non_camel_case_types)]
                     struct UpdateBatchSvc<T: Points>(pub Arc<T>);
@@ -7770,6 +8908,53 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/StreamUpdates" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamUpdatesSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::ServerStreamingService<super::StreamUpdatesRequest>
+                    for StreamUpdatesSvc<T> {
+                        type Response = super::UpdateEvent;
+                        type ResponseStream = T::StreamUpdatesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamUpdatesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::stream_updates(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamUpdatesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -7846,6 +9031,12 @@ pub struct SyncPointsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Number of inserted or updated points, provided for completed upserts with `update_filter`
+    #[prost(uint64, optional, tag = "4")]
+    pub updated_count: ::core::option::Option<u64>,
+    /// Number of existing points skipped because they don't match `update_filter`, provided for completed upserts with `update_filter`
+    #[prost(uint64, optional, tag = "5")]
+    pub skipped_count: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8200,6 +9391,27 @@ pub struct CountPointsInternal {
     #[prost(uint32, optional, tag = "2")]
     pub shard_id: ::core::option::Option<u32>,
 }
+/// Counts of all values are returned, so that responses of the shards can be merged exactly
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCountsInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    #[prost(uint32, tag = "4")]
+    pub shard_id: u32,
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+}
 /// A bare vector. No id reference here.
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -8315,7 +9527,7 @@ pub mod query_shard_points {
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct Query {
-        #[prost(oneof = "query::Score", tags = "1, 2, 3")]
+        #[prost(oneof = "query::Score", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
         pub score: ::core::option::Option<query::Score>,
     }
     /// Nested message and enum types in `Query`.
@@ -8333,6 +9545,21 @@ pub mod query_shard_points {
             /// Order by a field
             #[prost(message, tag = "3")]
             OrderBy(super::super::OrderBy),
+            /// Reciprocal rank fusion with custom parameters
+            #[prost(message, tag = "4")]
+            Rrf(super::super::Rrf),
+            /// Weighted sum of the scores of the prefetches
+            #[prost(message, tag = "5")]
+            Weighted(super::super::WeightedFusion),
+            /// Rescore with a formula
+            #[prost(message, tag = "6")]
+            Formula(super::super::Formula),
+            /// Rank by BM25 relevance to a text
+            #[prost(message, tag = "7")]
+            Text(super::super::TextQuery),
+            /// Sample points randomly
+            #[prost(enumeration = "super::super::Sample", tag = "8")]
+            Sample(i32),
         }
     }
     #[derive(serde::Serialize)]
@@ -8880,6 +10107,28 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "Query"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn facet(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FacetCountsInternal>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Facet",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Facet"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -8996,6 +10245,10 @@ pub mod points_internal_server {
             &self,
             request: tonic::Request<super::QueryPointsInternal>,
         ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status>;
+        async fn facet(
+            &self,
+            request: tonic::Request<super::FacetCountsInternal>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -9868,6 +11121,52 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/Facet" => {
+                    #[allow(non_camel_case_types)]
+                    struct FacetSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::FacetCountsInternal>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FacetCountsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::facet(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FacetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
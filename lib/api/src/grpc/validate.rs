@@ -228,6 +228,10 @@ impl Validate for grpc::FieldCondition {
 
 impl Validate for grpc::Vector {
     fn validate(&self) -> Result<(), ValidationErrors> {
+        if let Some(document) = &self.document {
+            return document.validate();
+        }
+
         match (&self.indices, self.vectors_count) {
             (Some(_), Some(_)) => {
                 let mut errors = ValidationErrors::new();
@@ -279,6 +283,11 @@ pub fn validate_u32_range_min_1(value: &Option<u32>) -> Result<(), ValidationErr
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(1), None))
 }
 
+/// Validate the value is in `[2, 10000]` or `None`.
+pub fn validate_u64_range_min_2_max_10000(value: &Option<u64>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(2), Some(10000)))
+}
+
 /// Validate the value is in `[100, ]` or `None`.
 pub fn validate_u64_range_min_100(value: &Option<u64>) -> Result<(), ValidationError> {
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(100), None))
@@ -304,6 +313,21 @@ pub fn validate_f32_range_min_0_5_max_1(value: &Option<f32>) -> Result<(), Valid
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.5), Some(1.0)))
 }
 
+/// Validate the value is in `[0.0, ]` or `None`.
+pub fn validate_f32_range_min_0(value: &Option<f32>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.0), None))
+}
+
+/// Validate the value is in `[0.0, 1.0]` or `None`.
+pub fn validate_f32_range_min_0_max_1(value: &Option<f32>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.0), Some(1.0)))
+}
+
+/// Validate the value is in `[1.0, ]` or `None`.
+pub fn validate_f32_range_min_1(value: &Option<f32>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(1.0), None))
+}
+
 /// Validate the value is in `[0.0, 1.0]` or `None`.
 pub fn validate_f64_range_1(value: &Option<f64>) -> Result<(), ValidationError> {
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.0), Some(1.0)))
@@ -28,6 +28,9 @@ impl From<Vector> for segment::data_types::vectors::Vector {
                     segment::data_types::vectors::MultiDenseVector::new_unchecked(vector),
                 )
             }
+            Vector::Document(document) => {
+                segment::data_types::vectors::Vector::Sparse(document.to_sparse_document())
+            }
         }
     }
 }
@@ -177,6 +180,7 @@ impl From<OrderByInterface> for OrderBy {
                 key,
                 direction: None,
                 start_from: None,
+                geo_point: None,
             },
             OrderByInterface::Struct(order_by) => order_by,
         }
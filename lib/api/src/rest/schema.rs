@@ -1,12 +1,17 @@
 use std::collections::HashMap;
 
 use common::types::ScoreType;
+use common::validation::validate_fusion_weights;
 use schemars::JsonSchema;
 use segment::common::utils::MaybeOneOrMany;
+use segment::data_types::bm25::Document;
 use segment::data_types::order_by::OrderBy;
 use segment::json_path::JsonPath;
-use segment::types::{Filter, SearchParams, ShardKey, WithPayloadInterface, WithVector};
+use segment::types::{
+    Condition, Filter, GeoPoint, SearchParams, ShardKey, WithPayloadInterface, WithVector,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sparse::common::sparse_vector::SparseVector;
 use validator::Validate;
 
@@ -22,6 +27,7 @@ pub enum Vector {
     Dense(DenseVector),
     Sparse(sparse::common::sparse_vector::SparseVector),
     MultiDense(MultiDenseVector),
+    Document(Document),
 }
 
 /// Full vector data per point separator with single and multiple vector modes
@@ -41,6 +47,7 @@ impl VectorStruct {
                 Vector::Dense(vector) => vector.is_empty(),
                 Vector::Sparse(vector) => vector.indices.is_empty(),
                 Vector::MultiDense(vector) => vector.is_empty(),
+                Vector::Document(document) => document.text.is_empty(),
             }),
         }
     }
@@ -160,6 +167,32 @@ pub enum OrderByInterface {
 pub enum Fusion {
     /// Reciprocal rank fusion
     Rrf,
+    /// Distribution-based score fusion
+    Dbsf,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Sample {
+    /// Uniformly random points
+    Random,
+}
+
+/// Reciprocal rank fusion with custom parameters
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct Rrf {
+    /// Ranking constant. The higher, the less the top positions dominate the fused score. Default is 2.
+    #[validate(range(min = 1))]
+    pub k: Option<usize>,
+}
+
+/// Weighted sum of the scores of the prefetches
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct WeightedFusion {
+    /// Weights of the scores of each prefetch, in the same order as the prefetches.
+    /// Weights must be finite and not negative.
+    #[validate(length(min = 1), custom = "validate_fusion_weights")]
+    pub weights: Vec<f32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -169,6 +202,7 @@ pub enum VectorInput {
     SparseVector(SparseVector),
     MultiDenseVector(MultiDenseVector),
     Id(segment::types::PointIdType),
+    Document(Document),
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -207,6 +241,10 @@ pub struct QueryRequestInternal {
 
     /// Options for specifying which payload to include or not. Default is false.
     pub with_payload: Option<WithPayloadInterface>,
+
+    /// Diversify the results of a `nearest` query with Maximal Marginal Relevance (MMR).
+    #[validate]
+    pub mmr: Option<Mmr>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -244,6 +282,37 @@ pub enum Query {
 
     /// Fuse the results of multiple prefetches.
     Fusion(Fusion),
+
+    /// Fuse the results of multiple prefetches with reciprocal rank fusion, with custom parameters.
+    Rrf(Rrf),
+
+    /// Fuse the results of multiple prefetches with a weighted sum of their scores.
+    Weighted(WeightedFusion),
+
+    /// Rescore the results of the prefetches with a formula, combining their scores and payload values.
+    Formula(FormulaQuery),
+
+    /// Rank the points by BM25 relevance of a full-text indexed payload field to the text.
+    Text(TextQuery),
+
+    /// Sample points from the collection, without scoring them against a query.
+    Sample(Sample),
+}
+
+/// Maximal Marginal Relevance (MMR) parameters.
+///
+/// A pool of candidates is fetched with a nearest neighbours search, then reordered so that each
+/// next point balances its relevance to the query against its similarity to the points before it.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct Mmr {
+    /// Trade-off between relevance and diversity, in the range [0, 1].
+    /// 0 only considers relevance, 1 only considers diversity. Default is 0.5.
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub diversity: Option<f32>,
+
+    /// Number of nearest neighbours to select the results from. Default is `limit` + `offset`.
+    #[validate(range(min = 1))]
+    pub candidates_limit: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -350,3 +419,191 @@ impl ContextPair {
         std::iter::once(&self.positive).chain(std::iter::once(&self.negative))
     }
 }
+
+/// Full-text query, ranking the points with BM25.
+///
+/// Document frequencies and average document length are computed in each shard separately,
+/// so the scores of different shards are only comparable if the points are evenly distributed
+/// among the shards.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TextQuery {
+    /// Payload key with a full-text index
+    pub key: JsonPath,
+
+    /// Text to search for
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct FormulaQuery {
+    /// Expression to compute the new score of each point
+    #[validate]
+    pub expression: Expression,
+
+    /// Values to use for the variables which have no value for a point.
+    /// Keys are payload keys, or `$score[<prefetch index>]`.
+    #[serde(default)]
+    pub defaults: HashMap<String, Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Expression {
+    /// Constant number
+    Constant(f32),
+    /// Payload key with a number, or `$score` for the score of the first prefetch,
+    /// or `$score[<prefetch index>]` for the score of another prefetch.
+    /// Points not found by a prefetch have a score of 0.
+    Variable(String),
+    /// Payload condition, evaluated as 1 if the point satisfies it, 0 otherwise
+    Condition(Box<Condition>),
+    GeoDistance(GeoDistance),
+    Datetime(DatetimeExpression),
+    DatetimeKey(DatetimeKeyExpression),
+    Mult(MultExpression),
+    Sum(SumExpression),
+    Neg(NegExpression),
+    Abs(AbsExpression),
+    Div(DivExpression),
+    Sqrt(SqrtExpression),
+    Pow(PowExpression),
+    Exp(ExpExpression),
+    Log10(Log10Expression),
+    Ln(LnExpression),
+    LinDecay(LinDecayExpression),
+    ExpDecay(ExpDecayExpression),
+    GaussDecay(GaussDecayExpression),
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GeoDistance {
+    /// Distance in meters between the origin and the geo point of the point
+    pub geo_distance: GeoDistanceParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GeoDistanceParams {
+    /// Geo point to measure the distance from
+    pub origin: GeoPoint,
+    /// Payload key with the geo point to measure the distance to
+    pub to: JsonPath,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DatetimeExpression {
+    /// Datetime in RFC 3339 format, or `now`. Evaluated as seconds since epoch.
+    pub datetime: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DatetimeKeyExpression {
+    /// Payload key with a datetime. Evaluated as seconds since epoch.
+    pub datetime_key: JsonPath,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MultExpression {
+    /// Multiply all these expressions
+    pub mult: Vec<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SumExpression {
+    /// Sum all these expressions
+    pub sum: Vec<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NegExpression {
+    /// Negate this expression
+    pub neg: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AbsExpression {
+    /// Absolute value of this expression
+    pub abs: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DivExpression {
+    pub div: DivParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DivParams {
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+    /// Result of the division when `right` is 0. If missing, such division fails the request.
+    pub by_zero_default: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SqrtExpression {
+    /// Square root of this expression
+    pub sqrt: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PowExpression {
+    pub pow: PowParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PowParams {
+    pub base: Box<Expression>,
+    pub exponent: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExpExpression {
+    /// Raise e to the power of this expression
+    pub exp: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Log10Expression {
+    /// Base 10 logarithm of this expression
+    pub log10: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LnExpression {
+    /// Natural logarithm of this expression
+    pub ln: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LinDecayExpression {
+    /// Linear decay, reaching 0 at `scale / (1 - midpoint)` away from the target
+    pub lin_decay: DecayParamsExpression,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExpDecayExpression {
+    /// Exponential decay
+    pub exp_decay: DecayParamsExpression,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GaussDecayExpression {
+    /// Gaussian decay
+    pub gauss_decay: DecayParamsExpression,
+}
+
+/// Decay of the distance between `x` and `target`: 1 when they are equal, `midpoint` when they are `scale` apart.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct DecayParamsExpression {
+    /// Value to decay
+    #[validate]
+    pub x: Box<Expression>,
+    /// Value at which the decay is 1. Default is 0.
+    #[validate]
+    pub target: Option<Box<Expression>>,
+    /// Distance from the target at which the decay is `midpoint`. Default is 1.
+    #[validate(range(min = 0.0))]
+    pub scale: Option<f32>,
+    /// Decay at `scale` distance from the target, between 0 and 1, exclusive. Default is 0.5.
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub midpoint: Option<f32>,
+}
@@ -3,7 +3,8 @@ use validator::{Validate, ValidationError};
 
 use super::schema::{BatchVectorStruct, Vector, VectorStruct};
 use super::{
-    ContextInput, Fusion, OrderByInterface, Query, QueryInterface, RecommendInput, VectorInput,
+    ContextInput, Expression, Fusion, OrderByInterface, Query, QueryInterface, RecommendInput,
+    VectorInput,
 };
 use crate::rest::NamedVectorStruct;
 
@@ -33,6 +34,7 @@ impl Validate for Vector {
             Vector::Dense(_) => Ok(()),
             Vector::Sparse(v) => v.validate(),
             Vector::MultiDense(m) => common::validation::validate_multi_vector(m),
+            Vector::Document(document) => document.validate(),
         }
     }
 }
@@ -64,7 +66,11 @@ impl Validate for Query {
             Query::Discover(discover) => discover.validate(),
             Query::Context(context) => context.validate(),
             Query::Fusion(fusion) => fusion.validate(),
+            Query::Rrf(rrf) => rrf.validate(),
+            Query::Weighted(weighted) => weighted.validate(),
             Query::OrderBy(order_by) => order_by.validate(),
+            Query::Formula(formula) => formula.validate(),
+            Query::Text(_) | Query::Sample(_) => Ok(()),
         }
     }
 }
@@ -76,6 +82,7 @@ impl Validate for VectorInput {
             VectorInput::DenseVector(_dense) => Ok(()),
             VectorInput::SparseVector(sparse) => sparse.validate(),
             VectorInput::MultiDenseVector(multi) => validate_multi_vector(multi),
+            VectorInput::Document(document) => document.validate(),
         }
     }
 }
@@ -117,7 +124,39 @@ impl Validate for ContextInput {
 impl Validate for Fusion {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
-            Fusion::Rrf => Ok(()),
+            Fusion::Rrf | Fusion::Dbsf => Ok(()),
+        }
+    }
+}
+
+impl Validate for Expression {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        match self {
+            Expression::Constant(_)
+            | Expression::Variable(_)
+            | Expression::GeoDistance(_)
+            | Expression::Datetime(_)
+            | Expression::DatetimeKey(_) => Ok(()),
+            Expression::Condition(condition) => condition.validate(),
+            Expression::Mult(mult) => common::validation::validate_iter(mult.mult.iter()),
+            Expression::Sum(sum) => common::validation::validate_iter(sum.sum.iter()),
+            Expression::Neg(neg) => neg.neg.validate(),
+            Expression::Abs(abs) => abs.abs.validate(),
+            Expression::Div(div) => {
+                div.div.left.validate()?;
+                div.div.right.validate()
+            }
+            Expression::Sqrt(sqrt) => sqrt.sqrt.validate(),
+            Expression::Pow(pow) => {
+                pow.pow.base.validate()?;
+                pow.pow.exponent.validate()
+            }
+            Expression::Exp(exp) => exp.exp.validate(),
+            Expression::Log10(log10) => log10.log10.validate(),
+            Expression::Ln(ln) => ln.ln.validate(),
+            Expression::LinDecay(decay) => decay.lin_decay.validate(),
+            Expression::ExpDecay(decay) => decay.exp_decay.validate(),
+            Expression::GaussDecay(decay) => decay.gauss_decay.validate(),
         }
     }
 }
@@ -7,6 +7,7 @@ use segment::types::QuantizationConfig;
 use semver::Version;
 
 use super::Collection;
+use crate::config::StrictModeConfig;
 use crate::operations::config_diff::*;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
@@ -23,7 +24,7 @@ lazy_static! {
 
 impl Collection {
     /// Updates collection params:
-    /// Saves new params on disk
+    /// Saves new params on disk and creates the expiration index, if time-to-live is enabled
     ///
     /// After this, `recreate_optimizers_blocking` must be called to create new optimizers using
     /// the updated configuration.
@@ -36,6 +37,7 @@ impl Collection {
             config.params = params_diff.update(&config.params)?;
         }
         self.collection_config.read().await.save(&self.path)?;
+        self.ensure_expiration_index().await?;
         Ok(())
     }
 
@@ -162,6 +164,26 @@ impl Collection {
         Ok(())
     }
 
+    /// Updates strict mode config:
+    /// Saves new params on disk
+    ///
+    /// Fields set in the given config replace the current ones, the others are left unchanged.
+    pub async fn update_strict_mode_config(
+        &self,
+        strict_mode_diff: StrictModeConfig,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            let strict_mode_config = match &config.strict_mode_config {
+                Some(current) => strict_mode_diff.update(current)?,
+                None => strict_mode_diff,
+            };
+            config.strict_mode_config = Some(strict_mode_config);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
     /// Handle replica changes
     ///
     /// add and remove replicas from replica set
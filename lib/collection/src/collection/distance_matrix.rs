@@ -0,0 +1,234 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use segment::data_types::vectors::{NamedVectorStruct, Vector};
+use segment::types::{
+    Condition, Filter, HasIdCondition, PointIdType, ScoredPoint, WithPayloadInterface, WithVector,
+};
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionError, CollectionResult, CollectionSearchMatrixRequest, CoreSearchRequest,
+    CoreSearchRequestBatch, SearchMatrixOffsetsResponse, SearchMatrixPair,
+    SearchMatrixPairsResponse,
+};
+use crate::operations::universal_query::collection_query::{CollectionQueryRequest, Query};
+use crate::operations::universal_query::shard_query::Sample;
+
+#[derive(Debug, Default)]
+pub struct CollectionSearchMatrixResponse {
+    /// Sampled point ids, sorted
+    pub sample_ids: Vec<PointIdType>,
+    /// Nearest neighbours of each sampled point, in the same order as `sample_ids`
+    pub nearests: Vec<Vec<ScoredPoint>>,
+}
+
+impl From<CollectionSearchMatrixResponse> for SearchMatrixPairsResponse {
+    fn from(response: CollectionSearchMatrixResponse) -> Self {
+        let CollectionSearchMatrixResponse {
+            sample_ids,
+            nearests,
+        } = response;
+
+        let pairs = sample_ids
+            .into_iter()
+            .zip(nearests)
+            .flat_map(|(a, nearest)| {
+                nearest.into_iter().map(move |scored| SearchMatrixPair {
+                    a,
+                    b: scored.id,
+                    score: scored.score,
+                })
+            })
+            .collect();
+
+        SearchMatrixPairsResponse { pairs }
+    }
+}
+
+impl From<CollectionSearchMatrixResponse> for SearchMatrixOffsetsResponse {
+    fn from(response: CollectionSearchMatrixResponse) -> Self {
+        let CollectionSearchMatrixResponse {
+            sample_ids,
+            nearests,
+        } = response;
+
+        let total = nearests.iter().map(Vec::len).sum();
+        let mut offsets_row = Vec::with_capacity(total);
+        let mut offsets_col = Vec::with_capacity(total);
+        let mut scores = Vec::with_capacity(total);
+
+        for (row, nearest) in nearests.into_iter().enumerate() {
+            for scored in nearest {
+                // Neighbours are searched among the sample only, so the id is always found
+                let Ok(col) = sample_ids.binary_search(&scored.id) else {
+                    continue;
+                };
+                offsets_row.push(row as u64);
+                offsets_col.push(col as u64);
+                scores.push(scored.score);
+            }
+        }
+
+        SearchMatrixOffsetsResponse {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids: sample_ids,
+        }
+    }
+}
+
+impl Collection {
+    /// Compute a sparse distance matrix for a random sample of points.
+    ///
+    /// Samples up to `sample_size` points which satisfy the filter and have the `using` vector,
+    /// then searches the `limit_per_sample` nearest neighbours of each sampled point among
+    /// the other sampled points.
+    pub async fn search_points_matrix(
+        &self,
+        request: CollectionSearchMatrixRequest,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<CollectionSearchMatrixResponse> {
+        let start = Instant::now();
+
+        let CollectionSearchMatrixRequest {
+            sample_size,
+            limit_per_sample,
+            filter,
+            using,
+        } = request;
+
+        if sample_size < 2 || limit_per_sample == 0 {
+            return Ok(CollectionSearchMatrixResponse::default());
+        }
+
+        // Fail early on unknown vector names
+        self.collection_config
+            .read()
+            .await
+            .params
+            .get_distance(&using)?;
+
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let mut sampled_vectors = self
+            .sample_vectors(
+                sample_size,
+                filter,
+                &using,
+                read_consistency,
+                &shard_selection,
+                timeout,
+            )
+            .await?;
+
+        if sampled_vectors.len() < 2 {
+            return Ok(CollectionSearchMatrixResponse::default());
+        }
+
+        // Sort by id for a deterministic output
+        sampled_vectors.sort_unstable_by_key(|(id, _)| *id);
+
+        let sample_ids: Vec<_> = sampled_vectors.iter().map(|(id, _)| *id).collect();
+
+        // All searches share the same set of sampled ids, instead of copying it per search
+        let sample_set: Arc<HashSet<_>> = Arc::new(sample_ids.iter().copied().collect());
+
+        let searches = sampled_vectors
+            .into_iter()
+            .map(|(id, vector)| CoreSearchRequest {
+                query: QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+                    vector,
+                    using.clone(),
+                )),
+                filter: Some(Filter {
+                    should: None,
+                    min_should: None,
+                    must: Some(vec![Condition::HasId(HasIdCondition::from(
+                        sample_set.clone(),
+                    ))]),
+                    must_not: Some(vec![Condition::HasId(HasIdCondition::from(HashSet::from(
+                        [id],
+                    )))]),
+                }),
+                params: None,
+                limit: limit_per_sample,
+                offset: 0,
+                with_payload: None,
+                with_vector: None,
+                score_threshold: None,
+            })
+            .collect();
+
+        let nearests = self
+            .core_search_batch(
+                CoreSearchRequestBatch { searches },
+                read_consistency,
+                shard_selection,
+                Some(timeout.saturating_sub(start.elapsed())),
+            )
+            .await?;
+
+        Ok(CollectionSearchMatrixResponse {
+            sample_ids,
+            nearests,
+        })
+    }
+
+    /// Select up to `sample_size` random points which satisfy the filter, with their `using`
+    /// vector.
+    ///
+    /// Points are sampled uniformly by the random sampling query of the shards, so only the
+    /// sample itself is read. Points without the vector can't take part in the matrix, and are
+    /// skipped.
+    async fn sample_vectors(
+        &self,
+        sample_size: usize,
+        filter: Option<Filter>,
+        using: &str,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Duration,
+    ) -> CollectionResult<Vec<(PointIdType, Vector)>> {
+        let request = CollectionQueryRequest {
+            prefetch: vec![],
+            query: Some(Query::Sample(Sample::Random)),
+            using: using.to_string(),
+            filter,
+            score_threshold: None,
+            limit: sample_size,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Selector(vec![using.to_string()]),
+            with_payload: WithPayloadInterface::Bool(false),
+            mmr: None,
+        };
+
+        // Shards don't limit the duration of the query themselves
+        let sample = self.query(request, read_consistency, shard_selection, Some(timeout));
+        let sampled_points = tokio::time::timeout(timeout, sample).await.map_err(|_| {
+            log::debug!(
+                "Search matrix sampling timeout reached: {} seconds",
+                timeout.as_secs()
+            );
+            CollectionError::timeout(timeout.as_secs() as usize, "Search matrix sampling")
+        })??;
+
+        let sampled_vectors = sampled_points
+            .into_iter()
+            .filter_map(|point| {
+                let vector = point.vector?.get(using)?.to_owned();
+                Some((point.id, vector))
+            })
+            .collect();
+
+        Ok(sampled_vectors)
+    }
+}
@@ -0,0 +1,71 @@
+use segment::types::{PayloadFieldSchema, PayloadSchemaType};
+
+use super::Collection;
+use crate::operations::point_ops::{PointOperations, WriteOrdering};
+use crate::operations::types::CollectionResult;
+use crate::operations::{expiration, CollectionUpdateOperations};
+
+impl Collection {
+    /// Create the payload index on the expiration time, if the collection has a time-to-live
+    ///
+    /// The index serves filters, which hide expired points from reads and select them for deletion.
+    pub async fn ensure_expiration_index(&self) -> CollectionResult<()> {
+        if self.collection_config.read().await.params.ttl_sec.is_none() {
+            return Ok(());
+        }
+
+        let field_name = expiration::expires_at_path();
+        let is_indexed = self
+            .payload_index_schema
+            .read()
+            .schema
+            .contains_key(&field_name);
+        if is_indexed {
+            return Ok(());
+        }
+
+        // Float index also serves expiration times, which are set explicitly as floats
+        self.create_payload_index(
+            field_name,
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Float),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delete expired points, if the collection has a time-to-live
+    ///
+    /// Deletion is issued by the update leader of each shard and goes through the regular update
+    /// path of the replica set, so that it is ordered with other updates and applied by all
+    /// replicas. Returns the number of shards, deletion was issued for.
+    pub async fn delete_expired_points(&self, wait: bool) -> CollectionResult<usize> {
+        if self.collection_config.read().await.params.ttl_sec.is_none() {
+            return Ok(0);
+        }
+
+        let now = expiration::now_timestamp();
+        let operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::DeletePointsByFilter(expiration::expired_filter(now)),
+        );
+
+        let _update_lock = self.updates_lock.read().await;
+        let shards_holder = self.shards_holder.read().await;
+
+        let mut deleted_shards = 0;
+        for replica_set in shards_holder.all_shards() {
+            if !replica_set.is_update_leader(WriteOrdering::Medium)
+                || !replica_set.local_has_expired_points(now).await
+            {
+                continue;
+            }
+
+            replica_set
+                .update_with_consistency(operation.clone(), wait, WriteOrdering::Medium)
+                .await?;
+            deleted_shards += 1;
+        }
+
+        Ok(deleted_shards)
+    }
+}
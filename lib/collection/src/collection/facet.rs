@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::TryStreamExt as _;
+use segment::data_types::facets::{FacetParams, FacetResponse};
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::CollectionResult;
+
+impl Collection {
+    /// Count points per value of the facet key in all selected shards.
+    ///
+    /// Shards return counts of all their values, which are summed up here before selecting
+    /// the `limit` most frequent values.
+    pub async fn facet(
+        &self,
+        request: FacetParams,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let limit = request.limit;
+
+        let shards_holder = self.shards_holder.read().await;
+        let shards = shards_holder.select_shards(&shard_selection)?;
+
+        let request = Arc::new(request);
+        let mut requests: futures::stream::FuturesUnordered<_> = shards
+            .into_iter()
+            // `facet` requests received through internal gRPC *always* have `shard_selection`
+            .map(|(shard, _shard_key)| {
+                shard.facet(
+                    request.clone(),
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                    timeout,
+                )
+            })
+            .collect();
+
+        let mut counts = HashMap::new();
+
+        while let Some(response) = requests.try_next().await? {
+            for (value, count) in response.into_counts() {
+                *counts.entry(value).or_insert(0) += count;
+            }
+        }
+
+        Ok(FacetResponse::top_hits(counts, limit))
+    }
+}
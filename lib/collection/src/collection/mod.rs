@@ -1,13 +1,18 @@
 mod collection_ops;
+pub mod distance_matrix;
+mod expiration;
+mod facet;
 pub mod payload_index_schema;
 mod point_ops;
 pub mod query;
+mod recall;
 mod resharding;
 mod search;
 mod shard_transfer;
 mod sharding_keys;
 mod snapshots;
 mod state_management;
+mod strict_mode;
 
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
@@ -30,6 +35,7 @@ use crate::config::CollectionConfig;
 use crate::operations::config_diff::{DiffConfig, OptimizersConfigDiff};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
+use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::OptimizersConfig;
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
@@ -465,6 +471,25 @@ impl Collection {
         replica_set.shard_recovery_point().await
     }
 
+    /// Read up to `limit` operations from the WAL of the local shard, starting at operation `from`
+    pub async fn read_wal_operations(
+        &self,
+        shard_id: ShardId,
+        from: Option<u64>,
+        limit: usize,
+    ) -> CollectionResult<Vec<(u64, CollectionUpdateOperations)>> {
+        let shard_holder_read = self.shards_holder.read().await;
+
+        let shard = shard_holder_read.get_shard(&shard_id);
+        let Some(replica_set) = shard else {
+            return Err(CollectionError::NotFound {
+                what: format!("Shard {shard_id}"),
+            });
+        };
+
+        replica_set.read_wal_operations(from, limit).await
+    }
+
     pub async fn update_shard_cutoff_point(
         &self,
         shard_id: ShardId,
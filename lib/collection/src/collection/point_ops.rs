@@ -5,7 +5,7 @@ use futures::stream::FuturesUnordered;
 use futures::{future, StreamExt as _, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools;
 use segment::data_types::order_by::{Direction, OrderBy};
-use segment::types::{ShardKey, WithPayload, WithPayloadInterface};
+use segment::types::{PayloadSchemaType, ShardKey, WithPayload, WithPayloadInterface};
 use validator::Validate as _;
 
 use super::Collection;
@@ -13,7 +13,7 @@ use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
-use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use crate::operations::{expiration, CollectionUpdateOperations, OperationWithClockTag};
 use crate::shards::shard::ShardId;
 
 impl Collection {
@@ -131,13 +131,19 @@ impl Collection {
     /// This method is cancel safe.
     pub async fn update_from_client(
         &self,
-        operation: CollectionUpdateOperations,
+        mut operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
         shard_keys_selection: Option<ShardKey>,
     ) -> CollectionResult<UpdateResult> {
         operation.validate()?;
 
+        // Assign expiration time once, so that all replicas store the same payload
+        if let Some(ttl_sec) = self.collection_config.read().await.params.ttl_sec {
+            expiration::check_keeps_expiration(&operation)?;
+            expiration::set_expiration(&mut operation, ttl_sec, expiration::now_timestamp());
+        }
+
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
 
@@ -188,7 +194,12 @@ impl Collection {
             }
         } else {
             // At least one result is always present.
-            results.pop().unwrap()
+            let mut result = results.pop().unwrap()?;
+            for other in results {
+                // Each shard reports the counts of its own points
+                result.merge_counts(&other?);
+            }
+            Ok(result)
         }
     }
 
@@ -227,15 +238,26 @@ impl Collection {
 
         // Handle case of order_by
         if let Some(order_by) = &order_by {
-            // Validate we have a range index for the order_by key
-            let has_range_index_for_order_by_field = self
+            let order_by_field_schema = self
                 .payload_index_schema
                 .read()
                 .schema
                 .get(&order_by.key)
-                .is_some_and(|field| field.has_range_index());
-
-            if !has_range_index_for_order_by_field {
+                .cloned();
+
+            if order_by.is_geo_distance() {
+                // Validate we have a geo index for the order_by key
+                let has_geo_index_for_order_by_field = order_by_field_schema
+                    .is_some_and(|field| field.kind() == PayloadSchemaType::Geo);
+
+                if !has_geo_index_for_order_by_field {
+                    return Err(CollectionError::bad_request(format!(
+                        "No geo index for `order_by` key: {}. Please create one to order by geo distance, see https://qdrant.tech/documentation/concepts/indexing/#payload-index.",
+                        &order_by.key
+                    )));
+                }
+            } else if !order_by_field_schema.is_some_and(|field| field.has_range_index()) {
+                // Validate we have a range index for the order_by key
                 return Err(CollectionError::bad_request(format!(
                     "No range index for `order_by` key: {}. Please create one to use `order_by`. Integer, float, and datetime payloads can have range indexes, see https://qdrant.tech/documentation/concepts/indexing/#payload-index.",
                     &order_by.key
@@ -1,23 +1,34 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::{future, TryFutureExt};
 use itertools::{Either, Itertools};
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
-use segment::types::{Order, ScoredPoint};
+use segment::common::maximal_marginal_relevance::maximal_marginal_relevance;
+use segment::data_types::vectors::{DenseVector, Named, VectorRef, VectorStruct};
+use segment::types::{Distance, Order, ScoredPoint, WithVector};
 use segment::utils::scored_point_ties::ScoredPointTies;
 use tokio::time::Instant;
 
 use super::Collection;
 use crate::common::fetch_vectors::resolve_referenced_vectors_batch;
+use crate::common::stopping_guard::StoppingGuard;
 use crate::common::transpose_iterator::transposed_iter;
 use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{CollectionError, CollectionResult};
-use crate::operations::universal_query::collection_query::CollectionQueryRequest;
+use crate::operations::universal_query::collection_query::{CollectionQueryRequest, Mmr};
 use crate::operations::universal_query::shard_query::{
-    Fusion, ScoringQuery, ShardQueryRequest, ShardQueryResponse,
+    ScoringQuery, ShardQueryRequest, ShardQueryResponse,
 };
 
+/// Query vector of a `nearest` query with MMR, resolved before fetching the candidates
+struct MmrQuery {
+    using: String,
+    vector: DenseVector,
+    distance: Distance,
+}
+
 struct IntermediateQueryInfo<'a> {
     scoring_query: Option<&'a ScoringQuery>,
     /// Limit + offset
@@ -31,6 +42,7 @@ impl Collection {
         request: Arc<ShardQueryRequest>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         // query all shards concurrently
         let shard_holder = self.shards_holder.read().await;
@@ -42,6 +54,7 @@ impl Collection {
                     Arc::clone(&request),
                     read_consistency,
                     shard_selection.is_shard_id(),
+                    timeout,
                 )
                 .and_then(move |mut records| async move {
                     if shard_key.is_none() {
@@ -66,8 +79,10 @@ impl Collection {
         request: CollectionQueryRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
         let instant = Instant::now();
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
 
         // Turn ids into vectors, if necessary
         let ids_to_vectors = resolve_referenced_vectors_batch(
@@ -78,21 +93,41 @@ impl Collection {
         )
         .await?;
 
+        let mmr = request.mmr;
         let request = Arc::new(request.try_into_shard_request(&ids_to_vectors)?);
 
+        // With MMR, shards return a pool of candidates with their vectors instead
+        let (shard_request, mmr) = match mmr {
+            Some(mmr) => {
+                let mmr_query = self.mmr_query(&request).await?;
+                let shard_request = mmr_candidates_request(&request, &mmr, &mmr_query.using);
+                (Arc::new(shard_request), Some((mmr, mmr_query)))
+            }
+            None => (Arc::clone(&request), None),
+        };
+
         let all_shards_results = self
-            .query_shards_concurrently(request.clone(), read_consistency, shard_selection)
+            .query_shards_concurrently(
+                shard_request.clone(),
+                read_consistency,
+                shard_selection,
+                Some(timeout.saturating_sub(instant.elapsed())),
+            )
             .await?;
 
         let mut merged_intermediates = self
-            .merge_intermediate_results_from_shards(request.as_ref(), all_shards_results)
+            .merge_intermediate_results_from_shards(shard_request.as_ref(), all_shards_results)
             .await?;
 
         let result = if let Some(ScoringQuery::Fusion(fusion)) = &request.query {
             // If the root query is a Fusion, the returned results correspond to each the prefetches.
-            match fusion {
-                Fusion::Rrf => rrf_scoring(merged_intermediates),
-            }
+            let collection_params = self.collection_config.read().await.params.clone();
+            let orders = request
+                .prefetches
+                .iter()
+                .map(|prefetch| ScoringQuery::order(prefetch.query.as_ref(), &collection_params))
+                .collect::<CollectionResult<Vec<_>>>()?;
+            fusion.fuse(merged_intermediates, &orders)
         } else {
             // Otherwise, it will be a list with a single list of scored points.
             debug_assert_eq!(merged_intermediates.len(), 1);
@@ -103,6 +138,15 @@ impl Collection {
             })?
         };
 
+        let result = match mmr {
+            Some((mmr, mmr_query)) => {
+                let timeout = timeout.saturating_sub(instant.elapsed());
+                self.diversify_with_mmr(&request, mmr, mmr_query, result, timeout)
+                    .await?
+            }
+            None => result,
+        };
+
         let result: Vec<_> = result
             .into_iter()
             .skip(request.offset)
@@ -115,6 +159,106 @@ impl Collection {
         Ok(result)
     }
 
+    /// Resolves the query vector and the distance for MMR.
+    ///
+    /// Called before the candidates are fetched, so that unsupported queries are rejected early.
+    async fn mmr_query(&self, request: &ShardQueryRequest) -> CollectionResult<MmrQuery> {
+        let Some(ScoringQuery::Vector(QueryEnum::Nearest(query))) = &request.query else {
+            return Err(CollectionError::bad_request(
+                "MMR can only be applied to a `nearest` query",
+            ));
+        };
+
+        let using = query.get_name().to_string();
+        let VectorRef::Dense(query_vector) = query.get_vector() else {
+            return Err(CollectionError::bad_request(
+                "MMR is only supported for dense vectors",
+            ));
+        };
+
+        let collection_config = self.collection_config.read().await;
+        let is_dense = collection_config
+            .params
+            .vectors
+            .get_params(&using)
+            .is_some_and(|params| params.multivec_config.is_none());
+        if !is_dense {
+            return Err(CollectionError::bad_request(
+                "MMR is only supported for dense vectors",
+            ));
+        }
+        let distance = collection_config.params.get_distance(&using)?;
+
+        Ok(MmrQuery {
+            using,
+            vector: query_vector.to_vec(),
+            distance,
+        })
+    }
+
+    /// Reorders the candidates with Maximal Marginal Relevance, keeping `offset` + `limit` of them.
+    ///
+    /// The computation is stopped if it takes longer than `timeout`.
+    async fn diversify_with_mmr(
+        &self,
+        request: &ShardQueryRequest,
+        mmr: Mmr,
+        mmr_query: MmrQuery,
+        candidates: Vec<ScoredPoint>,
+        timeout: Duration,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let MmrQuery {
+            using,
+            vector: query_vector,
+            distance,
+        } = mmr_query;
+
+        let candidates = candidates
+            .into_iter()
+            .map(|mut point| {
+                let vector = match point.vector.as_ref().and_then(|vector| vector.get(&using)) {
+                    Some(VectorRef::Dense(vector)) => vector.to_vec(),
+                    Some(_) => {
+                        return Err(CollectionError::bad_request(
+                            "MMR is only supported for dense vectors",
+                        ))
+                    }
+                    None => {
+                        return Err(CollectionError::service_error(format!(
+                            "Vector {using} of point {} is missing for MMR",
+                            point.id,
+                        )))
+                    }
+                };
+                strip_unrequested_vector(&mut point.vector, &request.with_vector, &using);
+                Ok((point, vector))
+            })
+            .collect::<CollectionResult<Vec<_>>>()?;
+
+        let take = request.offset + request.limit;
+
+        let is_stopped_guard = StoppingGuard::new();
+        let is_stopped = is_stopped_guard.get_is_stopped();
+        let task = self.search_runtime.spawn_blocking(move || {
+            maximal_marginal_relevance(
+                query_vector,
+                candidates,
+                distance,
+                mmr.diversity,
+                take,
+                &is_stopped,
+            )
+        });
+
+        let diversified = tokio::time::timeout(timeout, task).await.map_err(|_| {
+            log::debug!("MMR timeout reached: {} seconds", timeout.as_secs());
+            // StoppingGuard takes care of setting is_stopped to true
+            CollectionError::timeout(timeout.as_secs() as usize, "MMR")
+        })???;
+
+        Ok(diversified)
+    }
+
     /// To be called on the remote instance. Only used for the internal service.
     ///
     /// If the root query is a Fusion, the returned results correspond to each the prefetches.
@@ -130,7 +274,12 @@ impl Collection {
         // Results from all shards
         // Shape: [num_shards, num_internal_queries, num_scored_points]
         let all_shards_results = self
-            .query_shards_concurrently(Arc::clone(&request), read_consistency, shard_selection)
+            .query_shards_concurrently(
+                Arc::clone(&request),
+                read_consistency,
+                shard_selection,
+                None,
+            )
             .await?;
 
         let merged = self
@@ -206,6 +355,52 @@ impl Collection {
     }
 }
 
+/// Request for the pool of MMR candidates, which must include the vectors used by the query.
+fn mmr_candidates_request(
+    request: &ShardQueryRequest,
+    mmr: &Mmr,
+    using: &str,
+) -> ShardQueryRequest {
+    let using = using.to_string();
+    let with_vector = match &request.with_vector {
+        WithVector::Bool(true) => WithVector::Bool(true),
+        WithVector::Bool(false) => WithVector::Selector(vec![using]),
+        WithVector::Selector(names) if names.contains(&using) => {
+            WithVector::Selector(names.clone())
+        }
+        WithVector::Selector(names) => {
+            WithVector::Selector(names.iter().cloned().chain([using]).collect())
+        }
+    };
+
+    ShardQueryRequest {
+        limit: mmr.candidates_limit(request.limit, request.offset),
+        offset: 0,
+        with_vector,
+        ..request.clone()
+    }
+}
+
+/// Removes the vector which was only fetched for MMR, if it was not requested.
+fn strip_unrequested_vector(
+    vector: &mut Option<VectorStruct>,
+    with_vector: &WithVector,
+    using: &str,
+) {
+    match with_vector {
+        WithVector::Bool(true) => {}
+        WithVector::Bool(false) => *vector = None,
+        WithVector::Selector(names) if names.iter().any(|name| name == using) => {}
+        WithVector::Selector(_) => match vector {
+            Some(VectorStruct::Multi(vectors)) => {
+                vectors.remove(using);
+            }
+            Some(VectorStruct::Single(_)) => *vector = None,
+            None => {}
+        },
+    }
+}
+
 /// Returns a list of the query that corresponds to each of the results in each shard.
 ///
 /// Example: `[info1, info2, info3]` corresponds to `[result1, result2, result3]` of each shard
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::Collection;
+use crate::operations::types::{
+    CollectionError, CollectionRecallEstimation, CollectionResult, RecallEstimationRequest,
+    ShardRecallEstimation,
+};
+
+impl Collection {
+    /// Estimate recall of the approximate search in all local shards of the collection.
+    ///
+    /// Shards are estimated one by one, to limit the load of exact searches on the node.
+    /// The shards holder is locked for one shard at a time, so that shard transfers and
+    /// resharding are not blocked for the whole estimation.
+    ///
+    /// If `timeout` is given, it limits the estimation of the whole collection, otherwise each
+    /// shard is limited by the default search timeout.
+    pub async fn estimate_recall(
+        &self,
+        request: RecallEstimationRequest,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<CollectionRecallEstimation> {
+        let request = Arc::new(request);
+        let start = Instant::now();
+
+        let mut shard_ids: Vec<_> = self
+            .shards_holder
+            .read()
+            .await
+            .get_shards()
+            .map(|(shard_id, _)| *shard_id)
+            .collect();
+        shard_ids.sort_unstable();
+
+        let mut shards = Vec::new();
+        for shard_id in shard_ids {
+            let shard_timeout = match timeout {
+                Some(timeout) => {
+                    let remaining = timeout.saturating_sub(start.elapsed());
+                    if remaining.is_zero() {
+                        return Err(CollectionError::timeout(
+                            timeout.as_secs() as usize,
+                            "Recall estimation",
+                        ));
+                    }
+                    Some(remaining)
+                }
+                None => None,
+            };
+
+            let shards_holder = self.shards_holder.read().await;
+            // Shard may have been removed in the meantime
+            let Some(replica_set) = shards_holder.get_shard(&shard_id) else {
+                continue;
+            };
+            let Some(segments) = replica_set
+                .estimate_local_recall(request.clone(), shard_timeout)
+                .await?
+            else {
+                continue;
+            };
+            shards.push(ShardRecallEstimation { shard_id, segments });
+        }
+
+        Ok(CollectionRecallEstimation { shards })
+    }
+}
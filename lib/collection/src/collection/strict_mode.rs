@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use super::Collection;
+use crate::config::StrictModeConfig;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult, CountRequestInternal};
+use crate::operations::verification::StrictModeVerification;
+use crate::operations::CollectionUpdateOperations;
+
+impl Collection {
+    /// Strict mode config of the collection, if strict mode is enabled
+    pub async fn strict_mode_config(&self) -> Option<StrictModeConfig> {
+        self.collection_config
+            .read()
+            .await
+            .strict_mode_config
+            .clone()
+            .filter(StrictModeConfig::is_enabled)
+    }
+
+    /// Reject the read request if it exceeds the limits of the strict mode
+    pub async fn check_strict_mode(
+        &self,
+        request: &impl StrictModeVerification,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<()> {
+        self.check_strict_mode_batch([request], timeout).await
+    }
+
+    /// Reject the batch of read requests if any of them exceeds the limits of the strict mode
+    pub async fn check_strict_mode_batch<'a, R: StrictModeVerification + 'a>(
+        &self,
+        requests: impl IntoIterator<Item = &'a R>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<()> {
+        let Some(strict_mode_config) = self.strict_mode_config().await else {
+            return Ok(());
+        };
+
+        let requests: Vec<_> = requests.into_iter().collect();
+
+        check_timeout(&strict_mode_config, timeout)?;
+
+        if let Some(max_batch_size) = strict_mode_config.search_max_batchsize {
+            if requests.len() > max_batch_size {
+                return Err(CollectionError::strict_mode(
+                    format!("Batch size exceeded {} > {max_batch_size}", requests.len()),
+                    format!("Split the batch into requests of at most {max_batch_size} searches."),
+                ));
+            }
+        }
+
+        let payload_schema = self.payload_index_schema.read().schema.clone();
+        for request in requests {
+            request.check_strict_mode(&strict_mode_config, &payload_schema)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reject the update if it exceeds the limits of the strict mode
+    pub async fn check_strict_mode_update(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        let Some(strict_mode_config) = self.strict_mode_config().await else {
+            return Ok(());
+        };
+
+        if let (Some(max_batch_size), Some(batch_size)) = (
+            strict_mode_config.upsert_max_batchsize,
+            operation.upsert_batch_size(),
+        ) {
+            if batch_size > max_batch_size {
+                return Err(CollectionError::strict_mode(
+                    format!("Update batch size exceeded {batch_size} > {max_batch_size}"),
+                    format!("Split the update into batches of at most {max_batch_size} points."),
+                ));
+            }
+        }
+
+        let payload_schema = self.payload_index_schema.read().schema.clone();
+        operation.check_strict_mode(&strict_mode_config, &payload_schema)?;
+
+        if let Some(max_points_count) = strict_mode_config.max_points_count {
+            if operation.inserts_points() {
+                let count_request = CountRequestInternal {
+                    filter: None,
+                    exact: false,
+                };
+                let points_count = self
+                    .count(count_request, None, &ShardSelectorInternal::All)
+                    .await?
+                    .count;
+
+                if points_count >= max_points_count {
+                    return Err(CollectionError::strict_mode(
+                        format!("Max points count reached {points_count} >= {max_points_count}"),
+                        "Delete some points or increase the \"max_points_count\" limit.",
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_timeout(
+    strict_mode_config: &StrictModeConfig,
+    timeout: Option<Duration>,
+) -> CollectionResult<()> {
+    if let (Some(max_timeout), Some(timeout)) = (strict_mode_config.max_timeout, timeout) {
+        if timeout > Duration::from_secs(max_timeout as u64) {
+            return Err(CollectionError::strict_mode(
+                format!("Timeout exceeded {timeout:?} > {max_timeout}s"),
+                format!("Reduce the \"timeout\" parameter to or below {max_timeout}."),
+            ));
+        }
+    }
+
+    Ok(())
+}
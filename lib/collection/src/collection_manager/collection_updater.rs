@@ -1,7 +1,10 @@
+use std::sync::atomic::AtomicBool;
+
 use parking_lot::RwLock;
+use segment::common::operation_error::OperationError;
 use segment::types::SeqNumberType;
 
-use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
 use crate::collection_manager::segments_updater::*;
 use crate::operations::types::CollectionResult;
 use crate::operations::CollectionUpdateOperations;
@@ -42,10 +45,42 @@ impl CollectionUpdater {
         }
     }
 
+    /// Link updated points into appendable HNSW graphs of the segments
+    ///
+    /// Segments are only read locked here, so searches are not blocked while points are linked.
+    /// If `stopped` is set, remaining points are left pending until the next update.
+    fn link_pending_points(segments: &RwLock<SegmentHolder>, stopped: &AtomicBool) {
+        let appendable_segments: Vec<_> = {
+            let segments = segments.read();
+            segments
+                .appendable_segments_ids()
+                .into_iter()
+                .filter_map(|segment_id| segments.get(segment_id).cloned())
+                .collect()
+        };
+
+        for segment in appendable_segments {
+            let result = match segment {
+                LockedSegment::Original(segment) => segment.read().link_pending_points(stopped),
+                // Points of proxied segments are searched one by one until optimization is done
+                LockedSegment::Proxy(_) => Ok(()),
+            };
+            match result {
+                Ok(()) => {}
+                Err(OperationError::Cancelled { .. }) => {
+                    log::debug!("Linking points into HNSW graph cancelled");
+                    break;
+                }
+                Err(err) => log::error!("Failed to link points into HNSW graph: {err}"),
+            }
+        }
+    }
+
     pub fn update(
         segments: &RwLock<SegmentHolder>,
         op_num: SeqNumberType,
         operation: CollectionUpdateOperations,
+        stopped: &AtomicBool,
     ) -> CollectionResult<usize> {
         // Allow only one update at a time, ensure no data races between segments.
         // let _lock = self.update_lock.lock().unwrap();
@@ -66,6 +101,10 @@ impl CollectionUpdater {
 
         CollectionUpdater::handle_update_result(segments, op_num, &operation_result);
 
+        if operation_result.is_ok() {
+            CollectionUpdater::link_pending_points(segments, stopped);
+        }
+
         operation_result
     }
 }
@@ -1,19 +1,24 @@
 use std::cmp;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use bitvec::prelude::BitVec;
 use common::types::{PointOffsetType, TelemetryDetail};
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
+use segment::data_types::facets::{FacetParams, FacetValue};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::order_by::OrderValue;
 use segment::data_types::query_context::{QueryContext, SegmentQueryContext};
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 use segment::data_types::vectors::{QueryVector, Vector};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::field_index::CardinalityEstimation;
+use segment::index::rescore_formula::parsed_formula::ParsedFormula;
 use segment::json_path::JsonPath;
+use segment::spaces::tools::peek_top_largest_iterable;
 use segment::telemetry::SegmentTelemetry;
 use segment::types::{
     Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
@@ -662,6 +667,185 @@ impl SegmentEntry for ProxySegment {
         Ok(read_points)
     }
 
+    fn facet(
+        &self,
+        request: &FacetParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        let deleted_points = self.deleted_points.read();
+        let mut counts = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(request, is_stopped)?
+        } else {
+            let wrapped_filter = self
+                .add_deleted_points_condition_to_filter(request.filter.as_ref(), &deleted_points);
+            let wrapped_request = FacetParams {
+                filter: Some(wrapped_filter),
+                ..request.clone()
+            };
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(&wrapped_request, is_stopped)?
+        };
+        let write_segment_counts = self.write_segment.get().read().facet(request, is_stopped)?;
+        for (value, count) in write_segment_counts {
+            *counts.entry(value).or_insert(0) += count;
+        }
+        Ok(counts)
+    }
+
+    fn rescore_with_formula(
+        &self,
+        formula: &ParsedFormula,
+        prefetches_results: &[Vec<ScoredPoint>],
+        filter: Option<&Filter>,
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let deleted_points = self.deleted_points.read();
+        let mut scored_points = if deleted_points.is_empty() {
+            self.wrapped_segment.get().read().rescore_with_formula(
+                formula,
+                prefetches_results,
+                filter,
+                limit,
+                is_stopped,
+            )?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().rescore_with_formula(
+                formula,
+                prefetches_results,
+                Some(&wrapped_filter),
+                limit,
+                is_stopped,
+            )?
+        };
+        let mut write_segment_points = self.write_segment.get().read().rescore_with_formula(
+            formula,
+            prefetches_results,
+            filter,
+            limit,
+            is_stopped,
+        )?;
+        scored_points.append(&mut write_segment_points);
+        Ok(peek_top_largest_iterable(scored_points, limit))
+    }
+
+    fn text_query_statistics(&self, query: &TextQuery) -> OperationResult<TextQueryStatistics> {
+        let mut statistics = {
+            let wrapped_segment = self.wrapped_segment.get();
+            let wrapped_segment_guard = wrapped_segment.read();
+            let mut statistics = wrapped_segment_guard.text_query_statistics(query)?;
+            // Points deleted through the proxy are still in the wrapped segment
+            let deleted_points = self.deleted_points.read();
+            if !deleted_points.is_empty() {
+                statistics.subtract(
+                    &wrapped_segment_guard.text_query_points_statistics(query, &deleted_points)?,
+                );
+            }
+            statistics
+        };
+        let write_segment_statistics = self
+            .write_segment
+            .get()
+            .read()
+            .text_query_statistics(query)?;
+        statistics.merge(&write_segment_statistics);
+        Ok(statistics)
+    }
+
+    fn text_query_points_statistics(
+        &self,
+        query: &TextQuery,
+        point_ids: &HashSet<PointIdType>,
+    ) -> OperationResult<TextQueryStatistics> {
+        let mut statistics = {
+            let deleted_points = self.deleted_points.read();
+            let wrapped_point_ids: HashSet<_> =
+                point_ids.difference(&deleted_points).copied().collect();
+            self.wrapped_segment
+                .get()
+                .read()
+                .text_query_points_statistics(query, &wrapped_point_ids)?
+        };
+        let write_segment_statistics = self
+            .write_segment
+            .get()
+            .read()
+            .text_query_points_statistics(query, point_ids)?;
+        statistics.merge(&write_segment_statistics);
+        Ok(statistics)
+    }
+
+    fn text_search(
+        &self,
+        query: &TextQuery,
+        statistics: &TextQueryStatistics,
+        filter: Option<&Filter>,
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let deleted_points = self.deleted_points.read();
+        let mut scored_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .text_search(query, statistics, filter, limit, is_stopped)?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().text_search(
+                query,
+                statistics,
+                Some(&wrapped_filter),
+                limit,
+                is_stopped,
+            )?
+        };
+        let mut write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .text_search(query, statistics, filter, limit, is_stopped)?;
+        scored_points.append(&mut write_segment_points);
+        Ok(peek_top_largest_iterable(scored_points, limit))
+    }
+
+    fn read_random_filtered(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let deleted_points = self.deleted_points.read();
+        let mut scored_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .read_random_filtered(limit, filter, is_stopped)?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().read_random_filtered(
+                limit,
+                Some(&wrapped_filter),
+                is_stopped,
+            )?
+        };
+        let mut write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .read_random_filtered(limit, filter, is_stopped)?;
+        scored_points.append(&mut write_segment_points);
+        Ok(peek_top_largest_iterable(scored_points, limit))
+    }
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let deleted_points = self.deleted_points.read();
@@ -1333,6 +1517,57 @@ mod tests {
         assert_eq!(original_points.len() - 1, proxy_res.len());
     }
 
+    #[test]
+    fn test_text_query_statistics() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let original_segment = LockedSegment::new(build_segment_1(dir.path()));
+        original_segment
+            .get()
+            .write()
+            .create_field_index(
+                10,
+                &"color".parse().unwrap(),
+                Some(&PayloadSchemaType::Text.into()),
+            )
+            .unwrap();
+
+        let query = TextQuery {
+            key: "color".parse().unwrap(),
+            text: "red".to_string(),
+        };
+        let original_statistics = original_segment
+            .get()
+            .read()
+            .text_query_statistics(&query)
+            .unwrap();
+        assert_eq!(original_statistics.points_count, 5);
+        assert_eq!(original_statistics.total_length, 7);
+        assert_eq!(original_statistics.term_points_count["red"], 4);
+
+        let mut proxy_segment = wrap_proxy(&dir, original_segment);
+        proxy_segment.replicate_field_indexes(11).unwrap();
+
+        // Deleted point is still in the wrapped segment, but must not be counted
+        proxy_segment.delete_point(100, 2.into()).unwrap();
+        // Updated point is moved into the write segment, and must be counted once
+        proxy_segment
+            .set_full_payload(101, 4.into(), &json!({ "color": "red" }).into())
+            .unwrap();
+
+        let statistics = proxy_segment.text_query_statistics(&query).unwrap();
+        assert_eq!(statistics.points_count, 4);
+        assert_eq!(statistics.total_length, 5);
+        assert_eq!(statistics.term_points_count["red"], 3);
+
+        let point_ids = HashSet::from([1.into(), 2.into(), 4.into()]);
+        let points_statistics = proxy_segment
+            .text_query_points_statistics(&query, &point_ids)
+            .unwrap();
+        assert_eq!(points_statistics.points_count, 2);
+        assert_eq!(points_statistics.total_length, 2);
+        assert_eq!(points_statistics.term_points_count["red"], 2);
+    }
+
     #[test]
     fn test_sync_indexes() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
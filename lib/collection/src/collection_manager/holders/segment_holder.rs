@@ -14,7 +14,7 @@ use segment::common::operation_error::{OperationError, OperationResult};
 use segment::entry::entry_point::SegmentEntry;
 use segment::segment::{Segment, SegmentVersion};
 use segment::segment_constructor::build_segment;
-use segment::types::{PointIdType, SegmentConfig, SeqNumberType};
+use segment::types::{HnswConfig, PointIdType, SegmentConfig, SeqNumberType};
 
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::config::CollectionParams;
@@ -824,13 +824,19 @@ impl<'s> SegmentHolder {
 
     /// Create a new appendable segment and add it to the segment holder.
     ///
-    /// The segment configuration is sourced from the given collection parameters.
+    /// The segment configuration is sourced from the given collection parameters and HNSW config.
     pub fn create_appendable_segment(
         &mut self,
         segments_path: &Path,
         collection_params: &CollectionParams,
+        collection_hnsw: &HnswConfig,
     ) -> OperationResult<LockedSegment> {
-        let segment = self.build_tmp_segment(segments_path, Some(collection_params), true)?;
+        let segment = self.build_tmp_segment(
+            segments_path,
+            Some(collection_params),
+            Some(collection_hnsw),
+            true,
+        )?;
         self.add_new_locked(segment.clone());
         Ok(segment)
     }
@@ -839,7 +845,8 @@ impl<'s> SegmentHolder {
     ///
     /// The segment configuration is sourced from the given collection parameters. If none is
     /// specified this will fall back and clone the configuration of any existing appendable
-    /// segment in the segment holder.
+    /// segment in the segment holder. If a collection HNSW config is given, vectors with appendable
+    /// HNSW enabled get an appendable HNSW index.
     ///
     /// # Errors
     ///
@@ -856,13 +863,17 @@ impl<'s> SegmentHolder {
         &self,
         segments_path: &Path,
         collection_params: Option<&CollectionParams>,
+        collection_hnsw: Option<&HnswConfig>,
         save_version: bool,
     ) -> OperationResult<LockedSegment> {
         let config = match collection_params {
             // Base config on collection params
             Some(collection_params) => SegmentConfig {
-                vector_data: collection_params
-                    .to_base_vector_data()
+                vector_data: collection_hnsw
+                    .map_or_else(
+                        || collection_params.to_base_vector_data(),
+                        |collection_hnsw| collection_params.to_appendable_vector_data(collection_hnsw),
+                    )
                     .map_err(|err| OperationError::service_error(format!("Failed to source dense vector configuration from collection parameters: {err:?}")))?,
                 sparse_vector_data: collection_params
                     .to_sparse_vector_data()
@@ -901,7 +912,7 @@ impl<'s> SegmentHolder {
     )> {
         // Create temporary appendable segment to direct all proxy writes into
         let tmp_segment =
-            segments_lock.build_tmp_segment(segments_path, collection_params, false)?;
+            segments_lock.build_tmp_segment(segments_path, collection_params, None, false)?;
 
         // List all segments we want to snapshot
         let segment_ids = segments_lock.segment_ids();
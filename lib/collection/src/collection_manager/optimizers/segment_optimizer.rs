@@ -81,7 +81,7 @@ pub trait SegmentOptimizer {
     fn temp_segment(&self, save_version: bool) -> CollectionResult<LockedSegment> {
         let collection_params = self.collection_params();
         let config = SegmentConfig {
-            vector_data: collection_params.to_base_vector_data()?,
+            vector_data: collection_params.to_appendable_vector_data(self.hnsw_config())?,
             sparse_vector_data: collection_params.to_sparse_vector_data()?,
             payload_storage_type: if collection_params.on_disk_payload {
                 PayloadStorageType::OnDisk
@@ -152,7 +152,7 @@ pub trait SegmentOptimizer {
         let threshold_is_on_disk = maximal_vector_store_size_bytes
             >= thresholds.memmap_threshold_kb.saturating_mul(BYTES_IN_KB);
 
-        let mut vector_data = collection_params.to_base_vector_data()?;
+        let mut vector_data = collection_params.to_appendable_vector_data(self.hnsw_config())?;
         let mut sparse_vector_data = collection_params.to_sparse_vector_data()?;
 
         // If indexing, change to HNSW index and quantization
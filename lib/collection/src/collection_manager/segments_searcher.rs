@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use common::types::ScoreType;
@@ -6,10 +7,13 @@ use futures::future::try_join_all;
 use itertools::Itertools;
 use ordered_float::Float;
 use parking_lot::RwLock;
-use segment::common::operation_error::OperationError;
+use segment::common::operation_error::{check_process_stopped, OperationError};
+use segment::data_types::facets::{FacetParams, FacetValue};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::query_context::QueryContext;
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 use segment::data_types::vectors::{QueryVector, VectorStruct};
+use segment::index::rescore_formula::parsed_formula::ParsedFormula;
 use segment::types::{
     Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SeqNumberType,
     WithPayload, WithPayloadInterface, WithVector,
@@ -25,7 +29,11 @@ use crate::collection_manager::search_result_aggregator::BatchResultAggregator;
 use crate::common::stopping_guard::StoppingGuard;
 use crate::config::CollectionConfig;
 use crate::operations::query_enum::QueryEnum;
-use crate::operations::types::{CollectionResult, CoreSearchRequestBatch, Modifier, Record};
+use crate::operations::types::{
+    CollectionResult, CoreSearchRequestBatch, Modifier, RecallEstimationRequest, Record,
+    SegmentRecallEstimation, DEFAULT_RECALL_ESTIMATION_LIMIT, DEFAULT_RECALL_ESTIMATION_SAMPLE,
+    MAX_RECALL_ESTIMATION_LIMIT, MAX_RECALL_ESTIMATION_SAMPLE,
+};
 use crate::optimizers_builder::DEFAULT_INDEXING_THRESHOLD_KB;
 
 type BatchOffset = usize;
@@ -335,6 +343,210 @@ impl SegmentsSearcher {
         Ok(top_scores)
     }
 
+    /// Count points per value of the facet key in all segments, merging the counts together.
+    pub async fn facet(
+        segments: LockedSegmentHolder,
+        request: Arc<FacetParams>,
+        runtime_handle: &Handle,
+        is_stopped: Arc<AtomicBool>,
+    ) -> CollectionResult<HashMap<FacetValue, usize>> {
+        // Using block to ensure `segments` variable is dropped in the end of it
+        let facets: Vec<_> = {
+            let segments_lock = segments.read();
+            segments_lock
+                .non_appendable_then_appendable_segments()
+                .map(|segment| {
+                    let (segment, request, is_stopped) =
+                        (segment.clone(), request.clone(), is_stopped.clone());
+                    runtime_handle
+                        .spawn_blocking(move || segment.get().read().facet(&request, &is_stopped))
+                })
+                .collect()
+        };
+
+        let mut counts = HashMap::new();
+        for segment_counts in try_join_all(facets).await? {
+            for (value, count) in segment_counts? {
+                *counts.entry(value).or_insert(0) += count;
+            }
+        }
+
+        Ok(counts)
+    }
+
+    /// Estimate recall of the approximate search in every indexed segment.
+    ///
+    /// Points sampled from each segment are used as queries, results of the search with
+    /// the configured index and quantization are compared with the results of an exact search.
+    pub async fn estimate_recall(
+        segments: LockedSegmentHolder,
+        request: Arc<RecallEstimationRequest>,
+        runtime_handle: &Handle,
+        is_stopped: Arc<AtomicBool>,
+    ) -> CollectionResult<Vec<SegmentRecallEstimation>> {
+        // Using block to ensure `segments` variable is dropped in the end of it
+        let estimations: Vec<_> = {
+            let segments_lock = segments.read();
+            segments_lock
+                .non_appendable_then_appendable_segments()
+                .map(|segment| {
+                    let (segment, request, is_stopped) =
+                        (segment.clone(), request.clone(), is_stopped.clone());
+                    runtime_handle.spawn_blocking(move || {
+                        estimate_segment_recall(&segment, &request, is_stopped)
+                    })
+                })
+                .collect()
+        };
+
+        let mut recalls = Vec::new();
+        for segment_recalls in try_join_all(estimations).await? {
+            recalls.extend(segment_recalls?);
+        }
+
+        Ok(recalls)
+    }
+
+    /// Rescore the results of the prefetches with the formula, in every segment.
+    ///
+    /// Each segment returns its `limit` best points, the results are not merged.
+    pub async fn rescore_with_formula(
+        segments: LockedSegmentHolder,
+        formula: Arc<ParsedFormula>,
+        prefetches_results: Arc<Vec<Vec<ScoredPoint>>>,
+        filter: Option<Arc<Filter>>,
+        limit: usize,
+        runtime_handle: &Handle,
+        is_stopped: Arc<AtomicBool>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Using block to ensure `segments` variable is dropped in the end of it
+        let rescored: Vec<_> = {
+            let segments_lock = segments.read();
+            segments_lock
+                .non_appendable_then_appendable_segments()
+                .map(|segment| {
+                    let (segment, formula, prefetches_results, filter, is_stopped) = (
+                        segment.clone(),
+                        formula.clone(),
+                        prefetches_results.clone(),
+                        filter.clone(),
+                        is_stopped.clone(),
+                    );
+                    runtime_handle.spawn_blocking(move || {
+                        segment.get().read().rescore_with_formula(
+                            &formula,
+                            &prefetches_results,
+                            filter.as_deref(),
+                            limit,
+                            &is_stopped,
+                        )
+                    })
+                })
+                .collect()
+        };
+
+        let mut segments_results = Vec::with_capacity(rescored.len());
+        for segment_result in try_join_all(rescored).await? {
+            segments_results.push(segment_result?);
+        }
+
+        Ok(segments_results)
+    }
+
+    /// Score the points of every segment with BM25 relevance to the text query.
+    ///
+    /// Statistics of all segments are collected first, so that the scores of different segments
+    /// are comparable. Other shards of the collection are not taken into account, so the
+    /// statistics are per shard. Each segment returns its `limit` best points, the results are
+    /// not merged.
+    pub async fn text_search(
+        segments: LockedSegmentHolder,
+        query: Arc<TextQuery>,
+        filter: Option<Arc<Filter>>,
+        limit: usize,
+        runtime_handle: &Handle,
+        is_stopped: Arc<AtomicBool>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Using block to ensure `segments` variable is dropped in the end of it
+        let searches: Vec<_> = {
+            let segments_lock = segments.read();
+
+            let mut statistics = TextQueryStatistics::default();
+            for segment in segments_lock.non_appendable_then_appendable_segments() {
+                statistics.merge(&segment.get().read().text_query_statistics(&query)?);
+            }
+            let statistics = Arc::new(statistics);
+
+            segments_lock
+                .non_appendable_then_appendable_segments()
+                .map(|segment| {
+                    let (segment, query, statistics, filter, is_stopped) = (
+                        segment.clone(),
+                        query.clone(),
+                        statistics.clone(),
+                        filter.clone(),
+                        is_stopped.clone(),
+                    );
+                    runtime_handle.spawn_blocking(move || {
+                        segment.get().read().text_search(
+                            &query,
+                            &statistics,
+                            filter.as_deref(),
+                            limit,
+                            &is_stopped,
+                        )
+                    })
+                })
+                .collect()
+        };
+
+        let mut segments_results = Vec::with_capacity(searches.len());
+        for segment_result in try_join_all(searches).await? {
+            segments_results.push(segment_result?);
+        }
+
+        Ok(segments_results)
+    }
+
+    /// Sample random points, which satisfy the filter, from each segment
+    ///
+    /// The points are scored with random sampling keys, so that keeping the points with the
+    /// largest scores across all segments gives a uniform random sample.
+    pub async fn sample_random(
+        segments: LockedSegmentHolder,
+        filter: Option<Arc<Filter>>,
+        limit: usize,
+        runtime_handle: &Handle,
+        is_stopped: Arc<AtomicBool>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Using block to ensure `segments` variable is dropped in the end of it
+        let samples: Vec<_> = {
+            let segments_lock = segments.read();
+
+            segments_lock
+                .non_appendable_then_appendable_segments()
+                .map(|segment| {
+                    let (segment, filter, is_stopped) =
+                        (segment.clone(), filter.clone(), is_stopped.clone());
+                    runtime_handle.spawn_blocking(move || {
+                        segment.get().read().read_random_filtered(
+                            limit,
+                            filter.as_deref(),
+                            &is_stopped,
+                        )
+                    })
+                })
+                .collect()
+        };
+
+        let mut segments_results = Vec::with_capacity(samples.len());
+        for segment_result in try_join_all(samples).await? {
+            segments_results.push(segment_result?);
+        }
+
+        Ok(segments_results)
+    }
+
     /// Retrieve records for the given points ids from the segments
     /// - if payload is enabled, payload will be fetched
     /// - if vector is enabled, vector will be fetched
@@ -601,6 +813,147 @@ fn execute_batch_search(
     Ok((res, further_results))
 }
 
+/// Estimate recall of the approximate search for every indexed vector of the segment
+///
+/// Query points are excluded from their own results, so that trivial self-matches
+/// do not inflate the recall. The segment lock is released between vectors, so that
+/// updates and optimizations are not blocked for the whole estimation.
+fn estimate_segment_recall(
+    segment: &LockedSegment,
+    request: &RecallEstimationRequest,
+    is_stopped: Arc<AtomicBool>,
+) -> CollectionResult<Vec<SegmentRecallEstimation>> {
+    let sample = request
+        .sample
+        .unwrap_or(DEFAULT_RECALL_ESTIMATION_SAMPLE)
+        .min(MAX_RECALL_ESTIMATION_SAMPLE);
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_RECALL_ESTIMATION_LIMIT)
+        .min(MAX_RECALL_ESTIMATION_LIMIT);
+
+    let segment = segment.get();
+
+    let (segment_name, indexed_vectors, sampled_ids) = {
+        let segment = segment.read();
+
+        let segment_name = segment
+            .data_path()
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let indexed_vectors: Vec<_> = segment
+            .config()
+            .vector_data
+            .iter()
+            .filter(|(vector_name, vector_config)| {
+                !request
+                    .using
+                    .as_ref()
+                    .is_some_and(|using| using != *vector_name)
+                    && !matches!(vector_config.index, Indexes::Plain {})
+            })
+            .map(|(vector_name, _)| vector_name.clone())
+            .collect();
+        if indexed_vectors.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let sampled_ids: Vec<_> = segment
+            .read_random_filtered(sample, None, &is_stopped)?
+            .into_iter()
+            .map(|point| point.id)
+            .collect();
+
+        (segment_name, indexed_vectors, sampled_ids)
+    };
+
+    let exact_params = SearchParams {
+        exact: true,
+        ..Default::default()
+    };
+    // Estimation searches are not requested by users, they must not show up in search telemetry
+    let query_context = QueryContext::default()
+        .with_is_stopped(is_stopped.clone())
+        .with_record_telemetry(false);
+
+    let mut recalls = Vec::new();
+    for vector_name in indexed_vectors {
+        check_process_stopped(&is_stopped)?;
+
+        // Lock is taken for each vector separately, points may be changed in between
+        let segment = segment.read();
+
+        let mut query_ids = Vec::with_capacity(sampled_ids.len());
+        let mut queries = Vec::with_capacity(sampled_ids.len());
+        for &point_id in &sampled_ids {
+            if !segment.has_point(point_id) {
+                continue;
+            }
+            if let Some(vector) = segment.vector(&vector_name, point_id)? {
+                query_ids.push(point_id);
+                queries.push(QueryVector::from(vector));
+            }
+        }
+        if queries.is_empty() {
+            continue;
+        }
+        let queries: Vec<_> = queries.iter().collect();
+
+        // One extra neighbour is requested, as the query point itself is excluded
+        let search = |params: Option<&SearchParams>| {
+            segment.search_batch(
+                &vector_name,
+                &queries,
+                &WithPayload::from(false),
+                &WithVector::Bool(false),
+                None,
+                limit + 1,
+                params,
+                query_context.get_segment_query_context(),
+            )
+        };
+        let approximate = search(request.params.as_ref())?;
+        let exact = search(Some(&exact_params))?;
+        drop(segment);
+
+        let mut found = 0;
+        let mut expected = 0;
+        for ((query_id, approximate), exact) in query_ids.iter().zip(approximate).zip(exact) {
+            let neighbours = |points: Vec<ScoredPoint>| -> Vec<PointIdType> {
+                points
+                    .into_iter()
+                    .map(|point| point.id)
+                    .filter(|id| id != query_id)
+                    .take(limit)
+                    .collect()
+            };
+            let approximate = neighbours(approximate);
+            let exact = neighbours(exact);
+            expected += exact.len();
+            found += exact.iter().filter(|id| approximate.contains(id)).count();
+        }
+
+        // Segments too small to have neighbours are trivially fully recalled
+        let recall = if expected == 0 {
+            1.0
+        } else {
+            found as f64 / expected as f64
+        };
+
+        recalls.push(SegmentRecallEstimation {
+            segment: segment_name.clone(),
+            vector_name,
+            sample: query_ids.len(),
+            limit,
+            recall,
+        });
+    }
+
+    Ok(recalls)
+}
+
 /// Find the HNSW ef_construct for a named vector
 ///
 /// If the given named vector has no HNSW index, `None` is returned.
@@ -610,7 +963,7 @@ fn get_hnsw_ef_construct(config: &SegmentConfig, vector_name: &str) -> Option<us
         .get(vector_name)
         .and_then(|config| match &config.index {
             Indexes::Plain {} => None,
-            Indexes::Hnsw(hnsw) => Some(hnsw),
+            Indexes::Hnsw(hnsw) | Indexes::AppendableHnsw(hnsw) => Some(hnsw),
         })
         .map(|hnsw| hnsw.ef_construct)
 }
@@ -619,9 +972,17 @@ fn get_hnsw_ef_construct(config: &SegmentConfig, vector_name: &str) -> Option<us
 mod tests {
     use std::collections::HashSet;
 
+    use common::types::TelemetryDetail;
+    use rand::prelude::StdRng;
+    use rand::SeedableRng;
+    use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+    use segment::entry::entry_point::SegmentEntry as _;
     use segment::fixtures::index_fixtures::random_vector;
     use segment::index::VectorIndexEnum;
-    use segment::types::{Condition, HasIdCondition};
+    use segment::segment_constructor::build_segment;
+    use segment::types::{
+        Condition, Distance, HasIdCondition, HnswConfig, VectorDataConfig, VectorStorageType,
+    };
     use tempfile::Builder;
 
     use super::*;
@@ -702,6 +1063,141 @@ mod tests {
         assert!(result[1].id == 3.into() || result[1].id == 11.into());
     }
 
+    #[tokio::test]
+    async fn test_estimate_recall() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut rnd = StdRng::seed_from_u64(42);
+        let dim = 16;
+
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Cosine,
+                    storage_type: VectorStorageType::Memory,
+                    index: Indexes::AppendableHnsw(HnswConfig {
+                        m: 16,
+                        ef_construct: 64,
+                        full_scan_threshold: 1,
+                        max_indexing_threads: 2,
+                        on_disk: Some(false),
+                        payload_m: None,
+                        appendable: Some(true),
+                    }),
+                    quantization_config: None,
+                    multivec_config: None,
+                    datatype: None,
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: Default::default(),
+        };
+
+        let mut indexed_segment = build_segment(dir.path(), &config, true).unwrap();
+        for idx in 0..500u64 {
+            let vector = random_vector(&mut rnd, dim);
+            indexed_segment
+                .upsert_point(1, idx.into(), only_default_vector(&vector))
+                .unwrap();
+        }
+        // Updated points are linked into the graph by the update worker, link them here,
+        // otherwise they are fully scored and recall is trivially perfect
+        indexed_segment
+            .link_pending_points(&AtomicBool::new(false))
+            .unwrap();
+
+        // Plain segments are not estimated
+        let plain_segment = random_segment(dir.path(), 1, 100, dim);
+
+        let mut holder = SegmentHolder::default();
+        holder.add_new(indexed_segment);
+        holder.add_new(plain_segment);
+        let segment_holder = Arc::new(RwLock::new(holder));
+
+        let request = RecallEstimationRequest {
+            sample: Some(20),
+            limit: Some(5),
+            ..Default::default()
+        };
+
+        let recalls = SegmentsSearcher::estimate_recall(
+            segment_holder.clone(),
+            Arc::new(request),
+            &Handle::current(),
+            Arc::new(AtomicBool::new(false)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(recalls.len(), 1);
+        let recall = &recalls[0];
+        assert_eq!(recall.vector_name, DEFAULT_VECTOR_NAME);
+        assert_eq!(recall.sample, 20);
+        assert_eq!(recall.limit, 5);
+        assert!(recall.recall > 0.8, "recall is too low: {}", recall.recall);
+        assert!(recall.recall <= 1.0);
+
+        // Estimation searches are not reported in search telemetry of the segments
+        for (_id, segment) in segment_holder.read().iter() {
+            let telemetry = segment
+                .get()
+                .read()
+                .get_telemetry_data(TelemetryDetail::default());
+            for searches in telemetry.vector_index_searches {
+                assert_eq!(searches.unfiltered_hnsw.count, 0);
+                assert_eq!(searches.unfiltered_exact.count, 0);
+            }
+        }
+
+        // Vectors not matching `using` are skipped
+        let request = RecallEstimationRequest {
+            using: Some("missing".to_string()),
+            ..Default::default()
+        };
+
+        let recalls = SegmentsSearcher::estimate_recall(
+            segment_holder.clone(),
+            Arc::new(request),
+            &Handle::current(),
+            Arc::new(AtomicBool::new(false)),
+        )
+        .await
+        .unwrap();
+
+        assert!(recalls.is_empty());
+
+        // Requests not validated by the API are capped
+        let request = RecallEstimationRequest {
+            sample: Some(1),
+            limit: Some(MAX_RECALL_ESTIMATION_LIMIT * 10),
+            ..Default::default()
+        };
+
+        let recalls = SegmentsSearcher::estimate_recall(
+            segment_holder.clone(),
+            Arc::new(request),
+            &Handle::current(),
+            Arc::new(AtomicBool::new(false)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(recalls.len(), 1);
+        assert_eq!(recalls[0].limit, MAX_RECALL_ESTIMATION_LIMIT);
+
+        // Stopped estimation is cancelled
+        let result = SegmentsSearcher::estimate_recall(
+            segment_holder,
+            Arc::new(RecallEstimationRequest::default()),
+            &Handle::current(),
+            Arc::new(AtomicBool::new(true)),
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_segments_search_sampling() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -10,8 +10,8 @@ use segment::data_types::vectors::{BatchVectorStruct, VectorStruct};
 use segment::entry::entry_point::SegmentEntry;
 use segment::json_path::JsonPath;
 use segment::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    SeqNumberType,
+    Condition, Filter, HasIdCondition, Payload, PayloadFieldSchema, PayloadKeyType,
+    PayloadKeyTypeRef, PointIdType, SeqNumberType,
 };
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
@@ -443,6 +443,61 @@ where
     Ok(res)
 }
 
+/// Upsert points, but only update existing points which match the condition.
+/// Existing points which do not match the condition are left untouched, new points are inserted.
+/// Returns: number of updated points.
+pub(crate) fn conditional_upsert(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points: Vec<PointStruct>,
+    condition: &Filter,
+) -> CollectionResult<usize> {
+    // Find points, which exist but don't match the condition, and exclude them from the upsert
+    let point_ids: HashSet<PointIdType> = points.iter().map(|point| point.id).collect();
+    let excluded_filter = Filter {
+        should: None,
+        min_should: None,
+        must: Some(vec![Condition::HasId(HasIdCondition::from(point_ids))]),
+        must_not: Some(vec![Condition::Filter(condition.clone())]),
+    };
+    let excluded_points: HashSet<PointIdType> = points_by_filter(segments, &excluded_filter)?
+        .into_iter()
+        .collect();
+
+    let points_to_upsert = points
+        .iter()
+        .filter(|point| !excluded_points.contains(&point.id));
+    upsert_points(segments, op_num, points_to_upsert)
+}
+
+fn insert_operation_into_points(operation: PointInsertOperationsInternal) -> Vec<PointStruct> {
+    match operation {
+        PointInsertOperationsInternal::PointsBatch(batch) => {
+            let batch_vectors: BatchVectorStruct = batch.vectors.into();
+            let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
+            let vectors_iter = batch.ids.into_iter().zip(all_vectors);
+            match batch.payloads {
+                None => vectors_iter
+                    .map(|(id, vectors)| PointStruct {
+                        id,
+                        vector: VectorStruct::from(vectors).into(),
+                        payload: None,
+                    })
+                    .collect(),
+                Some(payloads) => vectors_iter
+                    .zip(payloads)
+                    .map(|((id, vectors), payload)| PointStruct {
+                        id,
+                        vector: VectorStruct::from(vectors).into(),
+                        payload,
+                    })
+                    .collect(),
+            }
+        }
+        PointInsertOperationsInternal::PointsList(points) => points,
+    }
+}
+
 pub(crate) fn process_point_operation(
     segments: &RwLock<SegmentHolder>,
     op_num: SeqNumberType,
@@ -451,34 +506,14 @@ pub(crate) fn process_point_operation(
     match point_operation {
         PointOperations::DeletePoints { ids, .. } => delete_points(&segments.read(), op_num, &ids),
         PointOperations::UpsertPoints(operation) => {
-            let points: Vec<_> = match operation {
-                PointInsertOperationsInternal::PointsBatch(batch) => {
-                    let batch_vectors: BatchVectorStruct = batch.vectors.into();
-                    let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
-                    let vectors_iter = batch.ids.into_iter().zip(all_vectors);
-                    match batch.payloads {
-                        None => vectors_iter
-                            .map(|(id, vectors)| PointStruct {
-                                id,
-                                vector: VectorStruct::from(vectors).into(),
-                                payload: None,
-                            })
-                            .collect(),
-                        Some(payloads) => vectors_iter
-                            .zip(payloads)
-                            .map(|((id, vectors), payload)| PointStruct {
-                                id,
-                                vector: VectorStruct::from(vectors).into(),
-                                payload,
-                            })
-                            .collect(),
-                    }
-                }
-                PointInsertOperationsInternal::PointsList(points) => points,
-            };
+            let points = insert_operation_into_points(operation);
             let res = upsert_points(&segments.read(), op_num, points.iter())?;
             Ok(res)
         }
+        PointOperations::UpsertPointsConditional(operation) => {
+            let points = insert_operation_into_points(operation.points_op);
+            conditional_upsert(&segments.read(), op_num, points, &operation.condition)
+        }
         PointOperations::DeletePointsByFilter(filter) => {
             delete_points_by_filter(&segments.read(), op_num, &filter)
         }
@@ -8,7 +8,11 @@ use itertools::Itertools;
 use parking_lot::RwLock;
 use segment::data_types::vectors::{only_default_vector, VectorStruct};
 use segment::entry::entry_point::SegmentEntry;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, PointIdType};
+use segment::json_path::path;
+use segment::types::{
+    Condition, FieldCondition, Filter, PayloadFieldSchema, PayloadKeyType, PointIdType, Range,
+};
+use serde_json::json;
 use tempfile::Builder;
 
 use crate::collection_manager::fixtures::{build_segment_1, build_segment_2, empty_segment};
@@ -16,7 +20,7 @@ use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentHolder, SegmentId,
 };
-use crate::collection_manager::segments_updater::upsert_points;
+use crate::collection_manager::segments_updater::{conditional_upsert, upsert_points};
 use crate::operations::point_ops::PointStruct;
 
 fn wrap_proxy(segments: LockedSegmentHolder, sid: SegmentId, path: &Path) -> SegmentId {
@@ -233,3 +237,58 @@ fn test_upsert_points_in_smallest_segment() {
         }
     }
 }
+
+#[test]
+fn test_conditional_upsert() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut holder = SegmentHolder::default();
+    let _sid = holder.add_new(empty_segment(dir.path()));
+    let segments = Arc::new(RwLock::new(holder));
+
+    let point_with_version = |id: u64, version: u64| PointStruct {
+        id: id.into(),
+        vector: VectorStruct::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
+        payload: Some(json!({ "version": version }).into()),
+    };
+
+    let points = vec![point_with_version(1, 1), point_with_version(2, 3)];
+    upsert_points(&segments.read(), 1000, &points).unwrap();
+
+    // Only overwrite points, which have a lower version
+    let condition = Filter::new_must(Condition::Field(FieldCondition::new_range(
+        path("version"),
+        Range {
+            lt: Some(2.0),
+            gt: None,
+            gte: None,
+            lte: None,
+        },
+    )));
+    let points = vec![
+        point_with_version(1, 2),
+        point_with_version(2, 2),
+        point_with_version(3, 2),
+    ];
+    let updated = conditional_upsert(&segments.read(), 1001, points, &condition).unwrap();
+    assert_eq!(updated, 1);
+
+    let segments_read = segments.read();
+    let version_of = |id: u64| {
+        let mut version = None;
+        segments_read
+            .read_points(&[id.into()], |id, segment| {
+                version = Some(segment.payload(id)?.0["version"].clone());
+                Ok(true)
+            })
+            .unwrap();
+        version
+    };
+
+    // Matching point is updated
+    assert_eq!(version_of(1), Some(json!(2)));
+    // Not matching point is left untouched
+    assert_eq!(version_of(2), Some(json!(3)));
+    // New point is inserted regardless of the condition
+    assert_eq!(version_of(3), Some(json!(2)));
+}
@@ -6,6 +6,7 @@ use std::path::Path;
 
 use atomicwrites::AtomicFile;
 use atomicwrites::OverwriteBehavior::AllowOverwrite;
+use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
@@ -57,6 +58,59 @@ impl Default for WalConfig {
     }
 }
 
+/// Limits on expensive requests to the collection.
+///
+/// Requests exceeding any of the configured limits are rejected before execution.
+/// Limits which are not set are not enforced.
+#[derive(
+    Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge, PartialEq, Eq, Hash,
+)]
+pub struct StrictModeConfig {
+    /// Whether strict mode is enabled for the collection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Max allowed `limit` parameter of read requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_query_limit: Option<usize>,
+    /// Max allowed `offset` parameter of read requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_query_offset: Option<usize>,
+    /// Max allowed `timeout` parameter, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_timeout: Option<usize>,
+    /// Allow filtering on fields without a suitable payload index in read requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unindexed_filtering_retrieve: Option<bool>,
+    /// Allow filtering on fields without a suitable payload index in updates, e.g. delete by filter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unindexed_filtering_update: Option<bool>,
+    /// Allow exact search, which bypasses the vector index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_allow_exact: Option<bool>,
+    /// Max number of requests in a batch of read requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub search_max_batchsize: Option<usize>,
+    /// Max number of points, or vectors, in a single update request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub upsert_max_batchsize: Option<usize>,
+    /// Max number of points in the collection, inserting new points is rejected once it is reached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_points_count: Option<usize>,
+}
+
+impl StrictModeConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+}
+
+impl DiffConfig<StrictModeConfig> for StrictModeConfig {}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum ShardingMethod {
@@ -106,6 +160,13 @@ pub struct CollectionParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Time-to-live of points in seconds, counted from their last upsert.
+    /// Expired points are hidden from reads and deleted in background.
+    /// The expiration time is stored in the reserved `_expires_at` payload key, which may be set
+    /// explicitly to override it for individual points.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub ttl_sec: Option<u64>,
 }
 
 impl CollectionParams {
@@ -129,6 +190,7 @@ impl Anonymize for CollectionParams {
             read_fan_out_factor: self.read_fan_out_factor,
             on_disk_payload: self.on_disk_payload,
             sparse_vectors: self.sparse_vectors.anonymize(),
+            ttl_sec: self.ttl_sec,
         }
     }
 }
@@ -161,6 +223,9 @@ pub struct CollectionConfig {
     pub wal_config: WalConfig,
     #[serde(default)]
     pub quantization_config: Option<QuantizationConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
 }
 
 impl CollectionConfig {
@@ -206,6 +271,7 @@ impl CollectionParams {
             read_fan_out_factor: None,
             on_disk_payload: default_on_disk_payload(),
             sparse_vectors: None,
+            ttl_sec: None,
         }
     }
 
@@ -367,6 +433,30 @@ impl CollectionParams {
             .collect())
     }
 
+    /// Convert into named vector data configs for new appendable segments
+    ///
+    /// Same as [`Self::to_base_vector_data`], but vectors with `appendable` HNSW enabled get an
+    /// appendable HNSW index, which links points into the graph right on insertion.
+    pub fn to_appendable_vector_data(
+        &self,
+        collection_hnsw: &HnswConfig,
+    ) -> CollectionResult<HashMap<String, VectorDataConfig>> {
+        let mut vector_data = self.to_base_vector_data()?;
+        vector_data.iter_mut().for_each(|(vector_name, config)| {
+            let param_hnsw = self
+                .vectors
+                .get_params(vector_name)
+                .and_then(|params| params.hnsw_config);
+            let vector_hnsw = param_hnsw
+                .and_then(|c| c.update(collection_hnsw).ok())
+                .unwrap_or_else(|| collection_hnsw.clone());
+            if vector_hnsw.appendable.unwrap_or_default() {
+                config.index = Indexes::AppendableHnsw(vector_hnsw);
+            }
+        });
+        Ok(vector_data)
+    }
+
     /// Convert into unoptimized sparse vector data configs
     ///
     /// It is the job of the segment optimizer to change this configuration with optimized settings
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use futures::Future;
@@ -89,7 +90,7 @@ fn discovery_into_core_search(
 
     let filter = {
         let not_ids = Filter::new_must_not(Condition::HasId(HasIdCondition {
-            has_id: referenced_ids.into_iter().collect(),
+            has_id: Arc::new(referenced_ids.into_iter().collect()),
         }));
 
         match &request.filter {
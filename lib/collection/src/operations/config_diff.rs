@@ -83,6 +83,10 @@ pub struct HnswConfigDiff {
     /// Custom M param for additional payload-aware HNSW links. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload_m: Option<usize>,
+    /// Link new points into HNSW graph of appendable segments right on insertion, so they are searched
+    /// with the index without waiting for optimization. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appendable: Option<bool>,
 }
 
 #[derive(
@@ -96,7 +100,9 @@ pub struct WalConfigDiff {
     pub wal_segments_ahead: Option<usize>,
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Merge, PartialEq, Eq, Hash)]
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge, PartialEq, Eq, Hash,
+)]
 pub struct CollectionParamsDiff {
     /// Number of replicas for each shard
     pub replication_factor: Option<NonZeroU32>,
@@ -110,6 +116,11 @@ pub struct CollectionParamsDiff {
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default)]
     pub on_disk_payload: Option<bool>,
+    /// Time-to-live of points in seconds, counted from their last upsert.
+    /// Expired points are hidden from reads and deleted in background.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub ttl_sec: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge)]
@@ -348,6 +359,7 @@ mod tests {
             write_consistency_factor: Some(NonZeroU32::new(2).unwrap()),
             read_fan_out_factor: None,
             on_disk_payload: None,
+            ttl_sec: None,
         };
 
         let new_params = diff.update(&params).unwrap();
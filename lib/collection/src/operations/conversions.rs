@@ -10,7 +10,7 @@ use api::grpc::qdrant::quantization_config_diff::Quantization;
 use api::grpc::qdrant::update_collection_cluster_setup_request::{
     Operation as ClusterOperationsPb, Operation,
 };
-use api::grpc::qdrant::CreateShardKey;
+use api::grpc::qdrant::{update_event, CreateShardKey};
 use api::rest::schema::ShardKeySelector;
 use common::types::ScoreType;
 use itertools::Itertools;
@@ -32,7 +32,7 @@ use super::types::{
 };
 use crate::config::{
     default_replication_factor, default_write_consistency_factor, CollectionConfig,
-    CollectionParams, ShardingMethod, WalConfig,
+    CollectionParams, ShardingMethod, StrictModeConfig, WalConfig,
 };
 use crate::lookup::types::WithLookupInterface;
 use crate::lookup::WithLookup;
@@ -46,19 +46,30 @@ use crate::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
 };
+use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
-    Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, WriteOrdering,
+    Batch, FilterSelector, PointIdsList, PointOperations, PointStruct, PointsSelector,
+    WriteOrdering,
 };
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
-    LocalShardInfo, LookupLocation, OptimizersStatus, RecommendRequestInternal, Record,
-    RemoteShardInfo, SearchRequestInternal, ShardTransferInfo, UpdateResult, UpdateStatus,
-    VectorParams, VectorsConfig,
+    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionResult, CollectionStatus,
+    CountResult, LocalShardInfo, LookupLocation, OptimizersStatus, RecommendRequestInternal,
+    Record, RemoteShardInfo, SearchMatrixOffsetsResponse, SearchMatrixPair,
+    SearchMatrixPairsResponse, SearchRequestInternal, ShardTransferInfo, UpdateResult,
+    UpdateStatus, VectorParams, VectorsConfig,
 };
+use crate::operations::vector_ops::VectorOperations;
+use crate::operations::{CollectionUpdateOperations, FieldIndexOperations};
 use crate::optimizers_builder::OptimizersConfig;
+use crate::shards::conversions::{
+    internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
+    internal_delete_index, internal_delete_payload, internal_delete_points,
+    internal_delete_points_by_filter, internal_delete_vectors, internal_delete_vectors_by_filter,
+    internal_set_payload, internal_update_vectors, internal_upsert_points,
+};
 use crate::shards::remote_shard::{CollectionCoreSearchRequest, CollectionSearchRequest};
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::transfer::ShardTransferMethod;
@@ -234,6 +245,7 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as usize),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as usize),
+            appendable: value.appendable,
         }
     }
 }
@@ -247,6 +259,7 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as u64),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as u64),
+            appendable: value.appendable,
         }
     }
 }
@@ -260,6 +273,40 @@ impl From<api::grpc::qdrant::WalConfigDiff> for WalConfigDiff {
     }
 }
 
+impl From<api::grpc::qdrant::StrictModeConfig> for StrictModeConfig {
+    fn from(value: api::grpc::qdrant::StrictModeConfig) -> Self {
+        Self {
+            enabled: value.enabled,
+            max_query_limit: value.max_query_limit.map(|v| v as usize),
+            max_query_offset: value.max_query_offset.map(|v| v as usize),
+            max_timeout: value.max_timeout.map(|v| v as usize),
+            unindexed_filtering_retrieve: value.unindexed_filtering_retrieve,
+            unindexed_filtering_update: value.unindexed_filtering_update,
+            search_allow_exact: value.search_allow_exact,
+            search_max_batchsize: value.search_max_batchsize.map(|v| v as usize),
+            upsert_max_batchsize: value.upsert_max_batchsize.map(|v| v as usize),
+            max_points_count: value.max_points_count.map(|v| v as usize),
+        }
+    }
+}
+
+impl From<StrictModeConfig> for api::grpc::qdrant::StrictModeConfig {
+    fn from(value: StrictModeConfig) -> Self {
+        Self {
+            enabled: value.enabled,
+            max_query_limit: value.max_query_limit.map(|v| v as u64),
+            max_query_offset: value.max_query_offset.map(|v| v as u64),
+            max_timeout: value.max_timeout.map(|v| v as u64),
+            unindexed_filtering_retrieve: value.unindexed_filtering_retrieve,
+            unindexed_filtering_update: value.unindexed_filtering_update,
+            search_allow_exact: value.search_allow_exact,
+            search_max_batchsize: value.search_max_batchsize.map(|v| v as u64),
+            upsert_max_batchsize: value.upsert_max_batchsize.map(|v| v as u64),
+            max_points_count: value.max_points_count.map(|v| v as u64),
+        }
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::CollectionParamsDiff> for CollectionParamsDiff {
     type Error = Status;
 
@@ -282,6 +329,7 @@ impl TryFrom<api::grpc::qdrant::CollectionParamsDiff> for CollectionParamsDiff {
                 .transpose()?,
             read_fan_out_factor: value.read_fan_out_factor,
             on_disk_payload: value.on_disk_payload,
+            ttl_sec: value.ttl_sec,
         })
     }
 }
@@ -393,6 +441,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                                 .collect(),
                         }
                     }),
+                    ttl_sec: config.params.ttl_sec,
                 }),
                 hnsw_config: Some(api::grpc::qdrant::HnswConfigDiff {
                     m: Some(config.hnsw_config.m as u64),
@@ -401,6 +450,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     max_indexing_threads: Some(config.hnsw_config.max_indexing_threads as u64),
                     on_disk: config.hnsw_config.on_disk,
                     payload_m: config.hnsw_config.payload_m.map(|v| v as u64),
+                    appendable: config.hnsw_config.appendable,
                 }),
                 optimizer_config: Some(api::grpc::qdrant::OptimizersConfigDiff {
                     deleted_threshold: Some(config.optimizer_config.deleted_threshold),
@@ -427,6 +477,9 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     wal_segments_ahead: Some(config.wal_config.wal_segments_ahead as u64),
                 }),
                 quantization_config: config.quantization_config.map(|x| x.into()),
+                strict_mode_config: config
+                    .strict_mode_config
+                    .map(api::grpc::qdrant::StrictModeConfig::from),
             }),
             payload_schema: payload_schema
                 .into_iter()
@@ -564,6 +617,8 @@ fn convert_datatype_from_proto(datatype: Option<i32>) -> Result<Option<Datatype>
                 api::grpc::qdrant::Datatype::Uint8 => Ok(Some(Datatype::Uint8)),
                 api::grpc::qdrant::Datatype::Float32 => Ok(Some(Datatype::Float32)),
                 api::grpc::qdrant::Datatype::Float16 => Ok(Some(Datatype::Float16)),
+                api::grpc::qdrant::Datatype::Bfloat16 => Ok(Some(Datatype::Bfloat16)),
+                api::grpc::qdrant::Datatype::Bit => Ok(Some(Datatype::Bit)),
                 api::grpc::qdrant::Datatype::Default => Ok(None),
             }
         } else {
@@ -728,6 +783,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                         .sharding_method
                         .map(sharding_method_from_proto)
                         .transpose()?,
+                    ttl_sec: params.ttl_sec,
                 },
             },
             hnsw_config: match config.hnsw_config {
@@ -749,6 +805,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     None
                 }
             },
+            strict_mode_config: config.strict_mode_config.map(StrictModeConfig::from),
         })
     }
 }
@@ -909,12 +966,144 @@ pub fn try_points_selector_from_grpc(
     }
 }
 
+/// Convert an operation read from the WAL of a shard into an update event for the client
+pub fn update_event_from_wal_operation(
+    collection_name: String,
+    operation_id: u64,
+    operation: CollectionUpdateOperations,
+) -> CollectionResult<api::grpc::qdrant::UpdateEvent> {
+    let operation = match operation {
+        CollectionUpdateOperations::PointOperation(point_ops) => match point_ops {
+            PointOperations::UpsertPoints(point_insert_operations) => internal_upsert_points(
+                None,
+                None,
+                collection_name,
+                point_insert_operations,
+                None,
+                false,
+                None,
+            )?
+            .upsert_points
+            .map(update_event::Operation::Upsert),
+            PointOperations::UpsertPointsConditional(conditional_upsert) => internal_upsert_points(
+                None,
+                None,
+                collection_name,
+                conditional_upsert.points_op,
+                Some(conditional_upsert.condition),
+                false,
+                None,
+            )?
+            .upsert_points
+            .map(update_event::Operation::Upsert),
+            PointOperations::DeletePoints { ids } => {
+                internal_delete_points(None, None, collection_name, ids, false, None)
+                    .delete_points
+                    .map(update_event::Operation::DeletePoints)
+            }
+            PointOperations::DeletePointsByFilter(filter) => {
+                internal_delete_points_by_filter(None, None, collection_name, filter, false, None)
+                    .delete_points
+                    .map(update_event::Operation::DeletePoints)
+            }
+            PointOperations::SyncPoints(operation) => Some(update_event::Operation::SyncPoints(
+                update_event::SyncPoints {
+                    points: operation
+                        .points
+                        .into_iter()
+                        .map(|point| point.try_into())
+                        .collect::<Result<Vec<_>, Status>>()?,
+                    from_id: operation.from_id.map(|id| id.into()),
+                    to_id: operation.to_id.map(|id| id.into()),
+                },
+            )),
+        },
+        CollectionUpdateOperations::VectorOperation(vector_ops) => match vector_ops {
+            VectorOperations::UpdateVectors(update_operation) => {
+                internal_update_vectors(None, None, collection_name, update_operation, false, None)
+                    .update_vectors
+                    .map(update_event::Operation::UpdateVectors)
+            }
+            VectorOperations::DeleteVectors(ids, vector_names) => internal_delete_vectors(
+                None,
+                None,
+                collection_name,
+                ids.points,
+                vector_names,
+                false,
+                None,
+            )
+            .delete_vectors
+            .map(update_event::Operation::DeleteVectors),
+            VectorOperations::DeleteVectorsByFilter(filter, vector_names) => {
+                internal_delete_vectors_by_filter(
+                    None,
+                    None,
+                    collection_name,
+                    filter,
+                    vector_names,
+                    false,
+                    None,
+                )
+                .delete_vectors
+                .map(update_event::Operation::DeleteVectors)
+            }
+        },
+        CollectionUpdateOperations::PayloadOperation(payload_ops) => match payload_ops {
+            PayloadOps::SetPayload(set_payload) => {
+                internal_set_payload(None, None, collection_name, set_payload, false, None)
+                    .set_payload_points
+                    .map(update_event::Operation::SetPayload)
+            }
+            PayloadOps::DeletePayload(delete_payload) => {
+                internal_delete_payload(None, None, collection_name, delete_payload, false, None)
+                    .delete_payload_points
+                    .map(update_event::Operation::DeletePayload)
+            }
+            PayloadOps::ClearPayload { points } => {
+                internal_clear_payload(None, None, collection_name, points, false, None)
+                    .clear_payload_points
+                    .map(update_event::Operation::ClearPayload)
+            }
+            PayloadOps::ClearPayloadByFilter(filter) => {
+                internal_clear_payload_by_filter(None, None, collection_name, filter, false, None)
+                    .clear_payload_points
+                    .map(update_event::Operation::ClearPayload)
+            }
+            PayloadOps::OverwritePayload(set_payload) => {
+                internal_set_payload(None, None, collection_name, set_payload, false, None)
+                    .set_payload_points
+                    .map(update_event::Operation::OverwritePayload)
+            }
+        },
+        CollectionUpdateOperations::FieldIndexOperation(field_index_op) => match field_index_op {
+            FieldIndexOperations::CreateIndex(create_index) => {
+                internal_create_index(None, None, collection_name, create_index, false, None)
+                    .create_field_index_collection
+                    .map(update_event::Operation::CreateFieldIndex)
+            }
+            FieldIndexOperations::DeleteIndex(delete_index) => {
+                internal_delete_index(None, None, collection_name, delete_index, false, None)
+                    .delete_field_index_collection
+                    .map(update_event::Operation::DeleteFieldIndex)
+            }
+        },
+    };
+
+    Ok(api::grpc::qdrant::UpdateEvent {
+        operation_id,
+        operation,
+    })
+}
+
 impl From<UpdateResult> for api::grpc::qdrant::UpdateResultInternal {
     fn from(res: UpdateResult) -> Self {
         Self {
             operation_id: res.operation_id,
             status: res.status.into(),
             clock_tag: res.clock_tag.map(Into::into),
+            updated_count: res.updated_count.map(|count| count as u64),
+            skipped_count: res.skipped_count.map(|count| count as u64),
         }
     }
 }
@@ -932,6 +1121,8 @@ impl TryFrom<api::grpc::qdrant::UpdateResultInternal> for UpdateResult {
         let res = Self {
             operation_id: res.operation_id,
             status: res.status.try_into()?,
+            updated_count: res.updated_count.map(|count| count as usize),
+            skipped_count: res.skipped_count.map(|count| count as usize),
             clock_tag: res.clock_tag.map(Into::into),
         };
 
@@ -996,6 +1187,42 @@ impl From<CountResult> for api::grpc::qdrant::CountResult {
     }
 }
 
+impl From<SearchMatrixPair> for api::grpc::qdrant::SearchMatrixPair {
+    fn from(pair: SearchMatrixPair) -> Self {
+        let SearchMatrixPair { a, b, score } = pair;
+        Self {
+            a: Some(a.into()),
+            b: Some(b.into()),
+            score,
+        }
+    }
+}
+
+impl From<SearchMatrixPairsResponse> for api::grpc::qdrant::SearchMatrixPairs {
+    fn from(response: SearchMatrixPairsResponse) -> Self {
+        Self {
+            pairs: response.pairs.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+impl From<SearchMatrixOffsetsResponse> for api::grpc::qdrant::SearchMatrixOffsets {
+    fn from(response: SearchMatrixOffsetsResponse) -> Self {
+        let SearchMatrixOffsetsResponse {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids,
+        } = response;
+        Self {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids: ids.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
     type Error = Status;
     fn try_from(value: api::grpc::qdrant::SearchPoints) -> Result<Self, Self::Error> {
@@ -1634,6 +1861,8 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
                 Distance::Euclid => api::grpc::qdrant::Distance::Euclid,
                 Distance::Dot => api::grpc::qdrant::Distance::Dot,
                 Distance::Manhattan => api::grpc::qdrant::Distance::Manhattan,
+                Distance::Hamming => api::grpc::qdrant::Distance::Hamming,
+                Distance::Jaccard => api::grpc::qdrant::Distance::Jaccard,
             }
             .into(),
             hnsw_config: value.hnsw_config.map(Into::into),
@@ -1655,6 +1884,8 @@ impl From<Datatype> for api::grpc::qdrant::Datatype {
             Datatype::Float32 => api::grpc::qdrant::Datatype::Float32,
             Datatype::Uint8 => api::grpc::qdrant::Datatype::Uint8,
             Datatype::Float16 => api::grpc::qdrant::Datatype::Float16,
+            Datatype::Bfloat16 => api::grpc::qdrant::Datatype::Bfloat16,
+            Datatype::Bit => api::grpc::qdrant::Datatype::Bit,
         }
     }
 }
@@ -0,0 +1,147 @@
+//! Expiration of points in collections with a time-to-live
+//!
+//! The moment a point expires at is stored in the reserved [`EXPIRES_AT_KEY`] payload key, in
+//! seconds since epoch. It is assigned to upserted points once, when the operation enters the
+//! collection, so that all replicas apply exactly the same payload.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use segment::json_path::JsonPath;
+use segment::types::{Condition, FieldCondition, Filter, Payload, Range};
+use serde_json::Value;
+
+use crate::operations::payload_ops::PayloadOps;
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations};
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::universal_query::shard_query::{ShardPrefetch, ShardQueryRequest};
+use crate::operations::CollectionUpdateOperations;
+
+/// Reserved payload key, which holds the time a point expires at, in seconds since epoch
+pub const EXPIRES_AT_KEY: &str = "_expires_at";
+
+/// Current time in seconds since epoch
+pub fn now_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Path of the reserved payload key, which holds the expiration time
+pub fn expires_at_path() -> JsonPath {
+    JsonPath {
+        first_key: EXPIRES_AT_KEY.to_string(),
+        rest: Vec::new(),
+    }
+}
+
+fn expires_at_condition(now: u64) -> Condition {
+    Condition::Field(FieldCondition::new_range(
+        expires_at_path(),
+        Range {
+            lt: Some(now as f64),
+            gt: None,
+            gte: None,
+            lte: None,
+        },
+    ))
+}
+
+/// Filter, which selects points that are expired at the given time
+pub fn expired_filter(now: u64) -> Filter {
+    Filter::new_must(expires_at_condition(now))
+}
+
+/// Extend the filter, so that it excludes points that are expired at the given time
+pub fn exclude_expired(filter: Option<&Filter>, now: u64) -> Filter {
+    let not_expired = Filter::new_must_not(expires_at_condition(now));
+    match filter {
+        Some(filter) => not_expired.merge(filter),
+        None => not_expired,
+    }
+}
+
+/// Extend all filters of the query, so that expired points are excluded on every stage
+pub fn exclude_expired_from_query(request: &mut ShardQueryRequest, now: u64) {
+    fn exclude_from_prefetch(prefetch: &mut ShardPrefetch, now: u64) {
+        prefetch.filter = Some(exclude_expired(prefetch.filter.as_ref(), now));
+        for prefetch in &mut prefetch.prefetches {
+            exclude_from_prefetch(prefetch, now);
+        }
+    }
+
+    request.filter = Some(exclude_expired(request.filter.as_ref(), now));
+    for prefetch in &mut request.prefetches {
+        exclude_from_prefetch(prefetch, now);
+    }
+}
+
+/// Assign the expiration time to all points upserted by the operation
+///
+/// Points, which already have the expiration time in their payload, keep it. This allows to set
+/// a custom expiration time per point.
+pub fn set_expiration(operation: &mut CollectionUpdateOperations, ttl_sec: u64, now: u64) {
+    let insert_operation = match operation {
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(operation)) => {
+            operation
+        }
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsConditional(
+            operation,
+        )) => &mut operation.points_op,
+        _ => return,
+    };
+
+    let expires_at = Value::from(now.saturating_add(ttl_sec));
+
+    let set_expires_at = |payload: &mut Option<Payload>| {
+        payload
+            .get_or_insert_with(Payload::default)
+            .0
+            .entry(EXPIRES_AT_KEY)
+            .or_insert_with(|| expires_at.clone());
+    };
+
+    match insert_operation {
+        PointInsertOperationsInternal::PointsBatch(batch) => {
+            let payloads = batch
+                .payloads
+                .get_or_insert_with(|| vec![None; batch.ids.len()]);
+            payloads.iter_mut().for_each(set_expires_at);
+        }
+        PointInsertOperationsInternal::PointsList(points) => {
+            points
+                .iter_mut()
+                .for_each(|point| set_expires_at(&mut point.payload));
+        }
+    }
+}
+
+/// Check that the operation does not drop the expiration time of points
+///
+/// Clearing or overwriting the whole payload would remove the expiration time, so that points
+/// would never expire. Overwrites are only allowed if they set the expiration time explicitly.
+pub fn check_keeps_expiration(operation: &CollectionUpdateOperations) -> CollectionResult<()> {
+    let CollectionUpdateOperations::PayloadOperation(payload_operation) = operation else {
+        return Ok(());
+    };
+
+    match payload_operation {
+        PayloadOps::ClearPayload { .. } | PayloadOps::ClearPayloadByFilter(_) => {
+            Err(CollectionError::bad_request(format!(
+                "Can't clear payload in a collection with time-to-live, as it removes the \
+                 `{EXPIRES_AT_KEY}` key. Delete specific payload keys instead"
+            )))
+        }
+        PayloadOps::OverwritePayload(operation)
+            if !operation.payload.0.contains_key(EXPIRES_AT_KEY) =>
+        {
+            Err(CollectionError::bad_request(format!(
+                "Can't overwrite payload in a collection with time-to-live without the \
+                 `{EXPIRES_AT_KEY}` key. Set it explicitly or use set payload instead"
+            )))
+        }
+        PayloadOps::SetPayload(_)
+        | PayloadOps::DeletePayload(_)
+        | PayloadOps::OverwritePayload(_) => Ok(()),
+    }
+}
@@ -3,6 +3,7 @@ pub mod config_diff;
 pub mod consistency_params;
 pub mod conversions;
 pub mod conversions_rest;
+pub mod expiration;
 pub mod operation_effect;
 pub mod payload_ops;
 pub mod point_ops;
@@ -16,6 +17,7 @@ pub mod universal_query;
 pub mod validation;
 pub mod vector_ops;
 pub mod vector_params_builder;
+pub mod verification;
 
 use std::collections::HashMap;
 
@@ -379,6 +381,11 @@ mod tests {
 
         fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
             let upsert = Self::UpsertPoints(PointInsertOperationsInternal::PointsList(Vec::new()));
+            let upsert_conditional =
+                Self::UpsertPointsConditional(ConditionalInsertOperationInternal::new(
+                    PointInsertOperationsInternal::PointsList(Vec::new()),
+                    Filter::default(),
+                ));
             let delete = Self::DeletePoints { ids: Vec::new() };
 
             let delete_by_filter = Self::DeletePointsByFilter(Filter {
@@ -396,6 +403,7 @@ mod tests {
 
             prop_oneof![
                 Just(upsert),
+                Just(upsert_conditional),
                 Just(delete),
                 Just(delete_by_filter),
                 Just(sync),
@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use api::rest::{BatchVectorStruct, ShardKeySelector, VectorStruct};
 use itertools::izip;
@@ -155,6 +155,11 @@ pub struct PointsBatch {
     pub batch: Batch,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// If specified, only existing points which match this filter are updated, others are skipped.
+    /// New points are always inserted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub update_filter: Option<Filter>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, JsonSchema, Validate)]
@@ -163,6 +168,11 @@ pub struct PointsList {
     pub points: Vec<PointStruct>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// If specified, only existing points which match this filter are updated, others are skipped.
+    /// New points are always inserted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub update_filter: Option<Filter>,
 }
 
 impl<'de> serde::Deserialize<'de> for PointInsertOperations {
@@ -213,10 +223,20 @@ impl Validate for PointInsertOperations {
 }
 
 impl PointInsertOperations {
-    pub fn decompose(self) -> (Option<ShardKeySelector>, PointInsertOperationsInternal) {
+    pub fn decompose(
+        self,
+    ) -> (
+        Option<ShardKeySelector>,
+        PointInsertOperationsInternal,
+        Option<Filter>,
+    ) {
         match self {
-            PointInsertOperations::PointsBatch(batch) => (batch.shard_key, batch.batch.into()),
-            PointInsertOperations::PointsList(list) => (list.shard_key, list.points.into()),
+            PointInsertOperations::PointsBatch(batch) => {
+                (batch.shard_key, batch.batch.into(), batch.update_filter)
+            }
+            PointInsertOperations::PointsList(list) => {
+                (list.shard_key, list.points.into(), list.update_filter)
+            }
         }
     }
 }
@@ -311,6 +331,7 @@ impl From<Batch> for PointInsertOperations {
         PointInsertOperations::PointsBatch(PointsBatch {
             batch,
             shard_key: None,
+            update_filter: None,
         })
     }
 }
@@ -320,6 +341,7 @@ impl From<Vec<PointStruct>> for PointInsertOperations {
         PointInsertOperations::PointsList(PointsList {
             points,
             shard_key: None,
+            update_filter: None,
         })
     }
 }
@@ -336,12 +358,46 @@ impl From<Vec<PointStruct>> for PointInsertOperationsInternal {
     }
 }
 
+/// Upsert, which only updates existing points matching the condition
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ConditionalInsertOperationInternal {
+    pub points_op: PointInsertOperationsInternal,
+    /// Condition existing points must match to be updated, non-matching points are skipped.
+    /// Points which do not exist yet are always inserted.
+    pub condition: Filter,
+}
+
+impl ConditionalInsertOperationInternal {
+    pub fn new(points_op: PointInsertOperationsInternal, condition: Filter) -> Self {
+        Self {
+            points_op,
+            condition,
+        }
+    }
+
+    /// Number of distinct points to upsert
+    pub fn points_count(&self) -> usize {
+        let ids: HashSet<PointIdType> = match &self.points_op {
+            PointInsertOperationsInternal::PointsBatch(batch) => {
+                batch.ids.iter().copied().collect()
+            }
+            PointInsertOperationsInternal::PointsList(points) => {
+                points.iter().map(|point| point.id).collect()
+            }
+        };
+        ids.len()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants)]
 #[strum_discriminants(derive(EnumIter))]
 #[serde(rename_all = "snake_case")]
 pub enum PointOperations {
     /// Insert or update points
     UpsertPoints(PointInsertOperationsInternal),
+    /// Insert new points, update only existing points which match the condition
+    UpsertPointsConditional(ConditionalInsertOperationInternal),
     /// Delete point if exists
     DeletePoints { ids: Vec<PointIdType> },
     /// Delete points by given filter criteria
@@ -354,6 +410,7 @@ impl PointOperations {
     pub fn is_write_operation(&self) -> bool {
         match self {
             PointOperations::UpsertPoints(_) => true,
+            PointOperations::UpsertPointsConditional(_) => true,
             PointOperations::DeletePoints { .. } => false,
             PointOperations::DeletePointsByFilter(_) => false,
             PointOperations::SyncPoints(_) => true,
@@ -365,6 +422,10 @@ impl Validate for PointOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
             PointOperations::UpsertPoints(upsert_points) => upsert_points.validate(),
+            PointOperations::UpsertPointsConditional(conditional_upsert) => {
+                conditional_upsert.points_op.validate()?;
+                conditional_upsert.condition.validate()
+            }
             PointOperations::DeletePoints { ids: _ } => Ok(()),
             PointOperations::DeletePointsByFilter(_) => Ok(()),
             PointOperations::SyncPoints(_) => Ok(()),
@@ -496,6 +557,15 @@ impl SplitByShard for PointOperations {
             PointOperations::UpsertPoints(upsert_points) => upsert_points
                 .split_by_shard(ring)
                 .map(PointOperations::UpsertPoints),
+            PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op,
+                condition,
+            }) => points_op.split_by_shard(ring).map(|points_op| {
+                PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op,
+                    condition: condition.clone(),
+                })
+            }),
             PointOperations::DeletePoints { ids } => split_iter_by_shard(ids, |id| *id, ring)
                 .map(|ids| PointOperations::DeletePoints { ids }),
             by_filter @ PointOperations::DeletePointsByFilter(_) => {
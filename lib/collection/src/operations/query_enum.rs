@@ -1,7 +1,11 @@
 use segment::data_types::vectors::{DenseVector, Named, NamedQuery, NamedVectorStruct, Vector};
+use segment::types::Order;
 use segment::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery};
 use sparse::common::sparse_vector::SparseVector;
 
+use crate::config::CollectionParams;
+use crate::operations::types::CollectionResult;
+
 impl QueryEnum {
     pub fn get_vector_name(&self) -> &str {
         match self {
@@ -22,6 +26,17 @@ impl QueryEnum {
         }
     }
 
+    /// Returns the expected order of the scores, depending on the distance of the vector
+    pub fn order(&self, collection_params: &CollectionParams) -> CollectionResult<Order> {
+        if self.is_distance_scored() {
+            Ok(collection_params
+                .get_distance(self.get_vector_name())?
+                .distance_order())
+        } else {
+            Ok(Order::LargeBetter)
+        }
+    }
+
     pub fn iterate_sparse(&self, mut f: impl FnMut(&str, &SparseVector)) {
         match self {
             QueryEnum::Nearest(vector) => match vector {
@@ -17,6 +17,7 @@ use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_error::OperationError;
+use segment::data_types::facets::FacetParams;
 use segment::data_types::groups::GroupId;
 use segment::data_types::vectors::{
     DenseVector, QueryVector, VectorRef, VectorStruct, DEFAULT_VECTOR_NAME,
@@ -273,12 +274,34 @@ pub struct UpdateResult {
     /// Update status
     pub status: UpdateStatus,
 
+    /// Number of inserted or updated points
+    /// Provided for completed upserts with `update_filter`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_count: Option<usize>,
+
+    /// Number of existing points, which were not updated because they don't match `update_filter`
+    /// Provided for completed upserts with `update_filter`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped_count: Option<usize>,
+
     /// Updated value for the external clock tick
     /// Provided if incoming update request also specify clock tick
     #[serde(skip)]
     pub clock_tag: Option<ClockTag>,
 }
 
+impl UpdateResult {
+    /// Add up the point counts of the results of the same operation in another shard
+    pub fn merge_counts(&mut self, other: &UpdateResult) {
+        let add = |count: Option<usize>, other: Option<usize>| match (count, other) {
+            (None, None) => None,
+            (count, other) => Some(count.unwrap_or(0) + other.unwrap_or(0)),
+        };
+        self.updated_count = add(self.updated_count, other.updated_count);
+        self.skipped_count = add(self.skipped_count, other.skipped_count);
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct ScrollRequest {
@@ -829,6 +852,209 @@ pub struct CountResult {
     pub count: usize,
 }
 
+pub const DEFAULT_FACET_LIMIT: usize = 10;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub facet_request: FacetRequestInternal,
+    /// Specify in which shards to look for the values, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Facet Request
+/// Counts the number of points for each distinct value of the given payload key.
+/// The key must be indexed with a `keyword`, `integer` or `bool` index.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequestInternal {
+    /// Payload key to count the values of
+    pub key: JsonPath,
+    /// Max number of values to return, the most frequent values go first. Default: 10
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+    /// Count only the points which satisfy these conditions
+    #[validate]
+    pub filter: Option<Filter>,
+}
+
+impl From<FacetRequestInternal> for FacetParams {
+    fn from(request: FacetRequestInternal) -> Self {
+        let FacetRequestInternal { key, limit, filter } = request;
+        FacetParams {
+            key,
+            limit: limit.unwrap_or(DEFAULT_FACET_LIMIT),
+            filter,
+        }
+    }
+}
+
+pub const DEFAULT_SEARCH_MATRIX_SAMPLE: usize = 10;
+
+pub const DEFAULT_SEARCH_MATRIX_LIMIT: usize = 3;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub search_matrix_request: SearchMatrixRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Search Matrix Request
+/// Samples points which satisfy the filter and finds the nearest neighbours of each of them
+/// within the sample.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixRequestInternal {
+    /// Sample only the points which satisfy these conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// How many points to select and search within. Default: 10
+    #[validate(range(min = 2, max = 10000))]
+    pub sample: Option<usize>,
+    /// How many neighbours per sample to find. Default: 3
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+    /// Define which vector name to use for querying. If missing, the default vector is used.
+    pub using: Option<String>,
+}
+
+/// Parameters of a search matrix request, with defaults applied
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionSearchMatrixRequest {
+    pub sample_size: usize,
+    pub limit_per_sample: usize,
+    pub filter: Option<Filter>,
+    pub using: String,
+}
+
+impl From<SearchMatrixRequestInternal> for CollectionSearchMatrixRequest {
+    fn from(request: SearchMatrixRequestInternal) -> Self {
+        let SearchMatrixRequestInternal {
+            filter,
+            sample,
+            limit,
+            using,
+        } = request;
+        CollectionSearchMatrixRequest {
+            sample_size: sample.unwrap_or(DEFAULT_SEARCH_MATRIX_SAMPLE),
+            limit_per_sample: limit.unwrap_or(DEFAULT_SEARCH_MATRIX_LIMIT),
+            filter,
+            using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string()),
+        }
+    }
+}
+
+/// Pair of points with their similarity score
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixPair {
+    /// Id of the sampled point
+    pub a: PointIdType,
+    /// Id of its neighbour in the sample
+    pub b: PointIdType,
+    /// Similarity score between the points
+    pub score: ScoreType,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixPairsResponse {
+    /// List of pairs of points with scores
+    pub pairs: Vec<SearchMatrixPair>,
+}
+
+/// Sparse matrix of scores in the coordinate format, indexing into the list of sampled ids
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixOffsetsResponse {
+    /// Row indices of the matrix
+    pub offsets_row: Vec<u64>,
+    /// Column indices of the matrix
+    pub offsets_col: Vec<u64>,
+    /// Scores associated with matrix coordinates
+    pub scores: Vec<ScoreType>,
+    /// Ids of the sampled points
+    pub ids: Vec<PointIdType>,
+}
+
+pub const DEFAULT_RECALL_ESTIMATION_SAMPLE: usize = 16;
+
+pub const DEFAULT_RECALL_ESTIMATION_LIMIT: usize = 10;
+
+pub const MAX_RECALL_ESTIMATION_SAMPLE: usize = 1000;
+
+pub const MAX_RECALL_ESTIMATION_LIMIT: usize = 100;
+
+/// Recall Estimation Request
+/// Samples points of each indexed segment and searches their nearest neighbours both with the
+/// index and exactly. Recall is the share of exact nearest neighbours found by the index.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct RecallEstimationRequest {
+    /// Define which vector name to estimate recall for. If missing, all dense vectors are used.
+    pub using: Option<String>,
+    /// How many points of each segment to use as queries. Default: 16, maximum: 1000
+    #[validate(range(min = 1, max = 1000))]
+    pub sample: Option<usize>,
+    /// How many nearest neighbours to compare, recall@k is estimated for this k. Default: 10, maximum: 100
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<usize>,
+    /// Params of the approximate search. If missing, the defaults of regular search are used.
+    #[validate]
+    pub params: Option<SearchParams>,
+}
+
+/// Recall of the approximate search, estimated on a single segment
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentRecallEstimation {
+    /// UUID of the segment
+    pub segment: String,
+    /// Name of the estimated vector
+    pub vector_name: String,
+    /// Number of sampled points used as queries
+    pub sample: usize,
+    /// Number of compared nearest neighbours
+    pub limit: usize,
+    /// Share of exact nearest neighbours found by the approximate search, in range [0, 1]
+    pub recall: f64,
+}
+
+impl Anonymize for SegmentRecallEstimation {
+    fn anonymize(&self) -> Self {
+        Self {
+            segment: self.segment.clone(),
+            vector_name: self.vector_name.anonymize(),
+            sample: self.sample,
+            limit: self.limit,
+            recall: self.recall,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardRecallEstimation {
+    pub shard_id: ShardId,
+    /// Recall of each indexed segment and vector of the shard
+    pub segments: Vec<SegmentRecallEstimation>,
+}
+
+/// Recall of the approximate search, estimated on local shards of the collection
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionRecallEstimation {
+    pub shards: Vec<ShardRecallEstimation>,
+}
+
 #[derive(Error, Debug, Clone, PartialEq)]
 #[error("{0}")]
 pub enum CollectionError {
@@ -867,6 +1093,10 @@ pub enum CollectionError {
     PreConditionFailed { description: String },
     #[error("Object Store error: {what}")]
     ObjectStoreError { what: String },
+    #[error("Out of range: {description}")]
+    OutOfRange { description: String },
+    #[error("Strict mode error: {description}")]
+    StrictMode { description: String },
 }
 
 impl CollectionError {
@@ -941,6 +1171,19 @@ impl CollectionError {
         }
     }
 
+    pub fn out_of_range(description: impl Into<String>) -> CollectionError {
+        CollectionError::OutOfRange {
+            description: description.into(),
+        }
+    }
+
+    /// Request rejected by the strict mode of the collection, `solution` tells how to comply
+    pub fn strict_mode(error: impl Into<String>, solution: impl Into<String>) -> CollectionError {
+        CollectionError::StrictMode {
+            description: format!("{}. Help: {}", error.into(), solution.into()),
+        }
+    }
+
     /// Returns true if the error is transient and the operation can be retried.
     /// Returns false if the error is not transient and the operation should fail on all replicas.
     pub fn is_transient(&self) -> bool {
@@ -960,6 +1203,8 @@ impl CollectionError {
             Self::InconsistentShardFailure { .. } => false,
             Self::ForwardProxyError { .. } => false,
             Self::ObjectStoreError { .. } => false,
+            Self::OutOfRange { .. } => false,
+            Self::StrictMode { .. } => false,
         }
     }
 }
@@ -1129,6 +1374,9 @@ impl From<tonic::Status> for CollectionError {
             tonic::Code::FailedPrecondition => CollectionError::PreConditionFailed {
                 description: format!("{err}"),
             },
+            tonic::Code::OutOfRange => CollectionError::OutOfRange {
+                description: format!("{err}"),
+            },
             _other => CollectionError::ServiceError {
                 error: format!("Tonic status error: {err}"),
                 backtrace: Some(Backtrace::force_capture().to_string()),
@@ -1235,11 +1483,15 @@ impl Record {
 /// Choosing different datatypes allows to optimize memory usage and performance vs accuracy.
 /// - For `float32` datatype - vectors are stored as single-precision floating point numbers, 4bytes.
 /// - For `uint8` datatype - vectors are stored as unsigned 8-bit integers, 1byte. It expects vector elements to be in range `[0, 255]`.
+/// - For `bfloat16` datatype - vectors are stored as brain floating point numbers, 2bytes. Keeps the value range of `float32` at a reduced precision.
+/// - For `bit` datatype - vectors are stored as packed bits, 8 dimensions per byte. Positive vector elements are stored as `1`, others as `0`. Requires `Hamming` or `Jaccard` distance.
 pub enum Datatype {
     #[default]
     Float32,
     Uint8,
     Float16,
+    Bfloat16,
+    Bit,
 }
 
 impl From<Datatype> for VectorStorageDatatype {
@@ -1248,12 +1500,15 @@ impl From<Datatype> for VectorStorageDatatype {
             Datatype::Float32 => VectorStorageDatatype::Float32,
             Datatype::Uint8 => VectorStorageDatatype::Uint8,
             Datatype::Float16 => VectorStorageDatatype::Float16,
+            Datatype::Bfloat16 => VectorStorageDatatype::Bfloat16,
+            Datatype::Bit => VectorStorageDatatype::Bit,
         }
     }
 }
 
 /// Params of single vector data storage
 #[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
+#[validate(schema(function = "validate_vector_params_datatype"))]
 #[serde(rename_all = "snake_case")]
 pub struct VectorParams {
     /// Size of a vectors used
@@ -1292,6 +1547,40 @@ pub fn validate_nonzerou64_range_min_1_max_65536(
     validate_range_generic(value.get(), Some(1), Some(65536))
 }
 
+/// Binary distances and the `bit` datatype can only be used together
+fn validate_vector_params_datatype(params: &VectorParams) -> Result<(), ValidationError> {
+    let is_bit_datatype = params.datatype == Some(Datatype::Bit);
+    if params.distance.is_binary() && !is_bit_datatype {
+        return Err(ValidationError::new(
+            "Hamming and Jaccard distances require bit datatype",
+        ));
+    }
+    if !is_bit_datatype {
+        return Ok(());
+    }
+    if !params.distance.is_binary() {
+        return Err(ValidationError::new(
+            "Bit datatype requires Hamming or Jaccard distance",
+        ));
+    }
+    if params.size.get() % 8 != 0 {
+        return Err(ValidationError::new(
+            "Size of bit vectors must be a multiple of 8",
+        ));
+    }
+    if params.multivec_config.is_some() {
+        return Err(ValidationError::new(
+            "Multivectors are not supported for bit datatype",
+        ));
+    }
+    if params.quantization_config.is_some() {
+        return Err(ValidationError::new(
+            "Quantization is not supported for bit datatype",
+        ));
+    }
+    Ok(())
+}
+
 /// Is considered empty if `None` or if diff has no field specified
 fn is_hnsw_diff_empty(hnsw_config: &Option<HnswConfigDiff>) -> bool {
     hnsw_config
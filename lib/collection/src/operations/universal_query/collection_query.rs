@@ -4,6 +4,7 @@ use api::rest::RecommendStrategy;
 use common::types::ScoreType;
 use itertools::Itertools;
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::text_query::TextQuery;
 use segment::data_types::vectors::{
     MultiDenseVector, NamedQuery, NamedVectorStruct, Vector, VectorRef, DEFAULT_VECTOR_NAME,
 };
@@ -12,7 +13,8 @@ use segment::types::{
 };
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
 
-use super::shard_query::{Fusion, ScoringQuery, ShardPrefetch, ShardQueryRequest};
+use super::formula::FormulaInternal;
+use super::shard_query::{Fusion, Sample, ScoringQuery, ShardPrefetch, ShardQueryRequest};
 use crate::common::fetch_vectors::ReferencedVectors;
 use crate::common::retrieve_request_trait::RetrieveRequest;
 use crate::operations::query_enum::QueryEnum;
@@ -32,6 +34,8 @@ pub struct CollectionQueryRequest {
     pub params: Option<SearchParams>,
     pub with_vector: WithVector,
     pub with_payload: WithPayloadInterface,
+    /// Diversify the results of a nearest query
+    pub mmr: Option<Mmr>,
 }
 
 impl CollectionQueryRequest {
@@ -44,6 +48,24 @@ impl CollectionQueryRequest {
     const DEFAULT_WITH_PAYLOAD: WithPayloadInterface = WithPayloadInterface::Bool(false);
 }
 
+/// Maximal Marginal Relevance parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mmr {
+    /// Trade-off between relevance (0) and diversity (1)
+    pub diversity: f32,
+    /// Number of nearest neighbours to select the results from, if it is bigger than `limit` + `offset`
+    pub candidates_limit: Option<usize>,
+}
+
+impl Mmr {
+    /// Number of candidates to fetch, so that there are enough to fill `limit` + `offset`
+    pub fn candidates_limit(&self, limit: usize, offset: usize) -> usize {
+        let take = limit + offset;
+        self.candidates_limit
+            .map_or(take, |candidates| candidates.max(take))
+    }
+}
+
 pub enum Query {
     /// Score points against some vector(s)
     Vector(VectorQuery<VectorInput>),
@@ -53,6 +75,15 @@ pub enum Query {
 
     /// Order by a payload field
     OrderBy(OrderBy),
+
+    /// Rescore the prefetches with a formula
+    Formula(FormulaInternal),
+
+    /// Rank by BM25 relevance of a full-text indexed field
+    Text(TextQuery),
+
+    /// Sample points randomly
+    Sample(Sample),
 }
 
 impl Query {
@@ -75,6 +106,9 @@ impl Query {
             }
             Query::Fusion(fusion) => ScoringQuery::Fusion(fusion),
             Query::OrderBy(order_by) => ScoringQuery::OrderBy(order_by),
+            Query::Formula(formula) => ScoringQuery::Formula(formula.parse()?),
+            Query::Text(text_query) => ScoringQuery::Text(text_query),
+            Query::Sample(sample) => ScoringQuery::Sample(sample),
         };
 
         Ok(scoring_query)
@@ -353,6 +387,14 @@ impl CollectionQueryRequest {
             ));
         }
 
+        // Check MMR is applied to a nearest query
+        if self.mmr.is_some() && !matches!(self.query, Some(Query::Vector(VectorQuery::Nearest(_))))
+        {
+            return Err(CollectionError::bad_request(
+                "MMR can only be applied to a `nearest` query",
+            ));
+        }
+
         // Check we actually fetched all referenced vectors in this request (and nested prefetches)
         for &point_id in &(&self).get_referenced_point_ids() {
             if ids_to_vectors.get(&None, point_id).is_none() {
@@ -408,6 +450,8 @@ impl CollectionQueryRequest {
 
 mod from_rest {
     use api::rest::schema as rest;
+    use segment::common::maximal_marginal_relevance::DEFAULT_MMR_DIVERSITY;
+    use segment::common::reciprocal_rank_fusion::DEFAULT_RRF_K;
 
     use super::*;
 
@@ -424,6 +468,7 @@ mod from_rest {
                 offset,
                 with_vector,
                 with_payload,
+                mmr,
             } = value;
 
             Self {
@@ -437,6 +482,21 @@ mod from_rest {
                 params,
                 with_vector: with_vector.unwrap_or(Self::DEFAULT_WITH_VECTOR),
                 with_payload: with_payload.unwrap_or(Self::DEFAULT_WITH_PAYLOAD),
+                mmr: mmr.map(From::from),
+            }
+        }
+    }
+
+    impl From<rest::Mmr> for Mmr {
+        fn from(value: rest::Mmr) -> Self {
+            let rest::Mmr {
+                diversity,
+                candidates_limit,
+            } = value;
+
+            Self {
+                diversity: diversity.unwrap_or(DEFAULT_MMR_DIVERSITY),
+                candidates_limit,
             }
         }
     }
@@ -482,6 +542,13 @@ mod from_rest {
                 rest::Query::Context(context) => Query::Vector(From::from(context)),
                 rest::Query::OrderBy(order_by) => Query::OrderBy(OrderBy::from(order_by)),
                 rest::Query::Fusion(fusion) => Query::Fusion(Fusion::from(fusion)),
+                rest::Query::Rrf(rrf) => Query::Fusion(Fusion::from(rrf)),
+                rest::Query::Weighted(weighted) => Query::Fusion(Fusion::from(weighted)),
+                rest::Query::Formula(formula) => Query::Formula(FormulaInternal::from(formula)),
+                rest::Query::Text(rest::TextQuery { key, text }) => {
+                    Query::Text(TextQuery { key, text })
+                }
+                rest::Query::Sample(rest::Sample::Random) => Query::Sample(Sample::Random),
             }
         }
     }
@@ -547,6 +614,9 @@ mod from_rest {
                     // TODO(universal-query): Validate at API level
                     Vector::MultiDense(MultiDenseVector::new_unchecked(multi_dense)),
                 ),
+                rest::VectorInput::Document(document) => {
+                    VectorInput::Vector(Vector::Sparse(document.to_sparse_query()))
+                }
             }
         }
     }
@@ -564,15 +634,33 @@ mod from_rest {
     impl From<rest::Fusion> for Fusion {
         fn from(value: rest::Fusion) -> Self {
             match value {
-                rest::Fusion::Rrf => Fusion::Rrf,
+                rest::Fusion::Rrf => Fusion::Rrf { k: DEFAULT_RRF_K },
+                rest::Fusion::Dbsf => Fusion::Dbsf,
             }
         }
     }
+
+    impl From<rest::Rrf> for Fusion {
+        fn from(value: rest::Rrf) -> Self {
+            let rest::Rrf { k } = value;
+            Fusion::Rrf {
+                k: k.unwrap_or(DEFAULT_RRF_K),
+            }
+        }
+    }
+
+    impl From<rest::WeightedFusion> for Fusion {
+        fn from(value: rest::WeightedFusion) -> Self {
+            let rest::WeightedFusion { weights } = value;
+            Fusion::Weighted { weights }
+        }
+    }
 }
 
 mod from_grpc {
     use api::grpc::qdrant::{self as grpc};
     use api::rest::ShardKeySelector;
+    use segment::common::maximal_marginal_relevance::DEFAULT_MMR_DIVERSITY;
     use tonic::Status;
 
     use super::*;
@@ -604,6 +692,7 @@ mod from_grpc {
                 with_vectors,
                 read_consistency,
                 shard_key_selector,
+                mmr,
             } = value;
 
             let request = CollectionQueryRequest {
@@ -629,6 +718,7 @@ mod from_grpc {
                     .map(TryFrom::try_from)
                     .transpose()?
                     .unwrap_or(CollectionQueryRequest::DEFAULT_WITH_PAYLOAD),
+                mmr: mmr.map(TryFrom::try_from).transpose()?,
             };
 
             let shard_key =
@@ -645,6 +735,35 @@ mod from_grpc {
         }
     }
 
+    impl TryFrom<grpc::Mmr> for Mmr {
+        type Error = Status;
+
+        fn try_from(value: grpc::Mmr) -> Result<Self, Self::Error> {
+            let grpc::Mmr {
+                diversity,
+                candidates_limit,
+            } = value;
+
+            let diversity = diversity.unwrap_or(DEFAULT_MMR_DIVERSITY);
+            if !(0.0..=1.0).contains(&diversity) {
+                return Err(Status::invalid_argument(
+                    "MMR diversity must be in the range [0, 1]",
+                ));
+            }
+
+            if candidates_limit == Some(0) {
+                return Err(Status::invalid_argument(
+                    "MMR candidates_limit must be at least 1",
+                ));
+            }
+
+            Ok(Self {
+                diversity,
+                candidates_limit: candidates_limit.map(|limit| limit as usize),
+            })
+        }
+    }
+
     impl TryFrom<grpc::PrefetchQuery> for CollectionPrefetch {
         type Error = Status;
 
@@ -697,6 +816,11 @@ mod from_grpc {
                 Variant::Context(context) => Query::Vector(TryFrom::try_from(context)?),
                 Variant::OrderBy(order_by) => Query::OrderBy(OrderBy::try_from(order_by)?),
                 Variant::Fusion(fusion) => Query::Fusion(Fusion::try_from(fusion)?),
+                Variant::Rrf(rrf) => Query::Fusion(Fusion::from(rrf)),
+                Variant::Weighted(weighted) => Query::Fusion(Fusion::from(weighted)),
+                Variant::Formula(formula) => Query::Formula(FormulaInternal::try_from(formula)?),
+                Variant::Text(text_query) => Query::Text(TextQuery::try_from(text_query)?),
+                Variant::Sample(sample) => Query::Sample(Sample::try_from(sample)?),
             };
 
             Ok(query)
@@ -789,6 +913,10 @@ mod from_grpc {
                     // TODO(universal-query): Validate at API level
                     Vector::MultiDense(From::from(multi_dense)),
                 ),
+                Variant::Document(document) => {
+                    let document = segment::data_types::bm25::Document::try_from(document)?;
+                    VectorInput::Vector(Vector::Sparse(document.to_sparse_query()))
+                }
             };
 
             Ok(vector_input)
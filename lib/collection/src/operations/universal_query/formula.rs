@@ -0,0 +1,542 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use segment::index::rescore_formula::parsed_formula::{
+    DecayKind, ParsedExpression, ParsedFormula, PreciseScore, VariableId, DEFAULT_DECAY_MIDPOINT,
+    DEFAULT_DECAY_SCALE,
+};
+use segment::json_path::JsonPath;
+use segment::types::{Condition, DateTimePayloadType, GeoPoint};
+use serde_json::Value;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Datetime which is evaluated as the time the request is received
+const DATETIME_NOW: &str = "now";
+
+/// Formula to rescore the prefetches with, as received from the API.
+///
+/// Validated and turned into a [`ParsedFormula`] when the request is converted into a shard request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormulaInternal {
+    pub formula: ExpressionInternal,
+    pub defaults: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionInternal {
+    Constant(f32),
+    Variable(String),
+    Condition(Box<Condition>),
+    GeoDistance {
+        origin: GeoPoint,
+        key: JsonPath,
+    },
+    Datetime(String),
+    DatetimeKey(JsonPath),
+    Mult(Vec<ExpressionInternal>),
+    Sum(Vec<ExpressionInternal>),
+    Neg(Box<ExpressionInternal>),
+    Div {
+        left: Box<ExpressionInternal>,
+        right: Box<ExpressionInternal>,
+        by_zero_default: Option<f32>,
+    },
+    Sqrt(Box<ExpressionInternal>),
+    Pow {
+        base: Box<ExpressionInternal>,
+        exponent: Box<ExpressionInternal>,
+    },
+    Exp(Box<ExpressionInternal>),
+    Log10(Box<ExpressionInternal>),
+    Ln(Box<ExpressionInternal>),
+    Abs(Box<ExpressionInternal>),
+    Decay {
+        kind: DecayKind,
+        x: Box<ExpressionInternal>,
+        target: Option<Box<ExpressionInternal>>,
+        midpoint: Option<f32>,
+        scale: Option<f32>,
+    },
+}
+
+impl FormulaInternal {
+    /// Validate the formula, and convert it into the representation executed by the segments
+    pub fn parse(self) -> CollectionResult<ParsedFormula> {
+        let FormulaInternal { formula, defaults } = self;
+
+        let mut conditions = Vec::new();
+        let formula = formula.parse(&mut conditions)?;
+
+        let defaults = defaults
+            .into_iter()
+            .map(|(key, value)| {
+                let variable = VariableId::from_str(&key).map_err(CollectionError::bad_input)?;
+                Ok((variable, value))
+            })
+            .collect::<CollectionResult<_>>()?;
+
+        Ok(ParsedFormula {
+            conditions,
+            defaults,
+            formula,
+        })
+    }
+}
+
+impl ExpressionInternal {
+    /// Conditions are moved into `conditions`, and referenced by their index in the parsed expression
+    fn parse(self, conditions: &mut Vec<Condition>) -> CollectionResult<ParsedExpression> {
+        let parsed = match self {
+            ExpressionInternal::Constant(constant) => {
+                ParsedExpression::Constant(PreciseScore::from(constant))
+            }
+            ExpressionInternal::Variable(variable) => ParsedExpression::Variable(
+                VariableId::from_str(&variable).map_err(CollectionError::bad_input)?,
+            ),
+            ExpressionInternal::Condition(condition) => {
+                let index = conditions.len();
+                conditions.push(*condition);
+                ParsedExpression::Condition(index)
+            }
+            ExpressionInternal::GeoDistance { origin, key } => {
+                ParsedExpression::GeoDistance { origin, key }
+            }
+            ExpressionInternal::Datetime(datetime) => {
+                ParsedExpression::Datetime(parse_datetime(&datetime)?)
+            }
+            ExpressionInternal::DatetimeKey(key) => ParsedExpression::DatetimeKey(key),
+            ExpressionInternal::Mult(expressions) => ParsedExpression::Mult(
+                expressions
+                    .into_iter()
+                    .map(|expression| expression.parse(conditions))
+                    .collect::<CollectionResult<_>>()?,
+            ),
+            ExpressionInternal::Sum(expressions) => ParsedExpression::Sum(
+                expressions
+                    .into_iter()
+                    .map(|expression| expression.parse(conditions))
+                    .collect::<CollectionResult<_>>()?,
+            ),
+            ExpressionInternal::Neg(expression) => {
+                ParsedExpression::Neg(expression.parse_boxed(conditions)?)
+            }
+            ExpressionInternal::Div {
+                left,
+                right,
+                by_zero_default,
+            } => ParsedExpression::Div {
+                left: left.parse_boxed(conditions)?,
+                right: right.parse_boxed(conditions)?,
+                by_zero_default: by_zero_default.map(PreciseScore::from),
+            },
+            ExpressionInternal::Sqrt(expression) => {
+                ParsedExpression::Sqrt(expression.parse_boxed(conditions)?)
+            }
+            ExpressionInternal::Pow { base, exponent } => ParsedExpression::Pow {
+                base: base.parse_boxed(conditions)?,
+                exponent: exponent.parse_boxed(conditions)?,
+            },
+            ExpressionInternal::Exp(expression) => {
+                ParsedExpression::Exp(expression.parse_boxed(conditions)?)
+            }
+            ExpressionInternal::Log10(expression) => {
+                ParsedExpression::Log10(expression.parse_boxed(conditions)?)
+            }
+            ExpressionInternal::Ln(expression) => {
+                ParsedExpression::Ln(expression.parse_boxed(conditions)?)
+            }
+            ExpressionInternal::Abs(expression) => {
+                ParsedExpression::Abs(expression.parse_boxed(conditions)?)
+            }
+            ExpressionInternal::Decay {
+                kind,
+                x,
+                target,
+                midpoint,
+                scale,
+            } => {
+                let midpoint = midpoint
+                    .map(PreciseScore::from)
+                    .unwrap_or(DEFAULT_DECAY_MIDPOINT);
+                let scale = scale.map(PreciseScore::from).unwrap_or(DEFAULT_DECAY_SCALE);
+                DecayKind::validate_params(midpoint, scale).map_err(CollectionError::bad_input)?;
+
+                ParsedExpression::Decay {
+                    kind,
+                    x: x.parse_boxed(conditions)?,
+                    target: target
+                        .map(|target| target.parse_boxed(conditions))
+                        .transpose()?,
+                    midpoint,
+                    scale,
+                }
+            }
+        };
+
+        Ok(parsed)
+    }
+
+    fn parse_boxed(
+        self,
+        conditions: &mut Vec<Condition>,
+    ) -> CollectionResult<Box<ParsedExpression>> {
+        self.parse(conditions).map(Box::new)
+    }
+}
+
+fn parse_datetime(datetime: &str) -> CollectionResult<DateTimePayloadType> {
+    if datetime == DATETIME_NOW {
+        return Ok(DateTimePayloadType::from(chrono::Utc::now()));
+    }
+
+    DateTimePayloadType::from_str(datetime).map_err(|_| {
+        CollectionError::bad_input(format!(
+            "'{datetime}' is not in a supported date/time format, please use RFC 3339 or `{DATETIME_NOW}`"
+        ))
+    })
+}
+
+mod from_rest {
+    use api::rest::schema as rest;
+
+    use super::*;
+
+    impl From<rest::FormulaQuery> for FormulaInternal {
+        fn from(value: rest::FormulaQuery) -> Self {
+            let rest::FormulaQuery {
+                expression,
+                defaults,
+            } = value;
+
+            FormulaInternal {
+                formula: ExpressionInternal::from(expression),
+                defaults,
+            }
+        }
+    }
+
+    impl From<rest::Expression> for ExpressionInternal {
+        fn from(value: rest::Expression) -> Self {
+            let boxed = |expression: Box<rest::Expression>| Box::new(Self::from(*expression));
+            let decay = |kind, params: rest::DecayParamsExpression| {
+                let rest::DecayParamsExpression {
+                    x,
+                    target,
+                    scale,
+                    midpoint,
+                } = params;
+                ExpressionInternal::Decay {
+                    kind,
+                    x: boxed(x),
+                    target: target.map(boxed),
+                    midpoint,
+                    scale,
+                }
+            };
+
+            match value {
+                rest::Expression::Constant(constant) => ExpressionInternal::Constant(constant),
+                rest::Expression::Variable(variable) => ExpressionInternal::Variable(variable),
+                rest::Expression::Condition(condition) => ExpressionInternal::Condition(condition),
+                rest::Expression::GeoDistance(rest::GeoDistance {
+                    geo_distance: rest::GeoDistanceParams { origin, to },
+                }) => ExpressionInternal::GeoDistance { origin, key: to },
+                rest::Expression::Datetime(rest::DatetimeExpression { datetime }) => {
+                    ExpressionInternal::Datetime(datetime)
+                }
+                rest::Expression::DatetimeKey(rest::DatetimeKeyExpression { datetime_key }) => {
+                    ExpressionInternal::DatetimeKey(datetime_key)
+                }
+                rest::Expression::Mult(rest::MultExpression { mult }) => {
+                    ExpressionInternal::Mult(mult.into_iter().map(Self::from).collect())
+                }
+                rest::Expression::Sum(rest::SumExpression { sum }) => {
+                    ExpressionInternal::Sum(sum.into_iter().map(Self::from).collect())
+                }
+                rest::Expression::Neg(rest::NegExpression { neg }) => {
+                    ExpressionInternal::Neg(boxed(neg))
+                }
+                rest::Expression::Abs(rest::AbsExpression { abs }) => {
+                    ExpressionInternal::Abs(boxed(abs))
+                }
+                rest::Expression::Div(rest::DivExpression {
+                    div:
+                        rest::DivParams {
+                            left,
+                            right,
+                            by_zero_default,
+                        },
+                }) => ExpressionInternal::Div {
+                    left: boxed(left),
+                    right: boxed(right),
+                    by_zero_default,
+                },
+                rest::Expression::Sqrt(rest::SqrtExpression { sqrt }) => {
+                    ExpressionInternal::Sqrt(boxed(sqrt))
+                }
+                rest::Expression::Pow(rest::PowExpression {
+                    pow: rest::PowParams { base, exponent },
+                }) => ExpressionInternal::Pow {
+                    base: boxed(base),
+                    exponent: boxed(exponent),
+                },
+                rest::Expression::Exp(rest::ExpExpression { exp }) => {
+                    ExpressionInternal::Exp(boxed(exp))
+                }
+                rest::Expression::Log10(rest::Log10Expression { log10 }) => {
+                    ExpressionInternal::Log10(boxed(log10))
+                }
+                rest::Expression::Ln(rest::LnExpression { ln }) => {
+                    ExpressionInternal::Ln(boxed(ln))
+                }
+                rest::Expression::LinDecay(rest::LinDecayExpression { lin_decay }) => {
+                    decay(DecayKind::Lin, lin_decay)
+                }
+                rest::Expression::ExpDecay(rest::ExpDecayExpression { exp_decay }) => {
+                    decay(DecayKind::Exp, exp_decay)
+                }
+                rest::Expression::GaussDecay(rest::GaussDecayExpression { gauss_decay }) => {
+                    decay(DecayKind::Gauss, gauss_decay)
+                }
+            }
+        }
+    }
+}
+
+mod from_grpc {
+    use api::grpc::conversions::{json_path_from_proto, proto_to_payloads};
+    use api::grpc::qdrant as grpc;
+    use tonic::Status;
+
+    use super::*;
+
+    impl TryFrom<grpc::Formula> for FormulaInternal {
+        type Error = Status;
+
+        fn try_from(value: grpc::Formula) -> Result<Self, Self::Error> {
+            let grpc::Formula {
+                expression,
+                defaults,
+            } = value;
+
+            let expression = expression
+                .ok_or_else(|| Status::invalid_argument("Formula expression is missing"))?;
+
+            Ok(FormulaInternal {
+                formula: ExpressionInternal::try_from(expression)?,
+                defaults: proto_to_payloads(defaults)?.into_iter().collect(),
+            })
+        }
+    }
+
+    impl TryFrom<grpc::Expression> for ExpressionInternal {
+        type Error = Status;
+
+        fn try_from(value: grpc::Expression) -> Result<Self, Self::Error> {
+            use grpc::expression::Variant;
+
+            let variant = value
+                .variant
+                .ok_or_else(|| Status::invalid_argument("Expression variant is missing"))?;
+
+            let expression = match variant {
+                Variant::Constant(constant) => ExpressionInternal::Constant(constant),
+                Variant::Variable(variable) => ExpressionInternal::Variable(variable),
+                Variant::Condition(condition) => {
+                    ExpressionInternal::Condition(Box::new(Condition::try_from(condition)?))
+                }
+                Variant::GeoDistance(grpc::GeoDistance { origin, to }) => {
+                    ExpressionInternal::GeoDistance {
+                        origin: origin.map(GeoPoint::from).ok_or_else(|| {
+                            Status::invalid_argument("Geo distance origin is missing")
+                        })?,
+                        key: json_path_from_proto(&to)?,
+                    }
+                }
+                Variant::Datetime(datetime) => ExpressionInternal::Datetime(datetime),
+                Variant::DatetimeKey(key) => {
+                    ExpressionInternal::DatetimeKey(json_path_from_proto(&key)?)
+                }
+                Variant::Mult(grpc::MultExpression { mult }) => ExpressionInternal::Mult(
+                    mult.into_iter()
+                        .map(Self::try_from)
+                        .collect::<Result<_, _>>()?,
+                ),
+                Variant::Sum(grpc::SumExpression { sum }) => ExpressionInternal::Sum(
+                    sum.into_iter()
+                        .map(Self::try_from)
+                        .collect::<Result<_, _>>()?,
+                ),
+                Variant::Div(div) => {
+                    let grpc::DivExpression {
+                        left,
+                        right,
+                        by_zero_default,
+                    } = *div;
+                    ExpressionInternal::Div {
+                        left: required_from_grpc(left, "left")?,
+                        right: required_from_grpc(right, "right")?,
+                        by_zero_default,
+                    }
+                }
+                Variant::Neg(expression) => ExpressionInternal::Neg(boxed_from_grpc(expression)?),
+                Variant::Abs(expression) => ExpressionInternal::Abs(boxed_from_grpc(expression)?),
+                Variant::Sqrt(expression) => ExpressionInternal::Sqrt(boxed_from_grpc(expression)?),
+                Variant::Pow(pow) => {
+                    let grpc::PowExpression { base, exponent } = *pow;
+                    ExpressionInternal::Pow {
+                        base: required_from_grpc(base, "base")?,
+                        exponent: required_from_grpc(exponent, "exponent")?,
+                    }
+                }
+                Variant::Exp(expression) => ExpressionInternal::Exp(boxed_from_grpc(expression)?),
+                Variant::Log10(expression) => {
+                    ExpressionInternal::Log10(boxed_from_grpc(expression)?)
+                }
+                Variant::Ln(expression) => ExpressionInternal::Ln(boxed_from_grpc(expression)?),
+                Variant::ExpDecay(params) => decay_from_grpc(DecayKind::Exp, *params)?,
+                Variant::GaussDecay(params) => decay_from_grpc(DecayKind::Gauss, *params)?,
+                Variant::LinDecay(params) => decay_from_grpc(DecayKind::Lin, *params)?,
+            };
+
+            Ok(expression)
+        }
+    }
+
+    fn boxed_from_grpc(
+        expression: Box<grpc::Expression>,
+    ) -> Result<Box<ExpressionInternal>, Status> {
+        ExpressionInternal::try_from(*expression).map(Box::new)
+    }
+
+    fn required_from_grpc(
+        expression: Option<Box<grpc::Expression>>,
+        name: &str,
+    ) -> Result<Box<ExpressionInternal>, Status> {
+        let expression = expression
+            .ok_or_else(|| Status::invalid_argument(format!("Expression `{name}` is missing")))?;
+        boxed_from_grpc(expression)
+    }
+
+    fn decay_from_grpc(
+        kind: DecayKind,
+        params: grpc::DecayParamsExpression,
+    ) -> Result<ExpressionInternal, Status> {
+        let grpc::DecayParamsExpression {
+            x,
+            target,
+            scale,
+            midpoint,
+        } = params;
+
+        Ok(ExpressionInternal::Decay {
+            kind,
+            x: required_from_grpc(x, "x")?,
+            target: target.map(boxed_from_grpc).transpose()?,
+            midpoint,
+            scale,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::grpc::qdrant as grpc;
+    use segment::json_path::path;
+    use segment::types::{FieldCondition, Match};
+
+    use super::*;
+
+    fn sample_formula() -> FormulaInternal {
+        let condition = Condition::Field(FieldCondition::new_match(
+            path("category"),
+            Match::from("news".to_string()),
+        ));
+
+        FormulaInternal {
+            formula: ExpressionInternal::Sum(vec![
+                ExpressionInternal::Mult(vec![
+                    ExpressionInternal::Variable("$score".to_string()),
+                    ExpressionInternal::Constant(0.8),
+                ]),
+                ExpressionInternal::Condition(Box::new(condition)),
+                ExpressionInternal::Decay {
+                    kind: DecayKind::Exp,
+                    x: Box::new(ExpressionInternal::DatetimeKey(path("published_at"))),
+                    target: Some(Box::new(ExpressionInternal::Datetime(
+                        "2024-06-01T00:00:00Z".to_string(),
+                    ))),
+                    midpoint: None,
+                    scale: Some(86400.0),
+                },
+            ]),
+            defaults: HashMap::from([("$score[1]".to_string(), Value::from(0.5))]),
+        }
+    }
+
+    #[test]
+    fn test_parse_formula() {
+        let parsed = sample_formula().parse().unwrap();
+
+        assert_eq!(parsed.conditions.len(), 1);
+        assert_eq!(
+            parsed.defaults,
+            HashMap::from([(VariableId::Score(1), Value::from(0.5))])
+        );
+
+        let ParsedExpression::Sum(terms) = &parsed.formula else {
+            panic!("expected sum, got {:?}", parsed.formula);
+        };
+        assert_eq!(terms[1], ParsedExpression::Condition(0));
+        assert!(matches!(
+            terms[2],
+            ParsedExpression::Decay {
+                kind: DecayKind::Exp,
+                midpoint: DEFAULT_DECAY_MIDPOINT,
+                scale: 86400.0,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_formula() {
+        let invalid_variable = FormulaInternal {
+            formula: ExpressionInternal::Variable("$score[x]".to_string()),
+            defaults: HashMap::new(),
+        };
+        assert!(invalid_variable.parse().is_err());
+
+        let invalid_datetime = FormulaInternal {
+            formula: ExpressionInternal::Datetime("yesterday".to_string()),
+            defaults: HashMap::new(),
+        };
+        assert!(invalid_datetime.parse().is_err());
+
+        let invalid_decay = FormulaInternal {
+            formula: ExpressionInternal::Decay {
+                kind: DecayKind::Gauss,
+                x: Box::new(ExpressionInternal::Constant(1.0)),
+                target: None,
+                midpoint: Some(1.5),
+                scale: None,
+            },
+            defaults: HashMap::new(),
+        };
+        assert!(invalid_decay.parse().is_err());
+    }
+
+    #[test]
+    fn test_grpc_round_trip() {
+        let parsed = sample_formula().parse().unwrap();
+
+        let grpc_formula = grpc::Formula::from(parsed.clone());
+        let round_trip = FormulaInternal::try_from(grpc_formula)
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        assert_eq!(round_trip, parsed);
+    }
+}
@@ -11,5 +11,6 @@
 //! 5. `PlannedQuery`: an easier-to-execute representation. Created in LocalShard
 
 pub mod collection_query;
+pub mod formula;
 pub mod planned_query;
 pub mod shard_query;
@@ -4,9 +4,11 @@ use std::sync::Arc;
 
 use api::rest::OrderByInterface;
 use common::types::ScoreType;
+use common::validation::validate_fusion_weights;
+use segment::data_types::text_query::TextQuery;
 use segment::types::{Filter, WithPayloadInterface, WithVector};
 
-use super::shard_query::{ScoringQuery, ShardPrefetch, ShardQueryRequest};
+use super::shard_query::{Fusion, Sample, ScoringQuery, ShardPrefetch, ShardQueryRequest};
 use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
     ScrollRequestInternal,
@@ -17,11 +19,30 @@ pub struct PlannedQuery {
     pub merge_plan: MergePlan,
     pub searches: Arc<CoreSearchRequestBatch>,
     pub scrolls: Arc<Vec<ScrollRequestInternal>>,
+    pub text_searches: Arc<Vec<TextSearchRequest>>,
+    pub samples: Arc<Vec<SampleRequest>>,
     pub offset: usize,
     pub with_vector: WithVector,
     pub with_payload: WithPayloadInterface,
 }
 
+/// Full-text search, scoring the points with BM25 in the segments
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSearchRequest {
+    pub query: TextQuery,
+    pub filter: Option<Filter>,
+    pub score_threshold: Option<ScoreType>,
+    pub limit: usize,
+}
+
+/// Random sample of points, scored with sampling keys in the segments
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleRequest {
+    pub sample: Sample,
+    pub filter: Option<Filter>,
+    pub limit: usize,
+}
+
 /// Defines how to merge multiple [prefetch sources](PrefetchSource)
 #[derive(Debug, PartialEq)]
 pub struct ResultsMerge {
@@ -46,8 +67,14 @@ pub enum PrefetchSource {
     /// A reference offset into the scrolls list
     ScrollsIdx(usize),
 
+    /// A reference offset into the text searches list
+    TextSearchesIdx(usize),
+
+    /// A reference offset into the samples list
+    SamplesIdx(usize),
+
     /// A nested prefetch
-    Prefetch(MergePlan),
+    Prefetch(Box<MergePlan>),
 }
 
 #[derive(Debug, PartialEq)]
@@ -81,16 +108,26 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
 
         let mut core_searches = Vec::new();
         let mut scrolls = Vec::new();
+        let mut text_searches = Vec::new();
+        let mut samples = Vec::new();
         let offset;
         let with_vector;
         let with_payload;
 
         let merge_plan = if !prefetches.is_empty() {
             offset = req_offset;
-            let sources = recurse_prefetches(&mut core_searches, &mut scrolls, prefetches, offset)?;
+            let sources = recurse_prefetches(
+                &mut core_searches,
+                &mut scrolls,
+                &mut text_searches,
+                &mut samples,
+                prefetches,
+                offset,
+            )?;
             let rescore = query.ok_or_else(|| {
                 CollectionError::bad_request("cannot have prefetches without a query".to_string())
             })?;
+            check_rescore_sources(&rescore, &sources)?;
 
             with_vector = req_with_vector;
             with_payload = req_with_payload;
@@ -122,6 +159,8 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
                     core_searches.push(core_search);
 
                     offset = 0; // already handled by the core search
+                    with_vector = WithVector::Bool(false); // already fetched by the core search
+                    with_payload = WithPayloadInterface::Bool(false);
 
                     vec![PrefetchSource::SearchesIdx(0)]
                 }
@@ -130,6 +169,11 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
                         "cannot apply Fusion without prefetches".to_string(),
                     ))
                 }
+                Some(ScoringQuery::Formula(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply Formula without prefetches".to_string(),
+                    ))
+                }
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     // Everything should come from 1 scroll
                     let scroll = ScrollRequestInternal {
@@ -144,9 +188,44 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
                     scrolls.push(scroll);
 
                     offset = req_offset;
+                    with_vector = WithVector::Bool(false); // already fetched by the scroll
+                    with_payload = WithPayloadInterface::Bool(false);
 
                     vec![PrefetchSource::ScrollsIdx(0)]
                 }
+                Some(ScoringQuery::Text(text_query)) => {
+                    // Everything should come from 1 text search
+                    let text_search = TextSearchRequest {
+                        query: text_query,
+                        filter: req_filter,
+                        score_threshold: req_score_threshold,
+                        limit: limit + req_offset,
+                    };
+
+                    text_searches.push(text_search);
+
+                    offset = req_offset;
+                    with_vector = req_with_vector;
+                    with_payload = req_with_payload;
+
+                    vec![PrefetchSource::TextSearchesIdx(0)]
+                }
+                Some(ScoringQuery::Sample(sample)) => {
+                    // Everything should come from 1 sample
+                    let sample = SampleRequest {
+                        sample,
+                        filter: req_filter,
+                        limit: limit + req_offset,
+                    };
+
+                    samples.push(sample);
+
+                    offset = req_offset;
+                    with_vector = req_with_vector;
+                    with_payload = req_with_payload;
+
+                    vec![PrefetchSource::SamplesIdx(0)]
+                }
                 None => {
                     // Everything should come from 1 scroll
                     let scroll = ScrollRequestInternal {
@@ -161,14 +240,13 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
                     scrolls.push(scroll);
 
                     offset = req_offset;
+                    with_vector = WithVector::Bool(false); // already fetched by the scroll
+                    with_payload = WithPayloadInterface::Bool(false);
 
                     vec![PrefetchSource::ScrollsIdx(0)]
                 }
             };
 
-            with_vector = WithVector::Bool(false);
-            with_payload = WithPayloadInterface::Bool(false);
-
             // Root-level query without prefetches is the only case where merge is `None`
             MergePlan {
                 sources,
@@ -182,6 +260,8 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
                 searches: core_searches,
             }),
             scrolls: Arc::new(scrolls),
+            text_searches: Arc::new(text_searches),
+            samples: Arc::new(samples),
             offset,
             with_vector,
             with_payload,
@@ -192,6 +272,8 @@ impl TryFrom<ShardQueryRequest> for PlannedQuery {
 fn recurse_prefetches(
     core_searches: &mut Vec<CoreSearchRequest>,
     scrolls: &mut Vec<ScrollRequestInternal>,
+    text_searches: &mut Vec<TextSearchRequest>,
+    samples: &mut Vec<SampleRequest>,
     prefetches: Vec<ShardPrefetch>,
     offset: usize,
 ) -> CollectionResult<Vec<PrefetchSource>> {
@@ -235,6 +317,11 @@ fn recurse_prefetches(
                         "cannot apply Fusion without prefetches".to_string(),
                     ))
                 }
+                Some(ScoringQuery::Formula(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply Formula without prefetches".to_string(),
+                    ))
+                }
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     let scroll = ScrollRequestInternal {
                         order_by: Some(OrderByInterface::Struct(order_by)),
@@ -250,6 +337,31 @@ fn recurse_prefetches(
 
                     PrefetchSource::ScrollsIdx(idx)
                 }
+                Some(ScoringQuery::Text(text_query)) => {
+                    let text_search = TextSearchRequest {
+                        query: text_query,
+                        filter,
+                        score_threshold,
+                        limit,
+                    };
+
+                    let idx = text_searches.len();
+                    text_searches.push(text_search);
+
+                    PrefetchSource::TextSearchesIdx(idx)
+                }
+                Some(ScoringQuery::Sample(sample)) => {
+                    let sample = SampleRequest {
+                        sample,
+                        filter,
+                        limit,
+                    };
+
+                    let idx = samples.len();
+                    samples.push(sample);
+
+                    PrefetchSource::SamplesIdx(idx)
+                }
                 None => {
                     let scroll = ScrollRequestInternal {
                         order_by: None,
@@ -268,11 +380,19 @@ fn recurse_prefetches(
             }
         } else {
             // This has nested prefetches. Recurse into them
-            let inner_sources = recurse_prefetches(core_searches, scrolls, prefetches, offset)?;
+            let inner_sources = recurse_prefetches(
+                core_searches,
+                scrolls,
+                text_searches,
+                samples,
+                prefetches,
+                offset,
+            )?;
 
             let rescore = query.ok_or_else(|| {
                 CollectionError::bad_request("cannot have prefetches without a query".to_string())
             })?;
+            check_rescore_sources(&rescore, &inner_sources)?;
 
             let prefetch_plan = MergePlan {
                 sources: inner_sources,
@@ -284,7 +404,7 @@ fn recurse_prefetches(
                 }),
             };
 
-            PrefetchSource::Prefetch(prefetch_plan)
+            PrefetchSource::Prefetch(Box::new(prefetch_plan))
         };
         sources.push(source);
     }
@@ -292,10 +412,49 @@ fn recurse_prefetches(
     Ok(sources)
 }
 
+/// Check that the rescoring parameters are compatible with the prefetches to rescore
+fn check_rescore_sources(
+    rescore: &ScoringQuery,
+    sources: &[PrefetchSource],
+) -> CollectionResult<()> {
+    match rescore {
+        ScoringQuery::Fusion(Fusion::Weighted { weights }) if weights.len() != sources.len() => {
+            Err(CollectionError::bad_request(format!(
+                "weighted fusion requires one weight per prefetch, got {} weights for {} prefetches",
+                weights.len(),
+                sources.len(),
+            )))
+        }
+        ScoringQuery::Fusion(Fusion::Weighted { weights })
+            if validate_fusion_weights(weights).is_err() =>
+        {
+            Err(CollectionError::bad_request(
+                "weighted fusion weights must be finite and not negative".to_string(),
+            ))
+        }
+        ScoringQuery::Fusion(Fusion::Rrf { k: 0 }) => Err(CollectionError::bad_request(
+            "RRF k must be greater than 0".to_string(),
+        )),
+        ScoringQuery::Formula(formula) if formula.required_prefetches() > sources.len() => {
+            Err(CollectionError::bad_request(format!(
+                "formula refers to the score of prefetch {}, but there are only {} prefetches",
+                formula.required_prefetches() - 1,
+                sources.len(),
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
 
+    use segment::common::reciprocal_rank_fusion::DEFAULT_RRF_K;
     use segment::data_types::vectors::{MultiDenseVector, NamedVectorStruct, Vector};
+    use segment::index::rescore_formula::parsed_formula::{
+        ParsedExpression, ParsedFormula, VariableId,
+    };
     use segment::types::{
         Condition, FieldCondition, Filter, Match, SearchParams, WithPayloadInterface, WithVector,
     };
@@ -303,7 +462,6 @@ mod tests {
 
     use super::*;
     use crate::operations::query_enum::QueryEnum;
-    use crate::operations::universal_query::shard_query::Fusion;
 
     #[test]
     fn test_try_from_double_rescore() {
@@ -368,7 +526,7 @@ mod tests {
         assert_eq!(
             planned_query.merge_plan,
             MergePlan {
-                sources: vec![PrefetchSource::Prefetch(MergePlan {
+                sources: vec![PrefetchSource::Prefetch(Box::new(MergePlan {
                     sources: vec![PrefetchSource::SearchesIdx(0)],
                     merge: Some(ResultsMerge {
                         rescore: ScoringQuery::Vector(QueryEnum::Nearest(
@@ -381,7 +539,7 @@ mod tests {
                         limit: 100,
                         score_threshold: None
                     })
-                })],
+                }))],
                 merge: Some(ResultsMerge {
                     rescore: ScoringQuery::Vector(QueryEnum::Nearest(
                         NamedVectorStruct::new_from_vector(
@@ -488,7 +646,7 @@ mod tests {
                     score_threshold: None,
                 },
             ],
-            query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+            query: Some(ScoringQuery::Fusion(Fusion::Rrf { k: DEFAULT_RRF_K })),
             filter: Some(Filter::default()),
             score_threshold: None,
             limit: 50,
@@ -540,7 +698,7 @@ mod tests {
                     PrefetchSource::SearchesIdx(1)
                 ],
                 merge: Some(ResultsMerge {
-                    rescore: ScoringQuery::Fusion(Fusion::Rrf),
+                    rescore: ScoringQuery::Fusion(Fusion::Rrf { k: DEFAULT_RRF_K }),
                     filter: Some(Filter::default()),
                     limit: 50,
                     score_threshold: None
@@ -560,7 +718,7 @@ mod tests {
     fn test_try_from_rrf_without_source() {
         let request = ShardQueryRequest {
             prefetches: vec![],
-            query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+            query: Some(ScoringQuery::Fusion(Fusion::Rrf { k: DEFAULT_RRF_K })),
             filter: Some(Filter::default()),
             score_threshold: None,
             limit: 50,
@@ -575,6 +733,235 @@ mod tests {
         assert!(planned_query.is_err())
     }
 
+    #[test]
+    fn test_try_from_weighted_fusion_weights_count() {
+        let dummy_vector = vec![1.0, 2.0, 3.0];
+        let make_request = |weights: Vec<f32>| ShardQueryRequest {
+            prefetches: ["dense", "other"]
+                .into_iter()
+                .map(|vector_name| ShardPrefetch {
+                    prefetches: Vec::new(),
+                    query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+                        NamedVectorStruct::new_from_vector(
+                            Vector::Dense(dummy_vector.clone()),
+                            vector_name,
+                        ),
+                    ))),
+                    limit: 100,
+                    params: None,
+                    filter: None,
+                    score_threshold: None,
+                })
+                .collect(),
+            query: Some(ScoringQuery::Fusion(Fusion::Weighted { weights })),
+            filter: None,
+            score_threshold: None,
+            limit: 50,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        // one weight per prefetch
+        let planned_query = PlannedQuery::try_from(make_request(vec![0.3, 0.7])).unwrap();
+        assert_eq!(
+            planned_query.merge_plan.merge.unwrap().rescore,
+            ScoringQuery::Fusion(Fusion::Weighted {
+                weights: vec![0.3, 0.7]
+            })
+        );
+
+        // missing weight
+        assert!(PlannedQuery::try_from(make_request(vec![1.0])).is_err());
+
+        // extra weight
+        assert!(PlannedQuery::try_from(make_request(vec![0.2, 0.3, 0.5])).is_err());
+
+        // invalid weights
+        assert!(PlannedQuery::try_from(make_request(vec![0.5, -0.5])).is_err());
+        assert!(PlannedQuery::try_from(make_request(vec![f32::NAN, 0.5])).is_err());
+        assert!(PlannedQuery::try_from(make_request(vec![0.5, f32::INFINITY])).is_err());
+    }
+
+    #[test]
+    fn test_try_from_formula_score_index() {
+        let dummy_vector = vec![1.0, 2.0, 3.0];
+        let make_request = |variable: usize| ShardQueryRequest {
+            prefetches: ["dense", "other"]
+                .into_iter()
+                .map(|vector_name| ShardPrefetch {
+                    prefetches: Vec::new(),
+                    query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+                        NamedVectorStruct::new_from_vector(
+                            Vector::Dense(dummy_vector.clone()),
+                            vector_name,
+                        ),
+                    ))),
+                    limit: 100,
+                    params: None,
+                    filter: None,
+                    score_threshold: None,
+                })
+                .collect(),
+            query: Some(ScoringQuery::Formula(ParsedFormula {
+                conditions: Vec::new(),
+                defaults: HashMap::new(),
+                formula: ParsedExpression::Sum(vec![
+                    ParsedExpression::Variable(VariableId::Score(0)),
+                    ParsedExpression::Variable(VariableId::Score(variable)),
+                ]),
+            })),
+            filter: None,
+            score_threshold: None,
+            limit: 50,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        // score of the second prefetch
+        assert!(PlannedQuery::try_from(make_request(1)).is_ok());
+
+        // there is no third prefetch
+        assert!(PlannedQuery::try_from(make_request(2)).is_err());
+
+        // formula without prefetches
+        let mut request = make_request(0);
+        request.prefetches.clear();
+        assert!(PlannedQuery::try_from(request).is_err());
+    }
+
+    #[test]
+    fn test_try_from_text_query() {
+        let text_query = TextQuery {
+            key: "description".try_into().unwrap(),
+            text: "quick brown fox".to_string(),
+        };
+        let request = ShardQueryRequest {
+            prefetches: vec![ShardPrefetch {
+                prefetches: Vec::new(),
+                query: Some(ScoringQuery::Text(text_query.clone())),
+                limit: 100,
+                params: None,
+                filter: None,
+                score_threshold: Some(1.0),
+            }],
+            query: Some(ScoringQuery::Fusion(Fusion::Rrf { k: DEFAULT_RRF_K })),
+            filter: None,
+            score_threshold: None,
+            limit: 10,
+            offset: 5,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        let planned_query = PlannedQuery::try_from(request).unwrap();
+
+        assert!(planned_query.searches.searches.is_empty());
+        assert!(planned_query.scrolls.is_empty());
+        assert_eq!(
+            planned_query.text_searches.as_slice(),
+            &[TextSearchRequest {
+                query: text_query,
+                filter: None,
+                score_threshold: Some(1.0),
+                limit: 105,
+            }]
+        );
+        assert_eq!(
+            planned_query.merge_plan.sources,
+            vec![PrefetchSource::TextSearchesIdx(0)]
+        );
+    }
+
+    #[test]
+    fn test_try_from_sample_query() {
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "city".try_into().unwrap(),
+            Match::new_value(segment::types::ValueVariants::Keyword("Berlin".to_string())),
+        )));
+        let request = ShardQueryRequest {
+            prefetches: Vec::new(),
+            query: Some(ScoringQuery::Sample(Sample::Random)),
+            filter: Some(filter.clone()),
+            score_threshold: None,
+            limit: 10,
+            offset: 5,
+            params: None,
+            with_vector: WithVector::Bool(true),
+            with_payload: WithPayloadInterface::Bool(true),
+        };
+
+        let planned_query = PlannedQuery::try_from(request).unwrap();
+
+        assert!(planned_query.searches.searches.is_empty());
+        assert!(planned_query.scrolls.is_empty());
+        assert!(planned_query.text_searches.is_empty());
+        assert_eq!(
+            planned_query.samples.as_slice(),
+            &[SampleRequest {
+                sample: Sample::Random,
+                filter: Some(filter),
+                limit: 15,
+            }]
+        );
+        assert_eq!(planned_query.offset, 5);
+        assert_eq!(planned_query.with_vector, WithVector::Bool(true));
+        assert_eq!(planned_query.with_payload, WithPayloadInterface::Bool(true));
+        assert_eq!(
+            planned_query.merge_plan,
+            MergePlan {
+                sources: vec![PrefetchSource::SamplesIdx(0)],
+                merge: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_try_from_root_text_query() {
+        let text_query = TextQuery {
+            key: "description".try_into().unwrap(),
+            text: "quick brown fox".to_string(),
+        };
+        let request = ShardQueryRequest {
+            prefetches: Vec::new(),
+            query: Some(ScoringQuery::Text(text_query.clone())),
+            filter: None,
+            score_threshold: None,
+            limit: 10,
+            offset: 5,
+            params: None,
+            with_vector: WithVector::Bool(true),
+            with_payload: WithPayloadInterface::Bool(true),
+        };
+
+        let planned_query = PlannedQuery::try_from(request).unwrap();
+
+        assert_eq!(
+            planned_query.text_searches.as_slice(),
+            &[TextSearchRequest {
+                query: text_query,
+                filter: None,
+                score_threshold: None,
+                limit: 15,
+            }]
+        );
+        assert_eq!(planned_query.offset, 5);
+        // Text search doesn't fetch payload and vectors, so they are fetched after merging
+        assert_eq!(planned_query.with_vector, WithVector::Bool(true));
+        assert_eq!(planned_query.with_payload, WithPayloadInterface::Bool(true));
+        assert_eq!(
+            planned_query.merge_plan,
+            MergePlan {
+                sources: vec![PrefetchSource::TextSearchesIdx(0)],
+                merge: None,
+            }
+        );
+    }
+
     #[test]
     fn test_base_params_mapping_in_try_from() {
         let dummy_vector = vec![1.0, 2.0, 3.0];
@@ -603,7 +990,7 @@ mod tests {
                 filter: dummy_filter.clone(),
                 score_threshold: Some(0.1),
             }],
-            query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+            query: Some(ScoringQuery::Fusion(Fusion::Rrf { k: DEFAULT_RRF_K })),
             filter: Some(Filter::default()),
             score_threshold: Some(0.666),
             limit: 50,
@@ -632,7 +1019,7 @@ mod tests {
             MergePlan {
                 sources: vec![PrefetchSource::SearchesIdx(0)],
                 merge: Some(ResultsMerge {
-                    rescore: ScoringQuery::Fusion(Fusion::Rrf),
+                    rescore: ScoringQuery::Fusion(Fusion::Rrf { k: DEFAULT_RRF_K }),
                     filter: Some(Filter::default()),
                     limit: 50,
                     score_threshold: Some(0.666)
@@ -1,12 +1,17 @@
 use api::grpc::qdrant as grpc;
 use common::types::ScoreType;
 use itertools::Itertools;
+use segment::common::reciprocal_rank_fusion::{rrf_scoring, DEFAULT_RRF_K};
+use segment::common::score_fusion::{dbsf_scoring, weighted_scoring};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::text_query::TextQuery;
 use segment::data_types::vectors::{NamedQuery, NamedVectorStruct, Vector, DEFAULT_VECTOR_NAME};
+use segment::index::rescore_formula::parsed_formula::ParsedFormula;
 use segment::types::{Filter, Order, ScoredPoint, SearchParams, WithPayloadInterface, WithVector};
 use segment::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery};
 use tonic::Status;
 
+use super::formula::FormulaInternal;
 use crate::config::CollectionParams;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::CollectionResult;
@@ -35,7 +40,32 @@ pub struct ShardQueryRequest {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Fusion {
-    Rrf,
+    /// Reciprocal rank fusion
+    Rrf { k: usize },
+    /// Distribution-based score fusion
+    Dbsf,
+    /// Weighted sum of the scores, with one weight per prefetch
+    Weighted { weights: Vec<ScoreType> },
+}
+
+impl Fusion {
+    /// Fuse the results of each prefetch into a single sorted list
+    ///
+    /// `orders` has the order of the scores of each prefetch, score-based fusions invert
+    /// the scores of small-is-better prefetches.
+    pub fn fuse(&self, sources: Vec<Vec<ScoredPoint>>, orders: &[Order]) -> Vec<ScoredPoint> {
+        match self {
+            Fusion::Rrf { k } => rrf_scoring(sources, *k),
+            Fusion::Dbsf => dbsf_scoring(sources, orders),
+            Fusion::Weighted { weights } => weighted_scoring(sources, weights, orders),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sample {
+    /// Uniformly random points
+    Random,
 }
 
 /// Same as `Query`, but with the resolved vector references.
@@ -44,20 +74,33 @@ pub enum ScoringQuery {
     /// Score points against some vector(s)
     Vector(QueryEnum),
 
-    /// Reciprocal rank fusion
+    /// Fuse the results of the prefetches
     Fusion(Fusion),
 
     /// Order by a payload field
     OrderBy(OrderBy),
+
+    /// Rescore the prefetches with a formula
+    Formula(ParsedFormula),
+
+    /// Rank by BM25 relevance of a full-text indexed field
+    Text(TextQuery),
+
+    /// Sample points, scored with random sampling keys
+    Sample(Sample),
 }
 
 impl ScoringQuery {
     pub fn needs_intermediate_results(&self) -> bool {
         match self {
             ScoringQuery::Fusion(fusion) => match fusion {
-                Fusion::Rrf => true,
+                Fusion::Rrf { .. } | Fusion::Dbsf | Fusion::Weighted { .. } => true,
             },
-            ScoringQuery::Vector(_) | ScoringQuery::OrderBy(_) => false,
+            ScoringQuery::Vector(_)
+            | ScoringQuery::OrderBy(_)
+            | ScoringQuery::Formula(_)
+            | ScoringQuery::Text(_)
+            | ScoringQuery::Sample(_) => false,
         }
     }
 
@@ -76,19 +119,16 @@ impl ScoringQuery {
     ) -> CollectionResult<Order> {
         let order = match opt_self {
             Some(scoring_query) => match scoring_query {
-                ScoringQuery::Vector(query_enum) => {
-                    if query_enum.is_distance_scored() {
-                        collection_params
-                            .get_distance(query_enum.get_vector_name())?
-                            .distance_order()
-                    } else {
+                ScoringQuery::Vector(query_enum) => query_enum.order(collection_params)?,
+                ScoringQuery::Fusion(fusion) => match fusion {
+                    Fusion::Rrf { .. } | Fusion::Dbsf | Fusion::Weighted { .. } => {
                         Order::LargeBetter
                     }
-                }
-                ScoringQuery::Fusion(fusion) => match fusion {
-                    Fusion::Rrf => Order::LargeBetter,
                 },
                 ScoringQuery::OrderBy(order_by) => Order::from(order_by.direction()),
+                ScoringQuery::Formula(_) | ScoringQuery::Text(_) | ScoringQuery::Sample(_) => {
+                    Order::LargeBetter
+                }
             },
             None => {
                 // Order by ID
@@ -271,19 +311,56 @@ impl TryFrom<i32> for Fusion {
 impl From<api::grpc::qdrant::Fusion> for Fusion {
     fn from(fusion: api::grpc::qdrant::Fusion) -> Self {
         match fusion {
-            api::grpc::qdrant::Fusion::Rrf => Fusion::Rrf,
+            api::grpc::qdrant::Fusion::Rrf => Fusion::Rrf { k: DEFAULT_RRF_K },
+            api::grpc::qdrant::Fusion::Dbsf => Fusion::Dbsf,
         }
     }
 }
 
-impl From<Fusion> for api::grpc::qdrant::Fusion {
-    fn from(fusion: Fusion) -> Self {
-        match fusion {
-            Fusion::Rrf => api::grpc::qdrant::Fusion::Rrf,
+impl TryFrom<i32> for Sample {
+    type Error = tonic::Status;
+
+    fn try_from(sample: i32) -> Result<Self, Self::Error> {
+        let sample = api::grpc::qdrant::Sample::from_i32(sample).ok_or_else(|| {
+            tonic::Status::invalid_argument(format!("invalid sample type value {sample}",))
+        })?;
+
+        Ok(Sample::from(sample))
+    }
+}
+
+impl From<api::grpc::qdrant::Sample> for Sample {
+    fn from(sample: api::grpc::qdrant::Sample) -> Self {
+        match sample {
+            api::grpc::qdrant::Sample::Random => Sample::Random,
         }
     }
 }
 
+impl From<Sample> for api::grpc::qdrant::Sample {
+    fn from(sample: Sample) -> Self {
+        match sample {
+            Sample::Random => api::grpc::qdrant::Sample::Random,
+        }
+    }
+}
+
+impl From<grpc::Rrf> for Fusion {
+    fn from(rrf: grpc::Rrf) -> Self {
+        let grpc::Rrf { k } = rrf;
+        Fusion::Rrf {
+            k: k.map(|k| k as usize).unwrap_or(DEFAULT_RRF_K),
+        }
+    }
+}
+
+impl From<grpc::WeightedFusion> for Fusion {
+    fn from(weighted: grpc::WeightedFusion) -> Self {
+        let grpc::WeightedFusion { weights } = weighted;
+        Fusion::Weighted { weights }
+    }
+}
+
 impl ScoringQuery {
     fn try_from_grpc_query(
         query: grpc::query_shard_points::Query,
@@ -299,9 +376,26 @@ impl ScoringQuery {
             grpc::query_shard_points::query::Score::Fusion(fusion) => {
                 ScoringQuery::Fusion(Fusion::try_from(fusion)?)
             }
+            grpc::query_shard_points::query::Score::Rrf(rrf) => {
+                ScoringQuery::Fusion(Fusion::from(rrf))
+            }
+            grpc::query_shard_points::query::Score::Weighted(weighted) => {
+                ScoringQuery::Fusion(Fusion::from(weighted))
+            }
             grpc::query_shard_points::query::Score::OrderBy(order_by) => {
                 ScoringQuery::OrderBy(OrderBy::try_from(order_by)?)
             }
+            grpc::query_shard_points::query::Score::Formula(formula) => ScoringQuery::Formula(
+                FormulaInternal::try_from(formula)?
+                    .parse()
+                    .map_err(|err| Status::invalid_argument(err.to_string()))?,
+            ),
+            grpc::query_shard_points::query::Score::Text(text_query) => {
+                ScoringQuery::Text(TextQuery::try_from(text_query)?)
+            }
+            grpc::query_shard_points::query::Score::Sample(sample) => {
+                ScoringQuery::Sample(Sample::try_from(sample)?)
+            }
         };
 
         Ok(scoring_query)
@@ -339,12 +433,32 @@ impl From<ScoringQuery> for grpc::query_shard_points::Query {
             ScoringQuery::Vector(query) => Self {
                 score: Some(Score::Vector(grpc::RawQuery::from(query))),
             },
-            ScoringQuery::Fusion(fusion) => Self {
-                score: Some(Score::Fusion(api::grpc::qdrant::Fusion::from(fusion) as i32)),
-            },
+            ScoringQuery::Fusion(fusion) => {
+                let score = match fusion {
+                    // Plain enum for the default `k`, understood by peers without configurable RRF
+                    Fusion::Rrf { k: DEFAULT_RRF_K } => {
+                        Score::Fusion(api::grpc::qdrant::Fusion::Rrf as i32)
+                    }
+                    Fusion::Rrf { k } => Score::Rrf(grpc::Rrf { k: Some(k as u32) }),
+                    Fusion::Dbsf => Score::Fusion(api::grpc::qdrant::Fusion::Dbsf as i32),
+                    Fusion::Weighted { weights } => {
+                        Score::Weighted(grpc::WeightedFusion { weights })
+                    }
+                };
+                Self { score: Some(score) }
+            }
             ScoringQuery::OrderBy(order_by) => Self {
                 score: Some(Score::OrderBy(grpc::OrderBy::from(order_by))),
             },
+            ScoringQuery::Formula(formula) => Self {
+                score: Some(Score::Formula(grpc::Formula::from(formula))),
+            },
+            ScoringQuery::Text(text_query) => Self {
+                score: Some(Score::Text(grpc::TextQuery::from(text_query))),
+            },
+            ScoringQuery::Sample(sample) => Self {
+                score: Some(Score::Sample(grpc::Sample::from(sample) as i32)),
+            },
         }
     }
 }
@@ -0,0 +1,387 @@
+//! Checks of requests against the [strict mode](StrictModeConfig) of a collection
+
+use std::collections::HashMap;
+
+use itertools::Itertools as _;
+use segment::data_types::facets::FacetParams;
+use segment::problems::unindexed_fields;
+use segment::types::{Filter, PayloadFieldSchema, PayloadKeyType, SearchParams};
+
+use super::payload_ops::PayloadOps;
+use super::point_ops::{PointInsertOperationsInternal, PointOperations};
+use super::types::{
+    CollectionError, CollectionResult, CollectionSearchMatrixRequest, CoreSearchRequest,
+    CountRequestInternal, DiscoverRequestInternal, RecommendRequestInternal, ScrollRequestInternal,
+    SearchRequestInternal,
+};
+use super::universal_query::collection_query::{CollectionPrefetch, CollectionQueryRequest};
+use super::vector_ops::VectorOperations;
+use super::CollectionUpdateOperations;
+use crate::config::StrictModeConfig;
+use crate::grouping::group_by::{GroupRequest, SourceRequest};
+
+/// Parameters of a request which are limited by the strict mode
+pub trait StrictModeVerification {
+    /// Max number of results requested
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    /// Number of results to skip
+    fn query_offset(&self) -> Option<usize> {
+        None
+    }
+
+    /// Filter selecting the points to read
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        None
+    }
+
+    /// Filter selecting the points to update
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        None
+    }
+
+    /// Whether exact search is requested
+    fn request_exact(&self) -> bool {
+        false
+    }
+
+    /// Check the request against the limits of the strict mode config.
+    ///
+    /// The config is expected to be enabled.
+    fn check_strict_mode(
+        &self,
+        strict_mode_config: &StrictModeConfig,
+        payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> CollectionResult<()> {
+        self.check_request_params(strict_mode_config, payload_schema)
+    }
+
+    /// Check the parameters of this request only, without any nested requests
+    fn check_request_params(
+        &self,
+        strict_mode_config: &StrictModeConfig,
+        payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> CollectionResult<()> {
+        if let (Some(max_limit), Some(limit)) =
+            (strict_mode_config.max_query_limit, self.query_limit())
+        {
+            if limit > max_limit {
+                return Err(CollectionError::strict_mode(
+                    format!("Limit exceeded {limit} > {max_limit}"),
+                    format!("Reduce the \"limit\" parameter to or below {max_limit}."),
+                ));
+            }
+        }
+
+        if let (Some(max_offset), Some(offset)) =
+            (strict_mode_config.max_query_offset, self.query_offset())
+        {
+            if offset > max_offset {
+                return Err(CollectionError::strict_mode(
+                    format!("Offset exceeded {offset} > {max_offset}"),
+                    format!("Reduce the \"offset\" parameter to or below {max_offset}."),
+                ));
+            }
+        }
+
+        if strict_mode_config.search_allow_exact == Some(false) && self.request_exact() {
+            return Err(CollectionError::strict_mode(
+                "Exact search disabled",
+                "Set \"exact\" to false.",
+            ));
+        }
+
+        if strict_mode_config.unindexed_filtering_retrieve == Some(false) {
+            if let Some(filter) = self.indexed_filter_read() {
+                check_filter_indexed(filter, payload_schema)?;
+            }
+        }
+
+        if strict_mode_config.unindexed_filtering_update == Some(false) {
+            if let Some(filter) = self.indexed_filter_write() {
+                check_filter_indexed(filter, payload_schema)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Fail if the filter has conditions on fields without a suitable payload index
+fn check_filter_indexed(
+    filter: &Filter,
+    payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+) -> CollectionResult<()> {
+    let unindexed = unindexed_fields(filter, payload_schema);
+
+    // Report the same field on every call
+    let Some((key, field_schemas)) = unindexed.into_iter().min_by_key(|(key, _)| key.to_string())
+    else {
+        return Ok(());
+    };
+
+    let index_types = field_schemas
+        .iter()
+        .map(|schema| schema.kind())
+        .unique()
+        .map(|kind| serde_json::to_string(&kind).unwrap_or_default())
+        .join(", ");
+
+    Err(CollectionError::strict_mode(
+        format!("Index required but not found for \"{key}\" of one of the following types: [{index_types}]"),
+        "Create an index for this key or use a different filter.",
+    ))
+}
+
+fn is_exact(params: Option<&SearchParams>) -> bool {
+    params.is_some_and(|params| params.exact)
+}
+
+impl StrictModeVerification for CoreSearchRequest {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        Some(self.offset)
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_exact(&self) -> bool {
+        is_exact(self.params.as_ref())
+    }
+}
+
+impl StrictModeVerification for SearchRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_exact(&self) -> bool {
+        is_exact(self.params.as_ref())
+    }
+}
+
+impl StrictModeVerification for RecommendRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_exact(&self) -> bool {
+        is_exact(self.params.as_ref())
+    }
+}
+
+impl StrictModeVerification for DiscoverRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_exact(&self) -> bool {
+        is_exact(self.params.as_ref())
+    }
+}
+
+impl StrictModeVerification for CountRequestInternal {
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for ScrollRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for FacetParams {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for CollectionSearchMatrixRequest {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.sample_size)
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for GroupRequest {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit * self.group_size)
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        match &self.source {
+            SourceRequest::Search(request) => request.indexed_filter_read(),
+            SourceRequest::Recommend(request) => request.indexed_filter_read(),
+        }
+    }
+
+    fn request_exact(&self) -> bool {
+        match &self.source {
+            SourceRequest::Search(request) => request.request_exact(),
+            SourceRequest::Recommend(request) => request.request_exact(),
+        }
+    }
+}
+
+impl StrictModeVerification for CollectionQueryRequest {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        Some(self.offset)
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_exact(&self) -> bool {
+        is_exact(self.params.as_ref())
+    }
+
+    fn check_strict_mode(
+        &self,
+        strict_mode_config: &StrictModeConfig,
+        payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> CollectionResult<()> {
+        // Prefetches are executed as well, each of them must comply
+        for prefetch in &self.prefetch {
+            prefetch.check_strict_mode(strict_mode_config, payload_schema)?;
+        }
+
+        self.check_request_params(strict_mode_config, payload_schema)
+    }
+}
+
+impl StrictModeVerification for CollectionPrefetch {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_exact(&self) -> bool {
+        is_exact(self.params.as_ref())
+    }
+
+    fn check_strict_mode(
+        &self,
+        strict_mode_config: &StrictModeConfig,
+        payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> CollectionResult<()> {
+        for prefetch in &self.prefetch {
+            prefetch.check_strict_mode(strict_mode_config, payload_schema)?;
+        }
+
+        self.check_request_params(strict_mode_config, payload_schema)
+    }
+}
+
+impl StrictModeVerification for CollectionUpdateOperations {
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        match self {
+            CollectionUpdateOperations::PointOperation(operation) => match operation {
+                PointOperations::DeletePointsByFilter(filter) => Some(filter),
+                PointOperations::UpsertPointsConditional(operation) => Some(&operation.condition),
+                PointOperations::UpsertPoints(_)
+                | PointOperations::DeletePoints { .. }
+                | PointOperations::SyncPoints(_) => None,
+            },
+            CollectionUpdateOperations::VectorOperation(operation) => match operation {
+                VectorOperations::DeleteVectorsByFilter(filter, _) => Some(filter),
+                VectorOperations::UpdateVectors(_) | VectorOperations::DeleteVectors(..) => None,
+            },
+            CollectionUpdateOperations::PayloadOperation(operation) => match operation {
+                PayloadOps::SetPayload(operation) | PayloadOps::OverwritePayload(operation) => {
+                    operation.filter.as_ref()
+                }
+                PayloadOps::DeletePayload(operation) => operation.filter.as_ref(),
+                PayloadOps::ClearPayloadByFilter(filter) => Some(filter),
+                PayloadOps::ClearPayload { .. } => None,
+            },
+            CollectionUpdateOperations::FieldIndexOperation(_) => None,
+        }
+    }
+}
+
+impl CollectionUpdateOperations {
+    /// Number of points inserted, or whose vectors are updated, by the operation
+    pub fn upsert_batch_size(&self) -> Option<usize> {
+        let points_count = |points: &PointInsertOperationsInternal| match points {
+            PointInsertOperationsInternal::PointsBatch(batch) => batch.ids.len(),
+            PointInsertOperationsInternal::PointsList(points) => points.len(),
+        };
+
+        match self {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(points)) => {
+                Some(points_count(points))
+            }
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPointsConditional(operation),
+            ) => Some(points_count(&operation.points_op)),
+            CollectionUpdateOperations::VectorOperation(VectorOperations::UpdateVectors(
+                operation,
+            )) => Some(operation.points.len()),
+            _ => None,
+        }
+    }
+
+    /// Whether the operation may add new points to the collection
+    pub fn inserts_points(&self) -> bool {
+        matches!(
+            self,
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(_)
+                    | PointOperations::UpsertPointsConditional(_)
+                    | PointOperations::SyncPoints(_)
+            )
+        )
+    }
+}
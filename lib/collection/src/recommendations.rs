@@ -1,5 +1,6 @@
 use std::future::Future;
 use std::iter::Peekable;
+use std::sync::Arc;
 use std::time::Duration;
 
 use api::rest::RecommendStrategy;
@@ -341,7 +342,7 @@ fn recommend_by_avg_vector(
             min_should: None,
             must: filter.clone().map(|filter| vec![Condition::Filter(filter)]),
             must_not: Some(vec![Condition::HasId(HasIdCondition {
-                has_id: reference_vectors_ids.iter().cloned().collect(),
+                has_id: Arc::new(reference_vectors_ids.iter().cloned().collect()),
             })]),
         }),
         with_payload,
@@ -405,7 +406,7 @@ fn recommend_by_best_score(
             min_should: None,
             must: filter.map(|filter| vec![Condition::Filter(filter)]),
             must_not: Some(vec![Condition::HasId(HasIdCondition {
-                has_id: reference_vectors_ids.into_iter().collect(),
+                has_id: Arc::new(reference_vectors_ids.into_iter().collect()),
             })]),
         }),
         params,
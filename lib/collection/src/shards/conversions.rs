@@ -11,7 +11,7 @@ use api::grpc::qdrant::{
 };
 use segment::data_types::vectors::VectorStruct;
 use segment::json_path::JsonPath;
-use segment::types::{Filter, PayloadFieldSchema, PayloadSchemaParams, PointIdType, ScoredPoint};
+use segment::types::{Filter, PayloadFieldSchema, PointIdType, ScoredPoint};
 use tonic::Status;
 
 use crate::operations::conversions::write_ordering_to_proto;
@@ -55,6 +55,7 @@ pub fn internal_upsert_points(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     point_insert_operations: PointInsertOperationsInternal,
+    update_filter: Option<Filter>,
     wait: bool,
     ordering: Option<WriteOrdering>,
 ) -> CollectionResult<UpsertPointsInternal> {
@@ -73,6 +74,7 @@ pub fn internal_upsert_points(
             },
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            update_filter: update_filter.map(Into::into),
         }),
     })
 }
@@ -341,42 +343,25 @@ pub fn internal_create_index(
     let (field_type, field_index_params) = create_index
         .field_schema
         .map(|field_schema| match field_schema {
-            PayloadFieldSchema::FieldType(field_type) => (
-                match field_type {
-                    segment::types::PayloadSchemaType::Keyword => {
-                        api::grpc::qdrant::FieldType::Keyword as i32
-                    }
-                    segment::types::PayloadSchemaType::Integer => {
-                        api::grpc::qdrant::FieldType::Integer as i32
-                    }
-                    segment::types::PayloadSchemaType::Float => {
-                        api::grpc::qdrant::FieldType::Float as i32
-                    }
-                    segment::types::PayloadSchemaType::Geo => {
-                        api::grpc::qdrant::FieldType::Geo as i32
-                    }
-                    segment::types::PayloadSchemaType::Text => {
-                        api::grpc::qdrant::FieldType::Text as i32
-                    }
-                    segment::types::PayloadSchemaType::Bool => {
-                        api::grpc::qdrant::FieldType::Bool as i32
-                    }
-                    segment::types::PayloadSchemaType::Datetime => {
-                        api::grpc::qdrant::FieldType::Datetime as i32
-                    }
-                },
-                None,
-            ),
-            PayloadFieldSchema::FieldParams(field_params) => match field_params {
-                PayloadSchemaParams::Text(text_index_params) => (
-                    api::grpc::qdrant::FieldType::Text as i32,
-                    Some(text_index_params.into()),
-                ),
-                PayloadSchemaParams::Integer(integer_params) => (
-                    api::grpc::qdrant::FieldType::Integer as i32,
-                    Some(integer_params.into()),
-                ),
-            },
+            PayloadFieldSchema::FieldType(field_type) => (field_type, None),
+            PayloadFieldSchema::FieldParams(field_params) => {
+                (field_params.kind(), Some(field_params.into()))
+            }
+        })
+        .map(|(field_type, field_params)| {
+            let field_type = match field_type {
+                segment::types::PayloadSchemaType::Keyword => api::grpc::qdrant::FieldType::Keyword,
+                segment::types::PayloadSchemaType::Integer => api::grpc::qdrant::FieldType::Integer,
+                segment::types::PayloadSchemaType::Float => api::grpc::qdrant::FieldType::Float,
+                segment::types::PayloadSchemaType::Geo => api::grpc::qdrant::FieldType::Geo,
+                segment::types::PayloadSchemaType::Text => api::grpc::qdrant::FieldType::Text,
+                segment::types::PayloadSchemaType::Bool => api::grpc::qdrant::FieldType::Bool,
+                segment::types::PayloadSchemaType::Datetime => {
+                    api::grpc::qdrant::FieldType::Datetime
+                }
+                segment::types::PayloadSchemaType::Uuid => api::grpc::qdrant::FieldType::Uuid,
+            };
+            (field_type as i32, field_params)
         })
         .map(|(field_type, field_params)| (Some(field_type), field_params))
         .unwrap_or((None, None));
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -48,6 +49,7 @@ impl DummyShard {
             variant_name: Some("dummy shard".into()),
             segments: vec![],
             optimizations: Default::default(),
+            recall: vec![],
         }
     }
 
@@ -106,7 +108,17 @@ impl ShardOperation for DummyShard {
         &self,
         _: Arc<ShardQueryRequest>,
         _: &Handle,
+        _: Option<Duration>,
     ) -> CollectionResult<ShardQueryResponse> {
         self.dummy()
     }
+
+    async fn facet(
+        &self,
+        _: Arc<FacetParams>,
+        _: &Handle,
+        _: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.dummy()
+    }
 }
@@ -4,6 +4,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
@@ -17,7 +18,8 @@ use crate::hash_ring::HashRing;
 use crate::operations::point_ops::{PointOperations, PointStruct, PointSyncOperation};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult, UpdateStatus,
+    CountRequestInternal, CountResult, PointRequestInternal, RecallEstimationRequest, Record,
+    SegmentRecallEstimation, UpdateResult, UpdateStatus,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::{
@@ -171,6 +173,21 @@ impl ForwardProxyShard {
         self.wrapped_shard.get_telemetry_data(detail)
     }
 
+    pub fn has_expired_points(&self, now: u64) -> bool {
+        self.wrapped_shard.has_expired_points(now)
+    }
+
+    pub async fn estimate_recall(
+        &self,
+        request: Arc<RecallEstimationRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<SegmentRecallEstimation>> {
+        self.wrapped_shard
+            .estimate_recall(request, search_runtime_handle, timeout)
+            .await
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.wrapped_shard.update_tracker()
     }
@@ -287,8 +304,23 @@ impl ShardOperation for ForwardProxyShard {
         &self,
         request: Arc<ShardQueryRequest>,
         search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
     ) -> CollectionResult<ShardQueryResponse> {
         let local_shard = &self.wrapped_shard;
-        local_shard.query(request, search_runtime_handle).await
+        local_shard
+            .query(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .facet(request, search_runtime_handle, timeout)
+            .await
     }
 }
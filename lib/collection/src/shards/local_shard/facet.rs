@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use segment::data_types::facets::{FacetParams, FacetResponse};
+use tokio::runtime::Handle;
+
+use super::LocalShard;
+use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::common::stopping_guard::StoppingGuard;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+impl LocalShard {
+    /// Count points per value of the facet key in this shard.
+    ///
+    /// Counts of all values are returned, so that responses of multiple shards can be merged
+    /// exactly. The `limit` of the request is applied only after merging.
+    pub async fn do_facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let is_stopped_guard = StoppingGuard::new();
+
+        let facet_request = SegmentsSearcher::facet(
+            Arc::clone(&self.segments),
+            request,
+            search_runtime_handle,
+            is_stopped_guard.get_is_stopped(),
+        );
+
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let counts = tokio::time::timeout(timeout, facet_request)
+            .await
+            .map_err(|_| {
+                log::debug!("Facet timeout reached: {} seconds", timeout.as_secs());
+                // StoppingGuard takes care of setting is_stopped to true
+                CollectionError::timeout(timeout.as_secs() as usize, "Facet")
+            })??;
+
+        Ok(FacetResponse::top_hits(counts, usize::MAX))
+    }
+}
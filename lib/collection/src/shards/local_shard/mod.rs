@@ -1,6 +1,8 @@
 pub mod clock_map;
 pub mod disk_usage_watcher;
+pub(super) mod facet;
 pub(super) mod query;
+pub(super) mod recall;
 pub(super) mod scroll;
 pub(super) mod search;
 pub(super) mod shard_ops;
@@ -27,8 +29,8 @@ use segment::index::field_index::CardinalityEstimation;
 use segment::segment::Segment;
 use segment::segment_constructor::{build_segment, load_segment};
 use segment::types::{
-    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, SegmentType,
+    BinaryQuantizationEncoding, CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType,
+    PayloadStorageType, PointIdType, QuantizationConfig, ScalarType, SegmentConfig, SegmentType,
 };
 use segment::utils::mem::Mem;
 use tokio::fs::{copy, create_dir_all, remove_dir_all, remove_file};
@@ -50,9 +52,9 @@ use crate::config::CollectionConfig;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
     check_sparse_compatible_with_segment_config, CollectionError, CollectionInfoInternal,
-    CollectionResult, CollectionStatus, OptimizersStatus,
+    CollectionResult, CollectionStatus, OptimizersStatus, SegmentRecallEstimation,
 };
-use crate::operations::OperationWithClockTag;
+use crate::operations::{expiration, CollectionUpdateOperations, OperationWithClockTag};
 use crate::optimizers_builder::{build_optimizers, clear_temp_segments, OptimizersConfig};
 use crate::shards::shard::ShardId;
 use crate::shards::shard_config::{ShardConfig, SHARD_CONFIG_FILE};
@@ -81,6 +83,8 @@ pub struct LocalShard {
     pub(super) path: PathBuf,
     pub(super) optimizers: Arc<Vec<Arc<Optimizer>>>,
     pub(super) optimizers_log: Arc<ParkingMutex<TrackerLog>>,
+    /// Latest recall estimations of the approximate search, reported in telemetry
+    pub(super) recall_estimations: ParkingMutex<Vec<SegmentRecallEstimation>>,
     update_runtime: Handle,
     disk_usage_watcher: DiskUsageWatcher,
 }
@@ -190,6 +194,7 @@ impl LocalShard {
             update_runtime,
             optimizers,
             optimizers_log,
+            recall_estimations: ParkingMutex::new(Vec::new()),
             disk_usage_watcher,
         }
     }
@@ -320,8 +325,15 @@ impl LocalShard {
             );
             log::warn!("Shard has no appendable segments, this should never happen. Creating new appendable segment now");
             let segments_path = LocalShard::segments_path(shard_path);
-            let collection_params = collection_config.read().await.params.clone();
-            segment_holder.create_appendable_segment(&segments_path, &collection_params)?;
+            let (collection_params, collection_hnsw) = {
+                let config = collection_config.read().await;
+                (config.params.clone(), config.hnsw_config.clone())
+            };
+            segment_holder.create_appendable_segment(
+                &segments_path,
+                &collection_params,
+                &collection_hnsw,
+            )?;
         }
 
         let local_shard = LocalShard::new(
@@ -437,7 +449,9 @@ impl LocalShard {
         let mut segment_holder = SegmentHolder::default();
         let mut build_handlers = vec![];
 
-        let vector_params = config.params.to_base_vector_data()?;
+        let vector_params = config
+            .params
+            .to_appendable_vector_data(&config.hnsw_config)?;
         let sparse_vector_params = config.params.to_sparse_vector_data()?;
         let segment_number = config.optimizer_config.get_number_segments();
 
@@ -553,13 +567,16 @@ impl LocalShard {
         // (`SerdeWal::read_all` may even start reading WAL from some already truncated
         // index *occasionally*), but the storage can handle it.
 
+        // Update workers are not running yet, so linking of points into HNSW graphs is not stopped
+        let stopped = AtomicBool::new(false);
+
         for (op_num, update) in wal.read_all(false) {
             if let Some(clock_tag) = update.clock_tag {
                 newest_clocks.advance_clock(clock_tag);
             }
 
             // Propagate `CollectionError::ServiceError`, but skip other error types.
-            match &CollectionUpdater::update(segments, op_num, update.operation) {
+            match &CollectionUpdater::update(segments, op_num, update.operation, &stopped) {
                 Err(err @ CollectionError::ServiceError { error, backtrace }) => {
                     let path = self.path.display();
 
@@ -869,6 +886,24 @@ impl LocalShard {
         Ok(all_points)
     }
 
+    /// Current time to exclude expired points at, if the collection has a time-to-live
+    pub(super) async fn expiration_timestamp(&self) -> Option<u64> {
+        let ttl_sec = self.collection_config.read().await.params.ttl_sec;
+        ttl_sec.map(|_| expiration::now_timestamp())
+    }
+
+    /// Whether any point of the shard is expired at the given time
+    pub fn has_expired_points(&self, now: u64) -> bool {
+        let expired_filter = expiration::expired_filter(now);
+        self.segments.read().iter().any(|(_, segment)| {
+            !segment
+                .get()
+                .read()
+                .read_filtered(None, Some(1), Some(&expired_filter))
+                .is_empty()
+        })
+    }
+
     pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> LocalShardTelemetry {
         let segments_read_guard = self.segments.read();
         let segments: Vec<_> = segments_read_guard
@@ -880,6 +915,7 @@ impl LocalShard {
             None => OptimizersStatus::Ok,
             Some(error) => OptimizersStatus::Error(error.to_string()),
         };
+        self.prune_recall_estimations(&segments_read_guard);
         drop(segments_read_guard);
         let optimizations = self
             .optimizers
@@ -900,6 +936,7 @@ impl LocalShard {
                 optimizations,
                 log: self.optimizers_log.lock().to_telemetry(),
             },
+            recall: self.recall_estimations.lock().clone(),
         }
     }
 
@@ -922,7 +959,10 @@ impl LocalShard {
 
                 let quantized_size_bytes = match quantization_config {
                     None => 0,
-                    Some(QuantizationConfig::Scalar(_)) => vector_size,
+                    Some(QuantizationConfig::Scalar(sq)) => match sq.scalar.r#type {
+                        ScalarType::Int8 | ScalarType::Float8 => vector_size,
+                        ScalarType::Int4 => vector_size / 2,
+                    },
                     Some(QuantizationConfig::Product(pq)) => match pq.product.compression {
                         CompressionRatio::X4 => vector_size,
                         CompressionRatio::X8 => vector_size / 2,
@@ -930,7 +970,13 @@ impl LocalShard {
                         CompressionRatio::X32 => vector_size / 8,
                         CompressionRatio::X64 => vector_size / 16,
                     },
-                    Some(QuantizationConfig::Binary(_)) => vector_size / 8,
+                    Some(QuantizationConfig::Binary(bq)) => {
+                        match bq.binary.encoding.unwrap_or_default() {
+                            BinaryQuantizationEncoding::OneBit => vector_size / 8,
+                            BinaryQuantizationEncoding::OneAndHalfBits => vector_size * 3 / 16,
+                            BinaryQuantizationEncoding::TwoBits => vector_size / 4,
+                        }
+                    }
                 };
 
                 vector_size * size_of::<VectorElementType>() + quantized_size_bytes
@@ -1015,6 +1061,34 @@ impl LocalShard {
         self.wal.recovery_point().await
     }
 
+    /// Read up to `limit` operations from the WAL, starting at the given operation ID
+    ///
+    /// If `from` is not specified, reading starts at the oldest operation still kept in the WAL.
+    /// Fails with [`CollectionError::OutOfRange`] if the requested operation is truncated already.
+    pub fn read_wal_operations(
+        &self,
+        from: Option<u64>,
+        limit: usize,
+    ) -> CollectionResult<Vec<(u64, CollectionUpdateOperations)>> {
+        let wal = self.wal.wal.lock();
+
+        let first_index = wal.first_closed_index();
+        let from = from.unwrap_or(first_index);
+        if from < first_index {
+            return Err(CollectionError::out_of_range(format!(
+                "operation {from} is truncated from the WAL, oldest available operation is {first_index}",
+            )));
+        }
+
+        let operations = wal
+            .read(from)
+            .take(limit)
+            .map(|(op_num, operation)| (op_num, operation.operation))
+            .collect();
+
+        Ok(operations)
+    }
+
     /// Update the cutoff point on the current shard
     ///
     /// This also updates the highest seen clocks.
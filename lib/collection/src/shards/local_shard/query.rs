@@ -1,79 +1,123 @@
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
 use api::rest::OrderByInterface;
-use futures::future::BoxFuture;
+use common::types::ScoreType;
+use futures::future::{try_join_all, BoxFuture};
 use futures::FutureExt;
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
+use segment::data_types::order_by::OrderBy;
+use segment::index::rescore_formula::parsed_formula::ParsedFormula;
 use segment::types::{
-    Filter, HasIdCondition, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    Filter, HasIdCondition, Order, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
+    WithVector,
 };
 use tokio::runtime::Handle;
 
 use super::LocalShard;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::common::stopping_guard::StoppingGuard;
+use crate::config::CollectionParams;
 use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
     ScrollRequestInternal,
 };
 use crate::operations::universal_query::planned_query::{
-    MergePlan, PlannedQuery, PrefetchSource, ResultsMerge,
+    MergePlan, PlannedQuery, PrefetchSource, ResultsMerge, SampleRequest, TextSearchRequest,
 };
-use crate::operations::universal_query::shard_query::{Fusion, ScoringQuery, ShardQueryResponse};
+use crate::operations::universal_query::shard_query::{Sample, ScoringQuery, ShardQueryResponse};
 
 struct PrefetchHolder {
     core_results: Vec<Vec<ScoredPoint>>,
     scrolls: Vec<Vec<ScoredPoint>>,
+    text_results: Vec<Vec<ScoredPoint>>,
+    samples: Vec<Vec<ScoredPoint>>,
+    /// Order of the scores of each search result
+    search_orders: Vec<Order>,
+    /// Order of the scores of each scroll result
+    scroll_orders: Vec<Order>,
+    collection_params: CollectionParams,
 }
 
 impl PrefetchHolder {
-    fn new(core_results: Vec<Vec<ScoredPoint>>, scrolls: Vec<Vec<ScoredPoint>>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        core_results: Vec<Vec<ScoredPoint>>,
+        scrolls: Vec<Vec<ScoredPoint>>,
+        text_results: Vec<Vec<ScoredPoint>>,
+        samples: Vec<Vec<ScoredPoint>>,
+        search_orders: Vec<Order>,
+        scroll_orders: Vec<Order>,
+        collection_params: CollectionParams,
+    ) -> Self {
         Self {
             core_results,
             scrolls,
+            text_results,
+            samples,
+            search_orders,
+            scroll_orders,
+            collection_params,
         }
     }
 
-    /// Returns an iterator by Cow over the sources in no specific order.
-    fn iter_sources(
-        &self,
-        core_indices: Vec<usize>,
-        scroll_indices: Vec<usize>,
-        merged_list: Vec<Vec<ScoredPoint>>,
-    ) -> PrefetchIterator {
-        PrefetchIterator {
-            prefetch_holder: self,
-            core_indices,
-            scroll_indices,
-            merged_list,
+    /// Order of the scores produced by the given source
+    fn get_order(&self, source: &PrefetchSource) -> CollectionResult<Order> {
+        match source {
+            PrefetchSource::SearchesIdx(idx) => {
+                self.search_orders.get(*idx).copied().ok_or_else(|| {
+                    CollectionError::service_error(format!(
+                        "Search order index {idx} is out of bounds"
+                    ))
+                })
+            }
+            PrefetchSource::ScrollsIdx(idx) => {
+                self.scroll_orders.get(*idx).copied().ok_or_else(|| {
+                    CollectionError::service_error(format!(
+                        "Scroll order index {idx} is out of bounds"
+                    ))
+                })
+            }
+            PrefetchSource::TextSearchesIdx(_) | PrefetchSource::SamplesIdx(_) => {
+                Ok(Order::LargeBetter)
+            }
+            PrefetchSource::Prefetch(merge_plan) => match &merge_plan.merge {
+                Some(merge) => ScoringQuery::order(Some(&merge.rescore), &self.collection_params),
+                // Without merge, the plan has a single source which is returned as is
+                None => match merge_plan.sources.first() {
+                    Some(source) => self.get_order(source),
+                    None => Ok(Order::LargeBetter),
+                },
+            },
         }
     }
-}
 
-struct PrefetchIterator<'a> {
-    prefetch_holder: &'a PrefetchHolder,
-    core_indices: Vec<usize>,
-    scroll_indices: Vec<usize>,
-    merged_list: Vec<Vec<ScoredPoint>>,
-}
+    fn get_search(&self, idx: usize) -> CollectionResult<&Vec<ScoredPoint>> {
+        self.core_results.get(idx).ok_or_else(|| {
+            CollectionError::service_error(format!("Search result index {idx} is out of bounds"))
+        })
+    }
 
-impl<'a> Iterator for PrefetchIterator<'a> {
-    type Item = Cow<'a, Vec<ScoredPoint>>;
+    fn get_scroll(&self, idx: usize) -> CollectionResult<&Vec<ScoredPoint>> {
+        self.scrolls.get(idx).ok_or_else(|| {
+            CollectionError::service_error(format!("Scroll result index {idx} is out of bounds"))
+        })
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(idx) = self.core_indices.pop() {
-            return self
-                .prefetch_holder
-                .core_results
-                .get(idx)
-                .map(Cow::Borrowed);
-        }
-        if let Some(idx) = self.scroll_indices.pop() {
-            return self.prefetch_holder.scrolls.get(idx).map(Cow::Borrowed);
-        }
-        self.merged_list.pop().map(Cow::Owned)
+    fn get_text_search(&self, idx: usize) -> CollectionResult<&Vec<ScoredPoint>> {
+        self.text_results.get(idx).ok_or_else(|| {
+            CollectionError::service_error(format!(
+                "Text search result index {idx} is out of bounds"
+            ))
+        })
+    }
+
+    fn get_sample(&self, idx: usize) -> CollectionResult<&Vec<ScoredPoint>> {
+        self.samples.get(idx).ok_or_else(|| {
+            CollectionError::service_error(format!("Sample result index {idx} is out of bounds"))
+        })
     }
 }
 
@@ -85,6 +129,25 @@ impl LocalShard {
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<ShardQueryResponse> {
+        let collection_params = self.collection_config.read().await.params.clone();
+
+        let search_orders = request
+            .searches
+            .searches
+            .iter()
+            .map(|search| search.query.order(&collection_params))
+            .collect::<CollectionResult<Vec<_>>>()?;
+
+        let scroll_orders = request
+            .scrolls
+            .iter()
+            .map(|scroll| match &scroll.order_by {
+                Some(order_by) => Order::from(OrderBy::from(order_by.clone()).direction()),
+                // Scroll by id
+                None => Order::SmallBetter,
+            })
+            .collect();
+
         let core_results = self
             .do_search(request.searches, search_runtime_handle, timeout)
             .await?;
@@ -93,7 +156,28 @@ impl LocalShard {
             .query_scroll_batch(request.scrolls, search_runtime_handle)
             .await?;
 
-        let prefetch_holder = PrefetchHolder::new(core_results, scrolls);
+        let text_results = try_join_all(request.text_searches.iter().map(|text_search| {
+            self.text_search(text_search.clone(), search_runtime_handle, timeout)
+        }))
+        .await?;
+
+        let samples = try_join_all(
+            request
+                .samples
+                .iter()
+                .map(|sample| self.sample_points(sample.clone(), search_runtime_handle, timeout)),
+        )
+        .await?;
+
+        let prefetch_holder = PrefetchHolder::new(
+            core_results,
+            scrolls,
+            text_results,
+            samples,
+            search_orders,
+            scroll_orders,
+            collection_params,
+        );
 
         let mut scored_points = self
             .recurse_prefetch(
@@ -146,32 +230,44 @@ impl LocalShard {
         'shard: 'query,
     {
         async move {
-            let max_len = merge_plan.sources.len();
-            let mut search_indices = Vec::with_capacity(max_len);
-            let mut scroll_indices = Vec::with_capacity(max_len);
-            let mut merged_list = Vec::with_capacity(max_len);
+            // Sources keep the order of the prefetches, fusion and rescoring may depend on it
+            let mut sources = Vec::with_capacity(merge_plan.sources.len());
+
+            let orders = merge_plan
+                .sources
+                .iter()
+                .map(|source| prefetch_holder.get_order(source))
+                .collect::<CollectionResult<Vec<_>>>()?;
 
             for source in merge_plan.sources.into_iter() {
                 match source {
-                    PrefetchSource::SearchesIdx(idx) => search_indices.push(idx),
-                    PrefetchSource::ScrollsIdx(idx) => scroll_indices.push(idx),
+                    PrefetchSource::SearchesIdx(idx) => {
+                        sources.push(Cow::Borrowed(prefetch_holder.get_search(idx)?))
+                    }
+                    PrefetchSource::ScrollsIdx(idx) => {
+                        sources.push(Cow::Borrowed(prefetch_holder.get_scroll(idx)?))
+                    }
+                    PrefetchSource::TextSearchesIdx(idx) => {
+                        sources.push(Cow::Borrowed(prefetch_holder.get_text_search(idx)?))
+                    }
+                    PrefetchSource::SamplesIdx(idx) => {
+                        sources.push(Cow::Borrowed(prefetch_holder.get_sample(idx)?))
+                    }
                     PrefetchSource::Prefetch(prefetch) => {
                         let merged = self
                             .recurse_prefetch(
-                                prefetch,
+                                *prefetch,
                                 prefetch_holder,
                                 search_runtime_handle,
                                 timeout,
                                 depth + 1,
                             )
                             .await?;
-                        merged_list.extend(merged);
+                        sources.extend(merged.into_iter().map(Cow::Owned));
                     }
                 }
             }
 
-            let sources = prefetch_holder.iter_sources(search_indices, scroll_indices, merged_list);
-
             let root_query_needs_intermediate_results = || {
                 merge_plan
                     .merge
@@ -181,11 +277,17 @@ impl LocalShard {
             };
 
             if depth == 0 && root_query_needs_intermediate_results() {
-                // in case of top level RRF, we need to propagate intermediate results
-                Ok(sources.map(Cow::into_owned).collect())
+                // in case of top level fusion, we need to propagate intermediate results
+                Ok(sources.into_iter().map(Cow::into_owned).collect())
             } else {
                 let merged = self
-                    .merge_prefetches(sources, merge_plan.merge, search_runtime_handle, timeout)
+                    .merge_prefetches(
+                        sources.into_iter(),
+                        &orders,
+                        merge_plan.merge,
+                        search_runtime_handle,
+                        timeout,
+                    )
                     .await?;
                 Ok(vec![merged])
             }
@@ -198,6 +300,7 @@ impl LocalShard {
     async fn rescore<'a>(
         &self,
         sources: impl Iterator<Item = Cow<'a, Vec<ScoredPoint>>>,
+        orders: &[Order],
         merge: ResultsMerge,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
@@ -210,7 +313,7 @@ impl LocalShard {
         } = merge;
 
         match rescore {
-            ScoringQuery::Fusion(Fusion::Rrf) => {
+            ScoringQuery::Fusion(fusion) => {
                 let sources: Vec<_> = sources.map(Cow::into_owned).collect();
 
                 // TODO(universal-query): Remove this ugly part when we propagate merged filters to leaf queries
@@ -222,9 +325,9 @@ impl LocalShard {
                     None
                 };
 
-                let mut top_rrf = rrf_scoring(sources);
+                let mut top_fused = fusion.fuse(sources, orders);
 
-                top_rrf = top_rrf
+                top_fused = top_fused
                     .into_iter()
                     .filter(|point| {
                         // TODO(universal-query): Remove this ugly part when we propagate merged filters to leaf queries
@@ -242,7 +345,48 @@ impl LocalShard {
                     .take(limit)
                     .collect();
 
-                Ok(top_rrf)
+                Ok(top_fused)
+            }
+            ScoringQuery::Formula(formula) => {
+                let sources: Vec<_> = sources.map(Cow::into_owned).collect();
+
+                self.rescore_with_formula(
+                    formula,
+                    sources,
+                    filter,
+                    score_threshold,
+                    limit,
+                    search_runtime_handle,
+                    timeout,
+                )
+                .await
+            }
+            ScoringQuery::Text(text_query) => {
+                // create single text search request for rescoring query
+                let filter = filter_with_sources_ids(sources, filter);
+
+                let text_search = TextSearchRequest {
+                    query: text_query,
+                    filter: Some(filter),
+                    score_threshold,
+                    limit,
+                };
+
+                self.text_search(text_search, search_runtime_handle, timeout)
+                    .await
+            }
+            ScoringQuery::Sample(sample) => {
+                // create single sample request among the prefetched points
+                let filter = filter_with_sources_ids(sources, filter);
+
+                let sample_request = SampleRequest {
+                    sample,
+                    filter: Some(filter),
+                    limit,
+                };
+
+                self.sample_points(sample_request, search_runtime_handle, timeout)
+                    .await
             }
             ScoringQuery::OrderBy(order_by) => {
                 // create single scroll request for rescoring query
@@ -302,18 +446,147 @@ impl LocalShard {
         }
     }
 
+    /// Rescore the points of the sources with the formula, in all segments
+    #[allow(clippy::too_many_arguments)]
+    async fn rescore_with_formula(
+        &self,
+        formula: ParsedFormula,
+        sources: Vec<Vec<ScoredPoint>>,
+        filter: Option<Filter>,
+        score_threshold: Option<ScoreType>,
+        limit: usize,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let is_stopped_guard = StoppingGuard::new();
+
+        let rescore_request = SegmentsSearcher::rescore_with_formula(
+            Arc::clone(&self.segments),
+            Arc::new(formula),
+            Arc::new(sources),
+            filter.map(Arc::new),
+            limit,
+            search_runtime_handle,
+            is_stopped_guard.get_is_stopped(),
+        );
+
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let segments_results = tokio::time::timeout(timeout, rescore_request)
+            .await
+            .map_err(|_| {
+                log::debug!(
+                    "Formula rescoring timeout reached: {} seconds",
+                    timeout.as_secs()
+                );
+                // StoppingGuard takes care of setting is_stopped to true
+                CollectionError::timeout(timeout.as_secs() as usize, "Formula rescoring")
+            })??;
+
+        Ok(merge_segments_results(
+            segments_results,
+            score_threshold,
+            limit,
+        ))
+    }
+
+    /// Find the points most relevant to the text query, in all segments
+    async fn text_search(
+        &self,
+        request: TextSearchRequest,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let TextSearchRequest {
+            query,
+            filter,
+            score_threshold,
+            limit,
+        } = request;
+
+        let is_stopped_guard = StoppingGuard::new();
+
+        let text_search = SegmentsSearcher::text_search(
+            Arc::clone(&self.segments),
+            Arc::new(query),
+            filter.map(Arc::new),
+            limit,
+            search_runtime_handle,
+            is_stopped_guard.get_is_stopped(),
+        );
+
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let segments_results =
+            tokio::time::timeout(timeout, text_search)
+                .await
+                .map_err(|_| {
+                    log::debug!("Text search timeout reached: {} seconds", timeout.as_secs());
+                    // StoppingGuard takes care of setting is_stopped to true
+                    CollectionError::timeout(timeout.as_secs() as usize, "Text search")
+                })??;
+
+        Ok(merge_segments_results(
+            segments_results,
+            score_threshold,
+            limit,
+        ))
+    }
+
+    /// Sample points which satisfy the filter, uniformly across all segments
+    async fn sample_points(
+        &self,
+        request: SampleRequest,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let SampleRequest {
+            sample: Sample::Random,
+            filter,
+            limit,
+        } = request;
+
+        let is_stopped_guard = StoppingGuard::new();
+
+        let sample = SegmentsSearcher::sample_random(
+            Arc::clone(&self.segments),
+            filter.map(Arc::new),
+            limit,
+            search_runtime_handle,
+            is_stopped_guard.get_is_stopped(),
+        );
+
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let segments_results = tokio::time::timeout(timeout, sample).await.map_err(|_| {
+            log::debug!("Sampling timeout reached: {} seconds", timeout.as_secs());
+            // StoppingGuard takes care of setting is_stopped to true
+            CollectionError::timeout(timeout.as_secs() as usize, "Sampling")
+        })??;
+
+        // Sampling keys of all segments are comparable, the largest ones form a uniform sample
+        Ok(merge_segments_results(segments_results, None, limit))
+    }
+
     /// Merge multiple prefetches into a single result up to the limit.
     /// Rescores if required.
     async fn merge_prefetches<'a>(
         &self,
         mut sources: impl Iterator<Item = Cow<'a, Vec<ScoredPoint>>>,
+        orders: &[Order],
         merge: Option<ResultsMerge>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
         if let Some(results_merge) = merge {
-            self.rescore(sources, results_merge, search_runtime_handle, timeout)
-                .await
+            self.rescore(
+                sources,
+                orders,
+                results_merge,
+                search_runtime_handle,
+                timeout,
+            )
+            .await
         } else {
             // The whole query request has no prefetches, and everything comes directly from a single source
             let top = sources
@@ -330,6 +603,42 @@ impl LocalShard {
     }
 }
 
+/// Merge the best points of each segment, larger scores are better.
+///
+/// A point can be in multiple segments, only its latest version is kept.
+fn merge_segments_results(
+    segments_results: Vec<Vec<ScoredPoint>>,
+    score_threshold: Option<ScoreType>,
+    limit: usize,
+) -> Vec<ScoredPoint> {
+    let mut latest_points: HashMap<PointIdType, ScoredPoint> = HashMap::new();
+    for point in segments_results.into_iter().flatten() {
+        match latest_points.entry(point.id) {
+            Entry::Occupied(mut entry) => {
+                if entry.get().version < point.version {
+                    entry.insert(point);
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(point);
+            }
+        }
+    }
+
+    let mut merged: Vec<_> = latest_points.into_values().collect();
+    merged.sort_unstable_by(|a, b| b.score.total_cmp(&a.score));
+
+    merged
+        .into_iter()
+        .take_while(|point| {
+            score_threshold
+                .map(|threshold| point.score >= threshold)
+                .unwrap_or(true)
+        })
+        .take(limit)
+        .collect()
+}
+
 /// Extracts point ids from sources, creates a filter and merges it with the provided filter.
 fn filter_with_sources_ids<'a>(
     sources: impl Iterator<Item = Cow<'a, Vec<ScoredPoint>>>,
@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use segment::entry::entry_point::SegmentEntry as _;
+use tokio::runtime::Handle;
+
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::common::stopping_guard::StoppingGuard;
+use crate::operations::types::{
+    CollectionError, CollectionResult, RecallEstimationRequest, SegmentRecallEstimation,
+};
+
+impl LocalShard {
+    /// Estimate recall of the approximate search in every indexed segment of this shard.
+    ///
+    /// Estimations are kept to be reported in telemetry. If the request targets a single vector,
+    /// only the previous estimations of this vector are replaced.
+    pub async fn estimate_recall(
+        &self,
+        request: Arc<RecallEstimationRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<SegmentRecallEstimation>> {
+        let is_stopped_guard = StoppingGuard::new();
+
+        let estimate_request = SegmentsSearcher::estimate_recall(
+            Arc::clone(&self.segments),
+            request.clone(),
+            search_runtime_handle,
+            is_stopped_guard.get_is_stopped(),
+        );
+
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let recalls = tokio::time::timeout(timeout, estimate_request)
+            .await
+            .map_err(|_| {
+                log::debug!(
+                    "Recall estimation timeout reached: {} seconds",
+                    timeout.as_secs()
+                );
+                // StoppingGuard takes care of setting is_stopped to true
+                CollectionError::timeout(timeout.as_secs() as usize, "Recall estimation")
+            })??;
+
+        {
+            let mut recall_estimations = self.recall_estimations.lock();
+            match &request.using {
+                Some(using) => recall_estimations.retain(|recall| &recall.vector_name != using),
+                None => recall_estimations.clear(),
+            }
+            recall_estimations.extend(recalls.iter().cloned());
+        }
+
+        // Segments may have been optimized away, while the estimation was running
+        self.prune_recall_estimations(&self.segments.read());
+
+        Ok(recalls)
+    }
+
+    /// Drop estimations of segments, which are no longer present in the shard
+    ///
+    /// Segments are replaced by optimizations, estimations of old segments would be reported
+    /// forever otherwise.
+    pub(super) fn prune_recall_estimations(&self, segments: &SegmentHolder) {
+        let segment_names: HashSet<_> = segments
+            .iter()
+            .filter_map(|(_id, segment)| {
+                segment
+                    .get()
+                    .read()
+                    .data_path()
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+            })
+            .collect();
+
+        self.recall_estimations
+            .lock()
+            .retain(|recall| segment_names.contains(&recall.segment));
+    }
+}
@@ -94,7 +94,7 @@ impl LocalShard {
                     .map(|(record, value)| ScoredPoint {
                         id: record.id,
                         version: 0,
-                        score: 0.0,
+                        score: order_by.score(value),
                         payload: record.payload,
                         vector: record.vector,
                         shard_key: record.shard_key,
@@ -1,22 +1,26 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    Condition, ExtendedPointId, Filter, HasIdCondition, ScoredPoint, WithPayload,
+    WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
 use tokio::sync::oneshot;
 
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::operations::point_ops::PointOperations;
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
+    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
     CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult, UpdateStatus,
 };
 use crate::operations::universal_query::planned_query::PlannedQuery;
 use crate::operations::universal_query::shard_query::ShardQueryRequest;
-use crate::operations::OperationWithClockTag;
+use crate::operations::{expiration, CollectionUpdateOperations, OperationWithClockTag};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::update_handler::{OperationData, UpdateSignal};
@@ -56,6 +60,14 @@ impl ShardOperation for LocalShard {
             ));
         }
 
+        // Conditional upserts report how many of their points were updated and skipped
+        let conditional_points_count = match &operation.operation {
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPointsConditional(conditional_upsert),
+            ) => Some(conditional_upsert.points_count()),
+            _ => None,
+        };
+
         let operation_id = {
             let update_sender = self.update_sender.load();
             let channel_permit = update_sender.reserve().await?;
@@ -71,6 +83,8 @@ impl ShardOperation for LocalShard {
                     return Ok(UpdateResult {
                         operation_id: None,
                         status: UpdateStatus::ClockRejected,
+                        updated_count: None,
+                        skipped_count: None,
                         clock_tag: operation.clock_tag,
                     });
                 }
@@ -89,16 +103,21 @@ impl ShardOperation for LocalShard {
         };
 
         if let Some(receiver) = callback_receiver {
-            let _res = receiver.await??;
+            let updated_count = receiver.await??;
             Ok(UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Completed,
+                updated_count: conditional_points_count.map(|_| updated_count),
+                skipped_count: conditional_points_count
+                    .map(|points_count| points_count.saturating_sub(updated_count)),
                 clock_tag: operation.clock_tag,
             })
         } else {
             Ok(UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Acknowledged,
+                updated_count: None,
+                skipped_count: None,
                 clock_tag: operation.clock_tag,
             })
         }
@@ -114,6 +133,12 @@ impl ShardOperation for LocalShard {
         search_runtime_handle: &Handle,
         order_by: Option<&OrderBy>,
     ) -> CollectionResult<Vec<Record>> {
+        let not_expired_filter = self
+            .expiration_timestamp()
+            .await
+            .map(|now| expiration::exclude_expired(filter, now));
+        let filter = not_expired_filter.as_ref().or(filter);
+
         match order_by {
             None => {
                 self.scroll_by_id(
@@ -162,16 +187,36 @@ impl ShardOperation for LocalShard {
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let request = match self.expiration_timestamp().await {
+            Some(now) => Arc::new(CoreSearchRequestBatch {
+                searches: request
+                    .searches
+                    .iter()
+                    .map(|search| CoreSearchRequest {
+                        filter: Some(expiration::exclude_expired(search.filter.as_ref(), now)),
+                        ..search.clone()
+                    })
+                    .collect(),
+            }),
+            None => request,
+        };
+
         self.do_search(request, search_runtime_handle, timeout)
             .await
     }
 
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
+        let not_expired_filter = self
+            .expiration_timestamp()
+            .await
+            .map(|now| expiration::exclude_expired(request.filter.as_ref(), now));
+        let filter = not_expired_filter.as_ref().or(request.filter.as_ref());
+
         let total_count = if request.exact {
-            let all_points = self.read_filtered(request.filter.as_ref())?;
+            let all_points = self.read_filtered(filter)?;
             all_points.len()
         } else {
-            self.estimate_cardinality(request.filter.as_ref())?.exp
+            self.estimate_cardinality(filter)?.exp
         };
         Ok(CountResult { count: total_count })
     }
@@ -182,19 +227,66 @@ impl ShardOperation for LocalShard {
         with_payload: &WithPayload,
         with_vector: &WithVector,
     ) -> CollectionResult<Vec<Record>> {
-        SegmentsSearcher::retrieve(self.segments(), &request.ids, with_payload, with_vector)
+        let Some(now) = self.expiration_timestamp().await else {
+            return SegmentsSearcher::retrieve(
+                self.segments(),
+                &request.ids,
+                with_payload,
+                with_vector,
+            );
+        };
+
+        // Only retrieve points, which are not expired yet
+        let requested_ids = Filter::new_must(Condition::HasId(HasIdCondition::from(
+            request.ids.iter().copied().collect::<HashSet<_>>(),
+        )));
+        let not_expired_ids = self.read_filtered(Some(&expiration::exclude_expired(
+            Some(&requested_ids),
+            now,
+        )))?;
+        let ids: Vec<_> = request
+            .ids
+            .iter()
+            .copied()
+            .filter(|id| not_expired_ids.contains(id))
+            .collect();
+
+        SegmentsSearcher::retrieve(self.segments(), &ids, with_payload, with_vector)
     }
 
     async fn query(
         &self,
         request: Arc<ShardQueryRequest>,
         search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let mut request = request.as_ref().to_owned();
+        if let Some(now) = self.expiration_timestamp().await {
+            expiration::exclude_expired_from_query(&mut request, now);
+        }
+
         self.do_planned_query(
-            PlannedQuery::try_from(request.as_ref().to_owned())?,
+            PlannedQuery::try_from(request)?,
             search_runtime_handle,
-            None,
+            timeout,
         )
         .await
     }
+
+    async fn facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let request = match self.expiration_timestamp().await {
+            Some(now) => Arc::new(FacetParams {
+                filter: Some(expiration::exclude_expired(request.filter.as_ref(), now)),
+                ..request.as_ref().clone()
+            }),
+            None => request,
+        };
+
+        self.do_facet(request, search_runtime_handle, timeout).await
+    }
 }
@@ -6,6 +6,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
@@ -21,10 +22,11 @@ use crate::operations::operation_effect::{
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, PointRequestInternal, RecallEstimationRequest, Record,
+    SegmentRecallEstimation, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
-use crate::operations::OperationWithClockTag;
+use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
@@ -124,9 +126,32 @@ impl ProxyShard {
         self.wrapped_shard.get_telemetry_data(detail)
     }
 
+    pub fn has_expired_points(&self, now: u64) -> bool {
+        self.wrapped_shard.has_expired_points(now)
+    }
+
+    pub async fn estimate_recall(
+        &self,
+        request: Arc<RecallEstimationRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<SegmentRecallEstimation>> {
+        self.wrapped_shard
+            .estimate_recall(request, search_runtime_handle, timeout)
+            .await
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.wrapped_shard.update_tracker()
     }
+
+    pub fn read_wal_operations(
+        &self,
+        from: Option<u64>,
+        limit: usize,
+    ) -> CollectionResult<Vec<(u64, CollectionUpdateOperations)>> {
+        self.wrapped_shard.read_wal_operations(from, limit)
+    }
 }
 
 #[async_trait]
@@ -252,9 +277,21 @@ impl ShardOperation for ProxyShard {
         &self,
         request: Arc<ShardQueryRequest>,
         search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
     ) -> CollectionResult<ShardQueryResponse> {
         self.wrapped_shard
-            .query(request, search_runtime_handle)
+            .query(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.wrapped_shard
+            .facet(request, search_runtime_handle, timeout)
             .await
     }
 }
@@ -6,6 +6,7 @@ use std::time::Duration;
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
 use parking_lot::Mutex as ParkingMutex;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -20,10 +21,11 @@ use super::update_tracker::UpdateTracker;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, PointRequestInternal, RecallEstimationRequest, Record,
+    SegmentRecallEstimation, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
-use crate::operations::OperationWithClockTag;
+use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
@@ -164,6 +166,28 @@ impl QueueProxyShard {
             .get_telemetry_data(detail)
     }
 
+    pub fn has_expired_points(&self, now: u64) -> bool {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .has_expired_points(now)
+    }
+
+    pub async fn estimate_recall(
+        &self,
+        request: Arc<RecallEstimationRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<SegmentRecallEstimation>> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .estimate_recall(request, search_runtime_handle, timeout)
+            .await
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.inner
             .as_ref()
@@ -172,6 +196,18 @@ impl QueueProxyShard {
             .update_tracker()
     }
 
+    pub fn read_wal_operations(
+        &self,
+        from: Option<u64>,
+        limit: usize,
+    ) -> CollectionResult<Vec<(u64, CollectionUpdateOperations)>> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .read_wal_operations(from, limit)
+    }
+
     /// Check if the queue proxy shard is already finalized
     #[cfg(debug_assertions)]
     fn is_finalized(&self) -> bool {
@@ -297,12 +333,27 @@ impl ShardOperation for QueueProxyShard {
         &self,
         request: Arc<ShardQueryRequest>,
         search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
     ) -> CollectionResult<ShardQueryResponse> {
         self.inner
             .as_ref()
             .expect("Queue proxy has been finalized")
             .wrapped_shard
-            .query(request, search_runtime_handle)
+            .query(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .facet(request, search_runtime_handle, timeout)
             .await
     }
 }
@@ -583,9 +634,21 @@ impl ShardOperation for Inner {
         &self,
         request: Arc<ShardQueryRequest>,
         search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         self.wrapped_shard
-            .query(request, search_runtime_handle)
+            .query(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.wrapped_shard
+            .facet(request, search_runtime_handle, timeout)
             .await
     }
 }
@@ -10,11 +10,11 @@ use api::grpc::qdrant::shard_snapshot_location::Location;
 use api::grpc::qdrant::shard_snapshots_client::ShardSnapshotsClient;
 use api::grpc::qdrant::{
     CollectionOperationResponse, CoreSearchBatchPointsInternal, CountPoints, CountPointsInternal,
-    GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal,
-    GetShardRecoveryPointRequest, HealthCheckRequest, InitiateShardTransferRequest,
-    QueryPointsInternal, QueryShardPoints, RecoverShardSnapshotRequest, RecoverSnapshotResponse,
-    ScrollPoints, ScrollPointsInternal, ShardSnapshotLocation, UpdateShardCutoffPointRequest,
-    WaitForShardStateRequest,
+    FacetCountsInternal, GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints,
+    GetPointsInternal, GetShardRecoveryPointRequest, HealthCheckRequest,
+    InitiateShardTransferRequest, QueryPointsInternal, QueryShardPoints,
+    RecoverShardSnapshotRequest, RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal,
+    ShardSnapshotLocation, UpdateShardCutoffPointRequest, WaitForShardStateRequest,
 };
 use api::grpc::transport_channel_pool::{AddTimeout, MAX_GRPC_CHANNEL_TIMEOUT};
 use async_trait::async_trait;
@@ -23,6 +23,7 @@ use parking_lot::Mutex;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
+use segment::data_types::facets::{FacetParams, FacetResponse, FacetValueHit};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -258,6 +259,23 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         point_insert_operations,
+                        None,
+                        wait,
+                        ordering,
+                    )?;
+                    self.with_points_client(|mut client| async move {
+                        client.upsert(tonic::Request::new(request.clone())).await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PointOperations::UpsertPointsConditional(conditional_upsert) => {
+                    let request = &internal_upsert_points(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        conditional_upsert.points_op,
+                        Some(conditional_upsert.condition),
                         wait,
                         ordering,
                     )?;
@@ -841,6 +859,7 @@ impl ShardOperation for RemoteShard {
         &self,
         request: Arc<ShardQueryRequest>,
         _search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
     ) -> CollectionResult<ShardQueryResponse> {
         let is_payload_required = request.with_payload.is_required();
 
@@ -854,7 +873,13 @@ impl ShardOperation for RemoteShard {
 
         let query_response = self
             .with_points_client(|mut client| async move {
-                client.query(tonic::Request::new(request.clone())).await
+                let mut request = tonic::Request::new(request.clone());
+
+                if let Some(timeout) = timeout {
+                    request.set_timeout(timeout);
+                }
+
+                client.query(request).await
             })
             .await?
             .into_inner();
@@ -873,4 +898,40 @@ impl ShardOperation for RemoteShard {
 
         result.map_err(CollectionError::from)
     }
+
+    async fn facet(
+        &self,
+        request: Arc<FacetParams>,
+        _search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let request = &FacetCountsInternal {
+            collection_name: self.collection_id.clone(),
+            key: request.key.to_string(),
+            filter: request.filter.clone().map(|filter| filter.into()),
+            shard_id: self.id,
+            timeout: timeout.map(|t| t.as_secs()),
+        };
+
+        let facet_response = self
+            .with_points_client(|mut client| async move {
+                let mut request = tonic::Request::new(request.clone());
+
+                if let Some(timeout) = timeout {
+                    request.set_timeout(timeout);
+                }
+
+                client.facet(request).await
+            })
+            .await?
+            .into_inner();
+
+        let hits = facet_response
+            .hits
+            .into_iter()
+            .map(FacetValueHit::try_from)
+            .collect::<Result<Vec<_>, Status>>()?;
+
+        Ok(FacetResponse { hits })
+    }
 }
@@ -27,7 +27,10 @@ use super::CollectionId;
 use crate::common::snapshots_manager::SnapshotStorageManager;
 use crate::config::CollectionConfig;
 use crate::operations::shared_storage_config::SharedStorageConfig;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{
+    CollectionError, CollectionResult, RecallEstimationRequest, SegmentRecallEstimation,
+};
+use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::OptimizersConfig;
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
@@ -856,6 +859,22 @@ impl ShardReplicaSet {
         local_shard.shard_recovery_point().await
     }
 
+    /// Read operations from the WAL of the local shard.
+    pub(crate) async fn read_wal_operations(
+        &self,
+        from: Option<u64>,
+        limit: usize,
+    ) -> CollectionResult<Vec<(u64, CollectionUpdateOperations)>> {
+        let local_shard = self.local.read().await;
+        let Some(local_shard) = local_shard.as_ref() else {
+            return Err(CollectionError::NotFound {
+                what: "Peer does not have local shard".into(),
+            });
+        };
+
+        local_shard.read_wal_operations(from, limit)
+    }
+
     /// Update the cutoff point for the local shard.
     pub(crate) async fn update_shard_cutoff_point(
         &self,
@@ -871,6 +890,31 @@ impl ShardReplicaSet {
         local_shard.update_cutoff(cutoff).await
     }
 
+    /// Whether the local shard has points, which are expired at the given time
+    pub(crate) async fn local_has_expired_points(&self, now: u64) -> bool {
+        let local_shard = self.local.read().await;
+        local_shard
+            .as_ref()
+            .is_some_and(|local_shard| local_shard.has_expired_points(now))
+    }
+
+    /// Estimate recall of the approximate search in the local shard, if there is one.
+    pub(crate) async fn estimate_local_recall(
+        &self,
+        request: Arc<RecallEstimationRequest>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Option<Vec<SegmentRecallEstimation>>> {
+        let local_shard = self.local.read().await;
+        let Some(local_shard) = local_shard.as_ref() else {
+            return Ok(None);
+        };
+
+        local_shard
+            .estimate_recall(request, &self.search_runtime, timeout)
+            .await
+            .map(Some)
+    }
+
     pub(crate) fn get_snapshots_storage_manager(&self) -> CollectionResult<SnapshotStorageManager> {
         SnapshotStorageManager::new(self.shared_storage_config.snapshots_config.clone())
     }
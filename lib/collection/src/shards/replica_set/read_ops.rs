@@ -2,6 +2,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use futures::FutureExt as _;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::*;
 
@@ -143,13 +144,34 @@ impl ShardReplicaSet {
         request: Arc<ShardQueryRequest>,
         read_consistency: Option<ReadConsistency>,
         local_only: bool,
+        timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         self.execute_and_resolve_read_operation(
             |shard| {
                 let request = Arc::clone(&request);
                 let search_runtime = self.search_runtime.clone();
 
-                async move { shard.query(request, &search_runtime).await }.boxed()
+                async move { shard.query(request, &search_runtime, timeout).await }.boxed()
+            },
+            read_consistency,
+            local_only,
+        )
+        .await
+    }
+
+    pub async fn facet(
+        &self,
+        request: Arc<FacetParams>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.execute_and_resolve_read_operation(
+            |shard| {
+                let request = Arc::clone(&request);
+                let search_runtime = self.search_runtime.clone();
+
+                async move { shard.facet(request, &search_runtime, timeout).await }.boxed()
             },
             read_consistency,
             local_only,
@@ -112,6 +112,11 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Whether this peer leads updates of the shard with the given ordering
+    pub(crate) fn is_update_leader(&self, ordering: WriteOrdering) -> bool {
+        self.leader_peer_for_update(ordering) == Some(self.this_peer_id())
+    }
+
     /// Designated a leader replica for the update based on the WriteOrdering
     fn leader_peer_for_update(&self, ordering: WriteOrdering) -> Option<PeerId> {
         match ordering {
@@ -578,6 +583,7 @@ mod tests {
             wal_config,
             hnsw_config: Default::default(),
             quantization_config: None,
+            strict_mode_config: None,
         };
 
         let shared_config = Arc::new(RwLock::new(config.clone()));
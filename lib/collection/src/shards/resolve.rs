@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::hash;
 
+use segment::data_types::facets::{FacetResponse, FacetValue};
 use segment::types::{Payload, ScoredPoint};
 use tinyvec::TinyVec;
 
@@ -41,6 +42,38 @@ impl Resolve for CountResult {
     }
 }
 
+impl Resolve for FacetResponse {
+    /// Resolve the count of each value independently, the same way as for [`CountResult`].
+    /// A value which is missing in some response is counted as `0` in it.
+    fn resolve(responses: Vec<Self>, condition: ResolveCondition) -> Self {
+        let responses_count = responses.len();
+
+        let mut counts_per_value: HashMap<FacetValue, Vec<usize>> = HashMap::new();
+        for response in responses {
+            for (value, count) in response.into_counts() {
+                counts_per_value.entry(value).or_default().push(count);
+            }
+        }
+
+        let resolved_counts = counts_per_value
+            .into_iter()
+            .map(|(value, mut counts)| {
+                counts.resize(responses_count, 0);
+                let count = match condition {
+                    ResolveCondition::All => counts.iter().copied().min().unwrap_or_default(),
+                    ResolveCondition::Majority => {
+                        counts.sort_unstable();
+                        counts.get(counts.len() / 2).copied().unwrap_or_default()
+                    }
+                };
+                (value, count)
+            })
+            .collect();
+
+        FacetResponse::top_hits(resolved_counts, usize::MAX)
+    }
+}
+
 impl Resolve for Vec<Record> {
     fn resolve(records: Vec<Self>, condition: ResolveCondition) -> Self {
         let mut resolved = Resolver::resolve(records, |record| record.id, record_eq, condition);
@@ -524,6 +557,40 @@ mod test {
         test_resolve_simple(input_4(), expected_4_majority(), ResolveCondition::Majority);
     }
 
+    fn facet_response(hits: &[(&str, usize)]) -> FacetResponse {
+        let counts = hits
+            .iter()
+            .map(|&(value, count)| (FacetValue::Keyword(value.to_string()), count))
+            .collect();
+        FacetResponse::top_hits(counts, usize::MAX)
+    }
+
+    fn facet_input() -> Vec<FacetResponse> {
+        vec![
+            facet_response(&[("a", 5), ("b", 3), ("c", 1)]),
+            facet_response(&[("a", 4), ("b", 3), ("d", 2)]),
+            facet_response(&[("a", 5), ("b", 2), ("c", 1)]),
+        ]
+    }
+
+    #[test]
+    fn resolve_facet_all() {
+        test_resolve(
+            facet_input(),
+            facet_response(&[("a", 4), ("b", 2)]),
+            ResolveCondition::All,
+        );
+    }
+
+    #[test]
+    fn resolve_facet_majority() {
+        test_resolve(
+            facet_input(),
+            facet_response(&[("a", 5), ("b", 3), ("c", 1)]),
+            ResolveCondition::Majority,
+        );
+    }
+
     fn test_resolve<T, E>(input: Vec<T>, expected: E, condition: ResolveCondition)
     where
         T: Resolve + Clone + PartialEq<E> + fmt::Debug,
@@ -1,12 +1,18 @@
 use core::marker::{Send, Sync};
 use std::future::{self, Future};
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
 use common::types::TelemetryDetail;
+use tokio::runtime::Handle;
 
 use super::local_shard::clock_map::RecoveryPoint;
 use super::update_tracker::UpdateTracker;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{
+    CollectionError, CollectionResult, RecallEstimationRequest, SegmentRecallEstimation,
+};
+use crate::operations::CollectionUpdateOperations;
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
 use crate::shards::local_shard::LocalShard;
@@ -75,6 +81,49 @@ impl Shard {
         telemetry
     }
 
+    /// Whether the local segments of the shard have points, which are expired at the given time
+    pub fn has_expired_points(&self, now: u64) -> bool {
+        match self {
+            Shard::Local(local_shard) => local_shard.has_expired_points(now),
+            Shard::Proxy(proxy_shard) => proxy_shard.has_expired_points(now),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.has_expired_points(now),
+            Shard::QueueProxy(proxy_shard) => proxy_shard.has_expired_points(now),
+            Shard::Dummy(_) => false,
+        }
+    }
+
+    /// Estimate recall of the approximate search in the local segments of the shard
+    pub async fn estimate_recall(
+        &self,
+        request: Arc<RecallEstimationRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<SegmentRecallEstimation>> {
+        match self {
+            Shard::Local(local_shard) => {
+                local_shard
+                    .estimate_recall(request, search_runtime_handle, timeout)
+                    .await
+            }
+            Shard::Proxy(proxy_shard) => {
+                proxy_shard
+                    .estimate_recall(request, search_runtime_handle, timeout)
+                    .await
+            }
+            Shard::ForwardProxy(proxy_shard) => {
+                proxy_shard
+                    .estimate_recall(request, search_runtime_handle, timeout)
+                    .await
+            }
+            Shard::QueueProxy(proxy_shard) => {
+                proxy_shard
+                    .estimate_recall(request, search_runtime_handle, timeout)
+                    .await
+            }
+            Shard::Dummy(_) => Ok(vec![]),
+        }
+    }
+
     pub async fn create_snapshot(
         &self,
         temp_path: &Path,
@@ -162,6 +211,26 @@ impl Shard {
         }
     }
 
+    pub fn read_wal_operations(
+        &self,
+        from: Option<u64>,
+        limit: usize,
+    ) -> CollectionResult<Vec<(u64, CollectionUpdateOperations)>> {
+        match self {
+            Self::Local(local_shard) => local_shard.read_wal_operations(from, limit),
+            Self::Proxy(proxy_shard) => proxy_shard.read_wal_operations(from, limit),
+            Self::ForwardProxy(proxy_shard) => {
+                proxy_shard.wrapped_shard.read_wal_operations(from, limit)
+            }
+            Self::QueueProxy(proxy_shard) => proxy_shard.read_wal_operations(from, limit),
+
+            Self::Dummy(_) => Err(CollectionError::service_error(format!(
+                "Reading WAL operations not supported on {}",
+                self.variant_name(),
+            ))),
+        }
+    }
+
     pub async fn update_cutoff(&self, cutoff: &RecoveryPoint) -> CollectionResult<()> {
         match self {
             Self::Local(local_shard) => local_shard.update_cutoff(cutoff).await,
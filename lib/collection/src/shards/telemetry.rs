@@ -7,7 +7,7 @@ use segment::telemetry::SegmentTelemetry;
 use serde::Serialize;
 
 use crate::collection_manager::optimizers::TrackerTelemetry;
-use crate::operations::types::OptimizersStatus;
+use crate::operations::types::{OptimizersStatus, SegmentRecallEstimation};
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
 
@@ -32,6 +32,9 @@ pub struct LocalShardTelemetry {
     pub variant_name: Option<String>,
     pub segments: Vec<SegmentTelemetry>,
     pub optimizations: OptimizerTelemetry,
+    /// Latest recall estimations of the approximate search in segments of the shard
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recall: Vec<SegmentRecallEstimation>,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Default)]
@@ -57,6 +60,7 @@ impl Anonymize for LocalShardTelemetry {
             variant_name: self.variant_name.clone(),
             segments: self.segments.anonymize(),
             optimizations: self.optimizations.anonymize(),
+            recall: self.recall.anonymize(),
         }
     }
 }
@@ -3,7 +3,7 @@ use segment::common::anonymize::Anonymize;
 use serde::Serialize;
 
 use crate::config::CollectionConfig;
-use crate::operations::types::ShardTransferInfo;
+use crate::operations::types::{SegmentRecallEstimation, ShardTransferInfo};
 use crate::shards::telemetry::ReplicaSetTelemetry;
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -24,6 +24,14 @@ impl CollectionTelemetry {
             .map(|s| s.info.num_vectors)
             .sum()
     }
+
+    /// Latest recall estimations of the approximate search in all local shards
+    pub fn recall_estimations(&self) -> impl Iterator<Item = &SegmentRecallEstimation> {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.local.as_ref())
+            .flat_map(|x| x.recall.iter())
+    }
 }
 
 impl Anonymize for CollectionTelemetry {
@@ -46,6 +54,7 @@ impl Anonymize for CollectionConfig {
             optimizer_config: self.optimizer_config.clone(),
             wal_config: self.wal_config.clone(),
             quantization_config: self.quantization_config.clone(),
+            strict_mode_config: self.strict_mode_config.clone(),
         }
     }
 }
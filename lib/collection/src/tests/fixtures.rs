@@ -41,6 +41,7 @@ pub fn create_collection_config() -> CollectionConfig {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
     }
 }
 
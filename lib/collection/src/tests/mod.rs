@@ -16,7 +16,7 @@ use parking_lot::{Mutex, RwLock};
 use rand::Rng;
 use segment::data_types::vectors::only_default_vector;
 use segment::index::hnsw_index::num_rayon_threads;
-use segment::types::{Distance, PointIdType};
+use segment::types::{Distance, HnswConfig, PointIdType};
 use tempfile::Builder;
 use tokio::time::{sleep, Instant};
 
@@ -240,6 +240,7 @@ async fn test_new_segment_when_all_over_capacity() {
         &segments,
         dir.path(),
         &collection_params,
+        &HnswConfig::default(),
         &optimizer_thresholds,
     )
     .unwrap();
@@ -250,6 +251,7 @@ async fn test_new_segment_when_all_over_capacity() {
         &segments,
         dir.path(),
         &collection_params,
+        &HnswConfig::default(),
         &optimizer_thresholds,
     )
     .unwrap();
@@ -287,6 +289,7 @@ async fn test_new_segment_when_all_over_capacity() {
         &segments,
         dir.path(),
         &collection_params,
+        &HnswConfig::default(),
         &optimizer_thresholds,
     )
     .unwrap();
@@ -1,17 +1,26 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use common::cpu::CpuBudget;
-use segment::data_types::vectors::{NamedVectorStruct, Vector, DEFAULT_VECTOR_NAME};
-use segment::types::{WithPayloadInterface, WithVector};
+use segment::common::reciprocal_rank_fusion::DEFAULT_RRF_K;
+use segment::data_types::text_query::TextQuery;
+use segment::data_types::vectors::{NamedVectorStruct, Vector, VectorStruct, DEFAULT_VECTOR_NAME};
+use segment::index::rescore_formula::parsed_formula::{ParsedExpression, ParsedFormula};
+use segment::types::{
+    Condition, Filter, GeoPoint, HasIdCondition, PayloadFieldSchema, PayloadSchemaType,
+    WithPayloadInterface, WithVector,
+};
 use tempfile::Builder;
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
 
+use crate::operations::point_ops::PointStruct;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::CollectionError;
 use crate::operations::universal_query::shard_query::{
-    Fusion, ScoringQuery, ShardPrefetch, ShardQueryRequest,
+    Fusion, Sample, ScoringQuery, ShardPrefetch, ShardQueryRequest,
 };
+use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::tests::fixtures::*;
@@ -46,7 +55,7 @@ async fn test_shard_query_rrf_rescoring() {
     // RRF query without prefetches
     let query = ShardQueryRequest {
         prefetches: vec![],
-        query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+        query: Some(ScoringQuery::Fusion(Fusion::Rrf { k: DEFAULT_RRF_K })),
         filter: None,
         score_threshold: None,
         limit: 0,
@@ -56,7 +65,7 @@ async fn test_shard_query_rrf_rescoring() {
         with_payload: WithPayloadInterface::Bool(false),
     };
 
-    let sources_scores = shard.query(Arc::new(query), &current_runtime).await;
+    let sources_scores = shard.query(Arc::new(query), &current_runtime, None).await;
     let expected_error =
         CollectionError::bad_request("cannot apply Fusion without prefetches".to_string());
     assert!(matches!(sources_scores, Err(err) if err == expected_error));
@@ -78,7 +87,7 @@ async fn test_shard_query_rrf_rescoring() {
     let outer_limit = 2;
     let query = ShardQueryRequest {
         prefetches: vec![nearest_query_prefetch.clone()],
-        query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+        query: Some(ScoringQuery::Fusion(Fusion::Rrf { k: DEFAULT_RRF_K })),
         filter: None,
         score_threshold: None,
         limit: outer_limit,
@@ -89,7 +98,7 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -122,7 +131,7 @@ async fn test_shard_query_rrf_rescoring() {
             nearest_query_prefetch.clone(),
             nearest_query_prefetch.clone(),
         ],
-        query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+        query: Some(ScoringQuery::Fusion(Fusion::Rrf { k: DEFAULT_RRF_K })),
         filter: None,
         score_threshold: None,
         limit: outer_limit,
@@ -133,7 +142,7 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -146,6 +155,38 @@ async fn test_shard_query_rrf_rescoring() {
     for source in sources_scores.iter() {
         assert_eq!(source.len(), inner_limit);
     }
+
+    // RRF query with a scroll and a search prefetch
+    let scroll_limit = 5;
+    let scroll_prefetch = ShardPrefetch {
+        prefetches: vec![],
+        query: None,
+        limit: scroll_limit,
+        params: None,
+        filter: None,
+        score_threshold: None,
+    };
+    let query = ShardQueryRequest {
+        prefetches: vec![scroll_prefetch, nearest_query_prefetch],
+        query: Some(ScoringQuery::Fusion(Fusion::Rrf { k: DEFAULT_RRF_K })),
+        filter: None,
+        score_threshold: None,
+        limit: outer_limit,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+
+    let sources_scores = shard
+        .query(Arc::new(query), &current_runtime, None)
+        .await
+        .unwrap();
+
+    // results are in the same order as the prefetches
+    assert_eq!(sources_scores.len(), 2);
+    assert_eq!(sources_scores[0].len(), scroll_limit);
+    assert_eq!(sources_scores[1].len(), inner_limit);
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -205,7 +246,7 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -229,7 +270,7 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -256,7 +297,7 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -314,7 +355,7 @@ async fn test_shard_query_payload_vector() {
     };
 
     let sources_scores = shard
-        .query(Arc::new(query), &current_runtime)
+        .query(Arc::new(query), &current_runtime, None)
         .await
         .unwrap();
 
@@ -328,3 +369,342 @@ async fn test_shard_query_payload_vector() {
         assert!(scored_point.payload.is_some());
     });
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shard_query_formula_rescoring() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let upsert_ops = upsert_operation();
+
+    shard.update(upsert_ops.into(), true).await.unwrap();
+
+    let nearest_query = QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+        Vector::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+        DEFAULT_VECTOR_NAME,
+    ));
+    let nearest_query_prefetch = ShardPrefetch {
+        prefetches: vec![], // no recursion here
+        query: Some(ScoringQuery::Vector(nearest_query)),
+        limit: 5,
+        params: None,
+        filter: None,
+        score_threshold: None,
+    };
+
+    // Closest to the location of point 5 first, but point 1 is boosted by a condition
+    let formula = ParsedFormula {
+        conditions: vec![Condition::HasId(HasIdCondition::from(HashSet::from([
+            1.into()
+        ])))],
+        defaults: HashMap::new(),
+        formula: ParsedExpression::Sum(vec![
+            ParsedExpression::Neg(Box::new(ParsedExpression::GeoDistance {
+                origin: GeoPoint {
+                    lon: 32.12,
+                    lat: 14.12,
+                },
+                key: "location".parse().unwrap(),
+            })),
+            ParsedExpression::Mult(vec![
+                ParsedExpression::Condition(0),
+                ParsedExpression::Constant(1e8),
+            ]),
+        ]),
+    };
+
+    let outer_limit = 3;
+    let query = ShardQueryRequest {
+        prefetches: vec![nearest_query_prefetch],
+        query: Some(ScoringQuery::Formula(formula)),
+        filter: None,
+        score_threshold: None,
+        limit: outer_limit,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+
+    let sources_scores = shard
+        .query(Arc::new(query), &current_runtime, None)
+        .await
+        .unwrap();
+
+    assert_eq!(sources_scores.len(), 1);
+    let ids: Vec<_> = sources_scores[0].iter().map(|point| point.id).collect();
+    assert_eq!(ids, vec![1.into(), 5.into(), 4.into()]);
+
+    // Point 5 is at the origin
+    assert_eq!(sources_scores[0][1].score, 0.0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shard_query_text() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let descriptions = [
+        "A quick brown fox",
+        "The fox and the hound",
+        "Fox, fox, fox! Where is the fox?",
+        "A lazy dog sleeps all day long",
+        "Nothing to see here",
+    ];
+    let points = descriptions
+        .iter()
+        .enumerate()
+        .map(|(i, description)| PointStruct {
+            id: (i as u64 + 1).into(),
+            vector: VectorStruct::from(vec![i as f32 + 1.0, 2.0, 3.0, 4.0]).into(),
+            payload: Some(
+                serde_json::from_value(serde_json::json!({ "description": description })).unwrap(),
+            ),
+        })
+        .collect::<Vec<_>>();
+    let upsert_ops = CollectionUpdateOperations::PointOperation(points.into());
+    shard.update(upsert_ops.into(), true).await.unwrap();
+
+    let create_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: "description".parse().unwrap(),
+            field_schema: Some(PayloadFieldSchema::FieldType(PayloadSchemaType::Text)),
+        }),
+    );
+    shard.update(create_index.into(), true).await.unwrap();
+
+    let text_query = |text: &str| {
+        ScoringQuery::Text(TextQuery {
+            key: "description".parse().unwrap(),
+            text: text.to_string(),
+        })
+    };
+
+    // Points without the terms are not returned, repeated terms rank higher
+    let query = ShardQueryRequest {
+        prefetches: vec![],
+        query: Some(text_query("fox")),
+        filter: None,
+        score_threshold: None,
+        limit: 10,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+    let sources_scores = shard
+        .query(Arc::new(query), &current_runtime, None)
+        .await
+        .unwrap();
+    assert_eq!(sources_scores.len(), 1);
+    let ids: Vec<_> = sources_scores[0].iter().map(|point| point.id).collect();
+    assert_eq!(ids.len(), 3);
+    assert_eq!(ids[0], 3.into());
+    assert!(sources_scores[0].iter().all(|point| point.score > 0.0));
+
+    // Rescore the results of a nearest search by relevance to the text
+    let nearest_query_prefetch = ShardPrefetch {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+            NamedVectorStruct::new_from_vector(
+                Vector::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+                DEFAULT_VECTOR_NAME,
+            ),
+        ))),
+        limit: 2,
+        params: None,
+        filter: Some(Filter::new_must_not(Condition::HasId(
+            HasIdCondition::from(HashSet::from([3.into()])),
+        ))),
+        score_threshold: None,
+    };
+    let query = ShardQueryRequest {
+        prefetches: vec![nearest_query_prefetch],
+        query: Some(text_query("lazy fox")),
+        filter: None,
+        score_threshold: None,
+        limit: 10,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+    let sources_scores = shard
+        .query(Arc::new(query), &current_runtime, None)
+        .await
+        .unwrap();
+    // Prefetch finds points 5 and 4, but only point 4 is relevant to the text.
+    // Point 3 is excluded by the prefetch filter.
+    let ids: Vec<_> = sources_scores[0].iter().map(|point| point.id).collect();
+    assert_eq!(ids, vec![4.into()]);
+
+    // Text queries require a full-text index
+    let query = ShardQueryRequest {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Text(TextQuery {
+            key: "location".parse().unwrap(),
+            text: "fox".to_string(),
+        })),
+        filter: None,
+        score_threshold: None,
+        limit: 10,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+    let result = shard.query(Arc::new(query), &current_runtime, None).await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shard_query_sample() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let points = (1..=100)
+        .map(|i: u64| PointStruct {
+            id: i.into(),
+            vector: VectorStruct::from(vec![i as f32, 2.0, 3.0, 4.0]).into(),
+            payload: Some(serde_json::from_value(serde_json::json!({ "parity": i % 2 })).unwrap()),
+        })
+        .collect::<Vec<_>>();
+    let upsert_ops = CollectionUpdateOperations::PointOperation(points.into());
+    shard.update(upsert_ops.into(), true).await.unwrap();
+
+    let parity_filter =
+        Filter::new_must(Condition::Field(segment::types::FieldCondition::new_match(
+            "parity".parse().unwrap(),
+            segment::types::Match::new_value(segment::types::ValueVariants::Integer(0)),
+        )));
+
+    // Sampled points are distinct and satisfy the filter
+    let query = ShardQueryRequest {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Sample(Sample::Random)),
+        filter: Some(parity_filter.clone()),
+        score_threshold: None,
+        limit: 10,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(true),
+    };
+    let sources_scores = shard
+        .query(Arc::new(query), &current_runtime, None)
+        .await
+        .unwrap();
+    assert_eq!(sources_scores.len(), 1);
+    let sample = &sources_scores[0];
+    assert_eq!(sample.len(), 10);
+    let ids: HashSet<_> = sample.iter().map(|point| point.id).collect();
+    assert_eq!(ids.len(), 10);
+    assert!(sample
+        .iter()
+        .all(|point| { point.payload.as_ref().unwrap().0["parity"] == serde_json::json!(0) }));
+    // Sampling keys are ordered like scores
+    assert!(sample.windows(2).all(|pair| pair[0].score >= pair[1].score));
+
+    // Sample among the results of a prefetch, asking for more points than there are
+    let nearest_query_prefetch = ShardPrefetch {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+            NamedVectorStruct::new_from_vector(
+                Vector::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+                DEFAULT_VECTOR_NAME,
+            ),
+        ))),
+        limit: 5,
+        params: None,
+        filter: None,
+        score_threshold: None,
+    };
+    let query = ShardQueryRequest {
+        prefetches: vec![nearest_query_prefetch.clone()],
+        query: Some(ScoringQuery::Sample(Sample::Random)),
+        filter: None,
+        score_threshold: None,
+        limit: 10,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+    let sources_scores = shard
+        .query(Arc::new(query), &current_runtime, None)
+        .await
+        .unwrap();
+    let sampled: HashSet<_> = sources_scores[0].iter().map(|point| point.id).collect();
+
+    let query = ShardQueryRequest {
+        prefetches: vec![],
+        query: nearest_query_prefetch.query,
+        filter: None,
+        score_threshold: None,
+        limit: 5,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+    let sources_scores = shard
+        .query(Arc::new(query), &current_runtime, None)
+        .await
+        .unwrap();
+    let prefetched: HashSet<_> = sources_scores[0].iter().map(|point| point.id).collect();
+    assert_eq!(sampled, prefetched);
+}
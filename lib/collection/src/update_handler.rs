@@ -11,7 +11,7 @@ use log::{debug, error, info, trace, warn};
 use parking_lot::Mutex;
 use segment::common::operation_error::OperationResult;
 use segment::index::hnsw_index::num_rayon_threads;
-use segment::types::SeqNumberType;
+use segment::types::{HnswConfig, SeqNumberType};
 use tokio::runtime::Handle;
 use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::sync::{oneshot, Mutex as TokioMutex};
@@ -117,6 +117,9 @@ pub struct UpdateHandler {
     shard_path: PathBuf,
     /// Whether we have ever triggered optimizers since starting.
     has_triggered_optimizers: Arc<AtomicBool>,
+    /// Set when workers are being stopped, cancels linking of points into HNSW graphs.
+    /// Replaced on every start of the workers.
+    update_worker_stopped: Arc<AtomicBool>,
 }
 
 impl UpdateHandler {
@@ -153,10 +156,12 @@ impl UpdateHandler {
             clocks,
             shard_path,
             has_triggered_optimizers: Default::default(),
+            update_worker_stopped: Default::default(),
         }
     }
 
     pub fn run_workers(&mut self, update_receiver: Receiver<UpdateSignal>) {
+        self.update_worker_stopped = Default::default();
         let (tx, rx) = mpsc::channel(self.shared_storage_config.update_queue_size);
         self.optimizer_worker = Some(self.runtime_handle.spawn(Self::optimization_worker_fn(
             self.optimizers.clone(),
@@ -169,12 +174,14 @@ impl UpdateHandler {
             self.optimizer_cpu_budget.clone(),
             self.max_optimization_threads,
             self.has_triggered_optimizers.clone(),
+            self.update_worker_stopped.clone(),
         )));
         self.update_worker = Some(self.runtime_handle.spawn(Self::update_worker_fn(
             update_receiver,
             tx,
             self.wal.clone(),
             self.segments.clone(),
+            self.update_worker_stopped.clone(),
         )));
         let (flush_tx, flush_rx) = oneshot::channel();
         self.flush_worker = Some(self.runtime_handle.spawn(Self::flush_worker(
@@ -200,6 +207,8 @@ impl UpdateHandler {
     /// Gracefully wait before all optimizations stop
     /// If some optimization is in progress - it will be finished before shutdown.
     pub async fn wait_workers_stops(&mut self) -> CollectionResult<()> {
+        // Don't wait for pending points to be linked, they are linked on the next update or load
+        self.update_worker_stopped.store(true, Ordering::Relaxed);
         let maybe_handle = self.update_worker.take();
         if let Some(handle) = maybe_handle {
             handle.await?;
@@ -229,7 +238,11 @@ impl UpdateHandler {
 
     /// Checks if there are any failed operations.
     /// If so - attempts to re-apply all failed operations.
-    async fn try_recover(segments: LockedSegmentHolder, wal: LockedWal) -> CollectionResult<usize> {
+    async fn try_recover(
+        segments: LockedSegmentHolder,
+        wal: LockedWal,
+        stopped: &AtomicBool,
+    ) -> CollectionResult<usize> {
         // Try to re-apply everything starting from the first failed operation
         let first_failed_operation_option = segments.read().failed_operation.iter().cloned().min();
         match first_failed_operation_option {
@@ -237,7 +250,7 @@ impl UpdateHandler {
             Some(first_failed_op) => {
                 let wal_lock = wal.lock();
                 for (op_num, operation) in wal_lock.read(first_failed_op) {
-                    CollectionUpdater::update(&segments, op_num, operation.operation)?;
+                    CollectionUpdater::update(&segments, op_num, operation.operation, stopped)?;
                 }
             }
         };
@@ -390,6 +403,7 @@ impl UpdateHandler {
         segments: &LockedSegmentHolder,
         segments_path: &Path,
         collection_params: &CollectionParams,
+        collection_hnsw: &HnswConfig,
         thresholds_config: &OptimizerThresholds,
     ) -> OperationResult<()> {
         let no_segment_with_capacity = {
@@ -414,9 +428,11 @@ impl UpdateHandler {
 
         if no_segment_with_capacity {
             log::debug!("Creating new appendable segment, all existing segments are over capacity");
-            segments
-                .write()
-                .create_appendable_segment(segments_path, collection_params)?;
+            segments.write().create_appendable_segment(
+                segments_path,
+                collection_params,
+                collection_hnsw,
+            )?;
         }
 
         Ok(())
@@ -505,6 +521,7 @@ impl UpdateHandler {
         optimizer_cpu_budget: CpuBudget,
         max_handles: Option<usize>,
         has_triggered_optimizers: Arc<AtomicBool>,
+        update_worker_stopped: Arc<AtomicBool>,
     ) {
         let max_handles = max_handles.unwrap_or(usize::MAX);
         let max_indexing_threads = optimizers
@@ -538,6 +555,7 @@ impl UpdateHandler {
                             &segments,
                             optimizer.segments_path(),
                             &optimizer.collection_params(),
+                            optimizer.hnsw_config(),
                             optimizer.threshold_config(),
                         );
                         if let Err(err) = result {
@@ -553,7 +571,7 @@ impl UpdateHandler {
                         continue;
                     }
 
-                    if Self::try_recover(segments.clone(), wal.clone())
+                    if Self::try_recover(segments.clone(), wal.clone(), &update_worker_stopped)
                         .await
                         .is_err()
                     {
@@ -611,6 +629,7 @@ impl UpdateHandler {
         optimize_sender: Sender<OptimizerSignal>,
         wal: LockedWal,
         segments: LockedSegmentHolder,
+        stopped: Arc<AtomicBool>,
     ) {
         while let Some(signal) = receiver.recv().await {
             match signal {
@@ -631,8 +650,9 @@ impl UpdateHandler {
                         Ok(())
                     };
 
-                    let operation_result = flush_res
-                        .and_then(|_| CollectionUpdater::update(&segments, op_num, operation));
+                    let operation_result = flush_res.and_then(|_| {
+                        CollectionUpdater::update(&segments, op_num, operation, &stopped)
+                    });
 
                     let res = match operation_result {
                         Ok(update_res) => optimize_sender
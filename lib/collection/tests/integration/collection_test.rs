@@ -1,28 +1,41 @@
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
+use std::num::NonZeroU32;
+use std::time::Duration;
 
+use api::grpc::qdrant::update_event;
 use api::rest::OrderByInterface;
+use collection::config::{CollectionConfig, CollectionParams, StrictModeConfig, WalConfig};
+use collection::operations::conversions::update_event_from_wal_operation;
 use collection::operations::payload_ops::{PayloadOps, SetPayloadOp};
-use collection::operations::point_ops::{Batch, PointOperations, PointStruct, WriteOrdering};
+use collection::operations::point_ops::{
+    Batch, ConditionalInsertOperationInternal, PointOperations, PointStruct, WriteOrdering,
+};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CountRequestInternal, PointRequestInternal, RecommendRequestInternal, ScrollRequestInternal,
-    SearchRequestInternal, UpdateStatus,
+    CollectionError, CollectionSearchMatrixRequest, CountRequestInternal, PointRequestInternal,
+    RecommendRequestInternal, ScrollRequestInternal, SearchMatrixOffsetsResponse,
+    SearchMatrixPairsResponse, SearchRequestInternal, UpdateStatus,
 };
-use collection::operations::CollectionUpdateOperations;
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::{expiration, CollectionUpdateOperations};
 use collection::recommendations::recommend_by;
 use collection::shards::replica_set::{ReplicaSetState, ReplicaState};
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
-use segment::data_types::vectors::{BatchVectorStruct, VectorStruct};
+use segment::data_types::order_by::{Direction, OrderBy, StartFrom};
+use segment::data_types::vectors::{BatchVectorStruct, VectorStruct, DEFAULT_VECTOR_NAME};
 use segment::types::{
-    Condition, ExtendedPointId, FieldCondition, Filter, HasIdCondition, Payload,
-    PayloadFieldSchema, PayloadSchemaType, PointIdType, WithPayloadInterface,
+    Condition, Distance, ExtendedPointId, FieldCondition, Filter, GeoPoint, HasIdCondition,
+    Payload, PayloadFieldSchema, PayloadSchemaType, PointIdType, SearchParams,
+    WithPayloadInterface,
 };
 use serde_json::Map;
 use tempfile::Builder;
 
-use crate::common::{load_local_collection, simple_collection_fixture, N_SHARDS};
+use crate::common::{
+    load_local_collection, new_local_collection, simple_collection_fixture, N_SHARDS,
+    TEST_OPTIMIZERS_CONFIG,
+};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_updater() {
@@ -548,6 +561,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        geo_point: None,
                     })),
                 },
                 None,
@@ -578,6 +592,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        geo_point: None,
                     })),
                 },
                 None,
@@ -617,6 +632,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        geo_point: None,
                     })),
                 },
                 None,
@@ -655,6 +671,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        geo_point: None,
                     })),
                 },
                 None,
@@ -715,6 +732,104 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
         .all(|&x| x == 2));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ordered_by_geo_distance() {
+    test_ordered_by_geo_distance_with_shards(1).await;
+    test_ordered_by_geo_distance_with_shards(N_SHARDS).await;
+}
+
+async fn test_ordered_by_geo_distance_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    const LOCATION_KEY: &str = "location";
+
+    // Points are further away from the origin with every id, point 4 has two locations
+    let payloads: Vec<Option<Payload>> = serde_json::from_str(
+        r#"[
+            { "location": { "lon": 0.0, "lat": 0.1 } },
+            { "location": { "lon": 0.0, "lat": 0.2 } },
+            { "location": { "lon": 0.0, "lat": -0.3 } },
+            { "location": { "lon": 0.4, "lat": 0.0 } },
+            { "location": [{ "lon": 10.0, "lat": 0.0 }, { "lon": -0.5, "lat": 0.0 }] },
+            null
+        ]"#,
+    )
+    .unwrap();
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        Batch {
+            ids: (0..6).map(|x| x.into()).collect_vec(),
+            vectors: BatchVectorStruct::from(vec![vec![1.0, 0.0, 0.0, 0.0]; 6]).into(),
+            payloads: Some(payloads),
+        }
+        .into(),
+    ));
+
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let scroll_request = |direction, start_from| ScrollRequestInternal {
+        offset: None,
+        limit: Some(3),
+        filter: None,
+        with_payload: Some(WithPayloadInterface::Bool(false)),
+        with_vector: false.into(),
+        order_by: Some(OrderByInterface::Struct(OrderBy {
+            key: LOCATION_KEY.parse().unwrap(),
+            direction: Some(direction),
+            start_from,
+            geo_point: Some(GeoPoint { lon: 0.0, lat: 0.0 }),
+        })),
+    };
+
+    // Ordering by geo distance requires a geo index
+    let result = collection
+        .scroll_by(
+            scroll_request(Direction::Asc, None),
+            None,
+            &ShardSelectorInternal::All,
+        )
+        .await;
+    assert!(matches!(result, Err(CollectionError::BadRequest { .. })));
+
+    collection
+        .create_payload_index_with_wait(
+            LOCATION_KEY.parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Geo),
+            true,
+        )
+        .await
+        .unwrap();
+
+    let scroll_ids = |request| async {
+        collection
+            .scroll_by(request, None, &ShardSelectorInternal::All)
+            .await
+            .unwrap()
+            .points
+            .into_iter()
+            .map(|point| point.id)
+            .collect_vec()
+    };
+
+    let nearest = scroll_ids(scroll_request(Direction::Asc, None)).await;
+    assert_eq!(nearest, vec![0.into(), 1.into(), 2.into()]);
+
+    let farthest = scroll_ids(scroll_request(Direction::Desc, None)).await;
+    assert_eq!(farthest, vec![4.into(), 3.into(), 2.into()]);
+
+    // Distance between (0, 0) and (0, 0.25) is about 27.8 km
+    let next_page = scroll_ids(scroll_request(
+        Direction::Asc,
+        Some(StartFrom::Float(25_000.0)),
+    ))
+    .await;
+    assert_eq!(next_page, vec![2.into(), 3.into(), 4.into()]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_delete_points_by_filter() {
     test_collection_delete_points_by_filter_with_shards(1).await;
@@ -799,6 +914,389 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
     assert_eq!(result.points.get(2).unwrap().id, 4.into());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_conditional_upsert_counts() {
+    test_collection_conditional_upsert_counts_with_shards(1).await;
+    test_collection_conditional_upsert_counts_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_conditional_upsert_counts_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let batch = |ids: Vec<u64>| Batch {
+        vectors: BatchVectorStruct::from(vec![vec![1.0, 0.0, 1.0, 1.0]; ids.len()]).into(),
+        ids: ids.into_iter().map(|x| x.into()).collect_vec(),
+        payloads: None,
+    };
+
+    let insert_points = CollectionUpdateOperations::PointOperation(batch(vec![0, 1, 2, 3]).into());
+    let insert_result = collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+    assert_eq!(insert_result.status, UpdateStatus::Completed);
+    // Counts are only reported for conditional upserts
+    assert_eq!(insert_result.updated_count, None);
+    assert_eq!(insert_result.skipped_count, None);
+
+    // Only points 0 and 1 can be updated, point 4 is new
+    let condition = Filter::new_must(Condition::HasId(HasIdCondition::from(
+        [0.into(), 1.into()]
+            .into_iter()
+            .collect::<HashSet<PointIdType>>(),
+    )));
+    let conditional_upsert =
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsConditional(
+            ConditionalInsertOperationInternal::new(batch(vec![0, 1, 2, 3, 4]).into(), condition),
+        ));
+    let upsert_result = collection
+        .update_from_client_simple(conditional_upsert, true, WriteOrdering::default())
+        .await
+        .unwrap();
+    assert_eq!(upsert_result.status, UpdateStatus::Completed);
+    assert_eq!(upsert_result.updated_count, Some(3));
+    assert_eq!(upsert_result.skipped_count, Some(2));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_expired_points_hidden() {
+    test_collection_expired_points_hidden_with_shards(1).await;
+    test_collection_expired_points_hidden_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_expired_points_hidden_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection_config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorParamsBuilder::new(4, Distance::Dot).build().into(),
+            shard_number: NonZeroU32::new(shard_number).unwrap(),
+            ttl_sec: Some(3600),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+    };
+
+    let collection = new_local_collection(
+        "test".to_string(),
+        collection_dir.path(),
+        &collection_dir.path().join("snapshots"),
+        &collection_config,
+    )
+    .await
+    .unwrap();
+
+    // Points 0 and 3 have explicit expiration time in the past
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: BatchVectorStruct::from(vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+                vec![1.0, 1.0, 0.0, 1.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+            ])
+            .into(),
+            payloads: serde_json::from_str(
+                r#"[{ "_expires_at": 1 }, null, { "k": "v" }, { "_expires_at": 1 }, null]"#,
+            )
+            .unwrap(),
+        }
+        .into(),
+    );
+
+    let insert_result = collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+    assert_eq!(insert_result.status, UpdateStatus::Completed);
+
+    let scroll_result = collection
+        .scroll_by(
+            ScrollRequestInternal {
+                offset: None,
+                limit: Some(10),
+                filter: None,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: false.into(),
+                order_by: None,
+            },
+            None,
+            &ShardSelectorInternal::All,
+        )
+        .await
+        .unwrap();
+
+    let scrolled_ids = scroll_result
+        .points
+        .iter()
+        .map(|point| point.id)
+        .collect_vec();
+    assert_eq!(scrolled_ids, vec![1.into(), 2.into(), 4.into()]);
+
+    // Expiration time is assigned to points without one, keeping the rest of the payload
+    let expires_at = expiration::now_timestamp() + 3600;
+    for point in &scroll_result.points {
+        let payload = point.payload.as_ref().unwrap();
+        let point_expires_at = payload.0[expiration::EXPIRES_AT_KEY].as_u64().unwrap();
+        assert!(point_expires_at <= expires_at && point_expires_at + 60 > expires_at);
+    }
+    assert_eq!(scroll_result.points[1].payload.as_ref().unwrap().len(), 2);
+
+    let count_result = collection
+        .count(
+            CountRequestInternal {
+                filter: None,
+                exact: true,
+            },
+            None,
+            &ShardSelectorInternal::All,
+        )
+        .await
+        .unwrap();
+    assert_eq!(count_result.count, 3);
+
+    let retrieved = collection
+        .retrieve(
+            PointRequestInternal {
+                ids: vec![0.into(), 1.into(), 3.into()],
+                with_payload: None,
+                with_vector: false.into(),
+            },
+            None,
+            &ShardSelectorInternal::All,
+        )
+        .await
+        .unwrap();
+    assert_eq!(retrieved.len(), 1);
+    assert_eq!(retrieved[0].id, 1.into());
+
+    let search_result = collection
+        .search(
+            SearchRequestInternal {
+                vector: vec![1.0, 0.0, 1.0, 1.0].into(),
+                with_payload: None,
+                with_vector: None,
+                filter: None,
+                params: None,
+                limit: 10,
+                offset: None,
+                score_threshold: None,
+            }
+            .into(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(search_result.len(), 3);
+    assert!(search_result
+        .iter()
+        .all(|point| point.id != 0.into() && point.id != 3.into()));
+
+    // Clearing or overwriting payload would drop the expiration time
+    let clear_payload = CollectionUpdateOperations::PayloadOperation(PayloadOps::ClearPayload {
+        points: vec![1.into()],
+    });
+    let clear_result = collection
+        .update_from_client_simple(clear_payload, true, WriteOrdering::default())
+        .await;
+    assert!(matches!(
+        clear_result,
+        Err(CollectionError::BadRequest { .. })
+    ));
+
+    let overwrite_payload =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::OverwritePayload(SetPayloadOp {
+            payload: serde_json::from_str(r#"{ "k": "v2" }"#).unwrap(),
+            points: Some(vec![1.into()]),
+            filter: None,
+            key: None,
+        }));
+    let overwrite_result = collection
+        .update_from_client_simple(overwrite_payload, true, WriteOrdering::default())
+        .await;
+    assert!(matches!(
+        overwrite_result,
+        Err(CollectionError::BadRequest { .. })
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_expired_points_deleted() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection_config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorParamsBuilder::new(4, Distance::Dot).build().into(),
+            shard_number: NonZeroU32::new(N_SHARDS).unwrap(),
+            ttl_sec: Some(3600),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+    };
+
+    let collection = new_local_collection(
+        "test".to_string(),
+        collection_dir.path(),
+        &collection_dir.path().join("snapshots"),
+        &collection_config,
+    )
+    .await
+    .unwrap();
+    collection.ensure_expiration_index().await.unwrap();
+
+    // Points 0 and 3 have explicit expiration time in the past
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: BatchVectorStruct::from(vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+                vec![1.0, 1.0, 0.0, 1.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+            ])
+            .into(),
+            payloads: serde_json::from_str(
+                r#"[{ "_expires_at": 1 }, null, null, { "_expires_at": 1 }, null]"#,
+            )
+            .unwrap(),
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let info = collection.info(&ShardSelectorInternal::All).await.unwrap();
+    assert_eq!(info.points_count, Some(5));
+    assert!(info
+        .payload_schema
+        .contains_key(&expiration::expires_at_path()));
+
+    let deleted_shards = collection.delete_expired_points(true).await.unwrap();
+    assert!(deleted_shards > 0);
+
+    let info = collection.info(&ShardSelectorInternal::All).await.unwrap();
+    assert_eq!(info.points_count, Some(3));
+
+    // Nothing is left to delete
+    let deleted_shards = collection.delete_expired_points(true).await.unwrap();
+    assert_eq!(deleted_shards, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_read_wal_operations() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0.into(), 1.into()],
+            vectors: BatchVectorStruct::from(vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+            ])
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    let set_payload =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+            payload: serde_json::from_str(r#"{ "k": "v" }"#).unwrap(),
+            points: Some(vec![1.into()]),
+            filter: None,
+            key: None,
+        }));
+    let delete_points = CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+        ids: vec![0.into()],
+    });
+
+    let mut operation_ids = Vec::new();
+    for operation in [insert_points, set_payload, delete_points] {
+        let result = collection
+            .update_from_client_simple(operation, true, WriteOrdering::default())
+            .await
+            .unwrap();
+        operation_ids.push(result.operation_id.unwrap());
+    }
+
+    let operations = collection.read_wal_operations(0, None, 10).await.unwrap();
+    let read_ids = operations.iter().map(|(id, _)| *id).collect_vec();
+    assert_eq!(read_ids, operation_ids);
+    assert!(matches!(
+        operations[1].1,
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(_)),
+    ));
+
+    // Resume after the first operation, with a limit
+    let operations = collection
+        .read_wal_operations(0, Some(operation_ids[0] + 1), 1)
+        .await
+        .unwrap();
+    assert_eq!(operations.len(), 1);
+    assert_eq!(operations[0].0, operation_ids[1]);
+
+    // Nothing new yet
+    let operations = collection
+        .read_wal_operations(0, Some(operation_ids[2] + 1), 10)
+        .await
+        .unwrap();
+    assert!(operations.is_empty());
+
+    let events = collection
+        .read_wal_operations(0, None, 10)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(operation_id, operation)| {
+            update_event_from_wal_operation("test".to_string(), operation_id, operation).unwrap()
+        })
+        .collect_vec();
+    assert!(matches!(
+        events[0].operation,
+        Some(update_event::Operation::Upsert(_)),
+    ));
+    assert!(matches!(
+        events[2].operation,
+        Some(update_event::Operation::DeletePoints(_)),
+    ));
+
+    let err = collection
+        .read_wal_operations(1, None, 10)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CollectionError::NotFound { .. }));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_local_load_initializing_not_stuck() {
     let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
@@ -840,3 +1338,248 @@ async fn test_collection_local_load_initializing_not_stuck() {
         }
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_search_matrix() {
+    test_collection_search_matrix_with_shards(1).await;
+    test_collection_search_matrix_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_search_matrix_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let payloads = (0..10)
+        .map(|i| {
+            let group = if i % 2 == 0 { "even" } else { "odd" };
+            Some(serde_json::from_value::<Payload>(serde_json::json!({ "group": group })).unwrap())
+        })
+        .collect_vec();
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..10).map(|i: u64| i.into()).collect_vec(),
+            vectors: BatchVectorStruct::from(
+                (0..10).map(|i| vec![1.0, i as f32, 0.0, 1.0]).collect_vec(),
+            )
+            .into(),
+            payloads: Some(payloads),
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let even_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        "group".parse().unwrap(),
+        "even".to_string().into(),
+    )));
+
+    let request = CollectionSearchMatrixRequest {
+        sample_size: 100,
+        limit_per_sample: 2,
+        filter: Some(even_filter),
+        using: DEFAULT_VECTOR_NAME.to_string(),
+    };
+
+    let response = collection
+        .search_points_matrix(request, ShardSelectorInternal::All, None, None)
+        .await
+        .unwrap();
+
+    // All matching points are sampled, in order of ids
+    let expected_ids = [0, 2, 4, 6, 8].map(PointIdType::from);
+    assert_eq!(response.sample_ids, expected_ids);
+    assert_eq!(response.nearests.len(), expected_ids.len());
+
+    for (id, nearest) in response.sample_ids.iter().zip(&response.nearests) {
+        assert_eq!(nearest.len(), 2);
+        for scored in nearest {
+            assert_ne!(scored.id, *id);
+            assert!(expected_ids.contains(&scored.id));
+        }
+    }
+
+    let offsets = SearchMatrixOffsetsResponse::from(response);
+    assert_eq!(offsets.ids, expected_ids);
+    assert_eq!(offsets.offsets_row.len(), 10);
+    assert_eq!(offsets.offsets_col.len(), 10);
+    assert_eq!(offsets.scores.len(), 10);
+    assert!(offsets
+        .offsets_row
+        .iter()
+        .zip(&offsets.offsets_col)
+        .all(|(row, col)| row != col));
+
+    // Sample is limited by the requested size
+    let request = CollectionSearchMatrixRequest {
+        sample_size: 4,
+        limit_per_sample: 3,
+        filter: None,
+        using: DEFAULT_VECTOR_NAME.to_string(),
+    };
+
+    let response = collection
+        .search_points_matrix(request, ShardSelectorInternal::All, None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(response.sample_ids.len(), 4);
+    assert!(response.sample_ids.windows(2).all(|w| w[0] < w[1]));
+
+    let pairs = SearchMatrixPairsResponse::from(response);
+    assert_eq!(pairs.pairs.len(), 4 * 3);
+    assert!(pairs.pairs.iter().all(|pair| pair.a != pair.b));
+
+    // Unknown vector name
+    let request = CollectionSearchMatrixRequest {
+        sample_size: 4,
+        limit_per_sample: 3,
+        filter: None,
+        using: "missing".to_string(),
+    };
+
+    let result = collection
+        .search_points_matrix(request, ShardSelectorInternal::All, None, None)
+        .await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_strict_mode() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    collection
+        .update_strict_mode_config(StrictModeConfig {
+            enabled: Some(true),
+            max_query_limit: Some(10),
+            max_timeout: Some(5),
+            unindexed_filtering_retrieve: Some(false),
+            unindexed_filtering_update: Some(false),
+            search_allow_exact: Some(false),
+            upsert_max_batchsize: Some(3),
+            max_points_count: Some(3),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    collection
+        .create_payload_index_with_wait(
+            "indexed".parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+            true,
+        )
+        .await
+        .unwrap();
+
+    let insert_points = |ids: Vec<u64>| {
+        let vectors = ids.iter().map(|_| vec![1.0, 0.0, 1.0, 1.0]).collect_vec();
+        CollectionUpdateOperations::PointOperation(
+            Batch {
+                ids: ids.into_iter().map(|x| x.into()).collect_vec(),
+                vectors: BatchVectorStruct::from(vectors).into(),
+                payloads: None,
+            }
+            .into(),
+        )
+    };
+
+    let too_large_batch = insert_points(vec![0, 1, 2, 3]);
+    let result = collection.check_strict_mode_update(&too_large_batch).await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    let batch = insert_points(vec![0, 1, 2]);
+    collection.check_strict_mode_update(&batch).await.unwrap();
+    collection
+        .update_from_client_simple(batch, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // The collection is full now
+    let result = collection
+        .check_strict_mode_update(&insert_points(vec![3]))
+        .await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    let filter_on = |key: &str| {
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            key.parse().unwrap(),
+            "value".to_string().into(),
+        )))
+    };
+
+    let delete_unindexed = CollectionUpdateOperations::PointOperation(
+        PointOperations::DeletePointsByFilter(filter_on("unindexed")),
+    );
+    let result = collection.check_strict_mode_update(&delete_unindexed).await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    let delete_indexed = CollectionUpdateOperations::PointOperation(
+        PointOperations::DeletePointsByFilter(filter_on("indexed")),
+    );
+    collection
+        .check_strict_mode_update(&delete_indexed)
+        .await
+        .unwrap();
+
+    let search_request = SearchRequestInternal {
+        vector: vec![1.0, 0.0, 1.0, 1.0].into(),
+        with_payload: None,
+        with_vector: None,
+        filter: Some(filter_on("indexed")),
+        params: None,
+        limit: 10,
+        offset: None,
+        score_threshold: None,
+    };
+    collection
+        .check_strict_mode(&search_request, Some(Duration::from_secs(5)))
+        .await
+        .unwrap();
+
+    let result = collection
+        .check_strict_mode(&search_request, Some(Duration::from_secs(10)))
+        .await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    let rejected_requests = [
+        SearchRequestInternal {
+            limit: 11,
+            ..search_request.clone()
+        },
+        SearchRequestInternal {
+            filter: Some(filter_on("unindexed")),
+            ..search_request.clone()
+        },
+        SearchRequestInternal {
+            params: Some(SearchParams {
+                exact: true,
+                ..Default::default()
+            }),
+            ..search_request.clone()
+        },
+    ];
+    for request in &rejected_requests {
+        let result = collection.check_strict_mode(request, None).await;
+        assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+    }
+
+    // Limits are not enforced once strict mode is disabled
+    collection
+        .update_strict_mode_config(StrictModeConfig {
+            enabled: Some(false),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    collection
+        .check_strict_mode_batch(&rejected_requests, None)
+        .await
+        .unwrap();
+}
@@ -86,6 +86,11 @@ impl<T: Ord> FixedLengthPriorityQueue<T> {
     pub fn is_empty(&self) -> bool {
         self.heap.is_empty()
     }
+
+    /// Returns the maximal number of elements, which the queue keeps
+    pub fn capacity(&self) -> usize {
+        self.length.get()
+    }
 }
 
 pub struct Iter<'a, T> {
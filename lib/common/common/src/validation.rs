@@ -90,6 +90,22 @@ where
     Ok(())
 }
 
+/// Validate that all weights are finite and not negative.
+pub fn validate_fusion_weights(weights: &[f32]) -> Result<(), ValidationError> {
+    match weights
+        .iter()
+        .position(|weight| !weight.is_finite() || *weight < 0.0)
+    {
+        Some(idx) => {
+            let mut err = ValidationError::new("finite_non_negative");
+            err.add_param(Cow::from("index"), &idx);
+            err.add_param(Cow::from("value"), &weights[idx].to_string());
+            Err(err)
+        }
+        None => Ok(()),
+    }
+}
+
 /// Validate that shard request has two different peers.
 pub fn validate_shard_different_peers(
     from_peer_id: u64,
@@ -211,6 +227,15 @@ pub fn validate_multi_vector_len(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validate_fusion_weights() {
+        assert!(validate_fusion_weights(&[]).is_ok());
+        assert!(validate_fusion_weights(&[0.0, 0.5, 2.0]).is_ok());
+        assert!(validate_fusion_weights(&[0.5, -0.1]).is_err());
+        assert!(validate_fusion_weights(&[f32::NAN]).is_err());
+        assert!(validate_fusion_weights(&[1.0, f32::INFINITY]).is_err());
+    }
+
     #[test]
     fn test_validate_range_generic() {
         assert!(validate_range_generic(u64::MIN, None, None).is_ok());
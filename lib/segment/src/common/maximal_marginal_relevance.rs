@@ -0,0 +1,228 @@
+//! Maximal Marginal Relevance (MMR) is a method for diversifying a list of results.
+//! Points are picked one by one, balancing their relevance to the query against their similarity
+//! to the points which were already picked.
+//! See https://www.cs.cmu.edu/~jgc/publication/The_Use_MMR_Diversity_Based_LTMIR_1998.pdf
+
+use std::sync::atomic::AtomicBool;
+
+use bitvec::prelude::BitVec;
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::data_types::vectors::{DenseVector, QueryVector, VectorRef};
+use crate::types::{Distance, ScoredPoint};
+use crate::vector_storage::dense::volatile_dense_vector_storage::new_volatile_dense_vector_storage;
+use crate::vector_storage::{raw_scorer_impl, VectorStorage};
+
+/// Default trade-off between relevance and diversity
+pub const DEFAULT_MMR_DIVERSITY: f32 = 0.5;
+
+/// Select up to `limit` points out of `candidates` with Maximal Marginal Relevance.
+///
+/// Each candidate comes with its vector, as it is stored in the collection, so already
+/// preprocessed. The query is preprocessed in the same way, so that relevance and similarity are
+/// on the same scale. Both are measured with the given `distance`, by using the raw scorer over
+/// a temporary in-memory storage of the candidate vectors.
+///
+/// `diversity` is in `[0, 1]`: 0 only considers relevance, 1 only considers diversity.
+///
+/// The output keeps the original scores of the points, in the order they were selected.
+pub fn maximal_marginal_relevance(
+    query: DenseVector,
+    candidates: Vec<(ScoredPoint, DenseVector)>,
+    distance: Distance,
+    diversity: f32,
+    limit: usize,
+    is_stopped: &AtomicBool,
+) -> OperationResult<Vec<ScoredPoint>> {
+    if candidates.is_empty() || limit == 0 {
+        return Ok(Vec::new());
+    }
+
+    let query = distance.preprocess_vector(query);
+    let dim = query.len();
+    let mut vector_storage = new_volatile_dense_vector_storage(dim, distance);
+
+    let mut points = Vec::with_capacity(candidates.len());
+    for (offset, (point, vector)) in candidates.into_iter().enumerate() {
+        if vector.len() != dim {
+            return Err(OperationError::WrongVectorDimension {
+                expected_dim: dim,
+                received_dim: vector.len(),
+            });
+        }
+        // Do not perform preprocessing - vectors should be already processed
+        vector_storage.insert_vector(offset as PointOffsetType, VectorRef::from(&vector))?;
+        points.push(Some(point));
+    }
+
+    let point_deleted = BitVec::repeat(false, points.len());
+    let raw_scorer = raw_scorer_impl(
+        QueryVector::Nearest(query.into()),
+        &vector_storage,
+        &point_deleted,
+        is_stopped,
+    )?;
+
+    let relevance_weight = 1.0 - diversity;
+    let relevance: Vec<ScoreType> = (0..points.len() as PointOffsetType)
+        .map(|offset| raw_scorer.score_point(offset))
+        .collect();
+
+    // Highest similarity of each candidate to any of the selected points
+    let mut max_similarity = vec![ScoreType::NEG_INFINITY; points.len()];
+    let mut remaining: Vec<PointOffsetType> = (0..points.len() as PointOffsetType).collect();
+    let mut selected = Vec::with_capacity(limit.min(points.len()));
+
+    while selected.len() < limit && !remaining.is_empty() {
+        check_process_stopped(is_stopped)?;
+
+        let mmr_score = |offset: PointOffsetType| {
+            let offset = offset as usize;
+            let similarity_penalty = if selected.is_empty() {
+                0.0
+            } else {
+                max_similarity[offset]
+            };
+            relevance_weight * relevance[offset] - diversity * similarity_penalty
+        };
+
+        // On ties, prefer the candidate which came first
+        let (best_position, _) = remaining
+            .iter()
+            .enumerate()
+            .map(|(position, &offset)| (position, mmr_score(offset)))
+            .reduce(|best, current| if current.1 > best.1 { current } else { best })
+            .expect("remaining candidates are not empty");
+
+        let best_offset = remaining.remove(best_position);
+        for &offset in &remaining {
+            let similarity = raw_scorer.score_internal(best_offset, offset);
+            let max = &mut max_similarity[offset as usize];
+            *max = max.max(similarity);
+        }
+
+        selected.push(best_offset);
+    }
+
+    Ok(selected
+        .into_iter()
+        .filter_map(|offset| points[offset as usize].take())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_candidate(id: u64, score: f32, vector: DenseVector) -> (ScoredPoint, DenseVector) {
+        let point = ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        };
+        (point, vector)
+    }
+
+    fn candidates() -> Vec<(ScoredPoint, DenseVector)> {
+        vec![
+            make_candidate(1, 0.8, vec![0.8, 0.6]),
+            // near-duplicate of the first point
+            make_candidate(2, 0.78, vec![0.78, 0.6257]),
+            make_candidate(3, 0.6, vec![0.6, -0.8]),
+            make_candidate(4, 0.0, vec![0.0, 1.0]),
+        ]
+    }
+
+    fn ids(points: &[ScoredPoint]) -> Vec<u64> {
+        points
+            .iter()
+            .map(|point| match point.id {
+                crate::types::ExtendedPointId::NumId(id) => id,
+                crate::types::ExtendedPointId::Uuid(_) => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mmr_empty() {
+        let stopped = AtomicBool::new(false);
+        let result =
+            maximal_marginal_relevance(vec![1.0, 0.0], vec![], Distance::Dot, 0.5, 10, &stopped)
+                .unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_mmr_no_diversity_keeps_relevance_order() {
+        let stopped = AtomicBool::new(false);
+        let result = maximal_marginal_relevance(
+            vec![1.0, 0.0],
+            candidates(),
+            Distance::Dot,
+            0.0,
+            3,
+            &stopped,
+        )
+        .unwrap();
+        assert_eq!(ids(&result), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_mmr_skips_near_duplicates() {
+        let stopped = AtomicBool::new(false);
+        let result = maximal_marginal_relevance(
+            vec![1.0, 0.0],
+            candidates(),
+            Distance::Dot,
+            0.5,
+            2,
+            &stopped,
+        )
+        .unwrap();
+
+        // The most relevant point goes first, then its near-duplicate is pushed back
+        assert_eq!(ids(&result), vec![1, 3]);
+        // Original scores are kept
+        assert_eq!(result[0].score, 0.8);
+        assert_eq!(result[1].score, 0.6);
+    }
+
+    #[test]
+    fn test_mmr_cosine_query_is_normalized() {
+        let stopped = AtomicBool::new(false);
+        let candidates = || {
+            candidates()
+                .into_iter()
+                .map(|(point, vector)| (point, Distance::Cosine.preprocess_vector(vector)))
+                .collect::<Vec<_>>()
+        };
+
+        // Relevance must not depend on the length of the query, otherwise it would outweigh
+        // the similarity between the candidates
+        for query in [vec![1.0, 0.0], vec![10.0, 0.0], vec![0.1, 0.0]] {
+            let result =
+                maximal_marginal_relevance(query, candidates(), Distance::Cosine, 0.5, 2, &stopped)
+                    .unwrap();
+            assert_eq!(ids(&result), vec![1, 3]);
+        }
+    }
+
+    #[test]
+    fn test_mmr_wrong_dimension() {
+        let stopped = AtomicBool::new(false);
+        let result = maximal_marginal_relevance(
+            vec![1.0, 0.0],
+            vec![make_candidate(1, 1.0, vec![1.0, 0.0, 0.0])],
+            Distance::Dot,
+            0.5,
+            10,
+            &stopped,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -1,13 +1,16 @@
 pub mod anonymize;
 pub mod error_logging;
 pub mod macros;
+pub mod maximal_marginal_relevance;
 pub mod mmap_type;
 pub mod operation_error;
 pub mod operation_time_statistics;
+pub mod random_sampling;
 pub mod reciprocal_rank_fusion;
 pub mod rocksdb_buffered_delete_wrapper;
 pub mod rocksdb_buffered_update_wrapper;
 pub mod rocksdb_wrapper;
+pub mod score_fusion;
 pub mod utils;
 pub mod validate_snapshot_archive;
 pub mod vector_utils;
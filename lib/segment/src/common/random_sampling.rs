@@ -0,0 +1,82 @@
+//! Uniform random sampling which can be merged across segments and shards.
+//!
+//! Giving every point an independent uniform random key and keeping the `k` points with the
+//! largest keys selects a uniform sample of `k` points. The keys of the selected points are the
+//! `k` largest order statistics of `n` uniform variables, which can be generated directly for a
+//! sample that was drawn by other means. As keys of disjoint sets of points are comparable, keeping
+//! the `k` largest keys of their samples gives a uniform sample of their union.
+
+use common::types::ScoreType;
+use rand::Rng;
+
+/// Keys of the `count` largest of `population` uniform random variables, in descending order.
+///
+/// Keys are natural logarithms of the variables, which keeps them distinct for large populations.
+pub fn sampling_keys<R: Rng + ?Sized>(
+    rng: &mut R,
+    population: usize,
+    count: usize,
+) -> Vec<ScoreType> {
+    let count = count.min(population);
+    let mut keys = Vec::with_capacity(count);
+
+    let mut log_key = 0.0f64;
+    for remaining in (population - count + 1..=population).rev() {
+        // The largest of `remaining` uniform variables is distributed as `U^(1/remaining)`,
+        // the next one is the largest of the rest, scaled below the previous one
+        let uniform = 1.0 - rng.gen::<f64>();
+        log_key += uniform.ln() / remaining as f64;
+        keys.push(log_key as ScoreType);
+    }
+
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn test_sampling_keys_are_descending() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let keys = sampling_keys(&mut rng, 1_000_000_000, 100);
+        assert_eq!(keys.len(), 100);
+        assert!(keys.iter().all(|key| *key <= 0.0));
+        assert!(keys.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        assert_eq!(sampling_keys(&mut rng, 3, 10).len(), 3);
+        assert!(sampling_keys(&mut rng, 0, 10).is_empty());
+    }
+
+    #[test]
+    fn test_merged_samples_are_proportional() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        // Two sets of 900 and 100 points, sample 10 points from their union
+        let mut from_large = 0;
+        for _ in 0..1000 {
+            let large = sampling_keys(&mut rng, 900, 10);
+            let small = sampling_keys(&mut rng, 100, 10);
+
+            let mut merged: Vec<_> = large
+                .into_iter()
+                .map(|key| (key, true))
+                .chain(small.into_iter().map(|key| (key, false)))
+                .collect();
+            merged.sort_unstable_by(|a, b| b.0.total_cmp(&a.0));
+
+            from_large += merged
+                .iter()
+                .take(10)
+                .filter(|(_, is_large)| *is_large)
+                .count();
+        }
+
+        // 90% of the points are expected to come from the larger set
+        assert!((8800..9200).contains(&from_large), "{from_large}");
+    }
+}
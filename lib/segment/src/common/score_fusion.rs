@@ -0,0 +1,269 @@
+//! Score-based fusion methods, which combine the scores of the same points from multiple sources.
+//!
+//! Unlike [reciprocal rank fusion](super::reciprocal_rank_fusion), the input scores matter.
+//! Each source comes with its [`Order`]. Scores of small-is-better sources, like distances,
+//! are inverted, so that the fused scores are always larger-is-better.
+
+use std::collections::hash_map::Entry;
+
+use ahash::{HashMap, HashMapExt};
+use common::types::ScoreType;
+use ordered_float::OrderedFloat;
+
+use crate::types::{ExtendedPointId, Order, ScoredPoint};
+
+/// Number of standard deviations away from the mean, which are mapped to the 0 and 1 scores
+const DBSF_STD_DEVIATIONS: ScoreType = 3.0;
+
+/// Sum up the scores of the points, after transforming the scores of each source with `source_score`.
+///
+/// `source_score` receives the index of the source and its points, and returns a function to transform its scores.
+/// The transformed scores must be larger-is-better.
+///
+/// The output is a single sorted list of ScoredPoint.
+/// Does not break ties.
+fn sum_scores<F>(
+    responses: impl IntoIterator<Item = Vec<ScoredPoint>>,
+    mut source_score: impl FnMut(usize, &[ScoredPoint]) -> F,
+) -> Vec<ScoredPoint>
+where
+    F: Fn(ScoreType) -> ScoreType,
+{
+    // track scored points by id
+    let mut points_by_id: HashMap<ExtendedPointId, ScoredPoint> = HashMap::new();
+
+    for (source_idx, response) in responses.into_iter().enumerate() {
+        let score_fn = source_score(source_idx, &response);
+        for mut point in response {
+            let score = score_fn(point.score);
+            match points_by_id.entry(point.id) {
+                Entry::Occupied(mut entry) => {
+                    // accumulate score
+                    entry.get_mut().score += score;
+                }
+                Entry::Vacant(entry) => {
+                    point.score = score;
+                    // init score
+                    entry.insert(point);
+                }
+            }
+        }
+    }
+
+    let mut scores: Vec<_> = points_by_id.into_values().collect();
+    scores.sort_unstable_by(|a, b| {
+        // sort by score descending
+        OrderedFloat(b.score).cmp(&OrderedFloat(a.score))
+    });
+
+    scores
+}
+
+/// Distribution-Based Score Fusion (DBSF).
+///
+/// Normalizes the scores of each source, using its mean and standard deviation:
+/// `mean - 3 * std_dev` becomes 0 and `mean + 3 * std_dev` becomes 1. Normalized scores are then summed up.
+/// For sources with [`Order::SmallBetter`], the normalized scores are inverted.
+///
+/// `orders` has one order per source. Sources without an order are considered larger-is-better.
+///
+/// The output is a single sorted list of ScoredPoint.
+/// Does not break ties.
+pub fn dbsf_scoring(
+    responses: impl IntoIterator<Item = Vec<ScoredPoint>>,
+    orders: &[Order],
+) -> Vec<ScoredPoint> {
+    sum_scores(responses, |source_idx, points| {
+        let order = source_order(orders, source_idx);
+        let (mean, std_dev) = mean_and_std_dev(points);
+        let low = mean - DBSF_STD_DEVIATIONS * std_dev;
+        let range = 2.0 * DBSF_STD_DEVIATIONS * std_dev;
+
+        move |score| {
+            if range > 0.0 {
+                let normalized = ((score - low) / range).clamp(0.0, 1.0);
+                match order {
+                    Order::LargeBetter => normalized,
+                    Order::SmallBetter => 1.0 - normalized,
+                }
+            } else {
+                // All scores are the same, place them in the middle
+                0.5
+            }
+        }
+    })
+}
+
+/// Weighted sum of the scores of each source.
+///
+/// `weights` has one weight per source. Sources without a weight are ignored.
+/// Scores of sources with [`Order::SmallBetter`] are negated before weighting.
+///
+/// `orders` has one order per source. Sources without an order are considered larger-is-better.
+///
+/// The output is a single sorted list of ScoredPoint.
+/// Does not break ties.
+pub fn weighted_scoring(
+    responses: impl IntoIterator<Item = Vec<ScoredPoint>>,
+    weights: &[ScoreType],
+    orders: &[Order],
+) -> Vec<ScoredPoint> {
+    sum_scores(
+        responses.into_iter().take(weights.len()),
+        |source_idx, _| {
+            let weight = match source_order(orders, source_idx) {
+                Order::LargeBetter => weights[source_idx],
+                Order::SmallBetter => -weights[source_idx],
+            };
+            move |score| score * weight
+        },
+    )
+}
+
+fn source_order(orders: &[Order], source_idx: usize) -> Order {
+    orders
+        .get(source_idx)
+        .copied()
+        .unwrap_or(Order::LargeBetter)
+}
+
+fn mean_and_std_dev(points: &[ScoredPoint]) -> (ScoreType, ScoreType) {
+    if points.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let count = points.len() as ScoreType;
+    let mean = points.iter().map(|point| point.score).sum::<ScoreType>() / count;
+    let variance = points
+        .iter()
+        .map(|point| (point.score - mean).powi(2))
+        .sum::<ScoreType>()
+        / count;
+
+    (mean, variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_scored_point(id: u64, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    #[test]
+    fn test_dbsf_scoring_empty() {
+        let scored_points = dbsf_scoring(vec![], &[]);
+        assert_eq!(scored_points.len(), 0);
+    }
+
+    #[test]
+    fn test_dbsf_scoring_one() {
+        let responses = vec![vec![make_scored_point(1, 0.9)]];
+        let scored_points = dbsf_scoring(responses, &[]);
+        assert_eq!(scored_points.len(), 1);
+        assert_eq!(scored_points[0].score, 0.5);
+    }
+
+    #[test]
+    fn test_dbsf_scoring() {
+        // Scores of the two sources have very different scales
+        let responses = vec![
+            vec![
+                make_scored_point(1, 0.9),
+                make_scored_point(3, 0.8),
+                make_scored_point(2, 0.7),
+            ],
+            vec![
+                make_scored_point(3, 300.0),
+                make_scored_point(2, 200.0),
+                make_scored_point(4, 100.0),
+            ],
+        ];
+
+        let scored_points = dbsf_scoring(responses, &[]);
+        assert_eq!(scored_points.len(), 4);
+
+        // Point 3 is in the middle of the first source, and the best of the second one
+        let ids: Vec<_> = scored_points.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![3.into(), 2.into(), 1.into(), 4.into()]);
+
+        // Normalized scores of the same rank match, regardless of the scale of the source
+        assert!((scored_points[2].score - (scored_points[0].score - 0.5)).abs() < 1e-5);
+        assert!((scored_points[1].score - (scored_points[3].score + 0.5)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_weighted_scoring() {
+        let responses = vec![
+            vec![make_scored_point(1, 1.0), make_scored_point(2, 0.5)],
+            vec![make_scored_point(2, 1.0), make_scored_point(3, 0.5)],
+            vec![make_scored_point(4, 1.0)],
+        ];
+
+        // Third source has no weight
+        let scored_points = weighted_scoring(responses, &[0.2, 0.8], &[]);
+        assert_eq!(scored_points.len(), 3);
+
+        assert_eq!(scored_points[0].id, 2.into());
+        assert_eq!(scored_points[0].score, 0.5 * 0.2 + 1.0 * 0.8);
+
+        assert_eq!(scored_points[1].id, 3.into());
+        assert_eq!(scored_points[1].score, 0.5 * 0.8);
+
+        assert_eq!(scored_points[2].id, 1.into());
+        assert_eq!(scored_points[2].score, 0.2);
+    }
+
+    #[test]
+    fn test_dbsf_scoring_small_better() {
+        // Second source is a distance, its best point has the smallest score
+        let responses = vec![
+            vec![
+                make_scored_point(1, 0.9),
+                make_scored_point(2, 0.8),
+                make_scored_point(3, 0.7),
+            ],
+            vec![
+                make_scored_point(1, 1.0),
+                make_scored_point(2, 2.0),
+                make_scored_point(3, 3.0),
+            ],
+        ];
+
+        let scored_points = dbsf_scoring(responses, &[Order::LargeBetter, Order::SmallBetter]);
+
+        // Both sources agree on the ranking
+        let ids: Vec<_> = scored_points.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![1.into(), 2.into(), 3.into()]);
+        assert!((scored_points[1].score - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_weighted_scoring_small_better() {
+        let responses = vec![
+            vec![make_scored_point(1, 1.0), make_scored_point(2, 0.5)],
+            vec![make_scored_point(2, 1.0), make_scored_point(1, 4.0)],
+        ];
+
+        let scored_points = weighted_scoring(
+            responses,
+            &[1.0, 0.5],
+            &[Order::LargeBetter, Order::SmallBetter],
+        );
+
+        assert_eq!(scored_points[0].id, 2.into());
+        assert_eq!(scored_points[0].score, 0.5 - 0.5 * 1.0);
+
+        assert_eq!(scored_points[1].id, 1.into());
+        assert_eq!(scored_points[1].score, 1.0 - 0.5 * 4.0);
+    }
+}
@@ -152,6 +152,7 @@ mod tests {
                             max_indexing_threads: 0,
                             on_disk: None,
                             payload_m: Some(10),
+                            appendable: None,
                         }),
                         quantization_config: None,
                         on_disk: None,
@@ -183,6 +184,7 @@ mod tests {
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                appendable: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -194,14 +196,14 @@ mod tests {
         eprintln!("new = {:#?}", new_segment);
 
         match &new_segment.vector_data.get("vec1").unwrap().index {
-            Indexes::Plain { .. } => panic!("expected HNSW index"),
+            Indexes::Plain { .. } | Indexes::AppendableHnsw(_) => panic!("expected HNSW index"),
             Indexes::Hnsw(hnsw) => {
                 assert_eq!(hnsw.m, 20);
             }
         }
 
         match &new_segment.vector_data.get("vec2").unwrap().index {
-            Indexes::Plain { .. } => panic!("expected HNSW index"),
+            Indexes::Plain { .. } | Indexes::AppendableHnsw(_) => panic!("expected HNSW index"),
             Indexes::Hnsw(hnsw) => {
                 assert_eq!(hnsw.m, 25);
             }
@@ -258,6 +260,7 @@ mod tests {
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                appendable: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::{DimId, DimWeight};
+use validator::Validate;
+
+use crate::data_types::text_index::{TextIndexParams, TextIndexType, TokenizerType};
+use crate::index::field_index::full_text_index::tokenizers::Tokenizer;
+
+pub const DEFAULT_BM25_K1: f32 = 1.2;
+pub const DEFAULT_BM25_B: f32 = 0.75;
+pub const DEFAULT_BM25_AVG_LEN: f32 = 256.0;
+
+/// Parameters of the conversion of a text into a BM25 sparse vector
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct Bm25Config {
+    /// Tokenizer used to split the text into terms. Default: word
+    #[serde(default)]
+    pub tokenizer: TokenizerType,
+    /// Skip terms shorter than this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_token_len: Option<usize>,
+    /// Skip terms longer than this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_token_len: Option<usize>,
+    /// If true, lowercase all terms. Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lowercase: Option<bool>,
+    /// Term frequency saturation. The higher the value, the more repeated terms contribute. Default: 1.2
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0))]
+    pub k1: Option<f32>,
+    /// Document length normalization, from 0 (no normalization) to 1 (full normalization). Default: 0.75
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub b: Option<f32>,
+    /// Expected average length of the documents, in terms. Default: 256
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1.0))]
+    pub avg_len: Option<f32>,
+}
+
+impl Bm25Config {
+    fn text_index_params(&self) -> TextIndexParams {
+        TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: self.tokenizer,
+            min_token_len: self.min_token_len,
+            max_token_len: self.max_token_len,
+            lowercase: self.lowercase,
+            on_disk: None,
+            stopwords: None,
+            stemmer: None,
+            ascii_folding: None,
+            phrase_matching: None,
+        }
+    }
+
+    /// Convert a document into a sparse vector of BM25 term weights.
+    ///
+    /// The IDF part of BM25 is not included, it is applied at search time by `Modifier::Idf`.
+    pub fn document_to_sparse(&self, text: &str) -> SparseVector {
+        let k1 = self.k1.unwrap_or(DEFAULT_BM25_K1);
+        let b = self.b.unwrap_or(DEFAULT_BM25_B);
+        let avg_len = self.avg_len.unwrap_or(DEFAULT_BM25_AVG_LEN);
+
+        let mut doc_len = 0usize;
+        let mut term_frequencies: BTreeMap<DimId, usize> = BTreeMap::new();
+        Tokenizer::new(&self.text_index_params()).tokenize_doc(text, |token| {
+            doc_len += 1;
+            *term_frequencies.entry(token_to_dim_id(token)).or_default() += 1;
+        });
+
+        let length_norm = k1 * (1.0 - b + b * doc_len as f32 / avg_len);
+
+        let (indices, values) = term_frequencies
+            .into_iter()
+            .map(|(dim_id, tf)| {
+                let tf = tf as DimWeight;
+                (dim_id, tf * (k1 + 1.0) / (tf + length_norm))
+            })
+            .unzip();
+
+        SparseVector { indices, values }
+    }
+
+    /// Convert a query into a sparse vector, where each distinct term has a weight of 1.
+    pub fn query_to_sparse(&self, text: &str) -> SparseVector {
+        let mut dim_ids: BTreeMap<DimId, DimWeight> = BTreeMap::new();
+        Tokenizer::new(&self.text_index_params()).tokenize_query(text, |token| {
+            dim_ids.insert(token_to_dim_id(token), 1.0);
+        });
+
+        let (indices, values) = dim_ids.into_iter().unzip();
+
+        SparseVector { indices, values }
+    }
+}
+
+/// Map a term into a dimension of the sparse vector.
+///
+/// Stored vectors depend on this mapping, so it must never change.
+fn token_to_dim_id(token: &str) -> DimId {
+    seahash::hash(token.as_bytes()) as DimId
+}
+
+/// Text to be converted into a sparse vector on the server side
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct Document {
+    /// Text of the document
+    pub text: String,
+    /// Tokenization and BM25 parameters.
+    /// Should be the same for the documents and the queries of the same vector.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub bm25: Option<Bm25Config>,
+}
+
+impl Document {
+    /// Convert into a sparse vector, to be stored
+    pub fn to_sparse_document(&self) -> SparseVector {
+        self.bm25
+            .clone()
+            .unwrap_or_default()
+            .document_to_sparse(&self.text)
+    }
+
+    /// Convert into a sparse vector, to be used as a query
+    pub fn to_sparse_query(&self) -> SparseVector {
+        self.bm25
+            .clone()
+            .unwrap_or_default()
+            .query_to_sparse(&self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weight_of(vector: &SparseVector, token: &str) -> DimWeight {
+        let dim_id = token_to_dim_id(token);
+        let position = vector.indices.iter().position(|&i| i == dim_id).unwrap();
+        vector.values[position]
+    }
+
+    #[test]
+    fn test_document_to_sparse() {
+        let config = Bm25Config::default();
+
+        let vector = config.document_to_sparse("Hello hello world");
+        assert_eq!(vector.indices.len(), 2);
+        assert!(vector.is_sorted());
+
+        // Repeated term weighs more, but less than twice as much
+        let hello = weight_of(&vector, "hello");
+        let world = weight_of(&vector, "world");
+        assert!(hello > world);
+        assert!(hello < 2.0 * world);
+
+        // Longer documents are penalized
+        let long_vector = config.document_to_sparse(&format!("world {}", "filler ".repeat(500)));
+        assert!(weight_of(&long_vector, "world") < world);
+    }
+
+    #[test]
+    fn test_query_to_sparse() {
+        let config = Bm25Config::default();
+
+        let vector = config.query_to_sparse("Hello hello world");
+        assert_eq!(vector.indices.len(), 2);
+        assert_eq!(vector.values, vec![1.0, 1.0]);
+
+        // Query terms match the terms of the documents
+        let doc = config.document_to_sparse("the world says hello");
+        assert!(vector.indices.iter().all(|i| doc.indices.contains(i)));
+    }
+
+    #[test]
+    fn test_no_length_normalization() {
+        let config = Bm25Config {
+            b: Some(0.0),
+            ..Default::default()
+        };
+
+        let short = config.document_to_sparse("hello");
+        let long = config.document_to_sparse("hello world and everyone else");
+        assert_eq!(weight_of(&short, "hello"), weight_of(&long, "hello"));
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::json_path::JsonPath;
+use crate::types::{Filter, IntPayloadType, UuidIntType};
+
+/// Parameters of a facet request, as executed against segments
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetParams {
+    /// Payload key to count values of
+    pub key: JsonPath,
+    /// Max number of values to return
+    pub limit: usize,
+    /// Only count values of the points which satisfy this filter
+    pub filter: Option<Filter>,
+}
+
+/// Borrowed facet value, as it is stored in a field index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FacetValueRef<'a> {
+    Keyword(&'a str),
+    Int(IntPayloadType),
+    Uuid(UuidIntType),
+    Bool(bool),
+}
+
+impl<'a> FacetValueRef<'a> {
+    pub fn to_owned(&self) -> FacetValue {
+        match self {
+            FacetValueRef::Keyword(keyword) => FacetValue::Keyword((*keyword).to_string()),
+            FacetValueRef::Int(int) => FacetValue::Int(*int),
+            FacetValueRef::Uuid(uuid) => FacetValue::Keyword(Uuid::from_u128(*uuid).to_string()),
+            FacetValueRef::Bool(boolean) => FacetValue::Bool(*boolean),
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(untagged)]
+pub enum FacetValue {
+    Keyword(String),
+    Int(IntPayloadType),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FacetValueHit {
+    /// Value of the payload field
+    pub value: FacetValue,
+    /// Number of points which have this value
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FacetResponse {
+    /// Values with the highest counts, in descending order of counts
+    pub hits: Vec<FacetValueHit>,
+}
+
+impl FacetResponse {
+    /// Build a response out of aggregated counts, keeping only `limit` values with the highest counts.
+    ///
+    /// Ties are broken by value, so that the result is deterministic.
+    pub fn top_hits(counts: HashMap<FacetValue, usize>, limit: usize) -> Self {
+        let mut hits: Vec<_> = counts
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(value, count)| FacetValueHit { value, count })
+            .collect();
+
+        hits.sort_unstable_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+        hits.truncate(limit);
+
+        Self { hits }
+    }
+
+    /// Convert hits back into counts, to be merged with other responses
+    pub fn into_counts(self) -> impl Iterator<Item = (FacetValue, usize)> {
+        self.hits.into_iter().map(|hit| (hit.value, hit.count))
+    }
+}
@@ -0,0 +1,92 @@
+//! Parameters of payload indexes, which only have generic storage options.
+//!
+//! Integer and full-text indexes have their own parameters, see
+//! [`super::integer_index`] and [`super::text_index`].
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeywordIndexType {
+    #[default]
+    Keyword,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct KeywordIndexParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: KeywordIndexType,
+    /// If true, store the index on disk. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FloatIndexType {
+    #[default]
+    Float,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct FloatIndexParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: FloatIndexType,
+    /// If true, store the index on disk. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GeoIndexType {
+    #[default]
+    Geo,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoIndexParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: GeoIndexType,
+    /// If true, store the index on disk. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DatetimeIndexType {
+    #[default]
+    Datetime,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct DatetimeIndexParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: DatetimeIndexType,
+    /// If true, store the index on disk. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UuidIndexType {
+    #[default]
+    Uuid,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct UuidIndexParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: UuidIndexType,
+    /// If true, store the index on disk. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
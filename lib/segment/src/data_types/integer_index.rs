@@ -17,4 +17,7 @@ pub struct IntegerIndexParams {
     pub lookup: bool,
     /// If true - support ranges filters.
     pub range: bool,
+    /// If true, store the index on disk. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
 }
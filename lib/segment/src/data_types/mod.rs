@@ -1,9 +1,13 @@
+pub mod bm25;
+pub mod facets;
 pub mod groups;
+pub mod index;
 pub mod integer_index;
 pub mod named_vectors;
 pub mod order_by;
 pub mod primitive;
 pub mod query_context;
 pub mod text_index;
+pub mod text_query;
 pub mod tiny_map;
 pub mod vectors;
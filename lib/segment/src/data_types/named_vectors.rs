@@ -7,11 +7,14 @@ use super::primitive::PrimitiveVectorElement;
 use super::tiny_map;
 use super::vectors::{
     DenseVector, MultiDenseVector, TypedMultiDenseVector, TypedMultiDenseVectorRef, Vector,
-    VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+    VectorElementType, VectorElementTypeBfloat16, VectorElementTypeBit, VectorElementTypeByte,
+    VectorElementTypeHalf, VectorRef,
 };
 use crate::common::operation_error::OperationError;
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+};
 use crate::types::{Distance, VectorDataConfig, VectorStorageDatatype};
 
 type CowKey<'a> = Cow<'a, str>;
@@ -324,6 +327,8 @@ impl<'a> NamedVectors<'a> {
                 Distance::Manhattan => {
                     <ManhattanMetric as Metric<VectorElementType>>::preprocess(dense_vector)
                 }
+                // binary distances are only defined on the bit datatype
+                Distance::Hamming | Distance::Jaccard => dense_vector,
             },
             Some(VectorStorageDatatype::Uint8) => match config.distance {
                 Distance::Cosine => {
@@ -338,6 +343,8 @@ impl<'a> NamedVectors<'a> {
                 Distance::Manhattan => {
                     <ManhattanMetric as Metric<VectorElementTypeByte>>::preprocess(dense_vector)
                 }
+                // binary distances are only defined on the bit datatype
+                Distance::Hamming | Distance::Jaccard => dense_vector,
             },
             Some(VectorStorageDatatype::Float16) => match config.distance {
                 Distance::Cosine => {
@@ -352,6 +359,37 @@ impl<'a> NamedVectors<'a> {
                 Distance::Manhattan => {
                     <ManhattanMetric as Metric<VectorElementTypeHalf>>::preprocess(dense_vector)
                 }
+                // binary distances are only defined on the bit datatype
+                Distance::Hamming | Distance::Jaccard => dense_vector,
+            },
+            Some(VectorStorageDatatype::Bfloat16) => match config.distance {
+                Distance::Cosine => {
+                    <CosineMetric as Metric<VectorElementTypeBfloat16>>::preprocess(dense_vector)
+                }
+                Distance::Euclid => {
+                    <EuclidMetric as Metric<VectorElementTypeBfloat16>>::preprocess(dense_vector)
+                }
+                Distance::Dot => {
+                    <DotProductMetric as Metric<VectorElementTypeBfloat16>>::preprocess(
+                        dense_vector,
+                    )
+                }
+                Distance::Manhattan => {
+                    <ManhattanMetric as Metric<VectorElementTypeBfloat16>>::preprocess(dense_vector)
+                }
+                // binary distances are only defined on the bit datatype
+                Distance::Hamming | Distance::Jaccard => dense_vector,
+            },
+            Some(VectorStorageDatatype::Bit) => match config.distance {
+                Distance::Hamming => {
+                    <HammingMetric as Metric<VectorElementTypeBit>>::preprocess(dense_vector)
+                }
+                Distance::Jaccard => {
+                    <JaccardMetric as Metric<VectorElementTypeBit>>::preprocess(dense_vector)
+                }
+                Distance::Cosine | Distance::Euclid | Distance::Dot | Distance::Manhattan => {
+                    dense_vector
+                }
             },
         }
     }
@@ -1,3 +1,4 @@
+use common::types::ScoreType;
 use num_cmp::NumCmp;
 use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
@@ -6,7 +7,8 @@ use validator::Validate;
 
 use crate::json_path::JsonPath;
 use crate::types::{
-    DateTimePayloadType, FloatPayloadType, IntPayloadType, Order, Payload, Range, RangeInterface,
+    DateTimePayloadType, FloatPayloadType, GeoPoint, IntPayloadType, Order, Payload, Range,
+    RangeInterface,
 };
 
 const INTERNAL_KEY_OF_ORDER_BY_VALUE: &str = "____ordered_with____";
@@ -68,6 +70,12 @@ pub struct OrderBy {
 
     /// Which payload value to start scrolling from. Default is the lowest value for `asc` and the highest for `desc`
     pub start_from: Option<StartFrom>,
+
+    /// Order by the distance in meters from this point, instead of by the payload value.
+    /// Requires a geo index for the key. Points with several locations are ordered by the nearest one.
+    /// The distance is returned as the score.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_point: Option<GeoPoint>,
 }
 
 impl OrderBy {
@@ -93,6 +101,21 @@ impl OrderBy {
         self.direction.unwrap_or_default()
     }
 
+    /// Whether points are ordered by the distance from a geo point
+    pub fn is_geo_distance(&self) -> bool {
+        self.geo_point.is_some()
+    }
+
+    /// Score of a point with the given order value.
+    ///
+    /// Only the distance of geo ordering is a meaningful score, other orderings score all points with 0.
+    pub fn score(&self, value: OrderValue) -> ScoreType {
+        match value {
+            OrderValue::Float(distance) if self.is_geo_distance() => distance as ScoreType,
+            OrderValue::Float(_) | OrderValue::Int(_) => 0.0,
+        }
+    }
+
     pub fn start_from(&self) -> OrderValue {
         self.start_from
             .as_ref()
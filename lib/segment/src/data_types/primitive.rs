@@ -1,12 +1,15 @@
 use std::borrow::Cow;
 
-use half::f16;
+use half::{bf16, f16};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use super::named_vectors::CowMultiVector;
 use super::vectors::TypedMultiDenseVector;
-use crate::data_types::vectors::{VectorElementType, VectorElementTypeByte, VectorElementTypeHalf};
+use crate::data_types::vectors::{
+    PackedBits, VectorElementType, VectorElementTypeBfloat16, VectorElementTypeBit,
+    VectorElementTypeByte, VectorElementTypeHalf,
+};
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::types::{Distance, QuantizationConfig, VectorStorageDatatype};
@@ -119,6 +122,56 @@ impl PrimitiveVectorElement for VectorElementTypeHalf {
     }
 }
 
+impl PrimitiveVectorElement for VectorElementTypeBfloat16 {
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        Cow::Owned(vector.iter().map(|&x| bf16::from_f32(x)).collect())
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        Cow::Owned(vector.iter().map(|&x| bf16::to_f32(x)).collect_vec())
+    }
+
+    fn quantization_preprocess<'a>(
+        _quantization_config: &QuantizationConfig,
+        _distance: Distance,
+        vector: &'a [Self],
+    ) -> Cow<'a, [f32]> {
+        Cow::Owned(vector.iter().map(|&x| bf16::to_f32(x)).collect_vec())
+    }
+
+    fn from_float_multivector(
+        multivector: CowMultiVector<VectorElementType>,
+    ) -> CowMultiVector<Self> {
+        CowMultiVector::Owned(TypedMultiDenseVector::new(
+            multivector
+                .as_vec_ref()
+                .flattened_vectors
+                .iter()
+                .map(|&x| bf16::from_f32(x))
+                .collect_vec(),
+            multivector.as_vec_ref().dim,
+        ))
+    }
+
+    fn into_float_multivector(
+        multivector: CowMultiVector<Self>,
+    ) -> CowMultiVector<VectorElementType> {
+        CowMultiVector::Owned(TypedMultiDenseVector::new(
+            multivector
+                .as_vec_ref()
+                .flattened_vectors
+                .iter()
+                .map(|&x| bf16::to_f32(x))
+                .collect_vec(),
+            multivector.as_vec_ref().dim,
+        ))
+    }
+
+    fn datatype() -> VectorStorageDatatype {
+        VectorStorageDatatype::Bfloat16
+    }
+}
+
 impl PrimitiveVectorElement for VectorElementTypeByte {
     fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
         Cow::Owned(vector.iter().map(|&x| x as u8).collect())
@@ -151,6 +204,8 @@ impl PrimitiveVectorElement for VectorElementTypeByte {
                 Distance::Manhattan => {
                     <ManhattanMetric as Metric<VectorElementType>>::preprocess(vector)
                 }
+                // binary distances are only defined on the bit datatype
+                Distance::Hamming | Distance::Jaccard => vector,
             };
             Cow::from(preprocessed_vector)
         }
@@ -188,3 +243,49 @@ impl PrimitiveVectorElement for VectorElementTypeByte {
         ))
     }
 }
+
+impl PrimitiveVectorElement for VectorElementTypeBit {
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        Cow::Owned(PackedBits::pack(&vector))
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        Cow::Owned(PackedBits::unpack(&vector))
+    }
+
+    fn quantization_preprocess<'a>(
+        _quantization_config: &QuantizationConfig,
+        _distance: Distance,
+        vector: &'a [Self],
+    ) -> Cow<'a, [f32]> {
+        Cow::Owned(PackedBits::unpack(vector))
+    }
+
+    fn datatype() -> VectorStorageDatatype {
+        VectorStorageDatatype::Bit
+    }
+
+    fn from_float_multivector(
+        multivector: CowMultiVector<VectorElementType>,
+    ) -> CowMultiVector<Self> {
+        let multivector = multivector.as_vec_ref();
+        CowMultiVector::Owned(TypedMultiDenseVector::new(
+            multivector
+                .flattened_vectors
+                .chunks_exact(multivector.dim)
+                .flat_map(PackedBits::pack)
+                .collect_vec(),
+            multivector.dim.div_ceil(PackedBits::BITS),
+        ))
+    }
+
+    fn into_float_multivector(
+        multivector: CowMultiVector<Self>,
+    ) -> CowMultiVector<VectorElementType> {
+        let multivector = multivector.as_vec_ref();
+        CowMultiVector::Owned(TypedMultiDenseVector::new(
+            PackedBits::unpack(multivector.flattened_vectors),
+            multivector.dim * PackedBits::BITS,
+        ))
+    }
+}
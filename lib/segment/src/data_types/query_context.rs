@@ -21,6 +21,10 @@ pub struct QueryContext {
     /// Is changed externally if API times out or cancelled.
     is_stopped: Arc<AtomicBool>,
 
+    /// Whether searches are recorded in the search telemetry of vector indices.
+    /// Disabled for internal searches, which are not requested by users.
+    record_telemetry: bool,
+
     /// Statistics of the element frequency,
     /// collected over all segments.
     /// Required for processing sparse vector search with `idf-dot` similarity.
@@ -34,6 +38,7 @@ impl QueryContext {
             available_point_count: 0,
             search_optimized_threshold_kb,
             is_stopped: Arc::new(AtomicBool::new(false)),
+            record_telemetry: true,
             idf: tiny_map::TinyMap::new(),
         }
     }
@@ -47,6 +52,11 @@ impl QueryContext {
         self
     }
 
+    pub fn with_record_telemetry(mut self, record_telemetry: bool) -> Self {
+        self.record_telemetry = record_telemetry;
+        self
+    }
+
     pub fn available_point_count(&self) -> usize {
         self.available_point_count
     }
@@ -107,6 +117,7 @@ impl<'a> SegmentQueryContext<'a> {
                 available_point_count: query_context.available_point_count,
                 search_optimized_threshold_kb: query_context.search_optimized_threshold_kb,
                 is_stopped: Some(&query_context.is_stopped),
+                record_telemetry: query_context.record_telemetry,
                 idf: query_context.idf.get(vector_name),
                 deleted_points: self.deleted_points,
             }
@@ -136,6 +147,8 @@ pub struct VectorQueryContext<'a> {
 
     is_stopped: Option<&'a AtomicBool>,
 
+    record_telemetry: bool,
+
     idf: Option<&'a HashMap<DimId, usize>>,
 
     deleted_points: Option<&'a BitSlice>,
@@ -176,6 +189,10 @@ impl VectorQueryContext<'_> {
             .unwrap_or_else(|| SimpleCow::Owned(AtomicBool::new(false)))
     }
 
+    pub fn record_telemetry(&self) -> bool {
+        self.record_telemetry
+    }
+
     /// Compute advanced formula for Inverse Document Frequency (IDF) according to wikipedia.
     /// This should account for corner cases when `df` and `n` are small or zero.
     #[inline]
@@ -209,6 +226,7 @@ impl Default for VectorQueryContext<'_> {
             available_point_count: 0,
             search_optimized_threshold_kb: usize::MAX,
             is_stopped: None,
+            record_telemetry: true,
             idf: None,
             deleted_points: None,
         }
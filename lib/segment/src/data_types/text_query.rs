@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use crate::data_types::bm25::{DEFAULT_BM25_B, DEFAULT_BM25_K1};
+use crate::json_path::JsonPath;
+
+/// Full-text query, which ranks points by BM25 relevance of a text payload field, as executed
+/// against segments
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextQuery {
+    /// Payload key with a full-text index
+    pub key: JsonPath,
+    /// Text to search for
+    pub text: String,
+}
+
+/// Statistics of the indexed documents, which BM25 scores depend on
+///
+/// Statistics of all segments of a shard are summed up before scoring, so that scores of points of
+/// different segments are comparable. Shards don't share their statistics with each other.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextQueryStatistics {
+    /// Number of indexed points
+    pub points_count: usize,
+    /// Number of tokens in the documents of all indexed points, including repeated ones
+    pub total_length: usize,
+    /// Number of indexed points, which contain each token of the query
+    pub term_points_count: HashMap<String, usize>,
+}
+
+impl TextQueryStatistics {
+    pub fn merge(&mut self, other: &TextQueryStatistics) {
+        self.points_count += other.points_count;
+        self.total_length += other.total_length;
+        for (term, &count) in &other.term_points_count {
+            *self.term_points_count.entry(term.clone()).or_default() += count;
+        }
+    }
+
+    /// Remove the statistics of `other`, which must be a part of these statistics
+    pub fn subtract(&mut self, other: &TextQueryStatistics) {
+        self.points_count = self.points_count.saturating_sub(other.points_count);
+        self.total_length = self.total_length.saturating_sub(other.total_length);
+        for (term, &count) in &other.term_points_count {
+            if let Some(term_count) = self.term_points_count.get_mut(term) {
+                *term_count = term_count.saturating_sub(count);
+            }
+        }
+    }
+
+    pub fn average_length(&self) -> f32 {
+        if self.points_count == 0 {
+            return 1.0;
+        }
+        (self.total_length as f32 / self.points_count as f32).max(1.0)
+    }
+
+    /// Inverse document frequency of the term, same as for sparse vectors with IDF modifier
+    pub fn idf(&self, term: &str) -> f32 {
+        let n = self.points_count as f32;
+        let df = self.term_points_count.get(term).copied().unwrap_or(0) as f32;
+        ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+    }
+
+    /// BM25 score of a single term of the query in a document
+    pub fn term_score(&self, idf: f32, term_frequency: u32, document_length: usize) -> f32 {
+        let tf = term_frequency as f32;
+        let length_norm = DEFAULT_BM25_K1
+            * (1.0 - DEFAULT_BM25_B
+                + DEFAULT_BM25_B * document_length as f32 / self.average_length());
+        idf * tf * (DEFAULT_BM25_K1 + 1.0) / (tf + length_norm)
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::slice::ChunksExactMut;
 
-use half::f16;
+use half::{bf16, f16};
 use itertools::Itertools;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -190,8 +190,53 @@ pub type VectorElementType = f32;
 
 pub type VectorElementTypeHalf = f16;
 
+pub type VectorElementTypeBfloat16 = bf16;
+
 pub type VectorElementTypeByte = u8;
 
+pub type VectorElementTypeBit = PackedBits;
+
+/// Eight dimensions of a binary vector packed into a single byte, most significant bit first.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PackedBits(pub u8);
+
+impl PackedBits {
+    /// Number of vector dimensions stored in a single element
+    pub const BITS: usize = 8;
+
+    pub fn as_bytes(bits: &[PackedBits]) -> &[u8] {
+        // Safety: `PackedBits` is a transparent wrapper around `u8`
+        unsafe { std::slice::from_raw_parts(bits.as_ptr() as *const u8, bits.len()) }
+    }
+
+    /// Pack a vector into bits, dimension is set if its value is positive
+    pub fn pack(vector: &[VectorElementType]) -> Vec<PackedBits> {
+        vector
+            .chunks(Self::BITS)
+            .map(|chunk| {
+                let byte = chunk.iter().enumerate().fold(0u8, |byte, (i, &x)| {
+                    if x > 0.0 {
+                        byte | (0x80 >> i)
+                    } else {
+                        byte
+                    }
+                });
+                PackedBits(byte)
+            })
+            .collect()
+    }
+
+    /// Unpack bits into a vector of `0.0` and `1.0` values
+    pub fn unpack(bits: &[PackedBits]) -> DenseVector {
+        bits.iter()
+            .flat_map(|&PackedBits(byte)| {
+                (0..Self::BITS).map(move |i| if byte & (0x80 >> i) != 0 { 1.0 } else { 0.0 })
+            })
+            .collect()
+    }
+}
+
 pub const DEFAULT_VECTOR_NAME: &str = "";
 
 pub type TypedDenseVector<T> = Vec<T>;
@@ -1,14 +1,18 @@
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 
 use common::types::TelemetryDetail;
 
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
+use crate::data_types::facets::{FacetParams, FacetValue};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{OrderBy, OrderValue};
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
+use crate::data_types::text_query::{TextQuery, TextQueryStatistics};
 use crate::data_types::vectors::{QueryVector, Vector};
 use crate::index::field_index::CardinalityEstimation;
+use crate::index::rescore_formula::parsed_formula::ParsedFormula;
 use crate::json_path::JsonPath;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
@@ -126,6 +130,70 @@ pub trait SegmentEntry {
         order_by: &'a OrderBy,
     ) -> OperationResult<Vec<(OrderValue, PointIdType)>>;
 
+    /// Count points per value of the `request.key` field, among the points which satisfy
+    /// `request.filter`. Each point is counted only once per distinct value it has.
+    ///
+    /// Will fail if there is no keyword, integer or bool index for the key.
+    fn facet(
+        &self,
+        request: &FacetParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>>;
+
+    /// Rescore the points of the prefetches which are in this segment, and satisfy `filter`, with the formula.
+    ///
+    /// Returns the `limit` points with the highest new scores.
+    fn rescore_with_formula(
+        &self,
+        formula: &ParsedFormula,
+        prefetches_results: &[Vec<ScoredPoint>],
+        filter: Option<&Filter>,
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>>;
+
+    /// Statistics of the full-text index of `query.key`, needed to score the points with BM25.
+    ///
+    /// Will fail if there is no full-text index for the key.
+    fn text_query_statistics(&self, query: &TextQuery) -> OperationResult<TextQueryStatistics>;
+
+    /// Statistics of the full-text index of `query.key`, which only include the documents of the
+    /// given points.
+    ///
+    /// Points which are not in the segment are ignored. Will fail if there is no full-text index
+    /// for the key.
+    fn text_query_points_statistics(
+        &self,
+        query: &TextQuery,
+        point_ids: &HashSet<PointIdType>,
+    ) -> OperationResult<TextQueryStatistics>;
+
+    /// Score the points which satisfy `filter` with BM25 relevance of the `query.key` field to the
+    /// text, using the statistics of all segments.
+    ///
+    /// Returns the `limit` points with the highest scores. Will fail if there is no full-text
+    /// index for the key.
+    fn text_search(
+        &self,
+        query: &TextQuery,
+        statistics: &TextQueryStatistics,
+        filter: Option<&Filter>,
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>>;
+
+    /// Uniform random sample of up to `limit` points which satisfy `filter`.
+    ///
+    /// Points are scored with random sampling keys, so that samples of several segments can be
+    /// merged by keeping the points with the highest scores.
+    /// See [`crate::common::random_sampling`] for details.
+    fn read_random_filtered(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>>;
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType>;
 
@@ -1,4 +1,5 @@
 use std::ops::{Range, RangeInclusive};
+use std::sync::Arc;
 
 use fnv::FnvBuildHasher;
 use indexmap::IndexSet;
@@ -166,9 +167,11 @@ pub fn random_uncommon_condition<R: Rng + ?Sized>(rnd_gen: &mut R) -> Condition
             },
         )),
         2 => Condition::HasId(HasIdCondition {
-            has_id: (0..rnd_gen.gen_range(10..50))
-                .map(|_| ExtendedPointId::NumId(rnd_gen.gen_range(0..1000)))
-                .collect(),
+            has_id: Arc::new(
+                (0..rnd_gen.gen_range(10..50))
+                    .map(|_| ExtendedPointId::NumId(rnd_gen.gen_range(0..1000)))
+                    .collect(),
+            ),
         }),
         3 => Condition::IsEmpty(IsEmptyCondition {
             is_empty: PayloadField {
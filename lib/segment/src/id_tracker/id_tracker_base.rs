@@ -121,8 +121,15 @@ pub trait IdTracker {
         deleted_vector_bitslice: Option<&'a BitSlice>,
     ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         // Use seeded randomness, prevents 'inconsistencies' in search results with sampling
-        let mut rng = StdRng::seed_from_u64(SEED);
+        self.sample_ids_with_rng(deleted_vector_bitslice, StdRng::seed_from_u64(SEED))
+    }
 
+    /// Same as [`IdTracker::sample_ids`], but drawing the IDs from the given random generator
+    fn sample_ids_with_rng<'a>(
+        &'a self,
+        deleted_vector_bitslice: Option<&'a BitSlice>,
+        mut rng: StdRng,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         let total = self.total_point_count() as PointOffsetType;
         Box::new(
             (0..total)
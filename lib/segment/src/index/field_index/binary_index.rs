@@ -196,6 +196,27 @@ impl BinaryIndex {
         binary_item.has_true() as usize + binary_item.has_false() as usize
     }
 
+    /// Distinct boolean values of the point
+    pub fn get_point_values(&self, point_id: PointOffsetType) -> impl Iterator<Item = bool> {
+        let binary_item = self.memory.get(point_id);
+        [
+            binary_item.has_true().then_some(true),
+            binary_item.has_false().then_some(false),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Number of points which have a `true` value
+    pub fn trues_count(&self) -> usize {
+        self.memory.trues_count()
+    }
+
+    /// Number of points which have a `false` value
+    pub fn falses_count(&self) -> usize {
+        self.memory.falses_count()
+    }
+
     pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
         self.values_count(point_id) == 0
     }
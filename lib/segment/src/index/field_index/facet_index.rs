@@ -0,0 +1,112 @@
+use common::types::PointOffsetType;
+use itertools::Itertools;
+use smol_str::SmolStr;
+
+use super::binary_index::BinaryIndex;
+use super::map_index::MapIndex;
+use super::numeric_index::{NumericIndex, StreamRange};
+use crate::data_types::facets::FacetValueRef;
+use crate::types::{IntPayloadType, Range, RangeInterface, UuidIntType};
+
+/// Field indexes which are able to count points per value
+pub enum FacetIndex<'a> {
+    Keyword(&'a MapIndex<SmolStr>),
+    Uuid(&'a MapIndex<UuidIntType>),
+    IntMap(&'a MapIndex<IntPayloadType>),
+    Int(&'a NumericIndex<IntPayloadType>),
+    Bool(&'a BinaryIndex),
+}
+
+impl<'a> FacetIndex<'a> {
+    /// Unique values of the point
+    pub fn get_point_values(
+        &self,
+        point_id: PointOffsetType,
+    ) -> Box<dyn Iterator<Item = FacetValueRef<'a>> + 'a> {
+        match self {
+            FacetIndex::Keyword(index) => Box::new(
+                index
+                    .get_values(point_id)
+                    .into_iter()
+                    .flatten()
+                    .map(FacetValueRef::Keyword)
+                    .unique(),
+            ),
+            FacetIndex::Uuid(index) => Box::new(
+                index
+                    .get_values(point_id)
+                    .into_iter()
+                    .flatten()
+                    .map(|uuid| FacetValueRef::Uuid(*uuid))
+                    .unique(),
+            ),
+            FacetIndex::IntMap(index) => Box::new(
+                index
+                    .get_values(point_id)
+                    .into_iter()
+                    .flatten()
+                    .map(|int| FacetValueRef::Int(*int))
+                    .unique(),
+            ),
+            FacetIndex::Int(index) => Box::new(
+                index
+                    .get_values(point_id)
+                    .into_iter()
+                    .flatten()
+                    .map(|int| FacetValueRef::Int(*int))
+                    .unique(),
+            ),
+            FacetIndex::Bool(index) => {
+                Box::new(index.get_point_values(point_id).map(FacetValueRef::Bool))
+            }
+        }
+    }
+
+    /// Iterate over all unique values in the index, together with the number of points having them
+    pub fn iter_counts_per_value(
+        &self,
+    ) -> Box<dyn Iterator<Item = (FacetValueRef<'a>, usize)> + 'a> {
+        match self {
+            FacetIndex::Keyword(index) => Box::new(
+                index
+                    .iter_counts_per_value()
+                    .map(|(keyword, count)| (FacetValueRef::Keyword(keyword), count)),
+            ),
+            FacetIndex::Uuid(index) => Box::new(
+                index
+                    .iter_counts_per_value()
+                    .map(|(uuid, count)| (FacetValueRef::Uuid(*uuid), count)),
+            ),
+            FacetIndex::IntMap(index) => Box::new(
+                index
+                    .iter_counts_per_value()
+                    .map(|(int, count)| (FacetValueRef::Int(*int), count)),
+            ),
+            FacetIndex::Int(index) => {
+                let full_range = RangeInterface::Float(Range {
+                    lt: None,
+                    gt: None,
+                    gte: None,
+                    lte: None,
+                });
+                // Range is streamed in ascending order of values and points, so each value is a
+                // contiguous run. A point with a repeated value is deduplicated within the run.
+                Box::new(
+                    index
+                        .stream_range(&full_range)
+                        .dedup()
+                        .map(|(value, _)| value)
+                        .dedup_with_count()
+                        .map(|(count, value)| (FacetValueRef::Int(value), count)),
+                )
+            }
+            FacetIndex::Bool(index) => Box::new(
+                [
+                    (FacetValueRef::Bool(true), index.trues_count()),
+                    (FacetValueRef::Bool(false), index.falses_count()),
+                ]
+                .into_iter(),
+            ),
+        }
+    }
+}
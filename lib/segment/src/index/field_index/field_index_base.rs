@@ -1,9 +1,11 @@
 use std::fmt::Formatter;
+use std::path::PathBuf;
 
 use common::types::PointOffsetType;
 use serde_json::Value;
 use smol_str::SmolStr;
 
+use super::facet_index::FacetIndex;
 use super::map_index::MapIndex;
 use super::numeric_index::StreamRange;
 use crate::common::operation_error::OperationResult;
@@ -16,8 +18,8 @@ use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchText,
-    PayloadKeyType, RangeInterface,
+    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchPhrase,
+    MatchText, PayloadKeyType, RangeInterface, UuidIntType,
 };
 
 pub trait PayloadFieldIndex {
@@ -101,6 +103,7 @@ pub enum FieldIndex {
     DatetimeIndex(NumericIndex<IntPayloadType>),
     IntMapIndex(MapIndex<IntPayloadType>),
     KeywordIndex(MapIndex<SmolStr>),
+    UuidMapIndex(MapIndex<UuidIntType>),
     FloatIndex(NumericIndex<FloatPayloadType>),
     GeoIndex(GeoMapIndex),
     FullTextIndex(FullTextIndex),
@@ -114,6 +117,7 @@ impl std::fmt::Debug for FieldIndex {
             FieldIndex::DatetimeIndex(_index) => write!(f, "DatetimeIndex"),
             FieldIndex::IntMapIndex(_index) => write!(f, "IntMapIndex"),
             FieldIndex::KeywordIndex(_index) => write!(f, "KeywordIndex"),
+            FieldIndex::UuidMapIndex(_index) => write!(f, "UuidMapIndex"),
             FieldIndex::FloatIndex(_index) => write!(f, "FloatIndex"),
             FieldIndex::GeoIndex(_index) => write!(f, "GeoIndex"),
             FieldIndex::BinaryIndex(_index) => write!(f, "BinaryIndex"),
@@ -140,6 +144,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(_) => None,
             FieldIndex::IntMapIndex(_) => None,
             FieldIndex::KeywordIndex(_) => None,
+            FieldIndex::UuidMapIndex(_) => None,
             FieldIndex::FloatIndex(_) => None,
             FieldIndex::GeoIndex(_) => None,
             FieldIndex::BinaryIndex(_) => None,
@@ -154,6 +159,16 @@ impl FieldIndex {
                     }
                     Some(false)
                 }
+                Some(Match::Phrase(MatchPhrase { phrase })) => {
+                    let query = full_text_index.parse_phrase(phrase);
+                    for value in full_text_index.get_values(payload_value) {
+                        let positions = full_text_index.parse_positions(&value);
+                        if query.check_phrase(&positions) {
+                            return Some(true);
+                        }
+                    }
+                    Some(false)
+                }
                 _ => None,
             },
         }
@@ -165,6 +180,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(payload_field_index) => payload_field_index,
             FieldIndex::IntMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(payload_field_index) => payload_field_index,
+            FieldIndex::UuidMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(payload_field_index) => payload_field_index,
@@ -179,6 +195,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::UuidMapIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index,
@@ -192,6 +209,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::UuidMapIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index.load(),
@@ -205,6 +223,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.clear(),
             FieldIndex::IntMapIndex(index) => index.clear(),
             FieldIndex::KeywordIndex(index) => index.clear(),
+            FieldIndex::UuidMapIndex(index) => index.clear(),
             FieldIndex::FloatIndex(index) => index.clear(),
             FieldIndex::GeoIndex(index) => index.clear(),
             FieldIndex::BinaryIndex(index) => index.clear(),
@@ -212,12 +231,28 @@ impl FieldIndex {
         }
     }
 
+    /// Files of the on-disk index, RocksDB based indexes have none
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            FieldIndex::IntIndex(index) => index.files(),
+            FieldIndex::DatetimeIndex(index) => index.files(),
+            FieldIndex::IntMapIndex(index) => index.files(),
+            FieldIndex::KeywordIndex(index) => index.files(),
+            FieldIndex::UuidMapIndex(index) => index.files(),
+            FieldIndex::FloatIndex(index) => index.files(),
+            FieldIndex::GeoIndex(index) => index.files(),
+            FieldIndex::BinaryIndex(_) => vec![],
+            FieldIndex::FullTextIndex(index) => index.files(),
+        }
+    }
+
     pub fn recreate(&self) -> OperationResult<()> {
         match self {
             FieldIndex::IntIndex(index) => index.recreate(),
             FieldIndex::DatetimeIndex(index) => index.recreate(),
             FieldIndex::IntMapIndex(index) => index.recreate(),
             FieldIndex::KeywordIndex(index) => index.recreate(),
+            FieldIndex::UuidMapIndex(index) => index.recreate(),
             FieldIndex::FloatIndex(index) => index.recreate(),
             FieldIndex::GeoIndex(index) => index.recreate(),
             FieldIndex::BinaryIndex(index) => index.recreate(),
@@ -271,6 +306,9 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::UuidMapIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
             FieldIndex::FloatIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
@@ -292,6 +330,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.remove_point(point_id),
             FieldIndex::IntMapIndex(index) => index.remove_point(point_id),
             FieldIndex::KeywordIndex(index) => index.remove_point(point_id),
+            FieldIndex::UuidMapIndex(index) => index.remove_point(point_id),
             FieldIndex::FloatIndex(index) => index.remove_point(point_id),
             FieldIndex::GeoIndex(index) => index.remove_point(point_id),
             FieldIndex::BinaryIndex(index) => index.remove_point(point_id),
@@ -305,6 +344,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.get_telemetry_data(),
             FieldIndex::IntMapIndex(index) => index.get_telemetry_data(),
             FieldIndex::KeywordIndex(index) => index.get_telemetry_data(),
+            FieldIndex::UuidMapIndex(index) => index.get_telemetry_data(),
             FieldIndex::FloatIndex(index) => index.get_telemetry_data(),
             FieldIndex::GeoIndex(index) => index.get_telemetry_data(),
             FieldIndex::BinaryIndex(index) => index.get_telemetry_data(),
@@ -318,6 +358,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.values_count(point_id),
             FieldIndex::IntMapIndex(index) => index.values_count(point_id),
             FieldIndex::KeywordIndex(index) => index.values_count(point_id),
+            FieldIndex::UuidMapIndex(index) => index.values_count(point_id),
             FieldIndex::FloatIndex(index) => index.values_count(point_id),
             FieldIndex::GeoIndex(index) => index.values_count(point_id),
             FieldIndex::BinaryIndex(index) => index.values_count(point_id),
@@ -331,6 +372,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.values_is_empty(point_id),
             FieldIndex::IntMapIndex(index) => index.values_is_empty(point_id),
             FieldIndex::KeywordIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::UuidMapIndex(index) => index.values_is_empty(point_id),
             FieldIndex::FloatIndex(index) => index.values_is_empty(point_id),
             FieldIndex::GeoIndex(index) => index.values_is_empty(point_id),
             FieldIndex::BinaryIndex(index) => index.values_is_empty(point_id),
@@ -345,11 +387,54 @@ impl FieldIndex {
             FieldIndex::FloatIndex(index) => Some(NumericFieldIndex::FloatIndex(index)),
             FieldIndex::IntMapIndex(_)
             | FieldIndex::KeywordIndex(_)
+            | FieldIndex::UuidMapIndex(_)
             | FieldIndex::GeoIndex(_)
             | FieldIndex::BinaryIndex(_)
             | FieldIndex::FullTextIndex(_) => None,
         }
     }
+
+    pub fn as_geo_index(&self) -> Option<&GeoMapIndex> {
+        match self {
+            FieldIndex::GeoIndex(index) => Some(index),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::UuidMapIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::BinaryIndex(_)
+            | FieldIndex::FullTextIndex(_) => None,
+        }
+    }
+
+    pub fn as_full_text_index(&self) -> Option<&FullTextIndex> {
+        match self {
+            FieldIndex::FullTextIndex(index) => Some(index),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::UuidMapIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::BinaryIndex(_) => None,
+        }
+    }
+
+    pub fn as_facet_index(&self) -> Option<FacetIndex> {
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(FacetIndex::Keyword(index)),
+            FieldIndex::UuidMapIndex(index) => Some(FacetIndex::Uuid(index)),
+            FieldIndex::IntMapIndex(index) => Some(FacetIndex::IntMap(index)),
+            FieldIndex::IntIndex(index) => Some(FacetIndex::Int(index)),
+            FieldIndex::BinaryIndex(index) => Some(FacetIndex::Bool(index)),
+            FieldIndex::DatetimeIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_) => None,
+        }
+    }
 }
 
 pub enum NumericFieldIndex<'a> {
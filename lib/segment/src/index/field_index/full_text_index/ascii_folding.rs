@@ -0,0 +1,91 @@
+use std::borrow::Cow;
+
+use unicode_normalization::char::{decompose_compatible, is_combining_mark};
+
+/// Replace non-ASCII characters with their ASCII equivalents, e.g. "Crème Brûlée" -> "Creme Brulee"
+///
+/// Characters without an ASCII equivalent are kept as is.
+pub fn fold_to_ascii(text: &str) -> Cow<str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        fold_char(c, &mut folded);
+    }
+    Cow::Owned(folded)
+}
+
+fn fold_char(c: char, folded: &mut String) {
+    if c.is_ascii() {
+        folded.push(c);
+        return;
+    }
+
+    // Compatibility decomposition splits letters and diacritics: "é" -> "e" + U+0301
+    let mut decomposed = String::new();
+    decompose_compatible(c, |part| {
+        if !is_combining_mark(part) {
+            decomposed.push(part);
+        }
+    });
+    if !decomposed.is_empty() && decomposed.is_ascii() {
+        folded.push_str(&decomposed);
+        return;
+    }
+
+    match special_folding(c) {
+        Some(replacement) => folded.push_str(replacement),
+        None => folded.push(c),
+    }
+}
+
+/// Characters, which are not decomposed into ASCII by Unicode normalization
+fn special_folding(c: char) -> Option<&'static str> {
+    let replacement = match c {
+        'ß' => "ss",
+        'ẞ' => "SS",
+        'æ' => "ae",
+        'Æ' => "AE",
+        'œ' => "oe",
+        'Œ' => "OE",
+        'ø' => "o",
+        'Ø' => "O",
+        'đ' | 'ð' => "d",
+        'Đ' | 'Ð' => "D",
+        'ł' => "l",
+        'Ł' => "L",
+        'þ' => "th",
+        'Þ' => "TH",
+        'ı' => "i",
+        'ħ' => "h",
+        'Ħ' => "H",
+        'ŧ' => "t",
+        'Ŧ' => "T",
+        'ŋ' => "n",
+        'Ŋ' => "N",
+        'ĸ' => "q",
+        '‘' | '’' | '‚' | '‛' | '′' => "'",
+        '“' | '”' | '„' | '‟' | '″' | '«' | '»' => "\"",
+        '‐' | '‑' | '‒' | '–' | '—' | '―' | '−' => "-",
+        _ => return None,
+    };
+    Some(replacement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_to_ascii() {
+        assert!(matches!(fold_to_ascii("hello"), Cow::Borrowed("hello")));
+        assert_eq!(fold_to_ascii("Crème Brûlée"), "Creme Brulee");
+        assert_eq!(fold_to_ascii("Ærøskøbing"), "AEroskobing");
+        assert_eq!(fold_to_ascii("straße"), "strasse");
+        assert_eq!(fold_to_ascii("Łódź"), "Lodz");
+        assert_eq!(fold_to_ascii("ﬁnal"), "final");
+        // No ASCII equivalent
+        assert_eq!(fold_to_ascii("мир"), "мир");
+    }
+}
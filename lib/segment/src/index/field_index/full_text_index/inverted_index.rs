@@ -1,11 +1,13 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 
 use common::types::PointOffsetType;
 use serde::{Deserialize, Serialize};
 
+use super::mmap_inverted_index::MmapInvertedIndex;
 use super::posting_list::{CompressedPostingList, PostingList};
 use super::postings_iterator::{
-    intersect_compressed_postings_iterator, intersect_postings_iterator,
+    check_phrase_positions, intersect_compressed_postings_iterator, intersect_postings_iterator,
 };
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
@@ -13,21 +15,53 @@ use crate::types::{FieldCondition, Match, PayloadKeyType};
 
 pub type TokenId = u32;
 
+/// Positions of each token in a document, in increasing order
+///
+/// Values of the document are separated by a gap in positions, so that phrases can't span several
+/// values.
+pub type DocumentPositions = HashMap<TokenId, Vec<u32>>;
+
 #[derive(Default, Serialize, Deserialize, Debug, Clone)]
 pub struct Document {
     tokens: Vec<TokenId>,
+    /// Number of occurrences of each token in the document, in the same order as `tokens`
+    frequencies: Vec<u32>,
 }
 
 impl Document {
-    pub fn new(mut tokens: Vec<TokenId>) -> Self {
-        tokens.sort_unstable();
-        Self { tokens }
+    pub fn new(tokens: Vec<TokenId>) -> Self {
+        Self::with_frequencies(tokens.into_iter().map(|token| (token, 1)).collect())
+    }
+
+    pub fn with_frequencies(mut tokens: Vec<(TokenId, u32)>) -> Self {
+        tokens.sort_unstable_by_key(|(token, _)| *token);
+        let (tokens, frequencies) = tokens.into_iter().unzip();
+        Self {
+            tokens,
+            frequencies,
+        }
     }
 
     pub fn len(&self) -> usize {
         self.tokens.len()
     }
 
+    /// Number of tokens in the document, including repeated ones
+    pub fn length(&self) -> usize {
+        self.frequencies
+            .iter()
+            .map(|&frequency| frequency as usize)
+            .sum()
+    }
+
+    /// Number of occurrences of the token in the document
+    pub fn term_frequency(&self, token: TokenId) -> u32 {
+        match self.tokens.binary_search(&token) {
+            Ok(position) => self.frequencies[position],
+            Err(_) => 0,
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.tokens.is_empty()
     }
@@ -43,10 +77,24 @@ impl Document {
 
 #[derive(Debug)]
 pub struct ParsedQuery {
+    /// Tokens of the query, in the order of the query text for phrases
     pub tokens: Vec<Option<TokenId>>,
+    /// If true, tokens must appear in the document next to each other and in the same order
+    pub is_phrase: bool,
 }
 
 impl ParsedQuery {
+    /// Check if the phrase of the query occurs in a document with the given token positions
+    pub fn check_phrase(&self, positions: &DocumentPositions) -> bool {
+        let phrase_positions: Option<Vec<&[u32]>> = self
+            .tokens
+            .iter()
+            .map(|token| positions.get(&(*token)?).map(Vec::as_slice))
+            .collect();
+        // There are unseen tokens -> no matches
+        phrase_positions.is_some_and(|phrase_positions| check_phrase_positions(&phrase_positions))
+    }
+
     pub fn check_match(&self, document: &Document) -> bool {
         if self.tokens.contains(&None) {
             return false;
@@ -62,6 +110,7 @@ impl ParsedQuery {
 pub enum InvertedIndex {
     Mutable(MutableInvertedIndex),
     Immutable(ImmutableInvertedIndex),
+    Mmap(MmapInvertedIndex),
 }
 
 impl InvertedIndex {
@@ -73,20 +122,25 @@ impl InvertedIndex {
         }
     }
 
-    pub fn document_from_tokens(&mut self, tokens: &BTreeSet<String>) -> Document {
+    pub fn new_mmap(path: &Path) -> InvertedIndex {
+        InvertedIndex::Mmap(MmapInvertedIndex::new(path))
+    }
+
+    pub fn document_from_tokens(&mut self, tokens: &BTreeMap<String, u32>) -> Document {
         let vocab = match self {
             InvertedIndex::Mutable(index) => &mut index.vocab,
             InvertedIndex::Immutable(index) => &mut index.vocab,
+            InvertedIndex::Mmap(index) => return index.document_from_tokens(tokens),
         };
         Self::document_from_tokens_impl(vocab, tokens)
     }
 
     fn document_from_tokens_impl(
         vocab: &mut HashMap<String, TokenId>,
-        tokens: &BTreeSet<String>,
+        tokens: &BTreeMap<String, u32>,
     ) -> Document {
         let mut document_tokens = vec![];
-        for token in tokens {
+        for (token, &frequency) in tokens {
             // check if in vocab
             let vocab_idx = match vocab.get(token) {
                 Some(&idx) => idx,
@@ -96,22 +150,49 @@ impl InvertedIndex {
                     next_token_id
                 }
             };
-            document_tokens.push(vocab_idx);
+            document_tokens.push((vocab_idx, frequency));
         }
 
-        Document::new(document_tokens)
+        Document::with_frequencies(document_tokens)
+    }
+
+    /// Positions of tokens in the document, which consists of the given values
+    ///
+    /// Tokens must already be in the vocabulary, unknown ones only occupy their positions.
+    pub fn document_positions(&self, values: &[Vec<String>]) -> DocumentPositions {
+        Self::document_positions_impl(values, |token| self.get_token(token))
+    }
+
+    fn document_positions_impl(
+        values: &[Vec<String>],
+        get_token: impl Fn(&str) -> Option<TokenId>,
+    ) -> DocumentPositions {
+        let mut positions = DocumentPositions::new();
+        let mut position = 0;
+        for value in values {
+            for token in value {
+                if let Some(token_id) = get_token(token) {
+                    positions.entry(token_id).or_default().push(position);
+                }
+                position += 1;
+            }
+            // Skip a position between values, so that phrases can't span them
+            position += 1;
+        }
+        positions
     }
 
     pub fn index_document(
         &mut self,
         idx: PointOffsetType,
         document: Document,
+        positions: Option<DocumentPositions>,
     ) -> OperationResult<()> {
         match self {
-            InvertedIndex::Mutable(index) => index.index_document(idx, document),
-            InvertedIndex::Immutable(_index) => Err(OperationError::service_error(
-                "Can't add values to immutable text index",
-            )),
+            InvertedIndex::Mutable(index) => index.index_document(idx, document, positions),
+            InvertedIndex::Immutable(_) | InvertedIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable text index"),
+            ),
         }
     }
 
@@ -119,13 +200,39 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.remove_document(idx),
             InvertedIndex::Immutable(index) => index.remove_document(idx),
+            InvertedIndex::Mmap(index) => index.remove_document(idx),
         }
     }
 
     pub fn filter(&self, query: &ParsedQuery) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
-        match self {
+        let candidates = match self {
             InvertedIndex::Mutable(index) => index.filter(query),
             InvertedIndex::Immutable(index) => index.filter(query),
+            InvertedIndex::Mmap(index) => index.filter(query),
+        };
+        if !query.is_phrase {
+            return candidates;
+        }
+        // Candidates contain all tokens of the phrase, intersect their positions
+        let phrase: Vec<_> = query.tokens.iter().flatten().copied().collect();
+        Box::new(candidates.filter(move |&idx| self.check_phrase(&phrase, idx)))
+    }
+
+    /// Check if the phrase occurs in the document of the point, by positions of its tokens
+    fn check_phrase(&self, phrase: &[TokenId], idx: PointOffsetType) -> bool {
+        let phrase_positions: Option<Vec<&[u32]>> = phrase
+            .iter()
+            .map(|&token_id| self.token_positions(token_id, idx))
+            .collect();
+        phrase_positions.is_some_and(|phrase_positions| check_phrase_positions(&phrase_positions))
+    }
+
+    /// Positions of the token in the document of the point, if phrase matching is enabled
+    fn token_positions(&self, token_id: TokenId, idx: PointOffsetType) -> Option<&[u32]> {
+        match self {
+            InvertedIndex::Mutable(index) => index.token_positions(token_id, idx),
+            InvertedIndex::Immutable(index) => index.token_positions(token_id, idx),
+            InvertedIndex::Mmap(index) => index.token_positions(token_id, idx),
         }
     }
 
@@ -137,6 +244,7 @@ impl InvertedIndex {
         let points_count = match self {
             InvertedIndex::Mutable(index) => index.points_count,
             InvertedIndex::Immutable(index) => index.points_count,
+            InvertedIndex::Mmap(index) => index.points_count,
         };
         let posting_lengths: Option<Vec<usize>> = query
             .tokens
@@ -157,6 +265,7 @@ impl InvertedIndex {
                         .unwrap()
                         .as_ref()
                         .map(|p| p.len()),
+                    Self::Mmap(index) => index.posting_len(idx),
                 },
             })
             .collect();
@@ -233,12 +342,17 @@ impl InvertedIndex {
                     .vocab_with_positngs_len_iter()
                     .filter_map(map_filter_condition),
             ),
+            InvertedIndex::Mmap(index) => Box::new(
+                index
+                    .vocab_with_postings_len_iter()
+                    .filter_map(map_filter_condition),
+            ),
         }
     }
 
     pub fn build_index(
         &mut self,
-        iter: impl Iterator<Item = OperationResult<(PointOffsetType, BTreeSet<String>)>>,
+        iter: impl Iterator<Item = OperationResult<(PointOffsetType, StoredTokens)>>,
     ) -> OperationResult<()> {
         let mut index = MutableInvertedIndex::default();
         index.build_index(iter)?;
@@ -250,22 +364,35 @@ impl InvertedIndex {
             InvertedIndex::Immutable(i) => {
                 *i = index.into();
             }
+            InvertedIndex::Mmap(_) => {
+                return Err(OperationError::service_error(
+                    "Can't build on-disk text index from storage",
+                ));
+            }
         }
 
         Ok(())
     }
 
     pub fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
-        match self {
+        let has_tokens = match self {
             InvertedIndex::Mutable(index) => index.check_match(parsed_query, point_id),
             InvertedIndex::Immutable(index) => index.check_match(parsed_query, point_id),
+            InvertedIndex::Mmap(index) => index.check_match(parsed_query, point_id),
+        };
+        if !has_tokens || !parsed_query.is_phrase {
+            return has_tokens;
         }
+        // unwrap crash safety: all tokens exist in the vocabulary if the point has them
+        let phrase: Vec<_> = parsed_query.tokens.iter().map(|x| x.unwrap()).collect();
+        self.check_phrase(&phrase, point_id)
     }
 
     pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
         match self {
             InvertedIndex::Mutable(index) => index.values_is_empty(point_id),
             InvertedIndex::Immutable(index) => index.values_is_empty(point_id),
+            InvertedIndex::Mmap(index) => index.values_is_empty(point_id),
         }
     }
 
@@ -273,6 +400,7 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.values_count(point_id),
             InvertedIndex::Immutable(index) => index.values_count(point_id),
+            InvertedIndex::Mmap(index) => index.values_count(point_id),
         }
     }
 
@@ -280,6 +408,7 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.points_count,
             InvertedIndex::Immutable(index) => index.points_count,
+            InvertedIndex::Mmap(index) => index.points_count,
         }
     }
 
@@ -287,32 +416,90 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.vocab.get(token).copied(),
             InvertedIndex::Immutable(index) => index.vocab.get(token).copied(),
+            InvertedIndex::Mmap(index) => index.get_token(token),
+        }
+    }
+
+    /// Points, which contain the token, with the number of occurrences of the token in each of them
+    pub fn term_postings(
+        &self,
+        token_id: TokenId,
+    ) -> Box<dyn Iterator<Item = (PointOffsetType, u32)> + '_> {
+        match self {
+            InvertedIndex::Mutable(index) => Box::new(index.term_postings(token_id)),
+            InvertedIndex::Immutable(index) => Box::new(index.term_postings(token_id)),
+            InvertedIndex::Mmap(index) => index.term_postings(token_id),
+        }
+    }
+
+    /// Number of points, which contain the token
+    pub fn term_points_count(&self, token_id: TokenId) -> usize {
+        match self {
+            InvertedIndex::Mutable(index) => index
+                .postings
+                .get(token_id as usize)
+                .and_then(Option::as_ref)
+                .map_or(0, PostingList::len),
+            InvertedIndex::Immutable(index) => index
+                .postings
+                .get(token_id as usize)
+                .and_then(Option::as_ref)
+                .map_or(0, CompressedPostingList::len),
+            InvertedIndex::Mmap(index) => index.posting_len(token_id).unwrap_or(0),
+        }
+    }
+
+    /// Number of tokens in the document of the point, including repeated ones
+    pub fn document_length(&self, point_id: PointOffsetType) -> usize {
+        match self {
+            InvertedIndex::Mutable(index) => index.get_doc(point_id).map_or(0, Document::length),
+            InvertedIndex::Immutable(index) => index.document_length(point_id),
+            InvertedIndex::Mmap(index) => index.document_length(point_id),
+        }
+    }
+
+    /// Number of tokens in the documents of all points, including repeated ones
+    pub fn total_length(&self) -> usize {
+        match self {
+            InvertedIndex::Mutable(index) => index.total_length,
+            InvertedIndex::Immutable(index) => index.total_length,
+            InvertedIndex::Mmap(index) => index.total_length,
         }
     }
 }
 
+/// Tokens of a stored document with their number of occurrences, and tokens of each its value in
+/// the text order, if phrase matching is enabled
+pub type StoredTokens = (BTreeMap<String, u32>, Option<Vec<Vec<String>>>);
+
 #[derive(Default)]
 pub struct MutableInvertedIndex {
-    postings: Vec<Option<PostingList>>,
-    vocab: HashMap<String, TokenId>,
-    point_to_docs: Vec<Option<Document>>,
+    pub(super) postings: Vec<Option<PostingList>>,
+    pub(super) vocab: HashMap<String, TokenId>,
+    pub(super) point_to_docs: Vec<Option<Document>>,
+    /// Positions of each token in the points of its posting, only stored if phrase matching is
+    /// enabled
+    pub(super) positions: Vec<HashMap<PointOffsetType, Vec<u32>>>,
     points_count: usize,
+    total_length: usize,
 }
 
 impl MutableInvertedIndex {
     fn build_index(
         &mut self,
-        iter: impl Iterator<Item = OperationResult<(PointOffsetType, BTreeSet<String>)>>,
+        iter: impl Iterator<Item = OperationResult<(PointOffsetType, StoredTokens)>>,
     ) -> OperationResult<()> {
         self.points_count = 0;
+        self.total_length = 0;
         self.vocab.clear();
         self.postings.clear();
         self.point_to_docs.clear();
+        self.positions.clear();
 
         // update point_to_docs
         for i in iter {
             self.points_count += 1;
-            let (idx, tokens) = i?;
+            let (idx, (tokens, sequences)) = i?;
 
             if self.point_to_docs.len() <= idx as usize {
                 self.point_to_docs
@@ -320,7 +507,15 @@ impl MutableInvertedIndex {
             }
 
             let document = InvertedIndex::document_from_tokens_impl(&mut self.vocab, &tokens);
+            self.total_length += document.length();
             self.point_to_docs[idx as usize] = Some(document);
+
+            if let Some(sequences) = sequences {
+                let positions = InvertedIndex::document_positions_impl(&sequences, |token| {
+                    self.vocab.get(token).copied()
+                });
+                self.set_positions(idx, positions);
+            }
         }
 
         // build postings from point_to_docs
@@ -347,8 +542,24 @@ impl MutableInvertedIndex {
         Ok(())
     }
 
-    fn index_document(&mut self, idx: PointOffsetType, document: Document) -> OperationResult<()> {
+    fn set_positions(&mut self, idx: PointOffsetType, positions: DocumentPositions) {
+        for (token_id, token_positions) in positions {
+            if self.positions.len() <= token_id as usize {
+                self.positions
+                    .resize_with(token_id as usize + 1, Default::default);
+            }
+            self.positions[token_id as usize].insert(idx, token_positions);
+        }
+    }
+
+    fn index_document(
+        &mut self,
+        idx: PointOffsetType,
+        document: Document,
+        positions: Option<DocumentPositions>,
+    ) -> OperationResult<()> {
         self.points_count += 1;
+        self.total_length += document.length();
         if self.point_to_docs.len() <= idx as usize {
             self.point_to_docs
                 .resize_with(idx as usize + 1, Default::default);
@@ -370,6 +581,9 @@ impl MutableInvertedIndex {
             }
         }
         self.point_to_docs[idx as usize] = Some(document);
+        if let Some(positions) = positions {
+            self.set_positions(idx, positions);
+        }
         Ok(())
     }
 
@@ -384,6 +598,7 @@ impl MutableInvertedIndex {
         };
 
         self.points_count -= 1;
+        self.total_length -= removed_doc.length();
 
         for removed_token in removed_doc.tokens() {
             // unwrap safety: posting list exists and contains the document id
@@ -391,6 +606,9 @@ impl MutableInvertedIndex {
             if let Some(vec) = posting {
                 vec.remove(idx);
             }
+            if let Some(positions) = self.positions.get_mut(*removed_token as usize) {
+                positions.remove(&idx);
+            }
         }
         true
     }
@@ -439,6 +657,31 @@ impl MutableInvertedIndex {
         self.point_to_docs.get(idx as usize)?.as_ref()
     }
 
+    fn token_positions(&self, token_id: TokenId, idx: PointOffsetType) -> Option<&[u32]> {
+        self.positions
+            .get(token_id as usize)?
+            .get(&idx)
+            .map(Vec::as_slice)
+    }
+
+    fn term_postings(
+        &self,
+        token_id: TokenId,
+    ) -> impl Iterator<Item = (PointOffsetType, u32)> + '_ {
+        let posting = self
+            .postings
+            .get(token_id as usize)
+            .and_then(Option::as_ref);
+        posting.into_iter().flat_map(move |posting| {
+            posting.iter().map(move |idx| {
+                let frequency = self
+                    .get_doc(idx)
+                    .map_or(0, |doc| doc.term_frequency(token_id));
+                (idx, frequency)
+            })
+        })
+    }
+
     fn vocab_with_positngs_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
         self.vocab.iter().filter_map(|(token, &posting_idx)| {
             if let Some(Some(postings)) = self.postings.get(posting_idx as usize) {
@@ -453,9 +696,16 @@ impl MutableInvertedIndex {
 #[derive(Default)]
 pub struct ImmutableInvertedIndex {
     postings: Vec<Option<CompressedPostingList>>,
+    /// Number of occurrences of the token in each point of its posting, in the posting order
+    frequencies: Vec<Vec<u32>>,
+    /// Positions of the token in each point of its posting, in the posting order. Only stored if
+    /// phrase matching is enabled
+    positions: Vec<Vec<Vec<u32>>>,
     vocab: HashMap<String, TokenId>,
     point_documents_tokens: Vec<Option<usize>>,
+    point_to_lengths: Vec<u32>,
     points_count: usize,
+    total_length: usize,
 }
 
 impl ImmutableInvertedIndex {
@@ -463,11 +713,45 @@ impl ImmutableInvertedIndex {
         if self.values_is_empty(idx) {
             return false; // Already removed or never actually existed
         }
+        self.total_length -= self.document_length(idx);
         self.point_documents_tokens[idx as usize] = None;
         self.points_count -= 1;
         true
     }
 
+    fn document_length(&self, point_id: PointOffsetType) -> usize {
+        if self.values_is_empty(point_id) {
+            return 0;
+        }
+        self.point_to_lengths
+            .get(point_id as usize)
+            .map_or(0, |&length| length as usize)
+    }
+
+    fn token_positions(&self, token_id: TokenId, idx: PointOffsetType) -> Option<&[u32]> {
+        let positions = self.positions.get(token_id as usize)?;
+        let posting = self.postings.get(token_id as usize)?.as_ref()?;
+        let posting_idx = posting.index_of(&idx)?;
+        positions.get(posting_idx).map(Vec::as_slice)
+    }
+
+    fn term_postings(
+        &self,
+        token_id: TokenId,
+    ) -> impl Iterator<Item = (PointOffsetType, u32)> + '_ {
+        let posting = self
+            .postings
+            .get(token_id as usize)
+            .and_then(Option::as_ref);
+        let frequencies = self.frequencies.get(token_id as usize);
+        posting
+            .into_iter()
+            .zip(frequencies)
+            .flat_map(|(posting, frequencies)| posting.iter().zip(frequencies.iter().copied()))
+            // in case of immutable index, deleted documents are still in the postings
+            .filter(|&(idx, _)| !self.values_is_empty(idx))
+    }
+
     fn filter(&self, query: &ParsedQuery) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         let postings_opt: Option<Vec<_>> = query
             .tokens
@@ -544,6 +828,41 @@ impl ImmutableInvertedIndex {
 
 impl From<MutableInvertedIndex> for ImmutableInvertedIndex {
     fn from(mut index: MutableInvertedIndex) -> Self {
+        let frequencies: Vec<Vec<u32>> = index
+            .postings
+            .iter()
+            .enumerate()
+            .map(|(token_id, posting)| {
+                posting
+                    .iter()
+                    .flat_map(|posting| posting.iter())
+                    .map(|idx| {
+                        index
+                            .get_doc(idx)
+                            .map_or(0, |doc| doc.term_frequency(token_id as TokenId))
+                    })
+                    .collect()
+            })
+            .collect();
+        // Positions are only kept, if phrase matching is enabled
+        let positions: Vec<Vec<Vec<u32>>> = if index.positions.iter().all(HashMap::is_empty) {
+            vec![]
+        } else {
+            let mut token_positions = std::mem::take(&mut index.positions);
+            token_positions.resize_with(index.postings.len(), Default::default);
+            index
+                .postings
+                .iter()
+                .zip(token_positions)
+                .map(|(posting, mut token_positions)| {
+                    posting
+                        .iter()
+                        .flat_map(|posting| posting.iter())
+                        .map(|idx| token_positions.remove(&idx).unwrap_or_default())
+                        .collect()
+                })
+                .collect()
+        };
         let postings: Vec<Option<CompressedPostingList>> = index
             .postings
             .into_iter()
@@ -553,13 +872,21 @@ impl From<MutableInvertedIndex> for ImmutableInvertedIndex {
 
         ImmutableInvertedIndex {
             postings,
+            frequencies,
+            positions,
             vocab: index.vocab,
             point_documents_tokens: index
                 .point_to_docs
                 .iter()
                 .map(|doc| doc.as_ref().map(|doc| doc.len()))
                 .collect(),
+            point_to_lengths: index
+                .point_to_docs
+                .iter()
+                .map(|doc| doc.as_ref().map_or(0, |doc| doc.length() as u32))
+                .collect(),
             points_count: index.points_count,
+            total_length: index.total_length,
         }
     }
 }
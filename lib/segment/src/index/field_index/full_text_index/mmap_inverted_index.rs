@@ -0,0 +1,386 @@
+use std::collections::BTreeMap;
+use std::iter;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use itertools::Itertools;
+
+use super::inverted_index::{Document, MutableInvertedIndex, ParsedQuery, TokenId};
+use super::postings_iterator::intersect_slice_postings_iterator;
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::index::field_index::mmap_storage::{
+    remove_index_dir, write_slice, MmapDeletedFlags, MmapFlatVecs, MmapSliceReadOnly,
+};
+
+const TOKENS_COUNT_FILE: &str = "point_to_tokens_count.bin";
+const LENGTHS_FILE: &str = "point_to_lengths.bin";
+const DELETED_POINTS_FILE: &str = "deleted_points.bin";
+const VOCAB: &str = "vocab";
+const POSTINGS: &str = "postings";
+const FREQUENCIES: &str = "frequencies";
+const POSITIONS: &str = "positions";
+
+/// Inverted index, stored in memory mapped files
+///
+/// Tokens are stored sorted, so a token id is its position in the sorted vocabulary. Points
+/// without a document, as well as removed points, are marked as deleted.
+///
+/// Term frequencies are stored in the same layout as postings. Indexes built without them assume
+/// that every token occurs once. Positions of tokens are only stored if phrase matching is
+/// enabled, as a list per each entry of the postings, in the same order.
+pub struct MmapInvertedIndex {
+    path: PathBuf,
+    storage: Option<Storage>,
+    pub(super) points_count: usize,
+    pub(super) total_length: usize,
+}
+
+struct Storage {
+    vocab: MmapFlatVecs<u8>,
+    postings: MmapFlatVecs<PointOffsetType>,
+    frequencies: Option<MmapFlatVecs<u32>>,
+    point_to_tokens_count: MmapSliceReadOnly<u32>,
+    point_to_lengths: Option<MmapSliceReadOnly<u32>>,
+    positions: Option<MmapFlatVecs<u32>>,
+    deleted_points: MmapDeletedFlags,
+}
+
+impl MmapInvertedIndex {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_owned(),
+            storage: None,
+            points_count: 0,
+            total_length: 0,
+        }
+    }
+
+    /// Write the content of a mutable index into `path`
+    pub fn build(path: &Path, index: &MutableInvertedIndex) -> OperationResult<()> {
+        std::fs::create_dir_all(path)?;
+
+        let vocab = index
+            .vocab
+            .iter()
+            .sorted_unstable_by(|(a, _), (b, _)| a.cmp(b))
+            .collect_vec();
+        MmapFlatVecs::create(path, VOCAB, vocab.iter().map(|(token, _)| token.as_bytes()))?;
+
+        let postings = vocab
+            .iter()
+            .map(
+                |(_, &token_id)| match index.postings.get(token_id as usize) {
+                    Some(Some(posting)) => posting.iter().collect_vec(),
+                    _ => vec![],
+                },
+            )
+            .collect_vec();
+        MmapFlatVecs::create(path, POSTINGS, postings.iter().map(Vec::as_slice))?;
+
+        let frequencies = vocab
+            .iter()
+            .zip(&postings)
+            .map(|((_, &token_id), posting)| {
+                posting
+                    .iter()
+                    .map(|&idx| {
+                        index.point_to_docs[idx as usize]
+                            .as_ref()
+                            .map_or(0, |doc| doc.term_frequency(token_id))
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+        MmapFlatVecs::create(path, FREQUENCIES, frequencies.iter().map(Vec::as_slice))?;
+
+        if index
+            .positions
+            .iter()
+            .any(|positions| !positions.is_empty())
+        {
+            let positions = vocab
+                .iter()
+                .zip(&postings)
+                .flat_map(|((_, &token_id), posting)| {
+                    let token_positions = index.positions.get(token_id as usize);
+                    posting.iter().map(move |idx| {
+                        token_positions
+                            .and_then(|token_positions| token_positions.get(idx))
+                            .map_or(&[][..], Vec::as_slice)
+                    })
+                });
+            MmapFlatVecs::create(path, POSITIONS, positions)?;
+        }
+
+        let point_to_tokens_count = index
+            .point_to_docs
+            .iter()
+            .map(|doc| doc.as_ref().map_or(0, |doc| doc.len() as u32))
+            .collect_vec();
+        write_slice(&path.join(TOKENS_COUNT_FILE), &point_to_tokens_count)?;
+
+        let point_to_lengths = index
+            .point_to_docs
+            .iter()
+            .map(|doc| doc.as_ref().map_or(0, |doc| doc.length() as u32))
+            .collect_vec();
+        write_slice(&path.join(LENGTHS_FILE), &point_to_lengths)?;
+
+        let deleted_path = path.join(DELETED_POINTS_FILE);
+        MmapDeletedFlags::create(&deleted_path, index.point_to_docs.len())?;
+        if let Some(mut deleted_points) = MmapDeletedFlags::open(&deleted_path)? {
+            for (idx, doc) in index.point_to_docs.iter().enumerate() {
+                if doc.is_none() {
+                    deleted_points.delete(idx);
+                }
+            }
+            deleted_points.flusher()()?;
+        }
+        Ok(())
+    }
+
+    pub fn load(&mut self) -> OperationResult<bool> {
+        let (Some(vocab), Some(postings), Some(point_to_tokens_count), Some(deleted_points)) = (
+            MmapFlatVecs::open(&self.path, VOCAB)?,
+            MmapFlatVecs::open(&self.path, POSTINGS)?,
+            MmapSliceReadOnly::open(&self.path.join(TOKENS_COUNT_FILE))?,
+            MmapDeletedFlags::open(&self.path.join(DELETED_POINTS_FILE))?,
+        ) else {
+            return Ok(false);
+        };
+
+        let frequencies = MmapFlatVecs::open(&self.path, FREQUENCIES)?;
+        let point_to_lengths = MmapSliceReadOnly::open(&self.path.join(LENGTHS_FILE))?;
+        let positions = MmapFlatVecs::open(&self.path, POSITIONS)?;
+
+        self.points_count = point_to_tokens_count.as_slice().len() - deleted_points.deleted_count();
+        let storage = Storage {
+            vocab,
+            postings,
+            frequencies,
+            point_to_tokens_count,
+            point_to_lengths,
+            positions,
+            deleted_points,
+        };
+        self.total_length = (0..storage.point_to_tokens_count.as_slice().len())
+            .map(|idx| storage.document_length(idx as PointOffsetType))
+            .sum();
+        self.storage = Some(storage);
+        Ok(true)
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files = vec![
+            self.path.join(TOKENS_COUNT_FILE),
+            self.path.join(DELETED_POINTS_FILE),
+        ];
+        files.extend(MmapFlatVecs::<u8>::files(&self.path, VOCAB));
+        files.extend(MmapFlatVecs::<PointOffsetType>::files(&self.path, POSTINGS));
+        let Some(storage) = &self.storage else {
+            return files;
+        };
+        if storage.frequencies.is_some() {
+            files.extend(MmapFlatVecs::<u32>::files(&self.path, FREQUENCIES));
+        }
+        if storage.point_to_lengths.is_some() {
+            files.push(self.path.join(LENGTHS_FILE));
+        }
+        if storage.positions.is_some() {
+            files.extend(MmapFlatVecs::<u32>::files(&self.path, POSITIONS));
+        }
+        files
+    }
+
+    pub fn clear(self) -> OperationResult<()> {
+        remove_index_dir(&self.path)
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        match &self.storage {
+            Some(storage) => storage.deleted_points.flusher(),
+            None => Box::new(|| Ok(())),
+        }
+    }
+
+    pub fn get_token(&self, token: &str) -> Option<TokenId> {
+        let storage = self.storage.as_ref()?;
+        let (mut left, mut right) = (0, storage.vocab.len());
+        while left < right {
+            let mid = left + (right - left) / 2;
+            match storage.vocab.get(mid)?.cmp(token.as_bytes()) {
+                std::cmp::Ordering::Less => left = mid + 1,
+                std::cmp::Ordering::Greater => right = mid,
+                std::cmp::Ordering::Equal => return Some(mid as TokenId),
+            }
+        }
+        None
+    }
+
+    /// Document of known tokens only, new tokens can't be added to the vocabulary
+    pub fn document_from_tokens(&self, tokens: &BTreeMap<String, u32>) -> Document {
+        Document::with_frequencies(
+            tokens
+                .iter()
+                .filter_map(|(token, &frequency)| Some((self.get_token(token)?, frequency)))
+                .collect(),
+        )
+    }
+
+    /// Positions of the token in the document of the point, if phrase matching is enabled
+    pub fn token_positions(&self, token_id: TokenId, idx: PointOffsetType) -> Option<&[u32]> {
+        let storage = self.storage.as_ref()?;
+        if storage.deleted_points.is_deleted(idx as usize) {
+            return None;
+        }
+        let positions = storage.positions.as_ref()?;
+        let posting = storage.postings.get(token_id as usize)?;
+        let posting_idx = posting.binary_search(&idx).ok()?;
+        // Positions are stored per each entry of the postings of all tokens
+        positions.get(storage.postings.offset(token_id as usize)? + posting_idx)
+    }
+
+    pub fn posting_len(&self, token_id: TokenId) -> Option<usize> {
+        let storage = self.storage.as_ref()?;
+        storage
+            .postings
+            .get(token_id as usize)
+            .map(|posting| posting.len())
+    }
+
+    pub fn remove_document(&mut self, idx: PointOffsetType) -> bool {
+        let Some(storage) = &mut self.storage else {
+            return false;
+        };
+        if idx as usize >= storage.point_to_tokens_count.as_slice().len() {
+            return false; // Never actually existed
+        }
+        let length = storage.document_length(idx);
+        if !storage.deleted_points.delete(idx as usize) {
+            return false; // Already removed
+        }
+        self.points_count -= 1;
+        self.total_length -= length;
+        true
+    }
+
+    /// Number of tokens in the document of the point, including repeated ones
+    pub fn document_length(&self, idx: PointOffsetType) -> usize {
+        self.storage
+            .as_ref()
+            .map_or(0, |storage| storage.document_length(idx))
+    }
+
+    /// Points, which contain the token, with the number of occurrences of the token in each of them
+    pub fn term_postings(
+        &self,
+        token_id: TokenId,
+    ) -> Box<dyn Iterator<Item = (PointOffsetType, u32)> + '_> {
+        let Some(storage) = &self.storage else {
+            return Box::new(iter::empty());
+        };
+        let Some(posting) = storage.postings.get(token_id as usize) else {
+            return Box::new(iter::empty());
+        };
+        let frequencies = storage
+            .frequencies
+            .as_ref()
+            .and_then(|frequencies| frequencies.get(token_id as usize));
+        let postings = posting.iter().copied().enumerate().map(move |(i, idx)| {
+            let frequency = frequencies.map_or(1, |frequencies| frequencies[i]);
+            (idx, frequency)
+        });
+        // Removed documents are still in the postings
+        Box::new(postings.filter(|&(idx, _)| !storage.deleted_points.is_deleted(idx as usize)))
+    }
+
+    pub fn filter(&self, query: &ParsedQuery) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let Some(storage) = &self.storage else {
+            return Box::new(iter::empty());
+        };
+        let postings_opt: Option<Vec<_>> = query
+            .tokens
+            .iter()
+            .map(|&vocab_idx| storage.postings.get(vocab_idx? as usize))
+            .collect();
+        let Some(postings) = postings_opt else {
+            // There are unseen tokens -> no matches
+            return Box::new(iter::empty());
+        };
+        if postings.is_empty() {
+            // Empty request -> no matches
+            return Box::new(iter::empty());
+        }
+
+        // Removed documents are still in the postings
+        let filter = move |idx: PointOffsetType| !storage.deleted_points.is_deleted(idx as usize);
+        intersect_slice_postings_iterator(postings, filter)
+    }
+
+    pub fn values_count(&self, point_id: PointOffsetType) -> usize {
+        let Some(storage) = &self.storage else {
+            return 0;
+        };
+        if storage.deleted_points.is_deleted(point_id as usize) {
+            return 0;
+        }
+        storage
+            .point_to_tokens_count
+            .as_slice()
+            .get(point_id as usize)
+            .map_or(0, |&count| count as usize)
+    }
+
+    pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
+        self.values_count(point_id) == 0
+    }
+
+    pub fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
+        let Some(storage) = &self.storage else {
+            return false;
+        };
+        if parsed_query.tokens.contains(&None) {
+            return false;
+        }
+        // check presence of the document
+        if storage.deleted_points.is_deleted(point_id as usize) {
+            return false;
+        }
+        // Check that all tokens are in document
+        parsed_query.tokens.iter().all(|query_token| {
+            query_token
+                .and_then(|token_id| storage.postings.get(token_id as usize))
+                .is_some_and(|posting| posting.binary_search(&point_id).is_ok())
+        })
+    }
+
+    pub fn vocab_with_postings_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.storage.iter().flat_map(|storage| {
+            storage
+                .vocab
+                .iter()
+                .zip(storage.postings.iter())
+                .filter(|(_, posting)| !posting.is_empty())
+                .filter_map(|(token, posting)| {
+                    Some((std::str::from_utf8(token).ok()?, posting.len()))
+                })
+        })
+    }
+}
+
+impl Storage {
+    fn document_length(&self, idx: PointOffsetType) -> usize {
+        if self.deleted_points.is_deleted(idx as usize) {
+            return 0;
+        }
+        let lengths = self
+            .point_to_lengths
+            .as_ref()
+            .unwrap_or(&self.point_to_tokens_count);
+        lengths
+            .as_slice()
+            .get(idx as usize)
+            .map_or(0, |&length| length as usize)
+    }
+}
@@ -1,8 +1,11 @@
+mod ascii_folding;
 mod inverted_index;
+mod mmap_inverted_index;
 mod posting_list;
 mod postings_iterator;
+mod stop_words;
 pub mod text_index;
-mod tokenizers;
+pub(crate) mod tokenizers;
 
 #[cfg(test)]
 mod tests;
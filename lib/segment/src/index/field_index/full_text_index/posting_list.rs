@@ -137,6 +137,31 @@ impl CompressedPostingList {
         self.chunks.len() * BitPackerImpl::BLOCK_LEN + self.reminder_postings.len()
     }
 
+    /// Index of the value in the posting list, if it is present
+    pub fn index_of(&self, val: &PointOffsetType) -> Option<usize> {
+        if !self.is_in_postings_range(*val) {
+            return None;
+        }
+
+        match self.find_chunk(val, None) {
+            Some(chunk_index) => {
+                let chunk_start = chunk_index * BitPackerImpl::BLOCK_LEN;
+                if self.chunks[chunk_index].initial == *val {
+                    return Some(chunk_start);
+                }
+
+                let mut decompressed = [0u32; BitPackerImpl::BLOCK_LEN];
+                self.decompress_chunk(&BitPackerImpl::new(), chunk_index, &mut decompressed);
+                let idx = decompressed.binary_search(val).ok()?;
+                Some(chunk_start + idx)
+            }
+            None => {
+                let idx = self.reminder_postings.binary_search(val).ok()?;
+                Some(self.chunks.len() * BitPackerImpl::BLOCK_LEN + idx)
+            }
+        }
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
         let bitpacker = BitPackerImpl::new();
         (0..self.chunks.len())
@@ -344,6 +369,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compressed_posting_index_of() {
+        for step in 1..3 {
+            let (compressed_posting_list, _) = generate_compressed_posting_list(step);
+            let values: Vec<_> = compressed_posting_list.iter().collect();
+            for i in 0..step * 1000 {
+                assert_eq!(
+                    compressed_posting_list.index_of(&i),
+                    values.binary_search(&i).ok(),
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_compressed_posting_visitor() {
         for build_step in 0..3 {
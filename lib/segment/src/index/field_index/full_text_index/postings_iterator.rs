@@ -49,6 +49,65 @@ pub fn intersect_compressed_postings_iterator<'a>(
     Box::new(and_iter)
 }
 
+/// Intersect sorted posting lists, stored as plain slices
+pub fn intersect_slice_postings_iterator<'a>(
+    mut postings: Vec<&'a [PointOffsetType]>,
+    filter: impl Fn(PointOffsetType) -> bool + 'a,
+) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
+    let smallest_posting_idx = postings
+        .iter()
+        .enumerate()
+        .min_by_key(|(_idx, posting)| posting.len())
+        .map(|(idx, _posting)| idx)
+        .unwrap();
+    let smallest_posting = postings.remove(smallest_posting_idx);
+
+    let and_iter = smallest_posting
+        .iter()
+        .copied()
+        .filter(move |doc_id| filter(*doc_id))
+        .filter(move |doc_id| {
+            postings
+                .iter()
+                .all(|posting| posting.binary_search(doc_id).is_ok())
+        });
+
+    Box::new(and_iter)
+}
+
+/// Check if the phrase occurs in a document, given positions of each phrase token in it
+///
+/// Phrase occurs at position `p`, if its i-th token is at position `p + i` of the document.
+/// Sorted positions of each phrase token are intersected one by one, shifted by the token offset.
+pub fn check_phrase_positions(phrase_positions: &[&[u32]]) -> bool {
+    let Some((first_positions, other_positions)) = phrase_positions.split_first() else {
+        return false;
+    };
+
+    let mut phrase_starts: Vec<u32> = first_positions.to_vec();
+    for (offset, positions) in other_positions.iter().enumerate() {
+        if phrase_starts.is_empty() {
+            break;
+        }
+        let offset = offset as u32 + 1;
+        let mut starts = phrase_starts.iter().copied().peekable();
+        let mut intersection = Vec::with_capacity(phrase_starts.len());
+        // Both lists are sorted, so merge them
+        for start in positions
+            .iter()
+            .filter(|&&position| position >= offset)
+            .map(|&position| position - offset)
+        {
+            while starts.next_if(|&candidate| candidate < start).is_some() {}
+            if starts.next_if_eq(&start).is_some() {
+                intersection.push(start);
+            }
+        }
+        phrase_starts = intersection;
+    }
+    !phrase_starts.is_empty()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,4 +149,24 @@ mod tests {
 
         assert_eq!(res, vec![2, 5]);
     }
+
+    #[test]
+    fn test_check_phrase_positions() {
+        // Document "1 2 3 1 2 4 5", positions of each token
+        let positions: [&[u32]; 6] = [&[], &[0, 3], &[1, 4], &[2], &[5], &[6]];
+        let check = |phrase: &[usize]| {
+            let phrase_positions: Vec<_> = phrase.iter().map(|&token| positions[token]).collect();
+            check_phrase_positions(&phrase_positions)
+        };
+        assert!(check(&[1]));
+        assert!(check(&[1, 2]));
+        assert!(check(&[1, 2, 4]));
+        assert!(check(&[2, 3, 1, 2]));
+        assert!(check(&[1, 2, 3, 1, 2, 4, 5]));
+        assert!(!check(&[2, 1]));
+        assert!(!check(&[1, 3]));
+        assert!(!check(&[5, 0]));
+        assert!(!check(&[]));
+        assert!(!check(&[0]));
+    }
 }
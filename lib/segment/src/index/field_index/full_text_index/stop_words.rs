@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+
+use crate::data_types::text_index::{Language, StopwordsInterface};
+
+/// Built-in stopwords of the language, one word per line
+fn builtin_stopwords(language: Language) -> &'static str {
+    match language {
+        Language::Danish => include_str!("stop_words/danish.txt"),
+        Language::Dutch => include_str!("stop_words/dutch.txt"),
+        Language::English => include_str!("stop_words/english.txt"),
+        Language::Finnish => include_str!("stop_words/finnish.txt"),
+        Language::French => include_str!("stop_words/french.txt"),
+        Language::German => include_str!("stop_words/german.txt"),
+        Language::Italian => include_str!("stop_words/italian.txt"),
+        Language::Norwegian => include_str!("stop_words/norwegian.txt"),
+        Language::Portuguese => include_str!("stop_words/portuguese.txt"),
+        Language::Russian => include_str!("stop_words/russian.txt"),
+        Language::Spanish => include_str!("stop_words/spanish.txt"),
+        Language::Swedish => include_str!("stop_words/swedish.txt"),
+    }
+}
+
+/// Set of stopwords, which are excluded from tokens
+///
+/// Stopwords are normalized the same way as tokens, so they are matched regardless of the case or
+/// diacritics, if the tokenizer is configured to ignore them.
+#[derive(Debug)]
+pub struct StopwordsFilter {
+    stopwords: HashSet<String>,
+}
+
+impl StopwordsFilter {
+    pub fn new<N: Fn(&str) -> String>(config: Option<&StopwordsInterface>, normalize: N) -> Self {
+        let (languages, custom): (&[Language], &[String]) = match config {
+            None => (&[], &[]),
+            Some(StopwordsInterface::Language(language)) => (std::slice::from_ref(language), &[]),
+            Some(StopwordsInterface::Set(set)) => (&set.languages, &set.custom),
+        };
+
+        let builtin = languages
+            .iter()
+            .flat_map(|&language| builtin_stopwords(language).lines());
+        let stopwords = builtin
+            .chain(custom.iter().map(String::as_str))
+            .map(str::trim)
+            .filter(|word| !word.is_empty())
+            .map(normalize)
+            .collect();
+
+        Self { stopwords }
+    }
+
+    pub fn is_stopword(&self, token: &str) -> bool {
+        self.stopwords.contains(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::text_index::StopwordsSet;
+
+    #[test]
+    fn test_builtin_stopwords() {
+        let config = StopwordsInterface::Language(Language::English);
+        let filter = StopwordsFilter::new(Some(&config), str::to_owned);
+        assert!(filter.is_stopword("the"));
+        assert!(filter.is_stopword("don't"));
+        assert!(!filter.is_stopword("run"));
+        // Normalization is up to the tokenizer
+        assert!(!filter.is_stopword("The"));
+    }
+
+    #[test]
+    fn test_stopwords_set() {
+        let config = StopwordsInterface::Set(StopwordsSet {
+            languages: vec![Language::English, Language::German],
+            custom: vec!["Qdrant".to_owned(), " ".to_owned()],
+        });
+        let filter = StopwordsFilter::new(Some(&config), str::to_lowercase);
+        assert!(filter.is_stopword("the"));
+        assert!(filter.is_stopword("und"));
+        assert!(filter.is_stopword("qdrant"));
+        assert!(!filter.is_stopword(""));
+        assert!(!filter.is_stopword("vector"));
+
+        // Every built-in list is available
+        for language in [
+            Language::Danish,
+            Language::Dutch,
+            Language::English,
+            Language::Finnish,
+            Language::French,
+            Language::German,
+            Language::Italian,
+            Language::Norwegian,
+            Language::Portuguese,
+            Language::Russian,
+            Language::Spanish,
+            Language::Swedish,
+        ] {
+            let config = StopwordsInterface::Language(language);
+            let filter = StopwordsFilter::new(Some(&config), str::to_owned);
+            assert!(!filter.stopwords.is_empty());
+        }
+    }
+}
@@ -161,6 +161,11 @@ fn test_prefix_search(#[case] immutable: bool) {
         min_token_len: None,
         max_token_len: None,
         lowercase: None,
+        on_disk: None,
+        stopwords: None,
+        stemmer: None,
+        ascii_folding: None,
+        phrase_matching: None,
     };
 
     let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
@@ -1,7 +1,9 @@
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::iter;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use common::types::PointOffsetType;
+use common::types::{PointOffsetType, ScoreType};
 use parking_lot::RwLock;
 use rocksdb::DB;
 use serde::{Deserialize, Serialize};
@@ -11,9 +13,11 @@ use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::data_types::text_index::TextIndexParams;
+use crate::data_types::text_query::TextQueryStatistics;
 use crate::index::field_index::full_text_index::inverted_index::{
-    Document, InvertedIndex, ParsedQuery,
+    Document, DocumentPositions, InvertedIndex, ParsedQuery, StoredTokens,
 };
+use crate::index::field_index::full_text_index::mmap_inverted_index::MmapInvertedIndex;
 use crate::index::field_index::full_text_index::tokenizers::Tokenizer;
 use crate::index::field_index::{
     CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, ValueIndexer,
@@ -23,8 +27,11 @@ use crate::types::{FieldCondition, Match, PayloadKeyType};
 
 pub struct FullTextIndex {
     inverted_index: InvertedIndex,
-    db_wrapper: DatabaseColumnWrapper,
-    config: TextIndexParams,
+    /// On-disk index keeps its documents in the mmap files, and has no RocksDB storage
+    db_wrapper: Option<DatabaseColumnWrapper>,
+    tokenizer: Tokenizer,
+    /// If true, positions of tokens in documents are stored for phrase matching
+    phrase_matching: bool,
 }
 
 impl FullTextIndex {
@@ -36,27 +43,48 @@ impl FullTextIndex {
         bincode::deserialize(data).unwrap()
     }
 
-    fn serialize_document_tokens(&self, tokens: BTreeSet<String>) -> OperationResult<Vec<u8>> {
+    fn serialize_document_tokens(
+        &self,
+        tokens: BTreeMap<String, u32>,
+        sequences: Option<Vec<Vec<String>>>,
+    ) -> OperationResult<Vec<u8>> {
         #[derive(Serialize)]
         struct StoredDocument {
-            tokens: BTreeSet<String>,
+            tokens: Vec<String>,
+            frequencies: Vec<u32>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            sequences: Option<Vec<Vec<String>>>,
         }
-        let doc = StoredDocument { tokens };
+        let (tokens, frequencies) = tokens.into_iter().unzip();
+        let doc = StoredDocument {
+            tokens,
+            frequencies,
+            sequences,
+        };
         serde_cbor::to_vec(&doc).map_err(|e| {
             OperationError::service_error(format!("Failed to serialize document: {e}"))
         })
     }
 
-    fn deserialize_document(data: &[u8]) -> OperationResult<BTreeSet<String>> {
+    fn deserialize_document(data: &[u8]) -> OperationResult<StoredTokens> {
         #[derive(Deserialize)]
         struct StoredDocument {
             tokens: BTreeSet<String>,
+            /// Documents stored without frequencies are considered to have every token once
+            #[serde(default)]
+            frequencies: Vec<u32>,
+            #[serde(default)]
+            sequences: Option<Vec<Vec<String>>>,
         }
         serde_cbor::from_slice::<StoredDocument>(data)
             .map_err(|e| {
                 OperationError::service_error(format!("Failed to deserialize document: {e}"))
             })
-            .map(|doc| doc.tokens)
+            .map(|doc| {
+                let frequencies = doc.frequencies.into_iter().chain(iter::repeat(1));
+                let tokens = doc.tokens.into_iter().zip(frequencies).collect();
+                (tokens, doc.sequences)
+            })
     }
 
     fn storage_cf_name(field: &str) -> String {
@@ -73,8 +101,58 @@ impl FullTextIndex {
         let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
         FullTextIndex {
             inverted_index: InvertedIndex::new(is_appendable),
-            db_wrapper,
-            config,
+            db_wrapper: Some(db_wrapper),
+            tokenizer: Tokenizer::new(&config),
+            phrase_matching: config.phrase_matching.unwrap_or(false),
+        }
+    }
+
+    pub fn new_mmap(path: &Path, config: TextIndexParams) -> Self {
+        FullTextIndex {
+            inverted_index: InvertedIndex::new_mmap(path),
+            db_wrapper: None,
+            tokenizer: Tokenizer::new(&config),
+            phrase_matching: config.phrase_matching.unwrap_or(false),
+        }
+    }
+
+    /// Convert a mutable index into an on-disk one, stored in `path`
+    ///
+    /// RocksDB storage of the index is removed afterwards.
+    pub fn into_mmap(self, path: &Path) -> OperationResult<Self> {
+        let FullTextIndex {
+            inverted_index: InvertedIndex::Mutable(index),
+            db_wrapper: Some(db_wrapper),
+            tokenizer,
+            phrase_matching,
+        } = self
+        else {
+            return Err(OperationError::service_error(
+                "Only mutable text index can be converted into on-disk index",
+            ));
+        };
+        MmapInvertedIndex::build(path, &index)?;
+        db_wrapper.remove_column_family()?;
+
+        let mut mmap_index = MmapInvertedIndex::new(path);
+        if !mmap_index.load()? {
+            return Err(OperationError::service_error(format!(
+                "Failed to load on-disk text index from {}",
+                path.display(),
+            )));
+        }
+        Ok(FullTextIndex {
+            inverted_index: InvertedIndex::Mmap(mmap_index),
+            db_wrapper: None,
+            tokenizer,
+            phrase_matching,
+        })
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        match &self.inverted_index {
+            InvertedIndex::Mmap(index) => index.files(),
+            InvertedIndex::Mutable(_) | InvertedIndex::Immutable(_) => vec![],
         }
     }
 
@@ -88,22 +166,151 @@ impl FullTextIndex {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
-        self.db_wrapper.recreate_column_family()
+        match &self.db_wrapper {
+            Some(db_wrapper) => db_wrapper.recreate_column_family(),
+            None => Ok(()),
+        }
     }
 
     pub fn parse_query(&self, text: &str) -> ParsedQuery {
         let mut tokens = HashSet::new();
-        Tokenizer::tokenize_query(text, &self.config, |token| {
+        self.tokenizer.tokenize_query(text, |token| {
             tokens.insert(self.inverted_index.get_token(token));
         });
         ParsedQuery {
             tokens: tokens.into_iter().collect(),
+            is_phrase: false,
+        }
+    }
+
+    /// Parse the phrase, tokens are kept in the order of the text
+    pub fn parse_phrase(&self, phrase: &str) -> ParsedQuery {
+        let mut tokens = vec![];
+        self.tokenizer.tokenize_query(phrase, |token| {
+            tokens.push(self.inverted_index.get_token(token));
+        });
+        ParsedQuery {
+            tokens,
+            is_phrase: true,
+        }
+    }
+
+    /// Positions of tokens in the text, in which phrases are searched
+    ///
+    /// Positions are given to query tokens, so for the prefix tokenizer those are words truncated
+    /// to the max token length. Unknown tokens only occupy their positions.
+    pub fn parse_positions(&self, text: &str) -> DocumentPositions {
+        let mut sequence = vec![];
+        self.tokenizer.tokenize_query(text, |token| {
+            sequence.push(token.to_owned());
+        });
+        self.inverted_index.document_positions(&[sequence])
+    }
+
+    /// If false, phrases can't be matched by the index, and the payload has to be checked instead
+    pub fn supports_phrase_matching(&self) -> bool {
+        self.phrase_matching
+    }
+
+    /// Statistics of the indexed documents for the tokens of the text query, needed for BM25
+    pub fn text_query_statistics(&self, text: &str) -> TextQueryStatistics {
+        let mut term_points_count = HashMap::new();
+        self.tokenizer.tokenize_query(text, |token| {
+            let points_count = self.inverted_index.get_token(token).map_or(0, |token_id| {
+                self.inverted_index.term_points_count(token_id)
+            });
+            term_points_count.insert(token.to_owned(), points_count);
+        });
+        TextQueryStatistics {
+            points_count: self.inverted_index.points_count(),
+            total_length: self.inverted_index.total_length(),
+            term_points_count,
+        }
+    }
+
+    /// Statistics of the documents of the given points only, for the tokens of the text query
+    pub fn points_text_query_statistics(
+        &self,
+        text: &str,
+        points: impl IntoIterator<Item = PointOffsetType>,
+    ) -> TextQueryStatistics {
+        let mut terms = HashMap::new();
+        self.tokenizer.tokenize_query(text, |token| {
+            terms.insert(token.to_owned(), self.inverted_index.get_token(token));
+        });
+
+        let mut statistics = TextQueryStatistics {
+            points_count: 0,
+            total_length: 0,
+            term_points_count: terms.keys().map(|term| (term.clone(), 0)).collect(),
+        };
+        for idx in points {
+            if self.inverted_index.values_is_empty(idx) {
+                continue;
+            }
+            statistics.points_count += 1;
+            statistics.total_length += self.inverted_index.document_length(idx);
+            for (term, &token_id) in &terms {
+                let Some(token_id) = token_id else {
+                    continue;
+                };
+                let term_query = ParsedQuery {
+                    tokens: vec![Some(token_id)],
+                    is_phrase: false,
+                };
+                if self.inverted_index.check_match(&term_query, idx) {
+                    *statistics
+                        .term_points_count
+                        .entry(term.clone())
+                        .or_default() += 1;
+                }
+            }
+        }
+        statistics
+    }
+
+    /// BM25 scores of the points, which contain any token of the text query
+    ///
+    /// `statistics` may include other segments, so that the scores are comparable between them.
+    pub fn score_text_query(
+        &self,
+        text: &str,
+        statistics: &TextQueryStatistics,
+    ) -> HashMap<PointOffsetType, ScoreType> {
+        let mut terms = HashSet::new();
+        self.tokenizer.tokenize_query(text, |token| {
+            terms.insert(token.to_owned());
+        });
+
+        let mut scores: HashMap<PointOffsetType, ScoreType> = HashMap::new();
+        for term in terms {
+            let Some(token_id) = self.inverted_index.get_token(&term) else {
+                continue;
+            };
+            let idf = statistics.idf(&term);
+            for (idx, term_frequency) in self.inverted_index.term_postings(token_id) {
+                let document_length = self.inverted_index.document_length(idx);
+                *scores.entry(idx).or_default() +=
+                    statistics.term_score(idf, term_frequency, document_length);
+            }
+        }
+        scores
+    }
+
+    /// Parsed query of the full-text condition, if the condition can be served by the index
+    fn parse_condition(&self, condition: &FieldCondition) -> Option<ParsedQuery> {
+        match &condition.r#match {
+            Some(Match::Text(text_match)) => Some(self.parse_query(&text_match.text)),
+            Some(Match::Phrase(phrase_match)) if self.phrase_matching => {
+                Some(self.parse_phrase(&phrase_match.phrase))
+            }
+            _ => None,
         }
     }
 
     pub fn parse_document(&self, text: &str) -> Document {
         let mut document_tokens = vec![];
-        Tokenizer::tokenize_doc(text, &self.config, |token| {
+        self.tokenizer.tokenize_doc(text, |token| {
             if let Some(token_id) = self.inverted_index.get_token(token) {
                 document_tokens.push(token_id);
             }
@@ -136,21 +343,35 @@ impl ValueIndexer<String> for FullTextIndex {
             return Ok(());
         }
 
-        let mut tokens: BTreeSet<String> = BTreeSet::new();
+        let mut tokens: BTreeMap<String, u32> = BTreeMap::new();
+        let mut sequences: Vec<Vec<String>> = vec![];
 
         for value in values {
-            Tokenizer::tokenize_doc(&value, &self.config, |token| {
-                tokens.insert(token.to_owned());
+            self.tokenizer.tokenize_doc(&value, |token| {
+                *tokens.entry(token.to_owned()).or_default() += 1;
             });
+            if self.phrase_matching {
+                let mut sequence = vec![];
+                self.tokenizer.tokenize_query(&value, |token| {
+                    sequence.push(token.to_owned());
+                });
+                sequences.push(sequence);
+            }
         }
 
         let document = self.inverted_index.document_from_tokens(&tokens);
-        self.inverted_index.index_document(idx, document)?;
-
-        let db_idx = Self::store_key(&idx);
-        let db_document = self.serialize_document_tokens(tokens)?;
-
-        self.db_wrapper.put(db_idx, db_document)?;
+        let sequences = self.phrase_matching.then_some(sequences);
+        let positions = sequences
+            .as_ref()
+            .map(|sequences| self.inverted_index.document_positions(sequences));
+        self.inverted_index
+            .index_document(idx, document, positions)?;
+
+        if let Some(db_wrapper) = &self.db_wrapper {
+            let db_idx = Self::store_key(&idx);
+            let db_document = self.serialize_document_tokens(tokens, sequences)?;
+            db_wrapper.put(db_idx, db_document)?;
+        }
 
         Ok(())
     }
@@ -164,8 +385,10 @@ impl ValueIndexer<String> for FullTextIndex {
 
     fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
         if self.inverted_index.remove_document(id) {
-            let db_doc_id = Self::store_key(&id);
-            self.db_wrapper.remove(db_doc_id)?;
+            if let Some(db_wrapper) = &self.db_wrapper {
+                let db_doc_id = Self::store_key(&id);
+                db_wrapper.remove(db_doc_id)?;
+            }
         }
         Ok(())
     }
@@ -177,11 +400,16 @@ impl PayloadFieldIndex for FullTextIndex {
     }
 
     fn load(&mut self) -> OperationResult<bool> {
-        if !self.db_wrapper.has_column_family()? {
+        let db_wrapper = match (&mut self.inverted_index, &self.db_wrapper) {
+            (InvertedIndex::Mmap(index), _) => return index.load(),
+            (_, Some(db_wrapper)) => db_wrapper,
+            (_, None) => return Ok(false),
+        };
+        if !db_wrapper.has_column_family()? {
             return Ok(false);
         };
 
-        let db = self.db_wrapper.lock_db();
+        let db = db_wrapper.lock_db();
         let i = db.iter()?.map(|(key, value)| {
             let idx = Self::restore_key(&key);
             let tokens = Self::deserialize_document(&value)?;
@@ -193,19 +421,26 @@ impl PayloadFieldIndex for FullTextIndex {
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.db_wrapper.remove_column_family()
+        match (self.inverted_index, self.db_wrapper) {
+            (InvertedIndex::Mmap(index), _) => index.clear(),
+            (_, Some(db_wrapper)) => db_wrapper.remove_column_family(),
+            (_, None) => Ok(()),
+        }
     }
 
     fn flusher(&self) -> Flusher {
-        self.db_wrapper.flusher()
+        match (&self.inverted_index, &self.db_wrapper) {
+            (InvertedIndex::Mmap(index), _) => index.flusher(),
+            (_, Some(db_wrapper)) => db_wrapper.flusher(),
+            (_, None) => Box::new(|| Ok(())),
+        }
     }
 
     fn filter(
         &self,
         condition: &FieldCondition,
     ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
-        if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
+        if let Some(parsed_query) = self.parse_condition(condition) {
             return Ok(self.inverted_index.filter(&parsed_query));
         }
         Err(OperationError::service_error("failed to filter"))
@@ -215,8 +450,7 @@ impl PayloadFieldIndex for FullTextIndex {
         &self,
         condition: &FieldCondition,
     ) -> OperationResult<CardinalityEstimation> {
-        if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
+        if let Some(parsed_query) = self.parse_condition(condition) {
             return Ok(self
                 .inverted_index
                 .estimate_cardinality(&parsed_query, condition));
@@ -242,7 +476,10 @@ mod tests {
 
     use super::*;
     use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
-    use crate::data_types::text_index::{TextIndexType, TokenizerType};
+    use crate::data_types::text_index::{
+        Language, SnowballParams, SnowballType, StemmingAlgorithm, StopwordsInterface,
+        TextIndexType, TokenizerType,
+    };
     use crate::json_path::path;
 
     fn filter_request(text: &str) -> FieldCondition {
@@ -271,6 +508,11 @@ mod tests {
             min_token_len: None,
             max_token_len: None,
             lowercase: None,
+            on_disk: None,
+            stopwords: None,
+            stemmer: None,
+            ascii_folding: None,
+            phrase_matching: None,
         };
 
         {
@@ -356,4 +598,276 @@ mod tests {
             assert_eq!(index.count_indexed_points(), 2);
         }
     }
+
+    #[test]
+    fn test_mmap_full_text_index() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("The celebration had a long way to go and even in the silent depths of Multivac's underground chambers, it hung in the air."),
+            serde_json::json!("If nothing else, there was the mere fact of isolation and silence."),
+            serde_json::json!([]),
+            serde_json::json!("It would not be halted long, of course, for the needs of peace would be pressing."),
+            serde_json::json!("Yet now, for a day, perhaps for a week, even Multivac might celebrate the great time, and rest."),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let mmap_path = temp_dir.path().join("mmap_index");
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+            on_disk: Some(true),
+            stopwords: None,
+            stemmer: None,
+            ascii_folding: None,
+            phrase_matching: None,
+        };
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text", true);
+            index.recreate().unwrap();
+            for (idx, payload) in payloads.iter().enumerate() {
+                index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+            }
+
+            let mut index = index.into_mmap(&mmap_path).unwrap();
+            assert!(!index.files().is_empty());
+            assert_eq!(index.count_indexed_points(), 4);
+
+            let search_res: Vec<_> = index.filter(&filter_request("multivac")).unwrap().collect();
+            assert_eq!(search_res, vec![0, 4]);
+            let search_res: Vec<_> = index.filter(&filter_request("the")).unwrap().collect();
+            assert_eq!(search_res, vec![0, 1, 3, 4]);
+            assert!(index
+                .filter(&filter_request("computer"))
+                .unwrap()
+                .next()
+                .is_none());
+            assert!(index.values_is_empty(2));
+
+            // New tokens can't be indexed into the on-disk index
+            assert!(index
+                .add_point(2, &[&serde_json::json!("giant computer")])
+                .is_err());
+
+            index.remove_point(0).unwrap();
+            index.remove_point(0).unwrap();
+            assert_eq!(index.count_indexed_points(), 3);
+            index.flusher()().unwrap();
+        }
+
+        let mut index = FullTextIndex::new_mmap(&mmap_path, config);
+        assert!(index.load().unwrap());
+        assert_eq!(index.count_indexed_points(), 3);
+
+        let search_res: Vec<_> = index.filter(&filter_request("multivac")).unwrap().collect();
+        assert_eq!(search_res, vec![4]);
+        let parsed_query = index.parse_query("the");
+        assert!(!index.check_match(&parsed_query, 0));
+        assert!(index.check_match(&parsed_query, 1));
+        assert_eq!(index.values_count(0), 0);
+
+        index.clear().unwrap();
+        assert!(!mmap_path.exists());
+    }
+
+    #[test]
+    fn test_full_text_normalization() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("Running through the Cafés of Paris"),
+            serde_json::json!("The runner ran to a café"),
+            serde_json::json!("He runs"),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams {
+            stopwords: Some(StopwordsInterface::Language(Language::English)),
+            stemmer: Some(StemmingAlgorithm::Snowball(SnowballParams {
+                r#type: SnowballType::Snowball,
+                language: Language::English,
+            })),
+            ascii_folding: Some(true),
+            ..Default::default()
+        };
+
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new(db, config, "text", true);
+        index.recreate().unwrap();
+        for (idx, payload) in payloads.iter().enumerate() {
+            index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+        }
+
+        // Same normalization is applied to the query
+        let search_res: Vec<_> = index.filter(&filter_request("run")).unwrap().collect();
+        assert_eq!(search_res, vec![0, 2]);
+        let search_res: Vec<_> = index.filter(&filter_request("RUNNING")).unwrap().collect();
+        assert_eq!(search_res, vec![0, 2]);
+        let search_res: Vec<_> = index.filter(&filter_request("cafe")).unwrap().collect();
+        assert_eq!(search_res, vec![0, 1]);
+        let search_res: Vec<_> = index.filter(&filter_request("the café")).unwrap().collect();
+        assert_eq!(search_res, vec![0, 1]);
+
+        // Stopwords are not indexed, so a query of stopwords only matches nothing
+        assert!(index
+            .filter(&filter_request("the"))
+            .unwrap()
+            .next()
+            .is_none());
+        assert_eq!(index.values_count(2), 1);
+    }
+
+    #[test]
+    fn test_full_text_phrase_matching() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("New York is big"),
+            serde_json::json!("York is new"),
+            // Phrases can't span several values
+            serde_json::json!(["I love new", "York"]),
+            serde_json::json!("The New York Times. New! New York!"),
+        ];
+        let phrase_request =
+            |phrase: &str| FieldCondition::new_match(path("text"), Match::new_phrase(phrase));
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let mmap_path = temp_dir.path().join("mmap_index");
+        let config = TextIndexParams {
+            phrase_matching: Some(true),
+            ..Default::default()
+        };
+
+        let check_index = |index: &FullTextIndex| {
+            let search_res: Vec<_> = index.filter(&filter_request("new york")).unwrap().collect();
+            assert_eq!(search_res, vec![0, 1, 2, 3]);
+            let search_res: Vec<_> = index.filter(&phrase_request("new york")).unwrap().collect();
+            assert_eq!(search_res, vec![0, 3]);
+            let search_res: Vec<_> = index
+                .filter(&phrase_request("NEW, new York"))
+                .unwrap()
+                .collect();
+            assert_eq!(search_res, vec![3]);
+            assert!(index
+                .filter(&phrase_request("york new"))
+                .unwrap()
+                .next()
+                .is_none());
+            assert!(index
+                .filter(&phrase_request("new paris"))
+                .unwrap()
+                .next()
+                .is_none());
+
+            let parsed_phrase = index.parse_phrase("is new");
+            assert!(!index.check_match(&parsed_phrase, 0));
+            assert!(index.check_match(&parsed_phrase, 1));
+            assert!(parsed_phrase.check_phrase(&index.parse_positions("what is new?")));
+            assert!(!parsed_phrase.check_phrase(&index.parse_positions("new is what?")));
+        };
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text", true);
+            index.recreate().unwrap();
+            for (idx, payload) in payloads.iter().enumerate() {
+                index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+            }
+            check_index(&index);
+            index.flusher()().unwrap();
+        }
+
+        // Positions are restored from the storage
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new(db.clone(), config.clone(), "text", false);
+        assert!(index.load().unwrap());
+        check_index(&index);
+
+        let mut index = FullTextIndex::new(db, config, "text", true);
+        assert!(index.load().unwrap());
+        let mut index = index.into_mmap(&mmap_path).unwrap();
+        check_index(&index);
+        index.remove_point(3).unwrap();
+        assert!(index.filter(&phrase_request("new york")).unwrap().eq([0]));
+
+        // Without stored positions, phrases can't be served by the index
+        let db = open_db_with_existing_cf(&temp_dir.path().join("other_db")).unwrap();
+        let index = FullTextIndex::new(db, TextIndexParams::default(), "text", true);
+        assert!(index.filter(&phrase_request("new york")).is_err());
+    }
+
+    #[test]
+    fn test_full_text_bm25_scores() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("The quick brown fox"),
+            serde_json::json!("Fox, fox, fox!"),
+            serde_json::json!(["A lazy dog", "is not a fox"]),
+            serde_json::json!("Nothing to see here"),
+            serde_json::json!("The fox jumps over the lazy dog, and then the dog chases the fox around the yard for a very long time"),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let mmap_path = temp_dir.path().join("mmap_index");
+        let config = TextIndexParams::default();
+
+        let sorted_scores = |index: &FullTextIndex, text: &str| {
+            let statistics = index.text_query_statistics(text);
+            let mut scores: Vec<_> = index
+                .score_text_query(text, &statistics)
+                .into_iter()
+                .collect();
+            scores.sort_unstable_by_key(|&(idx, _)| idx);
+            scores
+        };
+
+        let reference = {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text", true);
+            index.recreate().unwrap();
+            for (idx, payload) in payloads.iter().enumerate() {
+                index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+            }
+            index.flusher()().unwrap();
+
+            let statistics = index.text_query_statistics("fox dog");
+            assert_eq!(statistics.points_count, 5);
+            assert_eq!(statistics.term_points_count["fox"], 4);
+            assert_eq!(statistics.term_points_count["dog"], 2);
+
+            let scores = sorted_scores(&index, "fox");
+            let score_of = |idx| scores.iter().find(|&&(i, _)| i == idx).unwrap().1;
+            // Repeated terms score higher, long documents score lower
+            assert_eq!(scores.len(), 4);
+            assert!(score_of(1) > score_of(0));
+            assert!(score_of(0) > score_of(4));
+
+            // Points with any of the terms are scored, more matching terms score higher
+            let scores = sorted_scores(&index, "lazy dog");
+            assert_eq!(
+                scores.iter().map(|&(idx, _)| idx).collect::<Vec<_>>(),
+                vec![2, 4]
+            );
+            assert!(index.score_text_query("unicorn", &statistics).is_empty());
+
+            sorted_scores(&index, "fox lazy")
+        };
+
+        // Frequencies and lengths are restored from the storage
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new(db.clone(), config.clone(), "text", false);
+        assert!(index.load().unwrap());
+        assert_eq!(sorted_scores(&index, "fox lazy"), reference);
+
+        let mut index = FullTextIndex::new(db, config, "text", true);
+        assert!(index.load().unwrap());
+        let mut index = index.into_mmap(&mmap_path).unwrap();
+        assert_eq!(sorted_scores(&index, "fox lazy"), reference);
+
+        // Removed points are neither scored, nor counted in the statistics
+        index.remove_point(1).unwrap();
+        let statistics = index.text_query_statistics("fox");
+        assert_eq!(statistics.points_count, 4);
+        let scores = index.score_text_query("fox", &statistics);
+        assert!(!scores.contains_key(&1));
+        assert_eq!(scores.len(), 3);
+    }
 }
@@ -1,6 +1,11 @@
+use std::borrow::Cow;
+
 use charabia::Tokenize;
+use rust_stemmers::{Algorithm, Stemmer};
 
-use crate::data_types::text_index::{TextIndexParams, TokenizerType};
+use super::ascii_folding::fold_to_ascii;
+use super::stop_words::StopwordsFilter;
+use crate::data_types::text_index::{Language, StemmingAlgorithm, TextIndexParams, TokenizerType};
 
 struct WhiteSpaceTokenizer;
 
@@ -74,70 +79,179 @@ impl MultilingualTokenizer {
     }
 }
 
-pub struct Tokenizer;
+/// Splits text into tokens and normalizes them according to the index config
+///
+/// Each token is lowercased, folded into ASCII, checked against stopwords and stemmed, in this
+/// order. Documents and queries go through the same pipeline, so that their tokens match.
+pub struct Tokenizer {
+    tokenizer_type: TokenizerType,
+    min_token_len: Option<usize>,
+    max_token_len: Option<usize>,
+    lowercase: bool,
+    ascii_folding: bool,
+    stopwords_filter: StopwordsFilter,
+    stemmer: Option<Stemmer>,
+}
 
 impl Tokenizer {
-    fn doc_token_filter<'a, C: FnMut(&str) + 'a>(
-        config: &'a TextIndexParams,
-        mut callback: C,
-    ) -> impl FnMut(&str) + 'a {
+    pub fn new(config: &TextIndexParams) -> Self {
+        let lowercase = config.lowercase.unwrap_or(true);
+        let ascii_folding = config.ascii_folding.unwrap_or(false);
+        let stopwords_filter = StopwordsFilter::new(config.stopwords.as_ref(), |word| {
+            normalize(word, lowercase, ascii_folding).into_owned()
+        });
+        let stemmer = config.stemmer.as_ref().map(|stemmer| match stemmer {
+            StemmingAlgorithm::Snowball(params) => {
+                Stemmer::create(snowball_algorithm(params.language))
+            }
+        });
+        Self {
+            tokenizer_type: config.tokenizer,
+            min_token_len: config.min_token_len,
+            max_token_len: config.max_token_len,
+            lowercase,
+            ascii_folding,
+            stopwords_filter,
+            stemmer,
+        }
+    }
+
+    fn is_allowed_len(&self, token: &str) -> bool {
+        if self
+            .min_token_len
+            .map(|min_len| token.len() < min_len && token.chars().count() < min_len)
+            .unwrap_or(false)
+        {
+            return false;
+        }
+        if self
+            .max_token_len
+            .map(|max_len| token.len() > max_len && token.chars().count() > max_len)
+            .unwrap_or(false)
+        {
+            return false;
+        }
+        true
+    }
+
+    /// Normalize and stem the token, or skip it if it is a stopword
+    fn process_token<'a>(&self, token: &'a str) -> Option<Cow<'a, str>> {
+        let token = normalize(token, self.lowercase, self.ascii_folding);
+        if self.stopwords_filter.is_stopword(&token) {
+            return None;
+        }
+        let Some(stemmer) = &self.stemmer else {
+            return Some(token);
+        };
+        match token {
+            Cow::Borrowed(token) => Some(stemmer.stem(token)),
+            Cow::Owned(token) => Some(Cow::Owned(stemmer.stem(&token).into_owned())),
+        }
+    }
+
+    fn token_filter<'a, C: FnMut(&str) + 'a>(&'a self, mut callback: C) -> impl FnMut(&str) + 'a {
         move |token: &str| {
-            if config
-                .min_token_len
-                .map(|min_len| token.len() < min_len && token.chars().count() < min_len)
-                .unwrap_or(false)
-            {
+            if !self.is_allowed_len(token) {
                 return;
             }
-            if config
-                .max_token_len
-                .map(|max_len| token.len() > max_len && token.chars().count() > max_len)
-                .unwrap_or(false)
-            {
-                return;
+            if let Some(token) = self.process_token(token) {
+                callback(&token);
             }
-            if config.lowercase.unwrap_or(true) {
-                callback(&token.to_lowercase());
+        }
+    }
+
+    /// Prefixes are built from processed words, so stopwords and stemming apply to whole words
+    fn prefix_word_filter<'a, C: FnMut(&str) + 'a>(
+        &'a self,
+        mut callback: C,
+        is_query: bool,
+    ) -> impl FnMut(&str) + 'a {
+        let min_ngram = self.min_token_len.unwrap_or(1);
+        let max_ngram = self.max_token_len.unwrap_or(usize::MAX);
+        move |word: &str| {
+            let Some(word) = self.process_token(word) else {
+                return;
+            };
+            let ngram_filter = |ngram: &str| {
+                if self.is_allowed_len(ngram) {
+                    callback(ngram);
+                }
+            };
+            if is_query {
+                PrefixTokenizer::tokenize_query(&word, max_ngram, ngram_filter);
             } else {
-                callback(token);
+                PrefixTokenizer::tokenize(&word, min_ngram, max_ngram, ngram_filter);
+            }
+        }
+    }
+
+    pub fn tokenize_doc<C: FnMut(&str)>(&self, text: &str, mut callback: C) {
+        match self.tokenizer_type {
+            TokenizerType::Whitespace => {
+                WhiteSpaceTokenizer::tokenize(text, self.token_filter(&mut callback))
+            }
+            TokenizerType::Word => WordTokenizer::tokenize(text, self.token_filter(&mut callback)),
+            TokenizerType::Multilingual => {
+                MultilingualTokenizer::tokenize(text, self.token_filter(&mut callback))
+            }
+            TokenizerType::Prefix => {
+                WordTokenizer::tokenize(text, self.prefix_word_filter(&mut callback, false))
             }
         }
     }
 
-    pub fn tokenize_doc<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
-        let token_filter = Self::doc_token_filter(config, &mut callback);
-        match config.tokenizer {
-            TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, token_filter),
-            TokenizerType::Word => WordTokenizer::tokenize(text, token_filter),
-            TokenizerType::Multilingual => MultilingualTokenizer::tokenize(text, token_filter),
-            TokenizerType::Prefix => PrefixTokenizer::tokenize(
-                text,
-                config.min_token_len.unwrap_or(1),
-                config.max_token_len.unwrap_or(usize::MAX),
-                token_filter,
-            ),
+    pub fn tokenize_query<C: FnMut(&str)>(&self, text: &str, mut callback: C) {
+        match self.tokenizer_type {
+            TokenizerType::Whitespace => {
+                WhiteSpaceTokenizer::tokenize(text, self.token_filter(&mut callback))
+            }
+            TokenizerType::Word => WordTokenizer::tokenize(text, self.token_filter(&mut callback)),
+            TokenizerType::Multilingual => {
+                MultilingualTokenizer::tokenize(text, self.token_filter(&mut callback))
+            }
+            TokenizerType::Prefix => {
+                WordTokenizer::tokenize(text, self.prefix_word_filter(&mut callback, true))
+            }
         }
     }
+}
 
-    pub fn tokenize_query<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
-        let token_filter = Self::doc_token_filter(config, &mut callback);
-        match config.tokenizer {
-            TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, token_filter),
-            TokenizerType::Word => WordTokenizer::tokenize(text, token_filter),
-            TokenizerType::Multilingual => MultilingualTokenizer::tokenize(text, token_filter),
-            TokenizerType::Prefix => PrefixTokenizer::tokenize_query(
-                text,
-                config.max_token_len.unwrap_or(usize::MAX),
-                token_filter,
-            ),
+fn normalize(token: &str, lowercase: bool, ascii_folding: bool) -> Cow<str> {
+    let mut token = Cow::Borrowed(token);
+    if lowercase {
+        token = Cow::Owned(token.to_lowercase());
+    }
+    if ascii_folding {
+        if let Cow::Owned(folded) = fold_to_ascii(&token) {
+            token = Cow::Owned(folded);
         }
     }
+    token
+}
+
+fn snowball_algorithm(language: Language) -> Algorithm {
+    match language {
+        Language::Danish => Algorithm::Danish,
+        Language::Dutch => Algorithm::Dutch,
+        Language::English => Algorithm::English,
+        Language::Finnish => Algorithm::Finnish,
+        Language::French => Algorithm::French,
+        Language::German => Algorithm::German,
+        Language::Italian => Algorithm::Italian,
+        Language::Norwegian => Algorithm::Norwegian,
+        Language::Portuguese => Algorithm::Portuguese,
+        Language::Russian => Algorithm::Russian,
+        Language::Spanish => Algorithm::Spanish,
+        Language::Swedish => Algorithm::Swedish,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::data_types::text_index::TextIndexType;
+    use crate::data_types::text_index::{
+        SnowballParams, SnowballType, StopwordsInterface, StopwordsSet, TextIndexType,
+    };
 
     #[test]
     fn test_whitespace_tokenizer() {
@@ -246,17 +360,19 @@ mod tests {
     fn test_tokenizer() {
         let text = "Hello, Мир!";
         let mut tokens = Vec::new();
-        Tokenizer::tokenize_doc(
-            text,
-            &TextIndexParams {
-                r#type: TextIndexType::Text,
-                tokenizer: TokenizerType::Prefix,
-                min_token_len: Some(1),
-                max_token_len: Some(4),
-                lowercase: Some(true),
-            },
-            |token| tokens.push(token.to_owned()),
-        );
+        let tokenizer = Tokenizer::new(&TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Prefix,
+            min_token_len: Some(1),
+            max_token_len: Some(4),
+            lowercase: Some(true),
+            on_disk: None,
+            stopwords: None,
+            stemmer: None,
+            ascii_folding: None,
+            phrase_matching: None,
+        });
+        tokenizer.tokenize_doc(text, |token| tokens.push(token.to_owned()));
         eprintln!("tokens = {tokens:#?}");
         assert_eq!(tokens.len(), 7);
         assert_eq!(tokens.first(), Some(&"h".to_owned()));
@@ -267,4 +383,95 @@ mod tests {
         assert_eq!(tokens.get(5), Some(&"ми".to_owned()));
         assert_eq!(tokens.get(6), Some(&"мир".to_owned()));
     }
+
+    fn tokenize_doc_and_query(
+        config: &TextIndexParams,
+        doc: &str,
+        query: &str,
+    ) -> [Vec<String>; 2] {
+        let tokenizer = Tokenizer::new(config);
+        let mut doc_tokens = Vec::new();
+        tokenizer.tokenize_doc(doc, |token| doc_tokens.push(token.to_owned()));
+        let mut query_tokens = Vec::new();
+        tokenizer.tokenize_query(query, |token| query_tokens.push(token.to_owned()));
+        [doc_tokens, query_tokens]
+    }
+
+    #[test]
+    fn test_tokenizer_stemming() {
+        let config = TextIndexParams {
+            stemmer: Some(StemmingAlgorithm::Snowball(SnowballParams {
+                r#type: SnowballType::Snowball,
+                language: Language::English,
+            })),
+            ..Default::default()
+        };
+        let [doc_tokens, query_tokens] =
+            tokenize_doc_and_query(&config, "The cats are Running", "run cat");
+        assert_eq!(doc_tokens, ["the", "cat", "are", "run"]);
+        assert_eq!(query_tokens, ["run", "cat"]);
+
+        // Stemming applies to whole words, before building prefixes
+        let config = TextIndexParams {
+            tokenizer: TokenizerType::Prefix,
+            max_token_len: Some(5),
+            ..config
+        };
+        let [doc_tokens, query_tokens] = tokenize_doc_and_query(&config, "running", "running");
+        assert_eq!(doc_tokens, ["r", "ru", "run"]);
+        assert_eq!(query_tokens, ["run"]);
+    }
+
+    #[test]
+    fn test_tokenizer_stopwords() {
+        let config = TextIndexParams {
+            stopwords: Some(StopwordsInterface::Set(StopwordsSet {
+                languages: vec![Language::English],
+                custom: vec!["Vector".to_owned()],
+            })),
+            ..Default::default()
+        };
+        let [doc_tokens, query_tokens] = tokenize_doc_and_query(
+            &config,
+            "The quick brown fox jumps over the lazy dog",
+            "A VECTOR of the fox",
+        );
+        assert_eq!(
+            doc_tokens,
+            ["quick", "brown", "fox", "jumps", "lazy", "dog"]
+        );
+        assert_eq!(query_tokens, ["fox"]);
+
+        // Stopwords are whole words, not prefixes of other words
+        let config = TextIndexParams {
+            tokenizer: TokenizerType::Prefix,
+            max_token_len: Some(3),
+            ..config
+        };
+        let [doc_tokens, query_tokens] = tokenize_doc_and_query(&config, "a another", "a ano");
+        assert_eq!(doc_tokens, ["a", "an", "ano"]);
+        assert_eq!(query_tokens, ["ano"]);
+    }
+
+    #[test]
+    fn test_tokenizer_ascii_folding() {
+        let config = TextIndexParams {
+            ascii_folding: Some(true),
+            stopwords: Some(StopwordsInterface::Language(Language::French)),
+            ..Default::default()
+        };
+        let [doc_tokens, query_tokens] =
+            tokenize_doc_and_query(&config, "Crème brûlée à la française", "CREME Brulee");
+        assert_eq!(doc_tokens, ["creme", "brulee", "francaise"]);
+        assert_eq!(query_tokens, ["creme", "brulee"]);
+
+        // Without folding, diacritics are kept
+        let config = TextIndexParams {
+            ascii_folding: None,
+            phrase_matching: None,
+            ..config
+        };
+        let [doc_tokens, _] = tokenize_doc_and_query(&config, "Crème brûlée", "");
+        assert_eq!(doc_tokens, ["crème", "brûlée"]);
+    }
 }
@@ -52,10 +52,10 @@ impl ImmutableGeoMapIndex {
         self.point_to_values.get_values(idx)
     }
 
-    pub fn get_points_per_hash(&self) -> impl Iterator<Item = (&GeoHash, usize)> {
+    pub fn get_points_per_hash(&self) -> impl Iterator<Item = (GeoHash, usize)> + '_ {
         self.counts_per_hash
             .iter()
-            .map(|counts| (&counts.hash, counts.points as usize))
+            .map(|counts| (counts.hash.clone(), counts.points as usize))
     }
 
     pub fn get_points_of_hash(&self, hash: &GeoHash) -> usize {
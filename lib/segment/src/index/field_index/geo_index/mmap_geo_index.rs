@@ -0,0 +1,342 @@
+use std::collections::HashSet;
+use std::iter;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::mutable_geo_index::MutableGeoMapIndex;
+use crate::common::mmap_type::MmapSlice;
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::index::field_index::geo_hash::{encode_max_precision, GeoHash, GEOHASH_MAX_LENGTH};
+use crate::index::field_index::mmap_storage::{
+    open_mutable_slice, remove_index_dir, write_slice, MmapDeletedFlags, MmapFlatVecs,
+    MmapSliceReadOnly,
+};
+use crate::types::GeoPoint;
+
+const CONFIG_FILE: &str = "config.json";
+const COUNTS_PER_HASH_FILE: &str = "counts_per_hash.bin";
+const POINTS_MAP_HASHES_FILE: &str = "points_map_hashes.bin";
+const DELETED_POINTS_FILE: &str = "deleted_points.bin";
+const POINTS_MAP_IDS: &str = "points_map_ids";
+const POINT_TO_VALUES: &str = "point_to_values";
+
+#[derive(Serialize, Deserialize)]
+struct MmapGeoMapIndexConfig {
+    points_count: usize,
+    points_values_count: usize,
+    max_values_per_point: usize,
+}
+
+/// Geo-hash of a fixed size, which can be stored in a file
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct StoredGeoHash {
+    bytes: [u8; GEOHASH_MAX_LENGTH],
+    len: u32,
+}
+
+impl StoredGeoHash {
+    fn new(hash: &str) -> Self {
+        debug_assert!(hash.len() <= GEOHASH_MAX_LENGTH);
+        let len = hash.len().min(GEOHASH_MAX_LENGTH);
+        let mut bytes = [0; GEOHASH_MAX_LENGTH];
+        bytes[..len].copy_from_slice(&hash.as_bytes()[..len]);
+        Self {
+            bytes,
+            len: len as u32,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        let len = (self.len as usize).min(GEOHASH_MAX_LENGTH);
+        // Geo-hashes are always ASCII
+        std::str::from_utf8(&self.bytes[..len]).unwrap_or_default()
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Counts {
+    hash: StoredGeoHash,
+    points: u32,
+    values: u32,
+}
+
+/// Geo index, stored in memory mapped files
+///
+/// Same as the immutable geo index, it consists of counts per each geo-hash prefix and the list
+/// of points per each stored geo-hash, both sorted by geo-hash. Removed points are only marked as
+/// deleted, and counts are updated in place.
+pub struct MmapGeoMapIndex {
+    path: PathBuf,
+    storage: Option<Storage>,
+    pub points_count: usize,
+    pub points_values_count: usize,
+    pub max_values_per_point: usize,
+}
+
+struct Storage {
+    counts_per_hash: MmapSlice<Counts>,
+    points_map_hashes: MmapSliceReadOnly<StoredGeoHash>,
+    points_map_ids: MmapFlatVecs<PointOffsetType>,
+    point_to_values: MmapFlatVecs<GeoPoint>,
+    deleted_points: MmapDeletedFlags,
+}
+
+impl Storage {
+    fn counts_of_hash(&self, hash: &str) -> Option<&Counts> {
+        let position = self
+            .counts_per_hash
+            .binary_search_by(|counts| counts.hash.as_str().cmp(hash))
+            .ok()?;
+        self.counts_per_hash.get(position)
+    }
+
+    fn counts_of_hash_mut(&mut self, hash: &str) -> Option<&mut Counts> {
+        let position = self
+            .counts_per_hash
+            .binary_search_by(|counts| counts.hash.as_str().cmp(hash))
+            .ok()?;
+        self.counts_per_hash.get_mut(position)
+    }
+}
+
+impl MmapGeoMapIndex {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_owned(),
+            storage: None,
+            points_count: 0,
+            points_values_count: 0,
+            max_values_per_point: 0,
+        }
+    }
+
+    /// Write the content of a mutable index into `path`
+    pub fn build(path: &Path, index: &MutableGeoMapIndex) -> OperationResult<()> {
+        std::fs::create_dir_all(path)?;
+
+        // Both maps contain all prefixes of all stored geo-hashes
+        let counts_per_hash = index
+            .points_per_hash
+            .iter()
+            .map(|(hash, &points)| Counts {
+                hash: StoredGeoHash::new(hash),
+                points: points as u32,
+                values: index.values_per_hash.get(hash).copied().unwrap_or(0) as u32,
+            })
+            .collect_vec();
+        write_slice(&path.join(COUNTS_PER_HASH_FILE), &counts_per_hash)?;
+
+        let points_map = index
+            .points_map
+            .iter()
+            .filter(|(_, ids)| !ids.is_empty())
+            .map(|(hash, ids)| {
+                (
+                    StoredGeoHash::new(hash),
+                    ids.iter().copied().sorted().collect_vec(),
+                )
+            })
+            .collect_vec();
+        let points_map_hashes = points_map.iter().map(|(hash, _)| *hash).collect_vec();
+        write_slice(&path.join(POINTS_MAP_HASHES_FILE), &points_map_hashes)?;
+        MmapFlatVecs::create(
+            path,
+            POINTS_MAP_IDS,
+            points_map.iter().map(|(_, ids)| ids.as_slice()),
+        )?;
+
+        MmapFlatVecs::create(
+            path,
+            POINT_TO_VALUES,
+            index.point_to_values.iter().map(Vec::as_slice),
+        )?;
+        MmapDeletedFlags::create(&path.join(DELETED_POINTS_FILE), index.point_to_values.len())?;
+
+        let config = MmapGeoMapIndexConfig {
+            points_count: index.points_count,
+            points_values_count: index.points_values_count,
+            max_values_per_point: index.max_values_per_point,
+        };
+        atomic_save_json(&path.join(CONFIG_FILE), &config)?;
+        Ok(())
+    }
+
+    pub fn load(&mut self) -> OperationResult<bool> {
+        let config_path = self.path.join(CONFIG_FILE);
+        if !config_path.exists() {
+            return Ok(false);
+        }
+        let config: MmapGeoMapIndexConfig = read_json(&config_path)?;
+
+        let (
+            Some(counts_per_hash),
+            Some(points_map_hashes),
+            Some(points_map_ids),
+            Some(point_to_values),
+            Some(deleted_points),
+        ) = (
+            open_mutable_slice(&self.path.join(COUNTS_PER_HASH_FILE))?,
+            MmapSliceReadOnly::open(&self.path.join(POINTS_MAP_HASHES_FILE))?,
+            MmapFlatVecs::open(&self.path, POINTS_MAP_IDS)?,
+            MmapFlatVecs::open(&self.path, POINT_TO_VALUES)?,
+            MmapDeletedFlags::open(&self.path.join(DELETED_POINTS_FILE))?,
+        )
+        else {
+            return Ok(false);
+        };
+
+        let storage = Storage {
+            counts_per_hash,
+            points_map_hashes,
+            points_map_ids,
+            point_to_values,
+            deleted_points,
+        };
+
+        let deleted_values_count: usize = storage
+            .deleted_points
+            .iter_deleted()
+            .filter_map(|idx| storage.point_to_values.get(idx as usize))
+            .map(|values| values.len())
+            .sum();
+
+        self.points_count = config.points_count - storage.deleted_points.deleted_count();
+        self.points_values_count = config.points_values_count - deleted_values_count;
+        self.max_values_per_point = config.max_values_per_point;
+        self.storage = Some(storage);
+        Ok(true)
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files = vec![
+            self.path.join(CONFIG_FILE),
+            self.path.join(COUNTS_PER_HASH_FILE),
+            self.path.join(POINTS_MAP_HASHES_FILE),
+            self.path.join(DELETED_POINTS_FILE),
+        ];
+        files.extend(MmapFlatVecs::<PointOffsetType>::files(
+            &self.path,
+            POINTS_MAP_IDS,
+        ));
+        files.extend(MmapFlatVecs::<GeoPoint>::files(&self.path, POINT_TO_VALUES));
+        files
+    }
+
+    pub fn clear(self) -> OperationResult<()> {
+        remove_index_dir(&self.path)
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        match &self.storage {
+            Some(storage) => {
+                let counts_flusher = storage.counts_per_hash.flusher();
+                let deleted_points_flusher = storage.deleted_points.flusher();
+                Box::new(move || {
+                    counts_flusher()?;
+                    deleted_points_flusher()
+                })
+            }
+            None => Box::new(|| Ok(())),
+        }
+    }
+
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<&[GeoPoint]> {
+        let storage = self.storage.as_ref()?;
+        if storage.deleted_points.is_deleted(idx as usize) {
+            return Some(&[]);
+        }
+        storage.point_to_values.get(idx as usize)
+    }
+
+    pub fn get_points_per_hash(&self) -> impl Iterator<Item = (GeoHash, usize)> + '_ {
+        self.storage.iter().flat_map(|storage| {
+            storage
+                .counts_per_hash
+                .iter()
+                .map(|counts| (GeoHash::from(counts.hash.as_str()), counts.points as usize))
+        })
+    }
+
+    pub fn get_points_of_hash(&self, hash: &GeoHash) -> usize {
+        self.storage
+            .as_ref()
+            .and_then(|storage| storage.counts_of_hash(hash))
+            .map_or(0, |counts| counts.points as usize)
+    }
+
+    pub fn get_values_of_hash(&self, hash: &GeoHash) -> usize {
+        self.storage
+            .as_ref()
+            .and_then(|storage| storage.counts_of_hash(hash))
+            .map_or(0, |counts| counts.values as usize)
+    }
+
+    /// Not deleted points of all stored geo-hashes, which start with `geo`
+    pub fn get_stored_sub_regions(
+        &self,
+        geo: &GeoHash,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let Some(storage) = &self.storage else {
+            return Box::new(iter::empty());
+        };
+        let hashes = storage.points_map_hashes.as_slice();
+        let start = hashes.partition_point(|hash| hash.as_str() < geo.as_str());
+        let end = start
+            + hashes[start..]
+                .iter()
+                .take_while(|hash| hash.as_str().starts_with(geo.as_str()))
+                .count();
+        Box::new(
+            (start..end)
+                .filter_map(|position| storage.points_map_ids.get(position))
+                .flatten()
+                .copied()
+                .filter(|&idx| !storage.deleted_points.is_deleted(idx as usize)),
+        )
+    }
+
+    pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let Some(storage) = &mut self.storage else {
+            return Ok(());
+        };
+        let removed_geo_points = match storage.point_to_values.get(idx as usize) {
+            Some(values) if !values.is_empty() => values,
+            _ => return Ok(()),
+        };
+        if !storage.deleted_points.delete(idx as usize) {
+            return Ok(());
+        }
+
+        self.points_count -= 1;
+        self.points_values_count -= removed_geo_points.len();
+
+        let removed_geo_hashes = removed_geo_points
+            .iter()
+            .map(|point| encode_max_precision(point.lon, point.lat).unwrap())
+            .collect_vec();
+
+        let mut seen_hashes: HashSet<&str> = Default::default();
+        for geo_hash in &removed_geo_hashes {
+            for i in 0..=geo_hash.len() {
+                let sub_geo_hash = &geo_hash[0..i];
+                let is_new_for_point = seen_hashes.insert(sub_geo_hash);
+                let Some(counts) = storage.counts_of_hash_mut(sub_geo_hash) else {
+                    debug_assert!(false, "Hash count is not found for hash: {sub_geo_hash}");
+                    continue;
+                };
+                counts.values = counts.values.saturating_sub(1);
+                if is_new_for_point {
+                    counts.points = counts.points.saturating_sub(1);
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 pub mod immutable_geo_index;
+mod mmap_geo_index;
 pub mod mutable_geo_index;
 
 use std::cmp::{max, min};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -12,6 +14,7 @@ use rocksdb::DB;
 use serde_json::Value;
 
 use self::immutable_geo_index::ImmutableGeoMapIndex;
+use self::mmap_geo_index::MmapGeoMapIndex;
 use self::mutable_geo_index::MutableGeoMapIndex;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
@@ -36,6 +39,7 @@ const GEO_QUERY_MAX_REGION: usize = 12;
 pub enum GeoMapIndex {
     Mutable(MutableGeoMapIndex),
     Immutable(ImmutableGeoMapIndex),
+    Mmap(MmapGeoMapIndex),
 }
 
 impl GeoMapIndex {
@@ -48,10 +52,45 @@ impl GeoMapIndex {
         }
     }
 
-    fn db_wrapper(&self) -> &DatabaseColumnWrapper {
+    /// Index, stored in memory mapped files in the directory `path`
+    pub fn new_mmap(path: &Path) -> Self {
+        GeoMapIndex::Mmap(MmapGeoMapIndex::new(path))
+    }
+
+    /// Convert a fully loaded mutable index into an on-disk index in the directory `path`.
+    ///
+    /// Data of the mutable index is removed from RocksDB.
+    pub fn into_mmap(self, path: &Path) -> OperationResult<Self> {
+        let GeoMapIndex::Mutable(index) = self else {
+            return Err(OperationError::service_error(
+                "Only mutable geo index can be converted into on-disk index",
+            ));
+        };
+        MmapGeoMapIndex::build(path, &index)?;
+        index.db_wrapper().remove_column_family()?;
+
+        let mut mmap_index = MmapGeoMapIndex::new(path);
+        if !mmap_index.load()? {
+            return Err(OperationError::service_error(format!(
+                "Failed to load on-disk geo index from {}",
+                path.display(),
+            )));
+        }
+        Ok(GeoMapIndex::Mmap(mmap_index))
+    }
+
+    fn db_wrapper(&self) -> Option<&DatabaseColumnWrapper> {
         match self {
-            GeoMapIndex::Mutable(index) => index.db_wrapper(),
-            GeoMapIndex::Immutable(index) => index.db_wrapper(),
+            GeoMapIndex::Mutable(index) => Some(index.db_wrapper()),
+            GeoMapIndex::Immutable(index) => Some(index.db_wrapper()),
+            GeoMapIndex::Mmap(_) => None,
+        }
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            GeoMapIndex::Mutable(_) | GeoMapIndex::Immutable(_) => vec![],
+            GeoMapIndex::Mmap(index) => index.files(),
         }
     }
 
@@ -59,6 +98,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.points_count,
             GeoMapIndex::Immutable(index) => index.points_count,
+            GeoMapIndex::Mmap(index) => index.points_count,
         }
     }
 
@@ -66,6 +106,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.points_values_count,
             GeoMapIndex::Immutable(index) => index.points_values_count,
+            GeoMapIndex::Mmap(index) => index.points_values_count,
         }
     }
 
@@ -78,6 +119,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.max_values_per_point,
             GeoMapIndex::Immutable(index) => index.max_values_per_point,
+            GeoMapIndex::Mmap(index) => index.max_values_per_point,
         }
     }
 
@@ -85,6 +127,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.get_points_of_hash(hash),
             GeoMapIndex::Immutable(index) => index.get_points_of_hash(hash),
+            GeoMapIndex::Mmap(index) => index.get_points_of_hash(hash),
         }
     }
 
@@ -92,6 +135,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.get_values_of_hash(hash),
             GeoMapIndex::Immutable(index) => index.get_values_of_hash(hash),
+            GeoMapIndex::Mmap(index) => index.get_values_of_hash(hash),
         }
     }
 
@@ -100,7 +144,10 @@ impl GeoMapIndex {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
-        self.db_wrapper().recreate_column_family()
+        match self.db_wrapper() {
+            Some(db_wrapper) => db_wrapper.recreate_column_family(),
+            None => Ok(()),
+        }
     }
 
     fn encode_db_key(value: &str, idx: PointOffsetType) -> String {
@@ -145,13 +192,18 @@ impl GeoMapIndex {
     }
 
     pub fn flusher(&self) -> Flusher {
-        self.db_wrapper().flusher()
+        match self {
+            GeoMapIndex::Mutable(index) => index.db_wrapper().flusher(),
+            GeoMapIndex::Immutable(index) => index.db_wrapper().flusher(),
+            GeoMapIndex::Mmap(index) => index.flusher(),
+        }
     }
 
     pub fn get_values(&self, idx: PointOffsetType) -> Option<&[GeoPoint]> {
         match self {
             GeoMapIndex::Mutable(index) => index.get_values(idx),
             GeoMapIndex::Immutable(index) => index.get_values(idx),
+            GeoMapIndex::Mmap(index) => index.get_values(idx),
         }
     }
 
@@ -251,6 +303,12 @@ impl GeoMapIndex {
                     })
                     .unique(),
             ),
+            GeoMapIndex::Mmap(index) => Box::new(
+                values
+                    .into_iter()
+                    .flat_map(|top_geo_hash| index.get_stored_sub_regions(&top_geo_hash))
+                    .unique(),
+            ),
         }
     }
 
@@ -258,9 +316,9 @@ impl GeoMapIndex {
     fn get_large_hashes(
         &self,
         threshold: usize,
-    ) -> Box<dyn Iterator<Item = (&GeoHash, usize)> + '_> {
+    ) -> Box<dyn Iterator<Item = (GeoHash, usize)> + '_> {
         let filter_condition =
-            |(hash, size): &(&GeoHash, usize)| *size > threshold && !hash.is_empty();
+            |(hash, size): &(GeoHash, usize)| *size > threshold && !hash.is_empty();
         let mut large_regions = match self {
             GeoMapIndex::Mutable(index) => index
                 .get_points_per_hash()
@@ -270,6 +328,10 @@ impl GeoMapIndex {
                 .get_points_per_hash()
                 .filter(filter_condition)
                 .collect_vec(),
+            GeoMapIndex::Mmap(index) => index
+                .get_points_per_hash()
+                .filter(filter_condition)
+                .collect_vec(),
         };
 
         // smallest regions first
@@ -306,7 +368,7 @@ impl ValueIndexer<GeoPoint> for GeoMapIndex {
     fn add_many(&mut self, id: PointOffsetType, values: Vec<GeoPoint>) -> OperationResult<()> {
         match self {
             GeoMapIndex::Mutable(index) => index.add_many_geo_points(id, &values),
-            GeoMapIndex::Immutable(_) => Err(OperationError::service_error(
+            GeoMapIndex::Immutable(_) | GeoMapIndex::Mmap(_) => Err(OperationError::service_error(
                 "Can't add values to immutable geo index",
             )),
         }
@@ -331,6 +393,7 @@ impl ValueIndexer<GeoPoint> for GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.remove_point(id),
             GeoMapIndex::Immutable(index) => index.remove_point(id),
+            GeoMapIndex::Mmap(index) => index.remove_point(id),
         }
     }
 }
@@ -344,11 +407,16 @@ impl PayloadFieldIndex for GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.load(),
             GeoMapIndex::Immutable(index) => index.load(),
+            GeoMapIndex::Mmap(index) => index.load(),
         }
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.db_wrapper().remove_column_family()
+        match self {
+            GeoMapIndex::Mutable(index) => index.db_wrapper().remove_column_family(),
+            GeoMapIndex::Immutable(index) => index.db_wrapper().remove_column_family(),
+            GeoMapIndex::Mmap(index) => index.clear(),
+        }
     }
 
     fn flusher(&self) -> Flusher {
@@ -464,7 +532,7 @@ impl PayloadFieldIndex for GeoMapIndex {
                 .map(move |(geo_hash, size)| PayloadBlockCondition {
                     condition: FieldCondition::new_geo_bounding_box(
                         key.clone(),
-                        geo_hash_to_box(geo_hash),
+                        geo_hash_to_box(&geo_hash),
                     ),
                     cardinality: size,
                 }),
@@ -1233,4 +1301,62 @@ mod tests {
         // Only LOS_ANGELES is in the bounding box
         assert_eq!(point_offsets, vec![2]);
     }
+
+    #[test]
+    fn test_mmap_geo_index() {
+        let mut reference = build_random_index(1000, 3, true);
+        let temp_dir = Builder::new().prefix("mmap_dir").tempdir().unwrap();
+        let mmap_path = temp_dir.path().join("mmap_index");
+        let mut index = build_random_index(1000, 3, true)
+            .into_mmap(&mmap_path)
+            .unwrap();
+        assert!(matches!(index, GeoMapIndex::Mmap(_)));
+
+        let conditions = [NYC, BERLIN, TOKYO]
+            .into_iter()
+            .map(|center| {
+                condition_for_geo_radius(
+                    "test",
+                    GeoRadius {
+                        center,
+                        radius: 2_000_000.0,
+                    },
+                )
+            })
+            .collect_vec();
+
+        let check_same = |reference: &GeoMapIndex, index: &GeoMapIndex| {
+            assert_eq!(reference.points_count(), index.points_count());
+            assert_eq!(reference.points_values_count(), index.points_values_count());
+            for condition in &conditions {
+                let expected = reference.filter(condition).unwrap().sorted().collect_vec();
+                let actual = index.filter(condition).unwrap().sorted().collect_vec();
+                assert_eq!(expected, actual);
+
+                let expected = reference.estimate_cardinality(condition).unwrap();
+                let actual = index.estimate_cardinality(condition).unwrap();
+                assert!(expected.equals_min_exp_max(&actual));
+            }
+            assert_eq!(
+                reference.get_large_hashes(100).collect_vec(),
+                index.get_large_hashes(100).collect_vec(),
+            );
+        };
+
+        check_same(&reference, &index);
+
+        for idx in (0..1000).step_by(7) {
+            reference.remove_point(idx).unwrap();
+            index.remove_point(idx).unwrap();
+        }
+        check_same(&reference, &index);
+        assert!(index.values_is_empty(7));
+
+        index.flusher()().unwrap();
+        drop(index);
+
+        let mut index = GeoMapIndex::new_mmap(&mmap_path);
+        assert!(index.load().unwrap());
+        check_same(&reference, &index);
+    }
 }
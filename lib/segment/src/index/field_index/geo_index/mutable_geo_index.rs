@@ -64,10 +64,10 @@ impl MutableGeoMapIndex {
         self.point_to_values.get(idx as usize).map(Vec::as_slice)
     }
 
-    pub fn get_points_per_hash(&self) -> impl Iterator<Item = (&GeoHash, usize)> {
+    pub fn get_points_per_hash(&self) -> impl Iterator<Item = (GeoHash, usize)> + '_ {
         self.points_per_hash
             .iter()
-            .map(|(hash, count)| (hash, *count))
+            .map(|(hash, count)| (hash.clone(), *count))
     }
 
     pub fn get_points_of_hash(&self, hash: &GeoHash) -> usize {
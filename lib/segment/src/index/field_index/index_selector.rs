@@ -1,9 +1,11 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use parking_lot::RwLock;
 use rocksdb::DB;
 
 use super::binary_index::BinaryIndex;
+use crate::common::operation_error::OperationResult;
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
 use crate::index::field_index::map_index::MapIndex;
@@ -14,60 +16,44 @@ use crate::types::{
     FloatPayloadType, IntPayloadType, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
 };
 
+// Directories of on-disk indexes, within the directory of the indexed field
+const INT_INDEX_DIR: &str = "int";
+const DATETIME_INDEX_DIR: &str = "datetime";
+const INT_MAP_INDEX_DIR: &str = "int_map";
+const KEYWORD_INDEX_DIR: &str = "keyword";
+const UUID_INDEX_DIR: &str = "uuid";
+const FLOAT_INDEX_DIR: &str = "float";
+const GEO_INDEX_DIR: &str = "geo";
+const FULL_TEXT_INDEX_DIR: &str = "full_text";
+
 /// Selects index types based on field type
+///
+/// Immutable segments use on-disk indexes stored in `field_dir`, if the schema requests so.
 pub fn index_selector(
     field: &JsonPath,
     payload_schema: &PayloadFieldSchema,
     db: Arc<RwLock<DB>>,
+    field_dir: &Path,
     is_appendable: bool,
 ) -> Vec<FieldIndex> {
+    if !is_appendable && payload_schema.is_on_disk() {
+        return on_disk_index_selector(payload_schema, field_dir);
+    }
+
     let field: String = field.to_string();
     let field = field.as_str();
 
-    match payload_schema {
-        PayloadFieldSchema::FieldType(payload_type) => match payload_type {
-            PayloadSchemaType::Keyword => {
-                vec![FieldIndex::KeywordIndex(MapIndex::new(
+    let payload_type = match payload_schema {
+        PayloadFieldSchema::FieldType(payload_type) => *payload_type,
+        PayloadFieldSchema::FieldParams(payload_params) => match payload_params {
+            PayloadSchemaParams::Text(text_index_params) => {
+                return vec![FieldIndex::FullTextIndex(FullTextIndex::new(
                     db,
+                    text_index_params.clone(),
                     field,
                     is_appendable,
                 ))]
             }
-            PayloadSchemaType::Integer => vec![
-                FieldIndex::IntMapIndex(MapIndex::new(db.clone(), field, is_appendable)),
-                FieldIndex::IntIndex(NumericIndex::<IntPayloadType>::new(
-                    db,
-                    field,
-                    is_appendable,
-                )),
-            ],
-            PayloadSchemaType::Float => {
-                vec![FieldIndex::FloatIndex(
-                    NumericIndex::<FloatPayloadType>::new(db, field, is_appendable),
-                )]
-            }
-            PayloadSchemaType::Geo => vec![FieldIndex::GeoIndex(GeoMapIndex::new(
-                db,
-                field,
-                is_appendable,
-            ))],
-            PayloadSchemaType::Text => vec![FieldIndex::FullTextIndex(FullTextIndex::new(
-                db,
-                Default::default(),
-                field,
-                is_appendable,
-            ))],
-            PayloadSchemaType::Bool => vec![FieldIndex::BinaryIndex(BinaryIndex::new(db, field))],
-            PayloadSchemaType::Datetime => {
-                vec![FieldIndex::DatetimeIndex(
-                    NumericIndex::<IntPayloadType>::new(db, field, is_appendable),
-                )]
-            }
-        },
-        PayloadFieldSchema::FieldParams(payload_params) => match payload_params {
-            PayloadSchemaParams::Text(text_index_params) => vec![FieldIndex::FullTextIndex(
-                FullTextIndex::new(db, text_index_params.clone(), field, is_appendable),
-            )],
             PayloadSchemaParams::Integer(integer_params) => {
                 let lookup = integer_params.lookup.then(|| {
                     FieldIndex::IntMapIndex(MapIndex::new(db.clone(), field, is_appendable))
@@ -79,8 +65,141 @@ pub fn index_selector(
                         is_appendable,
                     ))
                 });
-                lookup.into_iter().chain(range).collect()
+                return lookup.into_iter().chain(range).collect();
             }
+            PayloadSchemaParams::Keyword(_)
+            | PayloadSchemaParams::Float(_)
+            | PayloadSchemaParams::Geo(_)
+            | PayloadSchemaParams::Datetime(_)
+            | PayloadSchemaParams::Uuid(_) => payload_params.kind(),
         },
+    };
+
+    match payload_type {
+        PayloadSchemaType::Keyword => {
+            vec![FieldIndex::KeywordIndex(MapIndex::new(
+                db,
+                field,
+                is_appendable,
+            ))]
+        }
+        PayloadSchemaType::Integer => vec![
+            FieldIndex::IntMapIndex(MapIndex::new(db.clone(), field, is_appendable)),
+            FieldIndex::IntIndex(NumericIndex::<IntPayloadType>::new(
+                db,
+                field,
+                is_appendable,
+            )),
+        ],
+        PayloadSchemaType::Float => {
+            vec![FieldIndex::FloatIndex(
+                NumericIndex::<FloatPayloadType>::new(db, field, is_appendable),
+            )]
+        }
+        PayloadSchemaType::Geo => vec![FieldIndex::GeoIndex(GeoMapIndex::new(
+            db,
+            field,
+            is_appendable,
+        ))],
+        PayloadSchemaType::Text => vec![FieldIndex::FullTextIndex(FullTextIndex::new(
+            db,
+            Default::default(),
+            field,
+            is_appendable,
+        ))],
+        PayloadSchemaType::Bool => vec![FieldIndex::BinaryIndex(BinaryIndex::new(db, field))],
+        PayloadSchemaType::Datetime => {
+            vec![FieldIndex::DatetimeIndex(
+                NumericIndex::<IntPayloadType>::new(db, field, is_appendable),
+            )]
+        }
+        PayloadSchemaType::Uuid => vec![FieldIndex::UuidMapIndex(MapIndex::new(
+            db,
+            field,
+            is_appendable,
+        ))],
     }
 }
+
+/// Selects on-disk index types, which are loaded from `field_dir`
+fn on_disk_index_selector(
+    payload_schema: &PayloadFieldSchema,
+    field_dir: &Path,
+) -> Vec<FieldIndex> {
+    let PayloadFieldSchema::FieldParams(payload_params) = payload_schema else {
+        return vec![];
+    };
+    match payload_params {
+        PayloadSchemaParams::Keyword(_) => vec![FieldIndex::KeywordIndex(MapIndex::new_mmap(
+            &field_dir.join(KEYWORD_INDEX_DIR),
+        ))],
+        PayloadSchemaParams::Integer(integer_params) => {
+            let lookup = integer_params.lookup.then(|| {
+                FieldIndex::IntMapIndex(MapIndex::new_mmap(&field_dir.join(INT_MAP_INDEX_DIR)))
+            });
+            let range = integer_params.range.then(|| {
+                FieldIndex::IntIndex(NumericIndex::new_mmap(&field_dir.join(INT_INDEX_DIR)))
+            });
+            lookup.into_iter().chain(range).collect()
+        }
+        PayloadSchemaParams::Float(_) => vec![FieldIndex::FloatIndex(NumericIndex::new_mmap(
+            &field_dir.join(FLOAT_INDEX_DIR),
+        ))],
+        PayloadSchemaParams::Geo(_) => vec![FieldIndex::GeoIndex(GeoMapIndex::new_mmap(
+            &field_dir.join(GEO_INDEX_DIR),
+        ))],
+        PayloadSchemaParams::Text(text_index_params) => {
+            vec![FieldIndex::FullTextIndex(FullTextIndex::new_mmap(
+                &field_dir.join(FULL_TEXT_INDEX_DIR),
+                text_index_params.clone(),
+            ))]
+        }
+        PayloadSchemaParams::Datetime(_) => vec![FieldIndex::DatetimeIndex(
+            NumericIndex::new_mmap(&field_dir.join(DATETIME_INDEX_DIR)),
+        )],
+        PayloadSchemaParams::Uuid(_) => vec![FieldIndex::UuidMapIndex(MapIndex::new_mmap(
+            &field_dir.join(UUID_INDEX_DIR),
+        ))],
+    }
+}
+
+/// Converts freshly built indexes of the field into on-disk ones, stored in `field_dir`
+///
+/// Indexes without an on-disk variant are kept as is.
+pub fn into_on_disk_indexes(
+    indexes: Vec<FieldIndex>,
+    field_dir: &Path,
+) -> OperationResult<Vec<FieldIndex>> {
+    indexes
+        .into_iter()
+        .map(|index| {
+            Ok(match index {
+                FieldIndex::IntIndex(index) => {
+                    FieldIndex::IntIndex(index.into_mmap(&field_dir.join(INT_INDEX_DIR))?)
+                }
+                FieldIndex::DatetimeIndex(index) => {
+                    FieldIndex::DatetimeIndex(index.into_mmap(&field_dir.join(DATETIME_INDEX_DIR))?)
+                }
+                FieldIndex::IntMapIndex(index) => {
+                    FieldIndex::IntMapIndex(index.into_mmap(&field_dir.join(INT_MAP_INDEX_DIR))?)
+                }
+                FieldIndex::KeywordIndex(index) => {
+                    FieldIndex::KeywordIndex(index.into_mmap(&field_dir.join(KEYWORD_INDEX_DIR))?)
+                }
+                FieldIndex::UuidMapIndex(index) => {
+                    FieldIndex::UuidMapIndex(index.into_mmap(&field_dir.join(UUID_INDEX_DIR))?)
+                }
+                FieldIndex::FloatIndex(index) => {
+                    FieldIndex::FloatIndex(index.into_mmap(&field_dir.join(FLOAT_INDEX_DIR))?)
+                }
+                FieldIndex::GeoIndex(index) => {
+                    FieldIndex::GeoIndex(index.into_mmap(&field_dir.join(GEO_INDEX_DIR))?)
+                }
+                FieldIndex::FullTextIndex(index) => FieldIndex::FullTextIndex(
+                    index.into_mmap(&field_dir.join(FULL_TEXT_INDEX_DIR))?,
+                ),
+                index @ FieldIndex::BinaryIndex(_) => index,
+            })
+        })
+        .collect()
+}
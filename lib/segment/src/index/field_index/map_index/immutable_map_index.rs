@@ -1,9 +1,6 @@
 use std::collections::HashMap;
-use std::fmt::Display;
-use std::hash::Hash;
 use std::iter;
 use std::ops::Range;
-use std::str::FromStr;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
@@ -11,12 +8,12 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 
 use super::mutable_map_index::MutableMapIndex;
-use super::MapIndex;
+use super::{MapIndex, MapIndexKey};
 use crate::common::operation_error::OperationResult;
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::index::field_index::immutable_point_to_values::ImmutablePointToValues;
 
-pub struct ImmutableMapIndex<N: Hash + Eq + Clone + Display + FromStr + Default> {
+pub struct ImmutableMapIndex<N: MapIndexKey> {
     value_to_points: HashMap<N, Range<u32>>,
     value_to_points_container: Vec<PointOffsetType>,
     point_to_values: ImmutablePointToValues<N>,
@@ -26,7 +23,7 @@ pub struct ImmutableMapIndex<N: Hash + Eq + Clone + Display + FromStr + Default>
     db_wrapper: DatabaseColumnWrapper,
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
+impl<N: MapIndexKey> ImmutableMapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> Self {
         let store_cf_name = MapIndex::<N>::storage_cf_name(field_name);
         let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
@@ -46,7 +43,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         value_to_points_container: &'a mut [PointOffsetType],
         value: &N,
     ) -> Option<&'a mut [PointOffsetType]> {
-        match value_to_points.get(value) {
+        match value_to_points.get(value.borrow()) {
             Some(vals_range) if vals_range.start < vals_range.end => {
                 let range = vals_range.start as usize..vals_range.end as usize;
                 let vals = &mut value_to_points_container[range];
@@ -59,7 +56,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
     /// Shrinks the range of values-to-points by one.
     /// Returns true if the last element was removed.
     fn shrink_value_range(value_to_points: &mut HashMap<N, Range<u32>>, value: &N) -> bool {
-        if let Some(range) = value_to_points.get_mut(value) {
+        if let Some(range) = value_to_points.get_mut(value.borrow()) {
             range.end -= 1;
             return range.start == range.end; // true if the last element was removed
         }
@@ -116,7 +113,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         }
 
         if Self::shrink_value_range(value_to_points, value) {
-            value_to_points.remove(value);
+            value_to_points.remove(value.borrow());
         }
     }
 
@@ -202,21 +199,14 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         self.value_to_points.len()
     }
 
-    pub fn get_points_with_value_count<Q>(&self, value: &Q) -> Option<usize>
-    where
-        Q: ?Sized,
-        N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
-    {
+    pub fn get_points_with_value_count(&self, value: &N::Referenced) -> Option<usize> {
         self.value_to_points.get(value).map(|p| p.len())
     }
 
-    pub fn get_iterator<Q>(&self, value: &Q) -> Box<dyn Iterator<Item = PointOffsetType> + '_>
-    where
-        Q: ?Sized,
-        N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
-    {
+    pub fn get_iterator(
+        &self,
+        value: &N::Referenced,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         if let Some(range) = self.value_to_points.get(value) {
             let range = range.start as usize..range.end as usize;
             Box::new(self.value_to_points_container[range].iter().cloned())
@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::iter;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::mutable_map_index::MutableMapIndex;
+use super::MapIndexKey;
+use crate::common::mmap_type::MmapSlice;
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::index::field_index::mmap_storage::{
+    open_mutable_slice, remove_index_dir, write_slice, MmapDeletedFlags, MmapFlatVecs,
+};
+
+const CONFIG_FILE: &str = "config.json";
+const COUNTS_FILE: &str = "counts.bin";
+const DELETED_POINTS_FILE: &str = "deleted_points.bin";
+const KEYS: &str = "keys";
+const POSTINGS: &str = "postings";
+const POINT_TO_VALUES: &str = "point_to_values";
+
+#[derive(Serialize, Deserialize)]
+struct MmapMapIndexConfig {
+    indexed_points: usize,
+    values_count: usize,
+}
+
+/// Map index, stored in memory mapped files
+///
+/// Unique values are stored sorted, so a value id is its position in the sorted list and lookups
+/// are served by a binary search. Removed points are only marked as deleted, and the number of
+/// points per value is updated in place.
+pub struct MmapMapIndex<N: MapIndexKey> {
+    path: PathBuf,
+    storage: Option<Storage>,
+    /// Amount of point which have at least one indexed payload value
+    indexed_points: usize,
+    values_count: usize,
+    unique_values_count: usize,
+    _phantom: PhantomData<N>,
+}
+
+struct Storage {
+    /// Binary representation of each unique value, sorted by value
+    keys: MmapFlatVecs<u8>,
+    /// Sorted ids of points for each value id
+    postings: MmapFlatVecs<PointOffsetType>,
+    /// Number of not deleted points for each value id
+    counts: MmapSlice<u32>,
+    /// Value ids of each point
+    point_to_values: MmapFlatVecs<u32>,
+    deleted_points: MmapDeletedFlags,
+}
+
+impl<N: MapIndexKey> MmapMapIndex<N> {
+    pub(super) fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_owned(),
+            storage: None,
+            indexed_points: 0,
+            values_count: 0,
+            unique_values_count: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Write the content of a mutable index into `path`
+    pub(super) fn build(path: &Path, index: &MutableMapIndex<N>) -> OperationResult<()> {
+        std::fs::create_dir_all(path)?;
+
+        let mut values = index
+            .map
+            .iter()
+            .filter(|(_, ids)| !ids.is_empty())
+            .collect_vec();
+        values.sort_unstable_by(|(a, _), (b, _)| (*a).borrow().cmp((*b).borrow()));
+
+        MmapFlatVecs::create(
+            path,
+            KEYS,
+            values
+                .iter()
+                .map(|(value, _)| N::as_bytes((*value).borrow())),
+        )?;
+
+        let postings = values
+            .iter()
+            .map(|(_, ids)| ids.iter().copied().collect_vec())
+            .collect_vec();
+        MmapFlatVecs::create(path, POSTINGS, postings.iter().map(Vec::as_slice))?;
+
+        let counts = postings.iter().map(|ids| ids.len() as u32).collect_vec();
+        write_slice(&path.join(COUNTS_FILE), &counts)?;
+
+        let value_ids: HashMap<&N, u32> = values
+            .iter()
+            .enumerate()
+            .map(|(id, (value, _))| (*value, id as u32))
+            .collect();
+        let point_to_values = index
+            .point_to_values
+            .iter()
+            .map(|values| values.iter().map(|value| value_ids[value]).collect_vec())
+            .collect_vec();
+        MmapFlatVecs::create(
+            path,
+            POINT_TO_VALUES,
+            point_to_values.iter().map(Vec::as_slice),
+        )?;
+        MmapDeletedFlags::create(&path.join(DELETED_POINTS_FILE), point_to_values.len())?;
+
+        let config = MmapMapIndexConfig {
+            indexed_points: index.indexed_points,
+            values_count: index.values_count,
+        };
+        atomic_save_json(&path.join(CONFIG_FILE), &config)?;
+        Ok(())
+    }
+
+    pub(super) fn load(&mut self) -> OperationResult<bool> {
+        let config_path = self.path.join(CONFIG_FILE);
+        if !config_path.exists() {
+            return Ok(false);
+        }
+        let config: MmapMapIndexConfig = read_json(&config_path)?;
+
+        let (Some(keys), Some(postings), Some(counts), Some(point_to_values), Some(deleted_points)) = (
+            MmapFlatVecs::open(&self.path, KEYS)?,
+            MmapFlatVecs::open(&self.path, POSTINGS)?,
+            open_mutable_slice(&self.path.join(COUNTS_FILE))?,
+            MmapFlatVecs::open(&self.path, POINT_TO_VALUES)?,
+            MmapDeletedFlags::open(&self.path.join(DELETED_POINTS_FILE))?,
+        ) else {
+            return Ok(false);
+        };
+
+        let storage = Storage {
+            keys,
+            postings,
+            counts,
+            point_to_values,
+            deleted_points,
+        };
+
+        let deleted_values_count: usize = storage
+            .deleted_points
+            .iter_deleted()
+            .filter_map(|idx| storage.point_to_values.get(idx as usize))
+            .map(|value_ids| value_ids.len())
+            .sum();
+
+        self.indexed_points = config.indexed_points - storage.deleted_points.deleted_count();
+        self.values_count = config.values_count - deleted_values_count;
+        self.unique_values_count = storage.counts.iter().filter(|&&count| count > 0).count();
+        self.storage = Some(storage);
+        Ok(true)
+    }
+
+    pub(super) fn files(&self) -> Vec<PathBuf> {
+        let mut files = vec![
+            self.path.join(CONFIG_FILE),
+            self.path.join(COUNTS_FILE),
+            self.path.join(DELETED_POINTS_FILE),
+        ];
+        files.extend(MmapFlatVecs::<u8>::files(&self.path, KEYS));
+        files.extend(MmapFlatVecs::<PointOffsetType>::files(&self.path, POSTINGS));
+        files.extend(MmapFlatVecs::<u32>::files(&self.path, POINT_TO_VALUES));
+        files
+    }
+
+    pub(super) fn clear(self) -> OperationResult<()> {
+        remove_index_dir(&self.path)
+    }
+
+    pub(super) fn flusher(&self) -> Flusher {
+        match &self.storage {
+            Some(storage) => {
+                let counts_flusher = storage.counts.flusher();
+                let deleted_points_flusher = storage.deleted_points.flusher();
+                Box::new(move || {
+                    counts_flusher()?;
+                    deleted_points_flusher()
+                })
+            }
+            None => Box::new(|| Ok(())),
+        }
+    }
+
+    /// Position of the value in the sorted list of unique values
+    fn value_id(&self, value: &N::Referenced) -> Option<usize> {
+        let storage = self.storage.as_ref()?;
+        let (mut left, mut right) = (0, storage.keys.len());
+        while left < right {
+            let mid = left + (right - left) / 2;
+            match N::from_bytes(storage.keys.get(mid)?).cmp(value) {
+                Ordering::Less => left = mid + 1,
+                Ordering::Greater => right = mid,
+                Ordering::Equal => return Some(mid),
+            }
+        }
+        None
+    }
+
+    fn point_value_ids(&self, idx: PointOffsetType) -> Option<&[u32]> {
+        let storage = self.storage.as_ref()?;
+        if storage.deleted_points.is_deleted(idx as usize) {
+            return Some(&[]);
+        }
+        storage.point_to_values.get(idx as usize)
+    }
+
+    pub(super) fn get_values(
+        &self,
+        idx: PointOffsetType,
+    ) -> Option<impl Iterator<Item = &N::Referenced> + '_> {
+        let storage = self.storage.as_ref()?;
+        let value_ids = self.point_value_ids(idx)?;
+        Some(
+            value_ids
+                .iter()
+                .filter_map(|&id| storage.keys.get(id as usize).map(N::from_bytes)),
+        )
+    }
+
+    pub(super) fn values_count(&self, idx: PointOffsetType) -> usize {
+        self.point_value_ids(idx)
+            .map_or(0, |value_ids| value_ids.len())
+    }
+
+    pub(super) fn get_indexed_points(&self) -> usize {
+        self.indexed_points
+    }
+
+    pub(super) fn get_values_count(&self) -> usize {
+        self.values_count
+    }
+
+    pub(super) fn get_unique_values_count(&self) -> usize {
+        self.unique_values_count
+    }
+
+    pub(super) fn get_points_with_value_count(&self, value: &N::Referenced) -> Option<usize> {
+        let storage = self.storage.as_ref()?;
+        let id = self.value_id(value)?;
+        storage.counts.get(id).map(|&count| count as usize)
+    }
+
+    pub(super) fn get_iterator(
+        &self,
+        value: &N::Referenced,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let Some((storage, id)) = self.storage.as_ref().zip(self.value_id(value)) else {
+            return Box::new(iter::empty());
+        };
+        Box::new(
+            storage
+                .postings
+                .get(id)
+                .unwrap_or_default()
+                .iter()
+                .copied()
+                .filter(|&idx| !storage.deleted_points.is_deleted(idx as usize)),
+        )
+    }
+
+    pub(super) fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N::Referenced> + '_> {
+        let Some(storage) = &self.storage else {
+            return Box::new(iter::empty());
+        };
+        Box::new(
+            storage
+                .keys
+                .iter()
+                .zip(storage.counts.iter())
+                .filter(|(_, &count)| count > 0)
+                .map(|(key, _)| N::from_bytes(key)),
+        )
+    }
+
+    pub(super) fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let Some(storage) = &mut self.storage else {
+            return Ok(());
+        };
+        let Some(value_ids) = storage.point_to_values.get(idx as usize) else {
+            return Ok(());
+        };
+        if value_ids.is_empty() || !storage.deleted_points.delete(idx as usize) {
+            return Ok(());
+        }
+
+        self.indexed_points -= 1;
+        self.values_count -= value_ids.len();
+
+        // Point is listed only once in the posting list of each of its values
+        for &id in value_ids.iter().unique() {
+            let Some(count) = storage
+                .counts
+                .get_mut(id as usize)
+                .filter(|count| **count > 0)
+            else {
+                continue;
+            };
+            *count -= 1;
+            if *count == 0 {
+                self.unique_values_count -= 1;
+            }
+        }
+        Ok(())
+    }
+}
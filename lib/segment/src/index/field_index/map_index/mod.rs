@@ -1,8 +1,13 @@
 pub mod immutable_map_index;
+mod mmap_map_index;
 pub mod mutable_map_index;
 
+use std::borrow::Borrow;
+use std::collections::HashSet;
 use std::fmt::Display;
 use std::hash::{BuildHasher, Hash};
+use std::iter;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -10,11 +15,14 @@ use common::types::PointOffsetType;
 use immutable_map_index::ImmutableMapIndex;
 use indexmap::IndexSet;
 use itertools::Itertools;
+use memory::mmap_ops::{transmute_from_u8, transmute_to_u8};
+use mmap_map_index::MmapMapIndex;
 use mutable_map_index::MutableMapIndex;
 use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 use smol_str::SmolStr;
+use uuid::Uuid;
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
@@ -23,19 +31,95 @@ use crate::index::field_index::stat_tools::number_of_selected_points;
 use crate::index::field_index::{
     CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
 };
-use crate::index::query_estimator::combine_should_estimations;
+use crate::index::query_estimator::{combine_must_estimations, combine_should_estimations};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept, MatchValue,
-    PayloadKeyType, ValueVariants,
+    AnyVariants, FieldCondition, IntPayloadType, Match, MatchAll, MatchAny, MatchExcept,
+    MatchValue, PayloadKeyType, UuidIntType, ValueVariants,
 };
 
-pub enum MapIndex<N: Hash + Eq + Clone + Display + FromStr + Default> {
+/// Parse a keyword into the integer representation of a UUID.
+///
+/// Only the canonical form (lowercase and hyphenated) is accepted, so that two keywords map to
+/// the same UUID only if they are equal strings. This keeps indexed matching the same as
+/// matching the raw payload.
+pub fn parse_uuid(keyword: &str) -> Option<UuidIntType> {
+    if keyword.len() != HYPHENATED_UUID_LEN || keyword.bytes().any(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+    Uuid::parse_str(keyword).ok().map(|uuid| uuid.as_u128())
+}
+
+/// Length of the hyphenated form of a UUID, like `550e8400-e29b-41d4-a716-446655440000`
+const HYPHENATED_UUID_LEN: usize = 36;
+
+/// Parse all keywords with [`parse_uuid`], or `None` if any of them is not a UUID
+pub fn parse_uuids<'a>(keywords: impl IntoIterator<Item = &'a String>) -> Option<Vec<UuidIntType>> {
+    keywords
+        .into_iter()
+        .map(|keyword| parse_uuid(keyword))
+        .collect()
+}
+
+/// Value type, which can be stored in a map index
+pub trait MapIndexKey:
+    Hash + Eq + Clone + Display + FromStr + Default + Borrow<Self::Referenced>
+{
+    /// Borrowed form of the value, used for lookups and for reading values from disk
+    type Referenced: ?Sized + Hash + Eq + Ord + Display;
+
+    /// Binary representation of the value in on-disk index
+    fn as_bytes(value: &Self::Referenced) -> &[u8];
+
+    /// Inverse of [`MapIndexKey::as_bytes`]
+    fn from_bytes(bytes: &[u8]) -> &Self::Referenced;
+}
+
+impl MapIndexKey for SmolStr {
+    type Referenced = str;
+
+    fn as_bytes(value: &str) -> &[u8] {
+        value.as_bytes()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> &str {
+        let value = std::str::from_utf8(bytes);
+        debug_assert!(value.is_ok(), "keyword in map index must be valid UTF-8");
+        value.unwrap_or_default()
+    }
+}
+
+impl MapIndexKey for IntPayloadType {
+    type Referenced = IntPayloadType;
+
+    fn as_bytes(value: &IntPayloadType) -> &[u8] {
+        transmute_to_u8(value)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> &IntPayloadType {
+        transmute_from_u8(bytes)
+    }
+}
+
+impl MapIndexKey for UuidIntType {
+    type Referenced = UuidIntType;
+
+    fn as_bytes(value: &UuidIntType) -> &[u8] {
+        transmute_to_u8(value)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> &UuidIntType {
+        transmute_from_u8(bytes)
+    }
+}
+
+pub enum MapIndex<N: MapIndexKey> {
     Mutable(MutableMapIndex<N>),
     Immutable(ImmutableMapIndex<N>),
+    Mmap(MmapMapIndex<N>),
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
+impl<N: MapIndexKey> MapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str, is_appendable: bool) -> Self {
         if is_appendable {
             MapIndex::Mutable(MutableMapIndex::new(db, field_name))
@@ -44,10 +128,38 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
-    fn get_db_wrapper(&self) -> &DatabaseColumnWrapper {
+    /// Index, stored in memory mapped files in the directory `path`
+    pub fn new_mmap(path: &Path) -> Self {
+        MapIndex::Mmap(MmapMapIndex::new(path))
+    }
+
+    /// Convert a fully loaded mutable index into an on-disk index in the directory `path`.
+    ///
+    /// Data of the mutable index is removed from RocksDB.
+    pub fn into_mmap(self, path: &Path) -> OperationResult<Self> {
+        let MapIndex::Mutable(index) = self else {
+            return Err(OperationError::service_error(
+                "Only mutable map index can be converted into on-disk index",
+            ));
+        };
+        MmapMapIndex::build(path, &index)?;
+        index.get_db_wrapper().remove_column_family()?;
+
+        let mut mmap_index = MmapMapIndex::new(path);
+        if !mmap_index.load()? {
+            return Err(OperationError::service_error(format!(
+                "Failed to load on-disk map index from {}",
+                path.display(),
+            )));
+        }
+        Ok(MapIndex::Mmap(mmap_index))
+    }
+
+    fn get_db_wrapper(&self) -> Option<&DatabaseColumnWrapper> {
         match self {
-            MapIndex::Mutable(index) => index.get_db_wrapper(),
-            MapIndex::Immutable(index) => index.get_db_wrapper(),
+            MapIndex::Mutable(index) => Some(index.get_db_wrapper()),
+            MapIndex::Immutable(index) => Some(index.get_db_wrapper()),
+            MapIndex::Mmap(_) => None,
         }
     }
 
@@ -55,13 +167,22 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.load_from_db(),
             MapIndex::Immutable(index) => index.load_from_db(),
+            MapIndex::Mmap(index) => index.load(),
         }
     }
 
-    pub fn get_values(&self, idx: PointOffsetType) -> Option<&[N]> {
+    pub fn get_values(
+        &self,
+        idx: PointOffsetType,
+    ) -> Option<Box<dyn Iterator<Item = &N::Referenced> + '_>> {
         match self {
-            MapIndex::Mutable(index) => index.get_values(idx),
-            MapIndex::Immutable(index) => index.get_values(idx),
+            MapIndex::Mutable(index) => Some(Box::new(
+                index.get_values(idx)?.iter().map(|value| value.borrow()),
+            )),
+            MapIndex::Immutable(index) => Some(Box::new(
+                index.get_values(idx)?.iter().map(|value| value.borrow()),
+            )),
+            MapIndex::Mmap(index) => Some(Box::new(index.get_values(idx)?)),
         }
     }
 
@@ -69,6 +190,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_indexed_points(),
             MapIndex::Immutable(index) => index.get_indexed_points(),
+            MapIndex::Mmap(index) => index.get_indexed_points(),
         }
     }
 
@@ -76,6 +198,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_values_count(),
             MapIndex::Immutable(index) => index.get_values_count(),
+            MapIndex::Mmap(index) => index.get_values_count(),
         }
     }
 
@@ -83,58 +206,105 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_unique_values_count(),
             MapIndex::Immutable(index) => index.get_unique_values_count(),
+            MapIndex::Mmap(index) => index.get_unique_values_count(),
         }
     }
 
-    fn get_points_with_value_count<Q>(&self, value: &Q) -> Option<usize>
-    where
-        Q: ?Sized,
-        N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
-    {
+    fn get_points_with_value_count(&self, value: &N::Referenced) -> Option<usize> {
         match self {
             MapIndex::Mutable(index) => index.get_points_with_value_count(value),
             MapIndex::Immutable(index) => index.get_points_with_value_count(value),
+            MapIndex::Mmap(index) => index.get_points_with_value_count(value),
         }
     }
 
-    fn get_iterator<Q>(&self, value: &Q) -> Box<dyn Iterator<Item = PointOffsetType> + '_>
-    where
-        Q: ?Sized,
-        N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
-    {
+    fn get_iterator(
+        &self,
+        value: &N::Referenced,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         match self {
             MapIndex::Mutable(index) => index.get_iterator(value),
             MapIndex::Immutable(index) => index.get_iterator(value),
+            MapIndex::Mmap(index) => index.get_iterator(value),
+        }
+    }
+
+    fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N::Referenced> + '_> {
+        match self {
+            MapIndex::Mutable(index) => {
+                Box::new(index.get_values_iterator().map(|value| value.borrow()))
+            }
+            MapIndex::Immutable(index) => {
+                Box::new(index.get_values_iterator().map(|value| value.borrow()))
+            }
+            MapIndex::Mmap(index) => index.get_values_iterator(),
+        }
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            MapIndex::Mutable(_) | MapIndex::Immutable(_) => vec![],
+            MapIndex::Mmap(index) => index.files(),
         }
     }
 
-    fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
         match self {
-            MapIndex::Mutable(index) => index.get_values_iterator(),
-            MapIndex::Immutable(index) => index.get_values_iterator(),
+            MapIndex::Mutable(index) => index.remove_point(id),
+            MapIndex::Immutable(index) => index.remove_point(id),
+            MapIndex::Mmap(index) => index.remove_point(id),
         }
     }
 
+    fn add_many_to_map<Q: Into<N>>(
+        &mut self,
+        id: PointOffsetType,
+        values: Vec<Q>,
+    ) -> OperationResult<()> {
+        match self {
+            MapIndex::Mutable(index) => index.add_many_to_map(id, values),
+            MapIndex::Immutable(_) | MapIndex::Mmap(_) => Err(OperationError::service_error(
+                "Can't add values to immutable map index",
+            )),
+        }
+    }
+
+    /// Iterate over unique values together with the number of points having them
+    pub fn iter_counts_per_value(&self) -> impl Iterator<Item = (&N::Referenced, usize)> + '_ {
+        self.get_values_iterator().map(|value| {
+            let count = self.get_points_with_value_count(value).unwrap_or(0);
+            (value, count)
+        })
+    }
+
     pub fn storage_cf_name(field: &str) -> String {
         format!("{field}_map")
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        match self.get_db_wrapper() {
+            Some(db_wrapper) => db_wrapper.recreate_column_family(),
+            None => Ok(()),
+        }
     }
 
     fn flusher(&self) -> Flusher {
-        self.get_db_wrapper().flusher()
+        match self {
+            MapIndex::Mutable(index) => index.get_db_wrapper().flusher(),
+            MapIndex::Immutable(index) => index.get_db_wrapper().flusher(),
+            MapIndex::Mmap(index) => index.flusher(),
+        }
     }
 
-    fn match_cardinality<Q>(&self, value: &Q) -> CardinalityEstimation
-    where
-        Q: ?Sized,
-        N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
-    {
+    fn clear(self) -> OperationResult<()> {
+        match self {
+            MapIndex::Mutable(index) => index.get_db_wrapper().recreate_column_family(),
+            MapIndex::Immutable(index) => index.get_db_wrapper().recreate_column_family(),
+            MapIndex::Mmap(index) => index.clear(),
+        }
+    }
+
+    fn match_cardinality(&self, value: &N::Referenced) -> CardinalityEstimation {
         let values_count = self.get_points_with_value_count(value).unwrap_or(0);
 
         CardinalityEstimation::exact(values_count)
@@ -171,13 +341,15 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
     }
 
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
-        self.get_values(point_id).map(|x| x.len()).unwrap_or(0)
+        match self {
+            MapIndex::Mutable(index) => index.get_values(point_id).map_or(0, |x| x.len()),
+            MapIndex::Immutable(index) => index.get_values(point_id).map_or(0, |x| x.len()),
+            MapIndex::Mmap(index) => index.values_count(point_id),
+        }
     }
 
     pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
-        self.get_values(point_id)
-            .map(|x| x.is_empty())
-            .unwrap_or(true)
+        self.values_count(point_id) == 0
     }
 
     /// Estimates cardinality for `except` clause
@@ -189,12 +361,12 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
     /// # Returns
     ///
     /// * `CardinalityEstimation` - estimation of cardinality
-    fn except_cardinality<Q, I>(&self, excluded: impl Iterator<Item = I>) -> CardinalityEstimation
+    fn except_cardinality<'a>(
+        &self,
+        excluded: impl Iterator<Item = &'a N::Referenced>,
+    ) -> CardinalityEstimation
     where
-        I: std::borrow::Borrow<Q>,
-        Q: ?Sized,
-        N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
+        N::Referenced: 'a,
     {
         // Minimal case: we exclude as many points as possible.
         // In this case, excluded points do not have any other values except excluded ones.
@@ -238,7 +410,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         // max = min(60, 20) = 20
 
         let excluded_value_counts: Vec<_> = excluded
-            .map(|val| self.get_points_with_value_count(val.borrow()).unwrap_or(0))
+            .map(|val| self.get_points_with_value_count(val).unwrap_or(0))
             .collect();
         let total_excluded_value_count: usize = excluded_value_counts.iter().sum();
 
@@ -292,23 +464,67 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
-    fn except_set<'a, A, K, S>(
+    fn except_set<'a, A, K>(
         &'a self,
         excluded: &'a IndexSet<K, A>,
     ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a>
     where
         A: BuildHasher,
-        K: std::borrow::Borrow<S> + Hash + Eq,
-        N: std::borrow::Borrow<S>,
-        S: ?Sized + Hash + Eq,
+        K: Borrow<N::Referenced> + Hash + Eq,
     {
         Box::new(
             self.get_values_iterator()
-                .filter(|key| !excluded.contains((*key).borrow()))
-                .flat_map(|key| self.get_iterator(key.borrow()))
+                .filter(|key| !excluded.contains(*key))
+                .flat_map(|key| self.get_iterator(key))
                 .unique(),
         )
     }
+
+    /// Estimates cardinality for `all` clause, as an intersection of the posting lists
+    fn match_all_cardinality<'a>(
+        &self,
+        values: impl Iterator<Item = &'a N::Referenced>,
+    ) -> CardinalityEstimation
+    where
+        N::Referenced: 'a,
+    {
+        let estimations = values
+            .map(|value| self.match_cardinality(value))
+            .collect_vec();
+        if estimations.is_empty() {
+            CardinalityEstimation::exact(0)
+        } else {
+            combine_must_estimations(&estimations, self.get_indexed_points())
+        }
+    }
+
+    /// Points, which have all of the given values.
+    ///
+    /// Intersects posting lists by iterating over the shortest one
+    /// and checking that each point has the rest of the values.
+    fn match_all_set<'a, K>(
+        &'a self,
+        values: Vec<K>,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a>
+    where
+        K: Borrow<N::Referenced> + 'a,
+    {
+        let Some(shortest) = values.iter().min_by_key(|value| {
+            self.get_points_with_value_count((*value).borrow())
+                .unwrap_or(0)
+        }) else {
+            return Box::new(iter::empty());
+        };
+
+        Box::new(self.get_iterator(shortest.borrow()).filter(move |&idx| {
+            self.get_values(idx).map_or(false, |point_values| {
+                let point_values: HashSet<_> = point_values.collect();
+                values
+                    .iter()
+                    .all(|value| point_values.contains(value.borrow()))
+            })
+        }))
+    }
 }
 
 impl PayloadFieldIndex for MapIndex<SmolStr> {
@@ -321,7 +537,7 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        MapIndex::clear(self)
     }
 
     fn flusher(&self) -> Flusher {
@@ -355,7 +571,10 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
             },
             Some(Match::Except(MatchExcept {
                 except: AnyVariants::Keywords(keywords),
-            })) => Ok(self.except_set::<_, _, str>(keywords)),
+            })) => Ok(self.except_set(keywords)),
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Keywords(keywords),
+            })) => Ok(self.match_all_set(keywords.iter().map(|k| k.as_str()).collect())),
             _ => Err(OperationError::service_error("failed to filter")),
         }
     }
@@ -401,7 +620,12 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
             },
             Some(Match::Except(MatchExcept {
                 except: AnyVariants::Keywords(keywords),
-            })) => Ok(self.except_cardinality::<str, &str>(keywords.iter().map(|k| k.as_str()))),
+            })) => Ok(self.except_cardinality(keywords.iter().map(|k| k.as_str()))),
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Keywords(keywords),
+            })) => Ok(self
+                .match_all_cardinality(keywords.iter().map(|k| k.as_str()))
+                .with_primary_clause(PrimaryCondition::Condition(condition.clone()))),
             _ => Err(OperationError::service_error(
                 "failed to estimate cardinality",
             )),
@@ -435,7 +659,7 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        MapIndex::clear(self)
     }
 
     fn flusher(&self) -> Flusher {
@@ -470,6 +694,9 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
             Some(Match::Except(MatchExcept {
                 except: AnyVariants::Integers(integers),
             })) => Ok(self.except_set(integers)),
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Integers(integers),
+            })) => Ok(self.match_all_set(integers.iter().collect())),
             _ => Err(OperationError::service_error("failed to filter")),
         }
     }
@@ -515,10 +742,12 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
             },
             Some(Match::Except(MatchExcept {
                 except: AnyVariants::Integers(integers),
-            })) => {
-                Ok(self
-                    .except_cardinality::<IntPayloadType, IntPayloadType>(integers.iter().cloned()))
-            }
+            })) => Ok(self.except_cardinality(integers.iter())),
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Integers(integers),
+            })) => Ok(self
+                .match_all_cardinality(integers.iter())
+                .with_primary_clause(PrimaryCondition::Condition(condition.clone()))),
             _ => Err(OperationError::service_error(
                 "failed to estimate cardinality",
             )),
@@ -542,16 +771,157 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
     }
 }
 
-impl ValueIndexer<String> for MapIndex<SmolStr> {
-    fn add_many(&mut self, id: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
-        match self {
-            MapIndex::Mutable(index) => index.add_many_to_map(id, values),
-            MapIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable map index",
+impl PayloadFieldIndex for MapIndex<UuidIntType> {
+    fn count_indexed_points(&self) -> usize {
+        self.get_indexed_points()
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        self.load_from_db()
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        MapIndex::clear(self)
+    }
+
+    fn flusher(&self) -> Flusher {
+        MapIndex::flusher(self)
+    }
+
+    fn filter<'a>(
+        &'a self,
+        condition: &'a FieldCondition,
+    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        match &condition.r#match {
+            // Points with strings which are not UUIDs are not in the index,
+            // so only conditions on UUIDs can be answered by the index.
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => match parse_uuid(keyword) {
+                Some(uuid) => Ok(self.get_iterator(&uuid)),
+                None => Err(OperationError::service_error("failed to filter")),
+            },
+            Some(Match::Any(MatchAny { any: any_variant })) => match any_variant {
+                AnyVariants::Keywords(keywords) => {
+                    let uuids = parse_uuids(keywords)
+                        .ok_or_else(|| OperationError::service_error("failed to filter"))?;
+                    Ok(Box::new(
+                        uuids
+                            .into_iter()
+                            .unique()
+                            .flat_map(|uuid| self.get_iterator(&uuid))
+                            .unique(),
+                    ))
+                }
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Ok(Box::new(vec![].into_iter()))
+                    } else {
+                        Err(OperationError::service_error(
+                            "failed to estimate cardinality",
+                        ))
+                    }
+                }
+            },
+            // `except` is not served by the index: points with strings which are not UUIDs
+            // must match it too, but they are not in the index.
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Keywords(keywords),
+            })) => {
+                let uuids = parse_uuids(keywords)
+                    .ok_or_else(|| OperationError::service_error("failed to filter"))?;
+                Ok(self.match_all_set(uuids.into_iter().unique().collect()))
+            }
+            _ => Err(OperationError::service_error("failed to filter")),
+        }
+    }
+
+    fn estimate_cardinality(
+        &self,
+        condition: &FieldCondition,
+    ) -> OperationResult<CardinalityEstimation> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => {
+                let uuid = parse_uuid(keyword).ok_or_else(|| {
+                    OperationError::service_error("failed to estimate cardinality")
+                })?;
+                let mut estimation = self.match_cardinality(&uuid);
+                estimation
+                    .primary_clauses
+                    .push(PrimaryCondition::Condition(condition.clone()));
+                Ok(estimation)
+            }
+            Some(Match::Any(MatchAny { any: any_variant })) => match any_variant {
+                AnyVariants::Keywords(keywords) => {
+                    let uuids = parse_uuids(keywords).ok_or_else(|| {
+                        OperationError::service_error("failed to estimate cardinality")
+                    })?;
+                    let estimations = uuids
+                        .into_iter()
+                        .unique()
+                        .map(|uuid| self.match_cardinality(&uuid))
+                        .collect::<Vec<_>>();
+                    let estimation = if estimations.is_empty() {
+                        CardinalityEstimation::exact(0)
+                    } else {
+                        combine_should_estimations(&estimations, self.get_indexed_points())
+                    };
+                    Ok(estimation
+                        .with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+                }
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Ok(CardinalityEstimation::exact(0)
+                            .with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+                    } else {
+                        Err(OperationError::service_error(
+                            "failed to estimate cardinality",
+                        ))
+                    }
+                }
+            },
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Keywords(keywords),
+            })) => {
+                let uuids = parse_uuids(keywords).ok_or_else(|| {
+                    OperationError::service_error("failed to estimate cardinality")
+                })?;
+                let estimation = self.match_all_cardinality(uuids.iter().unique());
+                Ok(estimation.with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+            }
+            _ => Err(OperationError::service_error(
+                "failed to estimate cardinality",
             )),
         }
     }
 
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        Box::new(
+            self.get_values_iterator()
+                .map(|value| (value, self.get_points_with_value_count(value).unwrap_or(0)))
+                .filter(move |(_value, count)| *count > threshold)
+                .map(move |(value, count)| PayloadBlockCondition {
+                    condition: FieldCondition::new_match(
+                        key.clone(),
+                        Uuid::from_u128(*value).to_string().into(),
+                    ),
+                    cardinality: count,
+                }),
+        )
+    }
+}
+
+impl ValueIndexer<String> for MapIndex<SmolStr> {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
+        self.add_many_to_map(id, values)
+    }
+
     fn get_value(&self, value: &Value) -> Option<String> {
         if let Value::String(keyword) = value {
             return Some(keyword.to_owned());
@@ -560,10 +930,7 @@ impl ValueIndexer<String> for MapIndex<SmolStr> {
     }
 
     fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
-        match self {
-            MapIndex::Mutable(index) => index.remove_point(id),
-            MapIndex::Immutable(index) => index.remove_point(id),
-        }
+        MapIndex::remove_point(self, id)
     }
 }
 
@@ -573,12 +940,7 @@ impl ValueIndexer<IntPayloadType> for MapIndex<IntPayloadType> {
         id: PointOffsetType,
         values: Vec<IntPayloadType>,
     ) -> OperationResult<()> {
-        match self {
-            MapIndex::Mutable(index) => index.add_many_to_map(id, values),
-            MapIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable map index",
-            )),
-        }
+        self.add_many_to_map(id, values)
     }
 
     fn get_value(&self, value: &Value) -> Option<IntPayloadType> {
@@ -589,10 +951,24 @@ impl ValueIndexer<IntPayloadType> for MapIndex<IntPayloadType> {
     }
 
     fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
-        match self {
-            MapIndex::Mutable(index) => index.remove_point(id),
-            MapIndex::Immutable(index) => index.remove_point(id),
+        MapIndex::remove_point(self, id)
+    }
+}
+
+impl ValueIndexer<UuidIntType> for MapIndex<UuidIntType> {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<UuidIntType>) -> OperationResult<()> {
+        self.add_many_to_map(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<UuidIntType> {
+        if let Value::String(keyword) = value {
+            return parse_uuid(keyword);
         }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        MapIndex::remove_point(self, id)
     }
 }
 
@@ -610,10 +986,7 @@ mod tests {
 
     const FIELD_NAME: &str = "test";
 
-    fn save_map_index<N: Hash + Eq + Clone + Display + FromStr + Debug + Default>(
-        data: &[Vec<N>],
-        path: &Path,
-    ) {
+    fn save_map_index<N: MapIndexKey + Debug>(data: &[Vec<N>], path: &Path) {
         let mut index =
             MapIndex::<N>::new(open_db_with_existing_cf(path).unwrap(), FIELD_NAME, true);
         index.recreate().unwrap();
@@ -628,22 +1001,18 @@ mod tests {
         index.flusher()().unwrap();
     }
 
-    fn load_map_index<N: Hash + Eq + Clone + Display + FromStr + Debug + Default>(
-        data: &[Vec<N>],
-        path: &Path,
-    ) -> MapIndex<N> {
+    fn load_map_index<N: MapIndexKey + Debug>(data: &[Vec<N>], path: &Path) -> MapIndex<N>
+    where
+        N::Referenced: Debug,
+    {
         let mut index =
             MapIndex::<N>::new(open_db_with_existing_cf(path).unwrap(), FIELD_NAME, true);
         index.load_from_db().unwrap();
         for (idx, values) in data.iter().enumerate() {
-            let index_values: HashSet<N> = HashSet::from_iter(
-                index
-                    .get_values(idx as PointOffsetType)
-                    .unwrap()
-                    .iter()
-                    .cloned(),
-            );
-            let check_values: HashSet<N> = HashSet::from_iter(values.iter().cloned());
+            let index_values: HashSet<&N::Referenced> =
+                HashSet::from_iter(index.get_values(idx as PointOffsetType).unwrap());
+            let check_values: HashSet<&N::Referenced> =
+                HashSet::from_iter(values.iter().map(|value| value.borrow()));
             assert_eq!(index_values, check_values);
         }
 
@@ -652,7 +1021,7 @@ mod tests {
 
     #[test]
     fn test_int_disk_map_index() {
-        let data = vec![
+        let data: Vec<Vec<IntPayloadType>> = vec![
             vec![1, 2, 3, 4, 5, 6],
             vec![1, 2, 3, 4, 5, 6],
             vec![13, 14, 15, 16, 17, 18],
@@ -666,7 +1035,7 @@ mod tests {
 
         // Ensure cardinality is non zero
         assert!(!index
-            .except_cardinality::<_, &_>(vec![].into_iter())
+            .except_cardinality(std::iter::empty())
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
 
@@ -674,26 +1043,26 @@ mod tests {
     fn test_string_disk_map_index() {
         let data = vec![
             vec![
-                String::from("AABB"),
-                String::from("UUFF"),
-                String::from("IIBB"),
+                SmolStr::from("AABB"),
+                SmolStr::from("UUFF"),
+                SmolStr::from("IIBB"),
             ],
             vec![
-                String::from("PPMM"),
-                String::from("QQXX"),
-                String::from("YYBB"),
+                SmolStr::from("PPMM"),
+                SmolStr::from("QQXX"),
+                SmolStr::from("YYBB"),
             ],
             vec![
-                String::from("FFMM"),
-                String::from("IICC"),
-                String::from("IIBB"),
+                SmolStr::from("FFMM"),
+                SmolStr::from("IICC"),
+                SmolStr::from("IIBB"),
             ],
             vec![
-                String::from("AABB"),
-                String::from("UUFF"),
-                String::from("IIBB"),
+                SmolStr::from("AABB"),
+                SmolStr::from("UUFF"),
+                SmolStr::from("IIBB"),
             ],
-            vec![String::from("PPGG")],
+            vec![SmolStr::from("PPGG")],
         ];
 
         let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
@@ -702,13 +1071,130 @@ mod tests {
 
         // Ensure cardinality is non zero
         assert!(!index
-            .except_cardinality::<str, &str>(vec![].into_iter())
+            .except_cardinality(std::iter::empty())
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
 
+    #[test]
+    fn test_uuid_disk_map_index() {
+        let uuids = [
+            "7b6e1a3c-1d8b-4c1e-9f3e-3a2f5b1c0d4e",
+            "c0ffee00-0000-4000-8000-000000000001",
+            "550e8400-e29b-41d4-a716-446655440000",
+        ];
+        let uuid_values: Vec<UuidIntType> =
+            uuids.iter().map(|uuid| parse_uuid(uuid).unwrap()).collect();
+
+        let data = vec![
+            vec![uuid_values[0], uuid_values[1]],
+            vec![uuid_values[1]],
+            vec![uuid_values[2]],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let index = load_map_index(&data, temp_dir.path());
+
+        let filter = |r#match: Match| {
+            let condition = FieldCondition::new_match(FIELD_NAME.parse().unwrap(), r#match);
+            let mut points = index.filter(&condition).ok()?.collect_vec();
+            points.sort_unstable();
+            Some(points)
+        };
+
+        assert_eq!(
+            filter(Match::new_value(ValueVariants::Keyword(uuids[1].into()))),
+            Some(vec![0, 1])
+        );
+        assert_eq!(
+            filter(Match::new_any(AnyVariants::Keywords(
+                [uuids[0], uuids[2]].into_iter().map(String::from).collect()
+            ))),
+            Some(vec![0, 2])
+        );
+
+        // Other forms of UUIDs and other strings are not handled by the index,
+        // the payload has to be checked instead
+        assert_eq!(
+            filter(Match::new_value(ValueVariants::Keyword(
+                uuids[1].to_uppercase()
+            ))),
+            None
+        );
+        assert_eq!(
+            filter(Match::new_value(ValueVariants::Keyword(
+                uuids[2].replace('-', "")
+            ))),
+            None
+        );
+        assert_eq!(
+            filter(Match::new_any(AnyVariants::Keywords(
+                [uuids[0], uuids[2], "not-a-uuid"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            ))),
+            None
+        );
+        assert_eq!(
+            filter(Match::new_except(AnyVariants::Keywords(
+                [uuids[1]].into_iter().map(String::from).collect()
+            ))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_uuid() {
+        let uuid = "550e8400-e29b-41d4-a716-446655440000";
+        assert_eq!(
+            parse_uuid(uuid),
+            Some(0x550e8400_e29b_41d4_a716_446655440000)
+        );
+        assert_eq!(parse_uuid(&uuid.to_uppercase()), None);
+        assert_eq!(parse_uuid(&uuid.replace('-', "")), None);
+        assert_eq!(parse_uuid(&format!("{{{uuid}}}")), None);
+        assert_eq!(parse_uuid("not-a-uuid"), None);
+        assert_eq!(parse_uuid(""), None);
+    }
+
+    #[test]
+    fn test_match_all_map_index() {
+        let data: Vec<Vec<IntPayloadType>> =
+            vec![vec![1, 2, 3], vec![1, 2], vec![2, 3], vec![1, 3]];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let index = load_map_index(&data, temp_dir.path());
+
+        let condition = |integers: &[IntPayloadType]| {
+            FieldCondition::new_match(
+                FIELD_NAME.parse().unwrap(),
+                Match::new_all(AnyVariants::Integers(integers.iter().copied().collect())),
+            )
+        };
+        let filter = |integers: &[IntPayloadType]| {
+            let condition = condition(integers);
+            let mut points = index.filter(&condition).unwrap().collect_vec();
+            points.sort_unstable();
+            points
+        };
+
+        assert_eq!(filter(&[1, 2]), vec![0, 1]);
+        assert_eq!(filter(&[3, 1]), vec![0, 3]);
+        assert_eq!(filter(&[1, 2, 3]), vec![0]);
+        assert!(filter(&[1, 4]).is_empty());
+        assert!(filter(&[]).is_empty());
+
+        let estimation = index.estimate_cardinality(&condition(&[1, 2])).unwrap();
+        assert!(estimation.min <= 2 && 2 <= estimation.max);
+        assert_eq!(estimation.max, 3);
+        assert_eq!(estimation.primary_clauses.len(), 1);
+    }
+
     #[test]
     fn test_empty_index() {
-        let data: Vec<Vec<String>> = vec![];
+        let data: Vec<Vec<SmolStr>> = vec![];
 
         let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
         save_map_index(&data, temp_dir.path());
@@ -716,7 +1202,76 @@ mod tests {
 
         // Ensure cardinality is zero
         assert!(index
-            .except_cardinality::<str, &str>(vec![].into_iter())
+            .except_cardinality(std::iter::empty())
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
+
+    #[test]
+    fn test_mmap_map_index() {
+        let data: Vec<Vec<SmolStr>> = vec![
+            vec!["a".into(), "b".into()],
+            vec!["b".into(), "c".into()],
+            vec![],
+            vec!["a".into(), "d".into()],
+            vec!["c".into()],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let index = load_map_index(&data, temp_dir.path());
+
+        let filter = |index: &MapIndex<SmolStr>, r#match: Match| {
+            let condition = FieldCondition::new_match(FIELD_NAME.parse().unwrap(), r#match);
+            let mut points = index.filter(&condition).unwrap().collect_vec();
+            points.sort_unstable();
+            points
+        };
+        let keyword = |keyword: &str| Match::new_value(ValueVariants::Keyword(keyword.into()));
+        let except = |keyword: &str| {
+            Match::new_except(AnyVariants::Keywords(
+                std::iter::once(keyword.into()).collect(),
+            ))
+        };
+
+        let mmap_path = temp_dir.path().join("mmap_index");
+        let mut index = index.into_mmap(&mmap_path).unwrap();
+        assert!(matches!(index, MapIndex::Mmap(_)));
+        assert_eq!(index.get_indexed_points(), 4);
+        assert_eq!(index.get_values_count(), 7);
+        assert_eq!(index.get_unique_values_count(), 4);
+
+        assert_eq!(filter(&index, keyword("a")), vec![0, 3]);
+        assert_eq!(filter(&index, keyword("c")), vec![1, 4]);
+        assert!(filter(&index, keyword("e")).is_empty());
+        assert_eq!(filter(&index, except("b")), vec![0, 1, 3, 4]);
+        assert_eq!(index.get_points_with_value_count("b"), Some(2));
+        assert_eq!(
+            index.get_values(3).unwrap().sorted().collect_vec(),
+            vec!["a", "d"],
+        );
+
+        MapIndex::remove_point(&mut index, 3).unwrap();
+        MapIndex::remove_point(&mut index, 4).unwrap();
+        index.flusher()().unwrap();
+        drop(index);
+
+        let mut index = MapIndex::<SmolStr>::new_mmap(&mmap_path);
+        assert!(index.load_from_db().unwrap());
+        assert_eq!(index.get_indexed_points(), 2);
+        assert_eq!(index.get_values_count(), 4);
+        assert_eq!(index.get_unique_values_count(), 3);
+        assert!(index.values_is_empty(3));
+
+        assert_eq!(filter(&index, keyword("a")), vec![0]);
+        assert_eq!(filter(&index, keyword("c")), vec![1]);
+        assert!(filter(&index, keyword("d")).is_empty());
+        assert_eq!(index.get_points_with_value_count("c"), Some(1));
+        assert_eq!(
+            index.get_values_iterator().sorted().collect_vec(),
+            vec!["a", "b", "c"],
+        );
+
+        index.clear().unwrap();
+        assert!(!mmap_path.exists());
+    }
 }
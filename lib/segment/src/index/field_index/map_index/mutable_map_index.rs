@@ -1,19 +1,16 @@
 use std::collections::{BTreeSet, HashMap};
-use std::fmt::Display;
-use std::hash::Hash;
 use std::iter;
-use std::str::FromStr;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
 use parking_lot::RwLock;
 use rocksdb::DB;
 
-use super::MapIndex;
+use super::{MapIndex, MapIndexKey};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 
-pub struct MutableMapIndex<N: Hash + Eq + Clone + Display + FromStr> {
+pub struct MutableMapIndex<N: MapIndexKey> {
     pub(super) map: HashMap<N, BTreeSet<PointOffsetType>>,
     pub(super) point_to_values: Vec<Vec<N>>,
     /// Amount of point which have at least one indexed payload value
@@ -22,7 +19,7 @@ pub struct MutableMapIndex<N: Hash + Eq + Clone + Display + FromStr> {
     pub(super) db_wrapper: DatabaseColumnWrapper,
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
+impl<N: MapIndexKey> MutableMapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> Self {
         let store_cf_name = MapIndex::<N>::storage_cf_name(field_name);
         let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
@@ -77,7 +74,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
         self.values_count -= removed_values.len();
 
         for value in &removed_values {
-            if let Some(vals) = self.map.get_mut(value) {
+            if let Some(vals) = self.map.get_mut(value.borrow()) {
                 vals.remove(&idx);
             }
             let key = MapIndex::encode_db_record(value, idx);
@@ -132,21 +129,14 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
         self.map.len()
     }
 
-    pub fn get_points_with_value_count<Q>(&self, value: &Q) -> Option<usize>
-    where
-        Q: ?Sized,
-        N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
-    {
+    pub fn get_points_with_value_count(&self, value: &N::Referenced) -> Option<usize> {
         self.map.get(value).map(|p| p.len())
     }
 
-    pub fn get_iterator<Q>(&self, value: &Q) -> Box<dyn Iterator<Item = PointOffsetType> + '_>
-    where
-        Q: ?Sized,
-        N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
-    {
+    pub fn get_iterator(
+        &self,
+        value: &N::Referenced,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         self.map
             .get(value)
             .map(|ids| Box::new(ids.iter().copied()) as Box<dyn Iterator<Item = PointOffsetType>>)
@@ -0,0 +1,280 @@
+//! Building blocks of on-disk field indexes
+//!
+//! On-disk field indexes are built once, from a fully populated in-memory index, when a segment is
+//! optimized. Their data is stored in memory mapped files, so it is only loaded into RAM on
+//! access by the page cache. The data is never changed after it is written, except for deletion
+//! flags and counters, which are updated in place when points are removed.
+//!
+//! All stored types must be plain data (no pointers, no padding-dependent invariants), because
+//! they are transmuted directly from the mapped bytes.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use memmap2::Mmap;
+use memory::mmap_ops::{
+    create_and_ensure_length, open_read_mmap, open_write_mmap, transmute_from_u8_to_slice,
+    transmute_to_u8_slice,
+};
+
+use crate::common::mmap_type::{MmapBitSlice, MmapSlice};
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+
+/// Write a slice of plain values into a file, replacing its content
+pub fn write_slice<T>(path: &Path, values: &[T]) -> OperationResult<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(transmute_to_u8_slice(values))?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Open a file written by [`write_slice`] as a mutable slice.
+///
+/// Returns `None` if the file doesn't exist.
+pub fn open_mutable_slice<T>(path: &Path) -> OperationResult<Option<MmapSlice<T>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mmap = open_write_mmap(path)?;
+    // Safety: files are only written by `write_slice` with the same type
+    let slice = unsafe { MmapSlice::try_from(mmap)? };
+    Ok(Some(slice))
+}
+
+/// Read-only slice of plain values on a memory mapped file
+pub struct MmapSliceReadOnly<T> {
+    mmap: Mmap,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> MmapSliceReadOnly<T> {
+    /// Open a file written by [`write_slice`].
+    ///
+    /// Returns `None` if the file doesn't exist.
+    pub fn open(path: &Path) -> OperationResult<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let mmap = open_read_mmap(path)?;
+        if mmap.len() % size_of::<T>() != 0 {
+            return Err(
+                crate::common::mmap_type::Error::SizeMultiple(size_of::<T>(), mmap.len()).into(),
+            );
+        }
+        Ok(Some(Self {
+            mmap,
+            _phantom: PhantomData,
+        }))
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        transmute_from_u8_to_slice(&self.mmap)
+    }
+}
+
+/// Flattened list of lists of plain values on memory mapped files
+///
+/// It is an on-disk analogue of `Vec<Vec<T>>`, which consists of two files: offsets of each list
+/// and values of all lists concatenated together.
+pub struct MmapFlatVecs<T> {
+    offsets: MmapSliceReadOnly<u64>,
+    data: MmapSliceReadOnly<T>,
+}
+
+impl<T> MmapFlatVecs<T> {
+    fn offsets_path(path: &Path, name: &str) -> PathBuf {
+        path.join(format!("{name}_offsets.bin"))
+    }
+
+    fn data_path(path: &Path, name: &str) -> PathBuf {
+        path.join(format!("{name}_data.bin"))
+    }
+
+    pub fn files(path: &Path, name: &str) -> Vec<PathBuf> {
+        vec![Self::offsets_path(path, name), Self::data_path(path, name)]
+    }
+
+    /// Write lists into the files `name` in the directory `path`
+    pub fn create<'a>(
+        path: &Path,
+        name: &str,
+        vecs: impl IntoIterator<Item = &'a [T]>,
+    ) -> OperationResult<()>
+    where
+        T: 'a,
+    {
+        let mut offsets_writer = BufWriter::new(File::create(Self::offsets_path(path, name))?);
+        let mut data_writer = BufWriter::new(File::create(Self::data_path(path, name))?);
+
+        let mut offset = 0u64;
+        offsets_writer.write_all(transmute_to_u8_slice(&[offset]))?;
+        for values in vecs {
+            data_writer.write_all(transmute_to_u8_slice(values))?;
+            offset += values.len() as u64;
+            offsets_writer.write_all(transmute_to_u8_slice(&[offset]))?;
+        }
+
+        offsets_writer.flush()?;
+        data_writer.flush()?;
+        Ok(())
+    }
+
+    /// Open lists, written by [`MmapFlatVecs::create`].
+    ///
+    /// Returns `None` if any of the files doesn't exist.
+    pub fn open(path: &Path, name: &str) -> OperationResult<Option<Self>> {
+        let Some(offsets) = MmapSliceReadOnly::open(&Self::offsets_path(path, name))? else {
+            return Ok(None);
+        };
+        let Some(data) = MmapSliceReadOnly::open(&Self::data_path(path, name))? else {
+            return Ok(None);
+        };
+        Ok(Some(Self { offsets, data }))
+    }
+
+    /// Number of lists
+    pub fn len(&self) -> usize {
+        self.offsets.as_slice().len().saturating_sub(1)
+    }
+
+    /// Number of values in all lists before the list `idx`
+    pub fn offset(&self, idx: usize) -> Option<usize> {
+        self.offsets
+            .as_slice()
+            .get(idx)
+            .map(|&offset| offset as usize)
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&[T]> {
+        let offsets = self.offsets.as_slice();
+        let start = *offsets.get(idx)? as usize;
+        let end = *offsets.get(idx + 1)? as usize;
+        self.data.as_slice().get(start..end)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.len()).filter_map(|idx| self.get(idx))
+    }
+}
+
+/// Deletion flags on a memory mapped file
+pub struct MmapDeletedFlags {
+    flags: MmapBitSlice,
+    deleted_count: usize,
+}
+
+impl MmapDeletedFlags {
+    /// Create a file with `len` flags, none of them set
+    pub fn create(path: &Path, len: usize) -> OperationResult<()> {
+        let bytes = len
+            .div_ceil(u8::BITS as usize)
+            .next_multiple_of(size_of::<usize>())
+            .max(size_of::<usize>());
+        create_and_ensure_length(path, bytes)?;
+        Ok(())
+    }
+
+    /// Open flags, created by [`MmapDeletedFlags::create`].
+    ///
+    /// Returns `None` if the file doesn't exist.
+    pub fn open(path: &Path) -> OperationResult<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let flags = MmapBitSlice::try_from(open_write_mmap(path)?, 0)?;
+        let deleted_count = flags.count_ones();
+        Ok(Some(Self {
+            flags,
+            deleted_count,
+        }))
+    }
+
+    pub fn is_deleted(&self, idx: usize) -> bool {
+        self.flags.get(idx).is_some_and(|flag| *flag)
+    }
+
+    /// Mark `idx` as deleted. Returns true if it was not deleted before.
+    pub fn delete(&mut self, idx: usize) -> bool {
+        if idx >= self.flags.len() || self.flags[idx] {
+            return false;
+        }
+        self.flags.set(idx, true);
+        self.deleted_count += 1;
+        true
+    }
+
+    pub fn deleted_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    pub fn iter_deleted(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.flags.iter_ones().map(|idx| idx as PointOffsetType)
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.flags.flusher()
+    }
+}
+
+/// Remove all files of an on-disk index
+pub fn remove_index_dir(path: &Path) -> OperationResult<()> {
+    if path.exists() {
+        std::fs::remove_dir_all(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_mmap_flat_vecs() {
+        let dir = Builder::new().prefix("flat_vecs").tempdir().unwrap();
+        let vecs: Vec<Vec<u64>> = vec![vec![1, 2, 3], vec![], vec![4], vec![5, 6]];
+
+        MmapFlatVecs::create(dir.path(), "test", vecs.iter().map(|v| v.as_slice())).unwrap();
+        let flat = MmapFlatVecs::<u64>::open(dir.path(), "test")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(flat.len(), vecs.len());
+        for (idx, values) in vecs.iter().enumerate() {
+            assert_eq!(flat.get(idx).unwrap(), values.as_slice());
+        }
+        assert!(flat.get(vecs.len()).is_none());
+
+        assert!(MmapFlatVecs::<u64>::open(dir.path(), "missing")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_mmap_deleted_flags() {
+        let dir = Builder::new().prefix("deleted_flags").tempdir().unwrap();
+        let path = dir.path().join("deleted.bin");
+
+        MmapDeletedFlags::create(&path, 100).unwrap();
+        {
+            let mut flags = MmapDeletedFlags::open(&path).unwrap().unwrap();
+            assert!(flags.delete(3));
+            assert!(!flags.delete(3));
+            assert!(flags.delete(42));
+            flags.flusher()().unwrap();
+        }
+
+        let flags = MmapDeletedFlags::open(&path).unwrap().unwrap();
+        assert_eq!(flags.deleted_count(), 2);
+        assert!(flags.is_deleted(42));
+        assert!(!flags.is_deleted(41));
+        assert!(!flags.is_deleted(100_000));
+        assert_eq!(flags.iter_deleted().collect::<Vec<_>>(), vec![3, 42]);
+    }
+}
@@ -0,0 +1,318 @@
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use super::immutable_numeric_index::NumericIndexKey;
+use super::mutable_numeric_index::MutableNumericIndex;
+use super::{Encodable, HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION};
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::index::field_index::histogram::{Histogram, Numericable, Point};
+use crate::index::field_index::mmap_storage::{
+    remove_index_dir, write_slice, MmapDeletedFlags, MmapFlatVecs, MmapSliceReadOnly,
+};
+
+const CONFIG_FILE: &str = "config.json";
+const PAIRS_VALUES_FILE: &str = "pairs_values.bin";
+const PAIRS_IDS_FILE: &str = "pairs_ids.bin";
+const DELETED_PAIRS_FILE: &str = "deleted_pairs.bin";
+const DELETED_POINTS_FILE: &str = "deleted_points.bin";
+const POINT_TO_VALUES: &str = "point_to_values";
+
+#[derive(Serialize, Deserialize)]
+struct MmapNumericIndexConfig {
+    points_count: usize,
+    values_count: usize,
+    max_values_per_point: usize,
+}
+
+/// Numeric index, stored in memory mapped files
+///
+/// Value-point pairs are stored sorted in two parallel files, so range queries are served by a
+/// binary search. Removed points are only marked as deleted.
+pub struct MmapNumericIndex<T: Encodable + Numericable + Default> {
+    path: PathBuf,
+    storage: Option<Storage<T>>,
+    pub(super) histogram: Histogram<T>,
+    pub(super) points_count: usize,
+    pub(super) max_values_per_point: usize,
+    values_count: usize,
+}
+
+struct Storage<T> {
+    pairs_values: MmapSliceReadOnly<T>,
+    pairs_ids: MmapSliceReadOnly<PointOffsetType>,
+    deleted_pairs: MmapDeletedFlags,
+    point_to_values: MmapFlatVecs<T>,
+    deleted_points: MmapDeletedFlags,
+}
+
+impl<T: Encodable + Numericable> Storage<T> {
+    fn key_at(&self, position: usize) -> NumericIndexKey<T> {
+        NumericIndexKey::new(
+            self.pairs_values.as_slice()[position],
+            self.pairs_ids.as_slice()[position],
+        )
+    }
+
+    fn point_at(&self, position: usize) -> Point<T> {
+        Point {
+            val: self.pairs_values.as_slice()[position],
+            idx: self.pairs_ids.as_slice()[position] as usize,
+        }
+    }
+
+    /// Index of the first pair in `..end`, for which `pred` is false.
+    /// Pairs are sorted, so `pred` must be true for some prefix only.
+    fn partition_point(&self, end: usize, pred: impl Fn(&NumericIndexKey<T>) -> bool) -> usize {
+        let (mut left, mut right) = (0, end);
+        while left < right {
+            let mid = left + (right - left) / 2;
+            if pred(&self.key_at(mid)) {
+                left = mid + 1;
+            } else {
+                right = mid;
+            }
+        }
+        left
+    }
+
+    fn range_positions(
+        &self,
+        start_bound: Bound<NumericIndexKey<T>>,
+        end_bound: Bound<NumericIndexKey<T>>,
+    ) -> std::ops::Range<usize> {
+        let len = self.pairs_ids.as_slice().len();
+        let start = match start_bound {
+            Bound::Included(bound) => self.partition_point(len, |key| key < &bound),
+            Bound::Excluded(bound) => self.partition_point(len, |key| key <= &bound),
+            Bound::Unbounded => 0,
+        };
+        let end = match end_bound {
+            Bound::Included(bound) => self.partition_point(len, |key| key <= &bound),
+            Bound::Excluded(bound) => self.partition_point(len, |key| key < &bound),
+            Bound::Unbounded => len,
+        };
+        start..end.max(start)
+    }
+
+    /// Largest not deleted pair in `..end`, which is less than `point`
+    fn left_neighbour(&self, point: &Point<T>, end: usize) -> Option<Point<T>> {
+        let key = NumericIndexKey::from(point.clone());
+        let position = self.partition_point(end, |other| other < &key);
+        (0..position)
+            .rev()
+            .find(|&position| !self.deleted_pairs.is_deleted(position))
+            .map(|position| self.point_at(position))
+    }
+
+    /// Smallest not deleted pair in `..end`, which is greater than `point`
+    fn right_neighbour(&self, point: &Point<T>, end: usize) -> Option<Point<T>> {
+        let key = NumericIndexKey::from(point.clone());
+        let position = self.partition_point(end, |other| other <= &key);
+        (position..end)
+            .find(|&position| !self.deleted_pairs.is_deleted(position))
+            .map(|position| self.point_at(position))
+    }
+}
+
+impl<T: Encodable + Numericable + Default> MmapNumericIndex<T> {
+    pub(super) fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_owned(),
+            storage: None,
+            histogram: Histogram::new(HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION),
+            points_count: 0,
+            max_values_per_point: 0,
+            values_count: 0,
+        }
+    }
+
+    /// Write the content of a mutable index into `path`
+    pub(super) fn build(path: &Path, index: &MutableNumericIndex<T>) -> OperationResult<()> {
+        std::fs::create_dir_all(path)?;
+
+        let (pairs_ids, pairs_values): (Vec<_>, Vec<_>) =
+            index.map.keys().map(|key| T::decode_key(key)).unzip();
+        write_slice(&path.join(PAIRS_VALUES_FILE), &pairs_values)?;
+        write_slice(&path.join(PAIRS_IDS_FILE), &pairs_ids)?;
+        MmapDeletedFlags::create(&path.join(DELETED_PAIRS_FILE), pairs_ids.len())?;
+
+        MmapFlatVecs::create(
+            path,
+            POINT_TO_VALUES,
+            index.point_to_values.iter().map(|values| values.as_slice()),
+        )?;
+        MmapDeletedFlags::create(&path.join(DELETED_POINTS_FILE), index.point_to_values.len())?;
+
+        let config = MmapNumericIndexConfig {
+            points_count: index.points_count,
+            values_count: pairs_ids.len(),
+            max_values_per_point: index.max_values_per_point,
+        };
+        atomic_save_json(&path.join(CONFIG_FILE), &config)?;
+        Ok(())
+    }
+
+    pub(super) fn load(&mut self) -> OperationResult<bool> {
+        let config_path = self.path.join(CONFIG_FILE);
+        if !config_path.exists() {
+            return Ok(false);
+        }
+        let config: MmapNumericIndexConfig = read_json(&config_path)?;
+
+        let (
+            Some(pairs_values),
+            Some(pairs_ids),
+            Some(deleted_pairs),
+            Some(point_to_values),
+            Some(deleted_points),
+        ) = (
+            MmapSliceReadOnly::open(&self.path.join(PAIRS_VALUES_FILE))?,
+            MmapSliceReadOnly::open(&self.path.join(PAIRS_IDS_FILE))?,
+            MmapDeletedFlags::open(&self.path.join(DELETED_PAIRS_FILE))?,
+            MmapFlatVecs::open(&self.path, POINT_TO_VALUES)?,
+            MmapDeletedFlags::open(&self.path.join(DELETED_POINTS_FILE))?,
+        )
+        else {
+            return Ok(false);
+        };
+
+        let storage = Storage {
+            pairs_values,
+            pairs_ids,
+            deleted_pairs,
+            point_to_values,
+            deleted_points,
+        };
+
+        // Pairs are sorted, so each inserted point is the largest one seen so far
+        let mut histogram = Histogram::new(HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION);
+        for position in 0..storage.pairs_ids.as_slice().len() {
+            if storage.deleted_pairs.is_deleted(position) {
+                continue;
+            }
+            histogram.insert(
+                storage.point_at(position),
+                |x| storage.left_neighbour(x, position + 1),
+                |x| storage.right_neighbour(x, position + 1),
+            );
+        }
+
+        self.histogram = histogram;
+        self.points_count = config.points_count - storage.deleted_points.deleted_count();
+        self.values_count = config.values_count - storage.deleted_pairs.deleted_count();
+        self.max_values_per_point = config.max_values_per_point;
+        self.storage = Some(storage);
+        Ok(true)
+    }
+
+    pub(super) fn files(&self) -> Vec<PathBuf> {
+        let mut files = vec![
+            self.path.join(CONFIG_FILE),
+            self.path.join(PAIRS_VALUES_FILE),
+            self.path.join(PAIRS_IDS_FILE),
+            self.path.join(DELETED_PAIRS_FILE),
+            self.path.join(DELETED_POINTS_FILE),
+        ];
+        files.extend(MmapFlatVecs::<T>::files(&self.path, POINT_TO_VALUES));
+        files
+    }
+
+    pub(super) fn clear(self) -> OperationResult<()> {
+        remove_index_dir(&self.path)
+    }
+
+    pub(super) fn flusher(&self) -> Flusher {
+        match &self.storage {
+            Some(storage) => {
+                let deleted_pairs_flusher = storage.deleted_pairs.flusher();
+                let deleted_points_flusher = storage.deleted_points.flusher();
+                Box::new(move || {
+                    deleted_pairs_flusher()?;
+                    deleted_points_flusher()
+                })
+            }
+            None => Box::new(|| Ok(())),
+        }
+    }
+
+    pub(super) fn get_values(&self, idx: PointOffsetType) -> Option<&[T]> {
+        let storage = self.storage.as_ref()?;
+        if storage.deleted_points.is_deleted(idx as usize) {
+            return Some(&[]);
+        }
+        storage.point_to_values.get(idx as usize)
+    }
+
+    pub(super) fn get_values_count(&self) -> usize {
+        self.values_count
+    }
+
+    pub(super) fn values_range(
+        &self,
+        start_bound: Bound<NumericIndexKey<T>>,
+        end_bound: Bound<NumericIndexKey<T>>,
+    ) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.orderable_values_range(start_bound, end_bound)
+            .map(|(_, idx)| idx)
+    }
+
+    pub(super) fn orderable_values_range(
+        &self,
+        start_bound: Bound<NumericIndexKey<T>>,
+        end_bound: Bound<NumericIndexKey<T>>,
+    ) -> impl DoubleEndedIterator<Item = (T, PointOffsetType)> + '_ {
+        let storage = self.storage.as_ref();
+        let positions = storage.map_or(0..0, |storage| {
+            storage.range_positions(start_bound, end_bound)
+        });
+        positions.filter_map(move |position| {
+            let storage = storage?;
+            if storage.deleted_pairs.is_deleted(position) {
+                return None;
+            }
+            Some((
+                storage.pairs_values.as_slice()[position],
+                storage.pairs_ids.as_slice()[position],
+            ))
+        })
+    }
+
+    pub(super) fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let Some(storage) = &mut self.storage else {
+            return Ok(());
+        };
+        let values_count = storage
+            .point_to_values
+            .get(idx as usize)
+            .map_or(0, |values| values.len());
+        if values_count == 0 || !storage.deleted_points.delete(idx as usize) {
+            return Ok(());
+        }
+        self.points_count -= 1;
+
+        for value_position in 0..values_count {
+            let value = storage.point_to_values.get(idx as usize).unwrap()[value_position];
+            let key = NumericIndexKey::new(value, idx);
+            let len = storage.pairs_ids.as_slice().len();
+            let position = storage.partition_point(len, |other| other < &key);
+            if position >= len || storage.key_at(position) != key {
+                debug_assert!(false, "value-point pair must be in the index");
+                continue;
+            }
+            if storage.deleted_pairs.delete(position) {
+                self.values_count -= 1;
+                self.histogram.remove(
+                    &storage.point_at(position),
+                    |x| storage.left_neighbour(x, len),
+                    |x| storage.right_neighbour(x, len),
+                );
+            }
+        }
+        Ok(())
+    }
+}
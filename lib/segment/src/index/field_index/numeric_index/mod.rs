@@ -1,4 +1,5 @@
 mod immutable_numeric_index;
+mod mmap_numeric_index;
 mod mutable_numeric_index;
 
 #[cfg(test)]
@@ -7,6 +8,7 @@ mod tests;
 use std::cmp::{max, min};
 use std::ops::Bound;
 use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -18,6 +20,7 @@ use rocksdb::DB;
 use serde_json::Value;
 
 use self::immutable_numeric_index::{ImmutableNumericIndex, NumericIndexKey};
+use self::mmap_numeric_index::MmapNumericIndex;
 use super::utils::check_boundaries;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
@@ -142,6 +145,7 @@ impl<T: Encodable + Numericable> Range<T> {
 pub enum NumericIndex<T: Encodable + Numericable + Default> {
     Mutable(MutableNumericIndex<T>),
     Immutable(ImmutableNumericIndex<T>),
+    Mmap(MmapNumericIndex<T>),
 }
 
 impl<T: Encodable + Numericable + Default> NumericIndex<T> {
@@ -153,10 +157,38 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         }
     }
 
-    fn get_db_wrapper(&self) -> &DatabaseColumnWrapper {
+    /// Create an on-disk index, stored in `path`. It has to be loaded before use.
+    pub fn new_mmap(path: &Path) -> Self {
+        NumericIndex::Mmap(MmapNumericIndex::new(path))
+    }
+
+    /// Convert a mutable index into an on-disk index, stored in `path`.
+    ///
+    /// The data of the mutable index is removed from RocksDB.
+    pub fn into_mmap(self, path: &Path) -> OperationResult<Self> {
+        let NumericIndex::Mutable(index) = self else {
+            return Err(OperationError::service_error(
+                "Only mutable numeric index can be converted into on-disk index",
+            ));
+        };
+        MmapNumericIndex::build(path, &index)?;
+        index.get_db_wrapper().remove_column_family()?;
+
+        let mut mmap_index = MmapNumericIndex::new(path);
+        if !mmap_index.load()? {
+            return Err(OperationError::service_error(format!(
+                "Failed to load on-disk numeric index from {}",
+                path.display(),
+            )));
+        }
+        Ok(NumericIndex::Mmap(mmap_index))
+    }
+
+    fn get_db_wrapper(&self) -> Option<&DatabaseColumnWrapper> {
         match self {
-            NumericIndex::Mutable(index) => index.get_db_wrapper(),
-            NumericIndex::Immutable(index) => index.get_db_wrapper(),
+            NumericIndex::Mutable(index) => Some(index.get_db_wrapper()),
+            NumericIndex::Immutable(index) => Some(index.get_db_wrapper()),
+            NumericIndex::Mmap(_) => None,
         }
     }
 
@@ -164,6 +196,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => &index.histogram,
             NumericIndex::Immutable(index) => &index.histogram,
+            NumericIndex::Mmap(index) => &index.histogram,
         }
     }
 
@@ -171,6 +204,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.points_count,
             NumericIndex::Immutable(index) => index.points_count,
+            NumericIndex::Mmap(index) => index.points_count,
         }
     }
 
@@ -178,6 +212,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.get_values_count(),
             NumericIndex::Immutable(index) => index.get_values_count(),
+            NumericIndex::Mmap(index) => index.get_values_count(),
         }
     }
 
@@ -186,24 +221,41 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        match self.get_db_wrapper() {
+            Some(db_wrapper) => db_wrapper.recreate_column_family(),
+            None => Ok(()),
+        }
     }
 
     pub fn load(&mut self) -> OperationResult<bool> {
         match self {
             NumericIndex::Mutable(index) => index.load(),
             NumericIndex::Immutable(index) => index.load(),
+            NumericIndex::Mmap(index) => index.load(),
         }
     }
 
     pub fn flusher(&self) -> Flusher {
-        self.get_db_wrapper().flusher()
+        match self {
+            NumericIndex::Mutable(index) => index.get_db_wrapper().flusher(),
+            NumericIndex::Immutable(index) => index.get_db_wrapper().flusher(),
+            NumericIndex::Mmap(index) => index.flusher(),
+        }
+    }
+
+    /// Files of the on-disk index. In-memory indexes are stored in RocksDB and have no own files.
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            NumericIndex::Mutable(_) | NumericIndex::Immutable(_) => vec![],
+            NumericIndex::Mmap(index) => index.files(),
+        }
     }
 
     pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
         match self {
             NumericIndex::Mutable(index) => index.remove_point(idx),
             NumericIndex::Immutable(index) => index.remove_point(idx),
+            NumericIndex::Mmap(index) => index.remove_point(idx),
         }
     }
 
@@ -211,6 +263,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.get_values(idx),
             NumericIndex::Immutable(index) => index.get_values(idx),
+            NumericIndex::Mmap(index) => index.get_values(idx),
         }
     }
 
@@ -223,6 +276,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.max_values_per_point,
             NumericIndex::Immutable(index) => index.max_values_per_point,
+            NumericIndex::Mmap(index) => index.max_values_per_point,
         }
     }
 
@@ -324,7 +378,11 @@ impl<T: Encodable + Numericable + Default> PayloadFieldIndex for NumericIndex<T>
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        match self {
+            NumericIndex::Mutable(index) => index.get_db_wrapper().recreate_column_family(),
+            NumericIndex::Immutable(index) => index.get_db_wrapper().recreate_column_family(),
+            NumericIndex::Mmap(index) => index.clear(),
+        }
     }
 
     fn flusher(&self) -> Flusher {
@@ -361,6 +419,7 @@ impl<T: Encodable + Numericable + Default> PayloadFieldIndex for NumericIndex<T>
                 Box::new(index.values_range(start_bound, end_bound))
             }
             NumericIndex::Immutable(index) => Box::new(index.values_range(start_bound, end_bound)),
+            NumericIndex::Mmap(index) => Box::new(index.values_range(start_bound, end_bound)),
         })
     }
 
@@ -460,9 +519,9 @@ impl ValueIndexer<IntPayloadType> for NumericIndex<IntPayloadType> {
     ) -> OperationResult<()> {
         match self {
             NumericIndex::Mutable(index) => index.add_many_to_list(id, values),
-            NumericIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable numeric index",
-            )),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable numeric index"),
+            ),
         }
     }
 
@@ -485,9 +544,9 @@ impl ValueIndexer<DateTimePayloadType> for NumericIndex<IntPayloadType> {
             NumericIndex::Mutable(index) => {
                 index.add_many_to_list(id, values.into_iter().map(|x| x.timestamp()))
             }
-            NumericIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable numeric index",
-            )),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable numeric index"),
+            ),
         }
     }
 
@@ -508,9 +567,9 @@ impl ValueIndexer<FloatPayloadType> for NumericIndex<FloatPayloadType> {
     ) -> OperationResult<()> {
         match self {
             NumericIndex::Mutable(index) => index.add_many_to_list(id, values),
-            NumericIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable numeric index",
-            )),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable numeric index"),
+            ),
         }
     }
 
@@ -554,6 +613,9 @@ where
             NumericIndex::Immutable(index) => {
                 Box::new(index.orderable_values_range(start_bound, end_bound))
             }
+            NumericIndex::Mmap(index) => {
+                Box::new(index.orderable_values_range(start_bound, end_bound))
+            }
         }
     }
 }
@@ -35,7 +35,7 @@ fn random_index(
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(i as PointOffsetType, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => unreachable!("index is mutable"),
         }
     }
 
@@ -43,7 +43,7 @@ fn random_index(
 
     // if immutable, we have to reload the index
     if immutable {
-        let db_ref = index.get_db_wrapper().database.clone();
+        let db_ref = index.get_db_wrapper().unwrap().database.clone();
         let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, false);
         new_index.load().unwrap();
         (temp_dir, new_index)
@@ -206,14 +206,14 @@ fn test_payload_blocks_small(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => unreachable!("index is mutable"),
         });
 
     index.flusher()().unwrap();
 
     // if immutable, we have to reload the index
     let index = if immutable {
-        let db_ref = index.get_db_wrapper().database.clone();
+        let db_ref = index.get_db_wrapper().unwrap().database.clone();
         let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, false);
         new_index.load().unwrap();
         new_index
@@ -250,12 +250,12 @@ fn test_numeric_index_load_from_disk(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => unreachable!("index is mutable"),
         });
 
     index.flusher()().unwrap();
 
-    let db_ref = index.get_db_wrapper().database.clone();
+    let db_ref = index.get_db_wrapper().unwrap().database.clone();
     let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, !immutable);
     new_index.load().unwrap();
 
@@ -296,14 +296,14 @@ fn test_numeric_index(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => unreachable!("index is mutable"),
         });
 
     index.flusher()().unwrap();
 
     // if immutable, we have to reload the index
     let index = if immutable {
-        let db_ref = index.get_db_wrapper().database.clone();
+        let db_ref = index.get_db_wrapper().unwrap().database.clone();
         let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, false);
         new_index.load().unwrap();
         new_index
@@ -404,3 +404,80 @@ fn test_empty_cardinality(#[case] immutable: bool) {
         },
     );
 }
+
+#[test]
+fn test_mmap_numeric_index() {
+    let (temp_dir, index) = random_index(1000, 2, false);
+
+    let ranges = [
+        Range {
+            lt: Some(20.0),
+            gt: None,
+            gte: Some(10.0),
+            lte: None,
+        },
+        Range {
+            lt: None,
+            gt: Some(50.0),
+            gte: None,
+            lte: Some(60.0),
+        },
+        Range {
+            lt: None,
+            gt: None,
+            gte: None,
+            lte: None,
+        },
+    ];
+
+    let filter = |index: &NumericIndex<f64>, range: &Range<FloatPayloadType>| {
+        index
+            .filter(&FieldCondition::new_range(path("unused"), range.clone()))
+            .unwrap()
+            .unique()
+            .sorted()
+            .collect_vec()
+    };
+
+    let expected = ranges
+        .iter()
+        .map(|range| filter(&index, range))
+        .collect_vec();
+    let expected_values = (0..1000).map(|idx| index.get_values(idx).unwrap().to_vec());
+    let expected_values = expected_values.collect_vec();
+
+    let mmap_path = temp_dir.path().join("mmap_index");
+    let mut index = index.into_mmap(&mmap_path).unwrap();
+    assert!(matches!(index, NumericIndex::Mmap(_)));
+    assert_eq!(index.count_indexed_points(), 1000);
+
+    for (range, expected) in ranges.iter().zip(&expected) {
+        assert_eq!(&filter(&index, range), expected);
+        cardinality_request(&index, range.clone());
+    }
+    for (idx, values) in expected_values.iter().enumerate() {
+        assert_eq!(index.get_values(idx as PointOffsetType).unwrap(), values);
+    }
+
+    let removed = |idx: &PointOffsetType| idx % 3 == 0;
+    for idx in (0..1000).filter(removed) {
+        index.remove_point(idx).unwrap();
+    }
+    index.flusher()().unwrap();
+    drop(index);
+
+    let mut index = NumericIndex::<f64>::new_mmap(&mmap_path);
+    assert!(index.load().unwrap());
+    assert_eq!(index.count_indexed_points(), 666);
+    assert_eq!(index.get_values_count(), 666 * 2);
+    assert!(index.values_is_empty(0));
+
+    for (range, expected) in ranges.iter().zip(&expected) {
+        let expected = expected.iter().copied().filter(|idx| !removed(idx));
+        assert_eq!(filter(&index, range), expected.collect_vec());
+        cardinality_request(&index, range.clone());
+    }
+
+    index.clear().unwrap();
+    assert!(!mmap_path.exists());
+}
@@ -91,6 +91,46 @@ impl EntryPoints {
         None
     }
 
+    /// Remove point from entry points, e.g. before linking it into the graph again.
+    ///
+    /// If there are no main entry points left, the highest extra entry point takes the place.
+    pub fn remove_point(&mut self, point_id: PointOffsetType) {
+        self.entry_points.retain(|entry| entry.point_id != point_id);
+
+        if self
+            .extra_entry_points
+            .iter()
+            .any(|entry| entry.point_id == point_id)
+        {
+            let mut extra_entry_points =
+                FixedLengthPriorityQueue::new(self.extra_entry_points.capacity());
+            for entry in self.extra_entry_points.iter() {
+                if entry.point_id != point_id {
+                    extra_entry_points.push(entry.clone());
+                }
+            }
+            self.extra_entry_points = extra_entry_points;
+        }
+
+        if self.entry_points.is_empty() {
+            if let Some(entry) = self.extra_entry_points.iter().max_by_key(|ep| ep.level) {
+                self.entry_points.push(entry.clone());
+            }
+        }
+    }
+
+    /// Change the number of extra entry points, e.g. as the graph grows.
+    pub fn set_extra_entry_points_num(&mut self, extra_entry_points: usize) {
+        if self.extra_entry_points.capacity() == extra_entry_points {
+            return;
+        }
+        let mut resized = FixedLengthPriorityQueue::new(extra_entry_points);
+        for entry in self.extra_entry_points.iter() {
+            resized.push(entry.clone());
+        }
+        self.extra_entry_points = resized;
+    }
+
     /// Find the highest `EntryPoint` which satisfies filtering condition of `checker`
     pub fn get_entry_point<F>(&self, checker: F) -> Option<EntryPoint>
     where
@@ -141,4 +181,27 @@ mod tests {
         assert_eq!(points.entry_points.len(), 5);
         assert_eq!(points.extra_entry_points.len(), 10);
     }
+
+    #[test]
+    fn test_remove_entry_point() {
+        let mut points = EntryPoints::new(2);
+
+        for (point_id, level) in [(0, 1), (1, 3), (2, 2), (3, 0)] {
+            points.new_point(point_id, level, |_x| true);
+        }
+        assert_eq!(points.entry_points[0].point_id, 1);
+
+        // The highest remaining point becomes the main entry point
+        points.remove_point(1);
+        assert_eq!(points.entry_points.len(), 1);
+        assert_eq!(points.entry_points[0].point_id, 2);
+        assert!(points.extra_entry_points.iter().all(|ep| ep.point_id != 1));
+        assert_eq!(points.get_entry_point(|x| x != 2).unwrap().point_id, 0);
+
+        points.set_extra_entry_points_num(10);
+        for point_id in 4..20 {
+            points.new_point(point_id, 0, |_x| true);
+        }
+        assert_eq!(points.extra_entry_points.len(), 10);
+    }
 }
@@ -12,7 +12,7 @@ use rand::Rng;
 
 use super::graph_links::GraphLinks;
 use crate::common::operation_error::OperationResult;
-use crate::index::hnsw_index::entry_points::EntryPoints;
+use crate::index::hnsw_index::entry_points::{EntryPoint, EntryPoints};
 use crate::index::hnsw_index::graph_layers::{GraphLayers, GraphLayersBase, LinkContainer};
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
@@ -191,10 +191,157 @@ impl GraphLayersBuilder {
             .merge_from_other(other.entry_points.into_inner());
     }
 
+    /// Seed the builder with links of an already built graph.
+    ///
+    /// `old_to_new` maps point ids of the `graph` into point ids of this builder.
+    /// Points mapped to `None` are skipped, together with all links pointing to them.
+    /// Seeded points keep their levels and remaining links. Points which kept all their links are
+    /// marked as ready, points which lost some links are not, so they have to be linked again
+    /// with `link_new_point`, as well as the points which are not seeded.
+    ///
+    /// Returns the list of seeded points.
+    pub fn fill_from_graph<TGraphLinks: GraphLinks>(
+        &mut self,
+        graph: &GraphLayers<TGraphLinks>,
+        old_to_new: &[Option<PointOffsetType>],
+    ) -> BitVec {
+        let mut seeded = BitVec::repeat(false, self.num_points());
+        for (old_id, new_id) in old_to_new.iter().enumerate() {
+            let Some(new_id) = *new_id else {
+                continue;
+            };
+            let old_id = old_id as PointOffsetType;
+            let level = graph.point_level(old_id);
+
+            let mut lost_links = false;
+            let remapped_links: Vec<LinkContainer> = (0..=level)
+                .map(|level| {
+                    let links = graph.links.links(old_id, level);
+                    let remapped: LinkContainer = links
+                        .iter()
+                        .filter_map(|&link| old_to_new.get(link as usize).copied().flatten())
+                        .collect();
+                    lost_links |= remapped.len() < links.len();
+                    remapped
+                })
+                .collect();
+
+            self.set_levels(new_id, level);
+            for (level, links) in remapped_links.into_iter().enumerate() {
+                *self.links_layers[new_id as usize][level].get_mut() = links;
+            }
+            seeded.set(new_id as usize, true);
+
+            // Remaining links are kept as candidates, the point is linked again to replace the
+            // lost ones
+            if lost_links {
+                continue;
+            }
+
+            self.entry_points
+                .get_mut()
+                .new_point(new_id, level, |_| true);
+            self.ready_list.get_mut().set(new_id as usize, true);
+        }
+        seeded
+    }
+
     fn num_points(&self) -> usize {
         self.links_layers.len()
     }
 
+    /// Number of points, which are already linked into the graph
+    pub fn num_ready_points(&self) -> usize {
+        self.ready_list.read().count_ones()
+    }
+
+    pub(super) fn is_ready(&self, point_id: PointOffsetType) -> bool {
+        self.ready_list
+            .read()
+            .get(point_id as usize)
+            .map(|x| *x)
+            .unwrap_or(false)
+    }
+
+    /// Prepare point to be linked into the graph with `link_new_point`.
+    ///
+    /// New points get a random level, and the graph grows to fit them.
+    /// Points which are already in the graph keep their level, but are unlinked from the graph,
+    /// so they can be linked again, e.g. after their vector has changed.
+    ///
+    /// Unlinking looks through links of all points on levels of the point, so it takes time
+    /// linear to the size of the graph.
+    pub fn reset_point<R>(&mut self, point_id: PointOffsetType, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        let ready_list = self.ready_list.get_mut();
+        if ready_list.len() <= point_id as usize {
+            ready_list.resize(point_id as usize + 1, false);
+        }
+        let was_ready = ready_list[point_id as usize];
+        ready_list.set(point_id as usize, false);
+
+        if was_ready {
+            let level = self.get_point_level(point_id);
+            for links in &mut self.links_layers[point_id as usize] {
+                links.get_mut().clear();
+            }
+            // Links to the point were chosen by its previous vector
+            for point_layers in &mut self.links_layers {
+                for links in point_layers.iter_mut().take(level + 1) {
+                    links.get_mut().retain(|&link| link != point_id);
+                }
+            }
+            self.entry_points.get_mut().remove_point(point_id);
+        } else {
+            let level = self.get_random_layer(rng);
+            self.set_levels(point_id, level);
+        }
+    }
+
+    /// Search for the nearest points in the graph, which is not yet converted into `GraphLayers`.
+    ///
+    /// Only points, which are already linked into the graph, are considered.
+    pub fn search(
+        &self,
+        top: usize,
+        ef: usize,
+        mut points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+    ) -> Vec<ScoredPointOffset> {
+        let entry_point = custom_entry_points
+            .and_then(|custom_entry_points| {
+                custom_entry_points
+                    .iter()
+                    .filter(|&&point_id| {
+                        self.is_ready(point_id) && points_scorer.check_vector(point_id)
+                    })
+                    .map(|&point_id| EntryPoint {
+                        point_id,
+                        level: self.get_point_level(point_id),
+                    })
+                    .max_by_key(|ep| ep.level)
+            })
+            .or_else(|| {
+                self.entry_points
+                    .lock()
+                    .get_entry_point(|point_id| points_scorer.check_vector(point_id))
+            });
+        let Some(entry_point) = entry_point else {
+            return Vec::default();
+        };
+
+        let zero_level_entry = self.search_entry(
+            entry_point.point_id,
+            entry_point.level,
+            0,
+            &mut points_scorer,
+        );
+        let nearest = self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer);
+        nearest.into_iter().take(top).collect()
+    }
+
     /// Generate random level for a new point, according to geometric distribution
     pub fn get_random_layer<R>(&self, rng: &mut R) -> usize
     where
@@ -206,6 +353,11 @@ impl GraphLayersBuilder {
         picked_level.round() as usize
     }
 
+    #[cfg(test)]
+    pub(super) fn point_links(&self, point_id: PointOffsetType, level: usize) -> LinkContainer {
+        self.links_layers[point_id as usize][level].read().clone()
+    }
+
     fn get_point_level(&self, point_id: PointOffsetType) -> usize {
         self.links_layers[point_id as usize].len() - 1
     }
@@ -235,6 +387,10 @@ impl GraphLayersBuilder {
     ) where
         F: FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
     {
+        if links.contains(&new_point_id) {
+            return;
+        }
+
         // ToDo: binary search here ? (most likely does not worth it)
         let new_to_target = score_internal(target_point_id, new_point_id);
 
@@ -387,6 +543,10 @@ impl GraphLayersBuilder {
                         for &other_point in &selected_nearest {
                             let mut other_point_links =
                                 self.links_layers[other_point as usize][curr_level].write();
+                            if other_point_links.contains(&point_id) {
+                                // Already linked, e.g. the point is linked again after reuse
+                                continue;
+                            }
                             if other_point_links.len() < level_m {
                                 // If linked point is lack of neighbours
                                 other_point_links.push(point_id);
@@ -658,6 +818,112 @@ mod tests {
         assert_eq!(reference_top.into_vec(), graph_search);
     }
 
+    #[test]
+    fn test_fill_from_graph() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let (vector_holder, old_graph_builder) =
+            create_graph_layer::<M, _>(num_vectors, dim, false, &mut rng);
+        let old_graph = old_graph_builder
+            .into_graph_layers::<GraphLinksRam>(None)
+            .unwrap();
+
+        // Pretend every 10th point was changed since the old graph was built
+        let old_to_new: Vec<_> = (0..num_vectors as PointOffsetType)
+            .map(|idx| (idx % 10 != 0).then_some(idx))
+            .collect();
+
+        let mut graph_layers_builder =
+            GraphLayersBuilder::new(num_vectors, M, M * 2, 16, 10, false);
+        let seeded = graph_layers_builder.fill_from_graph(&old_graph, &old_to_new);
+
+        assert!(seeded.count_ones() > 0);
+        let mut relinked = 0;
+        for idx in 0..num_vectors as PointOffsetType {
+            if seeded[idx as usize] {
+                assert!(old_to_new[idx as usize].is_some());
+                assert_eq!(
+                    graph_layers_builder.get_point_level(idx),
+                    old_graph.point_level(idx)
+                );
+                let links = graph_layers_builder.links_layers[idx as usize][0].read();
+                assert!(links.iter().all(|&link| link % 10 != 0));
+
+                // Points which lost any of their links must be linked again
+                let lost_links = (0..=old_graph.point_level(idx)).any(|level| {
+                    old_graph
+                        .links
+                        .links(idx, level)
+                        .iter()
+                        .any(|&link| link % 10 == 0)
+                });
+                assert_eq!(graph_layers_builder.is_ready(idx), !lost_links);
+                if lost_links {
+                    relinked += 1;
+                }
+            } else {
+                assert!(!graph_layers_builder.is_ready(idx));
+            }
+        }
+        assert!(relinked > 0);
+
+        for idx in 0..num_vectors as PointOffsetType {
+            if !seeded[idx as usize] {
+                let level = graph_layers_builder.get_random_layer(&mut rng);
+                graph_layers_builder.set_levels(idx, level);
+            }
+        }
+        for idx in 0..num_vectors as PointOffsetType {
+            if graph_layers_builder.is_ready(idx) {
+                continue;
+            }
+            let fake_filter_context = FakeFilterContext {};
+            let added_vector = vector_holder.vectors.get(idx).to_vec();
+            let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            graph_layers_builder.link_new_point(idx, scorer);
+        }
+
+        let graph = graph_layers_builder
+            .into_graph_layers::<GraphLinksRam>(None)
+            .unwrap();
+
+        let top = 5;
+        let ef = 64;
+        let attempts = 10;
+        let mut hits = 0;
+        for _ in 0..attempts {
+            let query = random_vector(&mut rng, dim);
+            let processed_query = <M as Metric<VectorElementType>>::preprocess(query.clone());
+            let mut reference_top = FixedLengthPriorityQueue::new(top);
+            for idx in 0..vector_holder.vectors.len() as PointOffsetType {
+                let vec = &vector_holder.vectors.get(idx);
+                reference_top.push(ScoredPointOffset {
+                    idx,
+                    score: M::similarity(vec, &processed_query),
+                });
+            }
+            let reference_top = reference_top.into_vec();
+
+            let fake_filter_context = FakeFilterContext {};
+            let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            let graph_search = graph.search(top, ef, scorer, None);
+
+            hits += graph_search
+                .iter()
+                .filter(|found| reference_top.iter().any(|x| x.idx == found.idx))
+                .count();
+        }
+
+        assert!(hits as f64 / (top * attempts) as f64 > 0.9);
+    }
+
     #[test]
     fn test_add_points() {
         let num_vectors = 1000;
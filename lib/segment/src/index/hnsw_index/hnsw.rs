@@ -1,3 +1,5 @@
+use std::cmp::min;
+use std::collections::BTreeSet;
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
@@ -5,7 +7,7 @@ use std::sync::Arc;
 use std::thread;
 
 use atomic_refcell::AtomicRefCell;
-use bitvec::prelude::BitSlice;
+use bitvec::prelude::{BitSlice, BitVec};
 #[cfg(target_os = "linux")]
 use common::cpu::linux_low_thread_priority;
 use common::cpu::CpuPermit;
@@ -30,12 +32,14 @@ use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
 use crate::index::hnsw_index::config::HnswGraphConfig;
 use crate::index::hnsw_index::graph_layers::GraphLayers;
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use crate::index::hnsw_index::num_rayon_threads;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::query_estimator::adjust_to_available_vectors;
 use crate::index::sample_estimation::sample_check_cardinality;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
-use crate::index::{PayloadIndex, VectorIndex};
+use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
+use crate::spaces::tools::peek_top_largest_iterable;
 use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::Condition::Field;
 use crate::types::{
@@ -57,6 +61,16 @@ const SINGLE_THREADED_HNSW_BUILD_THRESHOLD: usize = 32;
 #[cfg(not(debug_assertions))]
 const SINGLE_THREADED_HNSW_BUILD_THRESHOLD: usize = 256;
 
+/// Max fraction of points of an old graph, which may be deleted or changed since the old graph
+/// was built, for it to still be reused as a base of the new graph.
+/// Otherwise, the new graph is built from scratch.
+const HNSW_GRAPH_REUSE_MAX_DELETED_RATIO: f64 = 0.2;
+
+/// Number of extra entry points of a graph, which grows with the number of points in it
+fn extra_entry_points_num(num_vectors: usize, full_scan_threshold: usize) -> usize {
+    (num_vectors.checked_div(full_scan_threshold).unwrap_or(0) * 10).max(1)
+}
+
 pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
@@ -65,6 +79,12 @@ pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     config: HnswGraphConfig,
     path: PathBuf,
     graph: Option<GraphLayers<TGraphLinks>>,
+    // Graph of appendable index, updated points are linked into it with `link_pending_points`
+    appendable_graph: Option<GraphLayersBuilder>,
+    // Points of the appendable graph, which are not linked yet, searches score them all
+    pending_points: Mutex<BTreeSet<PointOffsetType>>,
+    // Held while pending points are linked, so that each point is linked once
+    linking_lock: Mutex<()>,
     searches_telemetry: HNSWSearchesTelemetry,
 }
 
@@ -130,6 +150,9 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             config,
             path: path.to_owned(),
             graph,
+            appendable_graph: None,
+            pending_points: Default::default(),
+            linking_lock: Default::default(),
             searches_telemetry: HNSWSearchesTelemetry {
                 unfiltered_hnsw: OperationDurationsAggregator::new(),
                 unfiltered_plain: OperationDurationsAggregator::new(),
@@ -141,11 +164,151 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         })
     }
 
-    #[cfg(test)]
-    pub(super) fn graph(&self) -> Option<&GraphLayers<TGraphLinks>> {
+    /// Open index, which links new points into its graph on every update.
+    ///
+    /// Graph is kept in RAM only, it is built from vectors of the storage on open.
+    pub fn open_appendable(
+        path: &Path,
+        id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+        vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+        quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
+        payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+        hnsw_config: HnswConfig,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self> {
+        let mut index = Self::open(
+            path,
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            hnsw_config,
+        )?;
+        // Persisted graph is not used by appendable index
+        index.graph = None;
+
+        let total_vector_count = index.vector_storage.borrow().total_vector_count();
+        index.appendable_graph = Some(GraphLayersBuilder::new(
+            total_vector_count,
+            index.config.m,
+            index.config.m0,
+            index.config.ef_construct,
+            extra_entry_points_num(total_vector_count, index.config.full_scan_threshold),
+            HNSW_USE_HEURISTIC,
+        ));
+
+        let ids: Vec<_> = {
+            let id_tracker = index.id_tracker.borrow();
+            let vector_storage = index.vector_storage.borrow();
+            id_tracker
+                .iter_ids_excluding(vector_storage.deleted_vector_bitslice())
+                .collect()
+        };
+        for vector_id in ids {
+            check_process_stopped(stopped)?;
+            index.reset_appendable_point(vector_id)?;
+        }
+        index.link_pending_points(stopped)?;
+
+        Ok(index)
+    }
+
+    /// Unlink point from the appendable graph, and queue it to be linked with its current vector.
+    fn reset_appendable_point(&mut self, point_id: PointOffsetType) -> OperationResult<()> {
+        let Some(graph) = &mut self.appendable_graph else {
+            return Err(OperationError::service_error("Cannot update HNSW index"));
+        };
+
+        graph.reset_point(point_id, &mut thread_rng());
+
+        let vector_storage = self.vector_storage.borrow();
+        graph
+            .get_entry_points()
+            .set_extra_entry_points_num(extra_entry_points_num(
+                vector_storage.total_vector_count(),
+                self.config.full_scan_threshold,
+            ));
+
+        // Deleted vectors are not linked, but still occupy a place in the graph
+        let pending_points = self.pending_points.get_mut();
+        if self.config.m == 0 || vector_storage.is_deleted_vector(point_id) {
+            pending_points.remove(&point_id);
+        } else {
+            pending_points.insert(point_id);
+        }
+
+        Ok(())
+    }
+
+    /// Link points, which were updated since the last call, into the appendable graph.
+    ///
+    /// Index is not changed otherwise, so searches may run while points are linked.
+    /// Until then, searches score pending points one by one.
+    pub fn link_pending_points(&self, stopped: &AtomicBool) -> OperationResult<()> {
+        let Some(graph) = &self.appendable_graph else {
+            return Ok(());
+        };
+        // Points are being linked by another thread already
+        let Some(_linking_guard) = self.linking_lock.try_lock() else {
+            return Ok(());
+        };
+
+        let pending_points: Vec<_> = self.pending_points.lock().iter().copied().collect();
+        if pending_points.is_empty() {
+            return Ok(());
+        }
+
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        let link_point = |point_id: PointOffsetType| -> OperationResult<()> {
+            check_process_stopped(stopped)?;
+            let vector = vector_storage.get_vector(point_id);
+            let raw_scorer = new_raw_scorer(
+                vector.as_vec_ref().into(),
+                &vector_storage,
+                id_tracker.deleted_point_bitslice(),
+            )?;
+            let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
+            graph.link_new_point(point_id, points_scorer);
+            self.pending_points.lock().remove(&point_id);
+            Ok(())
+        };
+
+        // First points are linked by a single thread, to avoid disconnected components in the graph
+        let (first_few_points, other_points) = pending_points.split_at(min(
+            pending_points.len(),
+            SINGLE_THREADED_HNSW_BUILD_THRESHOLD,
+        ));
+        for &point_id in first_few_points {
+            link_point(point_id)?;
+        }
+
+        if !other_points.is_empty() {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .thread_name(|idx| format!("hnsw-link-{idx}"))
+                .num_threads(num_rayon_threads(self.config.max_indexing_threads))
+                .build()?;
+            pool.install(|| {
+                other_points
+                    .par_iter()
+                    .try_for_each(|&point_id| link_point(point_id))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "testing")]
+    pub fn graph(&self) -> Option<&GraphLayers<TGraphLinks>> {
         self.graph.as_ref()
     }
 
+    #[cfg(test)]
+    pub(super) fn appendable_graph(&self) -> Option<&GraphLayersBuilder> {
+        self.appendable_graph.as_ref()
+    }
+
     pub fn get_quantized_vectors(&self) -> Arc<AtomicRefCell<Option<QuantizedVectors>>> {
         self.quantized_vectors.clone()
     }
@@ -261,6 +424,141 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         Ok(())
     }
 
+    /// Map points of the `old_index` graph into points of this index.
+    ///
+    /// Point is mapped only if it is present in both indices with the same version,
+    /// so its vector and links remain valid.
+    /// Returns the mapping together with the number of mapped points, or `None` if the old graph
+    /// is not compatible with this index or too many of its points were deleted.
+    fn map_old_graph_points<TOldGraphLinks: GraphLinks>(
+        &self,
+        old_index: &HNSWIndex<TOldGraphLinks>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<(Vec<Option<PointOffsetType>>, usize)>> {
+        let Some(old_graph) = &old_index.graph else {
+            return Ok(None);
+        };
+        if old_index.config.m != self.config.m
+            || old_index.config.m0 != self.config.m0
+            || old_index.config.ef_construct != self.config.ef_construct
+        {
+            return Ok(None);
+        }
+
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let old_id_tracker = old_index.id_tracker.borrow();
+        let old_vector_storage = old_index.vector_storage.borrow();
+        let old_deleted_bitslice = old_vector_storage.deleted_vector_bitslice();
+
+        let mut old_to_new = vec![None; old_graph.num_points()];
+        let mut mapped_count = 0;
+
+        for new_id in id_tracker.iter_ids_excluding(vector_storage.deleted_vector_bitslice()) {
+            check_process_stopped(stopped)?;
+            let Some(old_id) = id_tracker
+                .external_id(new_id)
+                .and_then(|external_id| old_id_tracker.internal_id(external_id))
+            else {
+                continue;
+            };
+            if old_id as usize >= old_to_new.len()
+                || old_deleted_bitslice
+                    .get(old_id as usize)
+                    .map(|x| *x)
+                    .unwrap_or(false)
+            {
+                continue;
+            }
+            if old_id_tracker.internal_version(old_id) != id_tracker.internal_version(new_id) {
+                continue;
+            }
+            // Point was not linked into the old graph
+            if old_graph.links.links(old_id, 0).is_empty() {
+                continue;
+            }
+            old_to_new[old_id as usize] = Some(new_id);
+            mapped_count += 1;
+        }
+
+        let old_linked_count = (0..old_graph.num_points() as PointOffsetType)
+            .filter(|&old_id| !old_graph.links.links(old_id, 0).is_empty())
+            .count();
+        let deleted_count = old_linked_count.saturating_sub(mapped_count);
+
+        if mapped_count == 0
+            || deleted_count as f64 > old_linked_count as f64 * HNSW_GRAPH_REUSE_MAX_DELETED_RATIO
+        {
+            return Ok(None);
+        }
+
+        Ok(Some((old_to_new, mapped_count)))
+    }
+
+    /// Seed `graph_layers_builder` with the graph of the old index, which has the most points
+    /// in common with this index.
+    ///
+    /// Returns the list of seeded points, these points keep their levels. Seeded points which are
+    /// not ready in the builder have lost some links, and have to be linked again.
+    fn reuse_old_graph(
+        &self,
+        old_indices: &[Arc<AtomicRefCell<VectorIndexEnum>>],
+        graph_layers_builder: &mut GraphLayersBuilder,
+        stopped: &AtomicBool,
+    ) -> OperationResult<BitVec> {
+        if self.config.m == 0 {
+            return Ok(BitVec::new());
+        }
+
+        let mut best_mapping: Option<(usize, Vec<Option<PointOffsetType>>, usize)> = None;
+        for (index_idx, old_index) in old_indices.iter().enumerate() {
+            let mapping = match &*old_index.borrow() {
+                VectorIndexEnum::HnswRam(old_index) => {
+                    self.map_old_graph_points(old_index, stopped)?
+                }
+                VectorIndexEnum::HnswMmap(old_index) => {
+                    self.map_old_graph_points(old_index, stopped)?
+                }
+                VectorIndexEnum::Plain(_)
+                | VectorIndexEnum::SparseRam(_)
+                | VectorIndexEnum::SparseImmutableRam(_)
+                | VectorIndexEnum::SparseMmap(_) => None,
+            };
+            let Some((old_to_new, mapped_count)) = mapping else {
+                continue;
+            };
+            let is_better = best_mapping
+                .as_ref()
+                .map(|(_, _, best_count)| mapped_count > *best_count)
+                .unwrap_or(true);
+            if is_better {
+                best_mapping = Some((index_idx, old_to_new, mapped_count));
+            }
+        }
+
+        let Some((index_idx, old_to_new, mapped_count)) = best_mapping else {
+            return Ok(BitVec::new());
+        };
+
+        debug!("reusing existing HNSW graph with {mapped_count} points");
+
+        let seeded = match &*old_indices[index_idx].borrow() {
+            VectorIndexEnum::HnswRam(old_index) => old_index
+                .graph
+                .as_ref()
+                .map(|graph| graph_layers_builder.fill_from_graph(graph, &old_to_new)),
+            VectorIndexEnum::HnswMmap(old_index) => old_index
+                .graph
+                .as_ref()
+                .map(|graph| graph_layers_builder.fill_from_graph(graph, &old_to_new)),
+            VectorIndexEnum::Plain(_)
+            | VectorIndexEnum::SparseRam(_)
+            | VectorIndexEnum::SparseImmutableRam(_)
+            | VectorIndexEnum::SparseMmap(_) => None,
+        };
+        Ok(seeded.unwrap_or_default())
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn search_with_graph(
         &self,
@@ -299,14 +597,35 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let filter_context = filter.map(|f| payload_index.filter_context(f));
         let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
 
-        match &self.graph {
-            Some(graph) => {
-                let search_result =
-                    graph.search(oversampled_top, ef, points_scorer, custom_entry_points);
-                self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
+        let search_result = match (&self.appendable_graph, &self.graph) {
+            (Some(appendable_graph), _) => {
+                // Points, which are not linked yet, are not reachable in the graph
+                let pending_points = self.pending_points.lock().clone();
+                let graph_result = appendable_graph.search(
+                    oversampled_top,
+                    ef,
+                    points_scorer,
+                    custom_entry_points,
+                );
+
+                let mut pending_ids: Vec<_> = pending_points.iter().copied().collect();
+                let mut pending_scorer =
+                    FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
+                let pending_result = pending_scorer.score_points(&mut pending_ids, 0);
+
+                // Points linked during the search may be found both ways
+                let candidates = graph_result
+                    .into_iter()
+                    .filter(|scored| !pending_points.contains(&scored.idx))
+                    .chain(pending_result.iter().copied());
+                peek_top_largest_iterable(candidates, oversampled_top)
             }
-            None => Ok(Default::default()),
-        }
+            (None, Some(graph)) => {
+                graph.search(oversampled_top, ef, points_scorer, custom_entry_points)
+            }
+            (None, None) => return Ok(Default::default()),
+        };
+        self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
     }
 
     fn search_vectors_with_graph(
@@ -524,161 +843,22 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         postprocess_result.truncate(top);
         Ok(postprocess_result)
     }
-}
-
-impl HNSWIndex<GraphLinksMmap> {
-    pub fn prefault_mmap_pages(&self) -> Option<mmap_ops::PrefaultMmapPages> {
-        self.graph.as_ref()?.prefault_mmap_pages(&self.path)
-    }
-}
-
-impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
-    fn search(
-        &self,
-        vectors: &[&QueryVector],
-        filter: Option<&Filter>,
-        top: usize,
-        params: Option<&SearchParams>,
-        query_context: &VectorQueryContext,
-    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
-        let exact = params.map(|params| params.exact).unwrap_or(false);
-        match filter {
-            None => {
-                let id_tracker = self.id_tracker.borrow();
-                let vector_storage = self.vector_storage.borrow();
-
-                // Determine whether to do a plain or graph search, and pick search timer aggregator
-                // Because an HNSW graph is built, we'd normally always assume to search the graph.
-                // But because a lot of points may be deleted in this graph, it may just be faster
-                // to do a plain search instead.
-                let plain_search = exact
-                    || vector_storage.available_vector_count() < self.config.full_scan_threshold;
-
-                // Do plain or graph search
-                if plain_search {
-                    let _timer = ScopeDurationMeasurer::new(if exact {
-                        &self.searches_telemetry.exact_unfiltered
-                    } else {
-                        &self.searches_telemetry.unfiltered_plain
-                    });
-                    let deleted_points = query_context
-                        .deleted_points()
-                        .unwrap_or(id_tracker.deleted_point_bitslice());
-
-                    let is_stopped = query_context.is_stopped();
-
-                    vectors
-                        .iter()
-                        .map(|&vector| {
-                            new_stoppable_raw_scorer(
-                                vector.to_owned(),
-                                &vector_storage,
-                                deleted_points,
-                                &is_stopped,
-                            )
-                            .map(|scorer| scorer.peek_top_all(top))
-                        })
-                        .collect()
-                } else {
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_hnsw);
-                    self.search_vectors_with_graph(vectors, None, top, params, query_context)
-                }
-            }
-            Some(query_filter) => {
-                // depending on the amount of filtered-out points the optimal strategy could be
-                // - to retrieve possible points and score them after
-                // - to use HNSW index with filtering condition
-
-                // if exact search is requested, we should not use HNSW index
-                if exact {
-                    let exact_params = params.map(|params| {
-                        let mut params = *params;
-                        params.quantization = Some(QuantizationSearchParams {
-                            ignore: true,
-                            rescore: Some(false),
-                            oversampling: None,
-                        }); // disable quantization for exact search
-                        params
-                    });
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.exact_filtered);
-                    return self.search_vectors_plain(
-                        vectors,
-                        query_filter,
-                        top,
-                        exact_params.as_ref(),
-                        query_context,
-                    );
-                }
-
-                let payload_index = self.payload_index.borrow();
-                let vector_storage = self.vector_storage.borrow();
-                let id_tracker = self.id_tracker.borrow();
-                let available_vector_count = vector_storage.available_vector_count();
-                let query_point_cardinality = payload_index.estimate_cardinality(query_filter);
-                let query_cardinality = adjust_to_available_vectors(
-                    query_point_cardinality,
-                    available_vector_count,
-                    id_tracker.available_point_count(),
-                );
-
-                if query_cardinality.max < self.config.full_scan_threshold {
-                    // if cardinality is small - use plain index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
-                    return self.search_vectors_plain(
-                        vectors,
-                        query_filter,
-                        top,
-                        params,
-                        query_context,
-                    );
-                }
-
-                if query_cardinality.min > self.config.full_scan_threshold {
-                    // if cardinality is high enough - use HNSW index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    return self.search_vectors_with_graph(
-                        vectors,
-                        filter,
-                        top,
-                        params,
-                        query_context,
-                    );
-                }
-
-                let filter_context = payload_index.filter_context(query_filter);
-
-                // Fast cardinality estimation is not enough, do sample estimation of cardinality
-                let id_tracker = self.id_tracker.borrow();
-                if sample_check_cardinality(
-                    id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
-                    |idx| filter_context.check(idx),
-                    self.config.full_scan_threshold,
-                    available_vector_count, // Check cardinality among available vectors
-                ) {
-                    // if cardinality is high enough - use HNSW index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    self.search_vectors_with_graph(vectors, filter, top, params, query_context)
-                } else {
-                    // if cardinality is small - use plain index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
-                    self.search_vectors_plain(vectors, query_filter, top, params, query_context)
-                }
-            }
-        }
-    }
 
-    fn build_index_with_progress(
+    /// Build index graph, reusing graph of one of the `old_indices` if possible.
+    ///
+    /// Old graph is reused if it was built with the same parameters and most of its points are
+    /// still present in this index unchanged. Only new or changed points are inserted then.
+    pub fn build_index_with_old_indices(
         &mut self,
         permit: Arc<CpuPermit>,
+        old_indices: &[Arc<AtomicRefCell<VectorIndexEnum>>],
         stopped: &AtomicBool,
-        _tick_progress: impl FnMut(),
     ) -> OperationResult<()> {
+        // Appendable graph is kept up to date on every update, only pending points are left
+        if self.appendable_graph.is_some() {
+            return self.link_pending_points(stopped);
+        }
+
         // Build main index graph
         let id_tracker = self.id_tracker.borrow();
         let vector_storage = self.vector_storage.borrow();
@@ -698,11 +878,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             self.config.m,
             self.config.m0,
             self.config.ef_construct,
-            (total_vector_count
-                .checked_div(indexing_threshold)
-                .unwrap_or(0)
-                * 10)
-                .max(1),
+            extra_entry_points_num(total_vector_count, indexing_threshold),
             HNSW_USE_HEURISTIC,
         );
 
@@ -732,7 +908,19 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             })
             .build()?;
 
-        for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
+        let reused_points =
+            self.reuse_old_graph(old_indices, &mut graph_layers_builder, stopped)?;
+        let is_reused = |vector_id: PointOffsetType| {
+            reused_points
+                .get(vector_id as usize)
+                .map(|x| *x)
+                .unwrap_or(false)
+        };
+
+        for vector_id in id_tracker
+            .iter_ids_excluding(deleted_bitslice)
+            .filter(|&vector_id| !is_reused(vector_id))
+        {
             check_process_stopped(stopped)?;
             let level = graph_layers_builder.get_random_layer(&mut rng);
             graph_layers_builder.set_levels(vector_id, level);
@@ -741,7 +929,11 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         let mut indexed_vectors = 0;
 
         if self.config.m > 0 {
-            let mut ids_iterator = id_tracker.iter_ids_excluding(deleted_bitslice);
+            // Reused points, which lost some links, are not ready and are linked again
+            let linked_points = graph_layers_builder.num_ready_points();
+            let mut ids_iterator = id_tracker
+                .iter_ids_excluding(deleted_bitslice)
+                .filter(|&vector_id| !graph_layers_builder.is_ready(vector_id));
 
             let first_few_ids: Vec<_> = ids_iterator
                 .by_ref()
@@ -749,7 +941,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                 .collect();
             let ids: Vec<_> = ids_iterator.collect();
 
-            indexed_vectors = ids.len() + first_few_ids.len();
+            indexed_vectors = ids.len() + first_few_ids.len() + linked_points;
 
             let insert_point = |vector_id| {
                 check_process_stopped(stopped)?;
@@ -866,6 +1058,171 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         debug!("finish additional payload field indexing");
         self.save()
     }
+}
+
+impl HNSWIndex<GraphLinksMmap> {
+    pub fn prefault_mmap_pages(&self) -> Option<mmap_ops::PrefaultMmapPages> {
+        self.graph.as_ref()?.prefault_mmap_pages(&self.path)
+    }
+}
+
+impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
+    fn search(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let exact = params.map(|params| params.exact).unwrap_or(false);
+        match filter {
+            None => {
+                let id_tracker = self.id_tracker.borrow();
+                let vector_storage = self.vector_storage.borrow();
+
+                // Determine whether to do a plain or graph search, and pick search timer aggregator
+                // Because an HNSW graph is built, we'd normally always assume to search the graph.
+                // But because a lot of points may be deleted in this graph, it may just be faster
+                // to do a plain search instead.
+                let plain_search = exact
+                    || vector_storage.available_vector_count() < self.config.full_scan_threshold;
+
+                // Do plain or graph search
+                if plain_search {
+                    let _timer = query_context.record_telemetry().then(|| {
+                        ScopeDurationMeasurer::new(if exact {
+                            &self.searches_telemetry.exact_unfiltered
+                        } else {
+                            &self.searches_telemetry.unfiltered_plain
+                        })
+                    });
+                    let deleted_points = query_context
+                        .deleted_points()
+                        .unwrap_or(id_tracker.deleted_point_bitslice());
+
+                    let is_stopped = query_context.is_stopped();
+
+                    vectors
+                        .iter()
+                        .map(|&vector| {
+                            new_stoppable_raw_scorer(
+                                vector.to_owned(),
+                                &vector_storage,
+                                deleted_points,
+                                &is_stopped,
+                            )
+                            .map(|scorer| scorer.peek_top_all(top))
+                        })
+                        .collect()
+                } else {
+                    let _timer = query_context.record_telemetry().then(|| {
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_hnsw)
+                    });
+                    self.search_vectors_with_graph(vectors, None, top, params, query_context)
+                }
+            }
+            Some(query_filter) => {
+                // depending on the amount of filtered-out points the optimal strategy could be
+                // - to retrieve possible points and score them after
+                // - to use HNSW index with filtering condition
+
+                // if exact search is requested, we should not use HNSW index
+                if exact {
+                    let exact_params = params.map(|params| {
+                        let mut params = *params;
+                        params.quantization = Some(QuantizationSearchParams {
+                            ignore: true,
+                            rescore: Some(false),
+                            oversampling: None,
+                        }); // disable quantization for exact search
+                        params
+                    });
+                    let _timer = query_context.record_telemetry().then(|| {
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.exact_filtered)
+                    });
+                    return self.search_vectors_plain(
+                        vectors,
+                        query_filter,
+                        top,
+                        exact_params.as_ref(),
+                        query_context,
+                    );
+                }
+
+                let payload_index = self.payload_index.borrow();
+                let vector_storage = self.vector_storage.borrow();
+                let id_tracker = self.id_tracker.borrow();
+                let available_vector_count = vector_storage.available_vector_count();
+                let query_point_cardinality = payload_index.estimate_cardinality(query_filter);
+                let query_cardinality = adjust_to_available_vectors(
+                    query_point_cardinality,
+                    available_vector_count,
+                    id_tracker.available_point_count(),
+                );
+
+                if query_cardinality.max < self.config.full_scan_threshold {
+                    // if cardinality is small - use plain index
+                    let _timer = query_context.record_telemetry().then(|| {
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality)
+                    });
+                    return self.search_vectors_plain(
+                        vectors,
+                        query_filter,
+                        top,
+                        params,
+                        query_context,
+                    );
+                }
+
+                if query_cardinality.min > self.config.full_scan_threshold {
+                    // if cardinality is high enough - use HNSW index
+                    let _timer = query_context.record_telemetry().then(|| {
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality)
+                    });
+                    return self.search_vectors_with_graph(
+                        vectors,
+                        filter,
+                        top,
+                        params,
+                        query_context,
+                    );
+                }
+
+                let filter_context = payload_index.filter_context(query_filter);
+
+                // Fast cardinality estimation is not enough, do sample estimation of cardinality
+                let id_tracker = self.id_tracker.borrow();
+                if sample_check_cardinality(
+                    id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
+                    |idx| filter_context.check(idx),
+                    self.config.full_scan_threshold,
+                    available_vector_count, // Check cardinality among available vectors
+                ) {
+                    // if cardinality is high enough - use HNSW index
+                    let _timer = query_context.record_telemetry().then(|| {
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality)
+                    });
+                    self.search_vectors_with_graph(vectors, filter, top, params, query_context)
+                } else {
+                    // if cardinality is small - use plain index
+                    let _timer = query_context.record_telemetry().then(|| {
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality)
+                    });
+                    self.search_vectors_plain(vectors, query_filter, top, params, query_context)
+                }
+            }
+        }
+    }
+
+    fn build_index_with_progress(
+        &mut self,
+        permit: Arc<CpuPermit>,
+        stopped: &AtomicBool,
+        _tick_progress: impl FnMut(),
+    ) -> OperationResult<()> {
+        self.build_index_with_old_indices(permit, &[], stopped)
+    }
 
     fn get_telemetry_data(&self, detail: TelemetryDetail) -> VectorIndexSearchesTelemetry {
         let tm = &self.searches_telemetry;
@@ -895,6 +1252,9 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
     }
 
     fn indexed_vector_count(&self) -> usize {
+        if let Some(appendable_graph) = &self.appendable_graph {
+            return appendable_graph.num_ready_points();
+        }
         self.config
             .indexed_vector_count
             // If indexed vector count is unknown, fall back to number of points
@@ -902,7 +1262,8 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             .unwrap_or(0)
     }
 
-    fn update_vector(&mut self, _id: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
-        Err(OperationError::service_error("Cannot update HNSW index"))
+    fn update_vector(&mut self, id: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
+        // Vector is already in the storage, it is read from there once the point is linked
+        self.reset_appendable_point(id)
     }
 }
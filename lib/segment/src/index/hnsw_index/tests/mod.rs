@@ -1,3 +1,4 @@
+mod test_appendable_graph;
 mod test_compact_graph_layer;
 mod test_graph_connectivity;
 
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+
+use common::types::PointOffsetType;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use tempfile::Builder;
+
+use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::{VectorIndex, VectorIndexEnum};
+use crate::segment::Segment;
+use crate::segment_constructor::build_segment;
+use crate::types::{
+    Distance, HnswConfig, Indexes, SegmentConfig, VectorDataConfig, VectorStorageType, WithPayload,
+};
+
+/// Check links of the appendable graph, `pending` points must not be linked yet
+fn check_graph_links(segment: &Segment, pending: &[PointOffsetType], check_inbound: bool) {
+    let vector_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow();
+    let VectorIndexEnum::HnswRam(hnsw_index) = &*vector_index else {
+        panic!("appendable HNSW index is expected");
+    };
+    let graph = hnsw_index.appendable_graph().unwrap();
+
+    let num_points = segment.total_point_count();
+    assert_eq!(
+        vector_index.indexed_vector_count(),
+        num_points - pending.len()
+    );
+
+    let mut has_inbound_links = vec![false; num_points];
+    for point_id in 0..num_points as PointOffsetType {
+        let links = graph.point_links(point_id, 0);
+        if pending.contains(&point_id) {
+            assert!(!graph.is_ready(point_id));
+            assert!(links.is_empty(), "pending point {point_id} has links");
+            continue;
+        }
+
+        assert!(graph.is_ready(point_id));
+        assert!(!links.is_empty(), "point {point_id} has no links");
+        for link in links {
+            assert_ne!(link, point_id, "point {point_id} is linked to itself");
+            assert!(
+                !pending.contains(&link),
+                "point {point_id} is linked to pending point {link}",
+            );
+            has_inbound_links[link as usize] = true;
+        }
+    }
+
+    if check_inbound {
+        for point_id in 0..num_points as PointOffsetType {
+            assert!(
+                pending.contains(&point_id) || has_inbound_links[point_id as usize],
+                "point {point_id} has no inbound links",
+            );
+        }
+    }
+}
+
+#[test]
+fn test_appendable_graph_links() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 16;
+    let num_vectors: u64 = 500;
+    let num_updated_vectors: u64 = 50;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::AppendableHnsw(HnswConfig {
+                    m: 16,
+                    ef_construct: 64,
+                    full_scan_threshold: 1,
+                    max_indexing_threads: 2,
+                    on_disk: Some(false),
+                    payload_m: None,
+                    appendable: Some(true),
+                }),
+                quantization_config: None,
+                multivec_config: None,
+                datatype: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+        sparse_vector_data: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+    // Upserts only queue points to be linked
+    for idx in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(1, idx.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+    let all_points: Vec<_> = (0..num_vectors as PointOffsetType).collect();
+    check_graph_links(&segment, &all_points, false);
+
+    segment.link_pending_points(&stopped).unwrap();
+    check_graph_links(&segment, &[], true);
+
+    // Updated points are unlinked from the graph, until they are linked with new vectors
+    let mut updated_vectors = Vec::new();
+    for idx in 0..num_updated_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(2, idx.into(), only_default_vector(&vector))
+            .unwrap();
+        updated_vectors.push(vector);
+    }
+    let updated_points: Vec<_> = (0..num_updated_vectors)
+        .map(|idx| segment.get_internal_id(idx.into()).unwrap())
+        .collect();
+    check_graph_links(&segment, &updated_points, false);
+
+    // Pending points are still found by search
+    for (idx, vector) in updated_vectors.iter().enumerate() {
+        let result = segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &vector.clone().into(),
+                &WithPayload::default(),
+                &false.into(),
+                None,
+                1,
+                None,
+            )
+            .unwrap();
+        assert_eq!(result[0].id, (idx as u64).into());
+    }
+
+    segment.link_pending_points(&stopped).unwrap();
+    check_graph_links(&segment, &[], false);
+}
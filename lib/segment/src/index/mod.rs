@@ -14,4 +14,5 @@ pub mod vector_index_base;
 mod visited_pool;
 
 pub use payload_index_base::*;
+pub use query_optimization::rescore_formula;
 pub use vector_index_base::*;
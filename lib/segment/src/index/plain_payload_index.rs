@@ -276,7 +276,9 @@ impl VectorIndex for PlainIndex {
 
         match filter {
             Some(filter) => {
-                let _timer = ScopeDurationMeasurer::new(&self.filtered_searches_telemetry);
+                let _timer = query_context
+                    .record_telemetry()
+                    .then(|| ScopeDurationMeasurer::new(&self.filtered_searches_telemetry));
                 let id_tracker = self.id_tracker.borrow();
                 let payload_index = self.payload_index.borrow();
                 let vector_storage = self.vector_storage.borrow();
@@ -300,7 +302,9 @@ impl VectorIndex for PlainIndex {
                     .collect()
             }
             None => {
-                let _timer = ScopeDurationMeasurer::new(&self.unfiltered_searches_telemetry);
+                let _timer = query_context
+                    .record_telemetry()
+                    .then(|| ScopeDurationMeasurer::new(&self.unfiltered_searches_telemetry));
                 let vector_storage = self.vector_storage.borrow();
                 let id_tracker = self.id_tracker.borrow();
                 let deleted_points = query_context
@@ -281,6 +281,7 @@ where
 mod tests {
     use std::collections::HashSet;
     use std::iter::FromIterator;
+    use std::sync::Arc;
 
     use super::*;
     use crate::json_path::path;
@@ -504,7 +505,9 @@ mod tests {
             min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(HasIdCondition {
-                has_id: HashSet::from_iter([1, 2, 3, 4, 5].into_iter().map(|x| x.into())),
+                has_id: Arc::new(HashSet::from_iter(
+                    [1, 2, 3, 4, 5].into_iter().map(|x| x.into()),
+                )),
             })]),
         };
 
@@ -535,7 +538,9 @@ mod tests {
                 }),
             ]),
             must_not: Some(vec![Condition::HasId(HasIdCondition {
-                has_id: HashSet::from_iter([1, 2, 3, 4, 5].into_iter().map(|x| x.into())),
+                has_id: Arc::new(HashSet::from_iter(
+                    [1, 2, 3, 4, 5].into_iter().map(|x| x.into()),
+                )),
             })]),
         };
 
@@ -1,22 +1,22 @@
 use std::collections::HashSet;
 
 use common::types::PointOffsetType;
-use serde_json::Value;
 
 use crate::common::utils::IndexesMap;
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::map_index::{parse_uuid, parse_uuids};
 use crate::index::field_index::FieldIndex;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
 use crate::payload_storage::condition_checker::INDEXSET_ITER_THRESHOLD;
 use crate::payload_storage::query_checker::{
-    check_field_condition, check_is_empty_condition, check_is_null_condition, check_payload,
-    select_nested_indexes,
+    check_field_condition, check_is_empty_condition, check_is_null_condition,
+    check_nested_condition, select_nested_indexes,
 };
 use crate::types::{
     AnyVariants, Condition, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox,
-    GeoPolygon, GeoRadius, IntPayloadType, Match, MatchAny, MatchExcept, MatchText, MatchValue,
-    OwnedPayloadRef, PayloadContainer, Range, RangeInterface, ValueVariants,
+    GeoPolygon, GeoRadius, IntPayloadType, Match, MatchAll, MatchAny, MatchExcept, MatchPhrase,
+    MatchText, MatchValue, Range, RangeInterface, ValueVariants,
 };
 
 pub fn condition_converter<'a>(
@@ -92,32 +92,11 @@ pub fn condition_converter<'a>(
             // In this case we want to use `nested.field`, but we only have `field` in query.
             // Therefore we need to trim `nested` part from key. So that query executor
             // can address proper index for nested field.
-            let nested_path = nested.array_key();
-
-            let nested_indexes = select_nested_indexes(&nested_path, field_indexes);
+            let nested_indexes = select_nested_indexes(&nested.array_key(), field_indexes);
 
             Box::new(move |point_id| {
                 payload_provider.with_payload(point_id, |payload| {
-                    let field_values = payload.get_value(&nested_path);
-
-                    for value in field_values {
-                        if let Value::Object(object) = value {
-                            let get_payload = || OwnedPayloadRef::from(object);
-                            if check_payload(
-                                Box::new(get_payload),
-                                // None because has_id in nested is not supported. So retrieving
-                                // IDs through the tracker would always return None.
-                                None,
-                                &nested.nested.filter,
-                                point_id,
-                                &nested_indexes,
-                            ) {
-                                // If at least one nested object matches, return true
-                                return true;
-                            }
-                        }
-                    }
-                    false
+                    check_nested_condition(nested, &payload, point_id, &nested_indexes)
                 })
             })
         }
@@ -271,14 +250,23 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index
                         .get_values(point_id)
-                        .map_or(false, |values| values.iter().any(|k| k == &keyword))
+                        .map_or(false, |mut values| values.any(|k| k == keyword))
+                }))
+            }
+            // Strings which are not UUIDs are not in the index, check them in the payload
+            (ValueVariants::Keyword(keyword), FieldIndex::UuidMapIndex(index)) => {
+                let uuid = parse_uuid(&keyword)?;
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index
+                        .get_values(point_id)
+                        .map_or(false, |mut values| values.any(|value| *value == uuid))
                 }))
             }
             (ValueVariants::Integer(value), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index
                         .get_values(point_id)
-                        .map_or(false, |values| values.iter().any(|i| i == &value))
+                        .map_or(false, |mut values| values.any(|i| i == &value))
                 }))
             }
             (ValueVariants::Bool(is_true), FieldIndex::BinaryIndex(index)) => {
@@ -301,27 +289,44 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
             }
             _ => None,
         },
+        Match::Phrase(MatchPhrase { phrase }) => match index {
+            FieldIndex::FullTextIndex(full_text_index)
+                if full_text_index.supports_phrase_matching() =>
+            {
+                let parsed_query = full_text_index.parse_phrase(&phrase);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    full_text_index.check_match(&parsed_query, point_id)
+                }))
+            }
+            _ => None,
+        },
         Match::Any(MatchAny { any }) => match (any, index) {
             (AnyVariants::Keywords(list), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
+                    index.get_values(point_id).map_or(false, |mut values| {
                         if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values
-                                .iter()
-                                .any(|k| list.iter().any(|s| s.as_str() == k.as_ref()))
+                            values.any(|k| list.iter().any(|s| s.as_str() == k))
                         } else {
-                            values.iter().any(|k| list.contains(k.as_str()))
+                            values.any(|k| list.contains(k))
                         }
                     })
                 }))
             }
+            (AnyVariants::Keywords(list), FieldIndex::UuidMapIndex(index)) => {
+                let list: HashSet<_> = parse_uuids(&list)?.into_iter().collect();
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index
+                        .get_values(point_id)
+                        .map_or(false, |mut values| values.any(|u| list.contains(u)))
+                }))
+            }
             (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
+                    index.get_values(point_id).map_or(false, |mut values| {
                         if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values.iter().any(|i| list.iter().any(|k| k == i))
+                            values.any(|i| list.iter().any(|k| k == i))
                         } else {
-                            values.iter().any(|i| list.contains(i))
+                            values.any(|i| list.contains(i))
                         }
                     })
                 }))
@@ -331,24 +336,24 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
         Match::Except(MatchExcept { except }) => match (except, index) {
             (AnyVariants::Keywords(list), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
+                    index.get_values(point_id).map_or(false, |mut values| {
                         if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values
-                                .iter()
-                                .any(|k| !list.iter().any(|s| s.as_str() == k.as_ref()))
+                            values.any(|k| !list.iter().any(|s| s.as_str() == k))
                         } else {
-                            values.iter().any(|k| !list.contains(k.as_str()))
+                            values.any(|k| !list.contains(k))
                         }
                     })
                 }))
             }
+            // Points with strings which are not UUIDs must match too, but they are not in the index
+            (AnyVariants::Keywords(_), FieldIndex::UuidMapIndex(_)) => None,
             (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
+                    index.get_values(point_id).map_or(false, |mut values| {
                         if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values.iter().any(|i| !list.iter().any(|k| k == i))
+                            values.any(|i| !list.iter().any(|k| k == i))
                         } else {
-                            values.iter().any(|i| !list.contains(i))
+                            values.any(|i| !list.contains(i))
                         }
                     })
                 }))
@@ -358,6 +363,34 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
                 index.values_count(point_id) > 0
             })),
         },
+        Match::All(MatchAll { all }) => match (all, index) {
+            (AnyVariants::Keywords(list), FieldIndex::KeywordIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index.get_values(point_id).map_or(false, |values| {
+                        let values: HashSet<_> = values.collect();
+                        !list.is_empty() && list.iter().all(|k| values.contains(k.as_str()))
+                    })
+                }))
+            }
+            (AnyVariants::Keywords(list), FieldIndex::UuidMapIndex(index)) => {
+                let list: HashSet<_> = parse_uuids(&list)?.into_iter().collect();
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index.get_values(point_id).map_or(false, |values| {
+                        let values: HashSet<_> = values.collect();
+                        !list.is_empty() && list.iter().all(|u| values.contains(u))
+                    })
+                }))
+            }
+            (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index.get_values(point_id).map_or(false, |values| {
+                        let values: HashSet<_> = values.collect();
+                        !list.is_empty() && list.iter().all(|i| values.contains(i))
+                    })
+                }))
+            }
+            _ => None,
+        },
     }
 }
 
@@ -0,0 +1,353 @@
+use std::str::FromStr;
+
+use ahash::AHashMap;
+use common::types::{PointOffsetType, ScoreType};
+use geo::{HaversineDistance, Point};
+use serde_json::Value;
+
+use super::parsed_formula::{ParsedExpression, ParsedFormula, PreciseScore, VariableId};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::json_path::{JsonPath, JsonPathInterface};
+use crate::payload_storage::FilterContext;
+use crate::types::{DateTimePayloadType, GeoPoint, Payload};
+
+const MICROS_PER_SECOND: PreciseScore = 1_000_000.0;
+
+/// Evaluates a formula for the points of a segment
+pub struct FormulaScorer<'a> {
+    formula: &'a ParsedFormula,
+    /// Scores of the points in each prefetch
+    prefetches_scores: Vec<AHashMap<PointOffsetType, ScoreType>>,
+    /// One checker per condition of the formula
+    condition_checkers: Vec<Box<dyn FilterContext + 'a>>,
+    payload_retriever: Box<dyn Fn(PointOffsetType) -> OperationResult<Payload> + 'a>,
+}
+
+impl<'a> FormulaScorer<'a> {
+    pub fn new(
+        formula: &'a ParsedFormula,
+        prefetches_scores: Vec<AHashMap<PointOffsetType, ScoreType>>,
+        condition_checkers: Vec<Box<dyn FilterContext + 'a>>,
+        payload_retriever: impl Fn(PointOffsetType) -> OperationResult<Payload> + 'a,
+    ) -> Self {
+        debug_assert_eq!(formula.conditions.len(), condition_checkers.len());
+        Self {
+            formula,
+            prefetches_scores,
+            condition_checkers,
+            payload_retriever: Box::new(payload_retriever),
+        }
+    }
+
+    /// Evaluate the formula for the point
+    pub fn score(&self, point_id: PointOffsetType) -> OperationResult<ScoreType> {
+        // Payload is only read if the formula needs it, and only once per point
+        let mut payload = None;
+        let score = self.eval(&self.formula.formula, point_id, &mut payload)? as ScoreType;
+
+        if !score.is_finite() {
+            return Err(OperationError::ValidationError {
+                description: format!(
+                    "Formula evaluated to a non-finite number ({score}), check for divisions by zero, logarithms or square roots of negative numbers, and overflows",
+                ),
+            });
+        }
+
+        Ok(score)
+    }
+
+    fn eval(
+        &self,
+        expression: &ParsedExpression,
+        point_id: PointOffsetType,
+        payload: &mut Option<Payload>,
+    ) -> OperationResult<PreciseScore> {
+        let score = match expression {
+            ParsedExpression::Constant(constant) => *constant,
+            ParsedExpression::Variable(VariableId::Score(prefetch_idx)) => {
+                let score = self
+                    .prefetches_scores
+                    .get(*prefetch_idx)
+                    .and_then(|scores| scores.get(&point_id));
+                match score {
+                    Some(score) => PreciseScore::from(*score),
+                    // Points not found by this prefetch have no score
+                    None => match self.default_value(&VariableId::Score(*prefetch_idx)) {
+                        Some(value) => value_to_number(value, &VariableId::Score(*prefetch_idx))?,
+                        None => 0.0,
+                    },
+                }
+            }
+            ParsedExpression::Variable(variable @ VariableId::Payload(key)) => {
+                let value = self.payload_value(key, point_id, payload)?;
+                value_to_number(self.value_or_default(value.as_ref(), variable)?, variable)?
+            }
+            ParsedExpression::Condition(condition_idx) => {
+                let checker = self.condition_checkers.get(*condition_idx).ok_or_else(|| {
+                    OperationError::service_error(format!(
+                        "Formula condition {condition_idx} is out of bounds"
+                    ))
+                })?;
+                if checker.check(point_id) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            ParsedExpression::GeoDistance { origin, key } => {
+                let variable = VariableId::Payload(key.clone());
+                let value = self.payload_value(key, point_id, payload)?;
+                let value = self.value_or_default(value.as_ref(), &variable)?;
+                let point: GeoPoint = serde_json::from_value(value.clone())
+                    .map_err(|_| type_error(&variable, "geo point"))?;
+                Point::new(origin.lon, origin.lat)
+                    .haversine_distance(&Point::new(point.lon, point.lat))
+            }
+            ParsedExpression::Datetime(datetime) => datetime_to_seconds(datetime),
+            ParsedExpression::DatetimeKey(key) => {
+                let variable = VariableId::Payload(key.clone());
+                let value = self.payload_value(key, point_id, payload)?;
+                let value = self.value_or_default(value.as_ref(), &variable)?;
+                let datetime = value
+                    .as_str()
+                    .and_then(|datetime| DateTimePayloadType::from_str(datetime).ok())
+                    .ok_or_else(|| type_error(&variable, "datetime"))?;
+                datetime_to_seconds(&datetime)
+            }
+            ParsedExpression::Mult(expressions) => {
+                let mut product = 1.0;
+                for expression in expressions {
+                    product *= self.eval(expression, point_id, payload)?;
+                }
+                product
+            }
+            ParsedExpression::Sum(expressions) => {
+                let mut sum = 0.0;
+                for expression in expressions {
+                    sum += self.eval(expression, point_id, payload)?;
+                }
+                sum
+            }
+            ParsedExpression::Neg(expression) => -self.eval(expression, point_id, payload)?,
+            ParsedExpression::Div {
+                left,
+                right,
+                by_zero_default,
+            } => {
+                let right = self.eval(right, point_id, payload)?;
+                match by_zero_default {
+                    Some(by_zero_default) if right == 0.0 => *by_zero_default,
+                    _ => self.eval(left, point_id, payload)? / right,
+                }
+            }
+            ParsedExpression::Sqrt(expression) => self.eval(expression, point_id, payload)?.sqrt(),
+            ParsedExpression::Pow { base, exponent } => {
+                let base = self.eval(base, point_id, payload)?;
+                base.powf(self.eval(exponent, point_id, payload)?)
+            }
+            ParsedExpression::Exp(expression) => self.eval(expression, point_id, payload)?.exp(),
+            ParsedExpression::Log10(expression) => {
+                self.eval(expression, point_id, payload)?.log10()
+            }
+            ParsedExpression::Ln(expression) => self.eval(expression, point_id, payload)?.ln(),
+            ParsedExpression::Abs(expression) => self.eval(expression, point_id, payload)?.abs(),
+            ParsedExpression::Decay {
+                kind,
+                x,
+                target,
+                midpoint,
+                scale,
+            } => {
+                let x = self.eval(x, point_id, payload)?;
+                let target = match target {
+                    Some(target) => self.eval(target, point_id, payload)?,
+                    None => 0.0,
+                };
+                kind.decay(x - target, *midpoint, *scale)
+            }
+        };
+
+        Ok(score)
+    }
+
+    /// First value in the payload of the point at `key`
+    fn payload_value(
+        &self,
+        key: &JsonPath,
+        point_id: PointOffsetType,
+        payload: &mut Option<Payload>,
+    ) -> OperationResult<Option<Value>> {
+        let payload = match payload {
+            Some(payload) => payload,
+            None => payload.insert((self.payload_retriever)(point_id)?),
+        };
+
+        let value = key
+            .value_get(&payload.0)
+            .into_iter()
+            .find_map(|value| match value {
+                Value::Null => None,
+                Value::Array(values) => values.iter().find(|value| !value.is_null()),
+                value => Some(value),
+            });
+
+        Ok(value.cloned())
+    }
+
+    fn default_value(&self, variable: &VariableId) -> Option<&Value> {
+        self.formula.defaults.get(variable)
+    }
+
+    fn value_or_default<'b>(
+        &'b self,
+        value: Option<&'b Value>,
+        variable: &VariableId,
+    ) -> OperationResult<&'b Value> {
+        value
+            .or_else(|| self.default_value(variable))
+            .ok_or_else(|| OperationError::ValidationError {
+                description: format!(
+                    "No value found for `{variable}` in the payload of a point, please provide a default for it",
+                ),
+            })
+    }
+}
+
+fn value_to_number(value: &Value, variable: &VariableId) -> OperationResult<PreciseScore> {
+    value.as_f64().ok_or_else(|| type_error(variable, "number"))
+}
+
+fn type_error(variable: &VariableId, expected_type: &str) -> OperationError {
+    OperationError::ValidationError {
+        description: format!(
+            "Value of `{variable}` in formula is expected to be a {expected_type}"
+        ),
+    }
+}
+
+fn datetime_to_seconds(datetime: &DateTimePayloadType) -> PreciseScore {
+    datetime.timestamp() as PreciseScore / MICROS_PER_SECOND
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::index::rescore_formula::parsed_formula::DecayKind;
+    use crate::json_path::path;
+    use crate::types::{Condition, HasIdCondition};
+
+    struct CheckerFn(fn(PointOffsetType) -> bool);
+
+    impl FilterContext for CheckerFn {
+        fn check(&self, point_id: PointOffsetType) -> bool {
+            (self.0)(point_id)
+        }
+    }
+
+    fn payloads() -> Vec<Payload> {
+        vec![
+            json!({ "price": 10, "tags": ["a", "b"], "published": "2024-01-01T00:00:00Z" }),
+            json!({ "price": [20.5, 1], "location": { "lon": 0.0, "lat": 1.0 } }),
+            json!({}),
+        ]
+        .into_iter()
+        .map(|value| serde_json::from_value(value).unwrap())
+        .collect()
+    }
+
+    fn scorer(formula: &ParsedFormula) -> FormulaScorer {
+        let prefetch_scores = AHashMap::from([(0, 0.5), (1, 0.25)]);
+        let other_prefetch_scores = AHashMap::from([(2, 2.0)]);
+        let payloads = payloads();
+        FormulaScorer::new(
+            formula,
+            vec![prefetch_scores, other_prefetch_scores],
+            vec![Box::new(CheckerFn(|point_id| point_id == 1))],
+            move |point_id| Ok(payloads[point_id as usize].clone()),
+        )
+    }
+
+    fn make_formula(expression: ParsedExpression) -> ParsedFormula {
+        ParsedFormula {
+            conditions: vec![Condition::HasId(HasIdCondition {
+                has_id: Default::default(),
+            })],
+            defaults: HashMap::from([(VariableId::Payload(path("price")), json!(1.5))]),
+            formula: expression,
+        }
+    }
+
+    fn var(variable: &str) -> Box<ParsedExpression> {
+        Box::new(ParsedExpression::Variable(variable.parse().unwrap()))
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // $score * 2 + price - condition
+        let formula = make_formula(ParsedExpression::Sum(vec![
+            ParsedExpression::Mult(vec![*var("$score"), ParsedExpression::Constant(2.0)]),
+            *var("price"),
+            ParsedExpression::Neg(Box::new(ParsedExpression::Condition(0))),
+        ]));
+        let scorer = scorer(&formula);
+
+        assert_eq!(scorer.score(0).unwrap(), 0.5 * 2.0 + 10.0);
+        // first number of the array, minus the condition
+        assert_eq!(scorer.score(1).unwrap(), 0.25 * 2.0 + 20.5 - 1.0);
+        // missing score is 0, missing price is the default
+        assert_eq!(scorer.score(2).unwrap(), 1.5);
+    }
+
+    #[test]
+    fn test_missing_and_wrong_values() {
+        let formula = make_formula(*var("missing"));
+        assert!(scorer(&formula).score(0).is_err());
+
+        let formula = make_formula(*var("tags"));
+        assert!(scorer(&formula).score(0).is_err());
+
+        // non-finite result
+        let formula = make_formula(ParsedExpression::Div {
+            left: Box::new(ParsedExpression::Constant(1.0)),
+            right: var("$score[1]"),
+            by_zero_default: None,
+        });
+        assert!(scorer(&formula).score(0).is_err());
+        assert_eq!(scorer(&formula).score(2).unwrap(), 0.5);
+
+        // default for division by zero
+        let formula = make_formula(ParsedExpression::Div {
+            left: Box::new(ParsedExpression::Constant(1.0)),
+            right: var("$score[1]"),
+            by_zero_default: Some(3.0),
+        });
+        assert_eq!(scorer(&formula).score(0).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_datetime_and_geo() {
+        // decay of the age of the point, with a scale of one day
+        let now: DateTimePayloadType = "2024-01-02T00:00:00Z".parse().unwrap();
+        let formula = make_formula(ParsedExpression::Decay {
+            kind: DecayKind::Exp,
+            x: Box::new(ParsedExpression::DatetimeKey(path("published"))),
+            target: Some(Box::new(ParsedExpression::Datetime(now))),
+            midpoint: 0.5,
+            scale: 86400.0,
+        });
+        let score = scorer(&formula).score(0).unwrap();
+        assert!((score - 0.5).abs() < 1e-6);
+
+        // one degree of latitude is about 111 km
+        let formula = make_formula(ParsedExpression::GeoDistance {
+            origin: GeoPoint { lon: 0.0, lat: 0.0 },
+            key: path("location"),
+        });
+        let distance = scorer(&formula).score(1).unwrap();
+        assert!((110_000.0..112_000.0).contains(&distance));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod formula_scorer;
+pub mod parsed_formula;
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::json_path::JsonPath;
+use crate::types::{Condition, DateTimePayloadType, GeoPoint};
+
+/// Precision used while evaluating a formula, high enough to represent datetimes in seconds
+pub type PreciseScore = f64;
+
+pub const DEFAULT_DECAY_MIDPOINT: PreciseScore = 0.5;
+pub const DEFAULT_DECAY_SCALE: PreciseScore = 1.0;
+
+const SCORE_VARIABLE: &str = "$score";
+
+/// Formula to rescore points with, as executed against segments
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedFormula {
+    /// Conditions used in the formula, referenced by their index
+    pub conditions: Vec<Condition>,
+    /// Values to use when a variable has no value for a point
+    pub defaults: HashMap<VariableId, Value>,
+    /// Root of the expression tree
+    pub formula: ParsedExpression,
+}
+
+impl ParsedFormula {
+    /// Number of prefetches the formula needs, to have all the scores it refers to
+    pub fn required_prefetches(&self) -> usize {
+        let mut required = 0;
+        self.formula.visit_variables(&mut |variable| {
+            if let VariableId::Score(index) = variable {
+                required = required.max(index + 1);
+            }
+        });
+        required
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VariableId {
+    /// Score of the point in the prefetch at this index
+    Score(usize),
+    /// Payload value of the point at this key
+    Payload(JsonPath),
+}
+
+impl FromStr for VariableId {
+    type Err = String;
+
+    /// Parse `$score`, `$score[<prefetch index>]` or a payload key
+    fn from_str(variable: &str) -> Result<Self, Self::Err> {
+        let Some(index) = variable.strip_prefix(SCORE_VARIABLE) else {
+            return JsonPath::from_str(variable)
+                .map(VariableId::Payload)
+                .map_err(|()| format!("Invalid payload key `{variable}` in formula variable"));
+        };
+
+        if index.is_empty() {
+            return Ok(VariableId::Score(0));
+        }
+
+        index
+            .strip_prefix('[')
+            .and_then(|index| index.strip_suffix(']'))
+            .and_then(|index| index.parse().ok())
+            .map(VariableId::Score)
+            .ok_or_else(|| {
+                format!("Invalid variable `{variable}`, expected `$score` or `$score[<prefetch index>]`")
+            })
+    }
+}
+
+impl Display for VariableId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VariableId::Score(index) => write!(f, "{SCORE_VARIABLE}[{index}]"),
+            VariableId::Payload(key) => write!(f, "{key}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedExpression {
+    /// Constant number
+    Constant(PreciseScore),
+    /// Score of a prefetch, or number in the payload
+    Variable(VariableId),
+    /// 1 if the condition at this index is satisfied, 0 otherwise
+    Condition(usize),
+    /// Distance in meters between the origin and the geo point in the payload at `key`
+    GeoDistance {
+        origin: GeoPoint,
+        key: JsonPath,
+    },
+    /// Constant datetime, evaluated as seconds since epoch
+    Datetime(DateTimePayloadType),
+    /// Datetime in the payload at this key, evaluated as seconds since epoch
+    DatetimeKey(JsonPath),
+    Mult(Vec<ParsedExpression>),
+    Sum(Vec<ParsedExpression>),
+    Neg(Box<ParsedExpression>),
+    Div {
+        left: Box<ParsedExpression>,
+        right: Box<ParsedExpression>,
+        /// Result when `right` is 0
+        by_zero_default: Option<PreciseScore>,
+    },
+    Sqrt(Box<ParsedExpression>),
+    Pow {
+        base: Box<ParsedExpression>,
+        exponent: Box<ParsedExpression>,
+    },
+    Exp(Box<ParsedExpression>),
+    Log10(Box<ParsedExpression>),
+    Ln(Box<ParsedExpression>),
+    Abs(Box<ParsedExpression>),
+    /// Decay of the distance between `x` and `target`
+    Decay {
+        kind: DecayKind,
+        x: Box<ParsedExpression>,
+        /// Defaults to 0
+        target: Option<Box<ParsedExpression>>,
+        midpoint: PreciseScore,
+        scale: PreciseScore,
+    },
+}
+
+impl ParsedExpression {
+    fn visit_variables(&self, visitor: &mut impl FnMut(&VariableId)) {
+        match self {
+            ParsedExpression::Variable(variable) => visitor(variable),
+            ParsedExpression::Constant(_)
+            | ParsedExpression::Condition(_)
+            | ParsedExpression::GeoDistance { .. }
+            | ParsedExpression::Datetime(_)
+            | ParsedExpression::DatetimeKey(_) => {}
+            ParsedExpression::Mult(expressions) | ParsedExpression::Sum(expressions) => {
+                for expression in expressions {
+                    expression.visit_variables(visitor);
+                }
+            }
+            ParsedExpression::Neg(expression)
+            | ParsedExpression::Sqrt(expression)
+            | ParsedExpression::Exp(expression)
+            | ParsedExpression::Log10(expression)
+            | ParsedExpression::Ln(expression)
+            | ParsedExpression::Abs(expression) => expression.visit_variables(visitor),
+            ParsedExpression::Div { left, right, .. } => {
+                left.visit_variables(visitor);
+                right.visit_variables(visitor);
+            }
+            ParsedExpression::Pow { base, exponent } => {
+                base.visit_variables(visitor);
+                exponent.visit_variables(visitor);
+            }
+            ParsedExpression::Decay { x, target, .. } => {
+                x.visit_variables(visitor);
+                if let Some(target) = target {
+                    target.visit_variables(visitor);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecayKind {
+    /// Linear decay, reaching 0 at `scale / (1 - midpoint)`
+    Lin,
+    /// Exponential decay
+    Exp,
+    /// Gaussian decay
+    Gauss,
+}
+
+impl DecayKind {
+    /// Check that the decay parameters are usable
+    pub fn validate_params(midpoint: PreciseScore, scale: PreciseScore) -> Result<(), String> {
+        if !(midpoint > 0.0 && midpoint < 1.0) {
+            return Err(format!(
+                "Decay midpoint must be between 0 and 1, exclusive, got {midpoint}"
+            ));
+        }
+        if !(scale > 0.0 && scale.is_finite()) {
+            return Err(format!("Decay scale must be greater than 0, got {scale}"));
+        }
+        Ok(())
+    }
+
+    /// Decay of the `distance` from the target.
+    ///
+    /// It is 1 when the distance is 0, and `midpoint` when the distance is `scale`.
+    pub fn decay(
+        self,
+        distance: PreciseScore,
+        midpoint: PreciseScore,
+        scale: PreciseScore,
+    ) -> PreciseScore {
+        let distance = distance.abs();
+        match self {
+            DecayKind::Lin => (1.0 - (1.0 - midpoint) / scale * distance).max(0.0),
+            DecayKind::Exp => (midpoint.ln() / scale * distance).exp(),
+            DecayKind::Gauss => (midpoint.ln() / (scale * scale) * distance * distance).exp(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json_path::path;
+
+    #[test]
+    fn test_parse_variable() {
+        assert_eq!("$score".parse(), Ok(VariableId::Score(0)));
+        assert_eq!("$score[2]".parse(), Ok(VariableId::Score(2)));
+        assert_eq!("price".parse(), Ok(VariableId::Payload(path("price"))));
+        assert!("$score[".parse::<VariableId>().is_err());
+        assert!("$score[-1]".parse::<VariableId>().is_err());
+        assert!("$scores".parse::<VariableId>().is_err());
+
+        // Round trip
+        let variable: VariableId = "$score[1]".parse().unwrap();
+        assert_eq!(variable.to_string().parse(), Ok(variable));
+    }
+
+    #[test]
+    fn test_decay() {
+        for kind in [DecayKind::Lin, DecayKind::Exp, DecayKind::Gauss] {
+            assert_eq!(kind.decay(0.0, 0.3, 10.0), 1.0);
+            assert!((kind.decay(10.0, 0.3, 10.0) - 0.3).abs() < 1e-9);
+            assert!((kind.decay(-10.0, 0.3, 10.0) - 0.3).abs() < 1e-9);
+            assert!(kind.decay(20.0, 0.3, 10.0) < 0.3);
+        }
+
+        // Linear decay reaches 0
+        assert_eq!(DecayKind::Lin.decay(100.0, 0.5, 10.0), 0.0);
+
+        assert!(DecayKind::validate_params(0.5, 1.0).is_ok());
+        assert!(DecayKind::validate_params(1.0, 1.0).is_err());
+        assert!(DecayKind::validate_params(0.5, 0.0).is_err());
+    }
+}
@@ -419,8 +419,9 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                     .full_scan_threshold
                     .unwrap_or(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD);
                 if query_cardinality.max < threshold {
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    let _timer = vector_query_context.record_telemetry().then(|| {
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality)
+                    });
                     self.search_plain(
                         &vector,
                         filter,
@@ -429,13 +430,16 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                         vector_query_context,
                     )
                 } else {
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.filtered_sparse);
+                    let _timer = vector_query_context.record_telemetry().then(|| {
+                        ScopeDurationMeasurer::new(&self.searches_telemetry.filtered_sparse)
+                    });
                     self.search_sparse(&vector, Some(filter), top, vector_query_context)
                 }
             }
             None => {
-                let _timer = ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_sparse);
+                let _timer = vector_query_context.record_telemetry().then(|| {
+                    ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_sparse)
+                });
                 self.search_sparse(&vector, filter, top, vector_query_context)
             }
         }
@@ -462,11 +466,13 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 vector_query_context,
             ),
             QueryVector::Recommend(_) | QueryVector::Discovery(_) | QueryVector::Context(_) => {
-                let _timer = if filter.is_some() {
-                    ScopeDurationMeasurer::new(&self.searches_telemetry.filtered_plain)
-                } else {
-                    ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_plain)
-                };
+                let _timer = vector_query_context.record_telemetry().then(|| {
+                    ScopeDurationMeasurer::new(if filter.is_some() {
+                        &self.searches_telemetry.filtered_plain
+                    } else {
+                        &self.searches_telemetry.unfiltered_plain
+                    })
+                });
                 self.search_scored(
                     query_vector,
                     filter,
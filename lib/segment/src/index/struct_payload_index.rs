@@ -16,7 +16,7 @@ use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
 use crate::common::utils::IndexesMap;
 use crate::common::Flusher;
 use crate::id_tracker::IdTrackerSS;
-use crate::index::field_index::index_selector::index_selector;
+use crate::index::field_index::index_selector::{index_selector, into_on_disk_indexes};
 use crate::index::field_index::{
     CardinalityEstimation, FieldIndex, PayloadBlockCondition, PrimaryCondition,
 };
@@ -52,6 +52,8 @@ pub struct StructPayloadIndex {
     /// Used to select unique point ids
     visited_pool: VisitedPool,
     db: Arc<RwLock<DB>>,
+    /// Immutable segments may store field indexes on disk
+    is_appendable: bool,
 }
 
 impl StructPayloadIndex {
@@ -98,11 +100,25 @@ impl StructPayloadIndex {
         self.config.save(&config_path)
     }
 
-    fn load_all_fields(&mut self, is_appendable: bool) -> OperationResult<()> {
+    /// Directory of on-disk indexes of the field
+    fn field_index_dir(&self, field: PayloadKeyTypeRef) -> PathBuf {
+        let field = field.to_string();
+        let name: String = field
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        // Different paths may have the same sanitized name
+        let hash = seahash::hash(field.as_bytes());
+        self.path
+            .join(PAYLOAD_FIELD_INDEX_PATH)
+            .join(format!("{name}-{hash:016x}"))
+    }
+
+    fn load_all_fields(&mut self) -> OperationResult<()> {
         let mut field_indexes: IndexesMap = Default::default();
 
         for (field, payload_schema) in &self.config.indexed_fields {
-            let field_index = self.load_from_db(field, payload_schema.to_owned(), is_appendable)?;
+            let field_index = self.load_from_db(field, payload_schema.to_owned())?;
             field_indexes.insert(field.clone(), field_index);
         }
         self.field_indexes = field_indexes;
@@ -113,9 +129,14 @@ impl StructPayloadIndex {
         &self,
         field: PayloadKeyTypeRef,
         payload_schema: PayloadFieldSchema,
-        is_appendable: bool,
     ) -> OperationResult<Vec<FieldIndex>> {
-        let mut indexes = index_selector(field, &payload_schema, self.db.clone(), is_appendable);
+        let mut indexes = index_selector(
+            field,
+            &payload_schema,
+            self.db.clone(),
+            &self.field_index_dir(field),
+            self.is_appendable,
+        );
 
         let mut is_loaded = true;
         for ref mut index in indexes.iter_mut() {
@@ -158,6 +179,7 @@ impl StructPayloadIndex {
             path: path.to_owned(),
             visited_pool: Default::default(),
             db,
+            is_appendable,
         };
 
         if !index.config_path().exists() {
@@ -165,7 +187,7 @@ impl StructPayloadIndex {
             index.save_config()?;
         }
 
-        index.load_all_fields(is_appendable)?;
+        index.load_all_fields()?;
 
         Ok(index)
     }
@@ -176,7 +198,9 @@ impl StructPayloadIndex {
         payload_schema: PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndex>> {
         let payload_storage = self.payload.borrow();
-        let mut field_indexes = index_selector(field, &payload_schema, self.db.clone(), true);
+        let field_dir = self.field_index_dir(field);
+        let mut field_indexes =
+            index_selector(field, &payload_schema, self.db.clone(), &field_dir, true);
         for index in &field_indexes {
             index.recreate()?;
         }
@@ -188,6 +212,14 @@ impl StructPayloadIndex {
             }
             Ok(true)
         })?;
+
+        if !self.is_appendable && payload_schema.is_on_disk() {
+            // Leftovers of a previous on-disk index of the field
+            if field_dir.exists() {
+                std::fs::remove_dir_all(&field_dir)?;
+            }
+            field_indexes = into_on_disk_indexes(field_indexes, &field_dir)?;
+        }
         Ok(field_indexes)
     }
 
@@ -368,6 +400,11 @@ impl PayloadIndex for StructPayloadIndex {
             }
         }
 
+        let field_dir = self.field_index_dir(field);
+        if field_dir.exists() {
+            std::fs::remove_dir_all(&field_dir)?;
+        }
+
         self.save_config()?;
         Ok(())
     }
@@ -566,6 +603,12 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        vec![self.config_path()]
+        let mut files = vec![self.config_path()];
+        for indexes in self.field_indexes.values() {
+            for index in indexes {
+                files.extend(index.files());
+            }
+        }
+        files
     }
 }
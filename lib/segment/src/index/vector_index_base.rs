@@ -2,6 +2,7 @@ use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
+use atomic_refcell::AtomicRefCell;
 use common::cpu::CpuPermit;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use sparse::index::inverted_index::inverted_index_immutable_ram::InvertedIndexImmutableRam;
@@ -74,6 +75,43 @@ impl VectorIndexEnum {
             Self::SparseMmap(_) => true,
         }
     }
+
+    /// Build index, reusing already built `old_indices` where supported.
+    ///
+    /// Indices which can't reuse other indices are built from scratch.
+    pub fn build_index_with_old_indices(
+        &mut self,
+        permit: Arc<CpuPermit>,
+        old_indices: &[Arc<AtomicRefCell<VectorIndexEnum>>],
+        stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        match self {
+            Self::HnswRam(index) => {
+                index.build_index_with_old_indices(permit, old_indices, stopped)
+            }
+            Self::HnswMmap(index) => {
+                index.build_index_with_old_indices(permit, old_indices, stopped)
+            }
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseImmutableRam(_)
+            | Self::SparseMmap(_) => self.build_index(permit, stopped),
+        }
+    }
+
+    /// Link points, which were updated since the last call, into the graph of appendable index.
+    ///
+    /// Requires shared access only, so searches are not blocked while points are linked.
+    pub fn link_pending_points(&self, stopped: &AtomicBool) -> OperationResult<()> {
+        match self {
+            Self::HnswRam(index) => index.link_pending_points(stopped),
+            Self::HnswMmap(index) => index.link_pending_points(stopped),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseImmutableRam(_)
+            | Self::SparseMmap(_) => Ok(()),
+        }
+    }
 }
 
 impl VectorIndex for VectorIndexEnum {
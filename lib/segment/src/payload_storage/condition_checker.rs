@@ -6,7 +6,7 @@ use serde_json::Value;
 
 use crate::types::{
     AnyVariants, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPoint,
-    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchText, MatchValue, Range,
+    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchPhrase, MatchText, MatchValue, Range,
     RangeInterface, ValueVariants, ValuesCount,
 };
 
@@ -70,6 +70,8 @@ impl ValueChecker for FieldCondition {
     fn check(&self, payload: &Value) -> bool {
         if self.values_count.is_some() {
             self.values_count.as_ref().unwrap().check_count(payload)
+        } else if let Some(Match::All(match_all)) = &self.r#match {
+            match_all.check_values([payload])
         } else {
             self._check(payload)
         }
@@ -91,6 +93,10 @@ impl ValueChecker for Match {
                 Value::String(stored) => stored.contains(text),
                 _ => false,
             },
+            Match::Phrase(MatchPhrase { phrase }) => match payload {
+                Value::String(stored) => stored.contains(phrase),
+                _ => false,
+            },
             Match::Any(MatchAny { any }) => match (payload, any) {
                 (Value::String(stored), AnyVariants::Keywords(list)) => {
                     if list.len() < INDEXSET_ITER_THRESHOLD {
@@ -136,6 +142,7 @@ impl ValueChecker for Match {
                 (Value::Number(_), _) => true,
                 (Value::String(_), _) => true,
             },
+            Match::All(match_all) => match_all.check_values([payload]),
         }
     }
 }
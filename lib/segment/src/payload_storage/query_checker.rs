@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use common::types::PointOffsetType;
+use serde_json::Value;
 
 use crate::common::utils::{check_is_empty, check_is_null, IndexesMap};
 use crate::id_tracker::IdTrackerSS;
@@ -16,8 +17,8 @@ use crate::payload_storage::condition_checker::ValueChecker;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::ConditionChecker;
 use crate::types::{
-    Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, MinShould,
-    OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType,
+    ArrayQuantifier, Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, Match,
+    MinShould, NestedCondition, OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType,
 };
 
 fn check_condition<F>(checker: &F, condition: &Condition) -> bool
@@ -131,21 +132,8 @@ where
             .and_then(|id_tracker| id_tracker.external_id(point_id))
             .map_or(false, |id| has_id.has_id.contains(&id)),
         Condition::Nested(nested) => {
-            let nested_path = nested.array_key();
-            let nested_indexes = select_nested_indexes(&nested_path, field_indexes);
-            get_payload()
-                .get_value(&nested_path)
-                .iter()
-                .filter_map(|value| value.as_object())
-                .any(|object| {
-                    check_payload(
-                        Box::new(|| OwnedPayloadRef::from(object)),
-                        None,
-                        &nested.nested.filter,
-                        point_id,
-                        &nested_indexes,
-                    )
-                })
+            let nested_indexes = select_nested_indexes(&nested.array_key(), field_indexes);
+            check_nested_condition(nested, get_payload().deref(), point_id, &nested_indexes)
         }
         Condition::Filter(_) => unreachable!(),
     };
@@ -153,6 +141,42 @@ where
     check_filter(&checker, query)
 }
 
+/// Check nested filter against the objects of the nested array, according to the array quantifier.
+/// `nested_indexes` are expected to be selected with [`select_nested_indexes`].
+pub fn check_nested_condition<R>(
+    nested: &NestedCondition,
+    payload: &impl PayloadContainer,
+    point_id: PointOffsetType,
+    nested_indexes: &HashMap<PayloadKeyType, R>,
+) -> bool
+where
+    R: AsRef<Vec<FieldIndex>>,
+{
+    let nested_path = nested.array_key();
+    let values = payload.get_value(&nested_path);
+
+    let check_object = |value: &Value| {
+        value.as_object().is_some_and(|object| {
+            check_payload(
+                Box::new(move || OwnedPayloadRef::from(object)),
+                // None because has_id in nested is not supported. So retrieving
+                // IDs through the tracker would always return None.
+                None,
+                nested.filter(),
+                point_id,
+                nested_indexes,
+            )
+        })
+    };
+
+    match nested.quantifier() {
+        ArrayQuantifier::Any => values.iter().any(|value| check_object(value)),
+        ArrayQuantifier::All => {
+            !values.is_empty() && values.iter().all(|value| check_object(value))
+        }
+    }
+}
+
 pub fn check_is_empty_condition(
     is_empty: &IsEmptyCondition,
     payload: &impl PayloadContainer,
@@ -175,6 +199,12 @@ where
     let field_values = payload.get_value(&field_condition.key);
     let field_indexes = field_indexes.get(&field_condition.key);
 
+    // Values of the field must contain all of the given values together,
+    // so it can't be checked value by value.
+    if let Some(Match::All(match_all)) = &field_condition.r#match {
+        return match_all.check_values(field_values);
+    }
+
     // This covers a case, when a field index affects the result of the condition.
     if let Some(field_indexes) = field_indexes {
         for p in field_values {
@@ -295,7 +325,8 @@ mod tests {
     use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
     use crate::payload_storage::PayloadStorage;
     use crate::types::{
-        DateTimeWrapper, FieldCondition, GeoBoundingBox, GeoPoint, PayloadField, Range, ValuesCount,
+        AnyVariants, DateTimeWrapper, FieldCondition, GeoBoundingBox, GeoPoint, Nested,
+        PayloadField, Range, ValuesCount,
     };
 
     #[test]
@@ -612,4 +643,91 @@ mod tests {
         let query = Filter::new_must(Condition::HasId(ids.into()));
         assert!(payload_checker.check(2, &query));
     }
+
+    #[test]
+    fn test_array_conditions() {
+        let dir = Builder::new().prefix("db_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+        let payload: Payload = json!(
+            {
+                "rating": [3, 7, 9, 9],
+                "tags": ["fast", "cheap"],
+                "extra_tags": "reliable",
+                "parts": [],
+                "items": [
+                    { "price": 10.0, "color": "red" },
+                    { "price": 20.0, "color": "blue" },
+                ]
+        })
+        .into();
+
+        let mut payload_storage: PayloadStorageEnum =
+            SimplePayloadStorage::open(db.clone()).unwrap().into();
+        let mut id_tracker = SimpleIdTracker::open(db).unwrap();
+
+        id_tracker.set_link(0.into(), 0).unwrap();
+        payload_storage.assign_all(0, &payload).unwrap();
+
+        let payload_checker = SimpleConditionChecker::new(
+            Arc::new(AtomicRefCell::new(payload_storage)),
+            Arc::new(AtomicRefCell::new(id_tracker)),
+        );
+
+        let match_all = |key: &str, all: AnyVariants| {
+            Filter::new_must(Condition::Field(FieldCondition::new_match(
+                path(key),
+                Match::new_all(all),
+            )))
+        };
+        let keywords = |keywords: &[&str]| {
+            AnyVariants::Keywords(keywords.iter().map(|k| k.to_string()).collect())
+        };
+
+        assert!(payload_checker.check(
+            0,
+            &match_all(
+                "rating",
+                AnyVariants::Integers([3, 9].into_iter().collect())
+            )
+        ));
+        assert!(!payload_checker.check(
+            0,
+            &match_all(
+                "rating",
+                AnyVariants::Integers([3, 8].into_iter().collect())
+            )
+        ));
+        assert!(payload_checker.check(0, &match_all("tags", keywords(&["cheap", "fast"]))));
+        assert!(!payload_checker.check(0, &match_all("tags", keywords(&["cheap", "reliable"]))));
+        assert!(!payload_checker.check(0, &match_all("tags", keywords(&[]))));
+        assert!(!payload_checker.check(0, &match_all("parts", keywords(&["fast"]))));
+        // Values of all matched paths are considered together
+        assert!(payload_checker.check(0, &match_all("items[].color", keywords(&["blue", "red"]))));
+
+        let nested = |key: &str, gt: f64, quantifier: Option<ArrayQuantifier>| {
+            Filter::new_must(Condition::Nested(NestedCondition::new(Nested {
+                key: path(key),
+                filter: Filter::new_must(Condition::Field(FieldCondition::new_range(
+                    path("price"),
+                    Range {
+                        lt: None,
+                        gt: Some(gt),
+                        gte: None,
+                        lte: None,
+                    },
+                ))),
+                quantifier,
+            })))
+        };
+
+        assert!(payload_checker.check(0, &nested("items", 15.0, None)));
+        assert!(payload_checker.check(0, &nested("items", 15.0, Some(ArrayQuantifier::Any))));
+        assert!(!payload_checker.check(0, &nested("items", 15.0, Some(ArrayQuantifier::All))));
+        assert!(payload_checker.check(0, &nested("items", 5.0, Some(ArrayQuantifier::All))));
+        assert!(!payload_checker.check(0, &nested("items", 25.0, Some(ArrayQuantifier::Any))));
+        // Empty or non-object arrays never match
+        assert!(!payload_checker.check(0, &nested("parts", 5.0, Some(ArrayQuantifier::All))));
+        assert!(!payload_checker.check(0, &nested("rating", 0.0, Some(ArrayQuantifier::All))));
+    }
 }
@@ -1,3 +1,3 @@
 pub mod unindexed_field;
 
-pub use unindexed_field::UnindexedField;
+pub use unindexed_field::{unindexed_fields, UnindexedField};
@@ -90,8 +90,7 @@ impl UnindexedField {
         payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
         collection_name: String,
     ) {
-        let unindexed_issues =
-            Extractor::new(filter, payload_schema, collection_name).into_issues();
+        let unindexed_issues = Extractor::new(filter, payload_schema).into_issues(collection_name);
 
         log::trace!("Found unindexed issues: {unindexed_issues:#?}");
 
@@ -155,32 +154,46 @@ impl Issue for UnindexedField {
     }
 }
 
+/// Fields used in the filter which have no payload index suitable for the condition on them.
+///
+/// Each field is mapped to the index schemas which would serve its conditions.
+pub fn unindexed_fields(
+    filter: &Filter,
+    payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+) -> HashMap<PayloadKeyType, Vec<PayloadFieldSchema>> {
+    Extractor::new(filter, payload_schema).unindexed_schema
+}
+
 /// Suggest any index, let user choose depending on their data type
 fn all_indexes() -> impl Iterator<Item = PayloadFieldSchema> {
     PayloadSchemaType::iter().map(PayloadFieldSchema::FieldType)
 }
 
-fn infer_schema_from_match_value(value: &MatchValue) -> PayloadFieldSchema {
+/// Both keyword and uuid indexes can serve string matches
+fn keyword_indexes() -> Vec<PayloadFieldSchema> {
+    vec![
+        PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+        PayloadFieldSchema::FieldType(PayloadSchemaType::Uuid),
+    ]
+}
+
+fn infer_schema_from_match_value(value: &MatchValue) -> Vec<PayloadFieldSchema> {
     match &value.value {
-        crate::types::ValueVariants::Keyword(_string) => {
-            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)
-        }
+        crate::types::ValueVariants::Keyword(_string) => keyword_indexes(),
         crate::types::ValueVariants::Integer(_integer) => {
-            PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)
+            vec![PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)]
         }
         crate::types::ValueVariants::Bool(_boolean) => {
-            PayloadFieldSchema::FieldType(PayloadSchemaType::Bool)
+            vec![PayloadFieldSchema::FieldType(PayloadSchemaType::Bool)]
         }
     }
 }
 
-fn infer_schema_from_any_variants(value: &AnyVariants) -> PayloadFieldSchema {
+fn infer_schema_from_any_variants(value: &AnyVariants) -> Vec<PayloadFieldSchema> {
     match value {
-        AnyVariants::Keywords(_strings) => {
-            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)
-        }
+        AnyVariants::Keywords(_strings) => keyword_indexes(),
         AnyVariants::Integers(_integers) => {
-            PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)
+            vec![PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)]
         }
     }
 }
@@ -199,19 +212,39 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
     let mut inferred = Vec::new();
 
     if let Some(r#match) = r#match {
-        inferred.push(match r#match {
+        inferred.extend(match r#match {
             Match::Value(match_value) => infer_schema_from_match_value(match_value),
-            Match::Text(_match_text) => {
-                PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(TextIndexParams {
+            Match::Text(_match_text) => vec![PayloadFieldSchema::FieldParams(
+                PayloadSchemaParams::Text(TextIndexParams {
                     r#type: TextIndexType::Text,
                     tokenizer: TokenizerType::default(),
                     min_token_len: None,
                     max_token_len: None,
                     lowercase: None,
-                }))
-            }
+                    on_disk: None,
+                    stopwords: None,
+                    stemmer: None,
+                    ascii_folding: None,
+                    phrase_matching: None,
+                }),
+            )],
+            Match::Phrase(_match_phrase) => vec![PayloadFieldSchema::FieldParams(
+                PayloadSchemaParams::Text(TextIndexParams {
+                    r#type: TextIndexType::Text,
+                    tokenizer: TokenizerType::default(),
+                    min_token_len: None,
+                    max_token_len: None,
+                    lowercase: None,
+                    on_disk: None,
+                    stopwords: None,
+                    stemmer: None,
+                    ascii_folding: None,
+                    phrase_matching: Some(true),
+                }),
+            )],
             Match::Any(match_any) => infer_schema_from_any_variants(&match_any.any),
             Match::Except(match_except) => infer_schema_from_any_variants(&match_except.except),
+            Match::All(match_all) => infer_schema_from_any_variants(&match_all.all),
         })
     }
     if let Some(range_interface) = range {
@@ -239,19 +272,16 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
 struct Extractor<'a> {
     payload_schema: &'a HashMap<PayloadKeyType, PayloadFieldSchema>,
     unindexed_schema: HashMap<PayloadKeyType, Vec<PayloadFieldSchema>>,
-    collection_name: String,
 }
 
 impl<'a> Extractor<'a> {
     fn new(
         filter: &Filter,
         payload_schema: &'a HashMap<PayloadKeyType, PayloadFieldSchema>,
-        collection_name: String,
     ) -> Self {
         let mut extractor = Self {
             payload_schema,
             unindexed_schema: HashMap::new(),
-            collection_name,
         };
 
         extractor.update_from_filter(None, filter);
@@ -259,13 +289,13 @@ impl<'a> Extractor<'a> {
         extractor
     }
 
-    fn into_issues(self) -> Vec<UnindexedField> {
+    fn into_issues(self, collection_name: String) -> Vec<UnindexedField> {
         self.unindexed_schema
             .into_iter()
             .filter_map(|(key, field_schemas)| {
                 let field_schemas = HashSet::from_iter(field_schemas);
 
-                UnindexedField::try_new(key, field_schemas, self.collection_name.clone()).ok()
+                UnindexedField::try_new(key, field_schemas, collection_name.clone()).ok()
             })
             .collect()
     }
@@ -329,7 +359,10 @@ impl<'a> Extractor<'a> {
 
         let needs_index = match self.payload_schema.get(&full_key) {
             Some(index_info) => {
-                let already_indexed = inferred.iter().any(|inferred| inferred == index_info);
+                // Index params (e.g. storage options) don't matter here, only the kind of index
+                let already_indexed = inferred
+                    .iter()
+                    .any(|inferred| inferred.kind() == index_info.kind());
 
                 !already_indexed
             }
@@ -3,17 +3,24 @@ use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 
+use ahash::AHashMap;
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitVec;
-use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset, TelemetryDetail};
+use geo::prelude::HaversineDistance;
+use geo::Point;
 use io::file_operations::{atomic_save_json, read_json};
 use io::storage_version::{StorageVersion, VERSION_FILE};
 use itertools::Either;
 use memory::mmap_ops;
 use parking_lot::{Mutex, RwLock};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom as _;
+use rand::SeedableRng as _;
 use rocksdb::DB;
 use sparse::common::sparse_vector::SparseVector;
 use tar::Builder;
@@ -23,25 +30,33 @@ use crate::common::operation_error::OperationError::TypeInferenceError;
 use crate::common::operation_error::{
     get_service_error, OperationError, OperationResult, SegmentFailedState,
 };
+use crate::common::random_sampling::sampling_keys;
 use crate::common::validate_snapshot_archive::open_snapshot_archive_with_validation;
 use crate::common::{check_named_vectors, check_query_vectors, check_stopped, check_vector_name};
+use crate::data_types::facets::{FacetParams, FacetValue};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
-use crate::data_types::vectors::{MultiDenseVector, QueryVector, Vector, VectorRef};
+use crate::data_types::text_query::{TextQuery, TextQueryStatistics};
+use crate::data_types::vectors::{MultiDenseVector, PackedBits, QueryVector, Vector, VectorRef};
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::full_text_index::text_index::FullTextIndex;
+use crate::index::field_index::geo_index::GeoMapIndex;
 use crate::index::field_index::numeric_index::StreamRange;
-use crate::index::field_index::CardinalityEstimation;
+use crate::index::field_index::{CardinalityEstimation, PayloadFieldIndex as _};
+use crate::index::rescore_formula::formula_scorer::FormulaScorer;
+use crate::index::rescore_formula::parsed_formula::ParsedFormula;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::json_path::JsonPath;
 use crate::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
-    PayloadSchemaType, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
-    SegmentState, SegmentType, SeqNumberType, VectorDataInfo, WithPayload, WithVector,
+    FieldCondition, Filter, GeoPoint, GeoRadius, Payload, PayloadFieldSchema, PayloadIndexInfo,
+    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType, ScoredPoint, SearchParams,
+    SegmentConfig, SegmentInfo, SegmentState, SegmentType, SeqNumberType, VectorDataInfo,
+    WithPayload, WithVector,
 };
 use crate::utils;
 use crate::utils::fs::find_symlink;
@@ -144,10 +159,14 @@ impl Segment {
             let vector = vectors.get(vector_name);
             match vector {
                 Some(vector) => {
-                    let mut vector_storage = vector_data.vector_storage.borrow_mut();
-                    vector_storage.insert_vector(internal_id, vector)?;
-                    let mut vector_index = vector_data.vector_index.borrow_mut();
-                    vector_index.update_vector(internal_id, vector)?;
+                    vector_data
+                        .vector_storage
+                        .borrow_mut()
+                        .insert_vector(internal_id, vector)?;
+                    vector_data
+                        .vector_index
+                        .borrow_mut()
+                        .update_vector(internal_id, vector)?;
                 }
                 None => {
                     // No vector provided, so we remove it
@@ -209,8 +228,8 @@ impl Segment {
         let new_index = self.id_tracker.borrow().total_point_count() as PointOffsetType;
         for (vector_name, vector_data) in self.vector_data.iter_mut() {
             let vector_opt = vectors.get(vector_name);
+            // Index may read the vector from the storage, so the storage must not stay borrowed
             let mut vector_storage = vector_data.vector_storage.borrow_mut();
-            let mut vector_index = vector_data.vector_index.borrow_mut();
             match vector_opt {
                 None => {
                     // placeholder vector for marking deletion
@@ -224,6 +243,12 @@ impl Segment {
                         VectorStorageEnum::DenseSimpleHalf(v) => {
                             Vector::from(vec![1.0; v.vector_dim()])
                         }
+                        VectorStorageEnum::DenseSimpleBfloat16(v) => {
+                            Vector::from(vec![1.0; v.vector_dim()])
+                        }
+                        VectorStorageEnum::DenseSimpleBit(v) => {
+                            Vector::from(vec![1.0; v.vector_dim() * PackedBits::BITS])
+                        }
                         VectorStorageEnum::DenseMemmap(v) => {
                             Vector::from(vec![1.0; v.vector_dim()])
                         }
@@ -233,6 +258,12 @@ impl Segment {
                         VectorStorageEnum::DenseMemmapHalf(v) => {
                             Vector::from(vec![1.0; v.vector_dim()])
                         }
+                        VectorStorageEnum::DenseMemmapBfloat16(v) => {
+                            Vector::from(vec![1.0; v.vector_dim()])
+                        }
+                        VectorStorageEnum::DenseMemmapBit(v) => {
+                            Vector::from(vec![1.0; v.vector_dim() * PackedBits::BITS])
+                        }
                         VectorStorageEnum::DenseAppendableMemmap(v) => {
                             Vector::from(vec![1.0; v.vector_dim()])
                         }
@@ -242,6 +273,12 @@ impl Segment {
                         VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                             Vector::from(vec![1.0; v.vector_dim()])
                         }
+                        VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => {
+                            Vector::from(vec![1.0; v.vector_dim()])
+                        }
+                        VectorStorageEnum::DenseAppendableMemmapBit(v) => {
+                            Vector::from(vec![1.0; v.vector_dim() * PackedBits::BITS])
+                        }
                         VectorStorageEnum::SparseSimple(_) => Vector::from(SparseVector::default()),
                         VectorStorageEnum::MultiDenseSimple(v) => {
                             Vector::from(MultiDenseVector::placeholder(v.vector_dim()))
@@ -252,6 +289,9 @@ impl Segment {
                         VectorStorageEnum::MultiDenseSimpleHalf(v) => {
                             Vector::from(MultiDenseVector::placeholder(v.vector_dim()))
                         }
+                        VectorStorageEnum::MultiDenseSimpleBfloat16(v) => {
+                            Vector::from(MultiDenseVector::placeholder(v.vector_dim()))
+                        }
                         VectorStorageEnum::MultiDenseAppendableMemmap(v) => {
                             Vector::from(MultiDenseVector::placeholder(v.vector_dim()))
                         }
@@ -261,14 +301,25 @@ impl Segment {
                         VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
                             Vector::from(MultiDenseVector::placeholder(v.vector_dim()))
                         }
+                        VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => {
+                            Vector::from(MultiDenseVector::placeholder(v.vector_dim()))
+                        }
                     };
                     vector_storage.insert_vector(new_index, VectorRef::from(&vector))?;
                     vector_storage.delete_vector(new_index)?;
-                    vector_index.update_vector(new_index, VectorRef::from(&vector))?;
+                    drop(vector_storage);
+                    vector_data
+                        .vector_index
+                        .borrow_mut()
+                        .update_vector(new_index, VectorRef::from(&vector))?;
                 }
                 Some(vec) => {
                     vector_storage.insert_vector(new_index, vec)?;
-                    vector_index.update_vector(new_index, vec)?;
+                    drop(vector_storage);
+                    vector_data
+                        .vector_index
+                        .borrow_mut()
+                        .update_vector(new_index, vec)?;
                 }
             }
         }
@@ -780,6 +831,22 @@ impl Segment {
         exp_stream_checks > exp_index_checks
     }
 
+    /// Full-text index of the payload key, as required by text queries
+    fn full_text_index<'a>(
+        payload_index: &'a StructPayloadIndex,
+        key: &JsonPath,
+    ) -> OperationResult<&'a FullTextIndex> {
+        payload_index
+            .field_indexes
+            .get(key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_full_text_index()))
+            .ok_or_else(|| OperationError::ValidationError {
+                description: format!(
+                    "There is no full-text index for the `{key}` key, please create one to use text queries",
+                ),
+            })
+    }
+
     fn read_by_id_stream(
         &self,
         offset: Option<PointIdType>,
@@ -885,6 +952,113 @@ impl Segment {
         Ok(page)
     }
 
+    /// Read points ordered by the distance of their nearest location to `order_by.geo_point`
+    pub fn filtered_read_by_geo_distance(
+        &self,
+        order_by: &OrderBy,
+        limit: Option<usize>,
+        filter: Option<&Filter>,
+    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+        let Some(geo_point) = &order_by.geo_point else {
+            return Err(OperationError::service_error(
+                "Geo point is required to order by geo distance",
+            ));
+        };
+
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let geo_index = payload_index
+            .field_indexes
+            .get(&order_by.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_geo_index()))
+            .ok_or_else(|| OperationError::ValidationError { description: "There is no geo index for the `order_by` key, please create one to order by geo distance".to_string() })?;
+
+        let origin = Point::new(geo_point.lon, geo_point.lat);
+        let direction = order_by.direction();
+        let start_from = order_by.start_from();
+
+        // Points with several locations are at the distance of the nearest one
+        let point_distance = |internal_id| {
+            let distance = geo_index
+                .get_values(internal_id)?
+                .iter()
+                .map(|value| origin.haversine_distance(&Point::new(value.lon, value.lat)))
+                .min_by(|a, b| a.total_cmp(b))?;
+            Some(OrderValue::from(distance))
+        };
+        let is_after_start = |value: &OrderValue| match direction {
+            Direction::Asc => value >= &start_from,
+            Direction::Desc => value <= &start_from,
+        };
+
+        let page = match limit {
+            Some(limit) => {
+                let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+                read_geo_distance_rings(
+                    geo_index,
+                    &order_by.key,
+                    origin,
+                    direction,
+                    limit,
+                    |internal_id| {
+                        let value = point_distance(internal_id)?;
+                        if !is_after_start(&value) {
+                            return None;
+                        }
+                        if let Some(filter_context) = &filter_context {
+                            if !filter_context.check(internal_id) {
+                                return None;
+                            }
+                        }
+                        let external_id = id_tracker.external_id(internal_id)?;
+                        Some((value, external_id))
+                    },
+                )
+            }
+            None => None,
+        };
+
+        // Without a limit, or if rings grow over the whole globe, all candidates are scanned
+        let mut page = match page {
+            Some(page) => page,
+            None => {
+                let candidates = match filter {
+                    None => Either::Left(id_tracker.iter_ids()),
+                    Some(filter) => Either::Right(payload_index.query_points(filter).into_iter()),
+                };
+
+                let values_ids_iterator = candidates
+                    .filter_map(|internal_id| Some((point_distance(internal_id)?, internal_id)))
+                    .filter(|(value, _)| is_after_start(value))
+                    .filter_map(|(value, internal_id)| {
+                        id_tracker
+                            .external_id(internal_id)
+                            .map(|external_id| (value, external_id))
+                    });
+
+                match (direction, limit) {
+                    (Direction::Asc, Some(limit)) => {
+                        peek_top_smallest_iterable(values_ids_iterator, limit)
+                    }
+                    (Direction::Desc, Some(limit)) => {
+                        peek_top_largest_iterable(values_ids_iterator, limit)
+                    }
+                    (_, None) => values_ids_iterator.collect(),
+                }
+            }
+        };
+
+        match direction {
+            Direction::Asc => page.sort_unstable_by_key(|(value, _)| *value),
+            Direction::Desc => {
+                page.sort_unstable_by(|(value_a, _), (value_b, _)| value_b.cmp(value_a))
+            }
+        }
+
+        Ok(page)
+    }
+
     pub fn filtered_read_by_id_stream(
         &self,
         offset: Option<PointIdType>,
@@ -1078,6 +1252,20 @@ impl Segment {
         self.id_tracker.borrow().total_point_count()
     }
 
+    /// Link updated points into graphs of appendable HNSW indices.
+    ///
+    /// Updates only reset points in the graph, because linking takes time and would block
+    /// searches for it. This function needs a read lock of the segment only.
+    pub fn link_pending_points(&self, stopped: &AtomicBool) -> OperationResult<()> {
+        for vector_data in self.vector_data.values() {
+            vector_data
+                .vector_index
+                .borrow()
+                .link_pending_points(stopped)?;
+        }
+        Ok(())
+    }
+
     pub fn prefault_mmap_pages(&self) {
         let tasks: Vec<_> = self
             .vector_data
@@ -1125,6 +1313,83 @@ impl Segment {
     }
 }
 
+/// Radius of the first circle of points read when ordering by geo distance, in meters.
+/// Every next circle has twice the radius of the previous one.
+const GEO_DISTANCE_INITIAL_RADIUS: f64 = 1_000.0;
+
+/// Read the `limit` nearest or farthest points from the origin, using geohash cells of the index
+///
+/// Nearest points are read from a growing circle around the origin, the farthest ones from
+/// a growing circle around its antipode. The geo index finds all points within the circle, so once
+/// it holds `limit` matching points, which are closer to the center than its radius, these points
+/// are the final page. `read_point` returns the distance and the external id of a matching point.
+///
+/// Returns `None` if the circle grows over the whole globe, or the index can't cover it.
+fn read_geo_distance_rings(
+    geo_index: &GeoMapIndex,
+    key: &JsonPath,
+    origin: Point,
+    direction: Direction,
+    limit: usize,
+    read_point: impl Fn(PointOffsetType) -> Option<(OrderValue, PointIdType)>,
+) -> Option<Vec<(OrderValue, PointIdType)>> {
+    let antipode = Point::new(
+        if origin.x() > 0.0 {
+            origin.x() - 180.0
+        } else {
+            origin.x() + 180.0
+        },
+        -origin.y(),
+    );
+    let max_distance = origin.haversine_distance(&antipode);
+
+    let center = match direction {
+        Direction::Asc => origin,
+        Direction::Desc => antipode,
+    };
+
+    let mut radius = GEO_DISTANCE_INITIAL_RADIUS;
+    while radius < max_distance {
+        let condition = FieldCondition::new_geo_radius(
+            key.clone(),
+            GeoRadius {
+                center: GeoPoint {
+                    lon: center.x(),
+                    lat: center.y(),
+                },
+                radius,
+            },
+        );
+
+        // Points closer to the center than the radius are all within the circle
+        let is_within = |value: &OrderValue| match direction {
+            Direction::Asc => value < &OrderValue::from(radius),
+            Direction::Desc => value > &OrderValue::from(max_distance - radius),
+        };
+
+        let mut internal_ids: Vec<_> = geo_index.filter(&condition).ok()?.collect();
+        internal_ids.sort_unstable();
+        internal_ids.dedup();
+
+        let points_within = internal_ids
+            .into_iter()
+            .filter_map(&read_point)
+            .filter(|(value, _)| is_within(value));
+
+        let page = match direction {
+            Direction::Asc => peek_top_smallest_iterable(points_within, limit),
+            Direction::Desc => peek_top_largest_iterable(points_within, limit),
+        };
+        if page.len() >= limit {
+            return Some(page);
+        }
+
+        radius *= 2.0;
+    }
+
+    None
+}
+
 /// This is a basic implementation of `SegmentEntry`,
 /// meaning that it implements the _actual_ operations with data and not any kind of proxy or wrapping
 impl SegmentEntry for Segment {
@@ -1411,6 +1676,10 @@ impl SegmentEntry for Segment {
         filter: Option<&'a Filter>,
         order_by: &'a OrderBy,
     ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+        if order_by.is_geo_distance() {
+            return self.filtered_read_by_geo_distance(order_by, limit, filter);
+        }
+
         match filter {
             None => self.filtered_read_by_value_stream(order_by, limit, None),
             Some(filter) => {
@@ -1423,6 +1692,263 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn facet(
+        &self,
+        request: &FacetParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        let payload_index = self.payload_index.borrow();
+
+        let facet_index = payload_index
+            .field_indexes
+            .get(&request.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_facet_index()))
+            .ok_or_else(|| OperationError::ValidationError {
+                description: format!(
+                    "There is no keyword, integer or bool index for the `{}` key, please create one to use facets",
+                    request.key,
+                ),
+            })?;
+
+        let mut counts = HashMap::new();
+
+        match &request.filter {
+            // Without a filter, counts can be taken directly from the index
+            None => {
+                for (value, count) in facet_index.iter_counts_per_value() {
+                    check_stopped(is_stopped)?;
+                    if count > 0 {
+                        *counts.entry(value).or_insert(0) += count;
+                    }
+                }
+            }
+            Some(filter) => {
+                for internal_id in payload_index.query_points(filter) {
+                    check_stopped(is_stopped)?;
+                    for value in facet_index.get_point_values(internal_id) {
+                        *counts.entry(value).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(counts
+            .into_iter()
+            .map(|(value, count)| (value.to_owned(), count))
+            .collect())
+    }
+
+    fn rescore_with_formula(
+        &self,
+        formula: &ParsedFormula,
+        prefetches_results: &[Vec<ScoredPoint>],
+        filter: Option<&Filter>,
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+
+        // Only the points of this segment are rescored here
+        let prefetches_scores: Vec<AHashMap<PointOffsetType, ScoreType>> = prefetches_results
+            .iter()
+            .map(|points| {
+                points
+                    .iter()
+                    .filter_map(|point| Some((id_tracker.internal_id(point.id)?, point.score)))
+                    .collect()
+            })
+            .collect();
+
+        let mut internal_ids: Vec<_> = prefetches_scores
+            .iter()
+            .flat_map(|scores| scores.keys().copied())
+            .collect();
+        internal_ids.sort_unstable();
+        internal_ids.dedup();
+
+        if let Some(filter) = filter {
+            let filter_context = payload_index.filter_context(filter);
+            internal_ids.retain(|&internal_id| filter_context.check(internal_id));
+        }
+
+        let condition_filters: Vec<_> = formula
+            .conditions
+            .iter()
+            .cloned()
+            .map(Filter::new_must)
+            .collect();
+        let condition_checkers = condition_filters
+            .iter()
+            .map(|filter| payload_index.filter_context(filter))
+            .collect();
+
+        let scorer = FormulaScorer::new(
+            formula,
+            prefetches_scores,
+            condition_checkers,
+            |internal_id| payload_index.payload(internal_id),
+        );
+
+        let mut scored_points = Vec::with_capacity(internal_ids.len());
+        for internal_id in internal_ids {
+            check_stopped(is_stopped)?;
+            let Some(point_id) = id_tracker.external_id(internal_id) else {
+                continue;
+            };
+            scored_points.push(ScoredPoint {
+                id: point_id,
+                version: id_tracker.internal_version(internal_id).unwrap_or(0),
+                score: scorer.score(internal_id)?,
+                payload: None,
+                vector: None,
+                shard_key: None,
+                order_value: None,
+            });
+        }
+
+        Ok(peek_top_largest_iterable(scored_points, limit))
+    }
+
+    fn text_query_statistics(&self, query: &TextQuery) -> OperationResult<TextQueryStatistics> {
+        let payload_index = self.payload_index.borrow();
+        let text_index = Self::full_text_index(&payload_index, &query.key)?;
+        Ok(text_index.text_query_statistics(&query.text))
+    }
+
+    fn text_query_points_statistics(
+        &self,
+        query: &TextQuery,
+        point_ids: &HashSet<PointIdType>,
+    ) -> OperationResult<TextQueryStatistics> {
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let text_index = Self::full_text_index(&payload_index, &query.key)?;
+        let internal_ids = point_ids
+            .iter()
+            .filter_map(|&point_id| id_tracker.internal_id(point_id));
+        Ok(text_index.points_text_query_statistics(&query.text, internal_ids))
+    }
+
+    fn text_search(
+        &self,
+        query: &TextQuery,
+        statistics: &TextQueryStatistics,
+        filter: Option<&Filter>,
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let text_index = Self::full_text_index(&payload_index, &query.key)?;
+
+        let scores = text_index.score_text_query(&query.text, statistics);
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+
+        let mut scored_points = Vec::with_capacity(scores.len());
+        for (internal_id, score) in scores {
+            check_stopped(is_stopped)?;
+            if filter_context
+                .as_ref()
+                .is_some_and(|filter_context| !filter_context.check(internal_id))
+            {
+                continue;
+            }
+            let Some(point_id) = id_tracker.external_id(internal_id) else {
+                continue;
+            };
+            scored_points.push(ScoredPoint {
+                id: point_id,
+                version: id_tracker.internal_version(internal_id).unwrap_or(0),
+                score,
+                payload: None,
+                vector: None,
+                shard_key: None,
+                order_value: None,
+            });
+        }
+
+        Ok(peek_top_largest_iterable(scored_points, limit))
+    }
+
+    fn read_random_filtered(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        if limit == 0 {
+            return Ok(vec![]);
+        }
+
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+
+        let available = id_tracker.available_point_count();
+        let expected = match filter {
+            None => available,
+            Some(filter) => payload_index.estimate_cardinality(filter).exp,
+        };
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+
+        let mut sampled = Vec::new();
+        let mut population = expected;
+
+        // Draw random points and reject those not matching the filter, while that is expected to
+        // take fewer draws than there are points, otherwise read all matching points
+        if limit.saturating_mul(2) <= expected {
+            let mut seen = HashSet::new();
+            for internal_id in id_tracker.sample_ids_with_rng(None, StdRng::from_entropy()) {
+                check_stopped(is_stopped)?;
+                if filter_context
+                    .as_ref()
+                    .is_some_and(|filter_context| !filter_context.check(internal_id))
+                {
+                    continue;
+                }
+                if seen.insert(internal_id) {
+                    sampled.push(internal_id);
+                    if sampled.len() >= limit {
+                        break;
+                    }
+                }
+            }
+            population = population.max(sampled.len());
+        }
+
+        if sampled.len() < limit {
+            let mut matching: Vec<_> = match filter {
+                None => id_tracker.iter_ids().collect(),
+                Some(filter) => payload_index.query_points(filter),
+            };
+            check_stopped(is_stopped)?;
+
+            let (chosen, _) = matching.partial_shuffle(&mut rand::thread_rng(), limit);
+            sampled = chosen.to_vec();
+            population = matching.len();
+        }
+
+        let keys = sampling_keys(&mut rand::thread_rng(), population, sampled.len());
+
+        let scored_points = sampled
+            .into_iter()
+            .zip(keys)
+            .filter_map(|(internal_id, score)| {
+                Some(ScoredPoint {
+                    id: id_tracker.external_id(internal_id)?,
+                    version: id_tracker.internal_version(internal_id).unwrap_or(0),
+                    score,
+                    payload: None,
+                    vector: None,
+                    shard_key: None,
+                    order_value: None,
+                })
+            })
+            .collect();
+
+        Ok(scored_points)
+    }
+
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let id_tracker = self.id_tracker.borrow();
         let iterator = id_tracker.iter_from(from).map(|x| x.0);
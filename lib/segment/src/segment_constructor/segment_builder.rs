@@ -19,13 +19,14 @@ use crate::common::operation_error::{check_process_stopped, OperationError, Oper
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::{IdTracker, IdTrackerEnum};
 use crate::index::struct_payload_index::StructPayloadIndex;
-use crate::index::{PayloadIndex, VectorIndex};
+use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::PayloadStorage;
 use crate::segment::{Segment, SegmentVersion};
 use crate::segment_constructor::load_segment;
 use crate::types::{
     PayloadFieldSchema, PayloadKeyType, SegmentConfig, SegmentState, SeqNumberType,
+    VectorStorageDatatype,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
@@ -43,6 +44,8 @@ pub struct SegmentBuilder {
     // Path to the temporary segment directory
     temp_path: PathBuf,
     indexed_fields: HashMap<PayloadKeyType, PayloadFieldSchema>,
+    // Vector indices of source segments, which might be reused on index building
+    old_vector_indices: HashMap<String, Vec<Arc<AtomicRefCell<VectorIndexEnum>>>>,
 }
 
 impl SegmentBuilder {
@@ -100,6 +103,7 @@ impl SegmentBuilder {
             destination_path,
             temp_path,
             indexed_fields: Default::default(),
+            old_vector_indices: Default::default(),
         })
     }
 
@@ -240,6 +244,19 @@ impl SegmentBuilder {
             self.indexed_fields.insert(field, payload_schema);
         }
 
+        for (vector_name, vector_data) in &other.vector_data {
+            let old_indices = self
+                .old_vector_indices
+                .entry(vector_name.to_owned())
+                .or_default();
+            if !old_indices
+                .iter()
+                .any(|old_index| Arc::ptr_eq(old_index, &vector_data.vector_index))
+            {
+                old_indices.push(vector_data.vector_index.clone());
+            }
+        }
+
         id_tracker.mapping_flusher()()?;
         id_tracker.versions_flusher()()?;
 
@@ -257,6 +274,7 @@ impl SegmentBuilder {
                 destination_path,
                 temp_path,
                 indexed_fields,
+                old_vector_indices,
             } = self;
 
             let appendable_flag = segment_config.is_appendable();
@@ -319,9 +337,14 @@ impl SegmentBuilder {
                     vector_storage_arc,
                     payload_index_arc.clone(),
                     quantized_vectors_arc,
+                    stopped,
                 )?;
 
-                vector_index.build_index(permit.clone(), stopped)?;
+                let old_indices = old_vector_indices
+                    .get(vector_name)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                vector_index.build_index_with_old_indices(permit.clone(), old_indices, stopped)?;
             }
 
             for (vector_name, sparse_vector_config) in &segment_config.sparse_vector_data {
@@ -408,6 +431,11 @@ impl SegmentBuilder {
                 continue;
             }
 
+            // Bit vectors are already compact, don't build quantization for them
+            if vector_config.datatype == Some(VectorStorageDatatype::Bit) {
+                continue;
+            }
+
             let max_threads = permit.num_cpus as usize;
 
             if let Some(quantization) = config.quantization_config(vector_name) {
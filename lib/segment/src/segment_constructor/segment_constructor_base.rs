@@ -34,23 +34,27 @@ use crate::types::{
     SparseVectorDataConfig, VectorDataConfig, VectorStorageDatatype, VectorStorageType,
 };
 use crate::vector_storage::dense::appendable_mmap_dense_vector_storage::{
-    open_appendable_memmap_vector_storage, open_appendable_memmap_vector_storage_byte,
+    open_appendable_memmap_vector_storage, open_appendable_memmap_vector_storage_bf16,
+    open_appendable_memmap_vector_storage_bit, open_appendable_memmap_vector_storage_byte,
     open_appendable_memmap_vector_storage_half,
 };
 use crate::vector_storage::dense::memmap_dense_vector_storage::{
-    open_memmap_vector_storage, open_memmap_vector_storage_byte, open_memmap_vector_storage_half,
+    open_memmap_vector_storage, open_memmap_vector_storage_bf16, open_memmap_vector_storage_bit,
+    open_memmap_vector_storage_byte, open_memmap_vector_storage_half,
 };
 use crate::vector_storage::dense::simple_dense_vector_storage::{
+    open_simple_dense_bf16_vector_storage, open_simple_dense_bit_vector_storage,
     open_simple_dense_byte_vector_storage, open_simple_dense_half_vector_storage,
     open_simple_dense_vector_storage,
 };
 use crate::vector_storage::multi_dense::appendable_mmap_multi_dense_vector_storage::{
-    open_appendable_memmap_multi_vector_storage, open_appendable_memmap_multi_vector_storage_byte,
+    open_appendable_memmap_multi_vector_storage, open_appendable_memmap_multi_vector_storage_bf16,
+    open_appendable_memmap_multi_vector_storage_byte,
     open_appendable_memmap_multi_vector_storage_half,
 };
 use crate::vector_storage::multi_dense::simple_multi_dense_vector_storage::{
-    open_simple_multi_dense_vector_storage, open_simple_multi_dense_vector_storage_byte,
-    open_simple_multi_dense_vector_storage_half,
+    open_simple_multi_dense_vector_storage, open_simple_multi_dense_vector_storage_bf16,
+    open_simple_multi_dense_vector_storage_byte, open_simple_multi_dense_vector_storage_half,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::simple_sparse_vector_storage::open_simple_sparse_vector_storage;
@@ -123,6 +127,17 @@ pub(crate) fn open_vector_storage(
                         *multi_vec_config,
                         stopped,
                     ),
+                    VectorStorageDatatype::Bfloat16 => open_simple_multi_dense_vector_storage_bf16(
+                        database.clone(),
+                        &db_column_name,
+                        vector_config.size,
+                        vector_config.distance,
+                        *multi_vec_config,
+                        stopped,
+                    ),
+                    VectorStorageDatatype::Bit => Err(OperationError::ValidationError {
+                        description: "Multivectors are not supported for bit datatype".to_string(),
+                    }),
                 }
             } else {
                 match storage_element_type {
@@ -147,6 +162,20 @@ pub(crate) fn open_vector_storage(
                         vector_config.distance,
                         stopped,
                     ),
+                    VectorStorageDatatype::Bfloat16 => open_simple_dense_bf16_vector_storage(
+                        database.clone(),
+                        &db_column_name,
+                        vector_config.size,
+                        vector_config.distance,
+                        stopped,
+                    ),
+                    VectorStorageDatatype::Bit => open_simple_dense_bit_vector_storage(
+                        database.clone(),
+                        &db_column_name,
+                        vector_config.size,
+                        vector_config.distance,
+                        stopped,
+                    ),
                 }
             }
         }
@@ -177,6 +206,17 @@ pub(crate) fn open_vector_storage(
                             *multi_vec_config,
                         )
                     }
+                    VectorStorageDatatype::Bfloat16 => {
+                        open_appendable_memmap_multi_vector_storage_bf16(
+                            vector_storage_path,
+                            vector_config.size,
+                            vector_config.distance,
+                            *multi_vec_config,
+                        )
+                    }
+                    VectorStorageDatatype::Bit => Err(OperationError::ValidationError {
+                        description: "Multivectors are not supported for bit datatype".to_string(),
+                    }),
                 }
             } else {
                 match storage_element_type {
@@ -195,6 +235,16 @@ pub(crate) fn open_vector_storage(
                         vector_config.size,
                         vector_config.distance,
                     ),
+                    VectorStorageDatatype::Bfloat16 => open_memmap_vector_storage_bf16(
+                        vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    ),
+                    VectorStorageDatatype::Bit => open_memmap_vector_storage_bit(
+                        vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    ),
                 }
             }
         }
@@ -224,6 +274,17 @@ pub(crate) fn open_vector_storage(
                             *multi_vec_config,
                         )
                     }
+                    VectorStorageDatatype::Bfloat16 => {
+                        open_appendable_memmap_multi_vector_storage_bf16(
+                            vector_storage_path,
+                            vector_config.size,
+                            vector_config.distance,
+                            *multi_vec_config,
+                        )
+                    }
+                    VectorStorageDatatype::Bit => Err(OperationError::ValidationError {
+                        description: "Multivectors are not supported for bit datatype".to_string(),
+                    }),
                 }
             } else {
                 match storage_element_type {
@@ -242,6 +303,16 @@ pub(crate) fn open_vector_storage(
                         vector_config.size,
                         vector_config.distance,
                     ),
+                    VectorStorageDatatype::Bfloat16 => open_appendable_memmap_vector_storage_bf16(
+                        vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    ),
+                    VectorStorageDatatype::Bit => open_appendable_memmap_vector_storage_bit(
+                        vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    ),
                 }
             }
         }
@@ -299,6 +370,7 @@ pub(crate) fn create_vector_index(
     vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
     payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
     quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
+    stopped: &AtomicBool,
 ) -> OperationResult<VectorIndexEnum> {
     let vector_index = match &vector_config.index {
         Indexes::Plain {} => VectorIndexEnum::Plain(PlainIndex::new(
@@ -327,6 +399,17 @@ pub(crate) fn create_vector_index(
                 )?)
             }
         }
+        Indexes::AppendableHnsw(vector_hnsw_config) => {
+            VectorIndexEnum::HnswRam(HNSWIndex::<GraphLinksRam>::open_appendable(
+                vector_index_path,
+                id_tracker.clone(),
+                vector_storage.clone(),
+                quantized_vectors.clone(),
+                payload_index.clone(),
+                vector_hnsw_config.clone(),
+                stopped,
+            )?)
+        }
     };
 
     Ok(vector_index)
@@ -446,6 +529,7 @@ fn create_segment(
             vector_storage.clone(),
             payload_index.clone(),
             quantized_vectors.clone(),
+            stopped,
         )?);
 
         check_process_stopped(stopped)?;
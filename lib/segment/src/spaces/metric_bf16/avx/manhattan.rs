@@ -0,0 +1,96 @@
+use std::arch::x86_64::*;
+
+use common::types::ScoreType;
+use half::bf16;
+
+use super::load_bf16x8_avx;
+use crate::data_types::vectors::VectorElementTypeBfloat16;
+use crate::spaces::simple_avx::hsum256_ps_avx;
+
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "fma")]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn avx_manhattan_similarity_bf16(
+    v1: &[VectorElementTypeBfloat16],
+    v2: &[VectorElementTypeBfloat16],
+) -> ScoreType {
+    let mask: __m256 = _mm256_set1_ps(-0.0f32); // 1 << 31 used to clear sign bit to mimic abs
+
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const __m128i = v1.as_ptr() as *const __m128i;
+    let mut ptr2: *const __m128i = v2.as_ptr() as *const __m128i;
+    let mut sum256_1: __m256 = _mm256_setzero_ps();
+    let mut sum256_2: __m256 = _mm256_setzero_ps();
+    let mut sum256_3: __m256 = _mm256_setzero_ps();
+    let mut sum256_4: __m256 = _mm256_setzero_ps();
+
+    let mut i: usize = 0;
+    while i < m {
+        let sub256_1: __m256 = _mm256_sub_ps(load_bf16x8_avx(ptr1), load_bf16x8_avx(ptr2));
+        sum256_1 = _mm256_add_ps(_mm256_andnot_ps(mask, sub256_1), sum256_1);
+
+        let sub256_2: __m256 = _mm256_sub_ps(
+            load_bf16x8_avx(ptr1.wrapping_add(1)),
+            load_bf16x8_avx(ptr2.wrapping_add(1)),
+        );
+        sum256_2 = _mm256_add_ps(_mm256_andnot_ps(mask, sub256_2), sum256_2);
+
+        let sub256_3: __m256 = _mm256_sub_ps(
+            load_bf16x8_avx(ptr1.wrapping_add(2)),
+            load_bf16x8_avx(ptr2.wrapping_add(2)),
+        );
+        sum256_3 = _mm256_add_ps(_mm256_andnot_ps(mask, sub256_3), sum256_3);
+
+        let sub256_4: __m256 = _mm256_sub_ps(
+            load_bf16x8_avx(ptr1.wrapping_add(3)),
+            load_bf16x8_avx(ptr2.wrapping_add(3)),
+        );
+        sum256_4 = _mm256_add_ps(_mm256_andnot_ps(mask, sub256_4), sum256_4);
+
+        ptr1 = ptr1.wrapping_add(4);
+        ptr2 = ptr2.wrapping_add(4);
+        i += 32;
+    }
+
+    let ptr1_bf16: *const bf16 = ptr1 as *const bf16;
+    let ptr2_bf16: *const bf16 = ptr2 as *const bf16;
+
+    let mut result = hsum256_ps_avx(sum256_1)
+        + hsum256_ps_avx(sum256_2)
+        + hsum256_ps_avx(sum256_3)
+        + hsum256_ps_avx(sum256_4);
+    for i in 0..n - m {
+        result += ((*ptr1_bf16.add(i)).to_f32() - (*ptr2_bf16.add(i)).to_f32()).abs();
+    }
+    -result
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::spaces::metric_bf16::simple_manhattan::manhattan_similarity_bf16;
+
+    #[test]
+    fn test_spaces_avx() {
+        if is_x86_feature_detected!("avx") && is_x86_feature_detected!("fma") {
+            let mut rng = rand::thread_rng();
+            for len in [32, 33, 64, 100, 257] {
+                let v1: Vec<bf16> = (0..len)
+                    .map(|_| bf16::from_f32(rng.gen_range(0.0..8.0)))
+                    .collect();
+                let v2: Vec<bf16> = (0..len)
+                    .map(|_| bf16::from_f32(rng.gen_range(0.0..8.0)))
+                    .collect();
+
+                let score_simd = unsafe { avx_manhattan_similarity_bf16(&v1, &v2) };
+                let score = manhattan_similarity_bf16(&v1, &v2);
+                assert!((score_simd - score).abs() <= 0.0005 * score.abs().max(1.0));
+            }
+        } else {
+            println!("avx test skipped");
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::arch::x86_64::*;
+
+pub mod dot;
+pub mod euclid;
+pub mod manhattan;
+
+/// Widen 8 packed bf16 values into 8 f32 values.
+///
+/// bf16 is the upper half of an f32, so interleaving with zeros yields the exact f32 bits.
+#[target_feature(enable = "avx")]
+#[inline]
+pub(crate) unsafe fn load_bf16x8_avx(ptr: *const __m128i) -> __m256 {
+    let zero = _mm_setzero_si128();
+    let packed = _mm_loadu_si128(ptr);
+    let lo = _mm_unpacklo_epi16(zero, packed);
+    let hi = _mm_unpackhi_epi16(zero, packed);
+    _mm256_castsi256_ps(_mm256_set_m128i(hi, lo))
+}
@@ -0,0 +1,77 @@
+use std::arch::aarch64::*;
+
+use common::types::ScoreType;
+use half::bf16;
+
+use super::load_bf16x8_neon;
+use crate::data_types::vectors::VectorElementTypeBfloat16;
+
+#[target_feature(enable = "neon")]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn neon_dot_similarity_bf16(
+    v1: &[VectorElementTypeBfloat16],
+    v2: &[VectorElementTypeBfloat16],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const bf16 = v1.as_ptr();
+    let mut ptr2: *const bf16 = v2.as_ptr();
+    let mut sum1 = vdupq_n_f32(0.);
+    let mut sum2 = vdupq_n_f32(0.);
+    let mut sum3 = vdupq_n_f32(0.);
+    let mut sum4 = vdupq_n_f32(0.);
+
+    let mut i: usize = 0;
+    while i < m {
+        let (a1, a2) = load_bf16x8_neon(ptr1);
+        let (b1, b2) = load_bf16x8_neon(ptr2);
+        sum1 = vfmaq_f32(sum1, a1, b1);
+        sum2 = vfmaq_f32(sum2, a2, b2);
+
+        let (a3, a4) = load_bf16x8_neon(ptr1.add(8));
+        let (b3, b4) = load_bf16x8_neon(ptr2.add(8));
+        sum3 = vfmaq_f32(sum3, a3, b3);
+        sum4 = vfmaq_f32(sum4, a4, b4);
+
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut result = vaddvq_f32(sum1) + vaddvq_f32(sum2) + vaddvq_f32(sum3) + vaddvq_f32(sum4);
+    for i in 0..n - m {
+        result += (*ptr1.add(i)).to_f32() * (*ptr2.add(i)).to_f32();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::arch::is_aarch64_feature_detected;
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::spaces::metric_bf16::simple_dot::dot_similarity_bf16;
+
+    #[test]
+    fn test_spaces_neon() {
+        if is_aarch64_feature_detected!("neon") {
+            let mut rng = rand::thread_rng();
+            for len in [16, 17, 32, 100, 257] {
+                let v1: Vec<bf16> = (0..len)
+                    .map(|_| bf16::from_f32(rng.gen_range(0.0..8.0)))
+                    .collect();
+                let v2: Vec<bf16> = (0..len)
+                    .map(|_| bf16::from_f32(rng.gen_range(0.0..8.0)))
+                    .collect();
+
+                let score_simd = unsafe { neon_dot_similarity_bf16(&v1, &v2) };
+                let score = dot_similarity_bf16(&v1, &v2);
+                assert!((score_simd - score).abs() <= 0.0005 * score.abs().max(1.0));
+            }
+        } else {
+            println!("neon test skipped");
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use std::arch::aarch64::*;
+
+use half::bf16;
+
+pub mod dot;
+pub mod euclid;
+pub mod manhattan;
+
+/// Widen 8 packed bf16 values into two vectors of 4 f32 values.
+///
+/// bf16 is the upper half of an f32, so shifting into the high bits yields the exact f32 bits.
+#[target_feature(enable = "neon")]
+#[inline]
+pub(crate) unsafe fn load_bf16x8_neon(ptr: *const bf16) -> (float32x4_t, float32x4_t) {
+    let packed = vld1q_u16(ptr as *const u16);
+    (
+        vreinterpretq_f32_u32(vshll_n_u16::<16>(vget_low_u16(packed))),
+        vreinterpretq_f32_u32(vshll_n_u16::<16>(vget_high_u16(packed))),
+    )
+}
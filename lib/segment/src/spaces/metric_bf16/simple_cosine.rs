@@ -0,0 +1,84 @@
+use common::types::ScoreType;
+
+use super::simple_dot::dot_similarity_bf16;
+use crate::data_types::vectors::{DenseVector, VectorElementTypeBfloat16};
+use crate::spaces::metric::Metric;
+#[cfg(target_arch = "x86_64")]
+use crate::spaces::metric_bf16::avx::dot::avx_dot_similarity_bf16;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::spaces::metric_bf16::neon::dot::neon_dot_similarity_bf16;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::spaces::metric_bf16::sse::dot::sse_dot_similarity_bf16;
+#[cfg(target_arch = "x86_64")]
+use crate::spaces::simple::MIN_DIM_SIZE_AVX;
+use crate::spaces::simple::{cosine_preprocess, CosineMetric, MIN_DIM_SIZE_SIMD};
+#[cfg(target_arch = "x86_64")]
+use crate::spaces::simple_avx::*;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::spaces::simple_neon::*;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::spaces::simple_sse::*;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeBfloat16> for CosineMetric {
+    fn distance() -> Distance {
+        Distance::Cosine
+    }
+
+    fn similarity(v1: &[VectorElementTypeBfloat16], v2: &[VectorElementTypeBfloat16]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx")
+                && is_x86_feature_detected!("fma")
+                && v1.len() >= MIN_DIM_SIZE_AVX
+            {
+                return unsafe { avx_dot_similarity_bf16(v1, v2) };
+            }
+        }
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse2") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { sse_dot_similarity_bf16(v1, v2) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { neon_dot_similarity_bf16(v1, v2) };
+            }
+        }
+
+        dot_similarity_bf16(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx")
+                && is_x86_feature_detected!("fma")
+                && vector.len() >= MIN_DIM_SIZE_AVX
+            {
+                return unsafe { cosine_preprocess_avx(vector) };
+            }
+        }
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse") && vector.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { cosine_preprocess_sse(vector) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") && vector.len() >= MIN_DIM_SIZE_SIMD
+            {
+                return unsafe { cosine_preprocess_neon(vector) };
+            }
+        }
+
+        cosine_preprocess(vector)
+    }
+}
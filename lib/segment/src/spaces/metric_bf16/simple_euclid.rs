@@ -0,0 +1,62 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeBfloat16};
+use crate::spaces::metric::Metric;
+#[cfg(target_arch = "x86_64")]
+use crate::spaces::metric_bf16::avx::euclid::avx_euclid_similarity_bf16;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::spaces::metric_bf16::neon::euclid::neon_euclid_similarity_bf16;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::spaces::metric_bf16::sse::euclid::sse_euclid_similarity_bf16;
+#[cfg(target_arch = "x86_64")]
+use crate::spaces::simple::MIN_DIM_SIZE_AVX;
+use crate::spaces::simple::{EuclidMetric, MIN_DIM_SIZE_SIMD};
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeBfloat16> for EuclidMetric {
+    fn distance() -> Distance {
+        Distance::Euclid
+    }
+
+    fn similarity(v1: &[VectorElementTypeBfloat16], v2: &[VectorElementTypeBfloat16]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx")
+                && is_x86_feature_detected!("fma")
+                && v1.len() >= MIN_DIM_SIZE_AVX
+            {
+                return unsafe { avx_euclid_similarity_bf16(v1, v2) };
+            }
+        }
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse2") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { sse_euclid_similarity_bf16(v1, v2) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { neon_euclid_similarity_bf16(v1, v2) };
+            }
+        }
+
+        euclid_similarity_bf16(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+pub fn euclid_similarity_bf16(
+    v1: &[VectorElementTypeBfloat16],
+    v2: &[VectorElementTypeBfloat16],
+) -> ScoreType {
+    -v1.iter()
+        .zip(v2)
+        .map(|(a, b)| (a.to_f32() - b.to_f32()).powi(2))
+        .sum::<f32>()
+}
@@ -0,0 +1,89 @@
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use common::types::ScoreType;
+use half::bf16;
+
+use super::load_bf16x8_sse;
+use crate::data_types::vectors::VectorElementTypeBfloat16;
+use crate::spaces::simple_sse::hsum128_ps_sse;
+
+#[target_feature(enable = "sse2")]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn sse_euclid_similarity_bf16(
+    v1: &[VectorElementTypeBfloat16],
+    v2: &[VectorElementTypeBfloat16],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const __m128i = v1.as_ptr() as *const __m128i;
+    let mut ptr2: *const __m128i = v2.as_ptr() as *const __m128i;
+    let mut sum128_1: __m128 = _mm_setzero_ps();
+    let mut sum128_2: __m128 = _mm_setzero_ps();
+    let mut sum128_3: __m128 = _mm_setzero_ps();
+    let mut sum128_4: __m128 = _mm_setzero_ps();
+
+    let mut i: usize = 0;
+    while i < m {
+        let (a1, a2) = load_bf16x8_sse(ptr1);
+        let (b1, b2) = load_bf16x8_sse(ptr2);
+        let sub128_1 = _mm_sub_ps(a1, b1);
+        let sub128_2 = _mm_sub_ps(a2, b2);
+        sum128_1 = _mm_add_ps(_mm_mul_ps(sub128_1, sub128_1), sum128_1);
+        sum128_2 = _mm_add_ps(_mm_mul_ps(sub128_2, sub128_2), sum128_2);
+
+        let (a3, a4) = load_bf16x8_sse(ptr1.add(1));
+        let (b3, b4) = load_bf16x8_sse(ptr2.add(1));
+        let sub128_3 = _mm_sub_ps(a3, b3);
+        let sub128_4 = _mm_sub_ps(a4, b4);
+        sum128_3 = _mm_add_ps(_mm_mul_ps(sub128_3, sub128_3), sum128_3);
+        sum128_4 = _mm_add_ps(_mm_mul_ps(sub128_4, sub128_4), sum128_4);
+
+        ptr1 = ptr1.add(2);
+        ptr2 = ptr2.add(2);
+        i += 16;
+    }
+
+    let ptr1_bf16: *const bf16 = ptr1 as *const bf16;
+    let ptr2_bf16: *const bf16 = ptr2 as *const bf16;
+
+    let mut result = hsum128_ps_sse(sum128_1)
+        + hsum128_ps_sse(sum128_2)
+        + hsum128_ps_sse(sum128_3)
+        + hsum128_ps_sse(sum128_4);
+    for i in 0..n - m {
+        result += ((*ptr1_bf16.add(i)).to_f32() - (*ptr2_bf16.add(i)).to_f32()).powi(2);
+    }
+    -result
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::spaces::metric_bf16::simple_euclid::euclid_similarity_bf16;
+
+    #[test]
+    fn test_spaces_sse() {
+        if is_x86_feature_detected!("sse2") {
+            let mut rng = rand::thread_rng();
+            for len in [16, 17, 32, 100, 257] {
+                let v1: Vec<bf16> = (0..len)
+                    .map(|_| bf16::from_f32(rng.gen_range(0.0..8.0)))
+                    .collect();
+                let v2: Vec<bf16> = (0..len)
+                    .map(|_| bf16::from_f32(rng.gen_range(0.0..8.0)))
+                    .collect();
+
+                let score_simd = unsafe { sse_euclid_similarity_bf16(&v1, &v2) };
+                let score = euclid_similarity_bf16(&v1, &v2);
+                assert!((score_simd - score).abs() <= 0.0005 * score.abs().max(1.0));
+            }
+        } else {
+            println!("sse test skipped");
+        }
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+pub mod dot;
+pub mod euclid;
+pub mod manhattan;
+
+/// Widen 8 packed bf16 values into two vectors of 4 f32 values.
+///
+/// bf16 is the upper half of an f32, so interleaving with zeros yields the exact f32 bits.
+#[target_feature(enable = "sse2")]
+#[inline]
+pub(crate) unsafe fn load_bf16x8_sse(ptr: *const __m128i) -> (__m128, __m128) {
+    let zero = _mm_setzero_si128();
+    let packed = _mm_loadu_si128(ptr);
+    (
+        _mm_castsi128_ps(_mm_unpacklo_epi16(zero, packed)),
+        _mm_castsi128_ps(_mm_unpackhi_epi16(zero, packed)),
+    )
+}
@@ -0,0 +1,65 @@
+use std::arch::x86_64::*;
+
+use super::{hsum256_epi64_avx, popcount256_epi64_avx};
+
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "avx2")]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn avx_hamming_similarity_bits(v1: &[u8], v2: &[u8]) -> f32 {
+    debug_assert!(v1.len() == v2.len());
+    debug_assert!(is_x86_feature_detected!("avx"));
+    debug_assert!(is_x86_feature_detected!("avx2"));
+
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+
+    // sum accumulator for 4x64 bit integers
+    let mut acc = _mm256_setzero_si256();
+    let len = v1.len();
+    for _ in 0..len / 32 {
+        // load 32 bytes
+        let p1 = _mm256_loadu_si256(ptr1 as *const __m256i);
+        let p2 = _mm256_loadu_si256(ptr2 as *const __m256i);
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+
+        // count differing bits
+        let diff = _mm256_xor_si256(p1, p2);
+        acc = _mm256_add_epi64(acc, popcount256_epi64_avx(diff));
+    }
+
+    let mut score = hsum256_epi64_avx(acc) as u32;
+
+    for _ in 0..len % 32 {
+        score += (*ptr1 ^ *ptr2).count_ones();
+        ptr1 = ptr1.add(1);
+        ptr2 = ptr2.add(1);
+    }
+
+    -(score as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::spaces::metric_bit::simple_hamming::hamming_similarity_bits;
+
+    #[test]
+    fn test_spaces_avx() {
+        if is_x86_feature_detected!("avx") && is_x86_feature_detected!("avx2") {
+            let mut rng = rand::thread_rng();
+            for len in [32, 33, 64, 100, 129] {
+                let v1: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+                let v2: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+
+                let dist_simple = hamming_similarity_bits(&v1, &v2);
+                let dist_avx = unsafe { avx_hamming_similarity_bits(&v1, &v2) };
+                assert_eq!(dist_simple, dist_avx);
+            }
+        } else {
+            println!("avx test skipped");
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::arch::x86_64::*;
+
+pub mod hamming;
+pub mod jaccard;
+
+/// Count set bits in every byte and sum them into 4x64 bit integers
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "avx2")]
+#[allow(clippy::missing_safety_doc)]
+pub(crate) unsafe fn popcount256_epi64_avx(v: __m256i) -> __m256i {
+    // number of set bits for every nibble value
+    let lookup = _mm256_setr_epi8(
+        0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4, 0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3,
+        3, 4,
+    );
+    let low_mask = _mm256_set1_epi8(0x0f);
+    let low = _mm256_and_si256(v, low_mask);
+    let high = _mm256_and_si256(_mm256_srli_epi16(v, 4), low_mask);
+    let counts = _mm256_add_epi8(
+        _mm256_shuffle_epi8(lookup, low),
+        _mm256_shuffle_epi8(lookup, high),
+    );
+    _mm256_sad_epu8(counts, _mm256_setzero_si256())
+}
+
+#[target_feature(enable = "avx")]
+#[allow(clippy::missing_safety_doc)]
+pub(crate) unsafe fn hsum256_epi64_avx(v: __m256i) -> u64 {
+    let mut lanes = [0u64; 4];
+    _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, v);
+    lanes.iter().sum()
+}
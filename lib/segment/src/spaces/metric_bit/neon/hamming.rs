@@ -0,0 +1,58 @@
+use std::arch::aarch64::*;
+
+#[target_feature(enable = "neon")]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn neon_hamming_similarity_bits(v1: &[u8], v2: &[u8]) -> f32 {
+    debug_assert!(v1.len() == v2.len());
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+
+    let mut sum32 = vdupq_n_u32(0);
+    let len = v1.len();
+    for _ in 0..len / 16 {
+        let p1 = vld1q_u8(ptr1);
+        let p2 = vld1q_u8(ptr2);
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+
+        // count differing bits per byte and widen into 32-bit accumulator
+        let counts = vcntq_u8(veorq_u8(p1, p2));
+        sum32 = vpadalq_u16(sum32, vpaddlq_u8(counts));
+    }
+    let mut score = vaddvq_u32(sum32);
+
+    for _ in 0..len % 16 {
+        score += (*ptr1 ^ *ptr2).count_ones();
+        ptr1 = ptr1.add(1);
+        ptr2 = ptr2.add(1);
+    }
+
+    -(score as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::arch::is_aarch64_feature_detected;
+
+    use rand::Rng;
+
+    use super::*;
+    use crate::spaces::metric_bit::simple_hamming::hamming_similarity_bits;
+
+    #[test]
+    fn test_spaces_neon() {
+        if is_aarch64_feature_detected!("neon") {
+            let mut rng = rand::thread_rng();
+            for len in [16, 17, 32, 100, 129] {
+                let v1: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+                let v2: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+
+                let dist_simple = hamming_similarity_bits(&v1, &v2);
+                let dist_neon = unsafe { neon_hamming_similarity_bits(&v1, &v2) };
+                assert_eq!(dist_simple, dist_neon);
+            }
+        } else {
+            println!("neon test skipped");
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod hamming;
+pub mod jaccard;
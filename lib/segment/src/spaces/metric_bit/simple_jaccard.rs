@@ -0,0 +1,105 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, PackedBits, VectorElementTypeBit};
+use crate::spaces::metric::Metric;
+#[cfg(target_arch = "x86_64")]
+use crate::spaces::metric_bit::avx2::jaccard::avx_jaccard_similarity_bits;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::spaces::metric_bit::neon::jaccard::neon_jaccard_similarity_bits;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::spaces::metric_bit::ssse3::jaccard::sse_jaccard_similarity_bits;
+#[cfg(target_arch = "x86_64")]
+use crate::spaces::simple::MIN_DIM_SIZE_AVX;
+use crate::spaces::simple::{JaccardMetric, MIN_DIM_SIZE_SIMD};
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeBit> for JaccardMetric {
+    fn distance() -> Distance {
+        Distance::Jaccard
+    }
+
+    fn similarity(v1: &[VectorElementTypeBit], v2: &[VectorElementTypeBit]) -> ScoreType {
+        let v1 = PackedBits::as_bytes(v1);
+        let v2 = PackedBits::as_bytes(v2);
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx")
+                && is_x86_feature_detected!("avx2")
+                && v1.len() >= MIN_DIM_SIZE_AVX
+            {
+                return unsafe { avx_jaccard_similarity_bits(v1, v2) };
+            }
+        }
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse2")
+                && is_x86_feature_detected!("ssse3")
+                && v1.len() >= MIN_DIM_SIZE_SIMD
+            {
+                return unsafe { sse_jaccard_similarity_bits(v1, v2) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { neon_jaccard_similarity_bits(v1, v2) };
+            }
+        }
+
+        jaccard_similarity_bits(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+/// Negated Jaccard distance, computed from the number of bits set in both vectors
+/// and the number of bits set in any of them
+pub fn jaccard_similarity_from_counts(intersection: u32, union: u32) -> ScoreType {
+    if union == 0 {
+        // Two empty sets are considered identical
+        return 0.0;
+    }
+    -(1.0 - intersection as ScoreType / union as ScoreType)
+}
+
+pub fn jaccard_similarity_bits(v1: &[u8], v2: &[u8]) -> ScoreType {
+    let (intersection, union) = v1
+        .iter()
+        .zip(v2)
+        .fold((0, 0), |(intersection, union), (a, b)| {
+            (
+                intersection + (a & b).count_ones(),
+                union + (a | b).count_ones(),
+            )
+        });
+    jaccard_similarity_from_counts(intersection, union)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jaccard_packed_bits() {
+        let v1 = PackedBits::pack(&[1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+        let v2 = PackedBits::pack(&[1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        // 2 common bits out of 5 set bits
+        let similarity = <JaccardMetric as Metric<VectorElementTypeBit>>::similarity(&v1, &v2);
+        assert!((similarity + 0.6).abs() < 1e-6);
+        assert_eq!(
+            <JaccardMetric as Metric<VectorElementTypeBit>>::similarity(&v1, &v1),
+            0.0
+        );
+
+        let empty = PackedBits::pack(&[0.0; 8]);
+        assert_eq!(
+            <JaccardMetric as Metric<VectorElementTypeBit>>::similarity(&empty, &empty),
+            0.0
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use std::arch::x86_64::*;
+
+use super::{hsum128_epi64_sse, popcount128_epi64_sse};
+use crate::spaces::metric_bit::simple_jaccard::jaccard_similarity_from_counts;
+
+#[target_feature(enable = "sse2")]
+#[target_feature(enable = "ssse3")]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn sse_jaccard_similarity_bits(v1: &[u8], v2: &[u8]) -> f32 {
+    debug_assert!(v1.len() == v2.len());
+    debug_assert!(is_x86_feature_detected!("sse2"));
+    debug_assert!(is_x86_feature_detected!("ssse3"));
+
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+
+    // sum accumulators for 2x64 bit integers
+    let mut intersection_acc = _mm_setzero_si128();
+    let mut union_acc = _mm_setzero_si128();
+    let len = v1.len();
+    for _ in 0..len / 16 {
+        // load 16 bytes
+        let p1 = _mm_loadu_si128(ptr1 as *const __m128i);
+        let p2 = _mm_loadu_si128(ptr2 as *const __m128i);
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+
+        let intersection = _mm_and_si128(p1, p2);
+        let union = _mm_or_si128(p1, p2);
+        intersection_acc = _mm_add_epi64(intersection_acc, popcount128_epi64_sse(intersection));
+        union_acc = _mm_add_epi64(union_acc, popcount128_epi64_sse(union));
+    }
+
+    let mut intersection = hsum128_epi64_sse(intersection_acc) as u32;
+    let mut union = hsum128_epi64_sse(union_acc) as u32;
+
+    for _ in 0..len % 16 {
+        intersection += (*ptr1 & *ptr2).count_ones();
+        union += (*ptr1 | *ptr2).count_ones();
+        ptr1 = ptr1.add(1);
+        ptr2 = ptr2.add(1);
+    }
+
+    jaccard_similarity_from_counts(intersection, union)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::spaces::metric_bit::simple_jaccard::jaccard_similarity_bits;
+
+    #[test]
+    fn test_spaces_sse() {
+        if is_x86_feature_detected!("sse2") && is_x86_feature_detected!("ssse3") {
+            let mut rng = rand::thread_rng();
+            for len in [16, 17, 32, 100, 129] {
+                let v1: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+                let v2: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+
+                let dist_simple = jaccard_similarity_bits(&v1, &v2);
+                let dist_sse = unsafe { sse_jaccard_similarity_bits(&v1, &v2) };
+                assert_eq!(dist_simple, dist_sse);
+            }
+        } else {
+            println!("sse test skipped");
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use std::arch::x86_64::*;
+
+pub mod hamming;
+pub mod jaccard;
+
+/// Count set bits in every byte and sum them into 2x64 bit integers
+#[target_feature(enable = "sse2")]
+#[target_feature(enable = "ssse3")]
+#[allow(clippy::missing_safety_doc)]
+pub(crate) unsafe fn popcount128_epi64_sse(v: __m128i) -> __m128i {
+    // number of set bits for every nibble value
+    let lookup = _mm_setr_epi8(0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4);
+    let low_mask = _mm_set1_epi8(0x0f);
+    let low = _mm_and_si128(v, low_mask);
+    let high = _mm_and_si128(_mm_srli_epi16(v, 4), low_mask);
+    let counts = _mm_add_epi8(
+        _mm_shuffle_epi8(lookup, low),
+        _mm_shuffle_epi8(lookup, high),
+    );
+    _mm_sad_epu8(counts, _mm_setzero_si128())
+}
+
+#[target_feature(enable = "sse2")]
+#[allow(clippy::missing_safety_doc)]
+pub(crate) unsafe fn hsum128_epi64_sse(v: __m128i) -> u64 {
+    let mut lanes = [0u64; 2];
+    _mm_storeu_si128(lanes.as_mut_ptr() as *mut __m128i, v);
+    lanes.iter().sum()
+}
@@ -33,6 +33,12 @@ pub struct EuclidMetric;
 #[derive(Clone)]
 pub struct ManhattanMetric;
 
+#[derive(Clone)]
+pub struct HammingMetric;
+
+#[derive(Clone)]
+pub struct JaccardMetric;
+
 impl Metric<VectorElementType> for EuclidMetric {
     fn distance() -> Distance {
         Distance::Euclid
@@ -211,6 +217,18 @@ impl MetricPostProcessing for CosineMetric {
     }
 }
 
+impl MetricPostProcessing for HammingMetric {
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score.abs()
+    }
+}
+
+impl MetricPostProcessing for JaccardMetric {
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score.abs()
+    }
+}
+
 pub fn euclid_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
     -v1.iter()
         .zip(v2)
@@ -5,6 +5,7 @@ use std::hash::Hash;
 use std::ops::Deref;
 use std::rc::Rc;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use common::types::ScoreType;
 use fnv::FnvBuildHasher;
@@ -23,14 +24,19 @@ use validator::{Validate, ValidationError, ValidationErrors};
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::utils::{self, MaybeOneOrMany, MultiValue};
+use crate::data_types::index::{
+    DatetimeIndexParams, FloatIndexParams, GeoIndexParams, KeywordIndexParams, UuidIndexParams,
+};
 use crate::data_types::integer_index::IntegerIndexParams;
 use crate::data_types::order_by::OrderValue;
 use crate::data_types::text_index::TextIndexParams;
-use crate::data_types::vectors::VectorStruct;
+use crate::data_types::vectors::{DenseVector, VectorElementType, VectorStruct};
 use crate::index::sparse_index::sparse_index_config::SparseIndexConfig;
 use crate::json_path::{JsonPath, JsonPathInterface};
-use crate::spaces::metric::MetricPostProcessing;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::metric::{Metric, MetricPostProcessing};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+};
 use crate::vector_storage::simple_sparse_vector_storage::SPARSE_VECTOR_DISTANCE;
 
 pub type PayloadKeyType = JsonPath;
@@ -44,6 +50,8 @@ pub type FloatPayloadType = f64;
 pub type IntPayloadType = i64;
 /// Type of datetime point payload
 pub type DateTimePayloadType = DateTimeWrapper;
+/// Type of Uuid point payload, as it is stored in the index
+pub type UuidIntType = u128;
 
 /// Wraps `DateTime<Utc>` to allow more flexible deserialization
 #[derive(Clone, Copy, Serialize, JsonSchema, Debug, PartialEq, PartialOrd)]
@@ -161,6 +169,10 @@ pub enum Distance {
     Dot,
     // <https://simple.wikipedia.org/wiki/Manhattan_distance>
     Manhattan,
+    // <https://en.wikipedia.org/wiki/Hamming_distance>
+    Hamming,
+    // <https://en.wikipedia.org/wiki/Jaccard_index>
+    Jaccard,
 }
 
 impl Distance {
@@ -170,13 +182,39 @@ impl Distance {
             Distance::Euclid => EuclidMetric::postprocess(score),
             Distance::Dot => DotProductMetric::postprocess(score),
             Distance::Manhattan => ManhattanMetric::postprocess(score),
+            Distance::Hamming => HammingMetric::postprocess(score),
+            Distance::Jaccard => JaccardMetric::postprocess(score),
+        }
+    }
+
+    /// Preprocess a float vector the same way, as it is preprocessed before being stored
+    pub fn preprocess_vector(&self, vector: DenseVector) -> DenseVector {
+        match self {
+            Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Euclid => <EuclidMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Dot => <DotProductMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Manhattan => {
+                <ManhattanMetric as Metric<VectorElementType>>::preprocess(vector)
+            }
+            // binary distances are only defined on the bit datatype
+            Distance::Hamming | Distance::Jaccard => vector,
         }
     }
 
     pub fn distance_order(&self) -> Order {
         match self {
             Distance::Cosine | Distance::Dot => Order::LargeBetter,
-            Distance::Euclid | Distance::Manhattan => Order::SmallBetter,
+            Distance::Euclid | Distance::Manhattan | Distance::Hamming | Distance::Jaccard => {
+                Order::SmallBetter
+            }
+        }
+    }
+
+    /// Whether the distance compares binary vectors, which requires the `bit` datatype
+    pub fn is_binary(&self) -> bool {
+        match self {
+            Distance::Cosine | Distance::Euclid | Distance::Dot | Distance::Manhattan => false,
+            Distance::Hamming | Distance::Jaccard => true,
         }
     }
 
@@ -189,7 +227,7 @@ impl Distance {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Order {
     LargeBetter,
     SmallBetter,
@@ -271,17 +309,10 @@ impl PayloadIndexInfo {
                 params: None,
                 points: points_count,
             },
-            PayloadFieldSchema::FieldParams(schema_params) => match schema_params {
-                PayloadSchemaParams::Text(_) => PayloadIndexInfo {
-                    data_type: PayloadSchemaType::Text,
-                    params: Some(schema_params),
-                    points: points_count,
-                },
-                PayloadSchemaParams::Integer(_) => PayloadIndexInfo {
-                    data_type: PayloadSchemaType::Integer,
-                    params: Some(schema_params),
-                    points: points_count,
-                },
+            PayloadFieldSchema::FieldParams(schema_params) => PayloadIndexInfo {
+                data_type: schema_params.kind(),
+                params: Some(schema_params),
+                points: points_count,
             },
         }
     }
@@ -429,6 +460,10 @@ pub enum Indexes {
     /// Use filterable HNSW index for approximate search. Is very fast even on a very huge collections,
     /// but require additional space to store index and additional time to build it.
     Hnsw(HnswConfig),
+    /// Use HNSW index, which links new points into the graph right on insertion.
+    /// Graph is kept in RAM and rebuilt on load, so it is intended for appendable segments only,
+    /// which are still converted into regular HNSW index by the optimizer.
+    AppendableHnsw(HnswConfig),
 }
 
 impl Indexes {
@@ -436,6 +471,8 @@ impl Indexes {
         match self {
             Indexes::Plain {} => false,
             Indexes::Hnsw(_) => true,
+            // Segment still needs to be optimized into a persisted index
+            Indexes::AppendableHnsw(_) => false,
         }
     }
 }
@@ -467,6 +504,10 @@ pub struct HnswConfig {
     /// Custom M param for hnsw graph built for payload index. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
     pub payload_m: Option<usize>,
+    /// Link new points into HNSW graph of appendable segments right on insertion, so they are searched
+    /// with the index without waiting for optimization. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
+    pub appendable: Option<bool>,
 }
 
 impl HnswConfig {
@@ -509,6 +550,10 @@ pub enum CompressionRatio {
 pub enum ScalarType {
     #[default]
     Int8,
+    /// 4 bit integers, two dimensions per byte
+    Int4,
+    /// 8 bit floats with 4 bits of exponent and 3 bits of mantissa
+    Float8,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
@@ -516,6 +561,8 @@ pub enum ScalarType {
 pub struct ScalarQuantizationConfig {
     /// Type of quantization to use
     /// If `int8` - 8 bit quantization will be used
+    /// If `int4` - 4 bit quantization will be used, halving memory usage of `int8` at the cost of precision
+    /// If `float8` - 8 bit floating point quantization will be used, which is not clipped by quantile
     pub r#type: ScalarType,
     /// Quantile for quantization. Expected value range in [0.5, 1.0]. If not set - use the whole range of values
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -578,11 +625,54 @@ impl std::hash::Hash for ScalarQuantizationConfig {
 
 impl Eq for ScalarQuantizationConfig {}
 
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BinaryQuantizationEncoding {
+    /// One bit per dimension, only the sign of the value relative to the center is stored
+    #[default]
+    OneBit,
+    /// Two bits per dimension, the value is encoded as one of three levels: below, around and above the center
+    TwoBits,
+    /// Half of the dimensions are encoded with two bits and the other half with one bit
+    OneAndHalfBits,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BinaryQuantizationQueryEncoding {
+    /// Query is encoded in the same way as stored vectors
+    Binary,
+    /// Query is quantized into 4 bits per dimension and scored against binary stored vectors
+    #[serde(rename = "scalar4bits")]
+    Scalar4Bits,
+    /// Query is quantized into 8 bits per dimension and scored against binary stored vectors
+    #[serde(rename = "scalar8bits")]
+    Scalar8Bits,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub struct BinaryQuantizationConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub always_ram: Option<bool>,
+    /// Number of bits used to encode each dimension of stored vectors. Default: `one_bit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<BinaryQuantizationEncoding>,
+    /// Encoding of the query vector. Scalar query encodings improve precision at the cost of
+    /// slower scoring. Default: `binary`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_encoding: Option<BinaryQuantizationQueryEncoding>,
+}
+
+impl BinaryQuantizationConfig {
+    /// Whether the configuration requires more than the plain one bit per dimension encoding
+    /// for stored vectors or queries
+    pub fn is_extended(&self) -> bool {
+        self.encoding.unwrap_or_default() != BinaryQuantizationEncoding::OneBit
+            || self
+                .query_encoding
+                .is_some_and(|encoding| encoding != BinaryQuantizationQueryEncoding::Binary)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
@@ -649,6 +739,7 @@ impl Default for HnswConfig {
             max_indexing_threads: 0,
             on_disk: Some(false),
             payload_m: None,
+            appendable: None,
         }
     }
 }
@@ -806,8 +897,12 @@ pub enum VectorStorageDatatype {
     Float32,
     // Half-precision floating point
     Float16,
+    // Brain floating point, half-precision with the exponent range of `Float32`
+    Bfloat16,
     // Unsigned 8-bit integer
     Uint8,
+    // Single bit, packed by 8 into a byte
+    Bit,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
@@ -864,6 +959,7 @@ impl VectorDataConfig {
         let is_index_appendable = match self.index {
             Indexes::Plain {} => true,
             Indexes::Hnsw(_) => false,
+            Indexes::AppendableHnsw(_) => true,
         };
         let is_storage_appendable = match self.storage_type {
             VectorStorageType::Memory => true,
@@ -904,6 +1000,7 @@ pub struct SegmentState {
 /// Geo point payload schema
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
 #[serde(try_from = "GeoPointShadow")]
+#[repr(C)]
 pub struct GeoPoint {
     pub lon: f64,
     pub lat: f64,
@@ -1150,14 +1247,49 @@ pub enum PayloadSchemaType {
     Text,
     Bool,
     Datetime,
+    Uuid,
 }
 
 /// Payload type with parameters
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
 #[serde(untagged, rename_all = "snake_case")]
 pub enum PayloadSchemaParams {
-    Text(TextIndexParams),
+    Keyword(KeywordIndexParams),
     Integer(IntegerIndexParams),
+    Float(FloatIndexParams),
+    Geo(GeoIndexParams),
+    Text(TextIndexParams),
+    Datetime(DatetimeIndexParams),
+    Uuid(UuidIndexParams),
+}
+
+impl PayloadSchemaParams {
+    /// Payload type of the field, indexed with these params
+    pub fn kind(&self) -> PayloadSchemaType {
+        match self {
+            PayloadSchemaParams::Keyword(_) => PayloadSchemaType::Keyword,
+            PayloadSchemaParams::Integer(_) => PayloadSchemaType::Integer,
+            PayloadSchemaParams::Float(_) => PayloadSchemaType::Float,
+            PayloadSchemaParams::Geo(_) => PayloadSchemaType::Geo,
+            PayloadSchemaParams::Text(_) => PayloadSchemaType::Text,
+            PayloadSchemaParams::Datetime(_) => PayloadSchemaType::Datetime,
+            PayloadSchemaParams::Uuid(_) => PayloadSchemaType::Uuid,
+        }
+    }
+
+    /// Whether the index should be stored on disk (mmap) in immutable segments
+    pub fn is_on_disk(&self) -> bool {
+        let on_disk = match self {
+            PayloadSchemaParams::Keyword(params) => params.on_disk,
+            PayloadSchemaParams::Integer(params) => params.on_disk,
+            PayloadSchemaParams::Float(params) => params.on_disk,
+            PayloadSchemaParams::Geo(params) => params.on_disk,
+            PayloadSchemaParams::Text(params) => params.on_disk,
+            PayloadSchemaParams::Datetime(params) => params.on_disk,
+            PayloadSchemaParams::Uuid(params) => params.on_disk,
+        };
+        on_disk.unwrap_or(false)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
@@ -1172,13 +1304,19 @@ impl PayloadFieldSchema {
         match self {
             PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Datetime)
-            | PayloadFieldSchema::FieldType(PayloadSchemaType::Float) => true,
+            | PayloadFieldSchema::FieldType(PayloadSchemaType::Float)
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Datetime(_))
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Float(_)) => true,
 
             PayloadFieldSchema::FieldType(PayloadSchemaType::Bool)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Text)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Geo)
-            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(_)) => false,
+            | PayloadFieldSchema::FieldType(PayloadSchemaType::Uuid)
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Keyword(_))
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Geo(_))
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(_))
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Uuid(_)) => false,
 
             PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(IntegerIndexParams {
                 range,
@@ -1186,6 +1324,22 @@ impl PayloadFieldSchema {
             })) => *range,
         }
     }
+
+    /// Payload type of the indexed field
+    pub fn kind(&self) -> PayloadSchemaType {
+        match self {
+            PayloadFieldSchema::FieldType(data_type) => *data_type,
+            PayloadFieldSchema::FieldParams(params) => params.kind(),
+        }
+    }
+
+    /// Whether the index should be stored on disk (mmap) in immutable segments
+    pub fn is_on_disk(&self) -> bool {
+        match self {
+            PayloadFieldSchema::FieldType(_) => false,
+            PayloadFieldSchema::FieldParams(params) => params.is_on_disk(),
+        }
+    }
 }
 
 impl From<PayloadSchemaType> for PayloadFieldSchema {
@@ -1202,17 +1356,14 @@ impl TryFrom<PayloadIndexInfo> for PayloadFieldSchema {
             return Ok(PayloadFieldSchema::FieldType(index_info.data_type));
         };
 
-        match (index_info.data_type, params) {
-            (PayloadSchemaType::Text, PayloadSchemaParams::Text(params)) => Ok(
-                PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(params)),
-            ),
-            (PayloadSchemaType::Integer, PayloadSchemaParams::Integer(params)) => Ok(
-                PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(params)),
-            ),
-            (data_type, PayloadSchemaParams::Integer(_) | PayloadSchemaParams::Text(_)) => Err(
-                format!("Payload field with type {data_type:?} has unexpected params"),
-            ),
+        if params.kind() != index_info.data_type {
+            return Err(format!(
+                "Payload field with type {:?} has unexpected params",
+                index_info.data_type,
+            ));
         }
+
+        Ok(PayloadFieldSchema::FieldParams(params))
     }
 }
 
@@ -1307,6 +1458,19 @@ impl From<String> for MatchText {
     }
 }
 
+/// Full-text match of the phrase: all tokens must be present, next to each other and in the same order.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchPhrase {
+    pub phrase: String,
+}
+
+impl From<String> for MatchPhrase {
+    fn from(phrase: String) -> Self {
+        MatchPhrase { phrase }
+    }
+}
+
 /// Exact match on any of the given values
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1321,14 +1485,52 @@ pub struct MatchExcept {
     pub except: AnyVariants,
 }
 
+/// Should have all of the given values, e.g. array contains all of them
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchAll {
+    pub all: AnyVariants,
+}
+
+impl MatchAll {
+    /// Check that the given values, with arrays flattened, contain every requested value.
+    /// Empty list of requested values matches nothing.
+    pub fn check_values<'a>(&self, values: impl IntoIterator<Item = &'a Value>) -> bool {
+        let values = values
+            .into_iter()
+            .flat_map(|value| match value {
+                Value::Array(array) => array.iter().collect(),
+                value => vec![value],
+            })
+            .collect_vec();
+
+        match &self.all {
+            AnyVariants::Keywords(list) => {
+                !list.is_empty()
+                    && list
+                        .iter()
+                        .all(|keyword| values.iter().any(|v| v.as_str() == Some(keyword.as_str())))
+            }
+            AnyVariants::Integers(list) => {
+                !list.is_empty()
+                    && list
+                        .iter()
+                        .all(|integer| values.iter().any(|v| v.as_i64() == Some(*integer)))
+            }
+        }
+    }
+}
+
 /// Match filter request
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(untagged, rename_all = "snake_case")]
 pub enum MatchInterface {
     Value(MatchValue),
     Text(MatchText),
+    Phrase(MatchPhrase),
     Any(MatchAny),
     Except(MatchExcept),
+    All(MatchAll),
 }
 
 /// Match filter request
@@ -1337,8 +1539,10 @@ pub enum MatchInterface {
 pub enum Match {
     Value(MatchValue),
     Text(MatchText),
+    Phrase(MatchPhrase),
     Any(MatchAny),
     Except(MatchExcept),
+    All(MatchAll),
 }
 
 impl Match {
@@ -1350,6 +1554,12 @@ impl Match {
         Self::Text(MatchText { text: text.into() })
     }
 
+    pub fn new_phrase(phrase: &str) -> Self {
+        Self::Phrase(MatchPhrase {
+            phrase: phrase.into(),
+        })
+    }
+
     pub fn new_any(any: AnyVariants) -> Self {
         Self::Any(MatchAny { any })
     }
@@ -1357,6 +1567,10 @@ impl Match {
     pub fn new_except(except: AnyVariants) -> Self {
         Self::Except(MatchExcept { except })
     }
+
+    pub fn new_all(all: AnyVariants) -> Self {
+        Self::All(MatchAll { all })
+    }
 }
 
 impl From<AnyVariants> for Match {
@@ -1370,10 +1584,14 @@ impl From<MatchInterface> for Match {
         match value {
             MatchInterface::Value(value) => Self::Value(MatchValue { value: value.value }),
             MatchInterface::Text(text) => Self::Text(MatchText { text: text.text }),
+            MatchInterface::Phrase(phrase) => Self::Phrase(MatchPhrase {
+                phrase: phrase.phrase,
+            }),
             MatchInterface::Any(any) => Self::Any(MatchAny { any: any.any }),
             MatchInterface::Except(except) => Self::Except(MatchExcept {
                 except: except.except,
             }),
+            MatchInterface::All(all) => Self::All(MatchAll { all: all.all }),
         }
     }
 }
@@ -1446,6 +1664,24 @@ impl From<Vec<IntPayloadType>> for MatchExcept {
     }
 }
 
+impl From<Vec<String>> for MatchAll {
+    fn from(keywords: Vec<String>) -> Self {
+        let keywords: IndexSet<String, FnvBuildHasher> = keywords.into_iter().collect();
+        MatchAll {
+            all: AnyVariants::Keywords(keywords),
+        }
+    }
+}
+
+impl From<Vec<IntPayloadType>> for MatchAll {
+    fn from(integers: Vec<IntPayloadType>) -> Self {
+        let integers: IndexSet<_, FnvBuildHasher> = integers.into_iter().collect();
+        MatchAll {
+            all: AnyVariants::Integers(integers),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum RangeInterface {
@@ -1887,21 +2123,44 @@ impl From<JsonPath> for IsEmptyCondition {
 /// ID-based filtering condition
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 pub struct HasIdCondition {
-    pub has_id: HashSet<PointIdType>,
+    /// Shared, so that many requests can filter by the same large set of ids without copying it
+    pub has_id: Arc<HashSet<PointIdType>>,
 }
 
 impl From<HashSet<PointIdType>> for HasIdCondition {
     fn from(set: HashSet<PointIdType>) -> Self {
+        HasIdCondition {
+            has_id: Arc::new(set),
+        }
+    }
+}
+
+impl From<Arc<HashSet<PointIdType>>> for HasIdCondition {
+    fn from(set: Arc<HashSet<PointIdType>>) -> Self {
         HasIdCondition { has_id: set }
     }
 }
 
+/// How many elements of the nested array must satisfy the filter
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ArrayQuantifier {
+    /// At least one element of the array must match the filter
+    #[default]
+    Any,
+    /// All elements of a non-empty array must match the filter
+    All,
+}
+
 /// Select points with payload for a specified nested field
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Validate)]
 pub struct Nested {
     pub key: PayloadKeyType,
     #[validate]
     pub filter: Filter,
+    /// Which elements of the array must match the filter. Default is `any`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantifier: Option<ArrayQuantifier>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Validate)]
@@ -1929,6 +2188,10 @@ impl NestedCondition {
     pub fn filter(&self) -> &Filter {
         &self.nested.filter
     }
+
+    pub fn quantifier(&self) -> ArrayQuantifier {
+        self.nested.quantifier.unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
@@ -1952,7 +2215,11 @@ pub enum Condition {
 impl Condition {
     pub fn new_nested(key: JsonPath, filter: Filter) -> Self {
         Self::Nested(NestedCondition {
-            nested: Nested { key, filter },
+            nested: Nested {
+                key,
+                filter,
+                quantifier: None,
+            },
         })
     }
 }
@@ -2681,6 +2948,36 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_match_all() {
+        let query = r#"
+        {
+            "must": [
+                {
+                    "key": "tags",
+                    "match": {
+                        "all": [1, 2, 3]
+                    }
+                }
+            ]
+        }
+        "#;
+
+        let filter: Filter = serde_json::from_str(query).unwrap();
+        let must = filter.must.unwrap();
+
+        let c = match must.first() {
+            Some(Condition::Field(c)) => c,
+            _ => panic!("Condition::Field expected"),
+        };
+
+        let expected: IndexSet<_, FnvBuildHasher> = IndexSet::from_iter([1, 2, 3]);
+        assert_eq!(
+            c.r#match,
+            Some(Match::new_all(AnyVariants::Integers(expected)))
+        );
+    }
+
     #[test]
     fn test_parse_nested_match_query() {
         let query = r#"
@@ -2839,6 +3136,36 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_parse_nested_quantifier() {
+        let query = r#"
+        {
+            "nested": {
+                "key": "country.cities",
+                "filter": { "must": [] },
+                "quantifier": "all"
+            }
+        }
+        "#;
+        let condition: Condition = serde_json::from_str(query).unwrap();
+        match condition {
+            Condition::Nested(nested_condition) => {
+                assert_eq!(nested_condition.quantifier(), ArrayQuantifier::All);
+            }
+            o => panic!("Condition::Nested expected but got {:?}", o),
+        }
+
+        let query = r#"{ "nested": { "key": "country.cities", "filter": { "must": [] } } }"#;
+        let condition: Condition = serde_json::from_str(query).unwrap();
+        match condition {
+            Condition::Nested(nested_condition) => {
+                assert_eq!(nested_condition.nested.quantifier, None);
+                assert_eq!(nested_condition.quantifier(), ArrayQuantifier::Any);
+            }
+            o => panic!("Condition::Nested expected but got {:?}", o),
+        }
+    }
+
     #[test]
     fn test_parse_single_nested_filter_query() {
         let query = r#"
@@ -15,7 +15,9 @@ use crate::vector_storage::dense::memmap_dense_vector_storage::MemmapDenseVector
 use crate::vector_storage::dense::mmap_dense_vectors::MmapDenseVectors;
 use crate::vector_storage::query_scorer::metric_query_scorer::MetricQueryScorer;
 use crate::vector_storage::query_scorer::QueryScorer;
-use crate::vector_storage::{RawScorer, VectorStorage as _, DEFAULT_STOPPED};
+use crate::vector_storage::{
+    binary_distance_error, RawScorer, VectorStorage as _, DEFAULT_STOPPED,
+};
 
 pub fn new<'a>(
     query: QueryVector,
@@ -243,6 +245,9 @@ impl<'a> AsyncRawScorerBuilder<'a> {
             Distance::Euclid => self._build_with_metric::<EuclidMetric>(),
             Distance::Dot => self._build_with_metric::<DotProductMetric>(),
             Distance::Manhattan => self._build_with_metric::<ManhattanMetric>(),
+            distance @ (Distance::Hamming | Distance::Jaccard) => {
+                Err(binary_distance_error(distance))
+            }
         }
     }
 
@@ -13,7 +13,7 @@ use crate::common::operation_error::{check_process_stopped, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
-use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::data_types::vectors::{PackedBits, VectorElementType, VectorRef};
 use crate::types::{Distance, VectorStorageDatatype};
 use crate::vector_storage::common::get_async_scorer;
 use crate::vector_storage::dense::mmap_dense_vectors::MmapDenseVectors;
@@ -69,6 +69,30 @@ pub fn open_memmap_vector_storage_half(
     Ok(VectorStorageEnum::DenseMemmapHalf(storage))
 }
 
+pub fn open_memmap_vector_storage_bf16(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<VectorStorageEnum> {
+    let storage =
+        open_memmap_vector_storage_with_async_io_impl(path, dim, distance, get_async_scorer())?;
+    Ok(VectorStorageEnum::DenseMemmapBfloat16(storage))
+}
+
+pub fn open_memmap_vector_storage_bit(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = open_memmap_vector_storage_with_async_io_impl(
+        path,
+        dim.div_ceil(PackedBits::BITS),
+        distance,
+        get_async_scorer(),
+    )?;
+    Ok(VectorStorageEnum::DenseMemmapBit(storage))
+}
+
 pub fn open_memmap_vector_storage_with_async_io(
     path: &Path,
     dim: usize,
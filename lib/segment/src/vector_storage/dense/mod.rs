@@ -3,3 +3,4 @@ pub mod dynamic_mmap_flags;
 pub mod memmap_dense_vector_storage;
 pub mod mmap_dense_vectors;
 pub mod simple_dense_vector_storage;
+pub mod volatile_dense_vector_storage;
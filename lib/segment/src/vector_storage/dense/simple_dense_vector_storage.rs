@@ -15,7 +15,7 @@ use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
-use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::data_types::vectors::{PackedBits, VectorElementType, VectorRef};
 use crate::types::{Distance, VectorStorageDatatype};
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 use crate::vector_storage::chunked_vectors::ChunkedVectors;
@@ -139,6 +139,42 @@ pub fn open_simple_dense_half_vector_storage(
     Ok(VectorStorageEnum::DenseSimpleHalf(storage))
 }
 
+pub fn open_simple_dense_bf16_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+    stopped: &AtomicBool,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = open_simple_dense_vector_storage_impl(
+        database,
+        database_column_name,
+        dim,
+        distance,
+        stopped,
+    )?;
+
+    Ok(VectorStorageEnum::DenseSimpleBfloat16(storage))
+}
+
+pub fn open_simple_dense_bit_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+    stopped: &AtomicBool,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = open_simple_dense_vector_storage_impl(
+        database,
+        database_column_name,
+        dim.div_ceil(PackedBits::BITS),
+        distance,
+        stopped,
+    )?;
+
+    Ok(VectorStorageEnum::DenseSimpleBit(storage))
+}
+
 impl<T: PrimitiveVectorElement> SimpleDenseVectorStorage<T> {
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
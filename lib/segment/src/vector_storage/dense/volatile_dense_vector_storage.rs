@@ -0,0 +1,156 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::sync::atomic::AtomicBool;
+
+use bitvec::prelude::{BitSlice, BitVec};
+use common::types::PointOffsetType;
+
+use crate::common::operation_error::{check_process_stopped, OperationResult};
+use crate::common::Flusher;
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::types::{Distance, VectorStorageDatatype};
+use crate::vector_storage::bitvec::bitvec_set_deleted;
+use crate::vector_storage::chunked_vectors::ChunkedVectors;
+use crate::vector_storage::{DenseVectorStorage, VectorStorage, VectorStorageEnum};
+
+/// In-memory vector storage without persistence
+///
+/// Used to score vectors which don't belong to any segment, e.g. a set of search results.
+pub struct VolatileDenseVectorStorage<T: PrimitiveVectorElement> {
+    dim: usize,
+    distance: Distance,
+    vectors: ChunkedVectors<T>,
+    /// BitVec for deleted flags. Grows dynamically upto last set flag.
+    deleted: BitVec,
+    /// Current number of deleted vectors.
+    deleted_count: usize,
+}
+
+pub fn new_volatile_dense_vector_storage(
+    dim: usize,
+    distance: Distance,
+) -> VolatileDenseVectorStorage<VectorElementType> {
+    VolatileDenseVectorStorage {
+        dim,
+        distance,
+        vectors: ChunkedVectors::new(dim),
+        deleted: BitVec::new(),
+        deleted_count: 0,
+    }
+}
+
+impl<T: PrimitiveVectorElement> VolatileDenseVectorStorage<T> {
+    /// Set deleted flag for given key. Returns previous deleted state.
+    #[inline]
+    fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
+        if key as usize >= self.vectors.len() {
+            return false;
+        }
+        let was_deleted = bitvec_set_deleted(&mut self.deleted, key, deleted);
+        if was_deleted != deleted {
+            if !was_deleted {
+                self.deleted_count += 1;
+            } else {
+                self.deleted_count = self.deleted_count.saturating_sub(1);
+            }
+        }
+        was_deleted
+    }
+}
+
+impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for VolatileDenseVectorStorage<T> {
+    fn vector_dim(&self) -> usize {
+        self.dim
+    }
+
+    fn get_dense(&self, key: PointOffsetType) -> &[T] {
+        self.vectors.get(key)
+    }
+}
+
+impl<T: PrimitiveVectorElement> VectorStorage for VolatileDenseVectorStorage<T> {
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn datatype(&self) -> VectorStorageDatatype {
+        T::datatype()
+    }
+
+    fn is_on_disk(&self) -> bool {
+        false
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.vectors.len()
+    }
+
+    fn available_size_in_bytes(&self) -> usize {
+        self.available_vector_count() * self.vector_dim() * std::mem::size_of::<T>()
+    }
+
+    fn get_vector(&self, key: PointOffsetType) -> CowVector {
+        self.get_vector_opt(key).expect("vector not found")
+    }
+
+    /// Get vector by key, if it exists.
+    fn get_vector_opt(&self, key: PointOffsetType) -> Option<CowVector> {
+        self.vectors
+            .get_opt(key)
+            .map(|slice| CowVector::from(T::slice_to_float_cow(slice.into())))
+    }
+
+    fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
+        let vector: &[VectorElementType] = vector.try_into()?;
+        let vector = T::slice_from_float_cow(Cow::from(vector));
+        self.vectors.insert(key, vector.as_ref())?;
+        self.set_deleted(key, false);
+        Ok(())
+    }
+
+    fn update_from(
+        &mut self,
+        other: &VectorStorageEnum,
+        other_ids: &mut impl Iterator<Item = PointOffsetType>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.vectors.len() as PointOffsetType;
+        for point_id in other_ids {
+            check_process_stopped(stopped)?;
+            // Do not perform preprocessing - vectors should be already processed
+            let other_vector = other.get_vector(point_id);
+            let other_vector = T::slice_from_float_cow(Cow::try_from(other_vector)?);
+            let other_deleted = other.is_deleted_vector(point_id);
+            let new_id = self.vectors.push(other_vector.as_ref())?;
+            self.set_deleted(new_id, other_deleted);
+        }
+        let end_index = self.vectors.len() as PointOffsetType;
+        Ok(start_index..end_index)
+    }
+
+    fn flusher(&self) -> Flusher {
+        Box::new(|| Ok(()))
+    }
+
+    fn files(&self) -> Vec<std::path::PathBuf> {
+        vec![]
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        Ok(!self.set_deleted(key, true))
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key as usize).map(|b| *b).unwrap_or(false)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.as_bitslice()
+    }
+}
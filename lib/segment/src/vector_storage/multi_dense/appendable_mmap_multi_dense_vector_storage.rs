@@ -84,6 +84,20 @@ pub fn open_appendable_memmap_multi_vector_storage_half(
     )))
 }
 
+pub fn open_appendable_memmap_multi_vector_storage_bf16(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    multi_vector_config: MultiVectorConfig,
+) -> OperationResult<VectorStorageEnum> {
+    let storage =
+        open_appendable_memmap_multi_vector_storage_impl(path, dim, distance, multi_vector_config)?;
+
+    Ok(VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(
+        Box::new(storage),
+    ))
+}
+
 pub fn open_appendable_memmap_multi_vector_storage_impl<T: PrimitiveVectorElement>(
     path: &Path,
     dim: usize,
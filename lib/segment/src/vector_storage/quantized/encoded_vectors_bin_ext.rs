@@ -0,0 +1,887 @@
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+use common::types::{PointOffsetType, ScoreType};
+use quantization::{
+    DistanceType, EncodedStorage, EncodedStorageBuilder, EncodedVectors, VectorParameters,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::{check_process_stopped, OperationResult};
+use crate::data_types::vectors::VectorElementType;
+use crate::types::{BinaryQuantizationEncoding, BinaryQuantizationQueryEncoding};
+
+const BITS_IN_WORD: usize = u64::BITS as usize;
+const BYTES_IN_WORD: usize = std::mem::size_of::<u64>();
+
+/// Distance from the center, in standard deviations, which separates the middle level of
+/// two bits encoding from the outer ones. Splits normally distributed values roughly in thirds.
+const TWO_BITS_THRESHOLD: f32 = 0.5;
+
+/// Expected distance from the center of normally distributed values, in standard deviations,
+/// for one bit encoding: `sqrt(2 / pi)`
+const ONE_BIT_RECONSTRUCTION: f32 = 0.798;
+
+/// Expected distance from the center of normally distributed values, in standard deviations,
+/// for the outer levels of two bits encoding: `pdf(0.5) / (1 - cdf(0.5))`
+const TWO_BITS_RECONSTRUCTION: f32 = 1.141;
+
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    encoding: BinaryQuantizationEncoding,
+    query_encoding: Option<BinaryQuantizationQueryEncoding>,
+    /// Values of each dimension are encoded relative to its center
+    centers: Vec<f32>,
+    /// Standard deviation of each dimension around its center
+    deviations: Vec<f32>,
+}
+
+/// Layout of bit planes of an encoded vector
+///
+/// The first plane has one bit for each dimension, the second plane covers only the first
+/// `extended_dim` dimensions. Extended dimensions use thermometer code: `00`, `10` and `11` for
+/// levels `-1`, `0` and `1`. For all other dimensions the second bit is implicitly equal to the
+/// first one, so each dimension is decoded as `x0 + x1 - 1` regardless of the encoding.
+#[derive(Clone, Copy)]
+struct Layout {
+    dim: usize,
+    extended_dim: usize,
+    words: usize,
+    extended_words: usize,
+    /// Mask of extended dimensions in the last word of the second plane
+    last_extended_mask: u64,
+}
+
+impl Layout {
+    fn new(dim: usize, encoding: BinaryQuantizationEncoding) -> Self {
+        let extended_dim = match encoding {
+            BinaryQuantizationEncoding::OneBit => 0,
+            BinaryQuantizationEncoding::TwoBits => dim,
+            BinaryQuantizationEncoding::OneAndHalfBits => dim.div_ceil(2),
+        };
+        let last_extended_mask = match extended_dim % BITS_IN_WORD {
+            0 => u64::MAX,
+            rem => (1 << rem) - 1,
+        };
+        Self {
+            dim,
+            extended_dim,
+            words: dim.div_ceil(BITS_IN_WORD),
+            extended_words: extended_dim.div_ceil(BITS_IN_WORD),
+            last_extended_mask,
+        }
+    }
+
+    fn vector_size(&self) -> usize {
+        (self.words + self.extended_words) * BYTES_IN_WORD
+    }
+
+    /// Both bit planes of word `w`, where `word` reads words of the encoded vector
+    #[inline]
+    fn planes(&self, w: usize, word: impl Fn(usize) -> u64) -> (u64, u64) {
+        let x0 = word(w);
+        let x1 = if w + 1 < self.extended_words {
+            word(self.words + w)
+        } else if w + 1 == self.extended_words {
+            let mask = self.last_extended_mask;
+            (word(self.words + w) & mask) | (x0 & !mask)
+        } else {
+            x0
+        };
+        (x0, x1)
+    }
+}
+
+#[inline]
+fn read_word(data: &[u8], w: usize) -> u64 {
+    let start = w * BYTES_IN_WORD;
+    u64::from_le_bytes(data[start..start + BYTES_IN_WORD].try_into().unwrap())
+}
+
+#[inline]
+fn popcount(word: u64) -> i64 {
+    i64::from(word.count_ones())
+}
+
+pub enum EncodedBinExtQuery {
+    /// Query encoded in the same way as stored vectors, with both planes expanded to all dimensions
+    Binary { first: Vec<u64>, second: Vec<u64> },
+    /// Query quantized into `bits` planes of unsigned integers: `value = min + step * integer`
+    Scalar {
+        planes: Vec<u64>,
+        bits: usize,
+        plane_counts: Vec<i64>,
+        min: f32,
+        step: f32,
+        /// Part of the score, which does not depend on the stored vector
+        offset: f32,
+    },
+}
+
+/// Parameters of encoding, shared by stored vectors and queries
+struct Codec {
+    metadata: Metadata,
+    vector_parameters: VectorParameters,
+    layout: Layout,
+    /// Distance from the center of values, decoded from outer levels of each dimension
+    reconstructions: Vec<f32>,
+    mean_square_reconstruction: f32,
+}
+
+/// Binary quantization with one, one and half or two bits per dimension, which can be scored
+/// against binary or scalar quantized queries.
+pub struct EncodedVectorsBinExt<TStorage: EncodedStorage> {
+    encoded_vectors: TStorage,
+    codec: Codec,
+}
+
+impl<TStorage: EncodedStorage> EncodedVectorsBinExt<TStorage> {
+    pub fn encode<'a>(
+        data: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
+        mut storage_builder: impl EncodedStorageBuilder<TStorage>,
+        vector_parameters: &VectorParameters,
+        encoding: BinaryQuantizationEncoding,
+        query_encoding: Option<BinaryQuantizationQueryEncoding>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self> {
+        let dim = vector_parameters.dim;
+        let mut sums = vec![0.0f64; dim];
+        let mut square_sums = vec![0.0f64; dim];
+        let mut count = 0usize;
+        for vector in data.clone() {
+            check_process_stopped(stopped)?;
+            for (i, &value) in vector.as_ref().iter().enumerate() {
+                sums[i] += f64::from(value);
+                square_sums[i] += f64::from(value) * f64::from(value);
+            }
+            count += 1;
+        }
+        let count = count.max(1) as f64;
+
+        // Dot product is not shift invariant, so values are encoded relative to zero
+        let centers: Vec<f64> = match vector_parameters.distance_type {
+            DistanceType::Dot => vec![0.0; dim],
+            DistanceType::L1 | DistanceType::L2 => sums.iter().map(|sum| sum / count).collect(),
+        };
+        let deviations = centers
+            .iter()
+            .zip(sums.iter().zip(&square_sums))
+            .map(|(center, (sum, square_sum))| {
+                let variance = square_sum / count - 2.0 * center * sum / count + center * center;
+                variance.max(0.0).sqrt() as f32
+            })
+            .collect();
+
+        let metadata = Metadata {
+            encoding,
+            query_encoding,
+            centers: centers.into_iter().map(|center| center as f32).collect(),
+            deviations,
+        };
+        let codec = Codec::new(metadata, vector_parameters.clone());
+
+        for vector in data {
+            check_process_stopped(stopped)?;
+            let words = codec.encode_vector(vector.as_ref());
+            let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+            storage_builder.push_vector_data(&bytes);
+        }
+
+        Ok(Self {
+            encoded_vectors: storage_builder.build(),
+            codec,
+        })
+    }
+
+    pub fn get_quantized_vector_size(
+        vector_parameters: &VectorParameters,
+        encoding: BinaryQuantizationEncoding,
+    ) -> usize {
+        Layout::new(vector_parameters.dim, encoding).vector_size()
+    }
+
+    fn vector_data(&self, i: PointOffsetType) -> &[u8] {
+        self.encoded_vectors
+            .get_vector_data(i as usize, self.codec.layout.vector_size())
+    }
+}
+
+impl Codec {
+    fn new(metadata: Metadata, vector_parameters: VectorParameters) -> Self {
+        let layout = Layout::new(vector_parameters.dim, metadata.encoding);
+        let reconstructions: Vec<f32> = metadata
+            .deviations
+            .iter()
+            .enumerate()
+            .map(|(i, deviation)| {
+                if i < layout.extended_dim {
+                    deviation * TWO_BITS_RECONSTRUCTION
+                } else {
+                    deviation * ONE_BIT_RECONSTRUCTION
+                }
+            })
+            .collect();
+        let mean_square_reconstruction = reconstructions.iter().map(|r| r * r).sum::<f32>()
+            / reconstructions.len().max(1) as f32;
+        Self {
+            metadata,
+            vector_parameters,
+            layout,
+            reconstructions,
+            mean_square_reconstruction,
+        }
+    }
+
+    fn encode_vector(&self, vector: &[VectorElementType]) -> Vec<u64> {
+        let layout = &self.layout;
+        let mut words = vec![0u64; layout.words + layout.extended_words];
+        for (i, &value) in vector.iter().enumerate() {
+            let (w, bit) = (i / BITS_IN_WORD, 1u64 << (i % BITS_IN_WORD));
+            let delta = value - self.metadata.centers[i];
+            if i < layout.extended_dim {
+                let threshold = TWO_BITS_THRESHOLD * self.metadata.deviations[i];
+                if delta > -threshold {
+                    words[w] |= bit;
+                }
+                if delta > threshold {
+                    words[layout.words + w] |= bit;
+                }
+            } else if delta > 0.0 {
+                words[w] |= bit;
+            }
+        }
+        words
+    }
+
+    fn encode_binary_query(&self, query: &[VectorElementType]) -> EncodedBinExtQuery {
+        let words = self.encode_vector(query);
+        let (first, second) = (0..self.layout.words)
+            .map(|w| self.layout.planes(w, |i| words[i]))
+            .unzip();
+        EncodedBinExtQuery::Binary { first, second }
+    }
+
+    fn encode_scalar_query(&self, query: &[VectorElementType], bits: usize) -> EncodedBinExtQuery {
+        let centers = &self.metadata.centers;
+        // Query is scored against reconstructed vectors `center + reconstruction * level`,
+        // so the reconstruction of each dimension is moved into the quantized query
+        let (weighted, offset): (Vec<f32>, f32) = match self.vector_parameters.distance_type {
+            DistanceType::Dot => (
+                query
+                    .iter()
+                    .zip(&self.reconstructions)
+                    .map(|(q, r)| q * r)
+                    .collect(),
+                query.iter().zip(centers).map(|(q, c)| q * c).sum(),
+            ),
+            // Manhattan distance is approximated with euclidean one, final scores are rescored
+            DistanceType::L1 | DistanceType::L2 => (
+                query
+                    .iter()
+                    .zip(centers)
+                    .zip(&self.reconstructions)
+                    .map(|((q, c), r)| 2.0 * (q - c) * r)
+                    .collect(),
+                -query
+                    .iter()
+                    .zip(centers)
+                    .map(|(q, c)| (q - c) * (q - c))
+                    .sum::<f32>(),
+            ),
+        };
+
+        let min = weighted.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = weighted.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let max_integer = ((1u32 << bits) - 1) as f32;
+        let step = if max > min {
+            (max - min) / max_integer
+        } else {
+            0.0
+        };
+
+        let words = self.layout.words;
+        let mut planes = vec![0u64; bits * words];
+        for (i, value) in weighted.iter().enumerate() {
+            let integer = if step > 0.0 {
+                ((value - min) / step).round().clamp(0.0, max_integer) as u32
+            } else {
+                0
+            };
+            let (w, bit) = (i / BITS_IN_WORD, 1u64 << (i % BITS_IN_WORD));
+            for plane in 0..bits {
+                if integer & (1 << plane) != 0 {
+                    planes[plane * words + w] |= bit;
+                }
+            }
+        }
+        let plane_counts = planes
+            .chunks_exact(words.max(1))
+            .map(|plane| plane.iter().copied().map(popcount).sum())
+            .collect();
+
+        EncodedBinExtQuery::Scalar {
+            planes,
+            bits,
+            plane_counts,
+            min: if min.is_finite() { min } else { 0.0 },
+            step,
+            offset,
+        }
+    }
+
+    /// Score binary encoded vectors, given as functions of expanded planes of each word
+    fn score_binary(
+        &self,
+        a: impl Fn(usize) -> (u64, u64),
+        b: impl Fn(usize) -> (u64, u64),
+    ) -> ScoreType {
+        let (mut product, mut sum_a, mut sum_b, mut square_a, mut square_b, mut manhattan) =
+            (0i64, 0i64, 0i64, 0i64, 0i64, 0i64);
+        for w in 0..self.layout.words {
+            let (a0, a1) = a(w);
+            let (b0, b1) = b(w);
+            // Each dimension is `x0 + x1` in [0, 2], thermometer code guarantees `x1 <= x0`
+            product +=
+                popcount(a0 & b0) + popcount(a0 & b1) + popcount(a1 & b0) + popcount(a1 & b1);
+            sum_a += popcount(a0) + popcount(a1);
+            sum_b += popcount(b0) + popcount(b1);
+            square_a += popcount(a0) + 3 * popcount(a1);
+            square_b += popcount(b0) + 3 * popcount(b1);
+            manhattan += popcount(a0 ^ b0) + popcount(a1 ^ b1);
+        }
+        let dim = self.layout.dim as i64;
+        let score = match self.vector_parameters.distance_type {
+            // Product of levels `(x - 1) * (y - 1)`
+            DistanceType::Dot => product - sum_a - sum_b + dim,
+            DistanceType::L2 => -(square_a + square_b - 2 * product),
+            DistanceType::L1 => -manhattan,
+        };
+        score as ScoreType
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn score_scalar(
+        &self,
+        planes: &[u64],
+        bits: usize,
+        plane_counts: &[i64],
+        min: f32,
+        step: f32,
+        offset: f32,
+        data: &[u8],
+    ) -> ScoreType {
+        let words = self.layout.words;
+        let mut plane_sums = [0i64; u8::BITS as usize];
+        let (mut sum, mut zero_levels) = (0i64, 0i64);
+        for w in 0..words {
+            let (x0, x1) = self.layout.planes(w, |i| read_word(data, i));
+            sum += popcount(x0) + popcount(x1);
+            zero_levels += popcount(x0 ^ x1);
+            for (plane, plane_sum) in plane_sums.iter_mut().enumerate().take(bits) {
+                let q = planes[plane * words + w];
+                *plane_sum += popcount(q & x0) + popcount(q & x1);
+            }
+        }
+
+        let dim = self.layout.dim as i64;
+        // Sum of `integer * level` over all dimensions, where `level = x0 + x1 - 1`
+        let integer_product: f32 = plane_sums
+            .iter()
+            .zip(plane_counts)
+            .enumerate()
+            .map(|(plane, (plane_sum, plane_count))| {
+                (1i64 << plane) as f32 * (plane_sum - plane_count) as f32
+            })
+            .sum();
+        let product = min * (sum - dim) as f32 + step * integer_product;
+
+        match self.vector_parameters.distance_type {
+            DistanceType::Dot => offset + product,
+            DistanceType::L1 | DistanceType::L2 => {
+                let non_zero_levels = (dim - zero_levels) as f32;
+                offset + product - self.mean_square_reconstruction * non_zero_levels
+            }
+        }
+    }
+}
+
+impl<TStorage: EncodedStorage> EncodedVectors<EncodedBinExtQuery>
+    for EncodedVectorsBinExt<TStorage>
+{
+    fn save(&self, data_path: &Path, meta_path: &Path) -> std::io::Result<()> {
+        self.encoded_vectors.save_to_file(data_path)?;
+        let metadata_bytes = serde_json::to_vec(&self.codec.metadata)?;
+        std::fs::write(meta_path, metadata_bytes)?;
+        Ok(())
+    }
+
+    fn load(
+        data_path: &Path,
+        meta_path: &Path,
+        vector_parameters: &VectorParameters,
+    ) -> std::io::Result<Self> {
+        let metadata: Metadata = serde_json::from_slice(&std::fs::read(meta_path)?)?;
+        let codec = Codec::new(metadata, vector_parameters.clone());
+        let encoded_vectors = TStorage::from_file(
+            data_path,
+            codec.layout.vector_size(),
+            vector_parameters.count,
+        )?;
+        Ok(Self {
+            encoded_vectors,
+            codec,
+        })
+    }
+
+    fn encode_query(&self, query: &[VectorElementType]) -> EncodedBinExtQuery {
+        match self.codec.metadata.query_encoding {
+            None | Some(BinaryQuantizationQueryEncoding::Binary) => {
+                self.codec.encode_binary_query(query)
+            }
+            Some(BinaryQuantizationQueryEncoding::Scalar4Bits) => {
+                self.codec.encode_scalar_query(query, 4)
+            }
+            Some(BinaryQuantizationQueryEncoding::Scalar8Bits) => {
+                self.codec.encode_scalar_query(query, 8)
+            }
+        }
+    }
+
+    fn score_point(&self, query: &EncodedBinExtQuery, i: PointOffsetType) -> ScoreType {
+        let data = self.vector_data(i);
+        let layout = &self.codec.layout;
+        match query {
+            EncodedBinExtQuery::Binary { first, second } => self.codec.score_binary(
+                |w| (first[w], second[w]),
+                |w| layout.planes(w, |j| read_word(data, j)),
+            ),
+            EncodedBinExtQuery::Scalar {
+                planes,
+                bits,
+                plane_counts,
+                min,
+                step,
+                offset,
+            } => self
+                .codec
+                .score_scalar(planes, *bits, plane_counts, *min, *step, *offset, data),
+        }
+    }
+
+    fn score_internal(&self, i: PointOffsetType, j: PointOffsetType) -> ScoreType {
+        let data_a = self.vector_data(i);
+        let data_b = self.vector_data(j);
+        let layout = &self.codec.layout;
+        self.codec.score_binary(
+            |w| layout.planes(w, |k| read_word(data_a, k)),
+            |w| layout.planes(w, |k| read_word(data_b, k)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rstest::rstest;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::types::MultiVectorConfig;
+    use crate::vector_storage::chunked_vectors::ChunkedVectors;
+    use crate::vector_storage::quantized::quantized_mmap_storage::{
+        QuantizedMmapStorage, QuantizedMmapStorageBuilder,
+    };
+    use crate::vector_storage::quantized::quantized_multivector_storage::{
+        create_offsets_file_from_iter, MultivectorOffset, MultivectorOffsetsStorage,
+        MultivectorOffsetsStorageMmap, QuantizedMultivectorStorage,
+    };
+
+    const VECTOR_COUNT: usize = 100;
+    const QUERY_COUNT: usize = 10;
+    const MULTIVECTOR_COUNT: usize = 30;
+    const MULTIVECTOR_DIM: usize = 65;
+    const SEED: u64 = 42;
+
+    /// Random vectors, where each dimension has its own center and spread
+    fn random_vectors(rng: &mut StdRng, count: usize, dim: usize) -> Vec<Vec<f32>> {
+        let centers: Vec<f32> = (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        let spreads: Vec<f32> = (0..dim).map(|_| rng.gen_range(0.1..2.0)).collect();
+        (0..count)
+            .map(|_| {
+                centers
+                    .iter()
+                    .zip(&spreads)
+                    .map(|(center, spread)| center + spread * rng.gen_range(-1.0f32..1.0))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn vector_parameters(
+        dim: usize,
+        count: usize,
+        distance_type: DistanceType,
+    ) -> VectorParameters {
+        let invert = matches!(distance_type, DistanceType::L1 | DistanceType::L2);
+        VectorParameters {
+            dim,
+            count,
+            distance_type,
+            invert,
+        }
+    }
+
+    fn encode<TStorage: EncodedStorage>(
+        vectors: &[Vec<f32>],
+        storage_builder: impl EncodedStorageBuilder<TStorage>,
+        vector_parameters: &VectorParameters,
+        encoding: BinaryQuantizationEncoding,
+        query_encoding: BinaryQuantizationQueryEncoding,
+    ) -> EncodedVectorsBinExt<TStorage> {
+        EncodedVectorsBinExt::encode(
+            vectors.iter(),
+            storage_builder,
+            vector_parameters,
+            encoding,
+            Some(query_encoding),
+            &AtomicBool::new(false),
+        )
+        .unwrap()
+    }
+
+    fn bit(words: &[u64], i: usize) -> i64 {
+        ((words[i / BITS_IN_WORD] >> (i % BITS_IN_WORD)) & 1) as i64
+    }
+
+    /// Levels `-1`, `0` and `1` of each dimension of an encoded vector
+    fn decode_levels(layout: &Layout, data: &[u8]) -> Vec<i64> {
+        let words: Vec<u64> = (0..layout.words + layout.extended_words)
+            .map(|w| read_word(data, w))
+            .collect();
+        (0..layout.dim)
+            .map(|i| {
+                let x0 = bit(&words, i);
+                let x1 = if i < layout.extended_dim {
+                    bit(&words[layout.words..], i)
+                } else {
+                    x0
+                };
+                assert!(
+                    x1 <= x0,
+                    "second bit of dimension {i} is set without the first one"
+                );
+                x0 + x1 - 1
+            })
+            .collect()
+    }
+
+    fn decode_all_levels<TStorage: EncodedStorage>(
+        encoded: &EncodedVectorsBinExt<TStorage>,
+    ) -> Vec<Vec<i64>> {
+        (0..encoded.codec.vector_parameters.count)
+            .map(|i| {
+                decode_levels(
+                    &encoded.codec.layout,
+                    encoded.vector_data(i as PointOffsetType),
+                )
+            })
+            .collect()
+    }
+
+    fn binary_score(distance_type: &DistanceType, a: &[i64], b: &[i64]) -> ScoreType {
+        let pairs = a.iter().zip(b);
+        let score: i64 = match distance_type {
+            DistanceType::Dot => pairs.map(|(a, b)| a * b).sum(),
+            DistanceType::L2 => -pairs.map(|(a, b)| (a - b) * (a - b)).sum::<i64>(),
+            DistanceType::L1 => -pairs.map(|(a, b)| (a - b).abs()).sum::<i64>(),
+        };
+        score as ScoreType
+    }
+
+    /// Score of the encoded query against a vector, computed dimension by dimension
+    fn expected_score(codec: &Codec, query: &EncodedBinExtQuery, levels: &[i64]) -> ScoreType {
+        let distance_type = &codec.vector_parameters.distance_type;
+        match query {
+            EncodedBinExtQuery::Binary { first, second } => {
+                let query_levels: Vec<i64> = (0..codec.layout.dim)
+                    .map(|i| bit(first, i) + bit(second, i) - 1)
+                    .collect();
+                binary_score(distance_type, &query_levels, levels)
+            }
+            EncodedBinExtQuery::Scalar {
+                planes,
+                bits,
+                min,
+                step,
+                offset,
+                ..
+            } => {
+                // Query value of each dimension is `min + step * integer`. Accumulated in double
+                // precision, so that the tolerance covers rounding errors of the tested score only.
+                let words = codec.layout.words;
+                let mut product = 0.0f64;
+                let mut non_zero_levels = 0;
+                for (i, &level) in levels.iter().enumerate() {
+                    let integer: i64 = (0..*bits)
+                        .map(|plane| bit(&planes[plane * words..], i) << plane)
+                        .sum();
+                    product += (f64::from(*min) + f64::from(*step) * integer as f64) * level as f64;
+                    non_zero_levels += level.abs();
+                }
+                let score = match distance_type {
+                    DistanceType::Dot => f64::from(*offset) + product,
+                    DistanceType::L1 | DistanceType::L2 => {
+                        let penalty = f64::from(codec.mean_square_reconstruction);
+                        f64::from(*offset) + product - penalty * non_zero_levels as f64
+                    }
+                };
+                score as ScoreType
+            }
+        }
+    }
+
+    fn assert_score(actual: ScoreType, expected: ScoreType) {
+        assert!(
+            (actual - expected).abs() <= 1e-3 * expected.abs().max(1.0),
+            "score {actual} differs from expected {expected}",
+        );
+    }
+
+    fn check_scores<TStorage: EncodedStorage>(
+        encoded: &EncodedVectorsBinExt<TStorage>,
+        queries: &[Vec<f32>],
+    ) {
+        let levels = decode_all_levels(encoded);
+        let distance_type = &encoded.codec.vector_parameters.distance_type;
+
+        for query in queries {
+            let encoded_query = encoded.encode_query(query);
+            for (i, vector_levels) in levels.iter().enumerate() {
+                assert_score(
+                    encoded.score_point(&encoded_query, i as PointOffsetType),
+                    expected_score(&encoded.codec, &encoded_query, vector_levels),
+                );
+            }
+        }
+
+        for (i, levels_a) in levels.iter().enumerate() {
+            for (j, levels_b) in levels.iter().enumerate() {
+                assert_eq!(
+                    encoded.score_internal(i as PointOffsetType, j as PointOffsetType),
+                    binary_score(distance_type, levels_a, levels_b),
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_score_against_levels(
+        #[values(DistanceType::Dot, DistanceType::L2, DistanceType::L1)]
+        distance_type: DistanceType,
+        #[values(
+            BinaryQuantizationEncoding::OneBit,
+            BinaryQuantizationEncoding::OneAndHalfBits,
+            BinaryQuantizationEncoding::TwoBits
+        )]
+        encoding: BinaryQuantizationEncoding,
+        #[values(
+            BinaryQuantizationQueryEncoding::Binary,
+            BinaryQuantizationQueryEncoding::Scalar4Bits,
+            BinaryQuantizationQueryEncoding::Scalar8Bits
+        )]
+        query_encoding: BinaryQuantizationQueryEncoding,
+        #[values(1, 63, 64, 130)] dim: usize,
+        #[values(false, true)] mmap: bool,
+    ) {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let vectors = random_vectors(&mut rng, VECTOR_COUNT, dim);
+        let queries = random_vectors(&mut rng, QUERY_COUNT, dim);
+        let vector_parameters = vector_parameters(dim, VECTOR_COUNT, distance_type);
+        let vector_size = EncodedVectorsBinExt::<QuantizedMmapStorage>::get_quantized_vector_size(
+            &vector_parameters,
+            encoding,
+        );
+
+        if mmap {
+            let dir = Builder::new().prefix("bin_ext_storage").tempdir().unwrap();
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                &dir.path().join("quantized.data"),
+                VECTOR_COUNT,
+                vector_size,
+            )
+            .unwrap();
+            let encoded: EncodedVectorsBinExt<QuantizedMmapStorage> = encode(
+                &vectors,
+                storage_builder,
+                &vector_parameters,
+                encoding,
+                query_encoding,
+            );
+            check_scores(&encoded, &queries);
+        } else {
+            let storage_builder = ChunkedVectors::<u8>::new(vector_size);
+            let encoded: EncodedVectorsBinExt<ChunkedVectors<u8>> = encode(
+                &vectors,
+                storage_builder,
+                &vector_parameters,
+                encoding,
+                query_encoding,
+            );
+            check_scores(&encoded, &queries);
+        }
+    }
+
+    fn check_multivector_scores<TStorage, TOffsetsStorage>(
+        encoded: EncodedVectorsBinExt<TStorage>,
+        offsets_storage: TOffsetsStorage,
+        offsets: &[MultivectorOffset],
+        queries: &[Vec<Vec<f32>>],
+    ) where
+        TStorage: EncodedStorage,
+        TOffsetsStorage: MultivectorOffsetsStorage,
+    {
+        let levels = decode_all_levels(&encoded);
+        let distance_type = &encoded.codec.vector_parameters.distance_type;
+
+        // Scores of each inner query and each inner vector of each query
+        let query_scores: Vec<Vec<Vec<ScoreType>>> = queries
+            .iter()
+            .map(|query| {
+                query
+                    .iter()
+                    .map(|inner_query| {
+                        let encoded_query = encoded.encode_query(inner_query);
+                        levels
+                            .iter()
+                            .map(|vector_levels| {
+                                expected_score(&encoded.codec, &encoded_query, vector_levels)
+                            })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+        let internal_scores: Vec<Vec<ScoreType>> = levels
+            .iter()
+            .map(|levels_a| {
+                levels
+                    .iter()
+                    .map(|levels_b| binary_score(distance_type, levels_a, levels_b))
+                    .collect()
+            })
+            .collect();
+
+        let multivector_storage = QuantizedMultivectorStorage::<EncodedBinExtQuery, _, _>::new(
+            MULTIVECTOR_DIM,
+            encoded,
+            offsets_storage,
+            MultiVectorConfig::default(),
+        );
+
+        for (query, inner_scores) in queries.iter().zip(&query_scores) {
+            let encoded_query = multivector_storage.encode_query(&query.concat());
+            for (i, offset) in offsets.iter().enumerate() {
+                let expected = inner_scores
+                    .iter()
+                    .map(|scores| max_similarity(scores, offset))
+                    .sum();
+                assert_score(
+                    multivector_storage.score_point(&encoded_query, i as PointOffsetType),
+                    expected,
+                );
+            }
+        }
+
+        for (i, offset_a) in offsets.iter().enumerate() {
+            for (j, offset_b) in offsets.iter().enumerate() {
+                let expected: ScoreType = (offset_a.start..offset_a.start + offset_a.count)
+                    .map(|a| max_similarity(&internal_scores[a as usize], offset_b))
+                    .sum();
+                assert_eq!(
+                    multivector_storage.score_internal(i as PointOffsetType, j as PointOffsetType),
+                    expected,
+                );
+            }
+        }
+    }
+
+    /// Maximal score among inner vectors of a multivector
+    fn max_similarity(scores: &[ScoreType], offset: &MultivectorOffset) -> ScoreType {
+        let range = offset.start as usize..(offset.start + offset.count) as usize;
+        scores[range]
+            .iter()
+            .copied()
+            .fold(ScoreType::NEG_INFINITY, ScoreType::max)
+    }
+
+    #[rstest]
+    fn test_multivector_score_against_levels(
+        #[values(DistanceType::Dot, DistanceType::L2, DistanceType::L1)]
+        distance_type: DistanceType,
+        #[values(
+            BinaryQuantizationEncoding::OneBit,
+            BinaryQuantizationEncoding::OneAndHalfBits,
+            BinaryQuantizationEncoding::TwoBits
+        )]
+        encoding: BinaryQuantizationEncoding,
+        #[values(
+            BinaryQuantizationQueryEncoding::Binary,
+            BinaryQuantizationQueryEncoding::Scalar4Bits,
+            BinaryQuantizationQueryEncoding::Scalar8Bits
+        )]
+        query_encoding: BinaryQuantizationQueryEncoding,
+        #[values(false, true)] mmap: bool,
+    ) {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let mut offsets = Vec::with_capacity(MULTIVECTOR_COUNT);
+        let mut start = 0;
+        for _ in 0..MULTIVECTOR_COUNT {
+            let count = rng.gen_range(1..=4);
+            offsets.push(MultivectorOffset { start, count });
+            start += count;
+        }
+        let vector_count = start as usize;
+        let vectors = random_vectors(&mut rng, vector_count, MULTIVECTOR_DIM);
+        let queries: Vec<_> = (0..QUERY_COUNT)
+            .map(|_| random_vectors(&mut rng, 3, MULTIVECTOR_DIM))
+            .collect();
+        let vector_parameters = vector_parameters(MULTIVECTOR_DIM, vector_count, distance_type);
+        let vector_size = EncodedVectorsBinExt::<QuantizedMmapStorage>::get_quantized_vector_size(
+            &vector_parameters,
+            encoding,
+        );
+
+        if mmap {
+            let dir = Builder::new().prefix("bin_ext_storage").tempdir().unwrap();
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                &dir.path().join("quantized.data"),
+                vector_count,
+                vector_size,
+            )
+            .unwrap();
+            let encoded: EncodedVectorsBinExt<QuantizedMmapStorage> = encode(
+                &vectors,
+                storage_builder,
+                &vector_parameters,
+                encoding,
+                query_encoding,
+            );
+            let offsets_path = dir.path().join("quantized.offsets");
+            create_offsets_file_from_iter(&offsets_path, offsets.len(), offsets.iter().copied())
+                .unwrap();
+            let offsets_storage = MultivectorOffsetsStorageMmap::load(&offsets_path).unwrap();
+            check_multivector_scores(encoded, offsets_storage, &offsets, &queries);
+        } else {
+            let storage_builder = ChunkedVectors::<u8>::new(vector_size);
+            let encoded: EncodedVectorsBinExt<ChunkedVectors<u8>> = encode(
+                &vectors,
+                storage_builder,
+                &vector_parameters,
+                encoding,
+                query_encoding,
+            );
+            check_multivector_scores(encoded, offsets.clone(), &offsets, &queries);
+        }
+    }
+}
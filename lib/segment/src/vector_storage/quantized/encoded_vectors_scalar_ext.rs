@@ -0,0 +1,418 @@
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+use common::types::{PointOffsetType, ScoreType};
+use quantization::{
+    DistanceType, EncodedStorage, EncodedStorageBuilder, EncodedVectors, VectorParameters,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::data_types::vectors::VectorElementType;
+use crate::types::ScalarType;
+
+/// Largest level of 4 bit quantization
+const INT4_MAX_LEVEL: u8 = 15;
+
+/// Largest finite value of float8 `e4m3` format
+const FLOAT8_MAX: f32 = 448.0;
+
+/// Code of the largest finite value of float8 `e4m3` format, all codes above are negative or NaN
+const FLOAT8_MAX_CODE: u8 = 0x7E;
+
+/// Maximal number of values used to estimate quantiles of the value range
+const QUANTILE_SAMPLE_SIZE: usize = 100_000;
+
+const CORRECTION_SIZE: usize = std::mem::size_of::<f32>();
+
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    r#type: ScalarType,
+    /// `int4`: value of the lowest level. Unused for `float8`
+    min: f32,
+    /// `int4`: distance between two adjacent levels.
+    /// `float8`: multiplier, which maps the largest absolute value to the largest float8 value
+    scale: f32,
+}
+
+/// Encoded vector layout:
+///
+/// - `int4`: `f32` correction of the score, followed by levels packed two per byte,
+///   low nibble first
+/// - `float8`: one `e4m3` value per dimension
+fn vector_size(dim: usize, r#type: &ScalarType) -> usize {
+    match r#type {
+        ScalarType::Int4 => CORRECTION_SIZE + dim.div_ceil(2),
+        ScalarType::Float8 => dim,
+        ScalarType::Int8 => unreachable!("int8 quantization is handled by `EncodedVectorsU8`"),
+    }
+}
+
+/// Decode float8 `e4m3` value: 1 sign bit, 4 exponent bits with bias 7 and 3 mantissa bits
+fn decode_float8(code: u8) -> f32 {
+    let exponent = i32::from((code >> 3) & 0x0F);
+    let mantissa = f32::from(code & 0x07);
+    let magnitude = if exponent == 0 {
+        mantissa * 2f32.powi(-9)
+    } else {
+        (1.0 + mantissa / 8.0) * 2f32.powi(exponent - 7)
+    };
+    if code & 0x80 != 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+pub enum EncodedScalarExtQuery {
+    /// Query quantized in the same way as stored vectors, with unpacked levels
+    Int4 { levels: Vec<u8>, correction: f32 },
+    /// Query multiplied by the scale of stored vectors
+    Float8 { values: Vec<f32> },
+}
+
+/// Parameters of encoding, shared by stored vectors and queries
+struct Codec {
+    metadata: Metadata,
+    vector_parameters: VectorParameters,
+    vector_size: usize,
+    /// Decoded values of all float8 codes
+    float8_values: Vec<f32>,
+}
+
+/// Scalar quantization with 4 bit integers or 8 bit floats per dimension
+pub struct EncodedVectorsScalarExt<TStorage: EncodedStorage> {
+    encoded_vectors: TStorage,
+    codec: Codec,
+}
+
+impl<TStorage: EncodedStorage> EncodedVectorsScalarExt<TStorage> {
+    pub fn encode<'a>(
+        data: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
+        mut storage_builder: impl EncodedStorageBuilder<TStorage>,
+        vector_parameters: &VectorParameters,
+        r#type: ScalarType,
+        quantile: Option<f32>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self> {
+        let metadata = match r#type {
+            ScalarType::Int4 => {
+                let (min, max) = Self::value_range(data.clone(), vector_parameters, quantile);
+                let step = (max - min) / f32::from(INT4_MAX_LEVEL);
+                Metadata {
+                    r#type,
+                    min,
+                    scale: if step > 0.0 { step } else { 1.0 },
+                }
+            }
+            ScalarType::Float8 => {
+                // Float8 keeps the dynamic range, so the largest value is never clipped
+                let mut max_abs = 0.0f32;
+                for vector in data.clone() {
+                    check_process_stopped(stopped)?;
+                    max_abs = vector
+                        .as_ref()
+                        .iter()
+                        .fold(max_abs, |max_abs, value| max_abs.max(value.abs()));
+                }
+                Metadata {
+                    r#type,
+                    min: 0.0,
+                    scale: if max_abs > 0.0 {
+                        FLOAT8_MAX / max_abs
+                    } else {
+                        1.0
+                    },
+                }
+            }
+            ScalarType::Int8 => {
+                return Err(OperationError::service_error(
+                    "int8 quantization is handled by `EncodedVectorsU8`",
+                ));
+            }
+        };
+        let codec = Codec::new(metadata, vector_parameters.clone());
+
+        for vector in data {
+            check_process_stopped(stopped)?;
+            storage_builder.push_vector_data(&codec.encode_vector(vector.as_ref()));
+        }
+
+        Ok(Self {
+            encoded_vectors: storage_builder.build(),
+            codec,
+        })
+    }
+
+    pub fn get_quantized_vector_size(
+        vector_parameters: &VectorParameters,
+        r#type: &ScalarType,
+    ) -> usize {
+        vector_size(vector_parameters.dim, r#type)
+    }
+
+    /// Range of values, clipped to the given quantile of all values
+    fn value_range<'a>(
+        data: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a>,
+        vector_parameters: &VectorParameters,
+        quantile: Option<f32>,
+    ) -> (f32, f32) {
+        let Some(quantile) = quantile else {
+            let (min, max) = data.fold((f32::MAX, f32::MIN), |range, vector| {
+                vector
+                    .as_ref()
+                    .iter()
+                    .fold(range, |(min, max), &value| (min.min(value), max.max(value)))
+            });
+            return if min <= max { (min, max) } else { (0.0, 0.0) };
+        };
+
+        // Take every n-th vector, so the sample covers the whole collection
+        let vectors_step = (vector_parameters.count * vector_parameters.dim)
+            .div_ceil(QUANTILE_SAMPLE_SIZE)
+            .max(1);
+        let mut sample: Vec<f32> = data
+            .step_by(vectors_step)
+            .flat_map(|vector| vector.as_ref().to_vec())
+            .collect();
+        if sample.is_empty() {
+            return (0.0, 0.0);
+        }
+        sample.sort_unstable_by(f32::total_cmp);
+
+        let cut = ((1.0 - quantile) / 2.0 * sample.len() as f32) as usize;
+        let cut = cut.min((sample.len() - 1) / 2);
+        (sample[cut], sample[sample.len() - 1 - cut])
+    }
+
+    fn vector_data(&self, i: PointOffsetType) -> &[u8] {
+        self.encoded_vectors
+            .get_vector_data(i as usize, self.codec.vector_size)
+    }
+
+    pub fn scalar_type(&self) -> &ScalarType {
+        &self.codec.metadata.r#type
+    }
+}
+
+impl Codec {
+    fn new(metadata: Metadata, vector_parameters: VectorParameters) -> Self {
+        let vector_size = vector_size(vector_parameters.dim, &metadata.r#type);
+        Self {
+            metadata,
+            vector_parameters,
+            vector_size,
+            float8_values: (0..=u8::MAX).map(decode_float8).collect(),
+        }
+    }
+
+    fn int4_levels(&self, vector: &[VectorElementType]) -> Vec<u8> {
+        vector
+            .iter()
+            .map(|value| {
+                ((value - self.metadata.min) / self.metadata.scale)
+                    .round()
+                    .clamp(0.0, f32::from(INT4_MAX_LEVEL)) as u8
+            })
+            .collect()
+    }
+
+    /// Part of the score, which depends on one vector only.
+    ///
+    /// With `value = min + scale * level` scores are expressed through the sum of level products:
+    /// - dot: `scale^2 * sum(a * b) + (correction(a) + correction(b))`
+    /// - l2: `2 * scale^2 * sum(a * b) + (correction(a) + correction(b))`
+    fn int4_correction(&self, levels: &[u8]) -> f32 {
+        let (min, scale) = (self.metadata.min, self.metadata.scale);
+        match self.vector_parameters.distance_type {
+            DistanceType::Dot => {
+                let sum: u32 = levels.iter().map(|&level| u32::from(level)).sum();
+                min * scale * sum as f32 + levels.len() as f32 * min * min / 2.0
+            }
+            DistanceType::L2 => {
+                let square_sum: u32 = levels
+                    .iter()
+                    .map(|&level| u32::from(level) * u32::from(level))
+                    .sum();
+                -scale * scale * square_sum as f32
+            }
+            DistanceType::L1 => 0.0,
+        }
+    }
+
+    fn encode_float8(&self, value: f32) -> u8 {
+        let magnitude = (value.abs() * self.metadata.scale).min(FLOAT8_MAX);
+        // Codes of non-negative values are sorted by value, so the nearest one is next to
+        // the insertion point
+        let positive_values = &self.float8_values[..=FLOAT8_MAX_CODE as usize];
+        let upper = positive_values
+            .partition_point(|&decoded| decoded < magnitude)
+            .min(FLOAT8_MAX_CODE as usize);
+        let nearest = if upper > 0
+            && magnitude - positive_values[upper - 1] <= positive_values[upper] - magnitude
+        {
+            upper - 1
+        } else {
+            upper
+        };
+        let code = nearest as u8;
+        if value < 0.0 && code != 0 {
+            code | 0x80
+        } else {
+            code
+        }
+    }
+
+    fn encode_vector(&self, vector: &[VectorElementType]) -> Vec<u8> {
+        match self.metadata.r#type {
+            ScalarType::Int4 => {
+                let levels = self.int4_levels(vector);
+                let mut encoded = Vec::with_capacity(self.vector_size);
+                encoded.extend_from_slice(&self.int4_correction(&levels).to_le_bytes());
+                encoded.extend(
+                    levels
+                        .chunks(2)
+                        .map(|pair| pair[0] | (pair.get(1).copied().unwrap_or(0) << 4)),
+                );
+                encoded
+            }
+            ScalarType::Float8 => vector
+                .iter()
+                .map(|&value| self.encode_float8(value))
+                .collect(),
+            ScalarType::Int8 => unreachable!("int8 quantization is handled by `EncodedVectorsU8`"),
+        }
+    }
+
+    fn encode_query(&self, query: &[VectorElementType]) -> EncodedScalarExtQuery {
+        match self.metadata.r#type {
+            ScalarType::Int4 => {
+                let levels = self.int4_levels(query);
+                let correction = self.int4_correction(&levels);
+                EncodedScalarExtQuery::Int4 { levels, correction }
+            }
+            ScalarType::Float8 => EncodedScalarExtQuery::Float8 {
+                values: query
+                    .iter()
+                    .map(|value| value * self.metadata.scale)
+                    .collect(),
+            },
+            ScalarType::Int8 => unreachable!("int8 quantization is handled by `EncodedVectorsU8`"),
+        }
+    }
+
+    /// Unpacked levels and correction of an int4 encoded vector
+    fn int4_vector<'a>(&self, data: &'a [u8]) -> (impl Iterator<Item = u8> + 'a, f32) {
+        let (correction, packed) = data.split_at(CORRECTION_SIZE);
+        let levels = packed
+            .iter()
+            .flat_map(|byte| [byte & 0x0F, byte >> 4])
+            .take(self.vector_parameters.dim);
+        (levels, f32::from_le_bytes(correction.try_into().unwrap()))
+    }
+
+    fn score_int4(
+        &self,
+        a: impl Iterator<Item = u8>,
+        correction_a: f32,
+        b: impl Iterator<Item = u8>,
+        correction_b: f32,
+    ) -> ScoreType {
+        let scale = self.metadata.scale;
+        match self.vector_parameters.distance_type {
+            DistanceType::Dot => {
+                let product: u32 = a.zip(b).map(|(a, b)| u32::from(a) * u32::from(b)).sum();
+                scale * scale * product as f32 + correction_a + correction_b
+            }
+            DistanceType::L2 => {
+                let product: u32 = a.zip(b).map(|(a, b)| u32::from(a) * u32::from(b)).sum();
+                2.0 * scale * scale * product as f32 + correction_a + correction_b
+            }
+            DistanceType::L1 => {
+                let distance: u32 = a.zip(b).map(|(a, b)| u32::from(a.abs_diff(b))).sum();
+                -scale * distance as f32
+            }
+        }
+    }
+
+    /// Score float8 encoded vector against values in the scaled space
+    fn score_float8(&self, a: impl Iterator<Item = f32>, b: &[u8]) -> ScoreType {
+        let scale = self.metadata.scale;
+        let b = b.iter().map(|&code| self.float8_values[usize::from(code)]);
+        match self.vector_parameters.distance_type {
+            DistanceType::Dot => a.zip(b).map(|(a, b)| a * b).sum::<f32>() / (scale * scale),
+            DistanceType::L2 => {
+                -a.zip(b).map(|(a, b)| (a - b) * (a - b)).sum::<f32>() / (scale * scale)
+            }
+            DistanceType::L1 => -a.zip(b).map(|(a, b)| (a - b).abs()).sum::<f32>() / scale,
+        }
+    }
+}
+
+impl<TStorage: EncodedStorage> EncodedVectors<EncodedScalarExtQuery>
+    for EncodedVectorsScalarExt<TStorage>
+{
+    fn save(&self, data_path: &Path, meta_path: &Path) -> std::io::Result<()> {
+        self.encoded_vectors.save_to_file(data_path)?;
+        let metadata_bytes = serde_json::to_vec(&self.codec.metadata)?;
+        std::fs::write(meta_path, metadata_bytes)?;
+        Ok(())
+    }
+
+    fn load(
+        data_path: &Path,
+        meta_path: &Path,
+        vector_parameters: &VectorParameters,
+    ) -> std::io::Result<Self> {
+        let metadata: Metadata = serde_json::from_slice(&std::fs::read(meta_path)?)?;
+        let codec = Codec::new(metadata, vector_parameters.clone());
+        let encoded_vectors =
+            TStorage::from_file(data_path, codec.vector_size, vector_parameters.count)?;
+        Ok(Self {
+            encoded_vectors,
+            codec,
+        })
+    }
+
+    fn encode_query(&self, query: &[VectorElementType]) -> EncodedScalarExtQuery {
+        self.codec.encode_query(query)
+    }
+
+    fn score_point(&self, query: &EncodedScalarExtQuery, i: PointOffsetType) -> ScoreType {
+        let data = self.vector_data(i);
+        match query {
+            EncodedScalarExtQuery::Int4 { levels, correction } => {
+                let (vector_levels, vector_correction) = self.codec.int4_vector(data);
+                self.codec.score_int4(
+                    levels.iter().copied(),
+                    *correction,
+                    vector_levels,
+                    vector_correction,
+                )
+            }
+            EncodedScalarExtQuery::Float8 { values } => {
+                self.codec.score_float8(values.iter().copied(), data)
+            }
+        }
+    }
+
+    fn score_internal(&self, i: PointOffsetType, j: PointOffsetType) -> ScoreType {
+        let data_a = self.vector_data(i);
+        let data_b = self.vector_data(j);
+        match self.codec.metadata.r#type {
+            ScalarType::Int4 => {
+                let (levels_a, correction_a) = self.codec.int4_vector(data_a);
+                let (levels_b, correction_b) = self.codec.int4_vector(data_b);
+                self.codec
+                    .score_int4(levels_a, correction_a, levels_b, correction_b)
+            }
+            ScalarType::Float8 => {
+                let values_a = data_a
+                    .iter()
+                    .map(|&code| self.codec.float8_values[usize::from(code)]);
+                self.codec.score_float8(values_a, data_b)
+            }
+            ScalarType::Int8 => unreachable!("int8 quantization is handled by `EncodedVectorsU8`"),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod encoded_vectors_bin_ext;
+pub mod encoded_vectors_scalar_ext;
 mod quantized_custom_query_scorer;
 mod quantized_mmap_storage;
 pub mod quantized_multivector_storage;
@@ -6,17 +6,17 @@ use quantization::EncodedVectors;
 use super::quantized_custom_query_scorer::QuantizedCustomQueryScorer;
 use super::quantized_query_scorer::QuantizedQueryScorer;
 use super::quantized_vectors::QuantizedVectorStorage;
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
-    DenseVector, MultiDenseVector, QueryVector, VectorElementType, VectorElementTypeByte,
-    VectorElementTypeHalf,
+    DenseVector, MultiDenseVector, QueryVector, VectorElementType, VectorElementTypeBfloat16,
+    VectorElementTypeByte, VectorElementTypeHalf,
 };
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::types::{Distance, QuantizationConfig, VectorStorageDatatype};
 use crate::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery, TransformInto};
-use crate::vector_storage::{raw_scorer_from_query_scorer, RawScorer};
+use crate::vector_storage::{binary_distance_error, raw_scorer_from_query_scorer, RawScorer};
 
 pub(super) struct QuantizedScorerBuilder<'a> {
     quantized_storage: &'a QuantizedVectorStorage,
@@ -62,6 +62,9 @@ impl<'a> QuantizedScorerBuilder<'a> {
                 Distance::Manhattan => {
                     self.build_with_metric::<VectorElementType, ManhattanMetric>()
                 }
+                distance @ (Distance::Hamming | Distance::Jaccard) => {
+                    Err(binary_distance_error(*distance))
+                }
             },
             VectorStorageDatatype::Uint8 => match self.distance {
                 Distance::Cosine => self.build_with_metric::<VectorElementTypeByte, CosineMetric>(),
@@ -72,6 +75,9 @@ impl<'a> QuantizedScorerBuilder<'a> {
                 Distance::Manhattan => {
                     self.build_with_metric::<VectorElementTypeByte, ManhattanMetric>()
                 }
+                distance @ (Distance::Hamming | Distance::Jaccard) => {
+                    Err(binary_distance_error(*distance))
+                }
             },
             VectorStorageDatatype::Float16 => match self.distance {
                 Distance::Cosine => self.build_with_metric::<VectorElementTypeHalf, CosineMetric>(),
@@ -82,7 +88,30 @@ impl<'a> QuantizedScorerBuilder<'a> {
                 Distance::Manhattan => {
                     self.build_with_metric::<VectorElementTypeHalf, ManhattanMetric>()
                 }
+                distance @ (Distance::Hamming | Distance::Jaccard) => {
+                    Err(binary_distance_error(*distance))
+                }
+            },
+            VectorStorageDatatype::Bfloat16 => match self.distance {
+                Distance::Cosine => {
+                    self.build_with_metric::<VectorElementTypeBfloat16, CosineMetric>()
+                }
+                Distance::Euclid => {
+                    self.build_with_metric::<VectorElementTypeBfloat16, EuclidMetric>()
+                }
+                Distance::Dot => {
+                    self.build_with_metric::<VectorElementTypeBfloat16, DotProductMetric>()
+                }
+                Distance::Manhattan => {
+                    self.build_with_metric::<VectorElementTypeBfloat16, ManhattanMetric>()
+                }
+                distance @ (Distance::Hamming | Distance::Jaccard) => {
+                    Err(binary_distance_error(*distance))
+                }
             },
+            VectorStorageDatatype::Bit => Err(OperationError::ValidationError {
+                description: "Quantization is not supported for bit vectors".to_string(),
+            }),
         }
     }
 
@@ -110,6 +139,18 @@ impl<'a> QuantizedScorerBuilder<'a> {
             QuantizedVectorStorage::BinaryMmap(storage) => {
                 self.new_quantized_scorer::<TElement, TMetric, _>(storage)
             }
+            QuantizedVectorStorage::ScalarExtRam(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
+            QuantizedVectorStorage::ScalarExtMmap(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
+            QuantizedVectorStorage::BinaryExtRam(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
+            QuantizedVectorStorage::BinaryExtMmap(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
             QuantizedVectorStorage::ScalarRamMulti(storage) => {
                 self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
             }
@@ -128,6 +169,18 @@ impl<'a> QuantizedScorerBuilder<'a> {
             QuantizedVectorStorage::BinaryMmapMulti(storage) => {
                 self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
             }
+            QuantizedVectorStorage::ScalarExtRamMulti(storage) => {
+                self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
+            QuantizedVectorStorage::ScalarExtMmapMulti(storage) => {
+                self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
+            QuantizedVectorStorage::BinaryExtRamMulti(storage) => {
+                self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
+            QuantizedVectorStorage::BinaryExtMmapMulti(storage) => {
+                self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
         }
     }
 
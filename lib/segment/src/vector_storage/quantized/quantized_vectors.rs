@@ -10,6 +10,8 @@ use quantization::{
 };
 use serde::{Deserialize, Serialize};
 
+use super::encoded_vectors_bin_ext::{EncodedBinExtQuery, EncodedVectorsBinExt};
+use super::encoded_vectors_scalar_ext::{EncodedScalarExtQuery, EncodedVectorsScalarExt};
 use super::quantized_multivector_storage::{
     create_offsets_file_from_iter, MultivectorOffset, MultivectorOffsetsStorage,
     MultivectorOffsetsStorageMmap, QuantizedMultivectorStorage,
@@ -22,7 +24,7 @@ use crate::data_types::vectors::{QueryVector, VectorElementType};
 use crate::types::{
     BinaryQuantization, BinaryQuantizationConfig, CompressionRatio, Distance, MultiVectorConfig,
     ProductQuantization, ProductQuantizationConfig, QuantizationConfig, ScalarQuantization,
-    ScalarQuantizationConfig, VectorStorageDatatype,
+    ScalarQuantizationConfig, ScalarType, VectorStorageDatatype,
 };
 use crate::vector_storage::chunked_vectors::ChunkedVectors;
 use crate::vector_storage::quantized::quantized_mmap_storage::{
@@ -79,19 +81,51 @@ type BinaryMmapMulti = QuantizedMultivectorStorage<
     MultivectorOffsetsStorageMmap,
 >;
 
+type ScalarExtRamMulti = QuantizedMultivectorStorage<
+    EncodedScalarExtQuery,
+    EncodedVectorsScalarExt<ChunkedVectors<u8>>,
+    Vec<MultivectorOffset>,
+>;
+
+type ScalarExtMmapMulti = QuantizedMultivectorStorage<
+    EncodedScalarExtQuery,
+    EncodedVectorsScalarExt<QuantizedMmapStorage>,
+    MultivectorOffsetsStorageMmap,
+>;
+
+type BinaryExtRamMulti = QuantizedMultivectorStorage<
+    EncodedBinExtQuery,
+    EncodedVectorsBinExt<ChunkedVectors<u8>>,
+    Vec<MultivectorOffset>,
+>;
+
+type BinaryExtMmapMulti = QuantizedMultivectorStorage<
+    EncodedBinExtQuery,
+    EncodedVectorsBinExt<QuantizedMmapStorage>,
+    MultivectorOffsetsStorageMmap,
+>;
+
 pub enum QuantizedVectorStorage {
     ScalarRam(EncodedVectorsU8<ChunkedVectors<u8>>),
     ScalarMmap(EncodedVectorsU8<QuantizedMmapStorage>),
+    ScalarExtRam(EncodedVectorsScalarExt<ChunkedVectors<u8>>),
+    ScalarExtMmap(EncodedVectorsScalarExt<QuantizedMmapStorage>),
     PQRam(EncodedVectorsPQ<ChunkedVectors<u8>>),
     PQMmap(EncodedVectorsPQ<QuantizedMmapStorage>),
     BinaryRam(EncodedVectorsBin<u128, ChunkedVectors<u8>>),
     BinaryMmap(EncodedVectorsBin<u128, QuantizedMmapStorage>),
+    BinaryExtRam(EncodedVectorsBinExt<ChunkedVectors<u8>>),
+    BinaryExtMmap(EncodedVectorsBinExt<QuantizedMmapStorage>),
     ScalarRamMulti(ScalarRamMulti),
     ScalarMmapMulti(ScalarMmapMulti),
+    ScalarExtRamMulti(ScalarExtRamMulti),
+    ScalarExtMmapMulti(ScalarExtMmapMulti),
     PQRamMulti(PQRamMulti),
     PQMmapMulti(PQMmapMulti),
     BinaryRamMulti(BinaryRamMulti),
     BinaryMmapMulti(BinaryMmapMulti),
+    BinaryExtRamMulti(BinaryExtRamMulti),
+    BinaryExtMmapMulti(BinaryExtMmapMulti),
 }
 
 pub struct QuantizedVectors {
@@ -104,26 +138,44 @@ pub struct QuantizedVectors {
 
 impl QuantizedVectors {
     pub fn default_rescoring(&self) -> bool {
-        matches!(
-            self.storage_impl,
-            QuantizedVectorStorage::BinaryRam(_) | QuantizedVectorStorage::BinaryMmap(_)
-        )
+        match &self.storage_impl {
+            QuantizedVectorStorage::BinaryRam(_)
+            | QuantizedVectorStorage::BinaryMmap(_)
+            | QuantizedVectorStorage::BinaryExtRam(_)
+            | QuantizedVectorStorage::BinaryExtMmap(_) => true,
+            // 16 levels are too coarse to keep the order of close candidates
+            QuantizedVectorStorage::ScalarExtRam(storage) => {
+                *storage.scalar_type() == ScalarType::Int4
+            }
+            QuantizedVectorStorage::ScalarExtMmap(storage) => {
+                *storage.scalar_type() == ScalarType::Int4
+            }
+            _ => false,
+        }
     }
 
     pub fn is_multivector(&self) -> bool {
         match self.storage_impl {
             QuantizedVectorStorage::ScalarRam(_) => false,
             QuantizedVectorStorage::ScalarMmap(_) => false,
+            QuantizedVectorStorage::ScalarExtRam(_) => false,
+            QuantizedVectorStorage::ScalarExtMmap(_) => false,
             QuantizedVectorStorage::PQRam(_) => false,
             QuantizedVectorStorage::PQMmap(_) => false,
             QuantizedVectorStorage::BinaryRam(_) => false,
             QuantizedVectorStorage::BinaryMmap(_) => false,
+            QuantizedVectorStorage::BinaryExtRam(_) => false,
+            QuantizedVectorStorage::BinaryExtMmap(_) => false,
             QuantizedVectorStorage::ScalarRamMulti(_) => true,
             QuantizedVectorStorage::ScalarMmapMulti(_) => true,
+            QuantizedVectorStorage::ScalarExtRamMulti(_) => true,
+            QuantizedVectorStorage::ScalarExtMmapMulti(_) => true,
             QuantizedVectorStorage::PQRamMulti(_) => true,
             QuantizedVectorStorage::PQMmapMulti(_) => true,
             QuantizedVectorStorage::BinaryRamMulti(_) => true,
             QuantizedVectorStorage::BinaryMmapMulti(_) => true,
+            QuantizedVectorStorage::BinaryExtRamMulti(_) => true,
+            QuantizedVectorStorage::BinaryExtMmapMulti(_) => true,
         }
     }
 
@@ -154,16 +206,34 @@ impl QuantizedVectors {
         match &self.storage_impl {
             QuantizedVectorStorage::ScalarRam(storage) => storage.save(&data_path, &meta_path)?,
             QuantizedVectorStorage::ScalarMmap(storage) => storage.save(&data_path, &meta_path)?,
+            QuantizedVectorStorage::ScalarExtRam(storage) => {
+                storage.save(&data_path, &meta_path)?
+            }
+            QuantizedVectorStorage::ScalarExtMmap(storage) => {
+                storage.save(&data_path, &meta_path)?
+            }
             QuantizedVectorStorage::PQRam(storage) => storage.save(&data_path, &meta_path)?,
             QuantizedVectorStorage::PQMmap(storage) => storage.save(&data_path, &meta_path)?,
             QuantizedVectorStorage::BinaryRam(storage) => storage.save(&data_path, &meta_path)?,
             QuantizedVectorStorage::BinaryMmap(storage) => storage.save(&data_path, &meta_path)?,
+            QuantizedVectorStorage::BinaryExtRam(storage) => {
+                storage.save(&data_path, &meta_path)?
+            }
+            QuantizedVectorStorage::BinaryExtMmap(storage) => {
+                storage.save(&data_path, &meta_path)?
+            }
             QuantizedVectorStorage::ScalarRamMulti(storage) => {
                 storage.save_multi(&data_path, &meta_path, &offsets_path)?
             }
             QuantizedVectorStorage::ScalarMmapMulti(storage) => {
                 storage.save_multi(&data_path, &meta_path, &offsets_path)?
             }
+            QuantizedVectorStorage::ScalarExtRamMulti(storage) => {
+                storage.save_multi(&data_path, &meta_path, &offsets_path)?
+            }
+            QuantizedVectorStorage::ScalarExtMmapMulti(storage) => {
+                storage.save_multi(&data_path, &meta_path, &offsets_path)?
+            }
             QuantizedVectorStorage::PQRamMulti(storage) => {
                 storage.save_multi(&data_path, &meta_path, &offsets_path)?
             }
@@ -176,6 +246,12 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryMmapMulti(storage) => {
                 storage.save_multi(&data_path, &meta_path, &offsets_path)?
             }
+            QuantizedVectorStorage::BinaryExtRamMulti(storage) => {
+                storage.save_multi(&data_path, &meta_path, &offsets_path)?
+            }
+            QuantizedVectorStorage::BinaryExtMmapMulti(storage) => {
+                storage.save_multi(&data_path, &meta_path, &offsets_path)?
+            }
         };
         Ok(())
     }
@@ -212,6 +288,9 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseSimpleHalf(v) => {
                 Self::create_impl(v, quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseSimpleBfloat16(v) => {
+                Self::create_impl(v, quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::DenseMemmap(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
@@ -221,6 +300,9 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseMemmapHalf(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseMemmapBfloat16(v) => {
+                Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::DenseAppendableMemmap(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
@@ -230,6 +312,16 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => {
+                Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
+            VectorStorageEnum::DenseSimpleBit(_)
+            | VectorStorageEnum::DenseMemmapBit(_)
+            | VectorStorageEnum::DenseAppendableMemmapBit(_) => {
+                Err(OperationError::ValidationError {
+                    description: "Quantization is not supported for bit vectors".to_string(),
+                })
+            }
             VectorStorageEnum::SparseSimple(_) => Err(OperationError::WrongSparse),
             VectorStorageEnum::MultiDenseSimple(v) => {
                 Self::create_multi_impl(v, quantization_config, path, max_threads, stopped)
@@ -240,6 +332,9 @@ impl QuantizedVectors {
             VectorStorageEnum::MultiDenseSimpleHalf(v) => {
                 Self::create_multi_impl(v, quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => {
+                Self::create_multi_impl(v, quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => {
                 Self::create_multi_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
@@ -249,6 +344,9 @@ impl QuantizedVectors {
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
                 Self::create_multi_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => {
+                Self::create_multi_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
         }
     }
 
@@ -439,6 +537,31 @@ impl QuantizedVectors {
         {
             let offsets_path = path.join(QUANTIZED_OFFSETS_PATH);
             match &config.quantization_config {
+                QuantizationConfig::Scalar(ScalarQuantization { scalar })
+                    if scalar.r#type != ScalarType::Int8 =>
+                {
+                    if Self::is_ram(scalar.always_ram, on_disk_vector_storage) {
+                        QuantizedVectorStorage::ScalarExtRamMulti(
+                            QuantizedMultivectorStorage::load_multi(
+                                &data_path,
+                                &meta_path,
+                                &offsets_path,
+                                &config.vector_parameters,
+                                multivector_config,
+                            )?,
+                        )
+                    } else {
+                        QuantizedVectorStorage::ScalarExtMmapMulti(
+                            QuantizedMultivectorStorage::load_multi(
+                                &data_path,
+                                &meta_path,
+                                &offsets_path,
+                                &config.vector_parameters,
+                                multivector_config,
+                            )?,
+                        )
+                    }
+                }
                 QuantizationConfig::Scalar(ScalarQuantization { scalar }) => {
                     if Self::is_ram(scalar.always_ram, on_disk_vector_storage) {
                         QuantizedVectorStorage::ScalarRamMulti(
@@ -483,6 +606,31 @@ impl QuantizedVectors {
                         )
                     }
                 }
+                QuantizationConfig::Binary(BinaryQuantization { binary })
+                    if binary.is_extended() =>
+                {
+                    if Self::is_ram(binary.always_ram, on_disk_vector_storage) {
+                        QuantizedVectorStorage::BinaryExtRamMulti(
+                            QuantizedMultivectorStorage::load_multi(
+                                &data_path,
+                                &meta_path,
+                                &offsets_path,
+                                &config.vector_parameters,
+                                multivector_config,
+                            )?,
+                        )
+                    } else {
+                        QuantizedVectorStorage::BinaryExtMmapMulti(
+                            QuantizedMultivectorStorage::load_multi(
+                                &data_path,
+                                &meta_path,
+                                &offsets_path,
+                                &config.vector_parameters,
+                                multivector_config,
+                            )?,
+                        )
+                    }
+                }
                 QuantizationConfig::Binary(BinaryQuantization { binary }) => {
                     if Self::is_ram(binary.always_ram, on_disk_vector_storage) {
                         QuantizedVectorStorage::BinaryRamMulti(
@@ -509,6 +657,23 @@ impl QuantizedVectors {
             }
         } else {
             match &config.quantization_config {
+                QuantizationConfig::Scalar(ScalarQuantization { scalar })
+                    if scalar.r#type != ScalarType::Int8 =>
+                {
+                    if Self::is_ram(scalar.always_ram, on_disk_vector_storage) {
+                        QuantizedVectorStorage::ScalarExtRam(EncodedVectorsScalarExt::load(
+                            &data_path,
+                            &meta_path,
+                            &config.vector_parameters,
+                        )?)
+                    } else {
+                        QuantizedVectorStorage::ScalarExtMmap(EncodedVectorsScalarExt::load(
+                            &data_path,
+                            &meta_path,
+                            &config.vector_parameters,
+                        )?)
+                    }
+                }
                 QuantizationConfig::Scalar(ScalarQuantization { scalar }) => {
                     if Self::is_ram(scalar.always_ram, on_disk_vector_storage) {
                         QuantizedVectorStorage::ScalarRam(EncodedVectorsU8::load(
@@ -539,6 +704,23 @@ impl QuantizedVectors {
                         )?)
                     }
                 }
+                QuantizationConfig::Binary(BinaryQuantization { binary })
+                    if binary.is_extended() =>
+                {
+                    if Self::is_ram(binary.always_ram, on_disk_vector_storage) {
+                        QuantizedVectorStorage::BinaryExtRam(EncodedVectorsBinExt::load(
+                            &data_path,
+                            &meta_path,
+                            &config.vector_parameters,
+                        )?)
+                    } else {
+                        QuantizedVectorStorage::BinaryExtMmap(EncodedVectorsBinExt::load(
+                            &data_path,
+                            &meta_path,
+                            &config.vector_parameters,
+                        )?)
+                    }
+                }
                 QuantizationConfig::Binary(BinaryQuantization { binary }) => {
                     if Self::is_ram(binary.always_ram, on_disk_vector_storage) {
                         QuantizedVectorStorage::BinaryRam(EncodedVectorsBin::load(
@@ -574,6 +756,17 @@ impl QuantizedVectors {
         on_disk_vector_storage: bool,
         stopped: &AtomicBool,
     ) -> OperationResult<QuantizedVectorStorage> {
+        if scalar_config.r#type != ScalarType::Int8 {
+            return Self::create_scalar_ext(
+                vectors,
+                vector_parameters,
+                scalar_config,
+                path,
+                on_disk_vector_storage,
+                stopped,
+            );
+        }
+
         let quantized_vector_size =
             EncodedVectorsU8::<QuantizedMmapStorage>::get_quantized_vector_size(vector_parameters);
         let in_ram = Self::is_ram(scalar_config.always_ram, on_disk_vector_storage);
@@ -617,6 +810,19 @@ impl QuantizedVectors {
         on_disk_vector_storage: bool,
         stopped: &AtomicBool,
     ) -> OperationResult<QuantizedVectorStorage> {
+        if scalar_config.r#type != ScalarType::Int8 {
+            return Self::create_scalar_ext_multi(
+                vectors,
+                offsets,
+                vector_parameters,
+                scalar_config,
+                multi_vector_config,
+                path,
+                on_disk_vector_storage,
+                stopped,
+            );
+        }
+
         let quantized_vector_size =
             EncodedVectorsU8::<QuantizedMmapStorage>::get_quantized_vector_size(vector_parameters);
         let in_ram = Self::is_ram(scalar_config.always_ram, on_disk_vector_storage);
@@ -665,6 +871,117 @@ impl QuantizedVectors {
         }
     }
 
+    fn create_scalar_ext<'a>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
+        vector_parameters: &quantization::VectorParameters,
+        scalar_config: &ScalarQuantizationConfig,
+        path: &Path,
+        on_disk_vector_storage: bool,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        let quantized_vector_size =
+            EncodedVectorsScalarExt::<QuantizedMmapStorage>::get_quantized_vector_size(
+                vector_parameters,
+                &scalar_config.r#type,
+            );
+        let in_ram = Self::is_ram(scalar_config.always_ram, on_disk_vector_storage);
+        if in_ram {
+            let mut storage_builder = ChunkedVectors::<u8>::new(quantized_vector_size);
+            storage_builder.try_set_capacity_exact(vector_parameters.count)?;
+            Ok(QuantizedVectorStorage::ScalarExtRam(
+                EncodedVectorsScalarExt::encode(
+                    vectors,
+                    storage_builder,
+                    vector_parameters,
+                    scalar_config.r#type.clone(),
+                    scalar_config.quantile,
+                    stopped,
+                )?,
+            ))
+        } else {
+            let mmap_data_path = path.join(QUANTIZED_DATA_PATH);
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                mmap_data_path.as_path(),
+                vector_parameters.count,
+                quantized_vector_size,
+            )?;
+            Ok(QuantizedVectorStorage::ScalarExtMmap(
+                EncodedVectorsScalarExt::encode(
+                    vectors,
+                    storage_builder,
+                    vector_parameters,
+                    scalar_config.r#type.clone(),
+                    scalar_config.quantile,
+                    stopped,
+                )?,
+            ))
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_scalar_ext_multi<'a>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
+        offsets: impl Iterator<Item = MultivectorOffset>,
+        vector_parameters: &quantization::VectorParameters,
+        scalar_config: &ScalarQuantizationConfig,
+        multi_vector_config: MultiVectorConfig,
+        path: &Path,
+        on_disk_vector_storage: bool,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        let quantized_vector_size =
+            EncodedVectorsScalarExt::<QuantizedMmapStorage>::get_quantized_vector_size(
+                vector_parameters,
+                &scalar_config.r#type,
+            );
+        let in_ram = Self::is_ram(scalar_config.always_ram, on_disk_vector_storage);
+        if in_ram {
+            let mut storage_builder = ChunkedVectors::<u8>::new(quantized_vector_size);
+            storage_builder.try_set_capacity_exact(vector_parameters.count)?;
+            let quantized_storage = EncodedVectorsScalarExt::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                scalar_config.r#type.clone(),
+                scalar_config.quantile,
+                stopped,
+            )?;
+            Ok(QuantizedVectorStorage::ScalarExtRamMulti(
+                QuantizedMultivectorStorage::new(
+                    vector_parameters.dim,
+                    quantized_storage,
+                    offsets.collect(),
+                    multi_vector_config,
+                ),
+            ))
+        } else {
+            let mmap_data_path = path.join(QUANTIZED_DATA_PATH);
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                mmap_data_path.as_path(),
+                vector_parameters.count,
+                quantized_vector_size,
+            )?;
+            let quantized_storage = EncodedVectorsScalarExt::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                scalar_config.r#type.clone(),
+                scalar_config.quantile,
+                stopped,
+            )?;
+            let offsets_path = path.join(QUANTIZED_OFFSETS_PATH);
+            create_offsets_file_from_iter(&offsets_path, vector_parameters.count, offsets)?;
+            Ok(QuantizedVectorStorage::ScalarExtMmapMulti(
+                QuantizedMultivectorStorage::new(
+                    vector_parameters.dim,
+                    quantized_storage,
+                    MultivectorOffsetsStorage::load(&offsets_path)?,
+                    multi_vector_config,
+                ),
+            ))
+        }
+    }
+
     fn create_pq<'a>(
         vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone + Send,
         vector_parameters: &quantization::VectorParameters,
@@ -784,6 +1101,17 @@ impl QuantizedVectors {
         on_disk_vector_storage: bool,
         stopped: &AtomicBool,
     ) -> OperationResult<QuantizedVectorStorage> {
+        if binary_config.is_extended() {
+            return Self::create_binary_ext(
+                vectors,
+                vector_parameters,
+                binary_config,
+                path,
+                on_disk_vector_storage,
+                stopped,
+            );
+        }
+
         let quantized_vector_size =
             EncodedVectorsBin::<u128, QuantizedMmapStorage>::get_quantized_vector_size_from_params(
                 vector_parameters,
@@ -823,6 +1151,19 @@ impl QuantizedVectors {
         on_disk_vector_storage: bool,
         stopped: &AtomicBool,
     ) -> OperationResult<QuantizedVectorStorage> {
+        if binary_config.is_extended() {
+            return Self::create_binary_ext_multi(
+                vectors,
+                offsets,
+                vector_parameters,
+                binary_config,
+                multi_vector_config,
+                path,
+                on_disk_vector_storage,
+                stopped,
+            );
+        }
+
         let quantized_vector_size =
             EncodedVectorsBin::<u8, QuantizedMmapStorage>::get_quantized_vector_size_from_params(
                 vector_parameters,
@@ -867,6 +1208,119 @@ impl QuantizedVectors {
         }
     }
 
+    fn create_binary_ext<'a>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
+        vector_parameters: &quantization::VectorParameters,
+        binary_config: &BinaryQuantizationConfig,
+        path: &Path,
+        on_disk_vector_storage: bool,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        let encoding = binary_config.encoding.unwrap_or_default();
+        let quantized_vector_size =
+            EncodedVectorsBinExt::<QuantizedMmapStorage>::get_quantized_vector_size(
+                vector_parameters,
+                encoding,
+            );
+        let in_ram = Self::is_ram(binary_config.always_ram, on_disk_vector_storage);
+        if in_ram {
+            let mut storage_builder = ChunkedVectors::<u8>::new(quantized_vector_size);
+            storage_builder.try_set_capacity_exact(vector_parameters.count)?;
+            Ok(QuantizedVectorStorage::BinaryExtRam(
+                EncodedVectorsBinExt::encode(
+                    vectors,
+                    storage_builder,
+                    vector_parameters,
+                    encoding,
+                    binary_config.query_encoding,
+                    stopped,
+                )?,
+            ))
+        } else {
+            let mmap_data_path = path.join(QUANTIZED_DATA_PATH);
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                mmap_data_path.as_path(),
+                vector_parameters.count,
+                quantized_vector_size,
+            )?;
+            Ok(QuantizedVectorStorage::BinaryExtMmap(
+                EncodedVectorsBinExt::encode(
+                    vectors,
+                    storage_builder,
+                    vector_parameters,
+                    encoding,
+                    binary_config.query_encoding,
+                    stopped,
+                )?,
+            ))
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_binary_ext_multi<'a>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
+        offsets: impl Iterator<Item = MultivectorOffset>,
+        vector_parameters: &quantization::VectorParameters,
+        binary_config: &BinaryQuantizationConfig,
+        multi_vector_config: MultiVectorConfig,
+        path: &Path,
+        on_disk_vector_storage: bool,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        let encoding = binary_config.encoding.unwrap_or_default();
+        let quantized_vector_size =
+            EncodedVectorsBinExt::<QuantizedMmapStorage>::get_quantized_vector_size(
+                vector_parameters,
+                encoding,
+            );
+        let in_ram = Self::is_ram(binary_config.always_ram, on_disk_vector_storage);
+        if in_ram {
+            let mut storage_builder = ChunkedVectors::<u8>::new(quantized_vector_size);
+            storage_builder.try_set_capacity_exact(vector_parameters.count)?;
+            let quantized_storage = EncodedVectorsBinExt::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                encoding,
+                binary_config.query_encoding,
+                stopped,
+            )?;
+            Ok(QuantizedVectorStorage::BinaryExtRamMulti(
+                QuantizedMultivectorStorage::new(
+                    vector_parameters.dim,
+                    quantized_storage,
+                    offsets.collect(),
+                    multi_vector_config,
+                ),
+            ))
+        } else {
+            let mmap_data_path = path.join(QUANTIZED_DATA_PATH);
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                mmap_data_path.as_path(),
+                vector_parameters.count,
+                quantized_vector_size,
+            )?;
+            let quantized_storage = EncodedVectorsBinExt::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                encoding,
+                binary_config.query_encoding,
+                stopped,
+            )?;
+            let offsets_path = path.join(QUANTIZED_OFFSETS_PATH);
+            create_offsets_file_from_iter(&offsets_path, vector_parameters.count, offsets)?;
+            Ok(QuantizedVectorStorage::BinaryExtMmapMulti(
+                QuantizedMultivectorStorage::new(
+                    vector_parameters.dim,
+                    quantized_storage,
+                    MultivectorOffsetsStorage::load(&offsets_path)?,
+                    multi_vector_config,
+                ),
+            ))
+        }
+    }
+
     fn is_ram(always_ram: Option<bool>, on_disk_vector_storage: bool) -> bool {
         !on_disk_vector_storage || always_ram == Some(true)
     }
@@ -884,6 +1338,8 @@ impl QuantizedVectors {
                 Distance::Euclid => quantization::DistanceType::L2,
                 Distance::Dot => quantization::DistanceType::Dot,
                 Distance::Manhattan => quantization::DistanceType::L1,
+                // bit vectors are never quantized
+                Distance::Hamming | Distance::Jaccard => quantization::DistanceType::L1,
             },
             invert: distance == Distance::Euclid || distance == Distance::Manhattan,
         }
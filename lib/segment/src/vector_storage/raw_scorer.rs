@@ -11,11 +11,13 @@ use super::query_scorer::sparse_custom_query_scorer::SparseCustomQueryScorer;
 use super::{DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorageEnum};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::vectors::{
-    DenseVector, MultiDenseVector, QueryVector, VectorElementType, VectorElementTypeByte,
-    VectorElementTypeHalf,
+    DenseVector, MultiDenseVector, QueryVector, VectorElementType, VectorElementTypeBfloat16,
+    VectorElementTypeBit, VectorElementTypeByte, VectorElementTypeHalf,
 };
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+};
 use crate::spaces::tools::peek_top_largest_iterable;
 use crate::types::Distance;
 use crate::vector_storage::query_scorer::metric_query_scorer::MetricQueryScorer;
@@ -117,6 +119,12 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::DenseSimpleHalf(vs) => {
             raw_scorer_half_impl(query, vs, point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseSimpleBfloat16(vs) => {
+            raw_scorer_bf16_impl(query, vs, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseSimpleBit(vs) => {
+            raw_scorer_bit_impl(query, vs, point_deleted, is_stopped)
+        }
 
         VectorStorageEnum::DenseMemmap(vs) => {
             if vs.has_async_reader() {
@@ -144,6 +152,12 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::DenseMemmapHalf(vs) => {
             raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseMemmapBfloat16(vs) => {
+            raw_scorer_bf16_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseMemmapBit(vs) => {
+            raw_scorer_bit_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
 
         VectorStorageEnum::DenseAppendableMemmap(vs) => {
             raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
@@ -154,6 +168,12 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::DenseAppendableMemmapHalf(vs) => {
             raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseAppendableMemmapBfloat16(vs) => {
+            raw_scorer_bf16_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseAppendableMemmapBit(vs) => {
+            raw_scorer_bit_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
         VectorStorageEnum::SparseSimple(vs) => {
             raw_sparse_scorer_impl(query, vs, point_deleted, is_stopped)
         }
@@ -166,6 +186,9 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::MultiDenseSimpleHalf(vs) => {
             raw_multi_scorer_half_impl(query, vs, point_deleted, is_stopped)
         }
+        VectorStorageEnum::MultiDenseSimpleBfloat16(vs) => {
+            raw_multi_scorer_bf16_impl(query, vs, point_deleted, is_stopped)
+        }
         VectorStorageEnum::MultiDenseAppendableMemmap(vs) => {
             raw_multi_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
@@ -175,11 +198,21 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::MultiDenseAppendableMemmapHalf(vs) => {
             raw_multi_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(vs) => {
+            raw_multi_scorer_bf16_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
     }
 }
 
 pub static DEFAULT_STOPPED: AtomicBool = AtomicBool::new(false);
 
+/// Error for binary distances used with a storage of non-binary vectors
+pub(crate) fn binary_distance_error(distance: Distance) -> OperationError {
+    OperationError::ValidationError {
+        description: format!("{distance:?} distance requires vectors with bit datatype"),
+    }
+}
+
 pub fn raw_sparse_scorer_impl<'a, TVectorStorage: SparseVectorStorage>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
@@ -260,6 +293,7 @@ pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementType>
             point_deleted,
             is_stopped,
         ),
+        distance @ (Distance::Hamming | Distance::Jaccard) => Err(binary_distance_error(distance)),
     }
 }
 
@@ -354,6 +388,7 @@ pub fn raw_scorer_byte_impl<'a, TVectorStorage: DenseVectorStorage<VectorElement
             point_deleted,
             is_stopped,
         ),
+        distance @ (Distance::Hamming | Distance::Jaccard) => Err(binary_distance_error(distance)),
     }
 }
 
@@ -448,6 +483,7 @@ pub fn raw_scorer_half_impl<'a, TVectorStorage: DenseVectorStorage<VectorElement
             point_deleted,
             is_stopped,
         ),
+        distance @ (Distance::Hamming | Distance::Jaccard) => Err(binary_distance_error(distance)),
     }
 }
 
@@ -511,6 +547,188 @@ fn new_scorer_half_with_metric<
     }
 }
 
+pub fn raw_scorer_bf16_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementTypeBfloat16>>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    match vector_storage.distance() {
+        Distance::Cosine => new_scorer_bf16_with_metric::<CosineMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => new_scorer_bf16_with_metric::<EuclidMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Dot => new_scorer_bf16_with_metric::<DotProductMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Manhattan => new_scorer_bf16_with_metric::<ManhattanMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        distance @ (Distance::Hamming | Distance::Jaccard) => Err(binary_distance_error(distance)),
+    }
+}
+
+fn new_scorer_bf16_with_metric<
+    'a,
+    TMetric: Metric<VectorElementTypeBfloat16> + 'a,
+    TVectorStorage: DenseVectorStorage<VectorElementTypeBfloat16>,
+>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            MetricQueryScorer::<VectorElementTypeBfloat16, TMetric, _>::new(
+                vector.try_into()?,
+                vector_storage,
+            ),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeBfloat16, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeBfloat16, TMetric, _, _, _>::new(
+                    discovery_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeBfloat16, TMetric, _, _, _>::new(
+                    context_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
+pub fn raw_scorer_bit_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementTypeBit>>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    match vector_storage.distance() {
+        Distance::Hamming => new_scorer_bit_with_metric::<HammingMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Jaccard => new_scorer_bit_with_metric::<JaccardMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        distance @ (Distance::Cosine | Distance::Euclid | Distance::Dot | Distance::Manhattan) => {
+            Err(OperationError::ValidationError {
+                description: format!("{distance:?} distance is not supported for bit vectors"),
+            })
+        }
+    }
+}
+
+fn new_scorer_bit_with_metric<
+    'a,
+    TMetric: Metric<VectorElementTypeBit> + 'a,
+    TVectorStorage: DenseVectorStorage<VectorElementTypeBit>,
+>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            MetricQueryScorer::<VectorElementTypeBit, TMetric, _>::new(
+                vector.try_into()?,
+                vector_storage,
+            ),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeBit, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeBit, TMetric, _, _, _>::new(
+                    discovery_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeBit, TMetric, _, _, _>::new(
+                    context_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
 pub fn raw_scorer_from_query_scorer<'a, TVector, TQueryScorer>(
     query_scorer: TQueryScorer,
     point_deleted: &'a BitSlice,
@@ -561,6 +779,7 @@ pub fn raw_multi_scorer_impl<'a, TVectorStorage: MultiVectorStorage<VectorElemen
             point_deleted,
             is_stopped,
         ),
+        distance @ (Distance::Hamming | Distance::Jaccard) => Err(binary_distance_error(distance)),
     }
 }
 
@@ -656,6 +875,7 @@ pub fn raw_multi_scorer_byte_impl<'a, TVectorStorage: MultiVectorStorage<VectorE
             point_deleted,
             is_stopped,
         ),
+        distance @ (Distance::Hamming | Distance::Jaccard) => Err(binary_distance_error(distance)),
     }
 }
 
@@ -751,6 +971,7 @@ pub fn raw_multi_scorer_half_impl<'a, TVectorStorage: MultiVectorStorage<VectorE
             point_deleted,
             is_stopped,
         ),
+        distance @ (Distance::Hamming | Distance::Jaccard) => Err(binary_distance_error(distance)),
     }
 }
 
@@ -815,6 +1036,105 @@ fn new_multi_scorer_half_with_metric<
     }
 }
 
+pub fn raw_multi_scorer_bf16_impl<
+    'a,
+    TVectorStorage: MultiVectorStorage<VectorElementTypeBfloat16>,
+>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    match vector_storage.distance() {
+        Distance::Cosine => new_multi_scorer_bf16_with_metric::<CosineMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => new_multi_scorer_bf16_with_metric::<EuclidMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Dot => new_multi_scorer_bf16_with_metric::<DotProductMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Manhattan => new_multi_scorer_bf16_with_metric::<ManhattanMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        distance @ (Distance::Hamming | Distance::Jaccard) => Err(binary_distance_error(distance)),
+    }
+}
+
+fn new_multi_scorer_bf16_with_metric<
+    'a,
+    TMetric: Metric<VectorElementTypeBfloat16> + 'a,
+    TVectorStorage: MultiVectorStorage<VectorElementTypeBfloat16>,
+>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            MultiMetricQueryScorer::<VectorElementTypeBfloat16, TMetric, _>::new(
+                vector.try_into()?,
+                vector_storage,
+            ),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<MultiDenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementTypeBfloat16, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<MultiDenseVector> =
+                discovery_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementTypeBfloat16, TMetric, _, _, _>::new(
+                    discovery_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<MultiDenseVector> = context_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementTypeBfloat16, TMetric, _, _, _>::new(
+                    context_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
 impl<'a, TVector, TQueryScorer> RawScorer for RawScorerImpl<'a, TVector, TQueryScorer>
 where
     TVector: ?Sized,
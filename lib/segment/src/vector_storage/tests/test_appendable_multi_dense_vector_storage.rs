@@ -69,12 +69,18 @@ fn do_test_delete_points(vector_dim: usize, vec_count: usize, storage: &mut Vect
             VectorStorageEnum::DenseSimple(_) => unreachable!(),
             VectorStorageEnum::DenseSimpleByte(_) => unreachable!(),
             VectorStorageEnum::DenseSimpleHalf(_) => unreachable!(),
+            VectorStorageEnum::DenseSimpleBfloat16(_) => unreachable!(),
+            VectorStorageEnum::DenseSimpleBit(_) => unreachable!(),
             VectorStorageEnum::DenseMemmap(_) => unreachable!(),
             VectorStorageEnum::DenseMemmapByte(_) => unreachable!(),
             VectorStorageEnum::DenseMemmapHalf(_) => unreachable!(),
+            VectorStorageEnum::DenseMemmapBfloat16(_) => unreachable!(),
+            VectorStorageEnum::DenseMemmapBit(_) => unreachable!(),
             VectorStorageEnum::DenseAppendableMemmap(_) => unreachable!(),
             VectorStorageEnum::DenseAppendableMemmapByte(_) => unreachable!(),
             VectorStorageEnum::DenseAppendableMemmapHalf(_) => unreachable!(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(_) => unreachable!(),
+            VectorStorageEnum::DenseAppendableMemmapBit(_) => unreachable!(),
             VectorStorageEnum::SparseSimple(_) => unreachable!(),
             VectorStorageEnum::MultiDenseSimple(v) => {
                 for (orig, vec) in orig_iter.zip(v.iterate_inner_vectors()) {
@@ -83,6 +89,7 @@ fn do_test_delete_points(vector_dim: usize, vec_count: usize, storage: &mut Vect
             }
             VectorStorageEnum::MultiDenseSimpleByte(_) => unreachable!(),
             VectorStorageEnum::MultiDenseSimpleHalf(_) => unreachable!(),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(_) => unreachable!(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => {
                 for (orig, vec) in orig_iter.zip(v.iterate_inner_vectors()) {
                     assert_eq!(orig, vec);
@@ -90,6 +97,7 @@ fn do_test_delete_points(vector_dim: usize, vec_count: usize, storage: &mut Vect
             }
             VectorStorageEnum::MultiDenseAppendableMemmapByte(_) => unreachable!(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(_) => unreachable!(),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(_) => unreachable!(),
         };
     }
 
@@ -15,8 +15,8 @@ use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
-    TypedMultiDenseVectorRef, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf,
-    VectorRef,
+    TypedMultiDenseVectorRef, VectorElementType, VectorElementTypeBfloat16, VectorElementTypeBit,
+    VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
 };
 use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
 use crate::vector_storage::dense::appendable_mmap_dense_vector_storage::AppendableMmapDenseVectorStorage;
@@ -122,16 +122,23 @@ pub enum VectorStorageEnum {
     DenseSimple(SimpleDenseVectorStorage<VectorElementType>),
     DenseSimpleByte(SimpleDenseVectorStorage<VectorElementTypeByte>),
     DenseSimpleHalf(SimpleDenseVectorStorage<VectorElementTypeHalf>),
+    DenseSimpleBfloat16(SimpleDenseVectorStorage<VectorElementTypeBfloat16>),
+    DenseSimpleBit(SimpleDenseVectorStorage<VectorElementTypeBit>),
     DenseMemmap(Box<MemmapDenseVectorStorage<VectorElementType>>),
     DenseMemmapByte(Box<MemmapDenseVectorStorage<VectorElementTypeByte>>),
     DenseMemmapHalf(Box<MemmapDenseVectorStorage<VectorElementTypeHalf>>),
+    DenseMemmapBfloat16(Box<MemmapDenseVectorStorage<VectorElementTypeBfloat16>>),
+    DenseMemmapBit(Box<MemmapDenseVectorStorage<VectorElementTypeBit>>),
     DenseAppendableMemmap(Box<AppendableMmapDenseVectorStorage<VectorElementType>>),
     DenseAppendableMemmapByte(Box<AppendableMmapDenseVectorStorage<VectorElementTypeByte>>),
     DenseAppendableMemmapHalf(Box<AppendableMmapDenseVectorStorage<VectorElementTypeHalf>>),
+    DenseAppendableMemmapBfloat16(Box<AppendableMmapDenseVectorStorage<VectorElementTypeBfloat16>>),
+    DenseAppendableMemmapBit(Box<AppendableMmapDenseVectorStorage<VectorElementTypeBit>>),
     SparseSimple(SimpleSparseVectorStorage),
     MultiDenseSimple(SimpleMultiDenseVectorStorage<VectorElementType>),
     MultiDenseSimpleByte(SimpleMultiDenseVectorStorage<VectorElementTypeByte>),
     MultiDenseSimpleHalf(SimpleMultiDenseVectorStorage<VectorElementTypeHalf>),
+    MultiDenseSimpleBfloat16(SimpleMultiDenseVectorStorage<VectorElementTypeBfloat16>),
     MultiDenseAppendableMemmap(Box<AppendableMmapMultiDenseVectorStorage<VectorElementType>>),
     MultiDenseAppendableMemmapByte(
         Box<AppendableMmapMultiDenseVectorStorage<VectorElementTypeByte>>,
@@ -139,6 +146,9 @@ pub enum VectorStorageEnum {
     MultiDenseAppendableMemmapHalf(
         Box<AppendableMmapMultiDenseVectorStorage<VectorElementTypeHalf>>,
     ),
+    MultiDenseAppendableMemmapBfloat16(
+        Box<AppendableMmapMultiDenseVectorStorage<VectorElementTypeBfloat16>>,
+    ),
 }
 
 impl VectorStorageEnum {
@@ -147,19 +157,29 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseSimple(_) => None,
             VectorStorageEnum::DenseSimpleByte(_) => None,
             VectorStorageEnum::DenseSimpleHalf(_) => None,
+            VectorStorageEnum::DenseSimpleBfloat16(_) => None,
+            VectorStorageEnum::DenseSimpleBit(_) => None,
             VectorStorageEnum::DenseMemmap(_) => None,
             VectorStorageEnum::DenseMemmapByte(_) => None,
             VectorStorageEnum::DenseMemmapHalf(_) => None,
+            VectorStorageEnum::DenseMemmapBfloat16(_) => None,
+            VectorStorageEnum::DenseMemmapBit(_) => None,
             VectorStorageEnum::DenseAppendableMemmap(_) => None,
             VectorStorageEnum::DenseAppendableMemmapByte(_) => None,
             VectorStorageEnum::DenseAppendableMemmapHalf(_) => None,
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(_) => None,
+            VectorStorageEnum::DenseAppendableMemmapBit(_) => None,
             VectorStorageEnum::SparseSimple(_) => None,
             VectorStorageEnum::MultiDenseSimple(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseSimpleByte(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseSimpleHalf(s) => Some(s.multi_vector_config()),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseAppendableMemmap(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(s) => Some(s.multi_vector_config()),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(s) => {
+                Some(s.multi_vector_config())
+            }
         }
     }
 }
@@ -170,19 +190,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.distance(),
             VectorStorageEnum::DenseSimpleByte(v) => v.distance(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.distance(),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.distance(),
+            VectorStorageEnum::DenseSimpleBit(v) => v.distance(),
             VectorStorageEnum::DenseMemmap(v) => v.distance(),
             VectorStorageEnum::DenseMemmapByte(v) => v.distance(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.distance(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.distance(),
+            VectorStorageEnum::DenseMemmapBit(v) => v.distance(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.distance(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.distance(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.distance(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.distance(),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.distance(),
             VectorStorageEnum::SparseSimple(v) => v.distance(),
             VectorStorageEnum::MultiDenseSimple(v) => v.distance(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.distance(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.distance(),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.distance(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.distance(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.distance(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.distance(),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.distance(),
         }
     }
 
@@ -191,19 +219,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.datatype(),
             VectorStorageEnum::DenseSimpleByte(v) => v.datatype(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.datatype(),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.datatype(),
+            VectorStorageEnum::DenseSimpleBit(v) => v.datatype(),
             VectorStorageEnum::DenseMemmap(v) => v.datatype(),
             VectorStorageEnum::DenseMemmapByte(v) => v.datatype(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.datatype(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.datatype(),
+            VectorStorageEnum::DenseMemmapBit(v) => v.datatype(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.datatype(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.datatype(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.datatype(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.datatype(),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.datatype(),
             VectorStorageEnum::SparseSimple(v) => v.datatype(),
             VectorStorageEnum::MultiDenseSimple(v) => v.datatype(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.datatype(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.datatype(),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.datatype(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.datatype(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.datatype(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.datatype(),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.datatype(),
         }
     }
 
@@ -212,19 +248,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.is_on_disk(),
             VectorStorageEnum::DenseSimpleByte(v) => v.is_on_disk(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseSimpleBit(v) => v.is_on_disk(),
             VectorStorageEnum::DenseMemmap(v) => v.is_on_disk(),
             VectorStorageEnum::DenseMemmapByte(v) => v.is_on_disk(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseMemmapBit(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.is_on_disk(),
             VectorStorageEnum::SparseSimple(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseSimple(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.is_on_disk(),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.is_on_disk(),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.is_on_disk(),
         }
     }
 
@@ -233,19 +277,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.total_vector_count(),
             VectorStorageEnum::DenseSimpleByte(v) => v.total_vector_count(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseSimpleBit(v) => v.total_vector_count(),
             VectorStorageEnum::DenseMemmap(v) => v.total_vector_count(),
             VectorStorageEnum::DenseMemmapByte(v) => v.total_vector_count(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseMemmapBit(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.total_vector_count(),
             VectorStorageEnum::SparseSimple(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseSimple(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.total_vector_count(),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.total_vector_count(),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.total_vector_count(),
         }
     }
 
@@ -254,19 +306,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseSimpleByte(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::DenseSimpleBit(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseMemmap(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseMemmapByte(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::DenseMemmapBit(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.available_size_in_bytes(),
             VectorStorageEnum::SparseSimple(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseSimple(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.available_size_in_bytes(),
         }
     }
 
@@ -275,19 +335,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.get_vector(key),
             VectorStorageEnum::DenseSimpleByte(v) => v.get_vector(key),
             VectorStorageEnum::DenseSimpleHalf(v) => v.get_vector(key),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.get_vector(key),
+            VectorStorageEnum::DenseSimpleBit(v) => v.get_vector(key),
             VectorStorageEnum::DenseMemmap(v) => v.get_vector(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.get_vector(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.get_vector(key),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.get_vector(key),
+            VectorStorageEnum::DenseMemmapBit(v) => v.get_vector(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.get_vector(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.get_vector(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.get_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.get_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.get_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseSimple(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.get_vector(key),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.get_vector(key),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.get_vector(key),
         }
     }
 
@@ -296,19 +364,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseSimpleByte(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseSimpleHalf(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseSimpleBit(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseMemmap(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseMemmapBit(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.get_vector_opt(key),
             VectorStorageEnum::SparseSimple(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseSimple(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.get_vector_opt(key),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.get_vector_opt(key),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.get_vector_opt(key),
         }
     }
 
@@ -317,19 +393,29 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseSimpleByte(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseSimpleHalf(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseSimpleBit(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseMemmap(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseMemmapByte(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseMemmapHalf(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseMemmapBit(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.insert_vector(key, vector),
             VectorStorageEnum::SparseSimple(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseSimple(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => {
+                v.insert_vector(key, vector)
+            }
         }
     }
 
@@ -343,9 +429,13 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::DenseSimpleByte(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::DenseSimpleHalf(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseSimpleBit(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::DenseMemmap(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::DenseMemmapByte(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::DenseMemmapHalf(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseMemmapBit(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => {
                 v.update_from(other, other_ids, stopped)
@@ -353,10 +443,19 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                 v.update_from(other, other_ids, stopped)
             }
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => {
+                v.update_from(other, other_ids, stopped)
+            }
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => {
+                v.update_from(other, other_ids, stopped)
+            }
             VectorStorageEnum::SparseSimple(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::MultiDenseSimple(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => {
+                v.update_from(other, other_ids, stopped)
+            }
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => {
                 v.update_from(other, other_ids, stopped)
             }
@@ -366,6 +465,9 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
                 v.update_from(other, other_ids, stopped)
             }
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => {
+                v.update_from(other, other_ids, stopped)
+            }
         }
     }
 
@@ -374,19 +476,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.flusher(),
             VectorStorageEnum::DenseSimpleByte(v) => v.flusher(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.flusher(),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.flusher(),
+            VectorStorageEnum::DenseSimpleBit(v) => v.flusher(),
             VectorStorageEnum::DenseMemmap(v) => v.flusher(),
             VectorStorageEnum::DenseMemmapByte(v) => v.flusher(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.flusher(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.flusher(),
+            VectorStorageEnum::DenseMemmapBit(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.flusher(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.flusher(),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.flusher(),
             VectorStorageEnum::SparseSimple(v) => v.flusher(),
             VectorStorageEnum::MultiDenseSimple(v) => v.flusher(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.flusher(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.flusher(),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.flusher(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.flusher(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.flusher(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.flusher(),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.flusher(),
         }
     }
 
@@ -395,19 +505,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.files(),
             VectorStorageEnum::DenseSimpleByte(v) => v.files(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.files(),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.files(),
+            VectorStorageEnum::DenseSimpleBit(v) => v.files(),
             VectorStorageEnum::DenseMemmap(v) => v.files(),
             VectorStorageEnum::DenseMemmapByte(v) => v.files(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.files(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.files(),
+            VectorStorageEnum::DenseMemmapBit(v) => v.files(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.files(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.files(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.files(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.files(),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.files(),
             VectorStorageEnum::SparseSimple(v) => v.files(),
             VectorStorageEnum::MultiDenseSimple(v) => v.files(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.files(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.files(),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.files(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.files(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.files(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.files(),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.files(),
         }
     }
 
@@ -416,19 +534,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.delete_vector(key),
             VectorStorageEnum::DenseSimpleByte(v) => v.delete_vector(key),
             VectorStorageEnum::DenseSimpleHalf(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseSimpleBit(v) => v.delete_vector(key),
             VectorStorageEnum::DenseMemmap(v) => v.delete_vector(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.delete_vector(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseMemmapBit(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.delete_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseSimple(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.delete_vector(key),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.delete_vector(key),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.delete_vector(key),
         }
     }
 
@@ -437,19 +563,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseSimpleByte(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseSimpleHalf(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseSimpleBit(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseMemmap(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseMemmapBit(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.is_deleted_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseSimple(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.is_deleted_vector(key),
         }
     }
 
@@ -458,19 +592,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseSimpleByte(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseSimpleBit(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseMemmap(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseMemmapByte(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseMemmapBit(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.deleted_vector_count(),
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.deleted_vector_count(),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.deleted_vector_count(),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.deleted_vector_count(),
         }
     }
 
@@ -479,19 +621,27 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseSimple(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseSimpleByte(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseSimpleBfloat16(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseSimpleBit(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseMemmap(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseMemmapByte(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseMemmapBit(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseAppendableMemmapBit(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::MultiDenseSimpleBfloat16(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::MultiDenseAppendableMemmapBfloat16(v) => v.deleted_vector_bitslice(),
         }
     }
 }
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::data_types::vectors::{only_default_vector, QueryVector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::index_fixtures::random_vector;
+use segment::index::{VectorIndex, VectorIndexEnum};
+use segment::segment::Segment;
+use segment::segment_constructor::{build_segment, load_segment};
+use segment::types::{
+    Distance, HnswConfig, Indexes, SearchParams, SegmentConfig, VectorDataConfig,
+    VectorStorageType, WithPayload,
+};
+use tempfile::Builder;
+
+fn check_recall(segment: &Segment, rnd: &mut StdRng, dim: usize) {
+    let top = 10;
+    let attempts = 20;
+    let mut hits = 0;
+    for _ in 0..attempts {
+        let query: QueryVector = random_vector(rnd, dim).into();
+
+        let index_result = segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query,
+                &WithPayload::default(),
+                &false.into(),
+                None,
+                top,
+                None,
+            )
+            .unwrap();
+        let exact_result = segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query,
+                &WithPayload::default(),
+                &false.into(),
+                None,
+                top,
+                Some(&SearchParams {
+                    exact: true,
+                    ..Default::default()
+                }),
+            )
+            .unwrap();
+
+        hits += index_result
+            .iter()
+            .filter(|found| exact_result.iter().any(|exact| exact.id == found.id))
+            .count();
+    }
+
+    assert!(
+        hits as f64 / (top * attempts) as f64 > 0.9,
+        "recall is too low: {hits} of {}",
+        top * attempts,
+    );
+}
+
+#[test]
+fn test_appendable_hnsw_links_upserted_points() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dim = 16;
+    let num_vectors: u64 = 1000;
+    let num_updated_vectors: u64 = 100;
+    let num_deleted_vectors: u64 = 50;
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::AppendableHnsw(HnswConfig {
+                    m: 16,
+                    ef_construct: 64,
+                    full_scan_threshold: 1,
+                    max_indexing_threads: 2,
+                    on_disk: Some(false),
+                    payload_m: None,
+                    appendable: Some(true),
+                }),
+                quantization_config: None,
+                multivec_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    assert!(segment.is_appendable());
+
+    for idx in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(1, idx.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+    // Re-link some points with new vectors and delete some others
+    for idx in 0..num_updated_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(2, idx.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+    for idx in num_updated_vectors..num_updated_vectors + num_deleted_vectors {
+        segment.delete_point(3, idx.into()).unwrap();
+    }
+
+    // Points which are not linked yet are found by search as well
+    check_recall(&segment, &mut rnd, dim);
+
+    segment.link_pending_points(&stopped).unwrap();
+    {
+        let vector_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_index
+            .borrow();
+        assert!(matches!(&*vector_index, VectorIndexEnum::HnswRam(_)));
+        assert_eq!(vector_index.indexed_vector_count(), num_vectors as usize);
+    }
+    check_recall(&segment, &mut rnd, dim);
+
+    // Graph is not persisted, it must be rebuilt on load
+    segment.flush(true).unwrap();
+    let path = segment.current_path.clone();
+    drop(segment);
+
+    let segment = load_segment(&path, &stopped).unwrap().unwrap();
+    assert_eq!(
+        segment.available_point_count(),
+        (num_vectors - num_deleted_vectors) as usize,
+    );
+    {
+        let vector_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_index
+            .borrow();
+        assert_eq!(
+            vector_index.indexed_vector_count(),
+            (num_vectors - num_deleted_vectors) as usize,
+        );
+    }
+    check_recall(&segment, &mut rnd, dim);
+}
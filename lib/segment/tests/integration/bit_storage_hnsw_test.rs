@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::CpuPermit;
+use rand::prelude::StdRng;
+use rand::{Rng, SeedableRng};
+use rstest::rstest;
+use segment::data_types::vectors::{
+    only_default_vector, DenseVector, QueryVector, DEFAULT_VECTOR_NAME,
+};
+use segment::entry::entry_point::SegmentEntry;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::HNSWIndex;
+use segment::index::hnsw_index::num_rayon_threads;
+use segment::index::VectorIndex;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, HnswConfig, Indexes, SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageDatatype, VectorStorageType,
+};
+use segment::vector_storage::VectorStorageEnum;
+use tempfile::Builder;
+
+fn random_bit_vector<R: Rng + ?Sized>(rnd: &mut R, dim: usize) -> DenseVector {
+    (0..dim)
+        .map(|_| if rnd.gen_bool(0.5) { 1.0 } else { 0.0 })
+        .collect()
+}
+
+#[rstest]
+#[case::hamming(Distance::Hamming, 10)]
+#[case::jaccard(Distance::Jaccard, 10)]
+fn test_bit_storage_hnsw(#[case] distance: Distance, #[case] max_failures: usize) {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 128;
+    let m = 16;
+    let num_vectors: u64 = 2_000;
+    let ef = 64;
+    let ef_construct = 64;
+    let full_scan_threshold = 0;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir_bit").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir_bit").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivec_config: None,
+                datatype: Some(VectorStorageDatatype::Bit),
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    // check that `segment` uses bit storage
+    {
+        let borrowed_storage = segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .borrow();
+        let raw_storage: &VectorStorageEnum = &borrowed_storage;
+        assert!(matches!(raw_storage, &VectorStorageEnum::DenseSimpleBit(_)));
+    }
+
+    let mut vectors = Vec::new();
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_bit_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, idx, only_default_vector(&vector))
+            .unwrap();
+        vectors.push(vector);
+    }
+
+    // packed vectors are restored exactly
+    let stored_vector = segment.vector(DEFAULT_VECTOR_NAME, 7.into()).unwrap();
+    assert_eq!(stored_vector, Some(vectors[7].clone().into()));
+
+    let hnsw_config = HnswConfig {
+        m,
+        ef_construct,
+        full_scan_threshold,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        appendable: None,
+    };
+
+    let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+    let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
+        hnsw_dir.path(),
+        segment.id_tracker.clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .clone(),
+        segment.payload_index.clone(),
+        hnsw_config,
+    )
+    .unwrap();
+
+    hnsw_index.build_index(permit, &stopped).unwrap();
+
+    let top = 5;
+    let mut hits = 0;
+    let attempts = 100;
+    for _ in 0..attempts {
+        let query: QueryVector = random_bit_vector(&mut rnd, dim).into();
+
+        let index_result = hnsw_index
+            .search(
+                &[&query],
+                None,
+                top,
+                Some(&SearchParams {
+                    hnsw_ef: Some(ef),
+                    ..Default::default()
+                }),
+                &Default::default(),
+            )
+            .unwrap();
+
+        let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_index
+            .borrow()
+            .search(&[&query], None, top, None, &Default::default())
+            .unwrap();
+
+        // compare scores, as ties between equally distant points are resolved arbitrarily
+        let index_scores: Vec<_> = index_result[0].iter().map(|p| p.score).collect();
+        let plain_scores: Vec<_> = plain_result[0].iter().map(|p| p.score).collect();
+        if index_scores == plain_scores {
+            hits += 1;
+        }
+    }
+    assert!(
+        attempts - hits <= max_failures,
+        "hits: {hits} of {attempts}"
+    );
+}
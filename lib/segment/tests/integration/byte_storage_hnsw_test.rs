@@ -84,6 +84,7 @@ fn compare_search_result(result_a: &[Vec<ScoredPointOffset>], result_b: &[Vec<Sc
 #[rstest]
 #[case::nearest(QueryVariant::Nearest, VectorStorageDatatype::Uint8, 32, 10)]
 #[case::nearest(QueryVariant::Nearest, VectorStorageDatatype::Float16, 32, 10)]
+#[case::nearest(QueryVariant::Nearest, VectorStorageDatatype::Bfloat16, 32, 10)]
 #[case::discovery(QueryVariant::Discovery, VectorStorageDatatype::Uint8, 128, 20)]
 #[case::recommend(
     QueryVariant::RecommendBestScore,
@@ -165,6 +166,7 @@ fn test_byte_storage_hnsw(
         assert!(
             matches!(raw_storage, &VectorStorageEnum::DenseSimpleByte(_))
                 | matches!(raw_storage, &VectorStorageEnum::DenseSimpleHalf(_))
+                | matches!(raw_storage, &VectorStorageEnum::DenseSimpleBfloat16(_))
         );
     }
 
@@ -207,6 +209,7 @@ fn test_byte_storage_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -51,8 +51,8 @@ where
     R: Rng + ?Sized,
 {
     match data_type {
-        VectorStorageDatatype::Float32 => unreachable!(),
-        VectorStorageDatatype::Float16 => {
+        VectorStorageDatatype::Float32 | VectorStorageDatatype::Bit => unreachable!(),
+        VectorStorageDatatype::Float16 | VectorStorageDatatype::Bfloat16 => {
             let mut vector = segment::fixtures::payload_fixtures::random_vector(rnd_gen, dim);
             vector.iter_mut().for_each(|x| *x -= 0.5);
             vector
@@ -192,6 +192,15 @@ fn sames_count(a: &[Vec<ScoredPointOffset>], b: &[Vec<ScoredPointOffset>]) -> us
     32, // ef
     80., // min_acc out of 100
 )]
+#[case::nearest_scalar_dot(
+    QueryVariant::Nearest,
+    VectorStorageDatatype::Bfloat16,
+    QuantizationVariant::Scalar,
+    Distance::Dot,
+    32, // dim
+    32, // ef
+    80., // min_acc out of 100
+)]
 #[case::nearest_scalar_dot(
     QueryVariant::Nearest,
     VectorStorageDatatype::Uint8,
@@ -271,6 +280,7 @@ fn test_byte_storage_binary_quantization_hnsw(
         assert!(
             matches!(raw_storage, &VectorStorageEnum::DenseSimpleByte(_))
                 | matches!(raw_storage, &VectorStorageEnum::DenseSimpleHalf(_))
+                | matches!(raw_storage, &VectorStorageEnum::DenseSimpleBfloat16(_))
         );
     }
 
@@ -307,7 +317,12 @@ fn test_byte_storage_binary_quantization_hnsw(
             always_ram: None,
         }
         .into(),
-        QuantizationVariant::Binary => BinaryQuantizationConfig { always_ram: None }.into(),
+        QuantizationVariant::Binary => BinaryQuantizationConfig {
+            always_ram: None,
+            encoding: None,
+            query_encoding: None,
+        }
+        .into(),
     };
 
     segment_byte
@@ -333,6 +348,7 @@ fn test_byte_storage_binary_quantization_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -21,9 +21,11 @@ use segment::segment_constructor::build_segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
 use segment::types::PayloadSchemaType::Keyword;
 use segment::types::{
+    BinaryQuantizationConfig, BinaryQuantizationEncoding, BinaryQuantizationQueryEncoding,
     CompressionRatio, Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, Payload,
     ProductQuantizationConfig, QuantizationConfig, QuantizationSearchParams,
-    ScalarQuantizationConfig, SearchParams, SegmentConfig, VectorDataConfig, VectorStorageType,
+    ScalarQuantizationConfig, ScalarType, SearchParams, SegmentConfig, VectorDataConfig,
+    VectorStorageType,
 };
 use segment::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use serde_json::json;
@@ -124,6 +126,7 @@ fn hnsw_quantized_search_test(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -356,6 +359,34 @@ fn hnsw_quantized_search_manhattan_test() {
     );
 }
 
+#[test]
+fn hnsw_int4_quantized_search_euclid_test() {
+    hnsw_quantized_search_test(
+        Distance::Euclid,
+        5003,
+        ScalarQuantizationConfig {
+            r#type: ScalarType::Int4,
+            quantile: Some(0.99),
+            always_ram: None,
+        }
+        .into(),
+    );
+}
+
+#[test]
+fn hnsw_float8_quantized_search_dot_test() {
+    hnsw_quantized_search_test(
+        Distance::Dot,
+        5003,
+        ScalarQuantizationConfig {
+            r#type: ScalarType::Float8,
+            quantile: None,
+            always_ram: Some(true),
+        }
+        .into(),
+    );
+}
+
 #[test]
 fn hnsw_product_quantization_cosine_test() {
     hnsw_quantized_search_test(
@@ -395,6 +426,34 @@ fn hnsw_product_quantization_manhattan_test() {
     );
 }
 
+#[test]
+fn hnsw_binary_two_bits_quantization_cosine_test() {
+    hnsw_quantized_search_test(
+        Distance::Cosine,
+        1003,
+        BinaryQuantizationConfig {
+            always_ram: None,
+            encoding: Some(BinaryQuantizationEncoding::TwoBits),
+            query_encoding: Some(BinaryQuantizationQueryEncoding::Scalar8Bits),
+        }
+        .into(),
+    );
+}
+
+#[test]
+fn hnsw_binary_one_and_half_bits_quantization_euclid_test() {
+    hnsw_quantized_search_test(
+        Distance::Euclid,
+        1003,
+        BinaryQuantizationConfig {
+            always_ram: Some(true),
+            encoding: Some(BinaryQuantizationEncoding::OneAndHalfBits),
+            query_encoding: Some(BinaryQuantizationQueryEncoding::Scalar4Bits),
+        }
+        .into(),
+    );
+}
+
 #[test]
 fn test_build_hnsw_using_quantization() {
     let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -420,6 +479,7 @@ fn test_build_hnsw_using_quantization() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     });
 
     let permit_cpu_count = num_rayon_threads(0);
@@ -1,6 +1,8 @@
 #![cfg(test)]
 
+pub mod appendable_hnsw_test;
 pub mod batch_search_test;
+mod bit_storage_hnsw_test;
 mod byte_storage_hnsw_test;
 pub mod byte_storage_quantization_test;
 pub mod disbalanced_vectors_test;
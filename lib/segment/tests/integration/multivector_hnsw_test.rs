@@ -94,6 +94,7 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
             Distance::Manhattan => {
                 <ManhattanMetric as Metric<VectorElementType>>::preprocess(vector.clone())
             }
+            Distance::Hamming | Distance::Jaccard => unreachable!(),
         };
         let vector_multi = MultiDenseVector::new(preprocessed_vector, vector.len());
 
@@ -131,6 +132,7 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     // single threaded mode to guarantee equivalency between single and multi hnsw
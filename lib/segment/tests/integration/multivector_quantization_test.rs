@@ -274,6 +274,8 @@ fn test_multivector_quantization_hnsw(
         .into(),
         QuantizationVariant::Binary => BinaryQuantizationConfig {
             always_ram: Some(false),
+            encoding: None,
+            query_encoding: None,
         }
         .into(),
     };
@@ -304,6 +306,7 @@ fn test_multivector_quantization_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -9,6 +9,7 @@ use indexmap::IndexSet;
 use itertools::Itertools;
 use rand::prelude::StdRng;
 use rand::{Rng, SeedableRng};
+use segment::data_types::index::{KeywordIndexParams, KeywordIndexType};
 use segment::data_types::integer_index::{IntegerIndexParams, IntegerIndexType};
 use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
@@ -21,7 +22,7 @@ use segment::fixtures::payload_fixtures::{
 use segment::index::field_index::{FieldIndex, PrimaryCondition};
 use segment::index::struct_payload_index::StructPayloadIndex;
 use segment::index::PayloadIndex;
-use segment::json_path::path;
+use segment::json_path::{path, JsonPath};
 use segment::payload_storage::in_memory_payload_storage::InMemoryPayloadStorage;
 use segment::payload_storage::PayloadStorage;
 use segment::segment::Segment;
@@ -31,7 +32,7 @@ use segment::types::PayloadSchemaType::{Integer, Keyword};
 use segment::types::{
     AnyVariants, Condition, Distance, FieldCondition, Filter, GeoBoundingBox, GeoLineString,
     GeoPoint, GeoPolygon, GeoRadius, Indexes, IsEmptyCondition, Match, Payload, PayloadField,
-    PayloadSchemaParams, PayloadSchemaType, Range, SegmentConfig, VectorDataConfig,
+    PayloadSchemaParams, PayloadSchemaType, Range, SegmentConfig, ValueVariants, VectorDataConfig,
     VectorStorageType, WithPayload,
 };
 use segment::utils::scored_point_ties::ScoredPointTies;
@@ -110,6 +111,7 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                     r#type: IntegerIndexType::Integer,
                     lookup: true,
                     range: false,
+                    on_disk: None,
                 },
             ))),
         )
@@ -123,6 +125,7 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                     r#type: IntegerIndexType::Integer,
                     lookup: false,
                     range: true,
+                    on_disk: None,
                 },
             ))),
         )
@@ -919,3 +922,246 @@ fn test_any_matcher_cardinality_estimation() {
     assert!(exact <= estimation.max);
     assert!(exact >= estimation.min);
 }
+
+#[test]
+fn test_all_matcher() {
+    // Compare `all` match over keyword and integer indexes with plain payload checks
+    let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
+    let dir2 = Builder::new().prefix("segment2_dir").tempdir().unwrap();
+
+    let (struct_segment, plain_segment) = build_test_segments(dir1.path(), dir2.path());
+
+    let point_ids = plain_segment.iter_points().take(50).collect_vec();
+    for point_id in point_ids {
+        let payload = plain_segment.payload(point_id).unwrap();
+        for key in [STR_KEY, INT_KEY] {
+            // Require the first two values of the point, so that the point itself must match
+            let values = match payload.0.get(key) {
+                Some(serde_json::Value::Array(values)) => values.iter().take(2).cloned().collect(),
+                Some(value) => vec![value.clone()],
+                None => continue,
+            };
+            let all: AnyVariants =
+                serde_json::from_value(serde_json::Value::Array(values)).unwrap();
+            let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+                path(key),
+                Match::new_all(all),
+            )));
+
+            let plain_result = plain_segment.read_filtered(None, None, Some(&filter));
+            let struct_result = struct_segment.read_filtered(None, None, Some(&filter));
+
+            assert!(plain_result.contains(&point_id), "{filter:?}");
+            assert_eq!(plain_result, struct_result, "{filter:?}");
+
+            let estimation = struct_segment
+                .payload_index
+                .borrow()
+                .estimate_cardinality(&filter);
+            assert!(estimation.min <= struct_result.len(), "{estimation:#?}");
+            assert!(struct_result.len() <= estimation.max, "{estimation:#?}");
+        }
+    }
+}
+
+#[test]
+fn test_uuid_index_matches_plain_payload() {
+    // Compare conditions over a uuid index with plain payload checks,
+    // when the payload has other forms of UUIDs and strings which are not UUIDs
+    let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
+    let dir2 = Builder::new().prefix("segment2_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: DIM,
+                distance: Distance::Dot,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivec_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut plain_segment = build_segment(dir1.path(), &config, true).unwrap();
+    let mut struct_segment = build_segment(dir2.path(), &config, true).unwrap();
+
+    let uuid_key = "uuid";
+    let uuid = "550e8400-e29b-41d4-a716-446655440000";
+    let other_uuid = "c0ffee00-0000-4000-8000-000000000001";
+    let uppercase_uuid = uuid.to_uppercase();
+    let simple_uuid = uuid.replace('-', "");
+
+    let values = [
+        json!(uuid),
+        json!(other_uuid),
+        json!(uppercase_uuid),
+        json!(simple_uuid),
+        json!("not-a-uuid"),
+        json!([uuid, "not-a-uuid"]),
+        json!([other_uuid, uppercase_uuid]),
+        json!(42),
+    ];
+
+    let mut rnd = StdRng::seed_from_u64(42);
+    for (idx, value) in values.iter().enumerate() {
+        let point_id = (idx as u64).into();
+        let vector = random_vector(&mut rnd, DIM);
+        let payload: Payload = json!({ uuid_key: value }).into();
+        for segment in [&mut plain_segment, &mut struct_segment] {
+            segment
+                .upsert_point(idx as u64, point_id, only_default_vector(&vector))
+                .unwrap();
+            segment
+                .set_full_payload(idx as u64, point_id, &payload)
+                .unwrap();
+        }
+    }
+
+    struct_segment
+        .create_field_index(
+            values.len() as u64,
+            &path(uuid_key),
+            Some(&PayloadSchemaType::Uuid.into()),
+        )
+        .unwrap();
+
+    let keywords = |keywords: &[&str]| -> IndexSet<String, FnvBuildHasher> {
+        keywords.iter().map(|keyword| keyword.to_string()).collect()
+    };
+
+    let matches = [
+        Match::new_value(ValueVariants::Keyword(uuid.to_string())),
+        Match::new_value(ValueVariants::Keyword(uppercase_uuid.clone())),
+        Match::new_value(ValueVariants::Keyword(simple_uuid.clone())),
+        Match::new_value(ValueVariants::Keyword("not-a-uuid".to_string())),
+        Match::new_any(AnyVariants::Keywords(keywords(&[uuid, other_uuid]))),
+        Match::new_any(AnyVariants::Keywords(keywords(&[other_uuid, "not-a-uuid"]))),
+        Match::new_except(AnyVariants::Keywords(keywords(&[uuid]))),
+        Match::new_except(AnyVariants::Keywords(keywords(&[uuid, "not-a-uuid"]))),
+        Match::new_all(AnyVariants::Keywords(keywords(&[uuid, "not-a-uuid"]))),
+        Match::new_all(AnyVariants::Keywords(keywords(&[other_uuid]))),
+    ];
+
+    for r#match in matches {
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            path(uuid_key),
+            r#match,
+        )));
+
+        let plain_result = plain_segment.read_filtered(None, None, Some(&filter));
+        let struct_result = struct_segment.read_filtered(None, None, Some(&filter));
+        assert_eq!(plain_result, struct_result, "{filter:?}");
+
+        let estimation = struct_segment
+            .payload_index
+            .borrow()
+            .estimate_cardinality(&filter);
+        assert!(estimation.min <= struct_result.len(), "{estimation:#?}");
+        assert!(struct_result.len() <= estimation.max, "{estimation:#?}");
+    }
+}
+
+#[test]
+fn test_on_disk_payload_index() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let mut payload_storage = InMemoryPayloadStorage::default();
+
+    let point_num = 10;
+    for i in 0..point_num {
+        let payload: Payload = json!({
+            "number": i,
+            "keyword": if i % 2 == 0 { "even" } else { "odd" },
+        })
+        .into();
+        payload_storage
+            .assign(i as PointOffsetType, &payload)
+            .unwrap();
+    }
+
+    let wrapped_payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
+    let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(point_num)));
+
+    let number: JsonPath = path("number");
+    let keyword: JsonPath = path("keyword");
+    let number_schema = FieldParams(PayloadSchemaParams::Integer(IntegerIndexParams {
+        r#type: IntegerIndexType::Integer,
+        lookup: true,
+        range: true,
+        on_disk: Some(true),
+    }));
+    let keyword_schema = FieldParams(PayloadSchemaParams::Keyword(KeywordIndexParams {
+        r#type: KeywordIndexType::Keyword,
+        on_disk: Some(true),
+    }));
+
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_range(
+        number.clone(),
+        Range {
+            lt: None,
+            gt: None,
+            gte: Some(3.0),
+            lte: Some(6.0),
+        },
+    )))
+    .merge(&Filter::new_must(Condition::Field(
+        FieldCondition::new_match(
+            keyword.clone(),
+            Match::new_value(ValueVariants::Keyword("even".to_owned())),
+        ),
+    )));
+
+    let index_files = {
+        let mut index = StructPayloadIndex::open(
+            wrapped_payload_storage.clone(),
+            id_tracker.clone(),
+            dir.path(),
+            false,
+        )
+        .unwrap();
+        index.set_indexed(&number, number_schema).unwrap();
+        index.set_indexed(&keyword, keyword_schema).unwrap();
+        index.flusher()().unwrap();
+
+        assert!(index.files().len() > 1);
+        assert!(index.files().iter().all(|file| file.exists()));
+        assert_eq!(
+            index
+                .query_points(&filter)
+                .into_iter()
+                .sorted()
+                .collect_vec(),
+            vec![4, 6]
+        );
+        index.files().into_iter().sorted().collect_vec()
+    };
+
+    let mut index =
+        StructPayloadIndex::open(wrapped_payload_storage, id_tracker, dir.path(), false).unwrap();
+    assert_eq!(
+        index.files().into_iter().sorted().collect_vec(),
+        index_files
+    );
+    assert_eq!(
+        index
+            .query_points(&filter)
+            .into_iter()
+            .sorted()
+            .collect_vec(),
+        vec![4, 6]
+    );
+
+    index.drop_index(&number).unwrap();
+    index.drop_index(&keyword).unwrap();
+    let config_files = index.files();
+    assert_eq!(config_files.len(), 1);
+    assert!(index_files
+        .iter()
+        .filter(|file| !config_files.contains(file))
+        .all(|file| !file.exists()));
+}
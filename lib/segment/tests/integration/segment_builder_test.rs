@@ -1,18 +1,27 @@
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use common::cpu::CpuPermit;
 use itertools::Itertools;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
 use segment::common::operation_error::OperationError;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{only_default_vector, VectorRef, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::index_fixtures::random_vector;
 use segment::index::hnsw_index::num_rayon_threads;
+use segment::index::VectorIndexEnum;
 use segment::segment::Segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
-use segment::types::{Indexes, SegmentConfig, VectorDataConfig, VectorStorageType};
+use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
+use segment::types::{
+    Distance, HnswConfig, Indexes, PointIdType, SearchParams, SegmentConfig, VectorDataConfig,
+    VectorStorageType, WithPayload,
+};
 use sparse::common::sparse_vector::SparseVector;
 use tempfile::Builder;
 
@@ -143,6 +152,247 @@ fn test_building_new_sparse_segment() {
     assert_eq!(merged_segment.point_version(3.into()), Some(100));
 }
 
+#[test]
+fn test_building_segment_reusing_hnsw_graph() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dim = 16;
+    let num_vectors: u64 = 2000;
+    let num_new_vectors: u64 = 100;
+    let num_updated_vectors: u64 = 50;
+
+    let mut plain_segment = build_simple_segment(dir.path(), dim, Distance::Cosine).unwrap();
+    for idx in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        plain_segment
+            .upsert_point(1, idx.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let config = hnsw_segment_config(&plain_segment);
+    let indexed_segment =
+        build_segment_from(dir.path(), temp_dir.path(), &config, &[&plain_segment]);
+    let old_levels = hnsw_point_levels(&indexed_segment);
+
+    // New points and a few updates of already indexed points
+    let mut new_segment = build_simple_segment(dir.path(), dim, Distance::Cosine).unwrap();
+    for idx in 0..num_updated_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        new_segment
+            .upsert_point(2, idx.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+    for idx in num_vectors..num_vectors + num_new_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        new_segment
+            .upsert_point(2, idx.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let merged_segment = build_segment_from(
+        dir.path(),
+        temp_dir.path(),
+        &config,
+        &[&indexed_segment, &new_segment],
+    );
+
+    assert_eq!(
+        merged_segment.available_point_count(),
+        (num_vectors + num_new_vectors) as usize,
+    );
+    assert_eq!(merged_segment.point_version(0.into()), Some(2));
+    assert!(matches!(
+        &*merged_segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_index
+            .borrow(),
+        VectorIndexEnum::HnswRam(_),
+    ));
+
+    // Unchanged points are taken from the old graph together with their levels,
+    // while levels of the new graph would be drawn at random
+    let merged_levels = hnsw_point_levels(&merged_segment);
+    for idx in num_updated_vectors..num_vectors {
+        let point_id = PointIdType::from(idx);
+        assert_eq!(merged_levels[&point_id], old_levels[&point_id]);
+    }
+
+    let top = 10;
+    let attempts = 20;
+    let mut hits = 0;
+    for _ in 0..attempts {
+        let query = random_vector(&mut rnd, dim).into();
+
+        let index_result = merged_segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query,
+                &WithPayload::default(),
+                &false.into(),
+                None,
+                top,
+                None,
+            )
+            .unwrap();
+        let exact_result = merged_segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query,
+                &WithPayload::default(),
+                &false.into(),
+                None,
+                top,
+                Some(&SearchParams {
+                    exact: true,
+                    ..Default::default()
+                }),
+            )
+            .unwrap();
+
+        hits += index_result
+            .iter()
+            .filter(|found| exact_result.iter().any(|exact| exact.id == found.id))
+            .count();
+    }
+
+    assert!(
+        hits as f64 / (top * attempts) as f64 > 0.9,
+        "recall is too low: {hits} of {}",
+        top * attempts,
+    );
+}
+
+#[test]
+fn test_building_segment_not_reusing_outdated_hnsw_graph() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dim = 16;
+    let num_vectors: u64 = 1000;
+
+    let mut plain_segment = build_simple_segment(dir.path(), dim, Distance::Cosine).unwrap();
+    let vectors: Vec<_> = (0..num_vectors)
+        .map(|_| random_vector(&mut rnd, dim))
+        .collect();
+    for (idx, vector) in vectors.iter().enumerate() {
+        plain_segment
+            .upsert_point(1, (idx as u64).into(), only_default_vector(vector))
+            .unwrap();
+    }
+
+    let config = hnsw_segment_config(&plain_segment);
+    let mut indexed_segment =
+        build_segment_from(dir.path(), temp_dir.path(), &config, &[&plain_segment]);
+    let old_levels = hnsw_point_levels(&indexed_segment);
+
+    let keeps_old_levels = |segment: &Segment| {
+        hnsw_point_levels(segment)
+            .into_iter()
+            .all(|(point_id, level)| old_levels[&point_id] == level)
+    };
+
+    // Same vectors with new versions, none of the old links can be trusted
+    let mut updated_segment = build_simple_segment(dir.path(), dim, Distance::Cosine).unwrap();
+    for (idx, vector) in vectors.iter().enumerate() {
+        updated_segment
+            .upsert_point(2, (idx as u64).into(), only_default_vector(vector))
+            .unwrap();
+    }
+    let rebuilt_segment = build_segment_from(
+        dir.path(),
+        temp_dir.path(),
+        &config,
+        &[&indexed_segment, &updated_segment],
+    );
+    assert_eq!(
+        rebuilt_segment.available_point_count(),
+        num_vectors as usize
+    );
+    assert!(!keeps_old_levels(&rebuilt_segment));
+
+    // 10% of points are deleted, the old graph is still reused
+    for idx in 0..num_vectors / 10 {
+        indexed_segment.delete_point(3, idx.into()).unwrap();
+    }
+    let reused_segment =
+        build_segment_from(dir.path(), temp_dir.path(), &config, &[&indexed_segment]);
+    assert_eq!(
+        reused_segment.available_point_count(),
+        (num_vectors - num_vectors / 10) as usize,
+    );
+    assert!(keeps_old_levels(&reused_segment));
+
+    // 30% of points are deleted, which is more than allowed for the old graph to be reused
+    for idx in num_vectors / 10..num_vectors * 3 / 10 {
+        indexed_segment.delete_point(3, idx.into()).unwrap();
+    }
+    let rebuilt_segment =
+        build_segment_from(dir.path(), temp_dir.path(), &config, &[&indexed_segment]);
+    assert_eq!(
+        rebuilt_segment.available_point_count(),
+        (num_vectors - num_vectors * 3 / 10) as usize,
+    );
+    assert!(!keeps_old_levels(&rebuilt_segment));
+}
+
+/// Config of the segment, with the default vector indexed by HNSW in RAM
+fn hnsw_segment_config(segment: &Segment) -> SegmentConfig {
+    let mut config = segment.segment_config.clone();
+    config
+        .vector_data
+        .get_mut(DEFAULT_VECTOR_NAME)
+        .unwrap()
+        .index = Indexes::Hnsw(HnswConfig {
+        m: 16,
+        ef_construct: 64,
+        full_scan_threshold: 1,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        appendable: None,
+    });
+    config
+}
+
+fn build_segment_from(
+    dir: &Path,
+    temp_dir: &Path,
+    config: &SegmentConfig,
+    segments: &[&Segment],
+) -> Segment {
+    let stopped = AtomicBool::new(false);
+    let mut builder = SegmentBuilder::new(dir, temp_dir, config).unwrap();
+    for segment in segments {
+        builder.update_from(segment, &stopped).unwrap();
+    }
+    let permit = CpuPermit::dummy(num_rayon_threads(0) as u32);
+    builder.build(permit, &stopped).unwrap()
+}
+
+/// Levels of all points in the HNSW graph of the default vector
+fn hnsw_point_levels(segment: &Segment) -> HashMap<PointIdType, usize> {
+    let id_tracker = segment.id_tracker.borrow();
+    let vector_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow();
+    let VectorIndexEnum::HnswRam(hnsw_index) = &*vector_index else {
+        panic!("default vector is expected to be indexed by HNSW in RAM");
+    };
+    let graph = hnsw_index.graph().unwrap();
+
+    segment
+        .iter_points()
+        .map(|point_id| {
+            let internal_id = id_tracker.internal_id(point_id).unwrap();
+            (point_id, graph.point_level(internal_id))
+        })
+        .collect()
+}
+
 fn estimate_build_time(segment: &Segment, stop_delay_millis: u64) -> (u64, bool) {
     let stopped = Arc::new(AtomicBool::new(false));
 
@@ -1,18 +1,30 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 use std::sync::atomic::AtomicBool;
 
+use geo::prelude::HaversineDistance;
+use geo::Point;
 use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use segment::common::operation_error::OperationError;
+use segment::data_types::facets::{FacetParams, FacetValue};
+use segment::data_types::integer_index::{IntegerIndexParams, IntegerIndexType};
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::order_by::{Direction, OrderBy, StartFrom};
 use segment::data_types::vectors::{
     only_default_vector, VectorRef, VectorStruct, DEFAULT_VECTOR_NAME,
 };
 use segment::entry::entry_point::SegmentEntry;
 use segment::fixtures::index_fixtures::random_vector;
+use segment::json_path::{path, JsonPath};
 use segment::segment_constructor::load_segment;
 use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
-use segment::types::{Condition, Distance, Filter, SearchParams, WithPayload};
+use segment::types::{
+    Condition, Distance, FieldCondition, Filter, GeoPoint, Payload, PayloadFieldSchema,
+    PayloadSchemaParams, PayloadSchemaType, PointIdType, SearchParams, WithPayload,
+};
+use serde_json::json;
 use tempfile::Builder;
 
 use crate::fixtures::segment::{build_segment_1, build_segment_3};
@@ -328,3 +340,209 @@ fn test_update_named_vector() {
     // check that nearests are the same
     assert_eq!(nearest_upsert.id, nearest_update.id);
 }
+
+#[test]
+fn test_facet_counts() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut segment = build_segment_1(dir.path());
+
+    let key: JsonPath = path("color");
+
+    let mut request = FacetParams {
+        key: key.clone(),
+        limit: 10,
+        filter: None,
+    };
+
+    let is_stopped = AtomicBool::new(false);
+
+    // Facets require an index on the key
+    let res = segment.facet(&request, &is_stopped);
+    assert!(matches!(res, Err(OperationError::ValidationError { .. })));
+
+    segment
+        .create_field_index(7, &key, Some(&PayloadSchemaType::Keyword.into()))
+        .unwrap();
+
+    let counts = segment.facet(&request, &is_stopped).unwrap();
+    assert_eq!(
+        counts,
+        HashMap::from([
+            (FacetValue::Keyword("red".to_string()), 4),
+            (FacetValue::Keyword("blue".to_string()), 3),
+        ]),
+    );
+
+    let ids: HashSet<_> = HashSet::from_iter([1.into(), 2.into(), 3.into()]);
+    request.filter = Some(Filter::new_must(Condition::HasId(ids.into())));
+
+    let counts = segment.facet(&request, &is_stopped).unwrap();
+    assert_eq!(
+        counts,
+        HashMap::from([
+            (FacetValue::Keyword("red".to_string()), 2),
+            (FacetValue::Keyword("blue".to_string()), 1),
+        ]),
+    );
+}
+
+#[test]
+fn test_facet_counts_repeated_values() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut segment = build_simple_segment(dir.path(), 4, Distance::Dot).unwrap();
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let payloads = [
+        json!({ "num": [1, 1] }),
+        json!({ "num": [1, 2] }),
+        json!({ "num": 2 }),
+        json!({ "num": [3, 3, 3] }),
+    ];
+    for (id, payload) in payloads.into_iter().enumerate() {
+        let id = id as u64;
+        segment
+            .upsert_point(
+                id,
+                id.into(),
+                only_default_vector(&random_vector(&mut rng, 4)),
+            )
+            .unwrap();
+        segment
+            .set_full_payload(id, id.into(), &payload.into())
+            .unwrap();
+    }
+
+    let key: JsonPath = path("num");
+    let all_ids: HashSet<_> = (0..4u64).map(PointIdType::from).collect();
+    let expected = HashMap::from([
+        (FacetValue::Int(1), 2),
+        (FacetValue::Int(2), 2),
+        (FacetValue::Int(3), 1),
+    ]);
+
+    let is_stopped = AtomicBool::new(false);
+
+    // Both the lookup and the range index are able to count values
+    for (op_num, (lookup, range)) in [(10, (true, false)), (20, (false, true))] {
+        let params = IntegerIndexParams {
+            r#type: IntegerIndexType::Integer,
+            lookup,
+            range,
+            on_disk: None,
+        };
+        segment
+            .create_field_index(
+                op_num,
+                &key,
+                Some(&PayloadFieldSchema::FieldParams(
+                    PayloadSchemaParams::Integer(params),
+                )),
+            )
+            .unwrap();
+
+        // Counts are taken from the index without a filter, and per point with it
+        for filter in [
+            None,
+            Some(Filter::new_must(Condition::HasId(all_ids.clone().into()))),
+        ] {
+            let request = FacetParams {
+                key: key.clone(),
+                limit: 10,
+                filter,
+            };
+            let counts = segment.facet(&request, &is_stopped).unwrap();
+            assert_eq!(counts, expected, "lookup: {lookup}, range: {range}");
+        }
+
+        segment.delete_field_index(op_num + 1, &key).unwrap();
+    }
+}
+
+#[test]
+fn test_order_by_geo_distance() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut segment = build_simple_segment(dir.path(), 4, Distance::Dot).unwrap();
+
+    let key: JsonPath = path("location");
+    let mut rng = StdRng::seed_from_u64(42);
+
+    // Half of the points are clustered around the origin, the rest are spread over the globe
+    let mut locations = HashMap::new();
+    for id in 0..1000u64 {
+        let (lon, lat): (f64, f64) = if id % 2 == 0 {
+            (rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0))
+        } else {
+            (rng.gen_range(-180.0..180.0), rng.gen_range(-85.0..85.0))
+        };
+        let payload: Payload = json!({
+            "location": { "lon": lon, "lat": lat },
+            "odd": id % 2 == 1,
+        })
+        .into();
+
+        segment
+            .upsert_point(
+                id,
+                id.into(),
+                only_default_vector(&random_vector(&mut rng, 4)),
+            )
+            .unwrap();
+        segment.set_full_payload(id, id.into(), &payload).unwrap();
+        locations.insert(id, Point::new(lon, lat));
+    }
+
+    segment
+        .create_field_index(1000, &key, Some(&PayloadSchemaType::Geo.into()))
+        .unwrap();
+
+    let origin = Point::new(0.5, 0.5);
+    let odd_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        path("odd"),
+        true.into(),
+    )));
+
+    for direction in [Direction::Asc, Direction::Desc] {
+        for filter in [None, Some(&odd_filter)] {
+            for start_from in [None, Some(StartFrom::Float(50_000.0))] {
+                let order_by = OrderBy {
+                    key: key.clone(),
+                    direction: Some(direction),
+                    start_from: start_from.clone(),
+                    geo_point: Some(GeoPoint { lon: 0.5, lat: 0.5 }),
+                };
+
+                let page = segment
+                    .filtered_read_by_geo_distance(&order_by, Some(10), filter)
+                    .unwrap();
+
+                // Same page, computed by a full scan
+                let start_from = start_from.map(|_| 50_000.0);
+                let mut expected = locations
+                    .iter()
+                    .filter(|(id, _)| filter.is_none() || *id % 2 == 1)
+                    .map(|(id, location)| (origin.haversine_distance(location), *id))
+                    .filter(|(distance, _)| match (direction, start_from) {
+                        (_, None) => true,
+                        (Direction::Asc, Some(start_from)) => *distance >= start_from,
+                        (Direction::Desc, Some(start_from)) => *distance <= start_from,
+                    })
+                    .collect_vec();
+                expected.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+                if direction == Direction::Desc {
+                    expected.reverse();
+                }
+
+                let page_ids = page.iter().map(|(_, id)| *id).collect_vec();
+                let expected_ids: Vec<PointIdType> = expected
+                    .iter()
+                    .take(10)
+                    .map(|(_, id)| (*id).into())
+                    .collect();
+                assert_eq!(page_ids, expected_ids, "{direction:?}, {filter:?}");
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use collection::config::{CollectionConfig, ShardingMethod};
+use collection::config::{CollectionConfig, ShardingMethod, StrictModeConfig};
 use collection::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
@@ -165,6 +165,17 @@ pub struct CreateCollection {
     /// Sparse vector data config.
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Time-to-live of points in seconds, counted from their last upsert.
+    /// Expired points are hidden from reads and deleted in background.
+    /// The expiration time is stored in the reserved `_expires_at` payload key, which may be set
+    /// explicitly to override it for individual points.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub ttl_sec: Option<u64>,
+    /// Strict-mode config.
+    #[serde(default)]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -211,6 +222,7 @@ pub struct UpdateCollection {
     #[serde(alias = "optimizer_config")]
     pub optimizers_config: Option<OptimizersConfigDiff>, // TODO: Allow updates for other configuration params as well
     /// Collection base params. If none - it is left unchanged.
+    #[validate]
     pub params: Option<CollectionParamsDiff>,
     /// HNSW parameters to update for the collection index. If none - it is left unchanged.
     #[validate]
@@ -222,6 +234,10 @@ pub struct UpdateCollection {
     /// Map of sparse vector data parameters to update for each sparse vector.
     #[validate]
     pub sparse_vectors: Option<SparseVectorsConfig>,
+    /// Strict-mode config to update. If none - it is left unchanged.
+    #[serde(default)]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
 }
 
 /// Operation for updating parameters of the existing collection
@@ -244,6 +260,7 @@ impl UpdateCollectionOperation {
                 optimizers_config: None,
                 quantization_config: None,
                 sparse_vectors: None,
+                strict_mode_config: None,
             },
             shard_replica_changes: None,
         }
@@ -403,6 +420,8 @@ impl From<CollectionConfig> for CreateCollection {
             init_from: None,
             quantization_config: value.quantization_config,
             sparse_vectors: value.params.sparse_vectors,
+            ttl_sec: value.params.ttl_sec,
+            strict_mode_config: value.strict_mode_config,
         }
     }
 }
@@ -22,6 +22,7 @@ pub fn error_to_status(error: StorageError) -> tonic::Status {
         StorageError::ChecksumMismatch { .. } => tonic::Code::DataLoss,
         StorageError::Forbidden { .. } => tonic::Code::PermissionDenied,
         StorageError::PreconditionFailed { .. } => tonic::Code::FailedPrecondition,
+        StorageError::OutOfRange { .. } => tonic::Code::OutOfRange,
     };
     tonic::Status::new(error_code, format!("{error}"))
 }
@@ -59,6 +60,8 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                     .sharding_method
                     .map(sharding_method_from_proto)
                     .transpose()?,
+                ttl_sec: value.ttl_sec,
+                strict_mode_config: value.strict_mode_config.map(Into::into),
             },
         )))
     }
@@ -88,6 +91,7 @@ impl TryFrom<api::grpc::qdrant::UpdateCollection> for CollectionMetaOperations {
                         config.map.into_iter().map(|(k, v)| (k, v.into())).collect(),
                     )
                 }),
+                strict_mode_config: value.strict_mode_config.map(Into::into),
             },
         )))
     }
@@ -32,6 +32,8 @@ pub enum StorageError {
     Forbidden { description: String },
     #[error("Pre-condition failure: {description}")]
     PreconditionFailed { description: String }, // system is not in the state to perform the operation
+    #[error("Out of range: {description}")]
+    OutOfRange { description: String },
 }
 
 impl StorageError {
@@ -131,6 +133,12 @@ impl StorageError {
                 description: overriding_description,
                 backtrace: None,
             },
+            CollectionError::OutOfRange { .. } => StorageError::OutOfRange {
+                description: overriding_description,
+            },
+            CollectionError::StrictMode { .. } => StorageError::Forbidden {
+                description: overriding_description,
+            },
         }
     }
 }
@@ -179,6 +187,10 @@ impl From<CollectionError> for StorageError {
                 description: format!("{err}"),
                 backtrace: None,
             },
+            CollectionError::OutOfRange { description } => StorageError::OutOfRange { description },
+            CollectionError::StrictMode { .. } => StorageError::Forbidden {
+                description: format!("{err}"),
+            },
         }
     }
 }
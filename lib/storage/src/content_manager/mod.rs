@@ -132,6 +132,7 @@ pub mod consensus_ops {
                     hnsw_config: None,
                     quantization_config: None,
                     sparse_vectors: None,
+                    strict_mode_config: None,
                 },
             );
             operation
@@ -126,6 +126,7 @@ impl TableOfContent {
             optimizers_config,
             quantization_config,
             sparse_vectors,
+            strict_mode_config,
         } = operation.update_collection;
         let collection = self
             .get_collection_unchecked(&operation.collection_name)
@@ -158,6 +159,9 @@ impl TableOfContent {
             collection.update_sparse_vectors_from_other(&diff).await?;
             recreate_optimizers = true;
         }
+        if let Some(diff) = strict_mode_config {
+            collection.update_strict_mode_config(diff).await?;
+        }
         if let Some(changes) = replica_changes {
             collection.handle_replica_changes(changes).await?;
         }
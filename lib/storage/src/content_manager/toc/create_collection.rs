@@ -44,6 +44,8 @@ impl TableOfContent {
             init_from,
             quantization_config,
             sparse_vectors,
+            ttl_sec,
+            strict_mode_config,
         } = operation;
 
         self.collections
@@ -150,6 +152,7 @@ impl TableOfContent {
                 },
             )?,
             read_fan_out_factor: None,
+            ttl_sec,
         };
         let wal_config = match wal_config_diff {
             None => self.storage_config.wal.clone(),
@@ -186,6 +189,7 @@ impl TableOfContent {
             optimizer_config: optimizers_config,
             hnsw_config,
             quantization_config,
+            strict_mode_config,
         };
         let collection = Collection::new(
             collection_name.to_string(),
@@ -217,6 +221,8 @@ impl TableOfContent {
         )
         .await?;
 
+        collection.ensure_expiration_index().await?;
+
         let local_shards = collection.get_local_shards().await;
 
         {
@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use collection::collection::distance_matrix::CollectionSearchMatrixResponse;
 use collection::collection::Collection;
 use collection::grouping::group_by::GroupRequest;
 use collection::grouping::GroupBy;
@@ -9,14 +10,16 @@ use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use collection::shards::shard::ShardId;
 use collection::{discovery, recommendations};
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt as _;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::types::{ScoredPoint, ShardKey};
 
 use super::TableOfContent;
 use crate::content_manager::errors::StorageError;
-use crate::rbac::Access;
+use crate::rbac::{Access, AccessRequirements};
 
 impl TableOfContent {
     /// Recommend points using positive and negative example from the request
@@ -41,6 +44,8 @@ impl TableOfContent {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request, timeout).await?;
+
         recommendations::recommend_by(
             request,
             &collection,
@@ -80,6 +85,10 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .check_strict_mode_batch(requests.iter().map(|(request, _)| request), timeout)
+            .await?;
+
         recommendations::recommend_batch_by(
             requests,
             &collection,
@@ -123,6 +132,10 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .check_strict_mode_batch(&request.searches, timeout)
+            .await?;
+
         collection
             .core_search_batch(request, read_consistency, shard_selection, timeout)
             .await
@@ -152,12 +165,78 @@ impl TableOfContent {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request, None).await?;
+
         collection
             .count(request, read_consistency, &shard_selection)
             .await
             .map_err(|err| err.into())
     }
 
+    /// Count points per value of the given payload key.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we count
+    /// * `request` - [`FacetParams`]
+    /// * `shard_selection` - which local shard to use
+    ///
+    /// # Result
+    ///
+    /// Most frequent values of the key, with their counts.
+    ///
+    pub async fn facet(
+        &self,
+        collection_name: &str,
+        mut request: FacetParams,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> Result<FacetResponse, StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request, timeout).await?;
+
+        collection
+            .facet(request, shard_selection, read_consistency, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Compute the distance matrix for a random sample of points.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we sample
+    /// * `request` - [`CollectionSearchMatrixRequest`]
+    /// * `shard_selection` - which local shard to use
+    ///
+    /// # Result
+    ///
+    /// Sampled point ids with the nearest neighbours of each of them within the sample.
+    ///
+    pub async fn search_points_matrix(
+        &self,
+        collection_name: &str,
+        mut request: CollectionSearchMatrixRequest,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> Result<CollectionSearchMatrixResponse, StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request, timeout).await?;
+
+        collection
+            .search_points_matrix(request, shard_selection, read_consistency, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Return specific points by IDs
     ///
     /// # Arguments
@@ -198,6 +277,7 @@ impl TableOfContent {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request, timeout).await?;
 
         let collection_by_name = |name| self.get_collection_opt(name);
 
@@ -225,6 +305,8 @@ impl TableOfContent {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request, timeout).await?;
+
         discovery::discover(
             request,
             &collection,
@@ -254,6 +336,9 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .check_strict_mode_batch(requests.iter().map(|(request, _)| request), timeout)
+            .await?;
 
         discovery::discover_batch(
             requests,
@@ -288,6 +373,8 @@ impl TableOfContent {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request, None).await?;
+
         collection
             .scroll_by(request, read_consistency, &shard_selection)
             .await
@@ -301,13 +388,15 @@ impl TableOfContent {
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         access: Access,
+        timeout: Option<Duration>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request, timeout).await?;
 
         collection
-            .query(request, read_consistency, &shard_selection)
+            .query(request, read_consistency, &shard_selection, timeout)
             .await
             .map_err(|err| err.into())
     }
@@ -406,6 +495,13 @@ impl TableOfContent {
             self.check_write_lock()?;
         }
 
+        // Only check the limits on the first node in the chain
+        if !shard_selector.is_shard_id() {
+            collection
+                .check_strict_mode_update(&operation.operation)
+                .await?;
+        }
+
         // TODO: `debug_assert(operation.clock_tag.is_none())` for `_update_shard_keys`/`update_from_client`!?
 
         let res = match shard_selector {
@@ -459,4 +555,56 @@ impl TableOfContent {
 
         Ok(res)
     }
+
+    /// Read up to `limit` operations from the WAL of a local shard, starting at operation `from`
+    ///
+    /// Requires access to the whole collection, as the operations are not filtered.
+    pub async fn read_wal_operations(
+        &self,
+        collection_name: &str,
+        shard_id: ShardId,
+        from: Option<u64>,
+        limit: usize,
+        access: &Access,
+    ) -> Result<Vec<(u64, CollectionUpdateOperations)>, StorageError> {
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        Ok(collection
+            .read_wal_operations(shard_id, from, limit)
+            .await?)
+    }
+
+    /// Estimate recall of the approximate search in local shards of the collection
+    ///
+    /// Requires access to the whole collection, as points of all shards are sampled.
+    pub async fn estimate_recall(
+        &self,
+        collection_name: &str,
+        request: RecallEstimationRequest,
+        access: &Access,
+        timeout: Option<Duration>,
+    ) -> Result<CollectionRecallEstimation, StorageError> {
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        Ok(collection.estimate_recall(request, timeout).await?)
+    }
+
+    /// Delete expired points from shards of the collection, which this peer leads updates of
+    ///
+    /// Requires access to the whole collection, as points of all shards may be deleted.
+    pub async fn delete_expired_points(
+        &self,
+        collection_name: &str,
+        access: &Access,
+    ) -> Result<usize, StorageError> {
+        let collection_pass = access
+            .check_collection_access(collection_name, AccessRequirements::new().write().whole())?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        Ok(collection.delete_expired_points(false).await?)
+    }
 }
@@ -7,15 +7,16 @@ use collection::lookup::WithLookup;
 use collection::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
 use collection::operations::point_ops::{PointIdsList, PointOperations};
 use collection::operations::types::{
-    ContextExamplePair, CoreSearchRequest, CountRequestInternal, DiscoverRequestInternal,
-    LookupLocation, PointRequestInternal, RecommendExample, RecommendRequestInternal,
-    ScrollRequestInternal,
+    CollectionSearchMatrixRequest, ContextExamplePair, CoreSearchRequest, CountRequestInternal,
+    DiscoverRequestInternal, LookupLocation, PointRequestInternal, RecommendExample,
+    RecommendRequestInternal, ScrollRequestInternal,
 };
 use collection::operations::universal_query::collection_query::{
     CollectionPrefetch, CollectionQueryRequest, Query, VectorInput, VectorQuery,
 };
 use collection::operations::vector_ops::VectorOperations;
 use collection::operations::CollectionUpdateOperations;
+use segment::data_types::facets::FacetParams;
 use segment::types::{Condition, ExtendedPointId, FieldCondition, Filter, Match, Payload};
 
 use super::{
@@ -241,6 +242,44 @@ impl CheckableCollectionOperation for CountRequestInternal {
     }
 }
 
+impl CheckableCollectionOperation for FacetParams {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
+impl CheckableCollectionOperation for CollectionSearchMatrixRequest {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
 impl CheckableCollectionOperation for GroupRequest {
     fn access_requirements(&self) -> AccessRequirements {
         AccessRequirements {
@@ -389,7 +428,7 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
     ) -> Result<(), StorageError> {
         match self {
             CollectionUpdateOperations::PointOperation(op) => match op {
-                PointOperations::UpsertPoints(_) => {
+                PointOperations::UpsertPoints(_) | PointOperations::UpsertPointsConditional(_) => {
                     view.check_whole_access()?;
                 }
                 PointOperations::DeletePoints { ids } => {
@@ -615,8 +654,9 @@ mod tests_ops {
     use api::rest::{BatchVectorStruct, OrderByInterface, RecommendStrategy, VectorStruct};
     use collection::operations::payload_ops::PayloadOpsDiscriminants;
     use collection::operations::point_ops::{
-        Batch, PointInsertOperationsInternal, PointInsertOperationsInternalDiscriminants,
-        PointOperationsDiscriminants, PointStruct, PointSyncOperation,
+        Batch, ConditionalInsertOperationInternal, PointInsertOperationsInternal,
+        PointInsertOperationsInternalDiscriminants, PointOperationsDiscriminants, PointStruct,
+        PointSyncOperation,
     };
     use collection::operations::query_enum::QueryEnum;
     use collection::operations::types::{SearchRequestInternal, UsingVector};
@@ -860,6 +900,65 @@ mod tests_ops {
         );
     }
 
+    #[test]
+    fn test_facet_params() {
+        let op = FacetParams {
+            key: "key".parse().unwrap(),
+            limit: 10,
+            filter: None,
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_allowed_rewrite(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+            |op| {
+                op.filter = Some(PayloadConstraint::new_test("col").to_filter());
+            },
+        );
+    }
+
+    #[test]
+    fn test_search_matrix_request() {
+        let op = CollectionSearchMatrixRequest {
+            sample_size: 10,
+            limit_per_sample: 3,
+            filter: None,
+            using: "".to_string(),
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_allowed_rewrite(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+            |op| {
+                op.filter = Some(PayloadConstraint::new_test("col").to_filter());
+            },
+        );
+    }
+
     #[test]
     fn test_group_request_source() {
         let op = GroupRequest {
@@ -1114,6 +1213,22 @@ mod tests_ops {
                 }
             }
 
+            PointOperationsDiscriminants::UpsertPointsConditional => {
+                let op = CollectionUpdateOperations::PointOperation(
+                    PointOperations::UpsertPointsConditional(
+                        ConditionalInsertOperationInternal::new(
+                            PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                                id: ExtendedPointId::NumId(12345),
+                                vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
+                                payload: None,
+                            }]),
+                            make_filter_from_ids(vec![ExtendedPointId::NumId(12345)]),
+                        ),
+                    ),
+                );
+                assert_requires_whole_write_access(&op);
+            }
+
             PointOperationsDiscriminants::DeletePoints => {
                 let op =
                     CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
@@ -9,7 +9,9 @@ use collection::operations::config_diff::OptimizersConfigDiff;
 use collection::operations::shared_storage_config::{
     SharedStorageConfig, DEFAULT_IO_SHARD_TRANSFER_LIMIT, DEFAULT_SNAPSHOTS_PATH,
 };
-use collection::operations::types::{NodeType, PeerMetadata};
+use collection::operations::types::{
+    NodeType, PeerMetadata, DEFAULT_RECALL_ESTIMATION_LIMIT, DEFAULT_RECALL_ESTIMATION_SAMPLE,
+};
 use collection::optimizers_builder::OptimizersConfig;
 use collection::shards::shard::PeerId;
 use collection::shards::transfer::ShardTransferMethod;
@@ -49,6 +51,38 @@ const fn default_io_shard_transfers_limit() -> Option<usize> {
     DEFAULT_IO_SHARD_TRANSFER_LIMIT
 }
 
+/// Configuration of the periodic recall estimation of the approximate search
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RecallEstimationConfig {
+    /// Interval between estimations in seconds. If not set, periodic estimation is disabled.
+    #[serde(default)]
+    pub interval_sec: Option<u64>,
+    /// How many points of each segment to use as queries
+    #[serde(default = "default_recall_estimation_sample")]
+    pub sample: usize,
+    /// How many nearest neighbours to compare
+    #[serde(default = "default_recall_estimation_limit")]
+    pub limit: usize,
+}
+
+impl Default for RecallEstimationConfig {
+    fn default() -> Self {
+        Self {
+            interval_sec: None,
+            sample: default_recall_estimation_sample(),
+            limit: default_recall_estimation_limit(),
+        }
+    }
+}
+
+const fn default_recall_estimation_sample() -> usize {
+    DEFAULT_RECALL_ESTIMATION_SAMPLE
+}
+
+const fn default_recall_estimation_limit() -> usize {
+    DEFAULT_RECALL_ESTIMATION_LIMIT
+}
+
 /// Global configuration of the storage, loaded on the service launch, default stored in ./config
 #[derive(Clone, Debug, Deserialize, Validate)]
 pub struct StorageConfig {
@@ -97,6 +131,9 @@ pub struct StorageConfig {
     /// Default values for collections.
     #[serde(default)]
     pub collection: Option<CollectionConfigDefaults>,
+    /// Periodic recall estimation of the approximate search.
+    #[serde(default)]
+    pub recall_estimation: RecallEstimationConfig,
 }
 
 impl StorageConfig {
@@ -68,6 +68,7 @@ fn test_alias_operation() {
         // update_concurrency: None,
         shard_transfer_method: None,
         collection: None,
+        recall_estimation: Default::default(),
     };
 
     let search_runtime = Runtime::new().unwrap();
@@ -112,6 +113,8 @@ fn test_alias_operation() {
                         init_from: None,
                         quantization_config: None,
                         sharding_method: None,
+                        ttl_sec: None,
+                        strict_mode_config: None,
                     },
                 )),
                 FULL_ACCESS.clone(),
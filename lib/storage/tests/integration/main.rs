@@ -1,2 +1,4 @@
 #[cfg(test)]
 pub mod alias_tests;
+#[cfg(test)]
+pub mod strict_mode_tests;
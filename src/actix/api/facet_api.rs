@@ -0,0 +1,48 @@
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path, Query};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::FacetRequest;
+use storage::dispatcher::Dispatcher;
+
+use super::read_params::ReadParams;
+use super::CollectionPath;
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers;
+
+#[post("/collections/{name}/facet")]
+async fn facet(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<FacetRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(async move {
+        let FacetRequest {
+            facet_request,
+            shard_key,
+        } = request.into_inner();
+
+        let shard_selection = match shard_key {
+            None => ShardSelectorInternal::All,
+            Some(shard_keys) => shard_keys.into(),
+        };
+
+        dispatcher
+            .toc(&access)
+            .facet(
+                &collection.name,
+                facet_request.into(),
+                shard_selection,
+                params.consistency,
+                access,
+                params.timeout(),
+            )
+            .await
+    })
+    .await
+}
+
+pub fn config_facet_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(facet);
+}
@@ -3,9 +3,11 @@ pub mod collections_api;
 pub mod count_api;
 pub mod debug_api;
 pub mod discovery_api;
+pub mod facet_api;
 pub mod issues_api;
 pub mod query_api;
 pub mod read_params;
+pub mod recall_api;
 pub mod recommend_api;
 pub mod retrieve_api;
 pub mod search_api;
@@ -0,0 +1,35 @@
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path, Query};
+use collection::operations::types::RecallEstimationRequest;
+use storage::dispatcher::Dispatcher;
+
+use super::collections_api::WaitTimeout;
+use super::CollectionPath;
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers;
+
+#[post("/collections/{name}/recall")]
+async fn estimate_recall(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<RecallEstimationRequest>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(async move {
+        dispatcher
+            .toc(&access)
+            .estimate_recall(
+                &collection.name,
+                request.into_inner(),
+                &access,
+                query.timeout(),
+            )
+            .await
+    })
+    .await
+}
+
+pub fn config_recall_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(estimate_recall);
+}
@@ -3,7 +3,8 @@ use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CoreSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
+    CoreSearchRequest, SearchGroupsRequest, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse,
+    SearchMatrixRequest, SearchRequest, SearchRequestBatch,
 };
 use itertools::Itertools;
 use storage::dispatcher::Dispatcher;
@@ -143,9 +144,83 @@ async fn search_point_groups(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/search/matrix/pairs")]
+async fn search_points_matrix_pairs(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchMatrixRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let SearchMatrixRequest {
+        search_matrix_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = dispatcher
+        .toc(&access)
+        .search_points_matrix(
+            &collection.name,
+            search_matrix_request.into(),
+            shard_selection,
+            params.consistency,
+            access,
+            params.timeout(),
+        )
+        .await
+        .map(SearchMatrixPairsResponse::from);
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/search/matrix/offsets")]
+async fn search_points_matrix_offsets(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchMatrixRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let SearchMatrixRequest {
+        search_matrix_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = dispatcher
+        .toc(&access)
+        .search_points_matrix(
+            &collection.name,
+            search_matrix_request.into(),
+            shard_selection,
+            params.consistency,
+            access,
+            params.timeout(),
+        )
+        .await
+        .map(SearchMatrixOffsetsResponse::from);
+
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
     cfg.service(search_points)
         .service(batch_search_points)
-        .service(search_point_groups);
+        .service(search_point_groups)
+        .service(search_points_matrix_pairs)
+        .service(search_points_matrix_offsets);
 }
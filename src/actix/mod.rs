@@ -26,8 +26,10 @@ use crate::actix::api::collections_api::config_collections_api;
 use crate::actix::api::count_api::count_points;
 use crate::actix::api::debug_api::config_debugger_api;
 use crate::actix::api::discovery_api::config_discovery_api;
+use crate::actix::api::facet_api::config_facet_api;
 use crate::actix::api::issues_api::config_issues_api;
 use crate::actix::api::query_api::config_query_api;
+use crate::actix::api::recall_api::config_recall_api;
 use crate::actix::api::recommend_api::config_recommend_api;
 use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
 use crate::actix::api::search_api::config_search_api;
@@ -171,6 +173,8 @@ pub fn init(
                 .configure(config_recommend_api)
                 .configure(config_discovery_api)
                 .configure(config_query_api)
+                .configure(config_facet_api)
+                .configure(config_recall_api)
                 .configure(config_shards_api)
                 .configure(config_issues_api)
                 .configure(config_debugger_api)
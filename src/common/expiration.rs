@@ -0,0 +1,32 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+
+const FULL_ACCESS: Access = Access::full("Expiration");
+
+/// Interval at which expired points are looked for
+///
+/// Expired points are hidden from reads right away, so this only affects how long they occupy
+/// storage.
+pub const EXPIRATION_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Periodically delete expired points from all collections with a time-to-live
+pub async fn run(toc: Arc<TableOfContent>, interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+
+        for collection_pass in toc.all_collections(&FULL_ACCESS).await {
+            let collection_name = collection_pass.name();
+            if let Err(err) = toc
+                .delete_expired_points(collection_name, &FULL_ACCESS)
+                .await
+            {
+                log::warn!(
+                    "Failed to delete expired points of collection {collection_name}: {err}"
+                );
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use collection::operations::types::SegmentRecallEstimation;
 use prometheus::proto::{Counter, Gauge, LabelPair, Metric, MetricFamily, MetricType};
 use prometheus::TextEncoder;
 use segment::common::operation_time_statistics::OperationDurationStatistics;
@@ -133,6 +134,27 @@ impl MetricsProvider for CollectionsTelemetry {
             MetricType::GAUGE,
             vec![gauge(vector_count as f64, &[])],
         ));
+
+        let mut recall_metrics = Vec::new();
+        for collection in self.collections.iter().flatten() {
+            match collection {
+                CollectionTelemetryEnum::Aggregated(a) => {
+                    recall_metrics.extend(a.recall.iter().map(|recall| recall_gauge(&a.id, recall)))
+                }
+                CollectionTelemetryEnum::Full(c) => recall_metrics.extend(
+                    c.recall_estimations()
+                        .map(|recall| recall_gauge(&c.id, recall)),
+                ),
+            }
+        }
+        if !recall_metrics.is_empty() {
+            metrics.push(metric_family(
+                "collection_recall",
+                "estimated recall of approximate search",
+                MetricType::GAUGE,
+                recall_metrics,
+            ));
+        }
     }
 }
 
@@ -383,6 +405,17 @@ fn gauge(value: f64, labels: &[(&str, &str)]) -> Metric {
     metric
 }
 
+fn recall_gauge(collection: &str, recall: &SegmentRecallEstimation) -> Metric {
+    gauge(
+        recall.recall,
+        &[
+            ("collection", collection),
+            ("vector", &recall.vector_name),
+            ("segment", &recall.segment),
+        ],
+    )
+}
+
 fn histogram(
     sample_count: u64,
     sample_sum: f64,
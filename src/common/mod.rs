@@ -2,6 +2,8 @@
 pub mod collections;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod error_reporting;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod expiration;
 #[allow(dead_code)]
 pub mod health;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
@@ -10,6 +12,8 @@ pub mod http_client;
 pub mod metrics;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod points;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod recall_estimation;
 pub mod snapshots;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod stacktrace;
@@ -8,8 +8,8 @@ use collection::operations::payload_ops::{
     DeletePayload, DeletePayloadOp, PayloadOps, SetPayload, SetPayloadOp,
 };
 use collection::operations::point_ops::{
-    FilterSelector, PointIdsList, PointInsertOperations, PointOperations, PointsSelector,
-    WriteOrdering,
+    ConditionalInsertOperationInternal, FilterSelector, PointIdsList, PointInsertOperations,
+    PointOperations, PointsSelector, WriteOrdering,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
@@ -169,9 +169,14 @@ pub async fn do_upsert_points(
     ordering: WriteOrdering,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
-    let (shard_key, operation) = operation.decompose();
-    let collection_operation =
-        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(operation));
+    let (shard_key, operation, update_filter) = operation.decompose();
+    let point_operation = match update_filter {
+        Some(condition) => PointOperations::UpsertPointsConditional(
+            ConditionalInsertOperationInternal::new(operation, condition),
+        ),
+        None => PointOperations::UpsertPoints(operation),
+    };
+    let collection_operation = CollectionUpdateOperations::PointOperation(point_operation);
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
 
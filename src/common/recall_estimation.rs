@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::operations::types::RecallEstimationRequest;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+use storage::types::RecallEstimationConfig;
+
+const FULL_ACCESS: Access = Access::full("Recall estimation");
+
+/// Periodically estimate recall of the approximate search in all collections
+///
+/// Estimations are kept in local shards and exposed through telemetry and metrics.
+pub async fn run(toc: Arc<TableOfContent>, interval: Duration, config: RecallEstimationConfig) {
+    let request = RecallEstimationRequest {
+        using: None,
+        sample: Some(config.sample),
+        limit: Some(config.limit),
+        params: None,
+    };
+
+    loop {
+        tokio::time::sleep(interval).await;
+
+        for collection_pass in toc.all_collections(&FULL_ACCESS).await {
+            let collection_name = collection_pass.name();
+            if let Err(err) = toc
+                .estimate_recall(collection_name, request.clone(), &FULL_ACCESS, None)
+                .await
+            {
+                log::warn!("Failed to estimate recall of collection {collection_name}: {err}");
+            }
+        }
+    }
+}
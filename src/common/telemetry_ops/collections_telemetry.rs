@@ -1,5 +1,5 @@
 use collection::config::CollectionParams;
-use collection::operations::types::OptimizersStatus;
+use collection::operations::types::{OptimizersStatus, SegmentRecallEstimation};
 use collection::telemetry::CollectionTelemetry;
 use common::types::{DetailsLevel, TelemetryDetail};
 use schemars::JsonSchema;
@@ -10,9 +10,12 @@ use storage::rbac::Access;
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
 pub struct CollectionsAggregatedTelemetry {
+    pub id: String,
     pub vectors: usize,
     pub optimizers_status: OptimizersStatus,
     pub params: CollectionParams,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recall: Vec<SegmentRecallEstimation>,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -38,10 +41,14 @@ impl From<CollectionTelemetry> for CollectionsAggregatedTelemetry {
             .max()
             .unwrap_or(OptimizersStatus::Ok);
 
+        let recall = telemetry.recall_estimations().cloned().collect();
+
         CollectionsAggregatedTelemetry {
             vectors: telemetry.count_vectors(),
             optimizers_status,
             params: telemetry.config.params,
+            recall,
+            id: telemetry.id,
         }
     }
 }
@@ -100,9 +107,11 @@ impl Anonymize for CollectionTelemetryEnum {
 impl Anonymize for CollectionsAggregatedTelemetry {
     fn anonymize(&self) -> Self {
         CollectionsAggregatedTelemetry {
+            id: self.id.anonymize(),
             optimizers_status: self.optimizers_status.clone(),
             vectors: self.vectors.anonymize(),
             params: self.params.anonymize(),
+            recall: self.recall.anonymize(),
         }
     }
 }
@@ -1345,6 +1345,8 @@ mod tests {
                             init_from: None,
                             quantization_config: None,
                             sharding_method: None,
+                            ttl_sec: None,
+                            strict_mode_config: None,
                         },
                     )),
                     Access::full("For test"),
@@ -46,6 +46,7 @@ use crate::common::helpers::{
 };
 use crate::common::telemetry::TelemetryCollector;
 use crate::common::telemetry_reporting::TelemetryReporter;
+use crate::common::{expiration, recall_estimation};
 use crate::greeting::welcome;
 use crate::migrations::single_to_cluster::handle_existing_collections;
 use crate::settings::Settings;
@@ -399,6 +400,30 @@ fn main() -> anyhow::Result<()> {
         log::info!("Telemetry reporting disabled");
     }
 
+    //
+    // Recall estimation
+    //
+
+    let recall_estimation_config = settings.storage.recall_estimation.clone();
+    if let Some(interval_sec) = recall_estimation_config.interval_sec {
+        log::info!("Recall estimation enabled, interval: {interval_sec} seconds");
+
+        runtime_handle.spawn(recall_estimation::run(
+            toc_arc.clone(),
+            Duration::from_secs(interval_sec),
+            recall_estimation_config,
+        ));
+    }
+
+    //
+    // Expiration of points in collections with a time-to-live
+    //
+
+    runtime_handle.spawn(expiration::run(
+        toc_arc.clone(),
+        expiration::EXPIRATION_CHECK_INTERVAL,
+    ));
+
     // Setup subscribers to listen for issue-able events
     issues_setup::setup_subscribers(&settings);
 
@@ -63,6 +63,8 @@ pub async fn handle_existing_collections(
                 optimizers_config: Some(collection_state.config.optimizer_config.into()),
                 init_from: None,
                 quantization_config: collection_state.config.quantization_config,
+                ttl_sec: collection_state.config.params.ttl_sec,
+                strict_mode_config: collection_state.config.strict_mode_config,
             },
         );
 
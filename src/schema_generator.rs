@@ -9,14 +9,17 @@ use collection::operations::snapshot_ops::{
 };
 use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionExistence, CollectionInfo,
-    CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
-    GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
-    RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
-    SearchRequestBatch, UpdateResult,
+    CollectionRecallEstimation, CollectionsAliasesResponse, CountRequest, CountResult,
+    DiscoverRequest, DiscoverRequestBatch, FacetRequest, GroupsResult, PointGroup, PointRequest,
+    RecallEstimationRequest, RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch,
+    ScrollRequest, ScrollResult, SearchGroupsRequest, SearchMatrixOffsetsResponse,
+    SearchMatrixPairsResponse, SearchMatrixRequest, SearchRequest, SearchRequestBatch,
+    UpdateResult,
 };
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::gen::SchemaSettings;
 use schemars::JsonSchema;
+use segment::data_types::facets::FacetResponse;
 use serde::Serialize;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CreateCollection, UpdateCollection,
@@ -82,6 +85,13 @@ struct AllDefinitions {
     bc: VersionInfo,
     bd: CollectionExistence,
     be: QueryRequest,
+    bf: FacetRequest,
+    bg: FacetResponse,
+    bh: SearchMatrixRequest,
+    bi: SearchMatrixOffsetsResponse,
+    bj: SearchMatrixPairsResponse,
+    bk: RecallEstimationRequest,
+    bl: CollectionRecallEstimation,
 }
 
 fn save_schema<T: JsonSchema>() {
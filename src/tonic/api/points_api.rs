@@ -1,3 +1,4 @@
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -5,19 +6,23 @@ use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
     ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
     DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
-    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, GetPoints,
-    GetResponse, PointsOperationResponse, RecommendBatchPoints, RecommendBatchResponse,
-    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse,
-    SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints,
-    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FacetCounts,
+    FacetResponse, GetPoints, GetResponse, PointsOperationResponse, RecommendBatchPoints,
+    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
+    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse,
+    SearchGroupsResponse, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse,
+    SearchMatrixPoints, SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints,
+    StreamUpdatesRequest, UpdateBatchPoints, UpdateBatchResponse, UpdateEvent, UpdatePointVectors,
+    UpsertPoints,
 };
 use collection::operations::types::CoreSearchRequest;
+use futures::Stream;
 use storage::dispatcher::Dispatcher;
 use tonic::{Request, Response, Status};
 
 use super::points_common::{
-    delete_vectors, discover, discover_batch, recommend_groups, search_groups, update_batch,
+    delete_vectors, discover, discover_batch, facet, recommend_groups, search_groups,
+    search_points_matrix_offsets, search_points_matrix_pairs, stream_updates, update_batch,
     update_vectors,
 };
 use super::validate;
@@ -444,4 +449,53 @@ impl Points for PointsService {
         )
         .await
     }
+
+    async fn facet(
+        &self,
+        mut request: Request<FacetCounts>,
+    ) -> Result<Response<FacetResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        facet(self.dispatcher.toc(&access), request.into_inner(), access).await
+    }
+
+    async fn search_matrix_pairs(
+        &self,
+        mut request: Request<SearchMatrixPoints>,
+    ) -> Result<Response<SearchMatrixPairsResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        search_points_matrix_pairs(self.dispatcher.toc(&access), request.into_inner(), access).await
+    }
+
+    async fn search_matrix_offsets(
+        &self,
+        mut request: Request<SearchMatrixPoints>,
+    ) -> Result<Response<SearchMatrixOffsetsResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        search_points_matrix_offsets(self.dispatcher.toc(&access), request.into_inner(), access)
+            .await
+    }
+
+    type StreamUpdatesStream = Pin<Box<dyn Stream<Item = Result<UpdateEvent, Status>> + Send>>;
+
+    async fn stream_updates(
+        &self,
+        mut request: Request<StreamUpdatesRequest>,
+    ) -> Result<Response<Self::StreamUpdatesStream>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        let toc = self.dispatcher.toc(&access).clone();
+        let updates = stream_updates(toc, request.into_inner(), access);
+        Ok(Response::new(Box::pin(updates)))
+    }
 }
@@ -2,23 +2,25 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use api::grpc::conversions::{json_path_from_proto, proto_to_payloads};
-use api::grpc::qdrant::payload_index_params::IndexParams;
 use api::grpc::qdrant::points_update_operation::{ClearPayload, Operation, PointStructList};
 use api::grpc::qdrant::{
     points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
     CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
     DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
-    FieldType, GetPoints, GetResponse, PayloadIndexParams, PointsOperationResponseInternal,
-    PointsSelector, ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse,
-    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse, SearchPointGroups,
-    SearchPoints, SearchResponse, SetPayloadPoints, SyncPoints, UpdateBatchPoints,
-    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    FacetCounts, FacetResponse, FieldType, GetPoints, GetResponse, PayloadIndexParams,
+    PointsOperationResponseInternal, PointsSelector, ReadConsistency as ReadConsistencyGrpc,
+    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
+    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse,
+    SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups,
+    SearchPoints, SearchResponse, SetPayloadPoints, StreamUpdatesRequest, SyncPoints,
+    UpdateBatchPoints, UpdateBatchResponse, UpdateEvent, UpdatePointVectors, UpsertPoints,
 };
 use api::rest::{OrderByInterface, ShardKeySelector};
+use collection::collection::distance_matrix::CollectionSearchMatrixResponse;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::{
-    try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
+    try_discover_request_from_grpc, try_points_selector_from_grpc, update_event_from_wal_operation,
+    write_ordering_from_proto,
 };
 use collection::operations::payload_ops::DeletePayload;
 use collection::operations::point_ops::{
@@ -27,15 +29,20 @@ use collection::operations::point_ops::{
 use collection::operations::query_enum::QueryEnum;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    default_exact_count, CoreSearchRequest, CoreSearchRequestBatch, PointRequestInternal,
-    RecommendExample, Record, ScrollRequestInternal,
+    default_exact_count, CollectionSearchMatrixRequest, CoreSearchRequest, CoreSearchRequestBatch,
+    PointRequestInternal, RecommendExample, Record, ScrollRequestInternal,
+    SearchMatrixOffsetsResponse as SearchMatrixOffsetsRest,
+    SearchMatrixPairsResponse as SearchMatrixPairsRest, DEFAULT_FACET_LIMIT,
+    DEFAULT_SEARCH_MATRIX_LIMIT, DEFAULT_SEARCH_MATRIX_SAMPLE,
 };
 use collection::operations::vector_ops::{DeleteVectors, PointVectors, UpdateVectors};
 use collection::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
 use collection::shards::shard::ShardId;
+use futures::{stream, Stream, TryStreamExt as _};
 use itertools::Itertools;
+use segment::data_types::facets::FacetParams;
 use segment::data_types::order_by::OrderBy;
-use segment::data_types::vectors::VectorStruct;
+use segment::data_types::vectors::{VectorStruct, DEFAULT_VECTOR_NAME};
 use segment::types::{
     ExtendedPointId, Filter, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
 };
@@ -52,6 +59,12 @@ use crate::common::points::{
     do_search_batch_points, do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
 };
 
+/// Max number of operations to read from the WAL at once, when streaming updates
+const STREAM_UPDATES_BATCH_SIZE: usize = 64;
+
+/// Interval to check the WAL for new operations, once all written operations are streamed
+const STREAM_UPDATES_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 fn extract_points_selector(
     points_selector: Option<PointsSelector>,
 ) -> Result<(Option<Vec<ExtendedPointId>>, Option<Filter>), Status> {
@@ -108,6 +121,7 @@ pub async fn upsert(
         points,
         ordering,
         shard_key_selector,
+        update_filter,
     } = upsert_points;
     let points = points
         .into_iter()
@@ -116,6 +130,7 @@ pub async fn upsert(
     let operation = PointInsertOperations::PointsList(PointsList {
         points,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
+        update_filter: update_filter.map(|f| f.try_into()).transpose()?,
     });
     let timing = Instant::now();
     let result = do_upsert_points(
@@ -526,6 +541,7 @@ pub async fn update_batch(
             points_update_operation::Operation::Upsert(PointStructList {
                 points,
                 shard_key_selector,
+                update_filter,
             }) => {
                 upsert(
                     toc.clone(),
@@ -535,6 +551,7 @@ pub async fn update_batch(
                         wait,
                         ordering,
                         shard_key_selector,
+                        update_filter,
                     },
                     clock_tag,
                     shard_selection,
@@ -742,6 +759,19 @@ pub async fn update_batch(
     }))
 }
 
+fn convert_payload_schema_type(field_type: FieldType) -> PayloadSchemaType {
+    match field_type {
+        FieldType::Keyword => PayloadSchemaType::Keyword,
+        FieldType::Integer => PayloadSchemaType::Integer,
+        FieldType::Float => PayloadSchemaType::Float,
+        FieldType::Geo => PayloadSchemaType::Geo,
+        FieldType::Text => PayloadSchemaType::Text,
+        FieldType::Bool => PayloadSchemaType::Bool,
+        FieldType::Datetime => PayloadSchemaType::Datetime,
+        FieldType::Uuid => PayloadSchemaType::Uuid,
+    }
+}
+
 fn convert_field_type(
     field_type: Option<i32>,
     field_index_params: Option<PayloadIndexParams>,
@@ -751,44 +781,25 @@ fn convert_field_type(
         .ok_or_else(|| Status::invalid_argument("cannot convert field_type"))?;
 
     let field_schema = match (field_type_parsed, field_index_params) {
-        // Parameterized text type
-        (
-            Some(FieldType::Text),
-            Some(PayloadIndexParams {
-                index_params: Some(IndexParams::TextIndexParams(text_index_params)),
-            }),
-        ) => Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(
-            text_index_params.try_into()?,
-        ))),
-        // Parameterized integer type
-        (
-            Some(FieldType::Integer),
-            Some(PayloadIndexParams {
-                index_params: Some(IndexParams::IntegerIndexParams(integer_params)),
-            }),
-        ) => Some(PayloadFieldSchema::FieldParams(
-            PayloadSchemaParams::Integer(integer_params.try_into()?),
-        )),
-        // Regular field types
-        (Some(v), None | Some(PayloadIndexParams { index_params: None })) => match v {
-            FieldType::Keyword => Some(PayloadSchemaType::Keyword.into()),
-            FieldType::Integer => Some(PayloadSchemaType::Integer.into()),
-            FieldType::Float => Some(PayloadSchemaType::Float.into()),
-            FieldType::Geo => Some(PayloadSchemaType::Geo.into()),
-            FieldType::Text => Some(PayloadSchemaType::Text.into()),
-            FieldType::Bool => Some(PayloadSchemaType::Bool.into()),
-            FieldType::Datetime => Some(PayloadSchemaType::Datetime.into()),
-        },
-        // Parameterized index with mismatching types
+        // Parameterized field types
         (
             Some(v),
             Some(PayloadIndexParams {
-                index_params: Some(_),
+                index_params: Some(index_params),
             }),
         ) => {
-            return Err(Status::invalid_argument(format!(
-                "field_type ({v:?}) and field_index_params do not match"
-            )))
+            let params = PayloadSchemaParams::try_from(index_params)?;
+            // Parameterized index with mismatching types
+            if params.kind() != convert_payload_schema_type(v) {
+                return Err(Status::invalid_argument(format!(
+                    "field_type ({v:?}) and field_index_params do not match"
+                )));
+            }
+            Some(PayloadFieldSchema::FieldParams(params))
+        }
+        // Regular field types
+        (Some(v), None | Some(PayloadIndexParams { index_params: None })) => {
+            Some(convert_payload_schema_type(v).into())
         }
         (None, Some(_)) => return Err(Status::invalid_argument("field type is missing")),
         (None, None) => None,
@@ -1506,6 +1517,127 @@ pub async fn count(
     Ok(Response::new(response))
 }
 
+pub async fn facet(
+    toc: &TableOfContent,
+    facet_counts: FacetCounts,
+    access: Access,
+) -> Result<Response<FacetResponse>, Status> {
+    let FacetCounts {
+        collection_name,
+        key,
+        filter,
+        limit,
+        timeout,
+        read_consistency,
+        shard_key_selector,
+    } = facet_counts;
+
+    let facet_params = FacetParams {
+        key: json_path_from_proto(&key)?,
+        limit: limit.map_or(DEFAULT_FACET_LIMIT, |limit| limit as usize),
+        filter: filter.map(TryInto::try_into).transpose()?,
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
+
+    let timeout = timeout.map(Duration::from_secs);
+
+    let timing = Instant::now();
+    let facet_response = toc
+        .facet(
+            &collection_name,
+            facet_params,
+            shard_selector,
+            read_consistency,
+            access,
+            timeout,
+        )
+        .await
+        .map_err(error_to_status)?;
+
+    let response = FacetResponse {
+        hits: facet_response.hits.into_iter().map(From::from).collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+async fn search_points_matrix(
+    toc: &TableOfContent,
+    request: SearchMatrixPoints,
+    access: Access,
+) -> Result<CollectionSearchMatrixResponse, Status> {
+    let SearchMatrixPoints {
+        collection_name,
+        filter,
+        sample,
+        limit,
+        using,
+        timeout,
+        read_consistency,
+        shard_key_selector,
+    } = request;
+
+    let search_matrix_request = CollectionSearchMatrixRequest {
+        sample_size: sample.map_or(DEFAULT_SEARCH_MATRIX_SAMPLE, |sample| sample as usize),
+        limit_per_sample: limit.map_or(DEFAULT_SEARCH_MATRIX_LIMIT, |limit| limit as usize),
+        filter: filter.map(TryInto::try_into).transpose()?,
+        using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string()),
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
+
+    let timeout = timeout.map(Duration::from_secs);
+
+    toc.search_points_matrix(
+        &collection_name,
+        search_matrix_request,
+        shard_selector,
+        read_consistency,
+        access,
+        timeout,
+    )
+    .await
+    .map_err(error_to_status)
+}
+
+pub async fn search_points_matrix_pairs(
+    toc: &TableOfContent,
+    request: SearchMatrixPoints,
+    access: Access,
+) -> Result<Response<SearchMatrixPairsResponse>, Status> {
+    let timing = Instant::now();
+    let search_matrix_response = search_points_matrix(toc, request, access).await?;
+
+    let response = SearchMatrixPairsResponse {
+        result: Some(SearchMatrixPairsRest::from(search_matrix_response).into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn search_points_matrix_offsets(
+    toc: &TableOfContent,
+    request: SearchMatrixPoints,
+    access: Access,
+) -> Result<Response<SearchMatrixOffsetsResponse>, Status> {
+    let timing = Instant::now();
+    let search_matrix_response = search_points_matrix(toc, request, access).await?;
+
+    let response = SearchMatrixOffsetsResponse {
+        result: Some(SearchMatrixOffsetsRest::from(search_matrix_response).into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
 pub async fn get(
     toc: &TableOfContent,
     get_points: GetPoints,
@@ -1556,3 +1688,61 @@ pub async fn get(
 
     Ok(Response::new(response))
 }
+
+/// Stream operations from the WAL of a local shard, waiting for new ones once all are streamed
+///
+/// The stream ends with an error if the collection or the shard is removed, or if the next
+/// operation to stream has been truncated from the WAL already.
+pub fn stream_updates(
+    toc: Arc<TableOfContent>,
+    stream_updates_request: StreamUpdatesRequest,
+    access: Access,
+) -> impl Stream<Item = Result<UpdateEvent, Status>> + Send + 'static {
+    let StreamUpdatesRequest {
+        collection_name,
+        shard_id,
+        start_from,
+    } = stream_updates_request;
+
+    stream::try_unfold(start_from, move |start_from| {
+        let toc = toc.clone();
+        let collection_name = collection_name.clone();
+        let access = access.clone();
+
+        async move {
+            loop {
+                let operations = toc
+                    .read_wal_operations(
+                        &collection_name,
+                        shard_id,
+                        start_from,
+                        STREAM_UPDATES_BATCH_SIZE,
+                        &access,
+                    )
+                    .await
+                    .map_err(error_to_status)?;
+
+                let Some(&(last_operation_id, _)) = operations.last() else {
+                    tokio::time::sleep(STREAM_UPDATES_POLL_INTERVAL).await;
+                    continue;
+                };
+
+                let events = operations
+                    .into_iter()
+                    .map(|(operation_id, operation)| {
+                        update_event_from_wal_operation(
+                            collection_name.clone(),
+                            operation_id,
+                            operation,
+                        )
+                        .map_err(|err| error_to_status(err.into()))
+                    })
+                    .collect::<Result<Vec<_>, Status>>()?;
+
+                let events = stream::iter(events.into_iter().map(Ok));
+                return Ok(Some((events, Some(last_operation_id + 1))));
+            }
+        }
+    })
+    .try_flatten()
+}
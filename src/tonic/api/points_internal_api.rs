@@ -1,19 +1,22 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use api::grpc::conversions::json_path_from_proto;
 use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
     ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, GetPointsInternal,
-    GetResponse, IntermediateResult, PointsOperationResponseInternal, QueryPointsInternal,
-    QueryResponse, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
-    ScrollPointsInternal, ScrollResponse, SearchBatchResponse, SetPayloadPointsInternal,
-    SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
+    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, FacetCountsInternal,
+    FacetResponse, GetPointsInternal, GetResponse, IntermediateResult,
+    PointsOperationResponseInternal, QueryPointsInternal, QueryResponse, QueryShardPoints,
+    RecommendPointsInternal, RecommendResponse, ScrollPointsInternal, ScrollResponse,
+    SearchBatchResponse, SetPayloadPointsInternal, SyncPointsInternal, UpdateVectorsInternal,
+    UpsertPointsInternal,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::ShardQueryRequest;
 use collection::shards::shard::ShardId;
+use segment::data_types::facets::FacetParams;
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use storage::rbac::Access;
@@ -494,4 +497,49 @@ impl PointsInternal for PointsInternalService {
 
         query(self.toc.as_ref(), collection_name, query_points, shard_id).await
     }
+
+    async fn facet(
+        &self,
+        request: Request<FacetCountsInternal>,
+    ) -> Result<Response<FacetResponse>, Status> {
+        validate_and_log(request.get_ref());
+
+        let FacetCountsInternal {
+            collection_name,
+            key,
+            filter,
+            shard_id,
+            timeout,
+        } = request.into_inner();
+
+        // Counts of all values are requested, so that they can be merged across shards
+        let facet_params = FacetParams {
+            key: json_path_from_proto(&key)?,
+            limit: usize::MAX,
+            filter: filter.map(TryInto::try_into).transpose()?,
+        };
+
+        let timeout = timeout.map(Duration::from_secs);
+
+        let timing = Instant::now();
+        let facet_response = self
+            .toc
+            .facet(
+                &collection_name,
+                facet_params,
+                ShardSelectorInternal::ShardId(shard_id),
+                None,
+                FULL_ACCESS.clone(),
+                timeout,
+            )
+            .await
+            .map_err(error_to_status)?;
+
+        let response = FacetResponse {
+            hits: facet_response.hits.into_iter().map(From::from).collect(),
+            time: timing.elapsed().as_secs_f64(),
+        };
+
+        Ok(Response::new(response))
+    }
 }